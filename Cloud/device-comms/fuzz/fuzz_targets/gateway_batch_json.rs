@@ -0,0 +1,28 @@
+// Gateway Batch JSON Fuzz Target
+// 
+// Feeds arbitrary bytes through the gateway batch ingestion path: the
+// GatewayBatch deserializer, validation of every reading and the synthetic
+// heartbeat records created for idle devices.
+
+#![no_main]
+
+use device_comms::domain::gateway::GatewayBatch;
+use device_comms::domain::telemetry::Telemetry;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(batch) = serde_json::from_slice::<GatewayBatch>(data) else {
+        return;
+    };
+
+    for reading in &batch.readings {
+        let _ = Telemetry::parse(
+            reading.device_id.clone(),
+            reading.telemetry_data.clone(),
+            reading.timestamp,
+        );
+    }
+    for heartbeat in batch.pending_heartbeats() {
+        let _ = heartbeat.to_telemetry(&batch.gateway_id);
+    }
+});
@@ -3,7 +3,8 @@
 // This module defines the shared application state that is injected into
 // all request handlers via Rocket's state management system.
 
-use crate::services::CosmosDbTelemetryStore;
+use crate::domain::canary::CanaryPolicy;
+use crate::services::{CosmosDbTelemetryStore, LastSeenTracker};
 
 /// Application state containing shared resources and dependencies
 /// 
@@ -19,17 +20,44 @@ pub struct AppState {
     /// This client is used by telemetry ingestion handlers to store
     /// incoming device data in the Cosmos DB database.
     pub cosmos_client: CosmosDbTelemetryStore,
+
+    /// Devices whose payloads are shadow-validated by the experimental pipeline
+    /// 
+    /// Loaded from the `CANARY_DEVICES` environment variable by default.
+    pub canary_policy: CanaryPolicy,
+
+    /// When each device was last heard from, directly or via a gateway
+    pub last_seen: LastSeenTracker,
 }
 
 impl AppState {
     /// Creates a new application state instance
     /// 
+    /// The canary policy is loaded from the environment; use
+    /// `with_canary_policy` to override it.
+    /// 
     /// # Arguments
     /// * `cosmos_client` - The configured Cosmos DB telemetry store client
     /// 
     /// # Returns
     /// * `Self` - A new AppState instance with the provided dependencies
     pub fn new(cosmos_client: CosmosDbTelemetryStore) -> Self {
-        Self { cosmos_client }
+        Self {
+            cosmos_client,
+            canary_policy: CanaryPolicy::from_env(),
+            last_seen: LastSeenTracker::new(),
+        }
+    }
+
+    /// Replaces the canary policy of this application state
+    /// 
+    /// # Arguments
+    /// * `canary_policy` - The devices to shadow-validate
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given canary policy
+    pub fn with_canary_policy(mut self, canary_policy: CanaryPolicy) -> Self {
+        self.canary_policy = canary_policy;
+        self
     }
 }
\ No newline at end of file
@@ -0,0 +1,274 @@
+// Canary Validation Domain Logic
+//
+// This module implements the canary validation mode used to de-risk payload
+// format migrations. Payloads from devices flagged as canaries are run
+// through both the current and the experimental validation pipeline and
+// the results are compared. Only the current pipeline's result is ever
+// stored; the experimental result is used for divergence reporting only.
+
+use std::collections::HashSet;
+use chrono::Utc;
+
+use crate::domain::telemetry::{Telemetry, TelemetryError};
+
+/// Maximum clock skew accepted by the experimental pipeline for timestamps
+/// reported in the future, in seconds
+const MAX_FUTURE_SKEW_SECS: i64 = 300;
+
+/// Set of devices whose payloads are shadow-validated by the experimental pipeline
+#[derive(Debug, Clone, Default)]
+pub struct CanaryPolicy {
+    devices: HashSet<String>,
+}
+
+impl CanaryPolicy {
+    /// Creates a canary policy for the given device IDs
+    ///
+    /// # Arguments
+    /// * `devices` - Device IDs to flag as canaries
+    ///
+    /// # Returns
+    /// * `Self` - A new CanaryPolicy instance
+    pub fn new<I, S>(devices: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        CanaryPolicy {
+            devices: devices.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Loads the canary policy from the `CANARY_DEVICES` environment variable
+    ///
+    /// The variable holds a comma-separated list of device IDs. When it is
+    /// unset or empty no device is treated as a canary.
+    pub fn from_env() -> Self {
+        let devices = std::env::var("CANARY_DEVICES").unwrap_or_default();
+        Self::new(
+            devices
+                .split(',')
+                .map(str::trim)
+                .filter(|device_id| !device_id.is_empty()),
+        )
+    }
+
+    /// Returns whether the given device is flagged as a canary
+    pub fn is_canary(&self, device_id: &str) -> bool {
+        self.devices.contains(device_id)
+    }
+}
+
+/// A difference between the current and experimental pipeline results
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// One pipeline accepted the payload and the other rejected it
+    Outcome {
+        current: Result<(), String>,
+        experimental: Result<(), String>,
+    },
+    /// Both pipelines accepted the payload but produced different timestamps
+    Timestamp {
+        current: Option<i64>,
+        experimental: Option<i64>,
+    },
+    /// Both pipelines accepted the payload but disagree on a telemetry value
+    Value {
+        key: String,
+        current: Option<String>,
+        experimental: Option<String>,
+    },
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Divergence::Outcome { current, experimental } => write!(
+                f,
+                "validation outcome differs (current: {:?}, experimental: {:?})",
+                current, experimental
+            ),
+            Divergence::Timestamp { current, experimental } => write!(
+                f,
+                "timestamp differs (current: {:?}, experimental: {:?})",
+                current, experimental
+            ),
+            Divergence::Value { key, current, experimental } => write!(
+                f,
+                "value for {} differs (current: {:?}, experimental: {:?})",
+                key, current, experimental
+            ),
+        }
+    }
+}
+
+/// Validates and normalizes telemetry with the experimental pipeline
+///
+/// Compared with `Telemetry::parse`, the experimental pipeline:
+/// - Trims surrounding whitespace from keys and values
+/// - Only accepts keys made of ASCII letters, digits and underscores
+/// - Rejects numeric-looking values that are not finite numbers
+/// - Rejects timestamps more than five minutes in the future
+///
+/// # Arguments
+/// * `input` - The telemetry payload as received
+///
+/// # Returns
+/// * `Result<Telemetry, TelemetryError>` - The document the experimental pipeline would store
+pub fn parse_experimental(input: &Telemetry) -> Result<Telemetry, TelemetryError> {
+    let device_id = input.device_id.trim();
+    if device_id.is_empty() {
+        return Err(TelemetryError::InvalidDeviceId);
+    }
+
+    let now = Utc::now().timestamp();
+    let timestamp = input.timestamp.unwrap_or(now);
+    if timestamp < 0 || timestamp > now + MAX_FUTURE_SKEW_SECS {
+        return Err(TelemetryError::InvalidTimestamp);
+    }
+
+    if input.telemetry_data.is_empty() {
+        return Err(TelemetryError::EmptyTelemetryData);
+    }
+
+    let mut telemetry_data = std::collections::HashMap::new();
+    for (key, value) in &input.telemetry_data {
+        let key = key.trim();
+        let value = value.trim();
+
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(TelemetryError::InvalidTelemetryValue(format!("Invalid key: {:?}", key)));
+        }
+        if value.is_empty() {
+            return Err(TelemetryError::InvalidTelemetryValue(format!("Empty value for key: {}", key)));
+        }
+        if let Ok(number) = value.parse::<f64>() {
+            if !number.is_finite() {
+                return Err(TelemetryError::InvalidTelemetryValue(format!("Non-finite value for key: {}", key)));
+            }
+        }
+
+        telemetry_data.insert(key.to_string(), value.to_string());
+    }
+
+    Ok(Telemetry::new(device_id.to_string(), telemetry_data, timestamp))
+}
+
+/// Compares the results of the current and experimental pipelines
+///
+/// Timestamps are only compared when the payload carried one, since both
+/// pipelines otherwise stamp it with their own notion of "now".
+///
+/// # Arguments
+/// * `input` - The telemetry payload as received
+/// * `current` - Result of the current pipeline
+/// * `experimental` - Result of the experimental pipeline
+///
+/// # Returns
+/// * `Vec<Divergence>` - Every difference found, empty if the pipelines agree
+pub fn compare(
+    input: &Telemetry,
+    current: &Result<Telemetry, TelemetryError>,
+    experimental: &Result<Telemetry, TelemetryError>,
+) -> Vec<Divergence> {
+    let (current, experimental) = match (current, experimental) {
+        (Ok(current), Ok(experimental)) => (current, experimental),
+        (Err(_), Err(_)) => return Vec::new(),
+        (current, experimental) => {
+            return vec![Divergence::Outcome {
+                current: current.as_ref().map(|_| ()).map_err(|e| e.to_string()),
+                experimental: experimental.as_ref().map(|_| ()).map_err(|e| e.to_string()),
+            }]
+        }
+    };
+
+    let mut divergences = Vec::new();
+
+    if input.timestamp.is_some() && current.timestamp != experimental.timestamp {
+        divergences.push(Divergence::Timestamp {
+            current: current.timestamp,
+            experimental: experimental.timestamp,
+        });
+    }
+
+    let mut keys: Vec<&String> = current
+        .telemetry_data
+        .keys()
+        .chain(experimental.telemetry_data.keys())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    keys.sort();
+
+    for key in keys {
+        let current_value = current.telemetry_data.get(key);
+        let experimental_value = experimental.telemetry_data.get(key);
+        if current_value != experimental_value {
+            divergences.push(Divergence::Value {
+                key: key.clone(),
+                current: current_value.cloned(),
+                experimental: experimental_value.cloned(),
+            });
+        }
+    }
+
+    divergences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn payload(data: &[(&str, &str)]) -> Telemetry {
+        let data: HashMap<String, String> = data
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Telemetry::new("device-1".to_string(), data, 1_700_000_000)
+    }
+
+    #[test]
+    fn test_canary_policy_membership() {
+        let policy = CanaryPolicy::new(["device-1", "device-2"]);
+
+        assert!(policy.is_canary("device-1"));
+        assert!(!policy.is_canary("device-3"));
+    }
+
+    #[test]
+    fn test_pipelines_agree_on_clean_payload() {
+        let input = payload(&[("temperature", "23.5"), ("status", "online")]);
+        let current = Telemetry::parse(input.device_id.clone(), input.telemetry_data.clone(), input.timestamp);
+        let experimental = parse_experimental(&input);
+
+        assert!(compare(&input, &current, &experimental).is_empty());
+    }
+
+    #[test]
+    fn test_value_divergence_on_untrimmed_value() {
+        let input = payload(&[("temperature", " 23.5 ")]);
+        let current = Telemetry::parse(input.device_id.clone(), input.telemetry_data.clone(), input.timestamp);
+        let experimental = parse_experimental(&input);
+
+        let divergences = compare(&input, &current, &experimental);
+        assert_eq!(divergences, vec![Divergence::Value {
+            key: "temperature".to_string(),
+            current: Some(" 23.5 ".to_string()),
+            experimental: Some("23.5".to_string()),
+        }]);
+    }
+
+    #[test]
+    fn test_outcome_divergence_on_non_finite_value() {
+        let input = payload(&[("temperature", "NaN")]);
+        let current = Telemetry::parse(input.device_id.clone(), input.telemetry_data.clone(), input.timestamp);
+        let experimental = parse_experimental(&input);
+
+        let divergences = compare(&input, &current, &experimental);
+        assert!(matches!(
+            divergences.as_slice(),
+            [Divergence::Outcome { current: Ok(()), experimental: Err(_) }]
+        ));
+    }
+}
@@ -21,6 +21,8 @@ pub enum ApiError {
     EmptyTelemetryData,
     /// Individual telemetry value is invalid or empty
     InvalidTelemetryValue(String),
+    /// Gateway ID of a batch upload is empty
+    InvalidGatewayId,
 
     // Database errors
     /// Generic database operation error with details
@@ -38,6 +40,7 @@ impl fmt::Display for ApiError {
             ApiError::InvalidTimestamp => write!(f, "Invalid timestamp format"),
             ApiError::EmptyTelemetryData => write!(f, "Telemetry data cannot be empty"),
             ApiError::InvalidTelemetryValue(msg) => write!(f, "Invalid telemetry value: {}", msg),
+            ApiError::InvalidGatewayId => write!(f, "Gateway ID cannot be empty"),
             ApiError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             ApiError::DeviceNotFound(device_id) => write!(f, "No telemetry found for device {}", device_id),
         }
@@ -60,7 +63,8 @@ impl From<ApiError> for rocket::http::Status {
             ApiError::InvalidDeviceId | 
             ApiError::InvalidTimestamp | 
             ApiError::EmptyTelemetryData | 
-            ApiError::InvalidTelemetryValue(_) => Status::BadRequest,
+            ApiError::InvalidTelemetryValue(_) |
+            ApiError::InvalidGatewayId => Status::BadRequest,
             
             // Not found errors (4xx) - resource doesn't exist
             ApiError::DeviceNotFound(_) => Status::NotFound,
@@ -0,0 +1,152 @@
+// Gateway Batch Domain Model
+// 
+// This module defines the batch payload uploaded by gateways that proxy
+// several devices. Besides telemetry readings, a batch may carry per-device
+// heartbeat markers for devices that are alive but had nothing to report,
+// so offline detection works per logical device rather than only per
+// gateway connection.
+
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use chrono::Utc;
+
+use crate::domain::telemetry::{deserialize_timestamp, Telemetry, TelemetryError};
+
+/// Telemetry key marking a synthetic heartbeat record
+pub const HEARTBEAT_KEY: &str = "heartbeat";
+
+/// A marker that a device behind a gateway was alive at a point in time
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Heartbeat {
+    /// The device that was seen by the gateway
+    pub device_id: String,
+
+    /// When the gateway last heard from the device (uses current time if not provided)
+    #[serde(deserialize_with = "deserialize_timestamp", default)]
+    pub timestamp: Option<i64>,
+}
+
+impl Heartbeat {
+    /// Converts the marker into a synthetic heartbeat telemetry record
+    /// 
+    /// The record carries a single `heartbeat` value and gets its own
+    /// document id so it never collides with a real reading taken in the
+    /// same second.
+    /// 
+    /// # Arguments
+    /// * `gateway_id` - The gateway that reported the heartbeat
+    /// 
+    /// # Returns
+    /// * `Result<Telemetry, TelemetryError>` - The heartbeat record or a validation error
+    pub fn to_telemetry(&self, gateway_id: &str) -> Result<Telemetry, TelemetryError> {
+        let data = HashMap::from([(HEARTBEAT_KEY.to_string(), "1".to_string())]);
+        let timestamp = self.timestamp.unwrap_or_else(|| Utc::now().timestamp());
+
+        let mut telemetry = Telemetry::parse(self.device_id.clone(), data, Some(timestamp))?
+            .with_gateway_id(Some(gateway_id.to_string()));
+        telemetry.id = Some(format!("{}-{}-{}", self.device_id, timestamp, HEARTBEAT_KEY));
+
+        Ok(telemetry)
+    }
+}
+
+/// A batch upload from a gateway proxying several devices
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GatewayBatch {
+    /// Unique identifier of the uploading gateway
+    pub gateway_id: String,
+
+    /// Telemetry readings from the proxied devices
+    #[serde(default)]
+    pub readings: Vec<Telemetry>,
+
+    /// Devices that were alive during the batch window
+    #[serde(default)]
+    pub heartbeats: Vec<Heartbeat>,
+}
+
+impl GatewayBatch {
+    /// Returns the heartbeat markers that need a synthetic record
+    /// 
+    /// A device with a reading in the same batch is already marked as seen
+    /// by that reading, so its heartbeat is skipped.
+    pub fn pending_heartbeats(&self) -> Vec<&Heartbeat> {
+        let reporting: HashSet<&str> = self.readings
+            .iter()
+            .map(|reading| reading.device_id.as_str())
+            .collect();
+
+        self.heartbeats
+            .iter()
+            .filter(|heartbeat| !reporting.contains(heartbeat.device_id.as_str()))
+            .collect()
+    }
+}
+
+/// Outcome of a single batch item that could not be stored
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RejectedItem {
+    /// The device the item belonged to
+    pub device_id: String,
+    /// Why the item was rejected
+    pub error: String,
+}
+
+/// Summary of a processed gateway batch
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BatchResult {
+    /// The uploading gateway
+    pub gateway_id: String,
+    /// Number of readings stored
+    pub accepted: usize,
+    /// Number of synthetic heartbeat records stored
+    pub heartbeats: usize,
+    /// Items that failed validation
+    pub rejected: Vec<RejectedItem>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heartbeat_to_telemetry() {
+        let heartbeat = Heartbeat { device_id: "sensor-001".to_string(), timestamp: Some(1_700_000_000) };
+
+        let telemetry = heartbeat.to_telemetry("gw-1").unwrap();
+
+        assert_eq!(telemetry.id.as_deref(), Some("sensor-001-1700000000-heartbeat"));
+        assert_eq!(telemetry.telemetry_data.get(HEARTBEAT_KEY).map(String::as_str), Some("1"));
+        assert_eq!(telemetry.gateway_id.as_deref(), Some("gw-1"));
+        assert_eq!(telemetry.timestamp, Some(1_700_000_000));
+        assert!(telemetry.is_heartbeat());
+    }
+
+    #[test]
+    fn test_heartbeat_rejects_empty_device_id() {
+        let heartbeat = Heartbeat { device_id: " ".to_string(), timestamp: None };
+
+        assert!(matches!(heartbeat.to_telemetry("gw-1"), Err(TelemetryError::InvalidDeviceId)));
+    }
+
+    #[test]
+    fn test_pending_heartbeats_skip_reporting_devices() {
+        let batch: GatewayBatch = serde_json::from_value(serde_json::json!({
+            "gateway_id": "gw-1",
+            "readings": [
+                { "device_id": "sensor-001", "telemetry_data": { "temperature": "21.0" }, "timestamp": 1700000000 }
+            ],
+            "heartbeats": [
+                { "device_id": "sensor-001", "timestamp": 1700000000 },
+                { "device_id": "sensor-002", "timestamp": "2023-11-14T22:13:20Z" }
+            ]
+        }))
+        .unwrap();
+
+        let pending = batch.pending_heartbeats();
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].device_id, "sensor-002");
+        assert_eq!(pending[0].timestamp, Some(1_700_000_000));
+    }
+}
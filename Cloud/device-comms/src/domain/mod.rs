@@ -6,6 +6,8 @@
 
 pub mod telemetry;
 pub mod error;
+pub mod canary;
+pub mod gateway;
 
 // Re-export all telemetry-related types for convenient access
 pub use telemetry::*;
\ No newline at end of file
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize, Deserializer};
 use std::{collections::HashMap};
 use chrono::{DateTime, Utc};
 
+use crate::domain::gateway::HEARTBEAT_KEY;
+
 /// Custom deserializer for timestamp fields that can handle multiple formats
 /// 
 /// This function can deserialize timestamps from:
@@ -20,7 +22,7 @@ use chrono::{DateTime, Utc};
 /// 
 /// # Returns
 /// * `Result<Option<i64>, D::Error>` - The parsed timestamp or None if null
-fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+pub(crate) fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -71,6 +73,16 @@ pub struct Telemetry {
     /// Uses custom deserializer to handle multiple timestamp formats
     #[serde(deserialize_with = "deserialize_timestamp", default)]
     pub timestamp: Option<i64>,
+
+    /// Random identifier of the device boot session that sent this telemetry
+    /// 
+    /// Lets all traffic from a single boot be grouped, e.g. to spot reboot loops
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    /// Gateway that forwarded this telemetry, if it was not sent directly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_id: Option<String>,
     
     // Cosmos DB metadata fields (not part of business logic)
     #[serde(rename = "_rid", skip_serializing_if = "Option::is_none")]
@@ -136,6 +148,8 @@ impl Telemetry {
             etag: None,
             attachments: None,
             timestamp: Some(timestamp),
+            session_id: None,
+            gateway_id: None,
         }
     }
 
@@ -189,6 +203,39 @@ impl Telemetry {
             etag: None,
             attachments: None,
             timestamp: Some(timestamp),
+            session_id: None,
+            gateway_id: None,
         })
     }
-}
+
+    /// Attaches the boot session id reported by the device
+    /// 
+    /// # Arguments
+    /// * `session_id` - The optional session id from the payload
+    /// 
+    /// # Returns
+    /// * `Self` - The telemetry with the session id set
+    pub fn with_session_id(mut self, session_id: Option<String>) -> Self {
+        self.session_id = session_id;
+        self
+    }
+
+    /// Records the gateway that forwarded this telemetry
+    /// 
+    /// # Arguments
+    /// * `gateway_id` - The forwarding gateway's identifier
+    /// 
+    /// # Returns
+    /// * `Self` - The telemetry with the gateway id set
+    pub fn with_gateway_id(mut self, gateway_id: Option<String>) -> Self {
+        self.gateway_id = gateway_id;
+        self
+    }
+
+    /// Returns whether this is a synthetic heartbeat record
+    /// 
+    /// Heartbeat records carry a single `heartbeat` value and no readings.
+    pub fn is_heartbeat(&self) -> bool {
+        self.telemetry_data.len() == 1 && self.telemetry_data.contains_key(HEARTBEAT_KEY)
+    }
+}
\ No newline at end of file
@@ -121,9 +121,11 @@ impl Application {
             .attach(cors)
             // Add request/response tracing for observability
             .attach(TracingFairing)
-            // Mount the telemetry ingestion endpoint
+            // Mount the telemetry ingestion endpoints
             .mount("/iot/data", routes![
                 routes::ingest_telemetry::ingest, 
+                routes::ingest_batch::ingest_batch,
+                routes::last_seen::last_seen,
             ]);
 
         // Log the server startup information
@@ -0,0 +1,158 @@
+// Gateway Batch Ingestion Route Handler
+// 
+// This module handles the POST /iot/data/ingest/batch endpoint for gateways
+// that upload telemetry on behalf of several devices, including synthetic
+// heartbeats for proxied devices that had nothing to report.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, warn, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::gateway::{BatchResult, GatewayBatch, RejectedItem};
+use crate::domain::telemetry::TelemetryError;
+use crate::app_state::AppState;
+use crate::routes::ingest_telemetry::{insert_telemetry, store_telemetry};
+
+/// Records a rejected batch item, or propagates a database failure
+/// 
+/// Validation errors only reject the offending item so one bad reading
+/// does not drop the rest of the batch; database errors fail the request
+/// so the gateway retries the upload.
+fn reject(rejected: &mut Vec<RejectedItem>, device_id: &str, error: ApiError) -> Result<(), ApiError> {
+    if let ApiError::DatabaseError(_) = error {
+        return Err(error);
+    }
+
+    warn!(device_id = %device_id, "Rejected batch item: {}", error);
+    rejected.push(RejectedItem {
+        device_id: device_id.to_string(),
+        error: error.to_string(),
+    });
+    Ok(())
+}
+
+/// Maps a heartbeat validation error to the API error reported for it
+fn heartbeat_error(error: TelemetryError) -> ApiError {
+    match error {
+        TelemetryError::InvalidDeviceId => ApiError::InvalidDeviceId,
+        TelemetryError::InvalidTimestamp => ApiError::InvalidTimestamp,
+        TelemetryError::EmptyTelemetryData => ApiError::EmptyTelemetryData,
+        TelemetryError::InvalidTelemetryValue(msg) => ApiError::InvalidTelemetryValue(msg),
+    }
+}
+
+/// Processes and stores a gateway batch
+/// 
+/// This function performs the following steps:
+/// 1. Validates and stores every reading, tagged with the gateway id
+/// 2. Stores a synthetic heartbeat record for every heartbeat marker
+///    whose device had no reading in the batch
+/// 3. Collects the items that failed validation
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `batch` - The batch uploaded by the gateway
+/// 
+/// # Returns
+/// * `Result<BatchResult, ApiError>` - A summary of the batch or an error
+async fn insert_batch(state: &AppState, batch: GatewayBatch) -> Result<BatchResult, ApiError> {
+    info!("Inserting batch from gateway {:?}", batch.gateway_id);
+
+    if batch.gateway_id.trim().is_empty() {
+        return Err(ApiError::InvalidGatewayId);
+    }
+
+    let mut result = BatchResult {
+        gateway_id: batch.gateway_id.clone(),
+        accepted: 0,
+        heartbeats: 0,
+        rejected: Vec::new(),
+    };
+
+    for reading in &batch.readings {
+        let reading = reading.clone().with_gateway_id(Some(batch.gateway_id.clone()));
+        match insert_telemetry(state, &reading).await {
+            Ok(()) => result.accepted += 1,
+            Err(e) => reject(&mut result.rejected, &reading.device_id, e)?,
+        }
+    }
+
+    for heartbeat in batch.pending_heartbeats() {
+        let stored = match heartbeat.to_telemetry(&batch.gateway_id) {
+            Ok(document) => store_telemetry(state, &document).await,
+            Err(e) => Err(heartbeat_error(e)),
+        };
+        match stored {
+            Ok(()) => result.heartbeats += 1,
+            Err(e) => reject(&mut result.rejected, &heartbeat.device_id, e)?,
+        }
+    }
+
+    info!(
+        "Batch from gateway {} stored: {} reading(s), {} heartbeat(s), {} rejected",
+        result.gateway_id, result.accepted, result.heartbeats, result.rejected.len()
+    );
+    Ok(result)
+}
+
+/// POST endpoint for ingesting a batch upload from a gateway
+/// 
+/// Gateways that proxy several devices upload their readings together with
+/// heartbeat markers for devices that were alive but had nothing to report.
+/// Each marker is stored as a synthetic telemetry record holding a single
+/// `heartbeat` value, so offline detection works per logical device.
+/// Invalid items are reported in the response without failing the batch.
+/// 
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `batch` - JSON payload containing the batch
+/// 
+/// # Returns
+/// * `Result<Json<BatchResult>, Status>` - Batch summary or HTTP error status
+/// 
+/// # Example Request
+/// ```json
+/// {
+///   "gateway_id": "gateway-01",
+///   "readings": [
+///     {
+///       "device_id": "sensor-001",
+///       "telemetry_data": { "temperature": "23.5" },
+///       "timestamp": 1640995200
+///     }
+///   ],
+///   "heartbeats": [
+///     { "device_id": "sensor-002", "timestamp": 1640995190 }
+///   ]
+/// }
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "gateway_id": "gateway-01",
+///   "accepted": 1,
+///   "heartbeats": 1,
+///   "rejected": []
+/// }
+/// ```
+#[post("/ingest/batch", data = "<batch>")]
+pub async fn ingest_batch(
+    state: &State<AppState>,
+    batch: Json<GatewayBatch>
+) -> Result<Json<BatchResult>, Status> {
+    info!("Received batch from gateway: {:?}", batch.gateway_id);
+
+    match insert_batch(state.inner(), batch.into_inner()).await {
+        Ok(result) => {
+            info!("Successfully processed batch");
+            Ok(Json(result))
+        }
+        Err(e) => {
+            error!("Error inserting batch: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
@@ -5,19 +5,46 @@
 
 use rocket::serde::json::Json;
 use rocket::{State, http::Status};
-use tracing::{info, error};
+use tracing::{info, warn, error};
 
-use crate::domain::telemetry::Telemetry;
+use crate::domain::canary;
+use crate::domain::telemetry::{Telemetry, TelemetryError};
 use crate::domain::error::ApiError;
 use crate::app_state::AppState;
 
+/// Shadow-validates a canary device's payload with the experimental pipeline
+/// 
+/// Runs the payload through the experimental validation pipeline and logs
+/// every divergence from the current pipeline's result. Nothing produced by
+/// the experimental pipeline is stored, so this never affects the response
+/// or the persisted data.
+/// 
+/// # Arguments
+/// * `telemetry` - The telemetry payload as received
+/// * `current` - Result of the current validation pipeline
+fn shadow_validate(telemetry: &Telemetry, current: &Result<Telemetry, TelemetryError>) {
+    let experimental = canary::parse_experimental(telemetry);
+    let divergences = canary::compare(telemetry, current, &experimental);
+
+    if divergences.is_empty() {
+        info!(device_id = %telemetry.device_id, "Canary validation matched current pipeline");
+        return;
+    }
+
+    for divergence in &divergences {
+        warn!(device_id = %telemetry.device_id, "Canary validation divergence: {}", divergence);
+    }
+}
+
 /// Processes and stores telemetry data in the database
 /// 
 /// This function validates the incoming telemetry data and stores it
 /// in the Cosmos DB database. It performs the following steps:
-/// 1. Validates the telemetry data using domain validation rules
+/// 1. Validates the telemetry data using domain validation rules,
+///    shadow-validating payloads from canary devices
 /// 2. Converts the validated data to JSON format for storage
-/// 3. Inserts the data into the Cosmos DB container
+/// 3. Inserts the data into the Cosmos DB container and records when
+///    the device was last seen
 /// 
 /// # Arguments
 /// * `state` - Application state containing the database client
@@ -25,24 +52,55 @@ use crate::app_state::AppState;
 /// 
 /// # Returns
 /// * `Result<(), ApiError>` - Success or an appropriate error
-async fn insert_telemetry(state: &AppState, telemetry: Json<Telemetry>) -> Result<(), ApiError> {
+pub(crate) async fn insert_telemetry(state: &AppState, telemetry: &Telemetry) -> Result<(), ApiError> {
     info!("Inserting telemetry: {:?}", telemetry);
 
     // Parse and validate the telemetry data using domain validation rules
-    let document = Telemetry::parse(
+    let parsed = Telemetry::parse(
         telemetry.device_id.clone(),
         telemetry.telemetry_data.clone(),
         telemetry.timestamp
-    ).map_err(|e| match e {
+    );
+
+    // Compare against the experimental pipeline for canary devices
+    if state.canary_policy.is_canary(&telemetry.device_id) {
+        shadow_validate(telemetry, &parsed);
+    }
+
+    let document = parsed.map_err(|e| match e {
         // Map domain validation errors to API errors
         crate::domain::telemetry::TelemetryError::InvalidDeviceId => ApiError::InvalidDeviceId,
         crate::domain::telemetry::TelemetryError::InvalidTimestamp => ApiError::InvalidTimestamp,
         crate::domain::telemetry::TelemetryError::EmptyTelemetryData => ApiError::EmptyTelemetryData,
         crate::domain::telemetry::TelemetryError::InvalidTelemetryValue(msg) => ApiError::InvalidTelemetryValue(msg),
-    })?;
+    })?
+    // Keep the boot session id so traffic can be grouped per device boot
+    .with_session_id(telemetry.session_id.clone())
+    .with_gateway_id(telemetry.gateway_id.clone());
+
+    store_telemetry(state, &document).await?;
+
+    info!("Telemetry inserted successfully");
+    Ok(())
+}
 
+/// Stores a validated telemetry document and records the device as seen
+/// 
+/// If the record is the newest this instance has seen for its device, the
+/// device index is updated as well. Readings, but not heartbeats, are also
+/// written to the latest-record container, which itself keeps whichever
+/// record is newest, so dashboards keep showing the last real values of a
+/// device that only sends heartbeats.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `document` - The validated telemetry to store
+/// 
+/// # Returns
+/// * `Result<(), ApiError>` - Success or a database error
+pub(crate) async fn store_telemetry(state: &AppState, document: &Telemetry) -> Result<(), ApiError> {
     // Convert the validated telemetry to JSON format for database storage
-    let inserted_document = serde_json::to_value(&document)
+    let inserted_document = serde_json::to_value(document)
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     // Insert the telemetry data into the Cosmos DB container
@@ -50,7 +108,18 @@ async fn insert_telemetry(state: &AppState, telemetry: Json<Telemetry>) -> Resul
         .await
         .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
-    info!("Telemetry inserted successfully");
+    // Keep the device index used for device discovery and the latest
+    // record read by dashboards up to date; the telemetry is stored either way
+    if let Some(seen) = state.last_seen.record(document) {
+        if let Err(e) = state.cosmos_client.upsert_device_index(&seen).await {
+            warn!("Error updating device index for {}: {}", seen.device_id, e);
+        }
+    }
+    if !document.is_heartbeat() {
+        if let Err(e) = state.cosmos_client.upsert_latest_telemetry(document).await {
+            warn!("Error updating latest record for {}: {}", document.device_id, e);
+        }
+    }
     Ok(())
 }
 
@@ -61,6 +130,7 @@ async fn insert_telemetry(state: &AppState, telemetry: Json<Telemetry>) -> Resul
 /// - device_id: Unique identifier for the IoT device
 /// - telemetry_data: Key-value pairs of sensor readings
 /// - timestamp: Optional Unix timestamp (uses current time if not provided)
+/// - session_id: Optional boot session identifier reported by the device
 /// 
 /// # Arguments
 /// * `state` - Application state injected by Rocket
@@ -88,7 +158,7 @@ pub async fn ingest(
     info!("Received telemetry: {:?}", telemetry);
     
     // Process the telemetry data and handle any errors
-    match insert_telemetry(state.inner(), telemetry).await {
+    match insert_telemetry(state.inner(), &telemetry).await {
         Ok(()) => {
             info!("Successfully processed telemetry");
             Ok("Telemetry ingested")
@@ -0,0 +1,52 @@
+// Device Last-Seen Route Handler
+// 
+// This module handles the GET /iot/data/last-seen/<device_id> endpoint for
+// checking when a device was last heard from, directly or via a gateway.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::app_state::AppState;
+use crate::services::LastSeen;
+
+/// GET endpoint for retrieving when a device was last seen
+/// 
+/// Readings and gateway heartbeats both count as the device being seen.
+/// Only traffic received by this service instance since it started is
+/// tracked; the stored telemetry (including heartbeat records) remains
+/// the durable source of truth.
+/// 
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `device_id` - The device identifier from the URL path
+/// 
+/// # Returns
+/// * `Result<Json<LastSeen>, Status>` - When the device was last seen, or 404
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-002",
+///   "timestamp": 1640995190,
+///   "gateway_id": "gateway-01",
+///   "heartbeat": true
+/// }
+/// ```
+#[get("/last-seen/<device_id>")]
+pub async fn last_seen(
+    state: &State<AppState>,
+    device_id: String
+) -> Result<Json<LastSeen>, Status> {
+    info!("Received last-seen request for device: {:?}", device_id);
+
+    match state.last_seen.get(&device_id) {
+        Some(seen) => Ok(Json(seen)),
+        None => {
+            let e = ApiError::DeviceNotFound(device_id);
+            error!("Error retrieving last-seen time: {}", e);
+            Err(e.into())
+        }
+    }
+}
@@ -4,4 +4,6 @@
 // communications service API endpoints.
 
 pub mod ingest_telemetry;
+pub mod ingest_batch;
+pub mod last_seen;
 
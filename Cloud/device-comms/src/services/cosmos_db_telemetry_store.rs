@@ -5,12 +5,26 @@
 // communications service.
 
 use super::AzureAuth;
-use azure_data_cosmos::CosmosClient;
+use azure_core::http::{Etag, StatusCode};
+use azure_data_cosmos::{CosmosClient, ItemOptions};
 use azure_data_cosmos::clients::ContainerClient;
 use futures::StreamExt;
+use serde::Deserialize;
 use crate::domain::telemetry::Telemetry;
+use crate::services::LastSeen;
 use std::sync::Arc;
 
+/// The fields of a latest-record document needed to replace it conditionally
+#[derive(Deserialize)]
+struct LatestVersion {
+    /// Unix timestamp of the stored record
+    #[serde(default)]
+    timestamp: Option<i64>,
+    /// Entity tag of the stored document
+    #[serde(rename = "_etag")]
+    etag: String,
+}
+
 /// Cosmos DB client for telemetry data storage and retrieval
 /// 
 /// This struct provides a thread-safe interface to Azure Cosmos DB for
@@ -23,6 +37,21 @@ pub struct CosmosDbTelemetryStore {
     /// This client is used for all database operations and is shared
     /// across multiple request handlers.
     pub container_client: Arc<ContainerClient>,
+
+    /// Container client for the device index
+    /// 
+    /// One document per device, holding when it was last seen, lives in a
+    /// sibling container named `<container_name>-devices`, partitioned by
+    /// device id. The device-monitor service lists devices from it.
+    pub devices_client: Arc<ContainerClient>,
+
+    /// Container client for the latest record of each device
+    /// 
+    /// A copy of each device's newest telemetry record lives in a sibling
+    /// container named `<container_name>-latest`, partitioned by device id,
+    /// so the device-monitor service reads current values with one lookup
+    /// per device instead of scanning the telemetry.
+    pub latest_client: Arc<ContainerClient>,
 }
 
 impl CosmosDbTelemetryStore {
@@ -56,13 +85,18 @@ impl CosmosDbTelemetryStore {
         // Create the Cosmos DB client with authentication
         let cosmos_client = CosmosClient::new(&cosmos_endpoint, azure_credential, None)?;
         
-        // Create a container client for the specified database and container
-        let container_client = cosmos_client
-            .database_client(&database_name)
-            .container_client(&container_name);
+        // Create container clients for the specified database and containers
+        let database_client = cosmos_client.database_client(&database_name);
+        let container_client = database_client.container_client(&container_name);
+        let devices_client =
+            database_client.container_client(&format!("{}-devices", container_name));
+        let latest_client =
+            database_client.container_client(&format!("{}-latest", container_name));
 
         Ok(CosmosDbTelemetryStore {
             container_client: Arc::new(container_client),
+            devices_client: Arc::new(devices_client),
+            latest_client: Arc::new(latest_client),
         })
     }
 
@@ -101,6 +135,92 @@ impl CosmosDbTelemetryStore {
         Ok(())
     }
 
+    /// Records in the device index when a device was last seen
+    /// 
+    /// # Arguments
+    /// * `seen` - When and how the device was last seen
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_device_index(
+        &self,
+        seen: &LastSeen,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // One document per device, so the device id doubles as the document id
+        let mut document = serde_json::to_value(seen)?;
+        document["id"] = serde_json::Value::String(seen.device_id.clone());
+
+        self.devices_client
+            .upsert_item(&seen.device_id, &document, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Records a device's newest telemetry record in the latest-record container
+    /// 
+    /// The stored document is only replaced by a record at least as new, and
+    /// only if it still has the entity tag it was read with, so concurrent
+    /// ingests on any number of instances never roll it back to an older
+    /// record. The write is retried whenever the document changes in between.
+    /// 
+    /// # Arguments
+    /// * `telemetry` - The record just stored for the device
+    /// 
+    /// # Returns
+    /// * `Result<bool, Box<dyn std::error::Error>>` - True if the record was stored, false if
+    ///   a newer record is already there, or an error
+    pub async fn upsert_latest_telemetry(
+        &self,
+        telemetry: &Telemetry,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        // One document per device, so the device id doubles as the document id
+        let mut document = serde_json::to_value(telemetry)?;
+        document["id"] = serde_json::Value::String(telemetry.device_id.clone());
+
+        loop {
+            let result = match self.read_latest_version(&telemetry.device_id).await? {
+                Some(current) if current.timestamp > telemetry.timestamp => return Ok(false),
+                Some(current) => {
+                    let options = ItemOptions {
+                        if_match_etag: Some(Etag::from(current.etag)),
+                        ..Default::default()
+                    };
+                    self.latest_client
+                        .replace_item(&telemetry.device_id, &telemetry.device_id, &document, Some(options))
+                        .await
+                }
+                None => self.latest_client.create_item(&telemetry.device_id, &document, None).await,
+            };
+
+            match result {
+                Ok(_) => return Ok(true),
+                Err(e) if is_precondition_failed(&e) || is_conflict(&e) => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Reads the timestamp and entity tag of a device's latest-record document
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Option<LatestVersion>, Box<dyn std::error::Error>>` - The stored version,
+    ///   None if the device has no document yet, or an error
+    async fn read_latest_version(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<LatestVersion>, Box<dyn std::error::Error>> {
+        // The device id doubles as the document id, so this is a point read
+        match self.latest_client.read_item::<LatestVersion>(device_id, device_id, None).await {
+            Ok(response) => Ok(Some(response.into_body().await?)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Retrieves all telemetry data for a specific device
     /// 
     /// This method queries the Cosmos DB container for all telemetry
@@ -133,3 +253,20 @@ impl CosmosDbTelemetryStore {
         Ok(items)
     }
 }
+
+/// True if a document does not exist (404 Not Found)
+fn is_not_found(error: &azure_core::Error) -> bool {
+    error.http_status() == Some(StatusCode::NotFound)
+}
+
+/// True if a conditional write failed because the document was changed
+/// since it was read (412 Precondition Failed)
+fn is_precondition_failed(error: &azure_core::Error) -> bool {
+    error.http_status() == Some(StatusCode::PreconditionFailed)
+}
+
+/// True if a document could not be created because one with the same id
+/// already exists (409 Conflict)
+fn is_conflict(error: &azure_core::Error) -> bool {
+    error.http_status() == Some(StatusCode::Conflict)
+}
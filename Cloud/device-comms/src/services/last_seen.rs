@@ -0,0 +1,112 @@
+// Device Last-Seen Tracking
+// 
+// This module keeps track of when each logical device was last heard from,
+// either directly or through a gateway, so offline detection works per
+// device even when several devices share one gateway connection.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::telemetry::Telemetry;
+
+/// When and how a device was last heard from
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LastSeen {
+    /// The device identifier
+    pub device_id: String,
+    /// Unix timestamp of the most recent reading or heartbeat
+    pub timestamp: i64,
+    /// Gateway that forwarded the most recent record, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_id: Option<String>,
+    /// Whether the most recent record was a synthetic heartbeat
+    pub heartbeat: bool,
+}
+
+/// In-memory record of the last time each device was seen
+#[derive(Clone, Default)]
+pub struct LastSeenTracker {
+    devices: Arc<RwLock<HashMap<String, LastSeen>>>,
+}
+
+impl LastSeenTracker {
+    /// Creates an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a stored telemetry document
+    /// 
+    /// Records older than the one already tracked for the device are
+    /// ignored, since gateways may upload batches out of order.
+    /// 
+    /// # Arguments
+    /// * `telemetry` - The telemetry that was stored
+    /// 
+    /// # Returns
+    /// * `Option<LastSeen>` - When the device was last seen, if this record changed it
+    pub fn record(&self, telemetry: &Telemetry) -> Option<LastSeen> {
+        let timestamp = telemetry.timestamp?;
+
+        let mut devices = self.devices.write().unwrap_or_else(|e| e.into_inner());
+        if devices.get(&telemetry.device_id).is_some_and(|seen| seen.timestamp > timestamp) {
+            return None;
+        }
+
+        let seen = LastSeen {
+            device_id: telemetry.device_id.clone(),
+            timestamp,
+            gateway_id: telemetry.gateway_id.clone(),
+            heartbeat: telemetry.is_heartbeat(),
+        };
+        devices.insert(telemetry.device_id.clone(), seen.clone());
+        Some(seen)
+    }
+
+    /// Returns when a device was last seen, if it has been seen by this instance
+    pub fn get(&self, device_id: &str) -> Option<LastSeen> {
+        self.devices
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(device_id)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::gateway::HEARTBEAT_KEY;
+
+    fn telemetry(device_id: &str, key: &str, timestamp: i64) -> Telemetry {
+        let data = HashMap::from([(key.to_string(), "1".to_string())]);
+        Telemetry::new(device_id.to_string(), data, timestamp)
+    }
+
+    #[test]
+    fn test_tracks_latest_record() {
+        let tracker = LastSeenTracker::new();
+
+        tracker.record(&telemetry("sensor-001", "temperature", 100));
+        tracker.record(&telemetry("sensor-001", HEARTBEAT_KEY, 200).with_gateway_id(Some("gw-1".to_string())));
+
+        let seen = tracker.get("sensor-001").unwrap();
+        assert_eq!(seen.timestamp, 200);
+        assert_eq!(seen.gateway_id.as_deref(), Some("gw-1"));
+        assert!(seen.heartbeat);
+        assert!(tracker.get("sensor-002").is_none());
+    }
+
+    #[test]
+    fn test_ignores_out_of_order_records() {
+        let tracker = LastSeenTracker::new();
+
+        assert!(tracker.record(&telemetry("sensor-001", "temperature", 200)).is_some());
+        assert!(tracker.record(&telemetry("sensor-001", HEARTBEAT_KEY, 100)).is_none());
+
+        let seen = tracker.get("sensor-001").unwrap();
+        assert_eq!(seen.timestamp, 200);
+        assert!(!seen.heartbeat);
+    }
+}
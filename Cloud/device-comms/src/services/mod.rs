@@ -6,7 +6,9 @@
 
 pub mod cosmos_db_telemetry_store;
 pub mod azure_auth;
+pub mod last_seen;
 
 // Re-export service types for convenient access
 pub use azure_auth::AzureAuth;
-pub use cosmos_db_telemetry_store::CosmosDbTelemetryStore;
\ No newline at end of file
+pub use cosmos_db_telemetry_store::CosmosDbTelemetryStore;
+pub use last_seen::{LastSeen, LastSeenTracker};
\ No newline at end of file
@@ -0,0 +1,132 @@
+// Gateway Batch Ingestion Integration Tests
+// 
+// This module contains integration tests for the gateway batch ingestion
+// endpoint and the per-device last-seen tracking it feeds.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_comms::domain::gateway::BatchResult;
+use device_comms::services::LastSeen;
+
+/// Test that heartbeats mark proxied devices as seen
+/// 
+/// This test verifies that:
+/// - Readings and heartbeats in a batch are stored
+/// - A device with only a heartbeat is reported as seen via the gateway
+#[tokio::test]
+async fn test_ingest_batch_with_heartbeats() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let reporting_device = app.generate_test_device_id();
+    let idle_device = app.generate_test_device_id();
+    let timestamp = chrono::Utc::now().timestamp();
+
+    let response = client
+        .post("/iot/data/ingest/batch")
+        .json(&serde_json::json!({
+            "gateway_id": "test-gateway",
+            "readings": [
+                { "device_id": reporting_device, "telemetry_data": { "temperature": "22.5" }, "timestamp": timestamp }
+            ],
+            "heartbeats": [
+                { "device_id": idle_device, "timestamp": timestamp }
+            ]
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let result: BatchResult = response.into_json().await.expect("Invalid batch result");
+    assert_eq!(result.accepted, 1);
+    assert_eq!(result.heartbeats, 1);
+    assert!(result.rejected.is_empty());
+
+    let response = client
+        .get(format!("/iot/data/last-seen/{}", idle_device))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let seen: LastSeen = response.into_json().await.expect("Invalid last-seen response");
+    assert_eq!(seen.timestamp, timestamp);
+    assert_eq!(seen.gateway_id.as_deref(), Some("test-gateway"));
+    assert!(seen.heartbeat);
+}
+
+/// Test that invalid items are rejected without failing the batch
+/// 
+/// This test verifies that:
+/// - Valid readings are still stored
+/// - The invalid reading is listed in the response
+#[tokio::test]
+async fn test_ingest_batch_rejects_invalid_items() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let invalid_device = app.generate_test_device_id();
+
+    let response = client
+        .post("/iot/data/ingest/batch")
+        .json(&serde_json::json!({
+            "gateway_id": "test-gateway",
+            "readings": [
+                { "device_id": device_id, "telemetry_data": { "temperature": "22.5" } },
+                { "device_id": invalid_device, "telemetry_data": {} }
+            ]
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let result: BatchResult = response.into_json().await.expect("Invalid batch result");
+    assert_eq!(result.accepted, 1);
+    assert_eq!(result.rejected.len(), 1);
+    assert_eq!(result.rejected[0].device_id, invalid_device);
+}
+
+/// Test batch ingestion with an empty gateway ID
+/// 
+/// This test verifies that:
+/// - Batches without a gateway ID are rejected
+/// - The API returns a 400 Bad Request status
+#[tokio::test]
+async fn test_ingest_batch_empty_gateway_id() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .post("/iot/data/ingest/batch")
+        .json(&serde_json::json!({ "gateway_id": "", "heartbeats": [] }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test last-seen lookup for an unknown device
+/// 
+/// This test verifies that the API returns 404 Not Found for a device
+/// that has not been seen.
+#[tokio::test]
+async fn test_last_seen_unknown_device() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/last-seen/{}", device_id))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -0,0 +1,84 @@
+// Fuzz Corpus Regression Tests
+// 
+// This module replays the committed cargo-fuzz corpus (fuzz/corpus) through
+// the same deserializers and validation as the fuzz targets, so inputs that
+// once crashed them are checked on every test run. No database is needed.
+
+use std::fs;
+use std::path::PathBuf;
+use device_comms::domain::canary::parse_experimental;
+use device_comms::domain::gateway::GatewayBatch;
+use device_comms::domain::telemetry::Telemetry;
+
+/// Reads every input in a fuzz target's corpus directory
+/// 
+/// # Arguments
+/// * `target` - Name of the fuzz target
+/// 
+/// # Returns
+/// * `Vec<(PathBuf, Vec<u8>)>` - Each input file with its contents
+fn corpus(target: &str) -> Vec<(PathBuf, Vec<u8>)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus").join(target);
+    let mut inputs: Vec<(PathBuf, Vec<u8>)> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Failed to read corpus {}: {}", dir.display(), e))
+        .map(|entry| {
+            let path = entry.expect("Failed to read corpus entry").path();
+            let data = fs::read(&path).expect("Failed to read corpus input");
+            (path, data)
+        })
+        .collect();
+    inputs.sort();
+
+    assert!(!inputs.is_empty(), "Corpus {} is empty", dir.display());
+    inputs
+}
+
+/// Test that the telemetry corpus is handled without panicking
+/// 
+/// This test verifies that:
+/// - Every input either fails to deserialize or passes through both validation pipelines
+/// - Accepted telemetry keeps its timestamp through a storage round trip
+#[test]
+fn test_telemetry_corpus() {
+    for (path, data) in corpus("telemetry_json") {
+        let Ok(telemetry) = serde_json::from_slice::<Telemetry>(&data) else {
+            continue;
+        };
+
+        let _ = Telemetry::parse(
+            telemetry.device_id.clone(),
+            telemetry.telemetry_data.clone(),
+            telemetry.timestamp,
+        );
+        let _ = parse_experimental(&telemetry);
+
+        let stored = serde_json::to_vec(&telemetry).expect("Failed to serialize telemetry");
+        let reread: Telemetry = serde_json::from_slice(&stored)
+            .unwrap_or_else(|e| panic!("{}: stored telemetry does not deserialize: {}", path.display(), e));
+        assert_eq!(reread.timestamp, telemetry.timestamp, "{}", path.display());
+    }
+}
+
+/// Test that the gateway batch corpus is handled without panicking
+/// 
+/// This test verifies that every input either fails to deserialize or has
+/// its readings validated and heartbeats converted.
+#[test]
+fn test_gateway_batch_corpus() {
+    for (_, data) in corpus("gateway_batch_json") {
+        let Ok(batch) = serde_json::from_slice::<GatewayBatch>(&data) else {
+            continue;
+        };
+
+        for reading in &batch.readings {
+            let _ = Telemetry::parse(
+                reading.device_id.clone(),
+                reading.telemetry_data.clone(),
+                reading.timestamp,
+            );
+        }
+        for heartbeat in batch.pending_heartbeats() {
+            let _ = heartbeat.to_telemetry(&batch.gateway_id);
+        }
+    }
+}
@@ -74,6 +74,8 @@ impl TestApp {
             .attach(cors) // Enable CORS for test requests
             .mount("/iot/data", routes![
                 device_comms::routes::ingest_telemetry::ingest,
+                device_comms::routes::ingest_batch::ingest_batch,
+                device_comms::routes::last_seen::last_seen,
             ]);
 
         // Create a tracked client for making test requests
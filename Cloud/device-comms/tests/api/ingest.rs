@@ -48,6 +48,37 @@ async fn test_ingest_telemetry() {
     assert_eq!(body, "Telemetry ingested");
 }
 
+/// Test telemetry ingestion with a boot session id
+/// 
+/// This test verifies that:
+/// - Payloads carrying the firmware's session_id field are accepted
+/// - The response indicates successful ingestion
+#[tokio::test]
+async fn test_ingest_telemetry_with_session_id() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    // Payload in the shape sent by the firmware
+    let payload = serde_json::json!({
+        "device_id": device_id,
+        "session_id": "1a2b3c4d",
+        "telemetry_data": { "temperature": "22.5" }
+    });
+
+    let response = client
+        .post("/iot/data/ingest")
+        .json(&payload)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_string().await.expect("Failed to read response body");
+    assert_eq!(body, "Telemetry ingested");
+}
+
 /// Test telemetry ingestion without providing a timestamp
 /// 
 /// This test verifies that:
@@ -7,3 +7,5 @@
 mod helper;
 
 mod ingest;
+mod batch;
+mod fuzz_corpus;
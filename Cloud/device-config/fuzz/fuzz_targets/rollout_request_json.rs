@@ -0,0 +1,28 @@
+// Rollout Request JSON Fuzz Target
+// 
+// Feeds arbitrary bytes through the rollout creation path: the
+// RolloutRequest deserializer (including the canary selection enum),
+// rollout validation and canary resolution.
+
+#![no_main]
+
+use std::collections::HashSet;
+
+use device_config::domain::{ConfigLimits, Rollout, RolloutRequest};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(request) = serde_json::from_slice::<RolloutRequest>(data) else {
+        return;
+    };
+
+    if let Ok(rollout) = Rollout::parse("fuzz-rollout".to_string(), request, &ConfigLimits::default()) {
+        // Canary devices are always distinct and drawn from the targeted group
+        assert!(rollout.canary_devices.iter().all(|d| rollout.devices.contains(d)));
+        let distinct: HashSet<&String> = rollout.devices.iter().collect();
+        assert_eq!(distinct.len(), rollout.devices.len());
+        let distinct: HashSet<&String> = rollout.canary_devices.iter().collect();
+        assert_eq!(distinct.len(), rollout.canary_devices.len());
+        let _ = rollout.remaining_devices();
+    }
+});
@@ -3,7 +3,14 @@
 // This module defines the shared application state that is injected into
 // all request handlers via Rocket's state management system.
 
-use crate::services::CosmosDbTelemetryStore;
+use tracing::{error, warn};
+
+use crate::domain::auth::AuthConfig;
+use crate::domain::limits::ConfigLimits;
+use crate::domain::secrets::ConfigCipher;
+use crate::domain::signing::ConfigSigner;
+use crate::services::{ConfigNotifier, CosmosDbTelemetryStore, EventPublisher, FetchRateLimiter};
+use crate::utils::conditional::CachePolicy;
 
 /// Application state containing shared resources and dependencies
 /// 
@@ -19,17 +26,267 @@ pub struct AppState {
     /// This client is used by configuration handlers to store and retrieve
     /// device configuration data in the Cosmos DB database.
     pub cosmos_client: CosmosDbTelemetryStore,
+
+    /// Credentials and roles used by the authorization request guards
+    /// 
+    /// Loaded from the `API_KEYS`, `DEVICE_KEYS`, `JWT_SECRET`, `AUTH_TENANT_ID`,
+    /// `AUTH_AUDIENCE` and `AUTH_DISABLED` environment variables by default.
+    pub auth: AuthConfig,
+
+    /// Key used to sign served configurations, if configured
+    /// 
+    /// Loaded from the `CONFIG_SIGNING_KEY` and `CONFIG_SIGNING_KEY_ID`
+    /// environment variables by default. Without a key, configurations are
+    /// served unsigned.
+    pub signer: Option<ConfigSigner>,
+
+    /// Encrypts secret configuration values before they are stored
+    /// 
+    /// Loaded from the `CONFIG_ENCRYPTION_KEY`, `CONFIG_ENCRYPTION_KEY_ID`
+    /// and `SECRET_CONFIG_KEYS` environment variables by default. Without a
+    /// key, secret values are stored in plaintext but still redacted from
+    /// audit entries and change events.
+    pub cipher: ConfigCipher,
+
+    /// Key count, key and value length, key character and reserved key limits
+    /// enforced on every stored configuration
+    /// 
+    /// Loaded from the `CONFIG_MAX_KEYS`, `CONFIG_MAX_KEY_LENGTH`,
+    /// `CONFIG_MAX_VALUE_LENGTH`, `CONFIG_KEY_SPECIAL_CHARS` and
+    /// `CONFIG_RESERVED_KEYS` environment variables by default.
+    pub limits: ConfigLimits,
+
+    /// Delivers configuration change events to registered webhooks and Event Grid
+    /// 
+    /// Loaded from the `CONFIG_EVENT_WEBHOOKS`, `CONFIG_EVENT_GRID_ENDPOINT`
+    /// and `CONFIG_EVENT_GRID_KEY` environment variables by default.
+    pub events: EventPublisher,
+
+    /// Wakes long-polling configuration requests when a configuration is stored
+    pub notifier: ConfigNotifier,
+
+    /// Limits how often each device may fetch its configuration
+    /// 
+    /// Loaded from the `CONFIG_FETCH_RATE_LIMIT` and
+    /// `CONFIG_FETCH_RATE_WINDOW_SECS` environment variables by default.
+    pub fetch_limiter: FetchRateLimiter,
+
+    /// How long caches may serve a configuration without revalidating it
+    /// 
+    /// Loaded from the `CONFIG_CACHE_MAX_AGE` environment variable by default.
+    pub cache_policy: CachePolicy,
+
+    /// Whether configuration updates must be approved by a second person
+    /// 
+    /// Loaded from the `CONFIG_REQUIRE_APPROVAL` environment variable by default.
+    pub approval_required: bool,
 }
 
 impl AppState {
     /// Creates a new application state instance
     /// 
+    /// The authorization configuration, signing key, encryption key,
+    /// configuration limits, event webhooks, fetch rate limit, cache policy
+    /// and approval requirement are loaded from the environment; use
+    /// `with_auth_config`, `with_signer`, `with_cipher`, `with_limits`,
+    /// `with_event_publisher`, `with_fetch_limiter`, `with_cache_policy` and
+    /// `with_approval_required` to override them.
+    /// 
     /// # Arguments
     /// * `cosmos_client` - The configured Cosmos DB configuration store client
     /// 
     /// # Returns
     /// * `Self` - A new AppState instance with the provided dependencies
     pub fn new(cosmos_client: CosmosDbTelemetryStore) -> Self {
-        Self { cosmos_client }
+        Self {
+            cosmos_client,
+            auth: AuthConfig::from_env(),
+            signer: load_signer(),
+            cipher: load_cipher(),
+            limits: load_limits(),
+            events: EventPublisher::from_env(),
+            notifier: ConfigNotifier::new(),
+            fetch_limiter: load_fetch_limiter(),
+            cache_policy: load_cache_policy(),
+            approval_required: load_approval_required(),
+        }
+    }
+
+    /// Replaces the authorization configuration of this application state
+    /// 
+    /// # Arguments
+    /// * `auth` - The credentials and roles to enforce
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given authorization configuration
+    pub fn with_auth_config(mut self, auth: AuthConfig) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Replaces the configuration signing key of this application state
+    /// 
+    /// # Arguments
+    /// * `signer` - The key to sign served configurations with (None to serve them unsigned)
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given signing key
+    pub fn with_signer(mut self, signer: Option<ConfigSigner>) -> Self {
+        self.signer = signer;
+        self
+    }
+
+    /// Replaces the secret value cipher of this application state
+    /// 
+    /// # Arguments
+    /// * `cipher` - The cipher to encrypt secret configuration values with
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given cipher
+    pub fn with_cipher(mut self, cipher: ConfigCipher) -> Self {
+        self.cipher = cipher;
+        self
     }
-}
\ No newline at end of file
+
+    /// Replaces the configuration limits of this application state
+    /// 
+    /// # Arguments
+    /// * `limits` - The limits to enforce on stored configurations
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given limits
+    pub fn with_limits(mut self, limits: ConfigLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Replaces the configuration change event publisher of this application state
+    /// 
+    /// # Arguments
+    /// * `events` - The publisher to deliver change events with
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given event publisher
+    pub fn with_event_publisher(mut self, events: EventPublisher) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Replaces the configuration fetch rate limiter of this application state
+    /// 
+    /// # Arguments
+    /// * `fetch_limiter` - The limiter to apply to configuration fetches
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given rate limiter
+    pub fn with_fetch_limiter(mut self, fetch_limiter: FetchRateLimiter) -> Self {
+        self.fetch_limiter = fetch_limiter;
+        self
+    }
+
+    /// Replaces the cache policy of this application state
+    /// 
+    /// # Arguments
+    /// * `cache_policy` - How long caches may serve a configuration
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given cache policy
+    pub fn with_cache_policy(mut self, cache_policy: CachePolicy) -> Self {
+        self.cache_policy = cache_policy;
+        self
+    }
+
+    /// Sets whether configuration updates must be approved by a second person
+    /// 
+    /// # Arguments
+    /// * `approval_required` - True to hold updates for approval
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given approval requirement
+    pub fn with_approval_required(mut self, approval_required: bool) -> Self {
+        self.approval_required = approval_required;
+        self
+    }
+}
+
+/// Loads the configuration signing key from the environment, logging problems
+fn load_signer() -> Option<ConfigSigner> {
+    match ConfigSigner::from_env() {
+        Ok(Some(signer)) => Some(signer),
+        Ok(None) => {
+            warn!("No CONFIG_SIGNING_KEY configured - configurations will be served unsigned");
+            None
+        }
+        Err(e) => {
+            error!("Invalid CONFIG_SIGNING_KEY, configurations will be served unsigned: {}", e);
+            None
+        }
+    }
+}
+
+/// Loads the secret value encryption key from the environment, logging problems
+fn load_cipher() -> ConfigCipher {
+    match ConfigCipher::from_env() {
+        Ok(cipher) if cipher.is_enabled() => cipher,
+        Ok(cipher) => {
+            warn!("No CONFIG_ENCRYPTION_KEY configured - secret configuration values will be stored in plaintext");
+            cipher
+        }
+        Err(e) => {
+            error!("Invalid CONFIG_ENCRYPTION_KEY, secret configuration values will be stored in plaintext: {}", e);
+            ConfigCipher::default()
+        }
+    }
+}
+
+/// Loads the configuration limits from the environment, logging problems
+fn load_limits() -> ConfigLimits {
+    match ConfigLimits::from_env() {
+        Ok(limits) => limits,
+        Err(e) => {
+            error!("Invalid configuration limits, using the defaults: {}", e);
+            ConfigLimits::default()
+        }
+    }
+}
+
+/// Loads the configuration fetch rate limit from the environment, logging problems
+fn load_fetch_limiter() -> FetchRateLimiter {
+    match FetchRateLimiter::from_env() {
+        Ok(limiter) if limiter.is_enabled() => limiter,
+        Ok(limiter) => {
+            warn!("CONFIG_FETCH_RATE_LIMIT is 0 - configuration fetches will not be rate limited");
+            limiter
+        }
+        Err(e) => {
+            error!("Invalid configuration fetch rate limit, using the defaults: {}", e);
+            FetchRateLimiter::default()
+        }
+    }
+}
+
+/// Loads the configuration cache policy from the environment, logging problems
+fn load_cache_policy() -> CachePolicy {
+    match CachePolicy::from_env() {
+        Ok(policy) => policy,
+        Err(e) => {
+            error!("Invalid CONFIG_CACHE_MAX_AGE, configurations will always be revalidated: {}", e);
+            CachePolicy::default()
+        }
+    }
+}
+
+/// Loads the configuration approval requirement from the environment, logging problems
+/// 
+/// An unrecognised value requires approval, so a typo cannot silently
+/// disable four-eyes review.
+fn load_approval_required() -> bool {
+    match std::env::var("CONFIG_REQUIRE_APPROVAL").unwrap_or_default().trim().to_ascii_lowercase().as_str() {
+        "" | "false" | "0" => false,
+        "true" | "1" => true,
+        other => {
+            error!("Invalid CONFIG_REQUIRE_APPROVAL {:?}, configuration updates will require approval", other);
+            true
+        }
+    }
+}
@@ -0,0 +1,299 @@
+// Configuration Approval Domain Model
+//
+// This module defines pending configuration changes for deployments that
+// require four-eyes review: when approval is required, updates are held as
+// pending changes until a second person approves them, and devices keep
+// fetching the previous configuration until then.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::audit::ConfigChange;
+use crate::domain::config::Config;
+
+/// Review state of a pending change
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// Waiting for review; devices do not see the change yet
+    Pending,
+    /// Approved and stored as the device's configuration
+    Approved,
+    /// Rejected; the change was discarded
+    Rejected,
+}
+
+impl ApprovalStatus {
+    /// Parses a status name as used in query parameters
+    ///
+    /// # Arguments
+    /// * `name` - "pending", "approved" or "rejected"
+    ///
+    /// # Returns
+    /// * `Option<Self>` - The status, or None if the name is unknown
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "pending" => Some(ApprovalStatus::Pending),
+            "approved" => Some(ApprovalStatus::Approved),
+            "rejected" => Some(ApprovalStatus::Rejected),
+            _ => None,
+        }
+    }
+
+    /// Returns the status name as stored and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalStatus::Pending => "pending",
+            ApprovalStatus::Approved => "approved",
+            ApprovalStatus::Rejected => "rejected",
+        }
+    }
+}
+
+/// A configuration change awaiting or past review
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigApproval {
+    /// Unique identifier of the change (also the Cosmos DB document id and partition key)
+    pub id: String,
+    /// The device the change applies to
+    pub device_id: String,
+    /// The proposed configuration, secret values encrypted
+    pub config: HashMap<String, String>,
+    /// Optional time at which the configuration becomes active once approved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apply_at: Option<DateTime<Utc>>,
+    /// Keys the change would modify at submission time, secret values redacted
+    #[serde(default)]
+    pub changes: Vec<ConfigChange>,
+    /// Number of the device's latest configuration version at submission
+    /// time, `None` if it had none
+    /// 
+    /// The change is only approved while this is still the latest version,
+    /// so reviewers never approve against a configuration that was replaced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_version: Option<u64>,
+    /// Current review state
+    pub status: ApprovalStatus,
+    /// Who submitted the change
+    pub requested_by: String,
+    /// When the change was submitted
+    pub requested_at: DateTime<Utc>,
+    /// Who approved or rejected the change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<String>,
+    /// When the change was approved or rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// Reason given by the reviewer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Entity tag of the stored document, as read from Cosmos DB
+    /// 
+    /// Reviews replace the document only if it still has this tag, so a
+    /// change is reviewed once even when reviewers race; never serialized.
+    #[serde(rename = "_etag", default, skip_serializing)]
+    pub etag: Option<String>,
+}
+
+/// Body of a request approving or rejecting a change
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ReviewRequest {
+    /// Optional reason, recorded with the review
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Error types that can occur while reviewing configuration changes
+#[derive(Debug, Serialize)]
+pub enum ApprovalError {
+    /// Unknown status name in a listing filter
+    InvalidStatus(String),
+    /// No change with this id exists
+    ApprovalNotFound(String),
+    /// The change was already approved or rejected
+    NotPending(String),
+    /// The reviewer submitted the change themselves
+    SelfReview,
+    /// The device's configuration changed since the change was submitted
+    Outdated(String),
+    /// The configuration no longer passes validation or the configured limits
+    ConfigRejected(String),
+    /// Database operation error
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for ApprovalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApprovalError::InvalidStatus(status) => write!(f, "Unknown approval status: {:?}", status),
+            ApprovalError::ApprovalNotFound(id) => write!(f, "Pending change not found: {}", id),
+            ApprovalError::NotPending(id) => write!(f, "Change {} was already reviewed", id),
+            ApprovalError::SelfReview => write!(f, "Changes must be reviewed by someone other than their author"),
+            ApprovalError::Outdated(id) => write!(f, "Change {} was submitted against a configuration that has since been replaced", id),
+            ApprovalError::ConfigRejected(msg) => write!(f, "Configuration rejected: {}", msg),
+            ApprovalError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ApprovalError {}
+
+impl ConfigApproval {
+    /// Creates a pending change
+    ///
+    /// # Arguments
+    /// * `config` - The validated configuration, secret values encrypted
+    /// * `changes` - Keys the change would modify, secret values redacted
+    /// * `requested_by` - Who submitted the change
+    ///
+    /// # Returns
+    /// * `Self` - A new ConfigApproval instance
+    pub fn new(config: Config, changes: Vec<ConfigChange>, requested_by: String) -> Self {
+        ConfigApproval {
+            id: uuid::Uuid::new_v4().to_string(),
+            device_id: config.device_id,
+            config: config.config,
+            apply_at: config.apply_at,
+            changes,
+            base_version: None,
+            status: ApprovalStatus::Pending,
+            requested_by,
+            requested_at: Utc::now(),
+            reviewed_by: None,
+            reviewed_at: None,
+            reason: None,
+            etag: None,
+        }
+    }
+
+    /// Records the device's latest configuration version at submission time
+    pub fn based_on(mut self, version_number: Option<u64>) -> Self {
+        self.base_version = version_number;
+        self
+    }
+
+    /// Checks that the device's configuration was not replaced since the
+    /// change was submitted
+    /// 
+    /// # Arguments
+    /// * `version_number` - Number of the device's latest version, `None` if it has none
+    /// 
+    /// # Returns
+    /// * `Result<(), ApprovalError>` - Success, or `Outdated` if another version was stored
+    pub fn check_base(&self, version_number: Option<u64>) -> Result<(), ApprovalError> {
+        if self.base_version != version_number {
+            return Err(ApprovalError::Outdated(self.id.clone()));
+        }
+        Ok(())
+    }
+
+    /// Checks that a reviewer may approve or reject the change
+    ///
+    /// # Arguments
+    /// * `reviewer` - Who is reviewing the change
+    ///
+    /// # Returns
+    /// * `Result<(), ApprovalError>` - Success, or why the review is not allowed
+    pub fn check_reviewer(&self, reviewer: &str) -> Result<(), ApprovalError> {
+        if self.status != ApprovalStatus::Pending {
+            return Err(ApprovalError::NotPending(self.id.clone()));
+        }
+        if self.requested_by == reviewer {
+            return Err(ApprovalError::SelfReview);
+        }
+        Ok(())
+    }
+
+    /// Records the outcome of a review
+    ///
+    /// # Arguments
+    /// * `status` - `Approved` or `Rejected`
+    /// * `reviewer` - Who reviewed the change
+    /// * `reason` - Optional reason given by the reviewer
+    ///
+    /// # Returns
+    /// * `Result<(), ApprovalError>` - Success, or why the review is not allowed
+    pub fn review(&mut self, status: ApprovalStatus, reviewer: &str, reason: Option<String>) -> Result<(), ApprovalError> {
+        self.check_reviewer(reviewer)?;
+        self.status = status;
+        self.reviewed_by = Some(reviewer.to_string());
+        self.reviewed_at = Some(Utc::now());
+        self.reason = reason.map(|reason| reason.trim().to_string()).filter(|reason| !reason.is_empty());
+        Ok(())
+    }
+
+    /// Returns the proposed configuration as it would be stored
+    pub fn proposed_config(&self) -> Config {
+        Config::new(self.device_id.clone(), self.config.clone()).scheduled(self.apply_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approval() -> ConfigApproval {
+        let config: HashMap<String, String> = [("LED".to_string(), "on".to_string())].into_iter().collect();
+        ConfigApproval::new(Config::new("sensor-001".to_string(), config), Vec::new(), "alice".to_string())
+    }
+
+    #[test]
+    fn test_review_requires_second_person() {
+        let mut change = approval();
+
+        assert!(matches!(change.review(ApprovalStatus::Approved, "alice", None), Err(ApprovalError::SelfReview)));
+        assert_eq!(change.status, ApprovalStatus::Pending);
+
+        change.review(ApprovalStatus::Approved, "bob", Some(" looks good ".to_string())).unwrap();
+        assert_eq!(change.status, ApprovalStatus::Approved);
+        assert_eq!(change.reviewed_by.as_deref(), Some("bob"));
+        assert_eq!(change.reason.as_deref(), Some("looks good"));
+    }
+
+    #[test]
+    fn test_review_only_once() {
+        let mut change = approval();
+        change.review(ApprovalStatus::Rejected, "bob", None).unwrap();
+
+        let result = change.review(ApprovalStatus::Approved, "carol", None);
+        assert!(matches!(result, Err(ApprovalError::NotPending(_))));
+        assert_eq!(change.status, ApprovalStatus::Rejected);
+    }
+
+    #[test]
+    fn test_base_version_must_be_current() {
+        let change = approval().based_on(Some(3));
+
+        assert!(change.check_base(Some(3)).is_ok());
+        assert!(matches!(change.check_base(Some(4)), Err(ApprovalError::Outdated(_))));
+        assert!(matches!(approval().check_base(Some(1)), Err(ApprovalError::Outdated(_))));
+        assert!(approval().check_base(None).is_ok());
+    }
+
+    #[test]
+    fn test_etag_is_not_serialized() {
+        let stored = serde_json::json!({
+            "id": "c1",
+            "device_id": "sensor-001",
+            "config": {},
+            "status": "pending",
+            "requested_by": "alice",
+            "requested_at": "2026-01-01T00:00:00Z",
+            "_etag": "\"0000-0001\""
+        });
+
+        let change: ConfigApproval = serde_json::from_value(stored).unwrap();
+        assert_eq!(change.etag.as_deref(), Some("\"0000-0001\""));
+        assert!(serde_json::to_value(&change).unwrap().get("_etag").is_none());
+    }
+
+    #[test]
+    fn test_status_names() {
+        for status in [ApprovalStatus::Pending, ApprovalStatus::Approved, ApprovalStatus::Rejected] {
+            assert_eq!(ApprovalStatus::parse(status.as_str()), Some(status));
+            assert_eq!(serde_json::to_value(status).unwrap(), status.as_str());
+        }
+        assert_eq!(ApprovalStatus::parse("unknown"), None);
+    }
+}
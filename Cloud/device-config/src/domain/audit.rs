@@ -0,0 +1,207 @@
+// Configuration Audit Domain Model
+//
+// This module defines the audit trail entries recorded for every
+// configuration mutation: who made the change, when, from where, and
+// which configuration keys changed.
+
+use std::collections::{BTreeSet, HashMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of configuration mutation recorded in the audit log
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Configuration written through POST /device-config/update
+    ConfigUpdate,
+    /// Rollout created and applied to its canary devices
+    RolloutCreate,
+    /// Rollout paused
+    RolloutPause,
+    /// Rollout resumed
+    RolloutResume,
+    /// Rollout promoted to the whole group
+    RolloutPromote,
+    /// Rollout aborted and canary devices reverted
+    RolloutAbort,
+    /// Firmware version assigned through POST /device-config/firmware/assign
+    FirmwareAssign,
+    /// Temporary override applied through POST /device-config/overrides
+    OverrideCreate,
+    /// Temporary override reverted, on expiry or by an operator
+    OverrideRevert,
+    /// Configuration submitted for approval
+    ConfigSubmit,
+    /// Pending configuration approved and stored
+    ConfigApprove,
+    /// Pending configuration rejected
+    ConfigReject,
+    /// Configuration soft-deleted through DELETE /device-config/devices/<device_id>
+    ConfigDelete,
+    /// Deleted configuration restored
+    ConfigRestore,
+    /// Earlier configuration version stored again through
+    /// POST /device-config/history/<device_id>/<version_number>/rollback
+    ConfigRollback,
+}
+
+impl AuditAction {
+    /// True if a configuration stored under this action is held back while
+    /// approval is required
+    ///
+    /// Approved changes were reviewed already, and reverting an override
+    /// only restores the values it replaced.
+    pub fn requires_approval(&self) -> bool {
+        !matches!(self, AuditAction::ConfigApprove | AuditAction::OverrideRevert)
+    }
+}
+
+/// A single configuration key change
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// The configuration key that changed
+    pub key: String,
+    /// Value before the change, `None` if the key was added
+    pub old_value: Option<String>,
+    /// Value after the change, `None` if the key was removed
+    pub new_value: Option<String>,
+}
+
+/// Who made a change and where the request came from
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditActor {
+    /// Verified JWT subject, API key name, `device:<id>` for a device key,
+    /// or "anonymous" while authorization is disabled
+    pub subject: String,
+    /// Client IP address as seen by the service
+    pub source_ip: Option<String>,
+    /// User-Agent header of the request
+    pub user_agent: Option<String>,
+}
+
+/// An immutable audit trail entry
+///
+/// Entries are only ever created, never updated or deleted.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    /// Unique identifier of the entry (also the Cosmos DB document id)
+    pub id: String,
+    /// The device ID or rollout ID the change applies to (partition key)
+    pub target: String,
+    /// What kind of mutation was made
+    pub action: AuditAction,
+    /// Rollout that caused the change, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout_id: Option<String>,
+    /// Who made the change
+    pub actor: AuditActor,
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+    /// Configuration keys that changed
+    #[serde(default)]
+    pub changes: Vec<ConfigChange>,
+}
+
+impl AuditEntry {
+    /// Creates a new audit entry timestamped now
+    ///
+    /// # Arguments
+    /// * `target` - The device ID or rollout ID the change applies to
+    /// * `action` - The kind of mutation
+    /// * `actor` - Who made the change
+    /// * `changes` - The configuration keys that changed
+    ///
+    /// # Returns
+    /// * `Self` - A new AuditEntry instance
+    pub fn new(
+        target: String,
+        action: AuditAction,
+        actor: AuditActor,
+        changes: Vec<ConfigChange>,
+    ) -> Self {
+        AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            target,
+            action,
+            rollout_id: None,
+            actor,
+            timestamp: Utc::now(),
+            changes,
+        }
+    }
+
+    /// Links the entry to the rollout that caused it
+    pub fn for_rollout(mut self, rollout_id: &str) -> Self {
+        self.rollout_id = Some(rollout_id.to_string());
+        self
+    }
+}
+
+/// Computes the key-level changes between two configurations
+///
+/// Keys are reported in sorted order; unchanged keys are omitted.
+///
+/// # Arguments
+/// * `old` - The configuration before the change, if any
+/// * `new` - The configuration after the change
+///
+/// # Returns
+/// * `Vec<ConfigChange>` - Added, removed and modified keys
+pub fn diff_configs(
+    old: Option<&HashMap<String, String>>,
+    new: &HashMap<String, String>,
+) -> Vec<ConfigChange> {
+    let empty = HashMap::new();
+    let old = old.unwrap_or(&empty);
+
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    keys.into_iter()
+        .filter(|key| old.get(*key) != new.get(*key))
+        .map(|key| ConfigChange {
+            key: key.clone(),
+            old_value: old.get(key).cloned(),
+            new_value: new.get(key).cloned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_diff_configs_added_changed_removed() {
+        let old = config(&[("LED", "off"), ("threshold", "25"), ("mode", "eco")]);
+        let new = config(&[("LED", "on"), ("threshold", "25"), ("interval", "30")]);
+
+        let changes = diff_configs(Some(&old), &new);
+
+        assert_eq!(changes, vec![
+            ConfigChange { key: "LED".to_string(), old_value: Some("off".to_string()), new_value: Some("on".to_string()) },
+            ConfigChange { key: "interval".to_string(), old_value: None, new_value: Some("30".to_string()) },
+            ConfigChange { key: "mode".to_string(), old_value: Some("eco".to_string()), new_value: None },
+        ]);
+    }
+
+    #[test]
+    fn test_diff_configs_without_previous() {
+        let new = config(&[("LED", "on")]);
+
+        let changes = diff_configs(None, &new);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].old_value, None);
+    }
+
+    #[test]
+    fn test_reviewed_actions_bypass_approval() {
+        assert!(AuditAction::ConfigUpdate.requires_approval());
+        assert!(AuditAction::OverrideCreate.requires_approval());
+        assert!(!AuditAction::ConfigApprove.requires_approval());
+        assert!(!AuditAction::OverrideRevert.requires_approval());
+    }
+}
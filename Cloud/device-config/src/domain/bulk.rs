@@ -0,0 +1,358 @@
+// Bulk Configuration Export/Import Domain Model
+//
+// This module defines the document used to move every device configuration
+// between environments, or to restore them after data loss, and decides
+// what an import does with each configuration in it.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::config::{Config, DEFAULT_CONFIG_ID};
+use crate::domain::limits::ConfigLimits;
+
+/// Version of the bundle format written by this service
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Every active device configuration, as exported by the service
+///
+/// The fleet-wide defaults are included under their reserved device ID.
+/// Secret values are exported as stored, so they stay encrypted when a
+/// key-encryption key is configured.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigBundle {
+    /// Version of the bundle format
+    pub format_version: u32,
+    /// When the bundle was exported
+    pub exported_at: DateTime<Utc>,
+    /// The active configuration of every device
+    pub configs: Vec<Config>,
+}
+
+impl ConfigBundle {
+    /// Creates a bundle of the given configurations, sorted by device ID
+    ///
+    /// # Arguments
+    /// * `configs` - The configurations to export
+    ///
+    /// # Returns
+    /// * `Self` - A new bundle in the current format
+    pub fn new(mut configs: Vec<Config>) -> Self {
+        configs.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+        ConfigBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            configs,
+        }
+    }
+
+    /// Serializes the bundle in the given format
+    ///
+    /// # Arguments
+    /// * `format` - JSON or YAML
+    ///
+    /// # Returns
+    /// * `Result<String, BulkError>` - The serialized bundle or an error
+    pub fn render(&self, format: BundleFormat) -> Result<String, BulkError> {
+        match format {
+            BundleFormat::Json => serde_json::to_string_pretty(self).map_err(|e| BulkError::InvalidDocument(e.to_string())),
+            BundleFormat::Yaml => serde_yaml::to_string(self).map_err(|e| BulkError::InvalidDocument(e.to_string())),
+        }
+    }
+
+    /// Parses a bundle in the given format
+    ///
+    /// # Arguments
+    /// * `document` - The serialized bundle
+    /// * `format` - JSON or YAML
+    ///
+    /// # Returns
+    /// * `Result<Self, BulkError>` - The bundle, or an error if it is malformed or in an unknown format version
+    pub fn parse(document: &str, format: BundleFormat) -> Result<Self, BulkError> {
+        let bundle: ConfigBundle = match format {
+            BundleFormat::Json => serde_json::from_str(document).map_err(|e| BulkError::InvalidDocument(e.to_string()))?,
+            BundleFormat::Yaml => serde_yaml::from_str(document).map_err(|e| BulkError::InvalidDocument(e.to_string()))?,
+        };
+
+        if bundle.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(BulkError::UnsupportedVersion(bundle.format_version));
+        }
+
+        Ok(bundle)
+    }
+}
+
+/// Serialization format of a bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleFormat {
+    Json,
+    Yaml,
+}
+
+impl BundleFormat {
+    /// Parses a `format` query parameter, defaulting to JSON
+    ///
+    /// # Arguments
+    /// * `name` - "json", "yaml" or "yml" (case-insensitive)
+    ///
+    /// # Returns
+    /// * `Result<Self, BulkError>` - The format, or an error for unknown names
+    pub fn parse(name: Option<&str>) -> Result<Self, BulkError> {
+        match name.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("json") => Ok(BundleFormat::Json),
+            Some("yaml") | Some("yml") => Ok(BundleFormat::Yaml),
+            Some(other) => Err(BulkError::InvalidFormat(other.to_string())),
+        }
+    }
+}
+
+/// What an import does with a device that already has a different configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Store the imported configuration as a new version
+    Overwrite,
+    /// Keep the existing configuration
+    Skip,
+}
+
+impl ConflictPolicy {
+    /// Parses an `on_conflict` query parameter, defaulting to overwrite
+    ///
+    /// # Arguments
+    /// * `name` - "overwrite" or "skip" (case-insensitive)
+    ///
+    /// # Returns
+    /// * `Result<Self, BulkError>` - The policy, or an error for unknown names
+    pub fn parse(name: Option<&str>) -> Result<Self, BulkError> {
+        match name.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("overwrite") => Ok(ConflictPolicy::Overwrite),
+            Some("skip") => Ok(ConflictPolicy::Skip),
+            Some(other) => Err(BulkError::InvalidConflictPolicy(other.to_string())),
+        }
+    }
+}
+
+/// What an import did, or would do in a dry run, with one configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// The device had no configuration; the imported one was stored
+    Created,
+    /// The device had a different configuration; the imported one was stored
+    Updated,
+    /// Nothing was stored
+    Skipped,
+    /// The configuration is invalid or could not be stored
+    Failed,
+}
+
+/// Result of importing one configuration
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ImportItem {
+    /// The device the configuration belongs to
+    pub device_id: String,
+    /// What happened to it
+    pub outcome: ImportOutcome,
+    /// Why it was skipped or failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ImportItem {
+    /// Creates a failed item with the given reason
+    pub fn failed(device_id: &str, reason: String) -> Self {
+        ImportItem {
+            device_id: device_id.to_string(),
+            outcome: ImportOutcome::Failed,
+            reason: Some(reason),
+        }
+    }
+}
+
+/// Summary of an import
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ImportReport {
+    /// True if nothing was stored
+    pub dry_run: bool,
+    /// Number of devices whose configuration was (or would be) created
+    pub created: usize,
+    /// Number of devices whose configuration was (or would be) updated
+    pub updated: usize,
+    /// Number of configurations left unchanged
+    pub skipped: usize,
+    /// Number of configurations that are invalid or could not be stored
+    pub failed: usize,
+    /// The outcome for every configuration in the bundle, in bundle order
+    pub items: Vec<ImportItem>,
+}
+
+impl ImportReport {
+    /// Summarizes the outcomes of an import
+    ///
+    /// # Arguments
+    /// * `dry_run` - Whether anything was stored
+    /// * `items` - The outcome for every configuration in the bundle
+    pub fn new(dry_run: bool, items: Vec<ImportItem>) -> Self {
+        let count = |outcome: ImportOutcome| items.iter().filter(|item| item.outcome == outcome).count();
+        ImportReport {
+            dry_run,
+            created: count(ImportOutcome::Created),
+            updated: count(ImportOutcome::Updated),
+            skipped: count(ImportOutcome::Skipped),
+            failed: count(ImportOutcome::Failed),
+            items,
+        }
+    }
+}
+
+/// Decides what importing a configuration does
+///
+/// Invalid configurations fail, configurations identical to the stored one
+/// are skipped, and configurations that differ are skipped or overwrite
+/// the stored one depending on the conflict policy. The fleet defaults,
+/// exported under `_default`, are validated like the defaults endpoint does.
+///
+/// # Arguments
+/// * `entry` - The imported configuration, with secret values in plaintext
+/// * `current` - The device's active configuration in plaintext, if it has one
+/// * `on_conflict` - What to do when the stored configuration differs
+/// * `limits` - The key and value limits every stored configuration must respect
+///
+/// # Returns
+/// * `ImportItem` - The planned outcome
+pub fn plan_import(entry: &Config, current: Option<&HashMap<String, String>>, on_conflict: ConflictPolicy, limits: &ConfigLimits) -> ImportItem {
+    let parsed = if entry.device_id == DEFAULT_CONFIG_ID {
+        Config::parse_defaults(entry.config.clone(), limits)
+    } else {
+        Config::parse(entry.device_id.clone(), entry.config.clone(), limits)
+    };
+    if let Err(e) = parsed {
+        return ImportItem::failed(&entry.device_id, e.to_string());
+    }
+
+    let (outcome, reason) = match current {
+        None => (ImportOutcome::Created, None),
+        Some(current) if *current == entry.config => (ImportOutcome::Skipped, Some("Configuration is unchanged")),
+        Some(_) if on_conflict == ConflictPolicy::Skip => (ImportOutcome::Skipped, Some("Device already has a configuration")),
+        Some(_) => (ImportOutcome::Updated, None),
+    };
+
+    ImportItem {
+        device_id: entry.device_id.clone(),
+        outcome,
+        reason: reason.map(str::to_string),
+    }
+}
+
+/// Error types that can occur while exporting or importing configurations
+#[derive(Debug, Serialize)]
+pub enum BulkError {
+    /// Unknown bundle format name
+    InvalidFormat(String),
+    /// Unknown conflict policy name
+    InvalidConflictPolicy(String),
+    /// The bundle could not be parsed or serialized
+    InvalidDocument(String),
+    /// The bundle was written in a format version this service cannot read
+    UnsupportedVersion(u32),
+    /// The bundle exceeds the import size limit
+    DocumentTooLarge,
+    /// Approval is required, so configurations cannot be imported
+    ApprovalRequired,
+    /// Database operation error
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for BulkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BulkError::InvalidFormat(name) => write!(f, "Unknown bundle format: {}", name),
+            BulkError::InvalidConflictPolicy(name) => write!(f, "Unknown conflict policy: {}", name),
+            BulkError::InvalidDocument(msg) => write!(f, "Invalid bundle: {}", msg),
+            BulkError::UnsupportedVersion(version) => write!(f, "Unsupported bundle format version: {}", version),
+            BulkError::DocumentTooLarge => write!(f, "Bundle exceeds the import size limit"),
+            BulkError::ApprovalRequired => write!(f, "Imports are disabled while configuration changes require approval"),
+            BulkError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BulkError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(device_id: &str, pairs: &[(&str, &str)]) -> Config {
+        let data = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Config::new(device_id.to_string(), data)
+    }
+
+    #[test]
+    fn test_bundle_round_trips_in_both_formats() {
+        let bundle = ConfigBundle::new(vec![
+            config("sensor-002", &[("LED", "off")]),
+            config("sensor-001", &[("LED", "on"), ("interval", "30")]),
+        ]);
+        assert_eq!(bundle.configs[0].device_id, "sensor-001");
+
+        for format in [BundleFormat::Json, BundleFormat::Yaml] {
+            let parsed = ConfigBundle::parse(&bundle.render(format).unwrap(), format).unwrap();
+            assert_eq!(parsed.configs.len(), 2);
+            assert_eq!(parsed.configs[0].config, bundle.configs[0].config);
+        }
+    }
+
+    #[test]
+    fn test_exported_defaults_import_again() {
+        let defaults = config(DEFAULT_CONFIG_ID, &[("LED", "off")]);
+        let bundle = ConfigBundle::new(vec![config("sensor-001", &[("LED", "on")]), defaults.clone()]);
+        let limits = ConfigLimits::default();
+
+        let parsed = ConfigBundle::parse(&bundle.render(BundleFormat::Json).unwrap(), BundleFormat::Json).unwrap();
+        let entry = parsed.configs.iter().find(|entry| entry.device_id == DEFAULT_CONFIG_ID).unwrap();
+
+        let item = plan_import(entry, None, ConflictPolicy::Overwrite, &limits);
+        assert_eq!((item.device_id.as_str(), item.outcome), (DEFAULT_CONFIG_ID, ImportOutcome::Created));
+        let changed = config(DEFAULT_CONFIG_ID, &[("LED", "on")]).config;
+        assert_eq!(plan_import(entry, Some(&changed), ConflictPolicy::Overwrite, &limits).outcome, ImportOutcome::Updated);
+        assert_eq!(plan_import(entry, Some(&defaults.config), ConflictPolicy::Overwrite, &limits).outcome, ImportOutcome::Skipped);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_versions_and_names() {
+        let document = r#"{"format_version": 2, "exported_at": "2025-01-01T00:00:00Z", "configs": []}"#;
+        assert!(matches!(ConfigBundle::parse(document, BundleFormat::Json), Err(BulkError::UnsupportedVersion(2))));
+        assert!(matches!(ConfigBundle::parse("configs: [", BundleFormat::Yaml), Err(BulkError::InvalidDocument(_))));
+
+        assert_eq!(BundleFormat::parse(Some("YML")).unwrap(), BundleFormat::Yaml);
+        assert!(BundleFormat::parse(Some("xml")).is_err());
+        assert_eq!(ConflictPolicy::parse(None).unwrap(), ConflictPolicy::Overwrite);
+        assert!(ConflictPolicy::parse(Some("merge")).is_err());
+    }
+
+    #[test]
+    fn test_plan_import_outcomes() {
+        let entry = config("sensor-001", &[("LED", "on")]);
+        let same = entry.config.clone();
+        let different = config("sensor-001", &[("LED", "off")]).config;
+        let limits = ConfigLimits::default();
+
+        assert_eq!(plan_import(&entry, None, ConflictPolicy::Skip, &limits).outcome, ImportOutcome::Created);
+        assert_eq!(plan_import(&entry, Some(&same), ConflictPolicy::Overwrite, &limits).outcome, ImportOutcome::Skipped);
+        assert_eq!(plan_import(&entry, Some(&different), ConflictPolicy::Overwrite, &limits).outcome, ImportOutcome::Updated);
+        assert_eq!(plan_import(&entry, Some(&different), ConflictPolicy::Skip, &limits).outcome, ImportOutcome::Skipped);
+        assert_eq!(plan_import(&config("", &[("LED", "on")]), None, ConflictPolicy::Overwrite, &limits).outcome, ImportOutcome::Failed);
+
+        let report = ImportReport::new(true, vec![
+            plan_import(&entry, None, ConflictPolicy::Skip, &limits),
+            plan_import(&entry, Some(&same), ConflictPolicy::Skip, &limits),
+        ]);
+        assert_eq!((report.created, report.updated, report.skipped, report.failed), (1, 0, 1, 0));
+
+        let oversized = config("sensor-001", &[("LED", &"x".repeat(limits.max_value_length + 1))]);
+        let item = plan_import(&oversized, None, ConflictPolicy::Overwrite, &limits);
+        assert_eq!(item.outcome, ImportOutcome::Failed);
+        assert!(item.reason.unwrap().contains("longer than"));
+    }
+}
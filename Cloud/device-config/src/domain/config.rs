@@ -5,8 +5,19 @@
 // validation of IoT device configuration data.
 
 use std::collections::HashMap;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::firmware::FirmwareManifest;
+use crate::domain::limits::ConfigLimits;
+use crate::domain::rollout::fnv1a;
+use crate::domain::signing::{canonical_json, ConfigSignature};
+
+/// Reserved device ID under which the fleet-wide default configuration is stored
+/// 
+/// Devices without a configuration of their own are served this one.
+pub const DEFAULT_CONFIG_ID: &str = "_default";
+
 /// Core configuration data structure representing IoT device settings
 /// 
 /// This struct represents a device configuration, including the device identifier
@@ -19,6 +30,82 @@ pub struct Config {
     /// 
     /// Examples: {"sampling_rate": "1000", "threshold": "25.5", "wifi_ssid": "MyNetwork"}
     pub config: HashMap<String, String>,
+    /// Optional time at which this configuration becomes active
+    /// 
+    /// Until this time passes, `get_config` keeps serving the previously
+    /// active version. Omitted or past values take effect immediately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apply_at: Option<DateTime<Utc>>,
+    /// Signature over the canonical form of this configuration
+    /// 
+    /// Only set on configurations served by `get_config` when a signing key
+    /// is configured; never stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ConfigSignature>,
+    /// True if this configuration was served from the fleet-wide defaults
+    /// 
+    /// Only set on configurations served by `get_config` for devices without
+    /// a configuration of their own; never stored.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_default: bool,
+    /// Firmware the device should be running, if a version is assigned
+    /// 
+    /// Only set on configurations served by `get_config`; never stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firmware: Option<FirmwareManifest>,
+    /// When this version became active, as recorded by the store
+    /// 
+    /// Read from stored configurations to set `Last-Modified`; never
+    /// serialized, since the store computes it on every write.
+    #[serde(default, skip_serializing)]
+    pub effective_at: Option<DateTime<Utc>>,
+    /// Identifier of this stored version, as assigned by the store
+    /// 
+    /// Read from stored configurations to record which version a device
+    /// fetched; never serialized, since the store assigns it on every write.
+    #[serde(rename = "id", default, skip_serializing)]
+    pub version: Option<String>,
+    /// Number of this stored version, counting from 1 for each device
+    /// 
+    /// Assigned by the store on every write and served so devices and
+    /// operators can display and compare versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_number: Option<u64>,
+    /// When the served version took effect
+    /// 
+    /// Only set on configurations served by `get_config`; never stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<DateTime<Utc>>,
+    /// Who stored this version, as recorded in the audit log
+    /// 
+    /// For a change held for approval, the caller who submitted it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_by: Option<String>,
+    /// Who approved this version, if it was held for approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+}
+
+/// Body of a request replacing the fleet-wide default configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DefaultConfigRequest {
+    /// Key-value pairs served to devices without a configuration of their own
+    pub config: HashMap<String, String>,
+    /// Optional time at which the new defaults become active
+    #[serde(default)]
+    pub apply_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of deleting or restoring a device's configuration
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigDeletion {
+    /// The device whose configuration was deleted or restored
+    pub device_id: String,
+    /// Number of stored versions deleted or restored
+    pub versions: usize,
+    /// When the configuration was deleted; absent once restored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 /// Error types that can occur during configuration validation
@@ -26,21 +113,47 @@ pub struct Config {
 pub enum ConfigError {
     /// Device ID is empty or invalid
     InvalidDeviceId,
+    /// Device ID is the reserved `_default`, which only the defaults endpoint may write
+    ReservedDeviceId,
     /// Configuration data is empty or invalid
     InvalidConfig,
+    /// Configuration has more keys than allowed (count, maximum)
+    TooManyKeys(usize, usize),
+    /// Key is empty or uses characters that are not allowed
+    InvalidKey(String),
+    /// Key is longer than allowed (key, maximum length)
+    KeyTooLong(String, usize),
+    /// Key is reserved
+    ReservedKey(String),
+    /// Value is longer than allowed (key, maximum length)
+    ValueTooLong(String, usize),
+    /// Value is rejected by the validator registered for its key (key, reason)
+    InvalidValue(String, String),
     /// Database operation error
     DatabaseError(String),
     /// Device configuration not found in database
     DeviceNotFound(String),
+    /// Approval is required, so the change must be submitted for review
+    ApprovalRequired,
 }
 
 impl std::fmt::Display for ConfigError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConfigError::InvalidDeviceId => write!(f, "Device ID cannot be empty"),
+            ConfigError::ReservedDeviceId => write!(f, "Device ID {} is reserved; use PUT /device-config/defaults", DEFAULT_CONFIG_ID),
             ConfigError::InvalidConfig => write!(f, "Configuration data cannot be empty"),
+            ConfigError::TooManyKeys(count, max) => {
+                write!(f, "Configuration has {} keys, at most {} are allowed", count, max)
+            }
+            ConfigError::InvalidKey(key) => write!(f, "Configuration key {:?} contains characters that are not allowed", key),
+            ConfigError::KeyTooLong(key, max) => write!(f, "Configuration key {} is longer than {} bytes", key, max),
+            ConfigError::ReservedKey(key) => write!(f, "Configuration key {} is reserved", key),
+            ConfigError::ValueTooLong(key, max) => write!(f, "Value of {} is longer than {} bytes", key, max),
+            ConfigError::InvalidValue(key, reason) => write!(f, "Invalid value for {}: {}", key, reason),
             ConfigError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             ConfigError::DeviceNotFound(msg) => write!(f, "Device configuration not found: {}", msg),
+            ConfigError::ApprovalRequired => write!(f, "Configuration changes must be submitted for approval"),
         }
     }
 }
@@ -63,6 +176,16 @@ impl Config {
         Config {
             device_id,
             config,
+            apply_at: None,
+            signature: None,
+            is_default: false,
+            firmware: None,
+            effective_at: None,
+            version: None,
+            version_number: None,
+            last_modified: None,
+            modified_by: None,
+            approved_by: None,
         }
     }
 
@@ -72,40 +195,189 @@ impl Config {
     /// validation fails.
     /// 
     /// # Arguments
-    /// * `device_id` - The device identifier (must not be empty)
+    /// * `device_id` - The device identifier (must not be empty nor the reserved `_default`)
     /// * `config` - The configuration parameters (must not be empty)
+    /// * `limits` - Key count, key and value length, key character and reserved key limits,
+    ///   and the per-key value validators
     /// 
     /// # Returns
     /// * `Result<Self, ConfigError>` - The validated configuration or an error
-    pub fn parse(device_id: String, config: HashMap<String, String>) -> Result<Self, ConfigError> {
+    pub fn parse(device_id: String, config: HashMap<String, String>, limits: &ConfigLimits) -> Result<Self, ConfigError> {
         // Validate device_id is not empty
         if device_id.trim().is_empty() {
             return Err(ConfigError::InvalidDeviceId);
         }
 
+        // The fleet-wide defaults are only written through `parse_defaults`
+        if device_id == DEFAULT_CONFIG_ID {
+            return Err(ConfigError::ReservedDeviceId);
+        }
+
+        Self::validate(device_id, config, limits)
+    }
+
+    /// Creates the fleet-wide default configuration with validation
+    /// 
+    /// Validates like `parse`, under the reserved device ID `_default`.
+    /// Only `update_defaults` may store the result.
+    /// 
+    /// # Arguments
+    /// * `config` - The default configuration parameters (must not be empty)
+    /// * `limits` - The limits and validators every stored configuration must respect
+    /// 
+    /// # Returns
+    /// * `Result<Self, ConfigError>` - The validated defaults or an error
+    pub fn parse_defaults(config: HashMap<String, String>, limits: &ConfigLimits) -> Result<Self, ConfigError> {
+        Self::validate(DEFAULT_CONFIG_ID.to_string(), config, limits)
+    }
+
+    /// Validates the configuration parameters shared by `parse` and `parse_defaults`
+    fn validate(device_id: String, config: HashMap<String, String>, limits: &ConfigLimits) -> Result<Self, ConfigError> {
+
         // Validate configuration data is not empty
         if config.is_empty() {
             return Err(ConfigError::InvalidConfig);
         }
 
         // Validate all configuration values are not empty
-        for (key, value) in &config {
+        for value in config.values() {
             if value.trim().is_empty() {
                 return Err(ConfigError::InvalidConfig);
             }
         }
 
+        // Validate the keys and values fit what devices can hold
+        limits.check(&config)?;
+
+        // Validate the values of keys with a registered validator
+        limits.validators.check(&config)?;
+
         // Create and return the validated configuration instance
         Ok(Config {
             device_id,
             config,
+            apply_at: None,
+            signature: None,
+            is_default: false,
+            firmware: None,
+            effective_at: None,
+            version: None,
+            version_number: None,
+            last_modified: None,
+            modified_by: None,
+            approved_by: None,
         })
     }
+
+    /// Schedules this configuration to become active at the given time
+    /// 
+    /// # Arguments
+    /// * `apply_at` - When the configuration should take effect (None for immediately)
+    /// 
+    /// # Returns
+    /// * `Self` - The configuration with its activation time set
+    pub fn scheduled(mut self, apply_at: Option<DateTime<Utc>>) -> Self {
+        self.apply_at = apply_at;
+        self
+    }
+
+    /// Returns true if this configuration is active at the given time
+    /// 
+    /// # Arguments
+    /// * `now` - The reference time to check against
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.apply_at.is_none_or(|apply_at| apply_at <= now)
+    }
+
+    /// Returns when a version stored at the given time becomes active
+    /// 
+    /// A version is never active before it is stored, so an `apply_at` in
+    /// the past takes effect when the version is stored; otherwise an older
+    /// version could be served in its place.
+    /// 
+    /// # Arguments
+    /// * `apply_at` - The scheduled activation time, if any
+    /// * `stored_at` - When the version is stored
+    /// 
+    /// # Returns
+    /// * `DateTime<Utc>` - The later of the two times
+    pub fn effective_at(apply_at: Option<DateTime<Utc>>, stored_at: DateTime<Utc>) -> DateTime<Utc> {
+        apply_at.map_or(stored_at, |apply_at| apply_at.max(stored_at))
+    }
+
+    /// Records who is storing this configuration
+    /// 
+    /// # Arguments
+    /// * `subject` - The audit subject of the caller
+    /// 
+    /// # Returns
+    /// * `Self` - The configuration with its author set
+    pub fn stored_by(mut self, subject: &str) -> Self {
+        self.modified_by = Some(subject.to_string());
+        self
+    }
+
+    /// Records who approved this configuration
+    /// 
+    /// # Arguments
+    /// * `subject` - The audit subject of the reviewer
+    /// 
+    /// # Returns
+    /// * `Self` - The configuration with its approver set
+    pub fn approved_by(mut self, subject: &str) -> Self {
+        self.approved_by = Some(subject.to_string());
+        self
+    }
+
+    /// Serves this fleet-wide default configuration to a specific device
+    /// 
+    /// The device ID is replaced so signatures and entity tags are computed
+    /// for the device that asked, and the configuration is flagged as a default.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device without a configuration of its own
+    /// 
+    /// # Returns
+    /// * `Self` - The configuration addressed to the device
+    pub fn as_default_for(mut self, device_id: &str) -> Self {
+        self.device_id = device_id.to_string();
+        self.is_default = true;
+        self
+    }
+}
+
+/// Computes the entity tag of a set of served configurations
+/// 
+/// The tag only depends on the device IDs, configuration values and
+/// assigned firmware, so re-storing an identical configuration keeps the
+/// same tag and polling devices are not sent a configuration they already
+/// have, while a new firmware assignment changes it.
+/// 
+/// # Arguments
+/// * `configs` - The configurations returned by `get_config`
+/// 
+/// # Returns
+/// * `String` - A 16 character hex tag, stable across builds and restarts
+pub fn config_etag(configs: &[Config]) -> String {
+    let canonical: Vec<String> = configs
+        .iter()
+        .map(|config| match &config.firmware {
+            Some(firmware) => format!(
+                "{}\n{}:{}",
+                canonical_json(&config.device_id, &config.config),
+                firmware.version,
+                firmware.sha256
+            ),
+            None => canonical_json(&config.device_id, &config.config),
+        })
+        .collect();
+    format!("{:016x}", fnv1a(canonical.join("\n").as_bytes()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::validators::ValidatorRegistry;
 
     #[test]
     fn test_config_new() {
@@ -119,13 +391,28 @@ mod tests {
         assert_eq!(config.config, config_data);
     }
 
+    #[test]
+    fn test_config_as_default_for() {
+        let mut config_data = HashMap::new();
+        config_data.insert("sampling_rate".to_string(), "1000".to_string());
+
+        let defaults = Config::new(DEFAULT_CONFIG_ID.to_string(), config_data.clone());
+        let served = defaults.as_default_for("new-device");
+
+        assert_eq!(served.device_id, "new-device");
+        assert_eq!(served.config, config_data);
+        assert!(served.is_default);
+        assert_eq!(serde_json::to_value(&served).unwrap()["is_default"], true);
+        assert!(serde_json::to_value(Config::new("d".to_string(), config_data)).unwrap().get("is_default").is_none());
+    }
+
     #[test]
     fn test_config_parse_valid() {
         let mut config_data = HashMap::new();
         config_data.insert("sampling_rate".to_string(), "1000".to_string());
         config_data.insert("threshold".to_string(), "25.5".to_string());
 
-        let result = Config::parse("test-device".to_string(), config_data.clone());
+        let result = Config::parse("test-device".to_string(), config_data.clone(), &ConfigLimits::default());
 
         assert!(result.is_ok());
         let config = result.unwrap();
@@ -138,7 +425,7 @@ mod tests {
         let mut config_data = HashMap::new();
         config_data.insert("sampling_rate".to_string(), "1000".to_string());
 
-        let result = Config::parse("".to_string(), config_data);
+        let result = Config::parse("".to_string(), config_data, &ConfigLimits::default());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -152,7 +439,7 @@ mod tests {
         let mut config_data = HashMap::new();
         config_data.insert("sampling_rate".to_string(), "1000".to_string());
 
-        let result = Config::parse("   ".to_string(), config_data);
+        let result = Config::parse("   ".to_string(), config_data, &ConfigLimits::default());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -165,7 +452,7 @@ mod tests {
     fn test_config_parse_empty_config() {
         let config_data = HashMap::new();
 
-        let result = Config::parse("test-device".to_string(), config_data);
+        let result = Config::parse("test-device".to_string(), config_data, &ConfigLimits::default());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -179,7 +466,7 @@ mod tests {
         let mut config_data = HashMap::new();
         config_data.insert("sampling_rate".to_string(), "".to_string());
 
-        let result = Config::parse("test-device".to_string(), config_data);
+        let result = Config::parse("test-device".to_string(), config_data, &ConfigLimits::default());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -193,7 +480,7 @@ mod tests {
         let mut config_data = HashMap::new();
         config_data.insert("sampling_rate".to_string(), "   ".to_string());
 
-        let result = Config::parse("test-device".to_string(), config_data);
+        let result = Config::parse("test-device".to_string(), config_data, &ConfigLimits::default());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -211,7 +498,7 @@ mod tests {
         config_data.insert("wifi_password".to_string(), "secret123".to_string());
         config_data.insert("mqtt_broker".to_string(), "mqtt.example.com".to_string());
 
-        let result = Config::parse("sensor-001".to_string(), config_data.clone());
+        let result = Config::parse("sensor-001".to_string(), config_data.clone(), &ConfigLimits::default());
 
         assert!(result.is_ok());
         let config = result.unwrap();
@@ -219,6 +506,72 @@ mod tests {
         assert_eq!(config.config, config_data);
     }
 
+    #[test]
+    fn test_config_parse_enforces_limits() {
+        let mut config_data = HashMap::new();
+        config_data.insert("LED".to_string(), "on".to_string());
+        config_data.insert("wifi ssid".to_string(), "MyNetwork".to_string());
+
+        let result = Config::parse("sensor-001".to_string(), config_data, &ConfigLimits::default());
+
+        match result.unwrap_err() {
+            ConfigError::InvalidKey(key) => assert_eq!(key, "wifi ssid"),
+            _ => panic!("Expected InvalidKey error"),
+        }
+    }
+
+    #[test]
+    fn test_config_parse_applies_validators() {
+        let mut config_data = HashMap::new();
+        config_data.insert("LED".to_string(), "on".to_string());
+        config_data.insert("interval".to_string(), "soon".to_string());
+
+        let result = Config::parse("sensor-001".to_string(), config_data.clone(), &ConfigLimits::default());
+        match result.unwrap_err() {
+            ConfigError::InvalidValue(key, _) => assert_eq!(key, "interval"),
+            _ => panic!("Expected InvalidValue error"),
+        }
+
+        // Registries can be replaced, e.g. to accept any value
+        let limits = ConfigLimits { validators: ValidatorRegistry::new(), ..ConfigLimits::default() };
+        assert!(Config::parse("sensor-001".to_string(), config_data, &limits).is_ok());
+    }
+
+    #[test]
+    fn test_config_apply_at_defaults_to_none() {
+        let json = r#"{"device_id": "sensor-001", "config": {"LED": "on"}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+
+        assert!(config.apply_at.is_none());
+        assert!(config.is_active_at(Utc::now()));
+    }
+
+    #[test]
+    fn test_config_scheduled_activation() {
+        let mut config_data = HashMap::new();
+        config_data.insert("LED".to_string(), "on".to_string());
+
+        let apply_at = Utc::now() + chrono::Duration::hours(1);
+        let config = Config::parse("sensor-001".to_string(), config_data, &ConfigLimits::default())
+            .unwrap()
+            .scheduled(Some(apply_at));
+
+        assert_eq!(config.apply_at, Some(apply_at));
+        assert!(!config.is_active_at(Utc::now()));
+        assert!(config.is_active_at(apply_at));
+    }
+
+    #[test]
+    fn test_config_effective_at_never_precedes_storage() {
+        let stored_at = Utc::now();
+        let earlier = stored_at - chrono::Duration::hours(1);
+        let later = stored_at + chrono::Duration::hours(1);
+
+        assert_eq!(Config::effective_at(None, stored_at), stored_at);
+        assert_eq!(Config::effective_at(Some(earlier), stored_at), stored_at);
+        assert_eq!(Config::effective_at(Some(later), stored_at), later);
+    }
+
     #[test]
     fn test_config_error_display() {
         let error = ConfigError::InvalidDeviceId;
@@ -233,4 +586,66 @@ mod tests {
         let error = ConfigError::DeviceNotFound("Device not found".to_string());
         assert_eq!(error.to_string(), "Device configuration not found: Device not found");
     }
+
+    #[test]
+    fn test_config_etag_tracks_values() {
+        let mut config_data = HashMap::new();
+        config_data.insert("LED".to_string(), "on".to_string());
+        let config = Config::new("test-device".to_string(), config_data.clone());
+        let same = Config::new("test-device".to_string(), config_data.clone())
+            .scheduled(Some(Utc::now()));
+
+        config_data.insert("LED".to_string(), "off".to_string());
+        let changed = Config::new("test-device".to_string(), config_data);
+
+        let etag = config_etag(std::slice::from_ref(&config));
+        assert_eq!(etag.len(), 16);
+        assert_eq!(etag, config_etag(&[same]));
+        assert_ne!(etag, config_etag(&[changed]));
+    }
+
+    #[test]
+    fn test_config_etag_tracks_firmware() {
+        let mut config_data = HashMap::new();
+        config_data.insert("LED".to_string(), "on".to_string());
+        let config = Config::new("test-device".to_string(), config_data);
+        let mut updating = config.clone();
+        updating.firmware = Some(FirmwareManifest {
+            version: "1.4.0".to_string(),
+            url: "https://firmware.example.com/1.4.0.bin".to_string(),
+            sha256: "a".repeat(64),
+            signature: "c2ln".to_string(),
+        });
+
+        let etag = config_etag(std::slice::from_ref(&config));
+        assert_ne!(etag, config_etag(std::slice::from_ref(&updating)));
+        assert!(serde_json::to_value(&config).unwrap().get("firmware").is_none());
+        assert_eq!(serde_json::to_value(&updating).unwrap()["firmware"]["version"], "1.4.0");
+    }
+    #[test]
+    fn test_config_deletion_omits_deleted_at_once_restored() {
+        let deleted = ConfigDeletion {
+            device_id: "test-device".to_string(),
+            versions: 2,
+            deleted_at: Some(Utc::now()),
+        };
+        let restored = ConfigDeletion { deleted_at: None, ..deleted.clone() };
+
+        assert!(serde_json::to_value(&deleted).unwrap().get("deleted_at").is_some());
+        let value = serde_json::to_value(&restored).unwrap();
+        assert!(value.get("deleted_at").is_none());
+        assert_eq!(value["versions"], 2);
+    }
+
+    #[test]
+    fn test_config_parse_reserved_device_id() {
+        let mut config_data = HashMap::new();
+        config_data.insert("LED".to_string(), "on".to_string());
+
+        let result = Config::parse(DEFAULT_CONFIG_ID.to_string(), config_data.clone(), &ConfigLimits::default());
+        assert!(matches!(result, Err(ConfigError::ReservedDeviceId)));
+
+        let defaults = Config::parse_defaults(config_data, &ConfigLimits::default()).unwrap();
+        assert_eq!(defaults.device_id, DEFAULT_CONFIG_ID);
+    }
 }
\ No newline at end of file
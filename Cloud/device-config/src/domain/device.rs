@@ -0,0 +1,442 @@
+// Configured Device Domain Model
+// 
+// This module defines the summary kept for every device that has a stored
+// configuration, the record of each device's last configuration fetch, the
+// configuration each device reports having applied, and the filtering and
+// paging used to list them, so clients can discover which devices exist,
+// whether they are polling and whether they run what they are served.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Number of devices returned per page when no page size is given
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Upper bound on the number of devices returned per page
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// Latest stored configuration of a device
+/// 
+/// One summary document is kept per device in the device index and
+/// replaced whenever a new configuration version is stored.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeviceSummary {
+    /// The device identifier (also the Cosmos DB document id)
+    pub id: String,
+    /// The device identifier
+    pub device_id: String,
+    /// Identifier of the most recently stored configuration version
+    pub version: String,
+    /// When the most recent version was stored
+    pub updated_at: DateTime<Utc>,
+    /// Number of the most recently stored version, counting from 1
+    /// 
+    /// Devices indexed before versions were numbered report 0.
+    #[serde(default)]
+    pub version_number: u64,
+    /// The most recently stored configuration, used for filtering
+    #[serde(default)]
+    pub config: HashMap<String, String>,
+    /// When the device's configuration was deleted, if it is
+    /// 
+    /// Deleted devices are not listed until their configuration is restored
+    /// or a new version is stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Entity tag of the stored document, as read from Cosmos DB, `None`
+    /// if the summary has not been stored yet
+    /// 
+    /// The summary is replaced only if it still has this tag, so two
+    /// versions stored at once never take the same number; never serialized.
+    #[serde(rename = "_etag", default, skip_serializing)]
+    pub etag: Option<String>,
+}
+
+impl DeviceSummary {
+    /// Creates the summary for a newly stored configuration version
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device identifier
+    /// * `version` - Identifier of the stored version
+    /// * `updated_at` - When the version was stored
+    /// * `config` - The stored configuration
+    /// 
+    /// # Returns
+    /// * `Self` - A new DeviceSummary instance
+    pub fn new(
+        device_id: String,
+        version: String,
+        updated_at: DateTime<Utc>,
+        config: HashMap<String, String>,
+    ) -> Self {
+        DeviceSummary {
+            id: device_id.clone(),
+            device_id,
+            version,
+            updated_at,
+            version_number: 0,
+            config,
+            deleted_at: None,
+            etag: None,
+        }
+    }
+
+    /// Builds the summary of a stored configuration version document
+    /// 
+    /// Used to index devices configured before the device index existed;
+    /// the summary is marked deleted if the version is.
+    /// 
+    /// # Arguments
+    /// * `document` - The stored version, as raw JSON
+    /// 
+    /// # Returns
+    /// * `Option<Self>` - The summary, or None if the document lacks its
+    ///   device ID, id or storage time
+    pub fn from_document(document: &serde_json::Value) -> Option<Self> {
+        let parse_time = |field: &str| {
+            document[field]
+                .as_str()
+                .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| time.with_timezone(&Utc))
+        };
+
+        let mut summary = DeviceSummary::new(
+            document["device_id"].as_str()?.to_string(),
+            document["id"].as_str()?.to_string(),
+            parse_time("timestamp")?,
+            serde_json::from_value(document["config"].clone()).unwrap_or_default(),
+        );
+        summary.version_number = document["version_number"].as_u64().unwrap_or(0);
+        if document["deleted"].as_bool() == Some(true) {
+            summary.deleted_at = parse_time("deleted_at").or(Some(summary.updated_at));
+        }
+        Some(summary)
+    }
+}
+
+/// Last configuration fetch of a device
+/// 
+/// One record is kept per device and replaced on every successful fetch,
+/// including `304 Not Modified` answers.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeviceFetch {
+    /// The device identifier (also the Cosmos DB document id)
+    pub id: String,
+    /// The device identifier (partition key)
+    pub device_id: String,
+    /// When the device fetched its configuration
+    pub fetched_at: DateTime<Utc>,
+    /// Client IP address of the fetch, if known
+    pub source_ip: Option<String>,
+    /// Identifier of the configuration version served, `None` if the
+    /// fleet-wide defaults were served
+    pub version: Option<String>,
+}
+
+impl DeviceFetch {
+    /// Creates the record of a fetch made now
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device identifier
+    /// * `source_ip` - Client IP address of the fetch, if known
+    /// * `version` - Identifier of the configuration version served
+    /// 
+    /// # Returns
+    /// * `Self` - A new DeviceFetch instance
+    pub fn new(device_id: String, source_ip: Option<String>, version: Option<String>) -> Self {
+        DeviceFetch {
+            id: device_id.clone(),
+            device_id,
+            fetched_at: Utc::now(),
+            source_ip,
+            version,
+        }
+    }
+}
+
+/// Body of a device's report of the configuration it applied
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportedConfigRequest {
+    /// Key-value pairs the device applied
+    pub config: HashMap<String, String>,
+    /// Number of the version the device applied, if it knows it
+    #[serde(default)]
+    pub version_number: Option<u64>,
+    /// When the device applied the configuration (time of the report if not provided)
+    #[serde(default)]
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// Configuration a device last reported having applied
+/// 
+/// One record is kept per device and replaced on every report, so it can
+/// be compared with the configuration the device is served.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReportedConfig {
+    /// The device identifier (also the Cosmos DB document id)
+    pub id: String,
+    /// The device identifier (partition key)
+    pub device_id: String,
+    /// Key-value pairs the device applied, secret values redacted
+    pub config: HashMap<String, String>,
+    /// Number of the version the device applied, `None` if it did not report one
+    #[serde(default)]
+    pub version_number: Option<u64>,
+    /// When the device applied the configuration
+    pub applied_at: DateTime<Utc>,
+}
+
+impl ReportedConfig {
+    /// Creates the record of a report made now
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device identifier
+    /// * `request` - The reported configuration
+    /// 
+    /// # Returns
+    /// * `Self` - A new ReportedConfig instance
+    pub fn new(device_id: String, request: ReportedConfigRequest) -> Self {
+        ReportedConfig {
+            id: device_id.clone(),
+            device_id,
+            config: request.config,
+            version_number: request.version_number,
+            applied_at: request.applied_at.unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+/// A device entry as returned by the device listing
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeviceListItem {
+    /// The device identifier
+    pub device_id: String,
+    /// Identifier of the most recently stored configuration version
+    pub version: String,
+    /// When the most recent version was stored
+    pub updated_at: DateTime<Utc>,
+    /// When the device last fetched its configuration, `None` if it never has
+    #[serde(default)]
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    /// Client IP address of the last fetch, if known
+    #[serde(default)]
+    pub last_fetch_ip: Option<String>,
+    /// True if the device has fetched its most recently stored version
+    #[serde(default)]
+    pub fetched_latest: bool,
+}
+
+/// A page of the device listing
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DevicePage {
+    /// Devices on this page, sorted by device id
+    pub devices: Vec<DeviceListItem>,
+    /// The 1-based page number
+    pub page: usize,
+    /// Maximum number of devices per page
+    pub page_size: usize,
+    /// Number of devices matching the filter across all pages
+    pub total: usize,
+}
+
+/// Key/value filter for the device listing
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceFilter {
+    /// Only list devices whose configuration has this key
+    pub key: Option<String>,
+    /// Only list devices whose configuration value for `key` equals this
+    pub value: Option<String>,
+}
+
+impl DeviceFilter {
+    /// Returns true if the device's configuration matches the filter
+    /// 
+    /// A value without a key matches any key holding that value.
+    pub fn matches(&self, config: &HashMap<String, String>) -> bool {
+        match (&self.key, &self.value) {
+            (None, None) => true,
+            (Some(key), None) => config.contains_key(key),
+            (Some(key), Some(value)) => config.get(key) == Some(value),
+            (None, Some(value)) => config.values().any(|v| v == value),
+        }
+    }
+}
+
+/// Filters, sorts and pages device summaries
+/// 
+/// Each listed device is annotated with its last fetch, if any.
+/// 
+/// # Arguments
+/// * `summaries` - Every known device summary
+/// * `fetches` - The last fetch of each device, by device id
+/// * `filter` - The key/value filter to apply
+/// * `page` - The 1-based page number (values below 1 are treated as 1)
+/// * `page_size` - Devices per page (clamped to 1..=MAX_PAGE_SIZE)
+/// 
+/// # Returns
+/// * `DevicePage` - The requested page
+pub fn paginate(
+    mut summaries: Vec<DeviceSummary>,
+    fetches: &HashMap<String, DeviceFetch>,
+    filter: &DeviceFilter,
+    page: usize,
+    page_size: usize,
+) -> DevicePage {
+    let page = page.max(1);
+    let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+
+    summaries.retain(|summary| filter.matches(&summary.config));
+    summaries.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+
+    let total = summaries.len();
+    let devices = summaries
+        .into_iter()
+        .skip((page - 1).saturating_mul(page_size))
+        .take(page_size)
+        .map(|summary| {
+            let fetch = fetches.get(&summary.device_id);
+            DeviceListItem {
+                fetched_latest: fetch.is_some_and(|fetch| fetch.version.as_ref() == Some(&summary.version)),
+                last_fetched_at: fetch.map(|fetch| fetch.fetched_at),
+                last_fetch_ip: fetch.and_then(|fetch| fetch.source_ip.clone()),
+                device_id: summary.device_id,
+                version: summary.version,
+                updated_at: summary.updated_at,
+            }
+        })
+        .collect();
+
+    DevicePage { devices, page, page_size, total }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(device_id: &str, pairs: &[(&str, &str)]) -> DeviceSummary {
+        let config = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        DeviceSummary::new(device_id.to_string(), format!("{}-v1", device_id), Utc::now(), config)
+    }
+
+    fn fleet() -> Vec<DeviceSummary> {
+        vec![
+            summary("sensor-003", &[("LED", "on")]),
+            summary("sensor-001", &[("LED", "off"), ("mode", "eco")]),
+            summary("sensor-002", &[("LED", "on"), ("mode", "eco")]),
+        ]
+    }
+
+    #[test]
+    fn test_summary_etag_is_read_but_not_written() {
+        let mut stored = serde_json::to_value(summary("sensor-001", &[("LED", "on")])).unwrap();
+        stored["_etag"] = serde_json::Value::from("\"0000-0002\"");
+
+        let read: DeviceSummary = serde_json::from_value(stored).unwrap();
+        assert_eq!(read.etag.as_deref(), Some("\"0000-0002\""));
+        assert!(serde_json::to_value(&read).unwrap().get("_etag").is_none());
+    }
+
+    #[test]
+    fn test_summary_from_document() {
+        let mut document = serde_json::json!({
+            "id": "\"sensor-001\"-2025-01-01T00:00:00+00:00",
+            "device_id": "sensor-001",
+            "config": { "LED": "on" },
+            "timestamp": "2025-01-01T00:00:00+00:00",
+            "version_number": 4
+        });
+
+        let summary = DeviceSummary::from_document(&document).unwrap();
+        assert_eq!(summary.id, "sensor-001");
+        assert_eq!(summary.version, "\"sensor-001\"-2025-01-01T00:00:00+00:00");
+        assert_eq!(summary.version_number, 4);
+        assert_eq!(summary.config.get("LED").map(String::as_str), Some("on"));
+        assert_eq!(summary.deleted_at, None);
+
+        document["deleted"] = serde_json::Value::Bool(true);
+        document["deleted_at"] = serde_json::Value::from("2025-02-01T00:00:00.000000Z");
+        let deleted = DeviceSummary::from_document(&document).unwrap();
+        assert_eq!(deleted.deleted_at.map(|time| time.to_rfc3339()), Some("2025-02-01T00:00:00+00:00".to_string()));
+
+        document["timestamp"] = serde_json::Value::Null;
+        assert_eq!(DeviceSummary::from_document(&document), None);
+    }
+
+    #[test]
+    fn test_reported_config_defaults_applied_at() {
+        let request: ReportedConfigRequest = serde_json::from_value(serde_json::json!({
+            "config": { "LED": "on" },
+            "version_number": 3
+        }))
+        .unwrap();
+
+        let before = Utc::now();
+        let reported = ReportedConfig::new("sensor-001".to_string(), request);
+        assert_eq!(reported.id, "sensor-001");
+        assert_eq!(reported.version_number, Some(3));
+        assert!(reported.applied_at >= before);
+
+        let request: ReportedConfigRequest = serde_json::from_value(serde_json::json!({
+            "config": {},
+            "applied_at": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        let reported = ReportedConfig::new("sensor-001".to_string(), request);
+        assert_eq!(reported.version_number, None);
+        assert_eq!(reported.applied_at.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_paginate_sorts_and_pages() {
+        let page = paginate(fleet(), &HashMap::new(), &DeviceFilter::default(), 2, 2);
+
+        assert_eq!(page.total, 3);
+        assert_eq!(page.devices.len(), 1);
+        assert_eq!(page.devices[0].device_id, "sensor-003");
+    }
+
+    #[test]
+    fn test_paginate_filters_by_key_and_value() {
+        let filter = DeviceFilter { key: Some("LED".to_string()), value: Some("on".to_string()) };
+        let page = paginate(fleet(), &HashMap::new(), &filter, 1, 50);
+
+        let ids: Vec<&str> = page.devices.iter().map(|d| d.device_id.as_str()).collect();
+        assert_eq!(ids, vec!["sensor-002", "sensor-003"]);
+
+        let filter = DeviceFilter { key: Some("mode".to_string()), value: None };
+        assert_eq!(paginate(fleet(), &HashMap::new(), &filter, 1, 50).total, 2);
+    }
+
+    #[test]
+    fn test_paginate_clamps_parameters() {
+        let page = paginate(fleet(), &HashMap::new(), &DeviceFilter::default(), 0, 0);
+
+        assert_eq!(page.page, 1);
+        assert_eq!(page.page_size, 1);
+        assert_eq!(page.devices[0].device_id, "sensor-001");
+    }
+
+    #[test]
+    fn test_paginate_reports_last_fetch() {
+        let fetches: HashMap<String, DeviceFetch> = [
+            DeviceFetch::new("sensor-001".to_string(), Some("203.0.113.7".to_string()), Some("sensor-001-v1".to_string())),
+            DeviceFetch::new("sensor-002".to_string(), None, Some("sensor-002-v0".to_string())),
+        ]
+        .into_iter()
+        .map(|fetch| (fetch.device_id.clone(), fetch))
+        .collect();
+
+        let page = paginate(fleet(), &fetches, &DeviceFilter::default(), 1, 50);
+
+        let current = &page.devices[0];
+        assert!(current.fetched_latest);
+        assert_eq!(current.last_fetch_ip.as_deref(), Some("203.0.113.7"));
+
+        // sensor-002 last fetched an older version, sensor-003 never fetched
+        assert!(!page.devices[1].fetched_latest);
+        assert!(page.devices[1].last_fetched_at.is_some());
+        assert!(!page.devices[2].fetched_latest);
+        assert!(page.devices[2].last_fetched_at.is_none());
+    }
+}
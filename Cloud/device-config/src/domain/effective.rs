@@ -0,0 +1,262 @@
+// Effective Configuration Domain Model
+//
+// This module defines the resolved view of a device's configuration: the
+// values the device is served right now, where each value came from
+// (fleet defaults, the device's own configuration, a rollout or a
+// temporary override), and what is about to change. It answers "why is
+// this device doing X" without reading the audit log by hand.
+
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::audit::ConfigChange;
+use crate::domain::firmware::FirmwareManifest;
+use crate::domain::overrides::{ConfigOverride, OverrideStatus};
+use crate::domain::rollout::{Rollout, RolloutStatus};
+
+/// Where an effective configuration value came from
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueSource {
+    /// The fleet-wide defaults; the device has no configuration of its own
+    Defaults,
+    /// The device's own configuration, as last updated
+    Device,
+    /// A rollout that reached the device and set this value
+    Rollout,
+    /// An active temporary override
+    Override,
+}
+
+/// A resolved configuration value and its provenance
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EffectiveValue {
+    /// The value served to the device, redacted for secret keys
+    pub value: String,
+    /// Where the value came from
+    pub source: ValueSource,
+    /// Id of the rollout or override that set the value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    /// When the override setting the value expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A rollout whose device group includes the device
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RolloutMembership {
+    /// Id of the rollout
+    pub id: String,
+    /// Human-readable name of the rollout
+    pub name: String,
+    /// Current state of the rollout
+    pub status: RolloutStatus,
+    /// Whether the device is one of the rollout's canary devices
+    pub canary: bool,
+    /// Whether the rollout's configuration was applied to the device and not reverted
+    pub applied: bool,
+}
+
+/// A stored version that becomes active later
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScheduledVersion {
+    /// Identifier of the stored version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// When the version becomes active
+    pub apply_at: DateTime<Utc>,
+    /// Keys the version changes relative to the one before it, secret values redacted
+    pub changes: Vec<ConfigChange>,
+}
+
+/// The fully resolved configuration of a device
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EffectiveConfig {
+    /// The device the configuration was resolved for
+    pub device_id: String,
+    /// True if the device is served the fleet-wide defaults
+    #[serde(default)]
+    pub is_default: bool,
+    /// Identifier of the active stored version, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// When the active version took effect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_at: Option<DateTime<Utc>>,
+    /// The served values by key, with their provenance
+    pub config: BTreeMap<String, EffectiveValue>,
+    /// Rollouts targeting the device, newest first
+    #[serde(default)]
+    pub rollouts: Vec<RolloutMembership>,
+    /// Versions scheduled to become active, soonest first
+    #[serde(default)]
+    pub scheduled: Vec<ScheduledVersion>,
+    /// Firmware the device should be running, if a version is assigned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firmware: Option<FirmwareManifest>,
+}
+
+impl RolloutMembership {
+    /// Describes a device's membership of a rollout
+    ///
+    /// Canary devices receive the configuration when the rollout is created
+    /// and the rest of the group when it is promoted; aborting restores the
+    /// canary devices' previous configuration.
+    ///
+    /// # Arguments
+    /// * `rollout` - The rollout
+    /// * `device_id` - The device to describe
+    ///
+    /// # Returns
+    /// * `Option<Self>` - The membership, or None if the device is not in the rollout's group
+    pub fn of(rollout: &Rollout, device_id: &str) -> Option<Self> {
+        if !rollout.devices.iter().any(|device| device == device_id) {
+            return None;
+        }
+
+        let canary = rollout.canary_devices.iter().any(|device| device == device_id);
+        let applied = match rollout.status {
+            RolloutStatus::Canary | RolloutStatus::Paused => canary,
+            RolloutStatus::Promoted => true,
+            RolloutStatus::Aborted => false,
+        };
+
+        Some(RolloutMembership {
+            id: rollout.id.clone(),
+            name: rollout.name.clone(),
+            status: rollout.status,
+            canary,
+            applied,
+        })
+    }
+}
+
+/// Attributes each served value to where it came from
+///
+/// A value is attributed to the first of these that set it to its current
+/// value: an active override, then an applied rollout (newest first), and
+/// otherwise the defaults or the device's own configuration. Values changed
+/// since an override or rollout set them are attributed to the device.
+///
+/// # Arguments
+/// * `current` - The served configuration, in plaintext
+/// * `is_default` - Whether the configuration is the fleet-wide defaults
+/// * `overrides` - The device's overrides, values in plaintext
+/// * `rollouts` - Rollouts applied to the device, newest first, configuration in plaintext
+///
+/// # Returns
+/// * `BTreeMap<String, EffectiveValue>` - The served values by key, with their provenance
+pub fn resolve_sources(
+    current: &HashMap<String, String>,
+    is_default: bool,
+    overrides: &[ConfigOverride],
+    rollouts: &[Rollout],
+) -> BTreeMap<String, EffectiveValue> {
+    current
+        .iter()
+        .map(|(key, value)| {
+            let sets = |values: &HashMap<String, String>| values.get(key) == Some(value);
+
+            let resolved = if let Some(item) = overrides
+                .iter()
+                .find(|item| item.status == OverrideStatus::Active && sets(&item.values))
+            {
+                EffectiveValue {
+                    value: value.clone(),
+                    source: ValueSource::Override,
+                    source_id: Some(item.id.clone()),
+                    expires_at: Some(item.expires_at),
+                }
+            } else if let Some(rollout) = rollouts.iter().find(|rollout| sets(&rollout.config)) {
+                EffectiveValue {
+                    value: value.clone(),
+                    source: ValueSource::Rollout,
+                    source_id: Some(rollout.id.clone()),
+                    expires_at: None,
+                }
+            } else {
+                EffectiveValue {
+                    value: value.clone(),
+                    source: if is_default { ValueSource::Defaults } else { ValueSource::Device },
+                    source_id: None,
+                    expires_at: None,
+                }
+            };
+
+            (key.clone(), resolved)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::domain::limits::ConfigLimits;
+    use crate::domain::rollout::{CanarySelection, RolloutAction, RolloutRequest};
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn rollout(id: &str, values: &[(&str, &str)]) -> Rollout {
+        let request = RolloutRequest {
+            name: format!("rollout-{}", id),
+            devices: vec!["sensor-001".to_string(), "sensor-002".to_string()],
+            canary: CanarySelection::Devices(vec!["sensor-001".to_string()]),
+            config: config(values),
+        };
+        Rollout::parse(id.to_string(), request, &ConfigLimits::default()).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_sources_precedence() {
+        let current = config(&[("LED", "on"), ("interval", "30"), ("threshold", "25"), ("mode", "eco")]);
+        let item = ConfigOverride::new(
+            "sensor-001".to_string(),
+            config(&[("LED", "on"), ("mode", "boost")]),
+            config(&[("LED", "off")]),
+            "alice".to_string(),
+            Duration::hours(1),
+        );
+        let rollouts = [rollout("r2", &[("LED", "on"), ("interval", "30")]), rollout("r1", &[("threshold", "25")])];
+
+        let resolved = resolve_sources(&current, false, std::slice::from_ref(&item), &rollouts);
+
+        assert_eq!(resolved["LED"].source, ValueSource::Override);
+        assert_eq!(resolved["LED"].source_id.as_deref(), Some(item.id.as_str()));
+        assert_eq!(resolved["LED"].expires_at, Some(item.expires_at));
+        assert_eq!(resolved["interval"].source, ValueSource::Rollout);
+        assert_eq!(resolved["interval"].source_id.as_deref(), Some("r2"));
+        assert_eq!(resolved["threshold"].source_id.as_deref(), Some("r1"));
+        // The override set a different value, so the device changed it since
+        assert_eq!(resolved["mode"].source, ValueSource::Device);
+    }
+
+    #[test]
+    fn test_resolve_sources_defaults() {
+        let current = config(&[("LED", "off")]);
+
+        let resolved = resolve_sources(&current, true, &[], &[]);
+
+        assert_eq!(resolved["LED"].value, "off");
+        assert_eq!(resolved["LED"].source, ValueSource::Defaults);
+        assert_eq!(resolved["LED"].source_id, None);
+    }
+
+    #[test]
+    fn test_rollout_membership() {
+        let mut item = rollout("r1", &[("LED", "on")]);
+
+        let canary = RolloutMembership::of(&item, "sensor-001").unwrap();
+        assert!(canary.canary && canary.applied);
+        let waiting = RolloutMembership::of(&item, "sensor-002").unwrap();
+        assert!(!waiting.canary && !waiting.applied);
+        assert_eq!(RolloutMembership::of(&item, "sensor-003"), None);
+
+        item.apply(RolloutAction::Promote).unwrap();
+        assert!(RolloutMembership::of(&item, "sensor-002").unwrap().applied);
+    }
+}
@@ -6,6 +6,12 @@
 use std::fmt;
 use rocket::http::Status;
 use crate::domain::config::ConfigError;
+use crate::domain::rollout::RolloutError;
+use crate::domain::auth::AuthError;
+use crate::domain::bulk::BulkError;
+use crate::domain::firmware::FirmwareError;
+use crate::domain::overrides::OverrideError;
+use crate::domain::approval::ApprovalError;
 
 /// Converts configuration errors to appropriate HTTP status codes
 /// 
@@ -13,16 +19,27 @@ use crate::domain::config::ConfigError;
 /// HTTP status codes for proper REST API error handling:
 /// - Validation errors -> 400 Bad Request
 /// - Not found errors -> 404 Not Found
+/// - Changes that must be submitted for approval -> 409 Conflict
 /// - Database errors -> 500 Internal Server Error
 impl From<ConfigError> for rocket::http::Status {
     fn from(error: ConfigError) -> Self {
         match error {
             // Client errors (4xx) - invalid request data
             ConfigError::InvalidDeviceId | 
-            ConfigError::InvalidConfig => Status::BadRequest,
+            ConfigError::ReservedDeviceId |
+            ConfigError::InvalidConfig |
+            ConfigError::TooManyKeys(..) |
+            ConfigError::InvalidKey(_) |
+            ConfigError::KeyTooLong(..) |
+            ConfigError::ReservedKey(_) |
+            ConfigError::ValueTooLong(..) |
+            ConfigError::InvalidValue(..) => Status::BadRequest,
             
             // Not found errors (4xx) - resource doesn't exist
             ConfigError::DeviceNotFound(_) => Status::NotFound,
+
+            // Conflict errors (4xx) - writes are held for approval
+            ConfigError::ApprovalRequired => Status::Conflict,
             
             // Server errors (5xx) - internal processing failure
             ConfigError::DatabaseError(_) => Status::InternalServerError,
@@ -30,3 +47,166 @@ impl From<ConfigError> for rocket::http::Status {
     }
 }
 
+/// Converts rollout errors to appropriate HTTP status codes
+/// 
+/// - Validation errors -> 400 Bad Request
+/// - Not found errors -> 404 Not Found
+/// - Invalid state transitions and rollouts while approval is required -> 409 Conflict
+/// - Database errors -> 500 Internal Server Error
+impl From<RolloutError> for rocket::http::Status {
+    fn from(error: RolloutError) -> Self {
+        match error {
+            // Client errors (4xx) - invalid request data
+            RolloutError::InvalidName |
+            RolloutError::InvalidDeviceGroup |
+            RolloutError::InvalidPercentage(_) |
+            RolloutError::UnknownCanaryDevice(_) |
+            RolloutError::DuplicateDevice(_) |
+            RolloutError::InvalidConfig |
+            RolloutError::ConfigRejected(_) => Status::BadRequest,
+
+            // Not found errors (4xx) - resource doesn't exist
+            RolloutError::RolloutNotFound(_) => Status::NotFound,
+
+            // Conflict errors (4xx) - action not allowed in the current state
+            RolloutError::InvalidTransition(_) |
+            RolloutError::ConcurrentUpdate(_) |
+            RolloutError::ApprovalRequired => Status::Conflict,
+
+            // Server errors (5xx) - internal processing failure
+            RolloutError::DatabaseError(_) => Status::InternalServerError,
+        }
+    }
+}
+
+/// Converts bulk export/import errors to appropriate HTTP status codes
+/// 
+/// - Malformed requests and bundles -> 400 Bad Request
+/// - Oversized bundles -> 413 Payload Too Large
+/// - Imports while approval is required -> 409 Conflict
+/// - Database errors -> 500 Internal Server Error
+impl From<BulkError> for rocket::http::Status {
+    fn from(error: BulkError) -> Self {
+        match error {
+            // Client errors (4xx) - invalid request data
+            BulkError::InvalidFormat(_) |
+            BulkError::InvalidConflictPolicy(_) |
+            BulkError::InvalidDocument(_) |
+            BulkError::UnsupportedVersion(_) => Status::BadRequest,
+
+            BulkError::DocumentTooLarge => Status::PayloadTooLarge,
+
+            // Conflict errors (4xx) - writes are held for approval
+            BulkError::ApprovalRequired => Status::Conflict,
+
+            // Server errors (5xx) - internal processing failure
+            BulkError::DatabaseError(_) => Status::InternalServerError,
+        }
+    }
+}
+
+/// Converts firmware errors to appropriate HTTP status codes
+/// 
+/// - Validation errors -> 400 Bad Request
+/// - Not found errors -> 404 Not Found
+/// - Re-registering an existing version -> 409 Conflict
+/// - Database errors -> 500 Internal Server Error
+impl From<FirmwareError> for rocket::http::Status {
+    fn from(error: FirmwareError) -> Self {
+        match error {
+            // Client errors (4xx) - invalid request data
+            FirmwareError::InvalidVersion(_) |
+            FirmwareError::InvalidUrl(_) |
+            FirmwareError::InvalidSha256 |
+            FirmwareError::InvalidSignature |
+            FirmwareError::InvalidDeviceGroup => Status::BadRequest,
+
+            // Not found errors (4xx) - resource doesn't exist
+            FirmwareError::ImageNotFound(_) |
+            FirmwareError::AssignmentNotFound(_) => Status::NotFound,
+
+            // Conflict errors (4xx) - images are immutable
+            FirmwareError::ImageExists(_) => Status::Conflict,
+
+            // Server errors (5xx) - internal processing failure
+            FirmwareError::DatabaseError(_) => Status::InternalServerError,
+        }
+    }
+}
+
+/// Converts override errors to appropriate HTTP status codes
+/// 
+/// - Invalid requests -> 400 Bad Request
+/// - Unknown overrides or devices without a configuration -> 404 Not Found
+/// - Keys already overridden, overrides already reverted or overrides while
+///   approval is required -> 409 Conflict
+/// - Database errors -> 500 Internal Server Error
+impl From<OverrideError> for rocket::http::Status {
+    fn from(error: OverrideError) -> Self {
+        match error {
+            // Client errors (4xx) - invalid request data
+            OverrideError::InvalidDeviceId |
+            OverrideError::EmptyOverride |
+            OverrideError::InvalidTtl(_) |
+            OverrideError::ConfigRejected(_) => Status::BadRequest,
+
+            // Not found errors (4xx) - resource doesn't exist
+            OverrideError::DeviceNotFound(_) |
+            OverrideError::OverrideNotFound(_) => Status::NotFound,
+
+            // Conflict errors (4xx) - overlapping or finished overrides
+            OverrideError::KeyOverridden(_) |
+            OverrideError::NotActive(_) |
+            OverrideError::ApprovalRequired => Status::Conflict,
+
+            // Server errors (5xx) - internal processing failure
+            OverrideError::DatabaseError(_) => Status::InternalServerError,
+        }
+    }
+}
+
+/// Converts approval errors to appropriate HTTP status codes
+/// 
+/// - Invalid filters or configurations -> 400 Bad Request
+/// - Reviewing one's own change -> 403 Forbidden
+/// - Unknown changes -> 404 Not Found
+/// - Changes already reviewed or submitted against a replaced configuration -> 409 Conflict
+/// - Database errors -> 500 Internal Server Error
+impl From<ApprovalError> for rocket::http::Status {
+    fn from(error: ApprovalError) -> Self {
+        match error {
+            // Client errors (4xx) - invalid request data
+            ApprovalError::InvalidStatus(_) |
+            ApprovalError::ConfigRejected(_) => Status::BadRequest,
+
+            // Forbidden errors (4xx) - four-eyes rule
+            ApprovalError::SelfReview => Status::Forbidden,
+
+            // Not found errors (4xx) - resource doesn't exist
+            ApprovalError::ApprovalNotFound(_) => Status::NotFound,
+
+            // Conflict errors (4xx) - each change is reviewed once
+            ApprovalError::NotPending(_) |
+            ApprovalError::Outdated(_) => Status::Conflict,
+
+            // Server errors (5xx) - internal processing failure
+            ApprovalError::DatabaseError(_) => Status::InternalServerError,
+        }
+    }
+}
+
+/// Converts authorization errors to appropriate HTTP status codes
+/// 
+/// - Missing or invalid credentials -> 401 Unauthorized
+/// - Authenticated caller without the required role -> 403 Forbidden
+impl From<&AuthError> for rocket::http::Status {
+    fn from(error: &AuthError) -> Self {
+        match error {
+            AuthError::MissingCredentials |
+            AuthError::InvalidToken(_) |
+            AuthError::InvalidApiKey => Status::Unauthorized,
+
+            AuthError::InsufficientRole { .. } => Status::Forbidden,
+        }
+    }
+}
@@ -0,0 +1,163 @@
+// Configuration Change Event Domain Model
+//
+// This module defines the machine-readable events published whenever a
+// device configuration changes, so external systems (CMDB, ITSM) can track
+// configuration changes without polling the audit log. Events are delivered
+// as-is to webhooks, or wrapped in the Event Grid event schema when
+// published to an Event Grid topic.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::audit::{AuditAction, AuditEntry, ConfigChange};
+
+/// Event type reported for every configuration change
+pub const CONFIG_CHANGED_EVENT: &str = "device_config.changed";
+
+/// Event type reported when a configuration change is submitted for approval
+pub const APPROVAL_REQUESTED_EVENT: &str = "device_config.approval_requested";
+
+/// Event type reported when a pending configuration change is rejected
+pub const APPROVAL_REJECTED_EVENT: &str = "device_config.approval_rejected";
+
+/// Version of the event data schema reported to Event Grid
+pub const EVENT_DATA_VERSION: &str = "1.0";
+
+/// A configuration change event, as delivered to webhooks
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigChangeEvent {
+    /// Unique identifier of the event (the audit entry id)
+    pub id: String,
+    /// "device_config.changed", or an approval event type
+    pub event_type: String,
+    /// The device whose configuration changed
+    pub device_id: String,
+    /// Identifier of the stored configuration version, or of the pending
+    /// change for approval events
+    pub version: String,
+    /// What kind of mutation caused the change
+    pub action: AuditAction,
+    /// Names of the configuration keys that changed, sorted
+    pub changed_keys: Vec<String>,
+    /// Old and new values of the changed keys
+    pub changes: Vec<ConfigChange>,
+    /// Who made the change
+    pub actor: String,
+    /// Rollout that caused the change, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout_id: Option<String>,
+    /// When the change was made
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ConfigChangeEvent {
+    /// Builds the event for a device configuration audit entry
+    ///
+    /// # Arguments
+    /// * `entry` - The audit entry recorded for the change
+    /// * `version` - Identifier of the stored configuration version
+    ///
+    /// # Returns
+    /// * `Self` - A new ConfigChangeEvent instance
+    pub fn from_audit(entry: &AuditEntry, version: String) -> Self {
+        ConfigChangeEvent {
+            id: entry.id.clone(),
+            event_type: CONFIG_CHANGED_EVENT.to_string(),
+            device_id: entry.target.clone(),
+            version,
+            action: entry.action,
+            changed_keys: entry.changes.iter().map(|change| change.key.clone()).collect(),
+            changes: entry.changes.clone(),
+            actor: entry.actor.subject.clone(),
+            rollout_id: entry.rollout_id.clone(),
+            timestamp: entry.timestamp,
+        }
+    }
+
+    /// Replaces the event type, for events about pending changes
+    pub fn with_type(mut self, event_type: &str) -> Self {
+        self.event_type = event_type.to_string();
+        self
+    }
+}
+
+/// A configuration change event in the Event Grid event schema
+///
+/// Event Grid topics accept a JSON array of these envelopes; the change
+/// event itself is carried in `data`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventGridEvent {
+    /// Unique identifier of the event (same as the change event id)
+    pub id: String,
+    /// Resource path of the change, used for subscription filtering
+    pub subject: String,
+    /// Same as the change event's type
+    pub event_type: String,
+    /// When the change was made
+    pub event_time: DateTime<Utc>,
+    /// The configuration change event
+    pub data: ConfigChangeEvent,
+    /// Version of the `data` schema
+    pub data_version: String,
+}
+
+impl From<ConfigChangeEvent> for EventGridEvent {
+    fn from(event: ConfigChangeEvent) -> Self {
+        EventGridEvent {
+            id: event.id.clone(),
+            subject: format!("devices/{}/config", event.device_id),
+            event_type: event.event_type.clone(),
+            event_time: event.timestamp,
+            data: event,
+            data_version: EVENT_DATA_VERSION.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::audit::AuditActor;
+
+    #[test]
+    fn test_event_from_audit_entry() {
+        let actor = AuditActor {
+            subject: "alice".to_string(),
+            source_ip: Some("10.0.0.1".to_string()),
+            user_agent: None,
+        };
+        let changes = vec![ConfigChange {
+            key: "LED".to_string(),
+            old_value: Some("off".to_string()),
+            new_value: Some("on".to_string()),
+        }];
+        let entry = AuditEntry::new("sensor-001".to_string(), AuditAction::RolloutPromote, actor, changes)
+            .for_rollout("rollout-1");
+
+        let event = ConfigChangeEvent::from_audit(&entry, "v1".to_string());
+
+        assert_eq!(event.id, entry.id);
+        assert_eq!(event.event_type, CONFIG_CHANGED_EVENT);
+        assert_eq!(event.device_id, "sensor-001");
+        assert_eq!(event.version, "v1");
+        assert_eq!(event.changed_keys, vec!["LED".to_string()]);
+        assert_eq!(event.actor, "alice");
+        assert_eq!(event.rollout_id.as_deref(), Some("rollout-1"));
+    }
+
+    #[test]
+    fn test_event_grid_envelope() {
+        let actor = AuditActor { subject: "alice".to_string(), source_ip: None, user_agent: None };
+        let entry = AuditEntry::new("sensor-001".to_string(), AuditAction::ConfigUpdate, actor, Vec::new());
+        let event = ConfigChangeEvent::from_audit(&entry, "v2".to_string());
+
+        let envelope = serde_json::to_value(EventGridEvent::from(event)).unwrap();
+
+        assert_eq!(envelope["id"], entry.id.as_str());
+        assert_eq!(envelope["subject"], "devices/sensor-001/config");
+        assert_eq!(envelope["eventType"], CONFIG_CHANGED_EVENT);
+        assert_eq!(envelope["dataVersion"], EVENT_DATA_VERSION);
+        assert_eq!(envelope["data"]["version"], "v2");
+    }
+}
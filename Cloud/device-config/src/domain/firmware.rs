@@ -0,0 +1,286 @@
+// Firmware Update Domain Model
+//
+// This module defines the firmware images registered for over-the-air
+// updates and the target version assigned to each device. The service
+// only distributes the manifest (where to download an image and how to
+// verify it); devices download, verify and install the image themselves.
+
+use chrono::{DateTime, Utc};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+/// Longest accepted firmware version string
+pub const MAX_VERSION_LEN: usize = 32;
+
+/// Length of a hex encoded SHA-256 digest
+const SHA256_HEX_LEN: usize = 64;
+
+/// Length of a decoded Ed25519 signature in bytes
+const SIGNATURE_LEN: usize = 64;
+
+/// Body of a request registering a firmware image
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FirmwareImageRequest {
+    /// Version of the image, e.g. "1.4.0"
+    pub version: String,
+    /// Where devices download the image from
+    pub url: String,
+    /// Hex encoded SHA-256 digest of the image
+    pub sha256: String,
+    /// Base64 encoded Ed25519 signature of the image
+    pub signature: String,
+}
+
+/// A registered firmware image
+///
+/// Images are immutable once registered; publish a new version instead of
+/// changing an existing one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FirmwareImage {
+    /// The version (also the Cosmos DB document id and partition key)
+    pub id: String,
+    /// Version of the image
+    pub version: String,
+    /// Where devices download the image from
+    pub url: String,
+    /// Hex encoded SHA-256 digest of the image, lowercase
+    pub sha256: String,
+    /// Base64 encoded Ed25519 signature of the image
+    pub signature: String,
+    /// When the image was registered
+    pub created_at: DateTime<Utc>,
+}
+
+/// Firmware update instructions served to a device alongside its configuration
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FirmwareManifest {
+    /// Version the device should be running
+    pub version: String,
+    /// Where to download the image from
+    pub url: String,
+    /// Hex encoded SHA-256 digest to check the download against
+    pub sha256: String,
+    /// Base64 encoded Ed25519 signature to verify before installing
+    pub signature: String,
+}
+
+/// Body of a request assigning a firmware version to a group of devices
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FirmwareAssignmentRequest {
+    /// The registered version to install
+    pub version: String,
+    /// The devices to update; `_default` sets the fleet-wide target
+    pub devices: Vec<String>,
+}
+
+/// Target firmware version of a device
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FirmwareAssignment {
+    /// The device identifier (also the Cosmos DB document id)
+    pub id: String,
+    /// The device identifier, or `_default` for the fleet-wide target (partition key)
+    pub device_id: String,
+    /// The version the device should be running
+    pub version: String,
+    /// When the version was assigned
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// Error types that can occur while managing firmware images and assignments
+#[derive(Debug, Serialize)]
+pub enum FirmwareError {
+    /// Version is empty, too long or uses characters that are not allowed
+    InvalidVersion(String),
+    /// Download URL is not an http(s) URL
+    InvalidUrl(String),
+    /// Digest is not a hex encoded SHA-256 digest
+    InvalidSha256,
+    /// Signature is not a base64 encoded Ed25519 signature
+    InvalidSignature,
+    /// Assignment lists no devices, or a blank device ID
+    InvalidDeviceGroup,
+    /// An image with this version is already registered
+    ImageExists(String),
+    /// No image with this version is registered
+    ImageNotFound(String),
+    /// No firmware version is assigned to the device or the fleet
+    AssignmentNotFound(String),
+    /// Database operation error
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for FirmwareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FirmwareError::InvalidVersion(version) => write!(f, "Invalid firmware version: {:?}", version),
+            FirmwareError::InvalidUrl(url) => write!(f, "Firmware URL must be an http(s) URL, got {:?}", url),
+            FirmwareError::InvalidSha256 => write!(f, "Firmware digest must be 64 hex characters"),
+            FirmwareError::InvalidSignature => write!(f, "Firmware signature must be a base64 encoded Ed25519 signature"),
+            FirmwareError::InvalidDeviceGroup => write!(f, "Firmware assignment device group cannot be empty"),
+            FirmwareError::ImageExists(version) => write!(f, "Firmware version {} is already registered", version),
+            FirmwareError::ImageNotFound(version) => write!(f, "Firmware version not found: {}", version),
+            FirmwareError::AssignmentNotFound(device_id) => write!(f, "No firmware assigned to device: {}", device_id),
+            FirmwareError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FirmwareError {}
+
+impl FirmwareImage {
+    /// Creates a firmware image from a registration request with validation
+    ///
+    /// # Arguments
+    /// * `request` - The registration request
+    ///
+    /// # Returns
+    /// * `Result<Self, FirmwareError>` - The validated image, with its digest lowercased, or an error
+    pub fn parse(request: FirmwareImageRequest) -> Result<Self, FirmwareError> {
+        let version = parse_version(&request.version)?;
+
+        // Devices fetch the image themselves, so only plain web URLs make sense
+        let url = request.url.trim();
+        let host = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .map(|rest| rest.split('/').next().unwrap_or_default());
+        if host.is_none_or(str::is_empty) || url.chars().any(char::is_whitespace) {
+            return Err(FirmwareError::InvalidUrl(request.url));
+        }
+
+        let sha256 = request.sha256.trim().to_ascii_lowercase();
+        if sha256.len() != SHA256_HEX_LEN || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(FirmwareError::InvalidSha256);
+        }
+
+        let signature = request.signature.trim();
+        match STANDARD.decode(signature) {
+            Ok(bytes) if bytes.len() == SIGNATURE_LEN => {}
+            _ => return Err(FirmwareError::InvalidSignature),
+        }
+
+        Ok(FirmwareImage {
+            id: version.clone(),
+            version,
+            url: url.to_string(),
+            sha256,
+            signature: signature.to_string(),
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Returns the manifest served to devices that should install this image
+    pub fn manifest(&self) -> FirmwareManifest {
+        FirmwareManifest {
+            version: self.version.clone(),
+            url: self.url.clone(),
+            sha256: self.sha256.clone(),
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+impl FirmwareAssignment {
+    /// Creates the assignment of a version to a device
+    ///
+    /// # Arguments
+    /// * `device_id` - The device identifier, or `_default` for the fleet-wide target
+    /// * `version` - The registered version to install
+    ///
+    /// # Returns
+    /// * `Self` - A new FirmwareAssignment instance
+    pub fn new(device_id: &str, version: &str) -> Self {
+        FirmwareAssignment {
+            id: device_id.to_string(),
+            device_id: device_id.to_string(),
+            version: version.to_string(),
+            assigned_at: Utc::now(),
+        }
+    }
+}
+
+impl FirmwareAssignmentRequest {
+    /// Validates the assignment request
+    ///
+    /// # Returns
+    /// * `Result<(), FirmwareError>` - Success, or the first problem found
+    pub fn validate(&self) -> Result<(), FirmwareError> {
+        parse_version(&self.version)?;
+        if self.devices.is_empty() || self.devices.iter().any(|d| d.trim().is_empty()) {
+            return Err(FirmwareError::InvalidDeviceGroup);
+        }
+        Ok(())
+    }
+}
+
+/// Validates a firmware version string
+///
+/// Versions are used as document ids and compared by devices, so they are
+/// limited to ASCII letters, digits, `.`, `-` and `+` (e.g. "1.4.0-rc.1").
+///
+/// # Arguments
+/// * `version` - The version to check
+///
+/// # Returns
+/// * `Result<String, FirmwareError>` - The trimmed version, or an error
+fn parse_version(version: &str) -> Result<String, FirmwareError> {
+    let trimmed = version.trim();
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+');
+    if trimmed.is_empty() || trimmed.len() > MAX_VERSION_LEN || !trimmed.chars().all(allowed) {
+        return Err(FirmwareError::InvalidVersion(version.to_string()));
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> FirmwareImageRequest {
+        FirmwareImageRequest {
+            version: "1.4.0".to_string(),
+            url: "https://firmware.example.com/rp-rot/1.4.0.bin".to_string(),
+            sha256: "A".repeat(64),
+            signature: STANDARD.encode([7u8; 64]),
+        }
+    }
+
+    #[test]
+    fn test_parse_valid_image() {
+        let image = FirmwareImage::parse(request()).unwrap();
+
+        assert_eq!(image.id, "1.4.0");
+        assert_eq!(image.sha256, "a".repeat(64));
+        assert_eq!(image.manifest().url, "https://firmware.example.com/rp-rot/1.4.0.bin");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_images() {
+        let invalid = [
+            FirmwareImageRequest { version: "".to_string(), ..request() },
+            FirmwareImageRequest { version: "1.4.0/../x".to_string(), ..request() },
+            FirmwareImageRequest { url: "ftp://firmware.example.com/1.4.0.bin".to_string(), ..request() },
+            FirmwareImageRequest { url: "https:///1.4.0.bin".to_string(), ..request() },
+            FirmwareImageRequest { sha256: "abc".to_string(), ..request() },
+            FirmwareImageRequest { sha256: "g".repeat(64), ..request() },
+            FirmwareImageRequest { signature: "not base64!".to_string(), ..request() },
+            FirmwareImageRequest { signature: STANDARD.encode([7u8; 32]), ..request() },
+        ];
+
+        for request in invalid {
+            assert!(FirmwareImage::parse(request.clone()).is_err(), "{:?} should be rejected", request);
+        }
+    }
+
+    #[test]
+    fn test_assignment_request_validation() {
+        let valid = FirmwareAssignmentRequest { version: "1.4.0".to_string(), devices: vec!["sensor-001".to_string()] };
+        assert!(valid.validate().is_ok());
+
+        let empty = FirmwareAssignmentRequest { devices: Vec::new(), ..valid.clone() };
+        assert!(matches!(empty.validate(), Err(FirmwareError::InvalidDeviceGroup)));
+
+        let blank = FirmwareAssignmentRequest { devices: vec![" ".to_string()], ..valid };
+        assert!(matches!(blank.validate(), Err(FirmwareError::InvalidDeviceGroup)));
+    }
+}
@@ -0,0 +1,124 @@
+// Configuration History Domain Model
+//
+// This module defines the entries of a device's configuration history:
+// every stored version, who stored it, when it took effect and which keys
+// it changed compared to the version stored before it, so operators can
+// review past configurations and roll back to one of them.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::audit::{diff_configs, ConfigChange};
+use crate::domain::config::Config;
+
+/// A stored version of a device's configuration
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigVersion {
+    /// Number of the version, counting from 1 for each device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_number: Option<u64>,
+    /// The configuration values of the version
+    pub config: HashMap<String, String>,
+    /// When the version took effect, or is scheduled to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_at: Option<DateTime<Utc>>,
+    /// Who stored the version, or submitted it for approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_by: Option<String>,
+    /// Who approved the version, if it was held for approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+    /// True if this is the version currently served to the device
+    #[serde(default)]
+    pub active: bool,
+    /// Keys changed compared to the version stored before it
+    #[serde(default)]
+    pub changes: Vec<ConfigChange>,
+}
+
+/// Builds a device's configuration history from its stored versions
+///
+/// Each version is compared to the one stored before it; the oldest
+/// version is compared to the next entry of `versions` if there is one,
+/// so callers can read one version more than they list to diff the last
+/// listed version. The extra version is not returned.
+///
+/// # Arguments
+/// * `versions` - The stored versions in plaintext, newest first
+/// * `limit` - Maximum number of versions to return
+/// * `active` - Number of the version currently served, if any
+///
+/// # Returns
+/// * `Vec<ConfigVersion>` - The history, newest first
+pub fn config_history(versions: &[Config], limit: usize, active: Option<u64>) -> Vec<ConfigVersion> {
+    versions
+        .iter()
+        .enumerate()
+        .take(limit)
+        .map(|(index, version)| {
+            let previous = versions.get(index + 1).map(|previous| &previous.config);
+            ConfigVersion {
+                version_number: version.version_number,
+                config: version.config.clone(),
+                effective_at: version.effective_at,
+                modified_by: version.modified_by.clone(),
+                approved_by: version.approved_by.clone(),
+                active: active.is_some() && version.version_number == active,
+                changes: diff_configs(previous, &version.config),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(number: u64, pairs: &[(&str, &str)]) -> Config {
+        let config = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let mut version = Config::new("sensor-001".to_string(), config);
+        version.version_number = Some(number);
+        version
+    }
+
+    #[test]
+    fn test_config_history_diffs_each_version_against_the_previous_one() {
+        let versions = vec![
+            version(3, &[("LED", "on"), ("interval", "30")]),
+            version(2, &[("LED", "off"), ("interval", "30")]),
+            version(1, &[("LED", "off")]),
+        ];
+
+        let history = config_history(&versions, 10, Some(3));
+
+        assert_eq!(history.len(), 3);
+        assert!(history[0].active);
+        assert!(!history[1].active);
+        assert_eq!(history[0].changes, vec![ConfigChange {
+            key: "LED".to_string(),
+            old_value: Some("off".to_string()),
+            new_value: Some("on".to_string()),
+        }]);
+        assert_eq!(history[1].changes.len(), 1);
+        assert_eq!(history[1].changes[0].key, "interval");
+        // The first version adds every key
+        assert_eq!(history[2].changes.len(), 1);
+        assert_eq!(history[2].changes[0].old_value, None);
+    }
+
+    #[test]
+    fn test_config_history_uses_the_extra_version_only_for_the_diff() {
+        let versions = vec![
+            version(2, &[("LED", "on")]),
+            version(1, &[("LED", "off")]),
+        ];
+
+        let history = config_history(&versions, 1, None);
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].version_number, Some(2));
+        assert_eq!(history[0].changes[0].old_value, Some("off".to_string()));
+        assert!(!history[0].active);
+    }
+}
@@ -0,0 +1,201 @@
+// Configuration Size and Key Constraints
+//
+// This module defines the limits every stored configuration must respect:
+// how many keys it may have, how long keys and values may be, which
+// characters a key may contain and which keys are reserved. Devices hold
+// their configuration in fixed-capacity buffers, so anything that would
+// not fit is rejected before it reaches storage. The limits also carry the
+// per-key value validators, so every caller of `Config::parse` applies them.
+
+use std::collections::HashMap;
+
+use crate::domain::config::ConfigError;
+use crate::domain::validators::ValidatorRegistry;
+
+/// Default maximum number of keys in a configuration
+pub const DEFAULT_MAX_KEYS: usize = 64;
+
+/// Default maximum length of a configuration key, in bytes
+pub const DEFAULT_MAX_KEY_LENGTH: usize = 64;
+
+/// Default maximum length of a configuration value, in bytes
+pub const DEFAULT_MAX_VALUE_LENGTH: usize = 256;
+
+/// Characters allowed in keys in addition to ASCII letters and digits
+pub const DEFAULT_KEY_SPECIAL_CHARS: &str = "_-.";
+
+/// Keys that are always reserved, since they name fields of the served
+/// configuration document
+const BUILTIN_RESERVED_KEYS: [&str; 5] = ["id", "device_id", "apply_at", "signature", "is_default"];
+
+/// Limits enforced on the keys and values of every configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigLimits {
+    /// Maximum number of keys in a configuration
+    pub max_keys: usize,
+    /// Maximum length of a key, in bytes
+    pub max_key_length: usize,
+    /// Maximum length of a value, in bytes
+    pub max_value_length: usize,
+    /// Characters allowed in keys in addition to ASCII letters and digits
+    pub key_special_chars: String,
+    /// Keys that may not be used, in addition to the built-in ones
+    pub reserved_keys: Vec<String>,
+    /// Validators applied to the values of specific keys
+    pub validators: ValidatorRegistry,
+}
+
+impl Default for ConfigLimits {
+    fn default() -> Self {
+        ConfigLimits {
+            max_keys: DEFAULT_MAX_KEYS,
+            max_key_length: DEFAULT_MAX_KEY_LENGTH,
+            max_value_length: DEFAULT_MAX_VALUE_LENGTH,
+            key_special_chars: DEFAULT_KEY_SPECIAL_CHARS.to_string(),
+            reserved_keys: Vec::new(),
+            validators: ValidatorRegistry::builtin(),
+        }
+    }
+}
+
+impl ConfigLimits {
+    /// Loads the limits from the environment
+    ///
+    /// Unset variables keep their default value. The built-in value
+    /// validators are always registered.
+    ///
+    /// # Environment Variables
+    /// * `CONFIG_MAX_KEYS` - Maximum number of keys in a configuration
+    /// * `CONFIG_MAX_KEY_LENGTH` - Maximum length of a key, in bytes
+    /// * `CONFIG_MAX_VALUE_LENGTH` - Maximum length of a value, in bytes
+    /// * `CONFIG_KEY_SPECIAL_CHARS` - Characters allowed in keys besides ASCII letters and digits
+    /// * `CONFIG_RESERVED_KEYS` - Comma-separated list of additional reserved keys
+    ///
+    /// # Returns
+    /// * `Result<Self, String>` - The limits, or a description of the invalid variable
+    pub fn from_env() -> Result<Self, String> {
+        let defaults = Self::default();
+        let reserved_keys = std::env::var("CONFIG_RESERVED_KEYS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        Ok(ConfigLimits {
+            max_keys: env_limit("CONFIG_MAX_KEYS", defaults.max_keys)?,
+            max_key_length: env_limit("CONFIG_MAX_KEY_LENGTH", defaults.max_key_length)?,
+            max_value_length: env_limit("CONFIG_MAX_VALUE_LENGTH", defaults.max_value_length)?,
+            key_special_chars: std::env::var("CONFIG_KEY_SPECIAL_CHARS").unwrap_or(defaults.key_special_chars),
+            reserved_keys,
+            validators: defaults.validators,
+        })
+    }
+
+    /// Returns whether a key is reserved
+    pub fn is_reserved(&self, key: &str) -> bool {
+        BUILTIN_RESERVED_KEYS.contains(&key) || self.reserved_keys.iter().any(|reserved| reserved == key)
+    }
+
+    /// Returns whether a key only uses allowed characters
+    pub fn is_valid_key(&self, key: &str) -> bool {
+        !key.is_empty()
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || self.key_special_chars.contains(c))
+    }
+
+    /// Checks a configuration against the limits
+    ///
+    /// Keys are checked in sorted order, so the same configuration always
+    /// reports the same violation.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration parameters to check
+    ///
+    /// # Returns
+    /// * `Result<(), ConfigError>` - Success, or the first limit the configuration exceeds
+    pub fn check(&self, config: &HashMap<String, String>) -> Result<(), ConfigError> {
+        if config.len() > self.max_keys {
+            return Err(ConfigError::TooManyKeys(config.len(), self.max_keys));
+        }
+
+        let mut keys: Vec<&String> = config.keys().collect();
+        keys.sort();
+        for key in keys {
+            if !self.is_valid_key(key) {
+                return Err(ConfigError::InvalidKey(key.clone()));
+            }
+            if key.len() > self.max_key_length {
+                return Err(ConfigError::KeyTooLong(key.clone(), self.max_key_length));
+            }
+            if self.is_reserved(key) {
+                return Err(ConfigError::ReservedKey(key.clone()));
+            }
+            if config[key].len() > self.max_value_length {
+                return Err(ConfigError::ValueTooLong(key.clone(), self.max_value_length));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a numeric limit from the environment, falling back to a default
+fn env_limit(name: &str, default: usize) -> Result<usize, String> {
+    match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse()
+            .map_err(|_| format!("{} must be a non-negative integer, got {:?}", name, value)),
+        _ => Ok(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_default_limits_accept_typical_config() {
+        let limits = ConfigLimits::default();
+        let data = config(&[("LED", "on"), ("sampling_rate", "1000"), ("wifi.ssid", "MyNetwork"), ("mqtt-host", "broker")]);
+
+        assert!(limits.check(&data).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_oversized_configs() {
+        let limits = ConfigLimits { max_keys: 2, max_key_length: 8, max_value_length: 4, ..ConfigLimits::default() };
+
+        let result = limits.check(&config(&[("a", "1"), ("b", "2"), ("c", "3")]));
+        assert!(matches!(result, Err(ConfigError::TooManyKeys(3, 2))));
+
+        let result = limits.check(&config(&[("very_long_key", "1")]));
+        assert!(matches!(result, Err(ConfigError::KeyTooLong(key, 8)) if key == "very_long_key"));
+
+        let result = limits.check(&config(&[("LED", "blinking")]));
+        assert!(matches!(result, Err(ConfigError::ValueTooLong(key, 4)) if key == "LED"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_and_reserved_keys() {
+        let limits = ConfigLimits { reserved_keys: vec!["firmware".to_string()], ..ConfigLimits::default() };
+
+        for key in ["", "wifi ssid", "led/state", "température"] {
+            let result = limits.check(&config(&[(key, "on")]));
+            assert!(matches!(result, Err(ConfigError::InvalidKey(_))), "{:?} should be rejected", key);
+        }
+
+        for key in ["device_id", "signature", "firmware"] {
+            let result = limits.check(&config(&[(key, "on")]));
+            assert!(matches!(result, Err(ConfigError::ReservedKey(_))), "{:?} should be reserved", key);
+        }
+
+        let strict = ConfigLimits { key_special_chars: "_".to_string(), ..ConfigLimits::default() };
+        assert!(matches!(strict.check(&config(&[("wifi.ssid", "x")])), Err(ConfigError::InvalidKey(_))));
+    }
+}
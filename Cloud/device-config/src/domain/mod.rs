@@ -6,7 +6,39 @@
 
 pub mod config;
 pub mod error;
+pub mod rollout;
+pub mod audit;
+pub mod auth;
+pub mod signing;
+pub mod event;
+pub mod device;
+pub mod secrets;
+pub mod validation;
+pub mod bulk;
+pub mod limits;
+pub mod firmware;
+pub mod overrides;
+pub mod approval;
+pub mod effective;
+pub mod validators;
+pub mod history;
 
 // Re-export all domain types for convenient access
 pub use config::*;
-pub use error::*;
\ No newline at end of file
+pub use error::*;
+pub use rollout::*;
+pub use audit::*;
+pub use auth::*;
+pub use signing::*;
+pub use event::*;
+pub use device::*;
+pub use secrets::*;
+pub use validation::*;
+pub use bulk::*;
+pub use limits::*;
+pub use firmware::*;
+pub use overrides::*;
+pub use approval::*;
+pub use effective::*;
+pub use validators::*;
+pub use history::*;
\ No newline at end of file
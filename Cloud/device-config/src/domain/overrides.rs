@@ -0,0 +1,278 @@
+// Temporary Configuration Override Domain Model
+//
+// This module defines temporary overrides: configuration values applied to
+// a device for a limited time (e.g. "LED on for an hour for diagnostics")
+// that are reverted to their previous values automatically when they
+// expire, so operators do not have to remember to undo them.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::config::DEFAULT_CONFIG_ID;
+
+/// Longest lifetime of an override, in seconds (7 days)
+pub const MAX_OVERRIDE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Body of a request applying a temporary override
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OverrideRequest {
+    /// The device to override
+    pub device_id: String,
+    /// The values to apply; other keys of the configuration are left as they are
+    pub config: HashMap<String, String>,
+    /// How long the values stay applied, in seconds
+    pub ttl_secs: u64,
+}
+
+/// Lifecycle of an override
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideStatus {
+    /// The override values are applied
+    Active,
+    /// The override expired and its values were reverted
+    Expired,
+    /// An operator reverted the override before it expired
+    Reverted,
+}
+
+/// A temporary override of some of a device's configuration values
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigOverride {
+    /// Unique identifier of the override (also the Cosmos DB document id)
+    pub id: String,
+    /// The overridden device (partition key)
+    pub device_id: String,
+    /// The applied values, secret values encrypted
+    pub values: HashMap<String, String>,
+    /// The values the overridden keys had before, secret values encrypted;
+    /// keys missing here did not exist and are removed on revert
+    pub previous: HashMap<String, String>,
+    /// Current state of the override
+    pub status: OverrideStatus,
+    /// Who applied the override
+    pub created_by: String,
+    /// When the override was applied
+    pub created_at: DateTime<Utc>,
+    /// When the override is reverted automatically
+    pub expires_at: DateTime<Utc>,
+    /// When the override was reverted, if it has been
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverted_at: Option<DateTime<Utc>>,
+}
+
+/// Error types that can occur while managing temporary overrides
+#[derive(Debug, Serialize)]
+pub enum OverrideError {
+    /// Device ID is empty or the reserved `_default`
+    InvalidDeviceId,
+    /// The override has no values
+    EmptyOverride,
+    /// TTL is zero or longer than `MAX_OVERRIDE_TTL_SECS`
+    InvalidTtl(u64),
+    /// The overridden configuration fails validation or the configured limits
+    ConfigRejected(String),
+    /// A key is already held by another active override of the device
+    KeyOverridden(String),
+    /// The device has no configuration of its own to override
+    DeviceNotFound(String),
+    /// No override with this id exists for the device
+    OverrideNotFound(String),
+    /// The override was already reverted
+    NotActive(String),
+    /// Approval is required, so overrides cannot be applied
+    ApprovalRequired,
+    /// Database operation error
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for OverrideError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverrideError::InvalidDeviceId => write!(f, "Overrides require a device ID other than {}", DEFAULT_CONFIG_ID),
+            OverrideError::EmptyOverride => write!(f, "Override must set at least one value"),
+            OverrideError::InvalidTtl(ttl) => write!(f, "Override TTL must be between 1 and {} seconds, got {}", MAX_OVERRIDE_TTL_SECS, ttl),
+            OverrideError::ConfigRejected(msg) => write!(f, "Overridden configuration rejected: {}", msg),
+            OverrideError::KeyOverridden(key) => write!(f, "Key {:?} is already overridden", key),
+            OverrideError::DeviceNotFound(device_id) => write!(f, "No configuration to override for device: {}", device_id),
+            OverrideError::OverrideNotFound(id) => write!(f, "Override not found: {}", id),
+            OverrideError::NotActive(id) => write!(f, "Override {} is no longer active", id),
+            OverrideError::ApprovalRequired => write!(f, "Overrides are disabled while configuration changes require approval"),
+            OverrideError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for OverrideError {}
+
+impl OverrideRequest {
+    /// Validates the override request
+    ///
+    /// # Returns
+    /// * `Result<Duration, OverrideError>` - How long the override lasts, or the first problem found
+    pub fn validate(&self) -> Result<Duration, OverrideError> {
+        if self.device_id.trim().is_empty() || self.device_id == DEFAULT_CONFIG_ID {
+            return Err(OverrideError::InvalidDeviceId);
+        }
+        if self.config.is_empty() {
+            return Err(OverrideError::EmptyOverride);
+        }
+        if self.ttl_secs == 0 || self.ttl_secs > MAX_OVERRIDE_TTL_SECS {
+            return Err(OverrideError::InvalidTtl(self.ttl_secs));
+        }
+        Ok(Duration::seconds(self.ttl_secs as i64))
+    }
+}
+
+impl ConfigOverride {
+    /// Creates an active override
+    ///
+    /// # Arguments
+    /// * `device_id` - The overridden device
+    /// * `values` - The applied values, as stored
+    /// * `previous` - The values the overridden keys had before, as stored
+    /// * `created_by` - Who applied the override
+    /// * `ttl` - How long the override lasts
+    ///
+    /// # Returns
+    /// * `Self` - A new ConfigOverride instance
+    pub fn new(
+        device_id: String,
+        values: HashMap<String, String>,
+        previous: HashMap<String, String>,
+        created_by: String,
+        ttl: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        ConfigOverride {
+            id: uuid::Uuid::new_v4().to_string(),
+            device_id,
+            values,
+            previous,
+            status: OverrideStatus::Active,
+            created_by,
+            created_at: now,
+            expires_at: now + ttl,
+            reverted_at: None,
+        }
+    }
+
+    /// Returns whether the override is active and past its expiry time
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.status == OverrideStatus::Active && self.expires_at <= now
+    }
+
+    /// Marks the override as reverted
+    ///
+    /// # Arguments
+    /// * `status` - `Expired` or `Reverted`
+    /// * `now` - When the values were reverted
+    ///
+    /// # Returns
+    /// * `Result<(), OverrideError>` - Success, or an error if the override was already reverted
+    pub fn finish(&mut self, status: OverrideStatus, now: DateTime<Utc>) -> Result<(), OverrideError> {
+        if self.status != OverrideStatus::Active {
+            return Err(OverrideError::NotActive(self.id.clone()));
+        }
+        self.status = status;
+        self.reverted_at = Some(now);
+        Ok(())
+    }
+}
+
+/// Computes the configuration left after reverting an override
+///
+/// A key is only restored while it still holds the override's value; keys
+/// changed since the override was applied keep their newer value.
+///
+/// # Arguments
+/// * `current` - The device's configuration, in plaintext
+/// * `values` - The override's values, in plaintext
+/// * `previous` - The values the overridden keys had before, in plaintext
+///
+/// # Returns
+/// * `(HashMap<String, String>, Vec<String>)` - The reverted configuration and the restored keys, sorted
+pub fn revert_values(
+    current: &HashMap<String, String>,
+    values: &HashMap<String, String>,
+    previous: &HashMap<String, String>,
+) -> (HashMap<String, String>, Vec<String>) {
+    let mut reverted = current.clone();
+    let mut restored = Vec::new();
+
+    for (key, value) in values {
+        if current.get(key) != Some(value) {
+            continue;
+        }
+        match previous.get(key) {
+            Some(old) => reverted.insert(key.clone(), old.clone()),
+            None => reverted.remove(key),
+        };
+        restored.push(key.clone());
+    }
+
+    restored.sort();
+    (reverted, restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_request_validation() {
+        let valid = OverrideRequest {
+            device_id: "sensor-001".to_string(),
+            config: config(&[("LED", "on")]),
+            ttl_secs: 3600,
+        };
+        assert_eq!(valid.validate().unwrap(), Duration::hours(1));
+
+        let invalid = [
+            OverrideRequest { device_id: DEFAULT_CONFIG_ID.to_string(), ..valid.clone() },
+            OverrideRequest { device_id: " ".to_string(), ..valid.clone() },
+            OverrideRequest { config: HashMap::new(), ..valid.clone() },
+            OverrideRequest { ttl_secs: 0, ..valid.clone() },
+            OverrideRequest { ttl_secs: MAX_OVERRIDE_TTL_SECS + 1, ..valid.clone() },
+        ];
+        for request in invalid {
+            assert!(request.validate().is_err(), "{:?} should be rejected", request);
+        }
+    }
+
+    #[test]
+    fn test_revert_values_restores_untouched_keys() {
+        let current = config(&[("LED", "on"), ("debug", "true"), ("sampling_rate", "500")]);
+        let values = config(&[("LED", "on"), ("debug", "true"), ("sampling_rate", "100")]);
+        let previous = config(&[("LED", "off"), ("sampling_rate", "1000")]);
+
+        let (reverted, restored) = revert_values(&current, &values, &previous);
+
+        // LED is restored, debug did not exist before, sampling_rate was changed since
+        assert_eq!(reverted, config(&[("LED", "off"), ("sampling_rate", "500")]));
+        assert_eq!(restored, vec!["LED".to_string(), "debug".to_string()]);
+    }
+
+    #[test]
+    fn test_override_expiry_and_finish() {
+        let mut item = ConfigOverride::new(
+            "sensor-001".to_string(),
+            config(&[("LED", "on")]),
+            config(&[("LED", "off")]),
+            "alice".to_string(),
+            Duration::minutes(5),
+        );
+
+        assert!(!item.is_expired_at(Utc::now()));
+        assert!(item.is_expired_at(Utc::now() + Duration::minutes(6)));
+
+        item.finish(OverrideStatus::Expired, Utc::now()).unwrap();
+        assert!(!item.is_expired_at(Utc::now() + Duration::minutes(6)));
+        assert!(matches!(item.finish(OverrideStatus::Reverted, Utc::now()), Err(OverrideError::NotActive(_))));
+    }
+}
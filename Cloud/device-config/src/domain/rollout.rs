@@ -0,0 +1,499 @@
+// Staged Rollout Domain Model
+//
+// This module defines the data structures and state machine for staged
+// (canary) configuration rollouts. A rollout applies a configuration change
+// to a subset of a device group first, and is then promoted to the whole
+// group or aborted.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::config::{Config, ConfigError};
+use crate::domain::limits::ConfigLimits;
+
+/// Selection of the canary devices that receive a rollout first
+///
+/// Serialized as either `{"percentage": 10}` or `{"devices": ["a", "b"]}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CanarySelection {
+    /// A percentage (1-100) of the group, selected deterministically
+    Percentage(u8),
+    /// An explicit, named subset of the group
+    Devices(Vec<String>),
+}
+
+/// Lifecycle state of a rollout
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutStatus {
+    /// The configuration has been applied to the canary devices only
+    Canary,
+    /// The rollout is on hold and cannot be promoted until resumed
+    Paused,
+    /// The configuration has been applied to the whole group
+    Promoted,
+    /// The rollout was abandoned and canary devices were reverted
+    Aborted,
+}
+
+/// Operator actions that move a rollout between states
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RolloutAction {
+    Pause,
+    Resume,
+    Promote,
+    Abort,
+}
+
+/// How aborting a rollout reverts one of its canary devices
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanaryRevert {
+    /// Store the configuration the device had before, secret values encrypted
+    Restore(HashMap<String, String>),
+    /// Soft-delete the version the rollout stored, since the device had no
+    /// configuration before
+    Withdraw(String),
+    /// The device was reconfigured after the rollout reached it; keep its
+    /// current configuration
+    Reconfigured,
+    /// The rollout never changed the device
+    Untouched,
+}
+
+/// Request body for creating a new rollout
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RolloutRequest {
+    /// Human-readable name of the rollout
+    pub name: String,
+    /// Device IDs making up the group the rollout targets
+    pub devices: Vec<String>,
+    /// Which part of the group receives the change first
+    pub canary: CanarySelection,
+    /// Configuration parameters to roll out
+    pub config: HashMap<String, String>,
+}
+
+/// A staged configuration rollout and its current status
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Rollout {
+    /// Unique identifier of the rollout (also the Cosmos DB document id)
+    pub id: String,
+    /// Human-readable name of the rollout
+    pub name: String,
+    /// Device IDs making up the targeted group
+    pub devices: Vec<String>,
+    /// The requested canary selection
+    pub canary: CanarySelection,
+    /// Devices resolved from the canary selection at creation time
+    pub canary_devices: Vec<String>,
+    /// Configuration parameters being rolled out
+    pub config: HashMap<String, String>,
+    /// Current lifecycle state
+    pub status: RolloutStatus,
+    /// Configuration each canary device had before the rollout, used on abort
+    /// 
+    /// `None` (serialized as `null`) records that the device had no
+    /// configuration of its own. Devices are added as the rollout reaches
+    /// them, so a device missing here was never changed by the rollout.
+    #[serde(default)]
+    pub previous_configs: HashMap<String, Option<HashMap<String, String>>>,
+    /// Version the rollout stored on each canary device, used on abort to
+    /// leave devices alone that were reconfigured since
+    #[serde(default)]
+    pub canary_versions: HashMap<String, String>,
+    /// When the rollout was created
+    pub created_at: DateTime<Utc>,
+    /// When the rollout last changed state
+    pub updated_at: DateTime<Utc>,
+    /// Entity tag of the stored document, as read from Cosmos DB
+    /// 
+    /// Actions replace the document only if it still has this tag, so
+    /// concurrent actions on a rollout are applied once; never serialized.
+    #[serde(rename = "_etag", default, skip_serializing)]
+    pub etag: Option<String>,
+}
+
+/// Error types that can occur while managing rollouts
+#[derive(Debug, Serialize)]
+pub enum RolloutError {
+    /// Rollout name is empty
+    InvalidName,
+    /// The device group is empty or contains an empty or reserved device ID
+    InvalidDeviceGroup,
+    /// Canary percentage is outside 1-100
+    InvalidPercentage(u8),
+    /// A named canary device is not part of the group
+    UnknownCanaryDevice(String),
+    /// A device is listed more than once in the group or the canary devices
+    DuplicateDevice(String),
+    /// Configuration data is empty or invalid
+    InvalidConfig,
+    /// Configuration exceeds the configured key and value limits
+    ConfigRejected(String),
+    /// The requested action is not allowed in the current state
+    InvalidTransition(String),
+    /// Another action changed the rollout while this one was applied
+    ConcurrentUpdate(String),
+    /// Approval is required, so configurations cannot be rolled out
+    ApprovalRequired,
+    /// Rollout not found in database
+    RolloutNotFound(String),
+    /// Database operation error
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for RolloutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RolloutError::InvalidName => write!(f, "Rollout name cannot be empty"),
+            RolloutError::InvalidDeviceGroup => write!(f, "Rollout device group cannot be empty"),
+            RolloutError::InvalidPercentage(p) => write!(f, "Canary percentage must be between 1 and 100, got {}", p),
+            RolloutError::UnknownCanaryDevice(d) => write!(f, "Canary device {} is not part of the rollout group", d),
+            RolloutError::DuplicateDevice(d) => write!(f, "Device {} is listed more than once", d),
+            RolloutError::InvalidConfig => write!(f, "Configuration data cannot be empty"),
+            RolloutError::ConfigRejected(msg) => write!(f, "Invalid rollout configuration: {}", msg),
+            RolloutError::InvalidTransition(msg) => write!(f, "Invalid rollout transition: {}", msg),
+            RolloutError::ConcurrentUpdate(id) => write!(f, "Rollout {} was changed by another action", id),
+            RolloutError::ApprovalRequired => write!(f, "Rollouts are disabled while configuration changes require approval"),
+            RolloutError::RolloutNotFound(id) => write!(f, "Rollout not found: {}", id),
+            RolloutError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for RolloutError {}
+
+impl Rollout {
+    /// Creates a new rollout from a request with validation
+    ///
+    /// Validates the group, canary selection and configuration, and resolves
+    /// the canary selection to a concrete list of devices.
+    ///
+    /// # Arguments
+    /// * `id` - The identifier to assign to the rollout
+    /// * `request` - The rollout creation request
+    /// * `limits` - The key and value limits the configuration must respect
+    ///
+    /// # Returns
+    /// * `Result<Self, RolloutError>` - The validated rollout or an error
+    pub fn parse(id: String, request: RolloutRequest, limits: &ConfigLimits) -> Result<Self, RolloutError> {
+        // Validate the rollout name is not empty
+        if request.name.trim().is_empty() {
+            return Err(RolloutError::InvalidName);
+        }
+
+        // Validate the device group is not empty
+        if request.devices.is_empty() {
+            return Err(RolloutError::InvalidDeviceGroup);
+        }
+
+        // Validate every device ID and the configuration using the regular
+        // config rules, since the configuration is stored for each device
+        for device_id in &request.devices {
+            Config::parse(device_id.clone(), request.config.clone(), limits)
+                .map_err(|e| match e {
+                    ConfigError::InvalidDeviceId | ConfigError::ReservedDeviceId => RolloutError::InvalidDeviceGroup,
+                    ConfigError::InvalidConfig => RolloutError::InvalidConfig,
+                    e => RolloutError::ConfigRejected(e.to_string()),
+                })?;
+        }
+
+        // A device listed twice would be configured, and reverted, twice
+        if let Some(duplicate) = find_duplicate(&request.devices) {
+            return Err(RolloutError::DuplicateDevice(duplicate.clone()));
+        }
+
+        // Resolve the canary selection to concrete devices
+        let canary_devices = match &request.canary {
+            CanarySelection::Percentage(percentage) => {
+                if *percentage == 0 || *percentage > 100 {
+                    return Err(RolloutError::InvalidPercentage(*percentage));
+                }
+                select_percentage(&id, &request.devices, *percentage)
+            }
+            CanarySelection::Devices(devices) => {
+                if devices.is_empty() {
+                    return Err(RolloutError::InvalidDeviceGroup);
+                }
+                if let Some(duplicate) = find_duplicate(devices) {
+                    return Err(RolloutError::DuplicateDevice(duplicate.clone()));
+                }
+                if let Some(unknown) = devices.iter().find(|d| !request.devices.contains(d)) {
+                    return Err(RolloutError::UnknownCanaryDevice(unknown.clone()));
+                }
+                devices.clone()
+            }
+        };
+
+        let now = Utc::now();
+        Ok(Rollout {
+            id,
+            name: request.name,
+            devices: request.devices,
+            canary: request.canary,
+            canary_devices,
+            config: request.config,
+            status: RolloutStatus::Canary,
+            previous_configs: HashMap::new(),
+            canary_versions: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+            etag: None,
+        })
+    }
+
+    /// Applies an operator action, moving the rollout to its next state
+    ///
+    /// Allowed transitions:
+    /// - Pause: canary -> paused
+    /// - Resume: paused -> canary
+    /// - Promote: canary -> promoted
+    /// - Abort: canary or paused -> aborted
+    ///
+    /// # Arguments
+    /// * `action` - The action to apply
+    ///
+    /// # Returns
+    /// * `Result<(), RolloutError>` - Success or an invalid transition error
+    pub fn apply(&mut self, action: RolloutAction) -> Result<(), RolloutError> {
+        let next = match (self.status, action) {
+            (RolloutStatus::Canary, RolloutAction::Pause) => RolloutStatus::Paused,
+            (RolloutStatus::Paused, RolloutAction::Resume) => RolloutStatus::Canary,
+            (RolloutStatus::Canary, RolloutAction::Promote) => RolloutStatus::Promoted,
+            (RolloutStatus::Canary, RolloutAction::Abort)
+            | (RolloutStatus::Paused, RolloutAction::Abort) => RolloutStatus::Aborted,
+            (status, action) => {
+                return Err(RolloutError::InvalidTransition(format!(
+                    "cannot {:?} a rollout in state {:?}",
+                    action, status
+                )))
+            }
+        };
+
+        self.status = next;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Records that the rollout's configuration was stored on a canary device
+    ///
+    /// # Arguments
+    /// * `device_id` - The canary device
+    /// * `previous` - The configuration it replaced, `None` if the device had none
+    /// * `version` - The version the rollout stored
+    pub fn record_canary(&mut self, device_id: &str, previous: Option<HashMap<String, String>>, version: String) {
+        self.previous_configs.insert(device_id.to_string(), previous);
+        self.canary_versions.insert(device_id.to_string(), version);
+    }
+
+    /// Decides how aborting the rollout reverts a canary device
+    ///
+    /// # Arguments
+    /// * `device_id` - The canary device
+    /// * `current_version` - The device's active version, if it has one
+    ///
+    /// # Returns
+    /// * `CanaryRevert` - What to do with the device
+    pub fn revert_for(&self, device_id: &str, current_version: Option<&str>) -> CanaryRevert {
+        let (Some(previous), Some(version)) =
+            (self.previous_configs.get(device_id), self.canary_versions.get(device_id))
+        else {
+            return CanaryRevert::Untouched;
+        };
+        if current_version != Some(version.as_str()) {
+            return CanaryRevert::Reconfigured;
+        }
+        match previous {
+            Some(previous) => CanaryRevert::Restore(previous.clone()),
+            None => CanaryRevert::Withdraw(version.clone()),
+        }
+    }
+
+    /// Returns the group devices that are not part of the canary set
+    pub fn remaining_devices(&self) -> Vec<String> {
+        self.devices
+            .iter()
+            .filter(|d| !self.canary_devices.contains(d))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Deterministically selects a percentage of devices for the canary stage
+///
+/// Devices are ordered by a stable hash of the rollout ID and device ID, so
+/// different rollouts pick different canaries while the same rollout always
+/// resolves the same way. At least one device is always selected.
+fn select_percentage(rollout_id: &str, devices: &[String], percentage: u8) -> Vec<String> {
+    let count = (devices.len() * percentage as usize).div_ceil(100).max(1);
+
+    let mut ordered: Vec<&String> = devices.iter().collect();
+    ordered.sort_by_key(|device| fnv1a(format!("{}:{}", rollout_id, device).as_bytes()));
+    ordered.into_iter().take(count).cloned().collect()
+}
+
+/// Returns the first device ID listed more than once, if any
+fn find_duplicate(devices: &[String]) -> Option<&String> {
+    let mut seen = std::collections::HashSet::new();
+    devices.iter().find(|device| !seen.insert(device.as_str()))
+}
+
+/// 64-bit FNV-1a hash, stable across builds and platforms
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(canary: CanarySelection) -> RolloutRequest {
+        let mut config = HashMap::new();
+        config.insert("LED".to_string(), "on".to_string());
+        RolloutRequest {
+            name: "led-on".to_string(),
+            devices: (0..10).map(|i| format!("device-{}", i)).collect(),
+            canary,
+            config,
+        }
+    }
+
+    #[test]
+    fn test_rollout_parse_percentage() {
+        let rollout = Rollout::parse("r1".to_string(), request(CanarySelection::Percentage(25)), &ConfigLimits::default()).unwrap();
+
+        assert_eq!(rollout.status, RolloutStatus::Canary);
+        assert_eq!(rollout.canary_devices.len(), 3);
+        assert_eq!(rollout.remaining_devices().len(), 7);
+        assert!(rollout.canary_devices.iter().all(|d| rollout.devices.contains(d)));
+    }
+
+    #[test]
+    fn test_rollout_percentage_selection_is_deterministic() {
+        let a = Rollout::parse("r1".to_string(), request(CanarySelection::Percentage(30)), &ConfigLimits::default()).unwrap();
+        let b = Rollout::parse("r1".to_string(), request(CanarySelection::Percentage(30)), &ConfigLimits::default()).unwrap();
+
+        assert_eq!(a.canary_devices, b.canary_devices);
+    }
+
+    #[test]
+    fn test_rollout_parse_invalid_percentage() {
+        let result = Rollout::parse("r1".to_string(), request(CanarySelection::Percentage(0)), &ConfigLimits::default());
+        assert!(matches!(result, Err(RolloutError::InvalidPercentage(0))));
+
+        let result = Rollout::parse("r1".to_string(), request(CanarySelection::Percentage(101)), &ConfigLimits::default());
+        assert!(matches!(result, Err(RolloutError::InvalidPercentage(101))));
+    }
+
+    #[test]
+    fn test_rollout_parse_named_devices() {
+        let canary = CanarySelection::Devices(vec!["device-1".to_string(), "device-2".to_string()]);
+        let rollout = Rollout::parse("r1".to_string(), request(canary), &ConfigLimits::default()).unwrap();
+
+        assert_eq!(rollout.canary_devices, vec!["device-1", "device-2"]);
+    }
+
+    #[test]
+    fn test_rollout_parse_unknown_canary_device() {
+        let canary = CanarySelection::Devices(vec!["not-in-group".to_string()]);
+        let result = Rollout::parse("r1".to_string(), request(canary), &ConfigLimits::default());
+
+        assert!(matches!(result, Err(RolloutError::UnknownCanaryDevice(d)) if d == "not-in-group"));
+    }
+
+    #[test]
+    fn test_rollout_parse_rejects_default_device() {
+        let mut req = request(CanarySelection::Percentage(10));
+        req.devices.push(crate::domain::config::DEFAULT_CONFIG_ID.to_string());
+
+        assert!(matches!(Rollout::parse("r1".to_string(), req, &ConfigLimits::default()), Err(RolloutError::InvalidDeviceGroup)));
+    }
+
+    #[test]
+    fn test_rollout_parse_validates_every_device() {
+        let mut req = request(CanarySelection::Percentage(10));
+        req.devices.push(" ".to_string());
+
+        assert!(matches!(Rollout::parse("r1".to_string(), req, &ConfigLimits::default()), Err(RolloutError::InvalidDeviceGroup)));
+    }
+
+    #[test]
+    fn test_rollout_parse_rejects_duplicate_devices() {
+        let mut req = request(CanarySelection::Percentage(10));
+        req.devices.push("device-3".to_string());
+        let result = Rollout::parse("r1".to_string(), req, &ConfigLimits::default());
+        assert!(matches!(result, Err(RolloutError::DuplicateDevice(d)) if d == "device-3"));
+
+        let canary = CanarySelection::Devices(vec!["device-1".to_string(), "device-1".to_string()]);
+        let result = Rollout::parse("r1".to_string(), request(canary), &ConfigLimits::default());
+        assert!(matches!(result, Err(RolloutError::DuplicateDevice(d)) if d == "device-1"));
+    }
+
+    #[test]
+    fn test_rollout_parse_empty_config() {
+        let mut req = request(CanarySelection::Percentage(10));
+        req.config.clear();
+
+        assert!(matches!(Rollout::parse("r1".to_string(), req, &ConfigLimits::default()), Err(RolloutError::InvalidConfig)));
+    }
+
+    #[test]
+    fn test_rollout_parse_enforces_limits() {
+        let limits = ConfigLimits { max_value_length: 1, ..ConfigLimits::default() };
+
+        let result = Rollout::parse("r1".to_string(), request(CanarySelection::Percentage(10)), &limits);
+
+        assert!(matches!(result, Err(RolloutError::ConfigRejected(msg)) if msg.contains("LED")));
+    }
+
+    #[test]
+    fn test_rollout_transitions() {
+        let mut rollout = Rollout::parse("r1".to_string(), request(CanarySelection::Percentage(10)), &ConfigLimits::default()).unwrap();
+
+        rollout.apply(RolloutAction::Pause).unwrap();
+        assert_eq!(rollout.status, RolloutStatus::Paused);
+
+        // A paused rollout must be resumed before it can be promoted
+        assert!(rollout.apply(RolloutAction::Promote).is_err());
+
+        rollout.apply(RolloutAction::Resume).unwrap();
+        rollout.apply(RolloutAction::Promote).unwrap();
+        assert_eq!(rollout.status, RolloutStatus::Promoted);
+
+        // Promoted rollouts are final
+        assert!(rollout.apply(RolloutAction::Abort).is_err());
+    }
+
+    #[test]
+    fn test_canary_selection_serialization() {
+        let json = serde_json::to_string(&CanarySelection::Percentage(10)).unwrap();
+        assert_eq!(json, r#"{"percentage":10}"#);
+
+        let parsed: CanarySelection = serde_json::from_str(r#"{"devices":["a"]}"#).unwrap();
+        assert_eq!(parsed, CanarySelection::Devices(vec!["a".to_string()]));
+    }
+
+    #[test]
+    fn test_revert_for_canary_devices() {
+        let canary = CanarySelection::Devices(vec!["device-1".to_string(), "device-2".to_string(), "device-3".to_string()]);
+        let mut rollout = Rollout::parse("r1".to_string(), request(canary), &ConfigLimits::default()).unwrap();
+        let previous: HashMap<String, String> = [("LED".to_string(), "off".to_string())].into_iter().collect();
+        rollout.record_canary("device-1", Some(previous.clone()), "v1".to_string());
+        rollout.record_canary("device-2", None, "v2".to_string());
+        rollout.record_canary("device-3", None, "v3".to_string());
+
+        assert_eq!(rollout.revert_for("device-1", Some("v1")), CanaryRevert::Restore(previous));
+        assert_eq!(rollout.revert_for("device-2", Some("v2")), CanaryRevert::Withdraw("v2".to_string()));
+        assert_eq!(rollout.revert_for("device-3", Some("v4")), CanaryRevert::Reconfigured);
+        assert_eq!(rollout.revert_for("device-3", None), CanaryRevert::Reconfigured);
+        assert_eq!(rollout.revert_for("device-4", Some("v1")), CanaryRevert::Untouched);
+
+        // No previous configuration is recorded explicitly
+        let stored = serde_json::to_value(&rollout).unwrap();
+        assert!(stored["previous_configs"]["device-2"].is_null());
+    }
+}
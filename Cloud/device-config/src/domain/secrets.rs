@@ -0,0 +1,433 @@
+// Secret Configuration Values
+//
+// This module encrypts sensitive configuration values such as Wi-Fi
+// passwords before they are written to Cosmos DB. Each value is encrypted
+// with its own random data key (AES-256-GCM), and the data key is wrapped
+// with a key-encryption key supplied by the environment, e.g. from a Key
+// Vault secret reference. Values are only decrypted when a configuration
+// is served to an authorized caller; audit entries and change events
+// carry a redacted placeholder instead.
+
+use std::collections::{HashMap, HashSet};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Serialize;
+
+use crate::domain::audit::ConfigChange;
+
+/// Prefix marking an encrypted value, followed by `<key_id>:<wrapped key>:<ciphertext>`
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Placeholder recorded in audit entries and events instead of secret values
+pub const REDACTED_VALUE: &str = "********";
+
+/// Key suffixes that are always treated as secret
+const SECRET_SUFFIXES: [&str; 3] = ["_password", "_secret", "_token"];
+
+/// Key identifier used when none is configured
+const DEFAULT_KEY_ID: &str = "local";
+
+/// Length of an AES-GCM nonce in bytes
+const NONCE_LEN: usize = 12;
+
+/// Error types that can occur while encrypting or decrypting secret values
+#[derive(Debug, Serialize)]
+pub enum SecretError {
+    /// The key-encryption key is not valid base64
+    InvalidEncoding(String),
+    /// The decoded key-encryption key is not 32 bytes
+    InvalidKeyLength(usize),
+    /// An encrypted value was stored but no key-encryption key is configured
+    KeyNotConfigured(String),
+    /// An encrypted value was wrapped with a different key-encryption key
+    UnknownKeyId(String),
+    /// An encrypted value is malformed or fails authentication
+    DecryptionFailed(String),
+    /// Encryption failed
+    EncryptionFailed(String),
+}
+
+impl std::fmt::Display for SecretError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretError::InvalidEncoding(msg) => write!(f, "Encryption key is not valid base64: {}", msg),
+            SecretError::InvalidKeyLength(len) => {
+                write!(f, "Encryption key must be 32 bytes, got {}", len)
+            }
+            SecretError::KeyNotConfigured(key) => {
+                write!(f, "Value of {} is encrypted but no encryption key is configured", key)
+            }
+            SecretError::UnknownKeyId(key_id) => write!(f, "Unknown encryption key id: {}", key_id),
+            SecretError::DecryptionFailed(key) => write!(f, "Failed to decrypt value of {}", key),
+            SecretError::EncryptionFailed(key) => write!(f, "Failed to encrypt value of {}", key),
+        }
+    }
+}
+
+impl std::error::Error for SecretError {}
+
+/// Returns whether a stored value is an encrypted secret
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Key-encryption key and the identifier recorded with every value it wraps
+#[derive(Clone)]
+struct EncryptionKey {
+    key_id: String,
+    cipher: Aes256Gcm,
+}
+
+/// Encrypts and decrypts the secret values of configurations
+///
+/// Without a key-encryption key, secret values are stored in plaintext
+/// but are still redacted from audit entries and change events.
+#[derive(Clone, Default)]
+pub struct ConfigCipher {
+    /// Key used to wrap data keys, if configured
+    key: Option<EncryptionKey>,
+    /// Configuration keys treated as secret in addition to the suffix rule
+    secret_keys: HashSet<String>,
+}
+
+impl ConfigCipher {
+    /// Creates a cipher from a 32-byte key-encryption key
+    ///
+    /// # Arguments
+    /// * `kek` - The key-encryption key
+    /// * `key_id` - Identifier recorded with every encrypted value ("local" if `None`)
+    ///
+    /// # Returns
+    /// * `Self` - A new ConfigCipher instance
+    pub fn new(kek: [u8; 32], key_id: Option<String>) -> Self {
+        // The identifier is embedded in a colon-separated value
+        let key_id = key_id
+            .unwrap_or_else(|| DEFAULT_KEY_ID.to_string())
+            .replace(':', "-");
+
+        ConfigCipher {
+            key: Some(EncryptionKey {
+                key_id,
+                cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&kek)),
+            }),
+            secret_keys: HashSet::new(),
+        }
+    }
+
+    /// Creates a cipher from a base64-encoded 32-byte key-encryption key
+    ///
+    /// # Arguments
+    /// * `encoded` - The base64 (standard alphabet) encoded key
+    /// * `key_id` - Identifier recorded with every encrypted value ("local" if `None`)
+    ///
+    /// # Returns
+    /// * `Result<Self, SecretError>` - The cipher or an error if the key is malformed
+    pub fn from_base64(encoded: &str, key_id: Option<String>) -> Result<Self, SecretError> {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| SecretError::InvalidEncoding(e.to_string()))?;
+        let kek: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| SecretError::InvalidKeyLength(bytes.len()))?;
+
+        Ok(Self::new(kek, key_id))
+    }
+
+    /// Additionally treats the given configuration keys as secret
+    ///
+    /// # Arguments
+    /// * `keys` - Configuration keys whose values are secret
+    ///
+    /// # Returns
+    /// * `Self` - The ConfigCipher with the keys registered
+    pub fn with_secret_keys<I>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.secret_keys.extend(keys);
+        self
+    }
+
+    /// Loads the key-encryption key and secret keys from the environment
+    ///
+    /// # Environment Variables
+    /// * `CONFIG_ENCRYPTION_KEY` - Base64-encoded 32-byte key-encryption key
+    /// * `CONFIG_ENCRYPTION_KEY_ID` - Optional key identifier
+    /// * `SECRET_CONFIG_KEYS` - Comma-separated list of additional secret keys
+    ///
+    /// # Returns
+    /// * `Result<Self, SecretError>` - The cipher (without a key if none is configured)
+    pub fn from_env() -> Result<Self, SecretError> {
+        let secret_keys: Vec<String> = std::env::var("SECRET_CONFIG_KEYS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let cipher = match std::env::var("CONFIG_ENCRYPTION_KEY") {
+            Ok(encoded) if !encoded.trim().is_empty() => {
+                let key_id = std::env::var("CONFIG_ENCRYPTION_KEY_ID").ok().filter(|id| !id.is_empty());
+                Self::from_base64(&encoded, key_id)?
+            }
+            _ => Self::default(),
+        };
+
+        Ok(cipher.with_secret_keys(secret_keys))
+    }
+
+    /// Returns whether secret values are encrypted before being stored
+    pub fn is_enabled(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Returns whether the value of a configuration key is secret
+    ///
+    /// Keys registered through `SECRET_CONFIG_KEYS` and any key ending in
+    /// `_password`, `_secret` or `_token` (e.g. `wifi_password`) are secret.
+    pub fn is_secret(&self, key: &str) -> bool {
+        self.secret_keys.contains(key)
+            || SECRET_SUFFIXES.iter().any(|suffix| key.ends_with(suffix))
+    }
+
+    /// Encrypts the secret values of a configuration for storage
+    ///
+    /// Values that are already encrypted are left unchanged, so stored
+    /// configurations can be written again as they are. Without a
+    /// key-encryption key the configuration is returned unchanged.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration parameters
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, String>, SecretError>` - The parameters with secret values encrypted
+    pub fn encrypt_config(&self, config: &HashMap<String, String>) -> Result<HashMap<String, String>, SecretError> {
+        let Some(key) = &self.key else {
+            return Ok(config.clone());
+        };
+
+        config
+            .iter()
+            .map(|(name, value)| {
+                if !self.is_secret(name) || is_encrypted(value) {
+                    return Ok((name.clone(), value.clone()));
+                }
+                encrypt_value(key, name, value).map(|encrypted| (name.clone(), encrypted))
+            })
+            .collect()
+    }
+
+    /// Decrypts the encrypted values of a stored configuration
+    ///
+    /// Values stored before encryption was enabled are returned as they are.
+    ///
+    /// # Arguments
+    /// * `config` - The stored configuration parameters
+    ///
+    /// # Returns
+    /// * `Result<HashMap<String, String>, SecretError>` - The parameters in plaintext
+    pub fn decrypt_config(&self, config: &HashMap<String, String>) -> Result<HashMap<String, String>, SecretError> {
+        config
+            .iter()
+            .map(|(name, value)| {
+                if !is_encrypted(value) {
+                    return Ok((name.clone(), value.clone()));
+                }
+                let key = self.key
+                    .as_ref()
+                    .ok_or_else(|| SecretError::KeyNotConfigured(name.clone()))?;
+                decrypt_value(key, name, value).map(|plaintext| (name.clone(), plaintext))
+            })
+            .collect()
+    }
+
+    /// Replaces the values of secret keys in a configuration with a placeholder
+    ///
+    /// # Arguments
+    /// * `config` - The configuration parameters in plaintext
+    ///
+    /// # Returns
+    /// * `HashMap<String, String>` - The parameters, safe to show to operators
+    pub fn redact_config(&self, config: &HashMap<String, String>) -> HashMap<String, String> {
+        config
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.is_secret(name) { REDACTED_VALUE.to_string() } else { value.clone() };
+                (name.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Replaces the values of secret keys in a list of changes with a placeholder
+    ///
+    /// # Arguments
+    /// * `changes` - Changes computed from plaintext configurations
+    ///
+    /// # Returns
+    /// * `Vec<ConfigChange>` - The changes, safe to record in the audit log
+    pub fn redact_changes(&self, changes: Vec<ConfigChange>) -> Vec<ConfigChange> {
+        changes
+            .into_iter()
+            .map(|mut change| {
+                if self.is_secret(&change.key) {
+                    change.old_value = change.old_value.map(|_| REDACTED_VALUE.to_string());
+                    change.new_value = change.new_value.map(|_| REDACTED_VALUE.to_string());
+                }
+                change
+            })
+            .collect()
+    }
+}
+
+/// Encrypts a single value with a fresh data key wrapped by the key-encryption key
+///
+/// The configuration key is bound to the ciphertext as associated data,
+/// so an encrypted value cannot be copied to another key.
+fn encrypt_value(key: &EncryptionKey, name: &str, value: &str) -> Result<String, SecretError> {
+    let failed = |_| SecretError::EncryptionFailed(name.to_string());
+
+    let data_key = Aes256Gcm::generate_key(&mut OsRng);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = Aes256Gcm::new(&data_key)
+        .encrypt(&nonce, Payload { msg: value.as_bytes(), aad: name.as_bytes() })
+        .map_err(failed)?;
+
+    let wrap_nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let wrapped_key = key.cipher
+        .encrypt(&wrap_nonce, data_key.as_slice())
+        .map_err(failed)?;
+
+    Ok(format!(
+        "{}{}:{}:{}",
+        ENCRYPTED_PREFIX,
+        key.key_id,
+        STANDARD.encode([wrap_nonce.as_slice(), &wrapped_key].concat()),
+        STANDARD.encode([nonce.as_slice(), &ciphertext].concat()),
+    ))
+}
+
+/// Unwraps the data key of an encrypted value and decrypts the value
+fn decrypt_value(key: &EncryptionKey, name: &str, value: &str) -> Result<String, SecretError> {
+    let failed = || SecretError::DecryptionFailed(name.to_string());
+
+    let mut parts = value[ENCRYPTED_PREFIX.len()..].splitn(3, ':');
+    let (Some(key_id), Some(wrapped_key), Some(ciphertext)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(failed());
+    };
+    if key_id != key.key_id {
+        return Err(SecretError::UnknownKeyId(key_id.to_string()));
+    }
+
+    let wrapped_key = STANDARD.decode(wrapped_key).map_err(|_| failed())?;
+    let ciphertext = STANDARD.decode(ciphertext).map_err(|_| failed())?;
+    if wrapped_key.len() < NONCE_LEN || ciphertext.len() < NONCE_LEN {
+        return Err(failed());
+    }
+
+    let (wrap_nonce, wrapped_key) = wrapped_key.split_at(NONCE_LEN);
+    let data_key = key.cipher
+        .decrypt(Nonce::from_slice(wrap_nonce), wrapped_key)
+        .map_err(|_| failed())?;
+    if data_key.len() != 32 {
+        return Err(failed());
+    }
+
+    let (nonce, ciphertext) = ciphertext.split_at(NONCE_LEN);
+    let plaintext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key))
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: name.as_bytes() })
+        .map_err(|_| failed())?;
+
+    String::from_utf8(plaintext).map_err(|_| failed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> HashMap<String, String> {
+        HashMap::from([
+            ("wifi_ssid".to_string(), "MyNetwork".to_string()),
+            ("wifi_password".to_string(), "hunter2".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_encrypts_only_secret_values() {
+        let cipher = ConfigCipher::new([7u8; 32], Some("kv-1".to_string()));
+
+        let stored = cipher.encrypt_config(&config()).unwrap();
+
+        assert_eq!(stored["wifi_ssid"], "MyNetwork");
+        assert!(stored["wifi_password"].starts_with("enc:v1:kv-1:"));
+        assert!(!stored["wifi_password"].contains("hunter2"));
+        assert_eq!(cipher.decrypt_config(&stored).unwrap(), config());
+    }
+
+    #[test]
+    fn test_encryption_is_idempotent() {
+        let cipher = ConfigCipher::new([7u8; 32], None);
+
+        let stored = cipher.encrypt_config(&config()).unwrap();
+        let restored = cipher.encrypt_config(&stored).unwrap();
+
+        assert_eq!(stored, restored);
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_key_and_moved_values() {
+        let cipher = ConfigCipher::new([7u8; 32], Some("kv-1".to_string()));
+        let stored = cipher.encrypt_config(&config()).unwrap();
+
+        let other = ConfigCipher::new([8u8; 32], Some("kv-1".to_string()));
+        assert!(matches!(other.decrypt_config(&stored), Err(SecretError::DecryptionFailed(_))));
+
+        let rotated = ConfigCipher::new([7u8; 32], Some("kv-2".to_string()));
+        assert!(matches!(rotated.decrypt_config(&stored), Err(SecretError::UnknownKeyId(_))));
+
+        assert!(matches!(
+            ConfigCipher::default().decrypt_config(&stored),
+            Err(SecretError::KeyNotConfigured(_))
+        ));
+
+        let moved = HashMap::from([("admin_password".to_string(), stored["wifi_password"].clone())]);
+        assert!(matches!(cipher.decrypt_config(&moved), Err(SecretError::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn test_secret_keys_and_redaction() {
+        let cipher = ConfigCipher::default().with_secret_keys(["api_key".to_string()]);
+        assert!(cipher.is_secret("wifi_password"));
+        assert!(cipher.is_secret("api_key"));
+        assert!(!cipher.is_secret("wifi_ssid"));
+        assert_eq!(cipher.encrypt_config(&config()).unwrap(), config());
+
+        let changes = cipher.redact_changes(vec![
+            ConfigChange { key: "wifi_password".to_string(), old_value: None, new_value: Some("hunter2".to_string()) },
+            ConfigChange { key: "LED".to_string(), old_value: Some("off".to_string()), new_value: Some("on".to_string()) },
+        ]);
+
+        assert_eq!(changes[0].old_value, None);
+        assert_eq!(changes[0].new_value.as_deref(), Some(REDACTED_VALUE));
+        assert_eq!(changes[1].new_value.as_deref(), Some("on"));
+
+        let redacted = cipher.redact_config(&config());
+        assert_eq!(redacted["wifi_password"], REDACTED_VALUE);
+        assert_eq!(redacted["wifi_ssid"], "MyNetwork");
+    }
+
+    #[test]
+    fn test_from_base64_rejects_bad_keys() {
+        assert!(matches!(
+            ConfigCipher::from_base64("not base64!", None),
+            Err(SecretError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            ConfigCipher::from_base64(&STANDARD.encode([1u8; 16]), None),
+            Err(SecretError::InvalidKeyLength(16))
+        ));
+        assert!(ConfigCipher::from_base64(&STANDARD.encode([1u8; 32]), None).unwrap().is_enabled());
+    }
+}
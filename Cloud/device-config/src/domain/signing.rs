@@ -0,0 +1,246 @@
+// Configuration Signing Domain Logic
+//
+// This module signs served configurations so devices can verify their
+// authenticity before applying them. Configurations are signed with
+// Ed25519 over a canonical JSON form that devices can rebuild exactly.
+
+use std::collections::{BTreeMap, HashMap};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::config::Config;
+
+/// Signature algorithm reported alongside every signature
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Signature attached to a served configuration
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigSignature {
+    /// Always "ed25519"
+    pub algorithm: String,
+    /// Identifies the public key devices should verify with
+    pub key_id: String,
+    /// Base64 (standard alphabet) encoded 64-byte signature
+    pub signature: String,
+}
+
+/// Public half of the signing key, as published to devices
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SigningKeyInfo {
+    /// Always "ed25519"
+    pub algorithm: String,
+    /// Identifier included in every signature made with this key
+    pub key_id: String,
+    /// Base64 (standard alphabet) encoded 32-byte public key
+    pub public_key: String,
+}
+
+/// Error types that can occur while loading the signing key
+#[derive(Debug, Serialize)]
+pub enum SigningError {
+    /// The key is not valid base64
+    InvalidEncoding(String),
+    /// The decoded key is not a 32-byte Ed25519 seed
+    InvalidKeyLength(usize),
+}
+
+impl std::fmt::Display for SigningError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SigningError::InvalidEncoding(msg) => write!(f, "Signing key is not valid base64: {}", msg),
+            SigningError::InvalidKeyLength(len) => {
+                write!(f, "Signing key must be 32 bytes, got {}", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SigningError {}
+
+/// Canonical form of a configuration, as signed
+///
+/// Fields are declared in alphabetical order and the configuration map is
+/// sorted, so serializing this struct always yields the same bytes.
+#[derive(Serialize)]
+struct CanonicalConfig<'a> {
+    config: BTreeMap<&'a str, &'a str>,
+    device_id: &'a str,
+}
+
+/// Builds the canonical JSON form of a configuration
+///
+/// The canonical form is compact JSON (no whitespace) with keys sorted
+/// lexicographically at every level:
+///
+/// ```text
+/// {"config":{"LED":"on","threshold":"25.5"},"device_id":"sensor-001"}
+/// ```
+///
+/// Only the device ID and configuration values are covered; scheduling
+/// metadata such as `apply_at` is not part of the signed payload.
+///
+/// # Arguments
+/// * `device_id` - The device the configuration is for
+/// * `config` - The configuration parameters
+///
+/// # Returns
+/// * `String` - The bytes that are signed, as a string
+pub fn canonical_json(device_id: &str, config: &HashMap<String, String>) -> String {
+    let canonical = CanonicalConfig {
+        config: config.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect(),
+        device_id,
+    };
+    // Serializing string maps cannot fail
+    serde_json::to_string(&canonical).unwrap_or_default()
+}
+
+/// Signs configurations with an Ed25519 key
+#[derive(Clone)]
+pub struct ConfigSigner {
+    key_id: String,
+    signing_key: SigningKey,
+}
+
+impl ConfigSigner {
+    /// Creates a signer from a 32-byte Ed25519 seed
+    ///
+    /// # Arguments
+    /// * `seed` - The private key seed
+    /// * `key_id` - Identifier for the key; derived from the public key if `None`
+    ///
+    /// # Returns
+    /// * `Self` - A new ConfigSigner instance
+    pub fn new(seed: [u8; 32], key_id: Option<String>) -> Self {
+        let signing_key = SigningKey::from_bytes(&seed);
+        let key_id = key_id.unwrap_or_else(|| {
+            // Default to the first 8 bytes of the public key, hex encoded
+            signing_key.verifying_key().as_bytes()[..8]
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect()
+        });
+
+        ConfigSigner { key_id, signing_key }
+    }
+
+    /// Creates a signer from a base64-encoded 32-byte seed
+    ///
+    /// # Arguments
+    /// * `encoded` - The base64 (standard alphabet) encoded seed
+    /// * `key_id` - Identifier for the key; derived from the public key if `None`
+    ///
+    /// # Returns
+    /// * `Result<Self, SigningError>` - The signer or an error if the key is malformed
+    pub fn from_base64(encoded: &str, key_id: Option<String>) -> Result<Self, SigningError> {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| SigningError::InvalidEncoding(e.to_string()))?;
+        let seed: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| SigningError::InvalidKeyLength(bytes.len()))?;
+
+        Ok(Self::new(seed, key_id))
+    }
+
+    /// Loads the signer from the environment
+    ///
+    /// # Environment Variables
+    /// * `CONFIG_SIGNING_KEY` - Base64-encoded 32-byte Ed25519 seed
+    /// * `CONFIG_SIGNING_KEY_ID` - Optional key identifier
+    ///
+    /// # Returns
+    /// * `Result<Option<Self>, SigningError>` - The signer, `None` if no key is configured
+    pub fn from_env() -> Result<Option<Self>, SigningError> {
+        let encoded = match std::env::var("CONFIG_SIGNING_KEY") {
+            Ok(encoded) if !encoded.trim().is_empty() => encoded,
+            _ => return Ok(None),
+        };
+        let key_id = std::env::var("CONFIG_SIGNING_KEY_ID").ok().filter(|id| !id.is_empty());
+
+        Self::from_base64(&encoded, key_id).map(Some)
+    }
+
+    /// Returns the public key information devices need to verify signatures
+    pub fn key_info(&self) -> SigningKeyInfo {
+        SigningKeyInfo {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            key_id: self.key_id.clone(),
+            public_key: STANDARD.encode(self.signing_key.verifying_key().as_bytes()),
+        }
+    }
+
+    /// Signs the canonical form of a configuration
+    ///
+    /// # Arguments
+    /// * `config` - The configuration to sign
+    ///
+    /// # Returns
+    /// * `ConfigSignature` - The signature and the key it was made with
+    pub fn sign(&self, config: &Config) -> ConfigSignature {
+        let payload = canonical_json(&config.device_id, &config.config);
+        let signature = self.signing_key.sign(payload.as_bytes());
+
+        ConfigSignature {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            key_id: self.key_id.clone(),
+            signature: STANDARD.encode(signature.to_bytes()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    fn config() -> Config {
+        let mut values = HashMap::new();
+        values.insert("threshold".to_string(), "25.5".to_string());
+        values.insert("LED".to_string(), "on".to_string());
+        Config::new("sensor-001".to_string(), values)
+    }
+
+    #[test]
+    fn test_canonical_json_is_sorted_and_compact() {
+        let config = config();
+
+        assert_eq!(
+            canonical_json(&config.device_id, &config.config),
+            r#"{"config":{"LED":"on","threshold":"25.5"},"device_id":"sensor-001"}"#
+        );
+    }
+
+    #[test]
+    fn test_signature_verifies_with_published_key() {
+        let signer = ConfigSigner::new([7u8; 32], None);
+        let config = config();
+
+        let signature = signer.sign(&config);
+        let info = signer.key_info();
+        assert_eq!(signature.key_id, info.key_id);
+        assert_eq!(info.key_id.len(), 16);
+
+        let public_key: [u8; 32] = STANDARD.decode(&info.public_key).unwrap().try_into().unwrap();
+        let signature_bytes: [u8; 64] = STANDARD.decode(&signature.signature).unwrap().try_into().unwrap();
+        let verifying_key = VerifyingKey::from_bytes(&public_key).unwrap();
+        let payload = canonical_json(&config.device_id, &config.config);
+
+        assert!(verifying_key.verify(payload.as_bytes(), &Signature::from_bytes(&signature_bytes)).is_ok());
+        assert!(verifying_key.verify(b"{}", &Signature::from_bytes(&signature_bytes)).is_err());
+    }
+
+    #[test]
+    fn test_from_base64_rejects_bad_keys() {
+        assert!(matches!(
+            ConfigSigner::from_base64("not base64!", None),
+            Err(SigningError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            ConfigSigner::from_base64(&STANDARD.encode([1u8; 16]), None),
+            Err(SigningError::InvalidKeyLength(16))
+        ));
+        assert!(ConfigSigner::from_base64(&STANDARD.encode([1u8; 32]), Some("k1".to_string())).is_ok());
+    }
+}
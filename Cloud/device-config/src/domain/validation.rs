@@ -0,0 +1,221 @@
+// Configuration Dry-Run Validation
+//
+// This module checks a configuration exactly as POST /device-config/update
+// would, without storing it, and resolves what the device would be served
+// once it takes effect. Operators use the report as a preflight check
+// before pushing configurations in bulk.
+
+use std::collections::{BTreeSet, HashMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::audit::{diff_configs, ConfigChange};
+use crate::domain::config::{config_etag, Config};
+use crate::domain::limits::ConfigLimits;
+use crate::domain::secrets::ConfigCipher;
+
+/// Outcome of validating a configuration without storing it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ValidationReport {
+    /// True if POST /device-config/update would accept the configuration
+    pub valid: bool,
+    /// Reasons the configuration would be rejected
+    pub errors: Vec<String>,
+    /// Accepted, but probably not what the operator intended
+    pub warnings: Vec<String>,
+    /// The configuration `get_config` would serve once it is active, with
+    /// secret values redacted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<Config>,
+    /// Entity tag devices would see for the resolved configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// Changes relative to what the device is served now, with secret values redacted
+    #[serde(default)]
+    pub changes: Vec<ConfigChange>,
+}
+
+impl ValidationReport {
+    /// Creates a report for a configuration that would be rejected
+    fn invalid(error: String) -> Self {
+        ValidationReport {
+            valid: false,
+            errors: vec![error],
+            warnings: Vec::new(),
+            resolved: None,
+            etag: None,
+            changes: Vec::new(),
+        }
+    }
+}
+
+/// Validates a configuration and resolves what the device would be served
+///
+/// # Arguments
+/// * `request` - The configuration as it would be sent to POST /device-config/update
+/// * `current` - The device's active configuration in plaintext, if it has one
+/// * `defaults` - The active fleet-wide defaults in plaintext, if any
+/// * `cipher` - Decides which values are secret and whether they are encrypted at rest
+/// * `limits` - The key and value limits POST /device-config/update enforces
+/// * `now` - The reference time for scheduled configurations
+///
+/// # Returns
+/// * `ValidationReport` - Errors, warnings and the resolved configuration
+pub fn validate_config(
+    request: &Config,
+    current: Option<&Config>,
+    defaults: Option<&Config>,
+    cipher: &ConfigCipher,
+    limits: &ConfigLimits,
+    now: DateTime<Utc>,
+) -> ValidationReport {
+    let document = match Config::parse(request.device_id.clone(), request.config.clone(), limits) {
+        Ok(document) => document.scheduled(request.apply_at),
+        Err(e) => return ValidationReport::invalid(e.to_string()),
+    };
+
+    let mut warnings = Vec::new();
+
+    // What the device is served today: its own configuration, or the defaults
+    let served = current.or(defaults);
+    match (current, defaults) {
+        (None, Some(defaults)) => {
+            let dropped = missing_keys(&defaults.config, &document.config);
+            warnings.push("Device is currently served the fleet defaults; they will no longer apply to it".to_string());
+            if !dropped.is_empty() {
+                warnings.push(format!("Default keys not in this configuration will no longer be served: {}", dropped.join(", ")));
+            }
+        }
+        (Some(current), _) => {
+            let removed = missing_keys(&current.config, &document.config);
+            if !removed.is_empty() {
+                warnings.push(format!("Keys removed from the active configuration: {}", removed.join(", ")));
+            }
+            if current.config == document.config {
+                warnings.push("Configuration is identical to the active configuration".to_string());
+            }
+        }
+        (None, None) => {}
+    }
+
+    match document.apply_at {
+        Some(apply_at) if apply_at <= now => {
+            warnings.push("apply_at is in the past; the configuration takes effect immediately".to_string());
+        }
+        Some(apply_at) => {
+            warnings.push(format!("Scheduled for {}; the device keeps its current configuration until then", apply_at.to_rfc3339()));
+        }
+        None => {}
+    }
+
+    let mut keys: Vec<&String> = document.config.keys().collect();
+    keys.sort();
+    for key in &keys {
+        let value = &document.config[*key];
+        if value.trim() != value {
+            warnings.push(format!("Value of {} has leading or trailing whitespace", key));
+        }
+    }
+
+    let secrets: Vec<&str> = keys.iter().filter(|key| cipher.is_secret(key)).map(|key| key.as_str()).collect();
+    if !secrets.is_empty() && !cipher.is_enabled() {
+        warnings.push(format!("Secret values will be stored unencrypted (CONFIG_ENCRYPTION_KEY is not set): {}", secrets.join(", ")));
+    }
+
+    let changes = cipher.redact_changes(diff_configs(served.map(|config| &config.config), &document.config));
+    let etag = config_etag(std::slice::from_ref(&document));
+    let mut resolved = document;
+    resolved.config = cipher.redact_config(&resolved.config);
+
+    ValidationReport {
+        valid: true,
+        errors: Vec::new(),
+        warnings,
+        resolved: Some(resolved),
+        etag: Some(etag),
+        changes,
+    }
+}
+
+/// Returns the keys of `old` that are missing from `new`, sorted
+fn missing_keys(old: &HashMap<String, String>, new: &HashMap<String, String>) -> Vec<String> {
+    old.keys()
+        .filter(|key| !new.contains_key(*key))
+        .cloned()
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::domain::config::DEFAULT_CONFIG_ID;
+
+    fn config(device_id: &str, pairs: &[(&str, &str)]) -> Config {
+        let data = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Config::new(device_id.to_string(), data)
+    }
+
+    #[test]
+    fn test_rejects_invalid_and_reserved_configs() {
+        let cipher = ConfigCipher::default();
+        let now = Utc::now();
+
+        let report = validate_config(&config("sensor-001", &[]), None, None, &cipher, &ConfigLimits::default(), now);
+        assert!(!report.valid);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.resolved.is_none());
+
+        let report = validate_config(&config(DEFAULT_CONFIG_ID, &[("LED", "on")]), None, None, &cipher, &ConfigLimits::default(), now);
+        assert!(!report.valid);
+    }
+
+    #[test]
+    fn test_resolves_config_and_redacts_secrets() {
+        let cipher = ConfigCipher::default();
+        let current = config("sensor-001", &[("LED", "off"), ("mode", "eco")]);
+        let request = config("sensor-001", &[("LED", "on"), ("wifi_password", "hunter2")]);
+
+        let report = validate_config(&request, Some(&current), None, &cipher, &ConfigLimits::default(), Utc::now());
+
+        assert!(report.valid);
+        let resolved = report.resolved.unwrap();
+        assert_eq!(resolved.config["LED"], "on");
+        assert_eq!(resolved.config["wifi_password"], "********");
+        assert_eq!(report.etag, Some(config_etag(&[request])));
+        assert_eq!(report.changes.len(), 3);
+        assert!(report.warnings.iter().any(|w| w.contains("removed") && w.contains("mode")));
+        assert!(report.warnings.iter().any(|w| w.contains("unencrypted") && w.contains("wifi_password")));
+    }
+
+    #[test]
+    fn test_warns_about_defaults_and_schedule() {
+        let cipher = ConfigCipher::default();
+        let now = Utc::now();
+        let defaults = config(DEFAULT_CONFIG_ID, &[("LED", "off"), ("interval", "60")]);
+        let request = config("sensor-001", &[("LED", "on ")]).scheduled(Some(now - Duration::hours(1)));
+
+        let report = validate_config(&request, None, Some(&defaults), &cipher, &ConfigLimits::default(), now);
+
+        assert!(report.valid);
+        assert!(report.warnings.iter().any(|w| w.contains("fleet defaults")));
+        assert!(report.warnings.iter().any(|w| w.contains("interval")));
+        assert!(report.warnings.iter().any(|w| w.contains("in the past")));
+        assert!(report.warnings.iter().any(|w| w.contains("whitespace")));
+        assert_eq!(report.changes.iter().find(|c| c.key == "LED").unwrap().old_value.as_deref(), Some("off"));
+    }
+
+    #[test]
+    fn test_identical_config_has_no_changes() {
+        let cipher = ConfigCipher::default();
+        let current = config("sensor-001", &[("LED", "on")]);
+
+        let report = validate_config(&current.clone(), Some(&current), None, &cipher, &ConfigLimits::default(), Utc::now());
+
+        assert!(report.valid);
+        assert!(report.changes.is_empty());
+        assert!(report.warnings.iter().any(|w| w.contains("identical")));
+    }
+}
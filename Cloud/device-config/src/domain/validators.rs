@@ -0,0 +1,201 @@
+// Per-Key Configuration Validators
+//
+// This module defines the registry of value validators applied to
+// configurations as they are parsed. Each validator is registered for one
+// key and checks the values stored under it, e.g. that `interval` is a
+// whole number of seconds or that `LED` is "on" or "off", so a value the
+// device cannot interpret is rejected before it is stored. Keys without a
+// validator accept any value within the configured limits.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::config::ConfigError;
+
+/// Checks the value of a configuration key
+///
+/// Implemented for closures taking the value and returning the reason it
+/// is rejected, so simple rules can be registered inline.
+pub trait KeyValidator: Send + Sync {
+    /// Checks a value, with surrounding whitespace already trimmed
+    ///
+    /// # Arguments
+    /// * `value` - The value to check
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - Success, or why the value is rejected
+    fn validate(&self, value: &str) -> Result<(), String>;
+}
+
+impl<F> KeyValidator for F
+where
+    F: Fn(&str) -> Result<(), String> + Send + Sync,
+{
+    fn validate(&self, value: &str) -> Result<(), String> {
+        self(value)
+    }
+}
+
+/// Accepts one of a fixed set of values, compared case-sensitively
+#[derive(Debug, Clone, PartialEq)]
+pub struct OneOf(pub Vec<String>);
+
+impl OneOf {
+    /// Creates a validator accepting the given values
+    pub fn new<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        OneOf(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl KeyValidator for OneOf {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if self.0.iter().any(|allowed| allowed == value) {
+            return Ok(());
+        }
+        let quoted: Vec<String> = self.0.iter().map(|allowed| format!("{:?}", allowed)).collect();
+        Err(format!("must be one of {}", quoted.join(", ")))
+    }
+}
+
+/// Accepts a whole number of seconds that fits in a `u32`, at least `min`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Seconds {
+    /// Smallest accepted number of seconds
+    pub min: u32,
+}
+
+impl KeyValidator for Seconds {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match value.parse::<u32>() {
+            Ok(seconds) if seconds >= self.min => Ok(()),
+            Ok(_) => Err(format!("must be at least {} seconds", self.min)),
+            Err(_) => Err(format!("must be a whole number of seconds between {} and {}", self.min, u32::MAX)),
+        }
+    }
+}
+
+/// Validators by configuration key
+///
+/// Cloning a registry is cheap; validators are shared.
+#[derive(Clone, Default)]
+pub struct ValidatorRegistry {
+    validators: HashMap<String, Arc<dyn KeyValidator>>,
+}
+
+impl std::fmt::Debug for ValidatorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.keys()).finish()
+    }
+}
+
+impl PartialEq for ValidatorRegistry {
+    /// Registries are equal if they validate the same keys
+    fn eq(&self, other: &Self) -> bool {
+        self.keys() == other.keys()
+    }
+}
+
+impl ValidatorRegistry {
+    /// Creates an empty registry, accepting any value
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry with the validators for the keys devices interpret
+    ///
+    /// * `interval` - reporting interval, a whole number of seconds, at least 1
+    /// * `LED` - LED state, "on" or "off"
+    pub fn builtin() -> Self {
+        Self::new()
+            .register("interval", Seconds { min: 1 })
+            .register("LED", OneOf::new(["on", "off"]))
+    }
+
+    /// Registers a validator for a key, replacing any previous one
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to validate
+    /// * `validator` - The validator to apply to its values
+    ///
+    /// # Returns
+    /// * `Self` - The registry with the validator added
+    pub fn register(mut self, key: impl Into<String>, validator: impl KeyValidator + 'static) -> Self {
+        self.validators.insert(key.into(), Arc::new(validator));
+        self
+    }
+
+    /// Returns the keys with a validator, sorted
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.validators.keys().map(String::as_str).collect();
+        keys.sort();
+        keys
+    }
+
+    /// Checks every value of a configuration that has a validator
+    ///
+    /// Keys are checked in sorted order, so the same configuration always
+    /// reports the same error.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration parameters to check
+    ///
+    /// # Returns
+    /// * `Result<(), ConfigError>` - Success, or the first value rejected, naming its key
+    pub fn check(&self, config: &HashMap<String, String>) -> Result<(), ConfigError> {
+        let mut keys: Vec<&String> = config.keys().filter(|key| self.validators.contains_key(*key)).collect();
+        keys.sort();
+        for key in keys {
+            self.validators[key]
+                .validate(config[key].trim())
+                .map_err(|reason| ConfigError::InvalidValue(key.clone(), reason))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_builtin_validators() {
+        let registry = ValidatorRegistry::builtin();
+
+        assert!(registry.check(&config(&[("LED", "on"), ("interval", "30"), ("mode", "anything")])).is_ok());
+        assert!(registry.check(&config(&[("LED", " off ")])).is_ok());
+
+        let invalid = [("LED", "blinking"), ("LED", "ON"), ("interval", "0"), ("interval", "-5"), ("interval", "1.5"), ("interval", "4294967296")];
+        for (key, value) in invalid {
+            let result = registry.check(&config(&[(key, value)]));
+            assert!(matches!(result, Err(ConfigError::InvalidValue(ref k, _)) if k == key), "{}={} should be rejected", key, value);
+        }
+    }
+
+    #[test]
+    fn test_registered_validator_reports_key_and_reason() {
+        let registry = ValidatorRegistry::new()
+            .register("mode", OneOf::new(["eco", "boost"]))
+            .register("threshold", |value: &str| {
+                value.parse::<f32>().map(|_| ()).map_err(|_| "must be a number".to_string())
+            });
+
+        assert_eq!(registry.keys(), vec!["mode", "threshold"]);
+        let result = registry.check(&config(&[("mode", "turbo"), ("threshold", "high")]));
+        match result {
+            Err(ConfigError::InvalidValue(key, reason)) => {
+                assert_eq!(key, "mode");
+                assert_eq!(reason, "must be one of \"eco\", \"boost\"");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(registry.check(&config(&[("LED", "blinking")])).is_ok());
+    }
+}
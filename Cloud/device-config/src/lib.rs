@@ -8,7 +8,7 @@
 use dotenvy::dotenv;
 use rocket::{
     routes,
-    fairing::{Fairing, Info, Kind},
+    fairing::{AdHoc, Fairing, Info, Kind},
     Request, Response,
     http::Status,
     serde::json::Json,
@@ -108,6 +108,28 @@ fn bad_request() -> Json<ErrorResponse> {
     })
 }
 
+/// Catches unauthorized errors and returns a proper error response
+/// 
+/// This catcher handles requests with missing or invalid credentials.
+#[catch(401)]
+fn unauthorized() -> Json<ErrorResponse> {
+    Json(ErrorResponse {
+        error: "Unauthorized".to_string(),
+        message: "Missing or invalid credentials".to_string(),
+    })
+}
+
+/// Catches forbidden errors and returns a proper error response
+/// 
+/// This catcher handles authenticated callers that lack the required role.
+#[catch(403)]
+fn forbidden() -> Json<ErrorResponse> {
+    Json(ErrorResponse {
+        error: "Forbidden".to_string(),
+        message: "The caller's role does not allow this operation".to_string(),
+    })
+}
+
 /// Catches internal server errors and returns a proper error response
 /// 
 /// This catcher handles unexpected server errors and database failures.
@@ -148,8 +170,13 @@ impl Application {
     /// 2. Configures CORS for cross-origin requests
     /// 3. Sets up Rocket configuration with secret key and address
     /// 4. Attaches the application state and middleware
-    /// 5. Mounts the configuration management routes
-    /// 6. Registers error catchers for proper error handling
+    /// 5. Starts the background task reverting expired overrides on liftoff
+    /// 6. Mounts the configuration management routes
+    /// 7. Registers error catchers for proper error handling
+    /// 
+    /// Fails if configuration updates require approval while authorization
+    /// is disabled: every caller is then `anonymous`, so no change could be
+    /// approved by someone other than its author.
     /// 
     /// # Arguments
     /// * `app_state` - The application state containing database connections and other shared resources
@@ -160,6 +187,11 @@ impl Application {
         // Load environment variables from .env file
         dotenv().ok();
 
+        // Four-eyes review tells authors and reviewers apart by their credentials
+        if app_state.approval_required && !app_state.auth.is_enabled() {
+            return Err("CONFIG_REQUIRE_APPROVAL needs authorization, but AUTH_DISABLED is set".into());
+        }
+
         // Configure CORS to allow all origins (for development - should be restricted in production)
         let cors = CorsOptions {
             allowed_origins: AllowedOrigins::All,
@@ -175,15 +207,28 @@ impl Application {
                 .merge(("address", "0.0.0.0"))
                 .merge(("port", 8002)))
             // Attach application state for dependency injection
-            .manage(app_state)
+            .manage(app_state.clone())
             // Enable CORS for cross-origin requests
             .attach(cors)
             // Add request/response tracing for observability
             .attach(TracingFairing)
+            // Revert temporary overrides once they expire
+            // Index devices configured before the device index existed
+            .attach(AdHoc::on_liftoff("Device Index Backfill", {
+                let app_state = app_state.clone();
+                |_| Box::pin(async move {
+                    tokio::spawn(routes::devices::backfill_device_index(app_state));
+                })
+            }))
+            .attach(AdHoc::on_liftoff("Override Expiry", |_| Box::pin(async move {
+                tokio::spawn(routes::overrides::run_override_expiry(app_state));
+            })))
             // Register error catchers for proper error handling
             .register("/", catchers![
                 unprocessable_entity,
                 bad_request,
+                unauthorized,
+                forbidden,
                 internal_server_error,
                 not_found,
             ])
@@ -191,6 +236,39 @@ impl Application {
             .mount("/device-config", routes![
                 routes::update_config::update_config_route,
                 routes::get_config::get_config_route,
+                routes::rollouts::create_rollout_route,
+                routes::rollouts::get_rollout_route,
+                routes::rollouts::pause_rollout_route,
+                routes::rollouts::resume_rollout_route,
+                routes::rollouts::promote_rollout_route,
+                routes::rollouts::abort_rollout_route,
+                routes::audit::get_audit_route,
+                routes::signing_key::get_signing_key_route,
+                routes::devices::list_devices_route,
+                routes::defaults::get_defaults_route,
+                routes::defaults::put_defaults_route,
+                routes::validate::validate_config_route,
+                routes::bulk::export_configs_route,
+                routes::bulk::import_configs_route,
+                routes::firmware::register_firmware_route,
+                routes::firmware::list_firmware_route,
+                routes::firmware::get_firmware_route,
+                routes::firmware::assign_firmware_route,
+                routes::firmware::get_firmware_assignment_route,
+                routes::overrides::create_override_route,
+                routes::overrides::list_overrides_route,
+                routes::overrides::revert_override_route,
+                routes::approvals::list_approvals_route,
+                routes::approvals::get_approval_route,
+                routes::approvals::approve_change_route,
+                routes::approvals::reject_change_route,
+                routes::effective::get_effective_config_route,
+                routes::delete_config::delete_config_route,
+                routes::delete_config::restore_config_route,
+                routes::history::get_history_route,
+                routes::history::rollback_config_route,
+                routes::reported::report_config_route,
+                routes::reported::get_reported_config_route,
             ]);
 
         // Log the server startup information
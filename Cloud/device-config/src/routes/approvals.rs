@@ -0,0 +1,308 @@
+// Configuration Approval Route Handlers
+//
+// This module handles the /device-config/approvals endpoints for listing,
+// approving and rejecting configuration changes held for four-eyes review,
+// and the submission of updates when approval is required.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::approval::{ApprovalError, ApprovalStatus, ConfigApproval, ReviewRequest};
+use crate::domain::audit::{diff_configs, AuditAction, AuditActor, AuditEntry};
+use crate::domain::config::{Config, ConfigError};
+use crate::domain::event::{ConfigChangeEvent, APPROVAL_REJECTED_EVENT, APPROVAL_REQUESTED_EVENT};
+use crate::app_state::AppState;
+use crate::routes::update_config::apply_approved_config;
+use crate::utils::auth_guard::{AdminAccess, ReadAccess};
+
+/// Holds a configuration update for approval instead of storing it
+///
+/// This function performs the following steps:
+/// 1. Validates the configuration like a direct update would
+/// 2. Records which keys it would change, with secret values redacted,
+///    and the device's latest version it was compared against
+/// 3. Stores it as a pending change, secret values encrypted
+/// 4. Records the submission in the audit log and publishes an
+///    approval request event
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who submitted the change, for the audit log
+/// * `config` - The proposed configuration
+///
+/// # Returns
+/// * `Result<ConfigApproval, ConfigError>` - The pending change or an error
+pub(crate) async fn submit_config(
+    state: &AppState,
+    actor: AuditActor,
+    config: Config,
+) -> Result<ConfigApproval, ConfigError> {
+    info!("Submitting config for device {} for approval", config.device_id);
+
+    let document = Config::parse(config.device_id.clone(), config.config, &state.limits)?
+        .scheduled(config.apply_at);
+
+    let previous = state.cosmos_client.read_config(&document.device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    let previous = previous
+        .first()
+        .map(|current| state.cipher.decrypt_config(&current.config))
+        .transpose()
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    let changes = state.cipher.redact_changes(diff_configs(previous.as_ref(), &document.config));
+    let base_version = latest_version(state, &document.device_id)
+        .await
+        .map_err(ConfigError::DatabaseError)?;
+
+    let mut stored = document;
+    stored.config = state.cipher.encrypt_config(&stored.config)
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    let approval = ConfigApproval::new(stored, changes.clone(), actor.subject.clone())
+        .based_on(base_version);
+
+    state.cosmos_client.upsert_approval(&approval)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    let entry = AuditEntry::new(approval.device_id.clone(), AuditAction::ConfigSubmit, actor, changes);
+    state.cosmos_client.insert_audit_entry(&entry)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    state.events.publish(ConfigChangeEvent::from_audit(&entry, approval.id.clone()).with_type(APPROVAL_REQUESTED_EVENT));
+
+    info!("Config change {} is pending approval", approval.id);
+    Ok(approval)
+}
+
+/// Reads the number of a device's latest configuration version, if it has one
+async fn latest_version(state: &AppState, device_id: &str) -> Result<Option<u64>, String> {
+    state.cosmos_client.read_device_summary(device_id)
+        .await
+        .map(|summary| summary.map(|summary| summary.version_number))
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces a reviewed change, failing with `NotPending` if another review
+/// replaced it first
+async fn claim_review(state: &AppState, approval: &ConfigApproval) -> Result<(), ApprovalError> {
+    let claimed = state.cosmos_client.replace_approval(approval)
+        .await
+        .map_err(|e| ApprovalError::DatabaseError(e.to_string()))?;
+    if !claimed {
+        return Err(ApprovalError::NotPending(approval.id.clone()));
+    }
+    Ok(())
+}
+
+/// Loads a change by id, returning a not found error if it does not exist
+async fn load_approval(state: &AppState, approval_id: &str) -> Result<ConfigApproval, ApprovalError> {
+    state.cosmos_client.read_approval(approval_id)
+        .await
+        .map_err(|e| ApprovalError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApprovalError::ApprovalNotFound(approval_id.to_string()))
+}
+
+/// Lists the changes in a review state
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `status` - The review state name, "pending" if not given
+///
+/// # Returns
+/// * `Result<Vec<ConfigApproval>, ApprovalError>` - The matching changes, oldest first, or an error
+async fn list_approvals(state: &AppState, status: Option<&str>) -> Result<Vec<ConfigApproval>, ApprovalError> {
+    let name = status.unwrap_or("pending");
+    let status = ApprovalStatus::parse(name)
+        .ok_or_else(|| ApprovalError::InvalidStatus(name.to_string()))?;
+
+    state.cosmos_client.read_approvals(status)
+        .await
+        .map_err(|e| ApprovalError::DatabaseError(e.to_string()))
+}
+
+/// Approves a pending change and stores it as the device's configuration
+///
+/// This function performs the following steps:
+/// 1. Refuses the change if another configuration version was stored for
+///    the device since it was submitted, since the reviewed diff is stale
+/// 2. Marks the change approved, provided no other review got there first
+/// 3. Stores the configuration through the regular update path, so it is
+///    validated against the current limits, audited and published as usual,
+///    with the submitter as its author and the reviewer as its approver;
+///    if that fails, the change is returned to pending
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who approved the change, for the audit log
+/// * `approval_id` - The change to approve
+/// * `review` - Optional reason for the approval
+///
+/// # Returns
+/// * `Result<ConfigApproval, ApprovalError>` - The approved change or an error
+async fn approve_change(
+    state: &AppState,
+    actor: AuditActor,
+    approval_id: String,
+    review: ReviewRequest,
+) -> Result<ConfigApproval, ApprovalError> {
+    let mut approval = load_approval(state, &approval_id).await?;
+    approval.check_reviewer(&actor.subject)?;
+    info!("Approving config change {} for device {}", approval.id, approval.device_id);
+
+    let current = latest_version(state, &approval.device_id)
+        .await
+        .map_err(ApprovalError::DatabaseError)?;
+    approval.check_base(current)?;
+
+    let mut config = approval.proposed_config();
+    config.config = state.cipher.decrypt_config(&config.config)
+        .map_err(|e| ApprovalError::DatabaseError(e.to_string()))?;
+
+    let pending = approval.clone();
+    approval.review(ApprovalStatus::Approved, &actor.subject, review.reason)?;
+    claim_review(state, &approval).await?;
+
+    if let Err(e) = apply_approved_config(state, actor.clone(), &approval.requested_by, config).await {
+        // Return the change to pending so it can be reviewed again
+        state.cosmos_client.upsert_approval(&pending)
+            .await
+            .map_err(|e| ApprovalError::DatabaseError(e.to_string()))?;
+        return Err(match e {
+            ConfigError::DatabaseError(msg) => ApprovalError::DatabaseError(msg),
+            other => ApprovalError::ConfigRejected(other.to_string()),
+        });
+    }
+
+    info!("Config change {} approved", approval.id);
+    Ok(approval)
+}
+
+/// Rejects a pending change, discarding it
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who rejected the change, for the audit log
+/// * `approval_id` - The change to reject
+/// * `review` - Optional reason for the rejection
+///
+/// # Returns
+/// * `Result<ConfigApproval, ApprovalError>` - The rejected change or an error
+async fn reject_change(
+    state: &AppState,
+    actor: AuditActor,
+    approval_id: String,
+    review: ReviewRequest,
+) -> Result<ConfigApproval, ApprovalError> {
+    let mut approval = load_approval(state, &approval_id).await?;
+    approval.review(ApprovalStatus::Rejected, &actor.subject, review.reason)?;
+    info!("Rejecting config change {} for device {}", approval.id, approval.device_id);
+
+    claim_review(state, &approval).await?;
+
+    let entry = AuditEntry::new(approval.device_id.clone(), AuditAction::ConfigReject, actor, Vec::new());
+    state.cosmos_client.insert_audit_entry(&entry)
+        .await
+        .map_err(|e| ApprovalError::DatabaseError(e.to_string()))?;
+    state.events.publish(ConfigChangeEvent::from_audit(&entry, approval.id.clone()).with_type(APPROVAL_REJECTED_EVENT));
+
+    Ok(approval)
+}
+
+/// Converts an approval result into a route response, logging the outcome
+fn respond<T>(result: Result<T, ApprovalError>) -> Result<Json<T>, Status> {
+    match result {
+        Ok(value) => Ok(Json(value)),
+        Err(e) => {
+            error!("Approval request failed: {}", e);
+            // Convert the approval error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
+
+/// GET endpoint for listing configuration changes by review state
+///
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `status` - Optional "pending" (default), "approved" or "rejected"
+///
+/// # Example Response
+/// ```json
+/// [
+///   {
+///     "id": "c2a4...",
+///     "device_id": "sensor-001",
+///     "config": { "LED": "on" },
+///     "changes": [{ "key": "LED", "old_value": "off", "new_value": "on" }],
+///     "status": "pending",
+///     "requested_by": "alice",
+///     "requested_at": "2026-01-01T00:00:00Z"
+///   }
+/// ]
+/// ```
+///
+/// Requires the read-only role or higher.
+#[get("/approvals?<status>")]
+pub async fn list_approvals_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    status: Option<String>,
+) -> Result<Json<Vec<ConfigApproval>>, Status> {
+    respond(list_approvals(state.inner(), status.as_deref()).await)
+}
+
+/// GET endpoint for retrieving a configuration change
+///
+/// Requires the read-only role or higher.
+#[get("/approvals/<approval_id>")]
+pub async fn get_approval_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    approval_id: String,
+) -> Result<Json<ConfigApproval>, Status> {
+    respond(load_approval(state.inner(), &approval_id).await)
+}
+
+/// POST endpoint for approving a pending configuration change
+///
+/// The change is stored and served to the device. A change cannot be
+/// approved by the person who submitted it (403), nor once another
+/// configuration version was stored for the device after it was
+/// submitted (409); it must then be rejected and submitted again.
+///
+/// # Example Request
+/// ```json
+/// { "reason": "Checked against the maintenance plan" }
+/// ```
+///
+/// Requires the admin role.
+#[post("/approvals/<approval_id>/approve", data = "<review>")]
+pub async fn approve_change_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    actor: AuditActor,
+    approval_id: String,
+    review: Option<Json<ReviewRequest>>,
+) -> Result<Json<ConfigApproval>, Status> {
+    let review = review.map(Json::into_inner).unwrap_or_default();
+    respond(approve_change(state.inner(), actor, approval_id, review).await)
+}
+
+/// POST endpoint for rejecting a pending configuration change
+///
+/// A change cannot be rejected by the person who submitted it (403).
+///
+/// Requires the admin role.
+#[post("/approvals/<approval_id>/reject", data = "<review>")]
+pub async fn reject_change_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    actor: AuditActor,
+    approval_id: String,
+    review: Option<Json<ReviewRequest>>,
+) -> Result<Json<ConfigApproval>, Status> {
+    let review = review.map(Json::into_inner).unwrap_or_default();
+    respond(reject_change(state.inner(), actor, approval_id, review).await)
+}
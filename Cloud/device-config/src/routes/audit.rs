@@ -0,0 +1,103 @@
+// Configuration Audit Route Handler
+// 
+// This module handles the GET /device-config/audit/<target> endpoint for
+// querying the configuration change trail of a device or rollout.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::AuditEntry;
+use crate::domain::config::ConfigError;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::AdminAccess;
+
+/// Number of entries returned when no limit is given
+const DEFAULT_AUDIT_LIMIT: usize = 100;
+
+/// Upper bound on the number of entries returned by a single query
+const MAX_AUDIT_LIMIT: usize = 1000;
+
+/// Retrieves the audit trail for a device or rollout from the database
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `target` - The device ID or rollout ID
+/// * `limit` - Maximum number of entries to return (capped at 1000)
+/// 
+/// # Returns
+/// * `Result<Vec<AuditEntry>, ConfigError>` - Audit entries, newest first, or an error
+async fn get_audit(
+    state: &AppState,
+    target: String,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEntry>, ConfigError> {
+    info!("Getting audit trail: {:?}", target);
+
+    let limit = limit.unwrap_or(DEFAULT_AUDIT_LIMIT).clamp(1, MAX_AUDIT_LIMIT);
+
+    let entries = state.cosmos_client.read_audit_entries(&target, limit)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    info!("Audit trail retrieved successfully ({} entries)", entries.len());
+    Ok(entries)
+}
+
+/// GET endpoint for querying the configuration audit trail
+/// 
+/// Returns the recorded configuration mutations for a device ID or rollout
+/// ID, newest first. An empty array is returned when nothing was recorded.
+/// 
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `target` - The device ID or rollout ID from the URL path
+/// * `limit` - Optional maximum number of entries (default 100, max 1000)
+/// 
+/// # Returns
+/// * `Result<Json<Vec<AuditEntry>>, Status>` - JSON array of audit entries or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /device-config/audit/sensor-001?limit=10
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// [
+///   {
+///     "id": "0f8e...",
+///     "target": "sensor-001",
+///     "action": "config_update",
+///     "actor": {
+///       "subject": "operator@example.com",
+///       "source_ip": "203.0.113.7",
+///       "user_agent": "curl/8.5.0"
+///     },
+///     "timestamp": "2025-01-01T00:00:00Z",
+///     "changes": [
+///       { "key": "sampling_rate", "old_value": "1000", "new_value": "2000" }
+///     ]
+///   }
+/// ]
+/// ```
+/// 
+/// Requires the admin role.
+#[get("/audit/<target>?<limit>")]
+pub async fn get_audit_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    target: String,
+    limit: Option<usize>,
+) -> Result<Json<Vec<AuditEntry>>, Status> {
+    info!("Received audit request for: {:?}", target);
+
+    match get_audit(state.inner(), target, limit).await {
+        Ok(entries) => Ok(Json(entries)),
+        Err(e) => {
+            error!("Error retrieving audit trail: {}", e);
+            // Convert the configuration error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,290 @@
+// Bulk Configuration Export/Import Route Handlers
+//
+// This module handles the /device-config/export and /device-config/import
+// endpoints, which move every device configuration between environments
+// as a single JSON or YAML document and restore them after data loss.
+
+use std::collections::{HashMap, HashSet};
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::ContentType;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::AuditActor;
+use crate::domain::bulk::{plan_import, BulkError, BundleFormat, ConfigBundle, ConflictPolicy, ImportItem, ImportOutcome, ImportReport};
+use crate::domain::config::{Config, DefaultConfigRequest, DEFAULT_CONFIG_ID};
+use crate::app_state::AppState;
+use crate::routes::update_config::{update_config, update_defaults};
+use crate::utils::auth_guard::AdminAccess;
+
+/// Largest bundle accepted by the import endpoint, in mebibytes
+const MAX_IMPORT_MIB: usize = 10;
+
+/// Exports the active configuration of every device, as stored
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `format` - The requested format name, JSON if not given
+///
+/// # Returns
+/// * `Result<(ContentType, String), BulkError>` - The serialized bundle, including the fleet defaults, or an error
+async fn export_configs(state: &AppState, format: Option<&str>) -> Result<(ContentType, String), BulkError> {
+    let format = BundleFormat::parse(format)?;
+
+    let summaries = state.cosmos_client.read_device_summaries()
+        .await
+        .map_err(|e| BulkError::DatabaseError(e.to_string()))?;
+
+    let mut configs = Vec::new();
+    for summary in summaries {
+        let active = state.cosmos_client.read_config(&summary.device_id)
+            .await
+            .map_err(|e| BulkError::DatabaseError(e.to_string()))?;
+        configs.extend(active.into_iter().next());
+    }
+
+    info!("Exporting {} configuration(s)", configs.len());
+    let content_type = match format {
+        BundleFormat::Json => ContentType::JSON,
+        BundleFormat::Yaml => ContentType::new("application", "yaml"),
+    };
+    Ok((content_type, ConfigBundle::new(configs).render(format)?))
+}
+
+/// Reads a device's active configuration in plaintext
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<Option<HashMap<String, String>>, String>` - The configuration, if any, or the reason it could not be read
+async fn read_current(state: &AppState, device_id: &str) -> Result<Option<HashMap<String, String>>, String> {
+    let current = state.cosmos_client.read_config(device_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    current
+        .first()
+        .map(|config| state.cipher.decrypt_config(&config.config).map_err(|e| e.to_string()))
+        .transpose()
+}
+
+/// Imports one configuration from a bundle
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who started the import, for the audit log
+/// * `entry` - The configuration to import
+/// * `dry_run` - If true, only decide what would happen
+/// * `on_conflict` - What to do when the device has a different configuration
+///
+/// # Returns
+/// * `ImportItem` - What happened to the configuration
+async fn import_config(
+    state: &AppState,
+    actor: &AuditActor,
+    mut entry: Config,
+    dry_run: bool,
+    on_conflict: ConflictPolicy,
+) -> ImportItem {
+    // Exported secret values are encrypted; compare and store them in plaintext
+    entry.config = match state.cipher.decrypt_config(&entry.config) {
+        Ok(config) => config,
+        Err(e) => return ImportItem::failed(&entry.device_id, e.to_string()),
+    };
+
+    let current = if entry.device_id.trim().is_empty() {
+        None
+    } else {
+        match read_current(state, &entry.device_id).await {
+            Ok(current) => current,
+            Err(e) => return ImportItem::failed(&entry.device_id, e),
+        }
+    };
+
+    let item = plan_import(&entry, current.as_ref(), on_conflict, &state.limits);
+    if dry_run || !matches!(item.outcome, ImportOutcome::Created | ImportOutcome::Updated) {
+        return item;
+    }
+
+    // The fleet defaults can only be stored through their own write path
+    let stored = if entry.device_id == DEFAULT_CONFIG_ID {
+        let request = DefaultConfigRequest { config: entry.config, apply_at: entry.apply_at };
+        update_defaults(state, actor.clone(), request).await
+    } else {
+        update_config(state, actor.clone(), Json(entry)).await
+    };
+    match stored {
+        Ok(()) => item,
+        Err(e) => ImportItem::failed(&item.device_id, e.to_string()),
+    }
+}
+
+/// Imports every configuration in a bundle
+///
+/// Configurations are imported one at a time through the regular update
+/// path, so each stored one is encrypted, audited and published as usual;
+/// the fleet defaults under `_default` are stored as the defaults endpoint
+/// does, so an exported bundle restores them too.
+/// A device listed more than once is only imported the first time.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who started the import, for the audit log
+/// * `data` - The serialized bundle
+/// * `format` - The bundle's format name, JSON if not given
+/// * `dry_run` - If true, nothing is stored
+/// * `on_conflict` - The conflict policy name, overwrite if not given
+///
+/// # Returns
+/// * `Result<ImportReport, BulkError>` - The outcome for every configuration, or an error if the bundle is unusable
+async fn import_configs(
+    state: &AppState,
+    actor: AuditActor,
+    data: Data<'_>,
+    format: Option<&str>,
+    dry_run: bool,
+    on_conflict: Option<&str>,
+) -> Result<ImportReport, BulkError> {
+    let format = BundleFormat::parse(format)?;
+    let on_conflict = ConflictPolicy::parse(on_conflict)?;
+
+    // Imported configurations would be stored without review; dry runs store nothing
+    if state.approval_required && !dry_run {
+        return Err(BulkError::ApprovalRequired);
+    }
+
+    let document = data.open(MAX_IMPORT_MIB.mebibytes())
+        .into_string()
+        .await
+        .map_err(|e| BulkError::InvalidDocument(e.to_string()))?;
+    if !document.is_complete() {
+        return Err(BulkError::DocumentTooLarge);
+    }
+    let bundle = ConfigBundle::parse(&document, format)?;
+
+    let mut seen = HashSet::new();
+    let mut items = Vec::with_capacity(bundle.configs.len());
+    for entry in bundle.configs {
+        if !seen.insert(entry.device_id.clone()) {
+            items.push(ImportItem::failed(&entry.device_id, "Device is listed more than once".to_string()));
+            continue;
+        }
+        items.push(import_config(state, &actor, entry, dry_run, on_conflict).await);
+    }
+
+    let report = ImportReport::new(dry_run, items);
+    info!(
+        "Imported configurations (dry run: {}): {} created, {} updated, {} skipped, {} failed",
+        dry_run, report.created, report.updated, report.skipped, report.failed
+    );
+    Ok(report)
+}
+
+/// GET endpoint for exporting every device configuration
+///
+/// Returns the active configuration of every device, and the fleet-wide
+/// defaults under `_default`, as one document. Secret values are exported
+/// as stored: encrypted when `CONFIG_ENCRYPTION_KEY` is set, so the bundle
+/// can only be imported by a service holding the same key.
+///
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `format` - Optional "json" (default) or "yaml"
+///
+/// # Returns
+/// * `Result<(ContentType, String), Status>` - The bundle or HTTP error status
+///
+/// # Example Response
+/// ```yaml
+/// format_version: 1
+/// exported_at: 2025-01-01T00:00:00Z
+/// configs:
+/// - device_id: sensor-001
+///   config:
+///     LED: 'on'
+/// ```
+///
+/// Requires the admin role.
+#[get("/export?<format>")]
+pub async fn export_configs_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    format: Option<String>,
+) -> Result<(ContentType, String), Status> {
+    info!("Received configuration export request");
+
+    match export_configs(state.inner(), format.as_deref()).await {
+        Ok(bundle) => Ok(bundle),
+        Err(e) => {
+            error!("Error exporting configurations: {}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// POST endpoint for importing a bundle of device configurations
+///
+/// Each configuration is created if the device has none, skipped if it is
+/// unchanged, and otherwise overwritten or skipped depending on
+/// `on_conflict`. Invalid configurations are reported and do not stop the
+/// rest of the import.
+///
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `actor` - Caller identity and origin, recorded in the audit log
+/// * `format` - Optional "json" (default) or "yaml", the format of the body
+/// * `dry_run` - If true, report what would happen without storing anything
+/// * `on_conflict` - Optional "overwrite" (default) or "skip"
+/// * `data` - The bundle, as returned by the export endpoint
+///
+/// # Returns
+/// * `Result<Json<ImportReport>, Status>` - The import report or HTTP error status
+///
+/// # Example Request
+/// ```bash
+/// POST /device-config/import?format=yaml&dry_run=true&on_conflict=skip
+/// ```
+///
+/// # Example Response
+/// ```json
+/// {
+///   "dry_run": true,
+///   "created": 1,
+///   "updated": 0,
+///   "skipped": 1,
+///   "failed": 0,
+///   "items": [
+///     { "device_id": "sensor-001", "outcome": "skipped", "reason": "Configuration is unchanged" },
+///     { "device_id": "sensor-002", "outcome": "created" }
+///   ]
+/// }
+/// ```
+///
+/// While `CONFIG_REQUIRE_APPROVAL` is set, only dry runs are accepted;
+/// imports are refused with `409 Conflict`.
+///
+/// Requires the admin role.
+#[post("/import?<format>&<dry_run>&<on_conflict>", data = "<data>")]
+pub async fn import_configs_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    actor: AuditActor,
+    format: Option<String>,
+    dry_run: Option<bool>,
+    on_conflict: Option<String>,
+    data: Data<'_>,
+) -> Result<Json<ImportReport>, Status> {
+    info!("Received configuration import request");
+
+    let dry_run = dry_run.unwrap_or(false);
+    match import_configs(state.inner(), actor, data, format.as_deref(), dry_run, on_conflict.as_deref()).await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            error!("Error importing configurations: {}", e);
+            Err(e.into())
+        }
+    }
+}
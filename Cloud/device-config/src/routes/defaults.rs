@@ -0,0 +1,120 @@
+// Fleet Default Configuration Route Handlers
+// 
+// This module handles the /device-config/defaults endpoints for reading and
+// replacing the fleet-wide default configuration, which is served to
+// devices that have no configuration of their own.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::AuditActor;
+use crate::domain::config::{Config, ConfigError, DefaultConfigRequest, DEFAULT_CONFIG_ID};
+use crate::app_state::AppState;
+use crate::routes::update_config::update_defaults;
+use crate::utils::auth_guard::{AdminAccess, ReadAccess};
+
+/// Retrieves the active fleet-wide default configuration
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// 
+/// # Returns
+/// * `Result<Config, ConfigError>` - The defaults, with secret values decrypted, or an error
+async fn get_defaults(state: &AppState) -> Result<Config, ConfigError> {
+    let mut defaults = state.cosmos_client.read_config(DEFAULT_CONFIG_ID)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ConfigError::DeviceNotFound(DEFAULT_CONFIG_ID.to_string()))?;
+
+    defaults.config = state.cipher.decrypt_config(&defaults.config)
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    Ok(defaults)
+}
+
+/// GET endpoint for retrieving the fleet-wide default configuration
+/// 
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<Config>, Status>` - The defaults, or 404 if none are stored
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "_default",
+///   "config": {
+///     "sampling_rate": "1000",
+///     "LED": "off"
+///   }
+/// }
+/// ```
+/// 
+/// Requires the read-only role or higher.
+#[get("/defaults")]
+pub async fn get_defaults_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+) -> Result<Json<Config>, Status> {
+    info!("Received default configuration request");
+
+    match get_defaults(state.inner()).await {
+        Ok(defaults) => Ok(Json(defaults)),
+        Err(e) => {
+            error!("Error retrieving default configuration: {}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// PUT endpoint for replacing the fleet-wide default configuration
+/// 
+/// The defaults are versioned, audited, encrypted and published like any
+/// device configuration, under the reserved device ID `_default`. Long-polling
+/// devices without a configuration of their own are woken up.
+/// 
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `actor` - Caller identity and origin, recorded in the audit log
+/// * `request` - JSON payload containing the default configuration
+/// 
+/// # Returns
+/// * `Result<&'static str, Status>` - Success message or HTTP error status
+/// 
+/// # Example Request
+/// ```json
+/// {
+///   "config": {
+///     "sampling_rate": "1000",
+///     "LED": "off"
+///   }
+/// }
+/// ```
+/// 
+/// Refused with `409 Conflict` while `CONFIG_REQUIRE_APPROVAL` is set.
+/// 
+/// Requires the admin role, since the defaults apply to every unconfigured device.
+#[put("/defaults", data = "<request>")]
+pub async fn put_defaults_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    actor: AuditActor,
+    request: Json<DefaultConfigRequest>,
+) -> Result<&'static str, Status> {
+    info!("Received default configuration update");
+
+    match update_defaults(state.inner(), actor, request.into_inner()).await {
+        Ok(_) => {
+            info!("Successfully updated default configuration");
+            Ok("Defaults updated")
+        }
+        Err(e) => {
+            error!("Error updating default configuration: {}", e);
+            Err(e.into())
+        }
+    }
+}
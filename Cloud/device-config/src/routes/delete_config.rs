@@ -0,0 +1,234 @@
+// Configuration Deletion Route Handlers
+//
+// This module handles the DELETE /device-config/devices/<device_id> and
+// POST /device-config/devices/<device_id>/restore endpoints. Deletion is
+// soft: every stored version is kept with a tombstone flag, so an
+// accidental delete can be undone until a new configuration is stored.
+
+use std::collections::HashMap;
+use chrono::Utc;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::{diff_configs, AuditAction, AuditActor, AuditEntry, ConfigChange};
+use crate::domain::config::{ConfigDeletion, ConfigError, DEFAULT_CONFIG_ID};
+use crate::domain::event::ConfigChangeEvent;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::AdminAccess;
+
+/// Reads a device's active configuration, with secret values decrypted
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<Option<(String, HashMap<String, String>)>, ConfigError>` - The active version id and
+///   configuration, if any, or an error
+async fn read_active(state: &AppState, device_id: &str) -> Result<Option<(String, HashMap<String, String>)>, ConfigError> {
+    let current = state.cosmos_client.read_config(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .next();
+
+    current
+        .map(|config| {
+            let values = state.cipher.decrypt_config(&config.config)
+                .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+            Ok((config.version.unwrap_or_default(), values))
+        })
+        .transpose()
+}
+
+/// Records a deletion or restore in the audit log and notifies listeners
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who made the change, for the audit log
+/// * `action` - The kind of mutation to record
+/// * `device_id` - The unique identifier of the device
+/// * `version` - The version deleted or restored
+/// * `changes` - The keys removed or restored
+///
+/// # Returns
+/// * `Result<(), ConfigError>` - Success or an appropriate error
+async fn record(
+    state: &AppState,
+    actor: AuditActor,
+    action: AuditAction,
+    device_id: &str,
+    version: String,
+    changes: Vec<ConfigChange>,
+) -> Result<(), ConfigError> {
+    let entry = AuditEntry::new(
+        device_id.to_string(),
+        action,
+        actor,
+        state.cipher.redact_changes(changes),
+    );
+    state.cosmos_client.insert_audit_entry(&entry)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    state.events.publish(ConfigChangeEvent::from_audit(&entry, version));
+    state.notifier.notify(device_id);
+    Ok(())
+}
+
+/// Soft-deletes every stored version of a device's configuration
+///
+/// This function performs the following steps:
+/// 1. Reads the active configuration to record which keys are removed
+/// 2. Marks every stored version, including scheduled ones, as deleted
+/// 3. Records the deletion in the audit log, with secret values redacted
+/// 4. Publishes a change event and wakes up long-polling devices, which
+///    fall back to the fleet-wide defaults
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who deleted the configuration, for the audit log
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<ConfigDeletion, ConfigError>` - What was deleted or an error
+async fn delete_config(state: &AppState, actor: AuditActor, device_id: &str) -> Result<ConfigDeletion, ConfigError> {
+    info!("Deleting config for device: {}", device_id);
+
+    let (version, previous) = read_active(state, device_id).await?.unzip();
+
+    let deleted_at = Utc::now();
+    let versions = state.cosmos_client.delete_configs(device_id, deleted_at)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    // Return 404 if the device has no configuration left to delete
+    if versions == 0 {
+        return Err(ConfigError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let changes = diff_configs(previous.as_ref(), &HashMap::new());
+    record(state, actor, AuditAction::ConfigDelete, device_id, version.unwrap_or_default(), changes).await?;
+
+    info!("Deleted {} configuration version(s)", versions);
+    Ok(ConfigDeletion {
+        device_id: device_id.to_string(),
+        versions,
+        deleted_at: Some(deleted_at),
+    })
+}
+
+/// Restores every deleted version of a device's configuration
+///
+/// The device is served its latest active version again, as if it had
+/// never been deleted; versions stored since the deletion stay newer.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who restored the configuration, for the audit log
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<ConfigDeletion, ConfigError>` - What was restored or an error
+async fn restore_config(state: &AppState, actor: AuditActor, device_id: &str) -> Result<ConfigDeletion, ConfigError> {
+    info!("Restoring config for device: {}", device_id);
+
+    let (_, previous) = read_active(state, device_id).await?.unzip();
+
+    let versions = state.cosmos_client.restore_configs(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    // Return 404 if nothing was deleted
+    if versions == 0 {
+        return Err(ConfigError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let (version, current) = read_active(state, device_id).await?.unzip();
+    let changes = diff_configs(previous.as_ref(), &current.unwrap_or_default());
+    record(state, actor, AuditAction::ConfigRestore, device_id, version.unwrap_or_default(), changes).await?;
+
+    info!("Restored {} configuration version(s)", versions);
+    Ok(ConfigDeletion {
+        device_id: device_id.to_string(),
+        versions,
+        deleted_at: None,
+    })
+}
+
+/// DELETE endpoint for soft-deleting a device's configuration
+///
+/// Every stored version is kept with a tombstone flag and the time of
+/// deletion. Deleted versions are no longer served, listed or exported;
+/// the device falls back to the fleet-wide defaults. Returns 404 if the
+/// device has no configuration to delete.
+///
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "versions": 3,
+///   "deleted_at": "2026-01-01T00:00:00Z"
+/// }
+/// ```
+///
+/// The reserved device ID `_default` is rejected with 400.
+///
+/// Requires the admin role.
+#[delete("/devices/<device_id>")]
+pub async fn delete_config_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    actor: AuditActor,
+    device_id: String,
+) -> Result<Json<ConfigDeletion>, Status> {
+    info!("Received configuration delete request for device: {}", device_id);
+
+    // The fleet-wide defaults are managed through PUT /device-config/defaults
+    if device_id == DEFAULT_CONFIG_ID {
+        error!("Rejected configuration delete for the reserved device ID {}", DEFAULT_CONFIG_ID);
+        return Err(Status::BadRequest);
+    }
+
+    match delete_config(state.inner(), actor, &device_id).await {
+        Ok(deletion) => Ok(Json(deletion)),
+        Err(e) => {
+            error!("Error deleting configuration: {}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// POST endpoint for restoring a deleted device configuration
+///
+/// Clears the tombstone from every deleted version, so the device is
+/// listed and served its configuration again. Returns 404 if the device
+/// has no deleted configuration.
+///
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "versions": 3
+/// }
+/// ```
+///
+/// Requires the admin role.
+#[post("/devices/<device_id>/restore")]
+pub async fn restore_config_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    actor: AuditActor,
+    device_id: String,
+) -> Result<Json<ConfigDeletion>, Status> {
+    info!("Received configuration restore request for device: {}", device_id);
+
+    match restore_config(state.inner(), actor, &device_id).await {
+        Ok(restored) => Ok(Json(restored)),
+        Err(e) => {
+            error!("Error restoring configuration: {}", e);
+            Err(e.into())
+        }
+    }
+}
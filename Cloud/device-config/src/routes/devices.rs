@@ -0,0 +1,139 @@
+// Device Listing Route Handler
+// 
+// This module handles the GET /device-config/devices endpoint for
+// discovering which devices have a stored configuration and whether they
+// are fetching it.
+
+use std::collections::HashMap;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::config::{ConfigError, DEFAULT_CONFIG_ID};
+use crate::domain::device::{paginate, DeviceFetch, DeviceFilter, DevicePage, DEFAULT_PAGE_SIZE};
+use crate::app_state::AppState;
+use crate::utils::auth_guard::ReadAccess;
+
+/// Lists the configured devices matching a filter
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `filter` - The key/value filter to apply
+/// * `page` - The 1-based page number (default 1)
+/// * `page_size` - Devices per page (default 50, max 500)
+/// 
+/// # Returns
+/// * `Result<DevicePage, ConfigError>` - The requested page of devices or an error
+async fn list_devices(
+    state: &AppState,
+    filter: DeviceFilter,
+    page: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<DevicePage, ConfigError> {
+    info!("Listing devices: {:?}", filter);
+
+    let mut summaries = state.cosmos_client.read_device_summaries()
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    // The fleet-wide defaults are stored like a device but are not one, and
+    // devices whose configuration was deleted are hidden until it is restored
+    summaries.retain(|summary| summary.device_id != DEFAULT_CONFIG_ID && summary.deleted_at.is_none());
+
+    let fetches: HashMap<String, DeviceFetch> = state.cosmos_client.read_device_fetches()
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|fetch| (fetch.device_id.clone(), fetch))
+        .collect();
+
+    let page = paginate(
+        summaries,
+        &fetches,
+        &filter,
+        page.unwrap_or(1),
+        page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+    );
+
+    info!("Listed {} of {} device(s)", page.devices.len(), page.total);
+    Ok(page)
+}
+
+/// Indexes the devices configured before the device index existed
+/// 
+/// Runs once at startup; devices already in the index are left alone, so
+/// only the first start after an upgrade adds any.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+pub async fn backfill_device_index(state: AppState) {
+    match state.cosmos_client.backfill_device_index().await {
+        Ok(0) => {}
+        Ok(count) => info!("Added {} device(s) to the device index", count),
+        Err(e) => error!("Error backfilling the device index: {}", e),
+    }
+}
+
+/// GET endpoint for listing configured devices
+/// 
+/// Returns the devices that have a stored configuration, sorted by device
+/// ID, with the identifier and storage time of their latest version and
+/// when and from where they last fetched their configuration.
+/// `fetched_latest` is false for devices that never fetched their latest
+/// version, including devices that never fetched at all.
+/// 
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `page` - Optional 1-based page number (default 1)
+/// * `page_size` - Optional number of devices per page (default 50, max 500)
+/// * `key` - Only list devices whose configuration has this key
+/// * `value` - Only list devices whose value for `key` equals this (or, without `key`, any key)
+/// 
+/// # Returns
+/// * `Result<Json<DevicePage>, Status>` - A page of devices or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /device-config/devices?key=LED&value=on&page=1&page_size=2
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "devices": [
+///     {
+///       "device_id": "sensor-001",
+///       "version": "\"sensor-001\"-2025-01-01T00:00:00+00:00",
+///       "updated_at": "2025-01-01T00:00:00Z",
+///       "last_fetched_at": "2025-01-01T00:05:00Z",
+///       "last_fetch_ip": "203.0.113.7",
+///       "fetched_latest": true
+///     }
+///   ],
+///   "page": 1,
+///   "page_size": 2,
+///   "total": 1
+/// }
+/// ```
+/// 
+/// Requires the read-only role or higher.
+#[get("/devices?<page>&<page_size>&<key>&<value>")]
+pub async fn list_devices_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    page: Option<usize>,
+    page_size: Option<usize>,
+    key: Option<String>,
+    value: Option<String>,
+) -> Result<Json<DevicePage>, Status> {
+    info!("Received device listing request");
+
+    match list_devices(state.inner(), DeviceFilter { key, value }, page, page_size).await {
+        Ok(page) => Ok(Json(page)),
+        Err(e) => {
+            error!("Error listing devices: {}", e);
+            // Convert the configuration error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// Effective Configuration Route Handler
+//
+// This module handles the GET /device-config/effective/<device_id> endpoint,
+// which shows the configuration a device is served right now with the
+// provenance of every value, the rollouts targeting the device and the
+// versions scheduled to replace it.
+
+use std::collections::HashMap;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::diff_configs;
+use crate::domain::config::{ConfigError, DEFAULT_CONFIG_ID};
+use crate::domain::effective::{resolve_sources, EffectiveConfig, RolloutMembership, ScheduledVersion};
+use crate::domain::overrides::OverrideStatus;
+use crate::domain::secrets::REDACTED_VALUE;
+use crate::app_state::AppState;
+use crate::routes::firmware::firmware_manifest;
+use crate::utils::auth_guard::ReadAccess;
+
+/// Decrypts the secret values of a configuration
+fn decrypt(state: &AppState, config: &HashMap<String, String>) -> Result<HashMap<String, String>, ConfigError> {
+    state.cipher.decrypt_config(config)
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))
+}
+
+/// Resolves the effective configuration of a device
+///
+/// This function performs the following steps:
+/// 1. Reads the device's active configuration, falling back to the
+///    fleet-wide defaults like `get_config` does
+/// 2. Attributes every value to an active override, an applied rollout,
+///    the defaults or the device's own configuration
+/// 3. Lists the rollouts targeting the device and the versions scheduled
+///    to become active, with the keys each would change
+/// 4. Attaches the firmware manifest the device is served
+///
+/// Secret values are redacted throughout.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<EffectiveConfig, ConfigError>` - The resolved configuration or an error
+async fn resolve_effective(state: &AppState, device_id: &str) -> Result<EffectiveConfig, ConfigError> {
+    info!("Resolving effective config for device {:?}", device_id);
+
+    let mut current = state.cosmos_client.read_config(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .next();
+    let mut is_default = false;
+    if current.is_none() && device_id != DEFAULT_CONFIG_ID {
+        current = state.cosmos_client.read_config(DEFAULT_CONFIG_ID)
+            .await
+            .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .next();
+        is_default = current.is_some();
+    }
+
+    let scheduled = state.cosmos_client.read_scheduled_configs(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    // Return 404 if nothing is served to the device, now or later
+    if current.is_none() && scheduled.is_empty() {
+        return Err(ConfigError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let values = current
+        .as_ref()
+        .map(|config| decrypt(state, &config.config))
+        .transpose()?
+        .unwrap_or_default();
+
+    let mut overrides = state.cosmos_client.read_overrides(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    overrides.retain(|item| item.status == OverrideStatus::Active);
+    for item in &mut overrides {
+        item.values = decrypt(state, &item.values)?;
+    }
+
+    let mut rollouts = Vec::new();
+    let mut applied = Vec::new();
+    for mut rollout in state.cosmos_client.read_device_rollouts(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+    {
+        let Some(membership) = RolloutMembership::of(&rollout, device_id) else {
+            continue;
+        };
+        if membership.applied {
+            rollout.config = decrypt(state, &rollout.config)?;
+            applied.push(rollout);
+        }
+        rollouts.push(membership);
+    }
+
+    let sources = resolve_sources(&values, is_default, &overrides, &applied);
+    let config = sources
+        .into_iter()
+        .map(|(key, mut resolved)| {
+            if state.cipher.is_secret(&key) {
+                resolved.value = REDACTED_VALUE.to_string();
+            }
+            (key, resolved)
+        })
+        .collect();
+
+    // Each scheduled version is compared with the one it replaces
+    let mut before = current.as_ref().map(|_| values.clone());
+    let mut versions = Vec::with_capacity(scheduled.len());
+    for version in scheduled {
+        let Some(apply_at) = version.effective_at else {
+            continue;
+        };
+        let plain = decrypt(state, &version.config)?;
+        let changes = state.cipher.redact_changes(diff_configs(before.as_ref(), &plain));
+        versions.push(ScheduledVersion {
+            version: version.version,
+            apply_at,
+            changes,
+        });
+        before = Some(plain);
+    }
+
+    let firmware = firmware_manifest(state, device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .map(|(manifest, _)| manifest);
+
+    let (version, effective_at) = current
+        .map(|config| (config.version, config.effective_at))
+        .unwrap_or_default();
+
+    Ok(EffectiveConfig {
+        device_id: device_id.to_string(),
+        is_default,
+        version,
+        effective_at,
+        config,
+        rollouts,
+        scheduled: versions,
+        firmware,
+    })
+}
+
+/// GET endpoint for the effective configuration of a device
+///
+/// Shows what the device is served and why: every value with its source
+/// (`defaults`, `device`, `rollout` or `override`, with the rollout or
+/// override id), the rollouts targeting the device and whether they
+/// reached it, and the versions scheduled to become active. Returns 404
+/// if the device is served nothing now and nothing is scheduled.
+///
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "is_default": false,
+///   "version": "\"sensor-001\"-2026-01-01T00:00:00+00:00",
+///   "effective_at": "2026-01-01T00:00:00Z",
+///   "config": {
+///     "LED": { "value": "on", "source": "override", "source_id": "8b1d...", "expires_at": "2026-01-01T01:00:00Z" },
+///     "interval": { "value": "30", "source": "rollout", "source_id": "4c0e..." },
+///     "wifi_password": { "value": "********", "source": "device" }
+///   },
+///   "rollouts": [
+///     { "id": "4c0e...", "name": "faster-sampling", "status": "canary", "canary": true, "applied": true }
+///   ],
+///   "scheduled": [
+///     {
+///       "version": "\"sensor-001\"-2026-01-01T00:05:00+00:00",
+///       "apply_at": "2026-01-02T02:00:00Z",
+///       "changes": [{ "key": "interval", "old_value": "30", "new_value": "60" }]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Requires the read-only role or higher.
+#[get("/effective/<device_id>")]
+pub async fn get_effective_config_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    device_id: String,
+) -> Result<Json<EffectiveConfig>, Status> {
+    info!("Received effective config request for device: {:?}", device_id);
+
+    match resolve_effective(state.inner(), &device_id).await {
+        Ok(effective) => Ok(Json(effective)),
+        Err(e) => {
+            error!("Error resolving effective configuration: {}", e);
+            // Convert the configuration error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
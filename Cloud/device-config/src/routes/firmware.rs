@@ -0,0 +1,266 @@
+// Firmware Update Route Handlers
+//
+// This module handles the /device-config/firmware endpoints, which register
+// firmware images and assign target versions to devices. The assigned
+// image's manifest is served with each device's configuration, so devices
+// learn about updates through their regular configuration polling.
+
+use std::collections::BTreeSet;
+use chrono::{DateTime, Utc};
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::{AuditAction, AuditActor, AuditEntry, ConfigChange};
+use crate::domain::config::DEFAULT_CONFIG_ID;
+use crate::domain::firmware::{
+    FirmwareAssignment, FirmwareAssignmentRequest, FirmwareError, FirmwareImage, FirmwareImageRequest,
+    FirmwareManifest,
+};
+use crate::app_state::AppState;
+use crate::utils::auth_guard::{AdminAccess, OperatorAccess, ReadAccess};
+
+/// Registers a firmware image
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `request` - The image registration request
+///
+/// # Returns
+/// * `Result<FirmwareImage, FirmwareError>` - The registered image or an error
+async fn register_image(state: &AppState, request: FirmwareImageRequest) -> Result<FirmwareImage, FirmwareError> {
+    let image = FirmwareImage::parse(request)?;
+    info!("Registering firmware version {}", image.version);
+
+    // Registered images are immutable; devices may already be installing them
+    if read_image(state, &image.version).await?.is_some() {
+        return Err(FirmwareError::ImageExists(image.version));
+    }
+
+    state.cosmos_client.insert_firmware_image(&image)
+        .await
+        .map_err(|e| FirmwareError::DatabaseError(e.to_string()))?;
+
+    info!("Firmware version {} registered", image.version);
+    Ok(image)
+}
+
+/// Reads a registered firmware image
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `version` - The version of the image
+///
+/// # Returns
+/// * `Result<Option<FirmwareImage>, FirmwareError>` - The image if registered, or an error
+async fn read_image(state: &AppState, version: &str) -> Result<Option<FirmwareImage>, FirmwareError> {
+    state.cosmos_client.read_firmware_image(version)
+        .await
+        .map_err(|e| FirmwareError::DatabaseError(e.to_string()))
+}
+
+/// Reads the firmware assignment of a device, falling back to the fleet-wide target
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<Option<FirmwareAssignment>, FirmwareError>` - The assignment that applies, if any, or an error
+async fn resolve_assignment(state: &AppState, device_id: &str) -> Result<Option<FirmwareAssignment>, FirmwareError> {
+    let assignment = state.cosmos_client.read_firmware_assignment(device_id)
+        .await
+        .map_err(|e| FirmwareError::DatabaseError(e.to_string()))?;
+    if assignment.is_some() || device_id == DEFAULT_CONFIG_ID {
+        return Ok(assignment);
+    }
+
+    state.cosmos_client.read_firmware_assignment(DEFAULT_CONFIG_ID)
+        .await
+        .map_err(|e| FirmwareError::DatabaseError(e.to_string()))
+}
+
+/// Resolves the firmware manifest served to a device with its configuration
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<Option<(FirmwareManifest, DateTime<Utc>)>, FirmwareError>` - The manifest and when it
+///   was assigned, if a version is assigned, or an error
+pub(crate) async fn firmware_manifest(
+    state: &AppState,
+    device_id: &str,
+) -> Result<Option<(FirmwareManifest, DateTime<Utc>)>, FirmwareError> {
+    let Some(assignment) = resolve_assignment(state, device_id).await? else {
+        return Ok(None);
+    };
+
+    let image = read_image(state, &assignment.version).await?;
+    Ok(image.map(|image| (image.manifest(), assignment.assigned_at)))
+}
+
+/// Assigns a firmware version to a group of devices
+///
+/// Each assignment replaces the device's previous one, is recorded in the
+/// audit log and wakes long-polling requests for the device.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who made the assignment, for the audit log
+/// * `request` - The version and devices to assign it to
+///
+/// # Returns
+/// * `Result<Vec<FirmwareAssignment>, FirmwareError>` - The stored assignments or an error
+async fn assign_firmware(
+    state: &AppState,
+    actor: AuditActor,
+    request: FirmwareAssignmentRequest,
+) -> Result<Vec<FirmwareAssignment>, FirmwareError> {
+    request.validate()?;
+    let version = request.version.trim();
+    info!("Assigning firmware version {} to {} device(s)", version, request.devices.len());
+
+    if read_image(state, version).await?.is_none() {
+        return Err(FirmwareError::ImageNotFound(version.to_string()));
+    }
+
+    let devices: BTreeSet<&str> = request.devices.iter().map(|d| d.trim()).collect();
+    let mut assignments = Vec::with_capacity(devices.len());
+    for device_id in devices {
+        let previous = state.cosmos_client.read_firmware_assignment(device_id)
+            .await
+            .map_err(|e| FirmwareError::DatabaseError(e.to_string()))?;
+
+        let assignment = FirmwareAssignment::new(device_id, version);
+        state.cosmos_client.upsert_firmware_assignment(&assignment)
+            .await
+            .map_err(|e| FirmwareError::DatabaseError(e.to_string()))?;
+
+        let change = ConfigChange {
+            key: "firmware_version".to_string(),
+            old_value: previous.map(|previous| previous.version),
+            new_value: Some(assignment.version.clone()),
+        };
+        let entry = AuditEntry::new(device_id.to_string(), AuditAction::FirmwareAssign, actor.clone(), vec![change]);
+        state.cosmos_client.insert_audit_entry(&entry)
+            .await
+            .map_err(|e| FirmwareError::DatabaseError(e.to_string()))?;
+
+        state.notifier.notify(device_id);
+        assignments.push(assignment);
+    }
+
+    info!("Firmware version {} assigned", version);
+    Ok(assignments)
+}
+
+/// Converts a firmware result into a route response, logging the outcome
+fn respond<T>(result: Result<T, FirmwareError>) -> Result<Json<T>, Status> {
+    match result {
+        Ok(value) => Ok(Json(value)),
+        Err(e) => {
+            error!("Firmware request failed: {}", e);
+            // Convert the firmware error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
+
+/// POST endpoint for registering a firmware image
+///
+/// The service does not host images; it records where devices download
+/// them from and how to verify them. Registering a version that already
+/// exists returns `409 Conflict`.
+///
+/// # Example Request
+/// ```json
+/// {
+///   "version": "1.4.0",
+///   "url": "https://firmware.example.com/rp-rot/1.4.0.bin",
+///   "sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+///   "signature": "q2V1...Ag=="
+/// }
+/// ```
+///
+/// Requires the admin role.
+#[post("/firmware", format = "json", data = "<request>")]
+pub async fn register_firmware_route(
+    state: &State<AppState>,
+    _access: AdminAccess,
+    request: Json<FirmwareImageRequest>,
+) -> Result<Json<FirmwareImage>, Status> {
+    respond(register_image(state.inner(), request.into_inner()).await)
+}
+
+/// GET endpoint for listing registered firmware images, newest first
+///
+/// Requires the read-only role or higher.
+#[get("/firmware")]
+pub async fn list_firmware_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+) -> Result<Json<Vec<FirmwareImage>>, Status> {
+    let images = state.cosmos_client.read_firmware_images()
+        .await
+        .map_err(|e| FirmwareError::DatabaseError(e.to_string()));
+    respond(images)
+}
+
+/// GET endpoint for retrieving a registered firmware image
+///
+/// Requires the read-only role or higher.
+#[get("/firmware/<version>")]
+pub async fn get_firmware_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    version: String,
+) -> Result<Json<FirmwareImage>, Status> {
+    let image = read_image(state.inner(), &version)
+        .await
+        .and_then(|image| image.ok_or(FirmwareError::ImageNotFound(version)));
+    respond(image)
+}
+
+/// POST endpoint for assigning a firmware version to a group of devices
+///
+/// The version must already be registered. Listing `_default` sets the
+/// fleet-wide target, used by devices without an assignment of their own.
+///
+/// # Example Request
+/// ```json
+/// {
+///   "version": "1.4.0",
+///   "devices": ["sensor-001", "sensor-002"]
+/// }
+/// ```
+///
+/// Requires the operator role or higher.
+#[post("/firmware/assign", format = "json", data = "<request>")]
+pub async fn assign_firmware_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    request: Json<FirmwareAssignmentRequest>,
+) -> Result<Json<Vec<FirmwareAssignment>>, Status> {
+    respond(assign_firmware(state.inner(), actor, request.into_inner()).await)
+}
+
+/// GET endpoint for retrieving the firmware version assigned to a device
+///
+/// Returns the device's own assignment, or the fleet-wide target if it has
+/// none, or 404 if neither exists.
+///
+/// Requires the read-only role or higher.
+#[get("/firmware/assignments/<device_id>")]
+pub async fn get_firmware_assignment_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    device_id: String,
+) -> Result<Json<FirmwareAssignment>, Status> {
+    let assignment = resolve_assignment(state.inner(), &device_id)
+        .await
+        .and_then(|assignment| assignment.ok_or(FirmwareError::AssignmentNotFound(device_id)));
+    respond(assignment)
+}
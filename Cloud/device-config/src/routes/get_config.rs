@@ -1,63 +1,283 @@
 // Configuration Retrieval Route Handler
-// 
+//
 // This module handles the GET /device-config/get/<device_id> endpoint for
-// retrieving device configuration data from the database.
+// retrieving device configuration data from the database. It supports
+// conditional requests (ETag / If-None-Match) and long polling so devices
+// do not re-download an unchanged configuration, and sets caching headers
+// so a CDN or reverse proxy can absorb fleet polling. Every answered fetch
+// is recorded so operators can tell which devices are polling.
 
+use std::net::IpAddr;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
 use rocket::serde::json::Json;
 use rocket::{State, http::Status};
-use tracing::{info, error};
+use tokio::time::Instant;
+use tracing::{info, error, warn};
 
-use crate::domain::config::Config;
+use crate::domain::config::{config_etag, Config, DEFAULT_CONFIG_ID};
 use crate::domain::config::ConfigError;
+use crate::domain::device::DeviceFetch;
 use crate::app_state::AppState;
+use crate::routes::firmware::firmware_manifest;
+use crate::utils::auth_guard::DeviceAccess;
+use crate::utils::conditional::{http_date, IfNoneMatch};
+
+/// Longest time a long-polling request is held open, in seconds
+const MAX_WAIT_SECS: u64 = 60;
+
+/// How often a long-polling request re-reads the database
+///
+/// Changes stored by this instance wake waiters immediately; the periodic
+/// re-check catches changes stored by other instances and scheduled
+/// versions becoming active.
+const RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Caching metadata of a served configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheHeaders {
+    /// Entity tag of the configuration, sent as `ETag`
+    pub etag: String,
+    /// Value of the `Cache-Control` header
+    pub cache_control: String,
+    /// When the configuration or firmware assignment last changed, if known
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+impl CacheHeaders {
+    /// Adds the caching headers to a response
+    fn apply<'r>(self, mut builder: response::Builder<'r>) -> response::Builder<'r> {
+        builder
+            .raw_header("ETag", format!("\"{}\"", self.etag))
+            .raw_header("Cache-Control", self.cache_control);
+        if let Some(last_modified) = self.last_modified {
+            builder.raw_header("Last-Modified", http_date(last_modified));
+        }
+        builder
+    }
+}
+
+/// Response of the configuration endpoint
+///
+/// The configuration variants carry the entity tag, cache lifetime and
+/// modification time of the current configuration in `ETag`,
+/// `Cache-Control` and `Last-Modified` headers.
+#[derive(Debug)]
+pub enum ConfigResponse {
+    /// The configuration differs from what the caller holds
+    Modified { configs: Vec<Config>, cache: CacheHeaders },
+    /// The caller already holds the current configuration (304)
+    NotModified { cache: CacheHeaders },
+    /// The device fetched too often (429), with the seconds to wait in `Retry-After`
+    RateLimited { retry_after: u64 },
+}
+
+impl<'r> Responder<'r, 'static> for ConfigResponse {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        match self {
+            ConfigResponse::Modified { configs, cache } => {
+                cache.apply(Response::build_from(Json(configs).respond_to(request)?)).ok()
+            }
+            ConfigResponse::NotModified { cache } => {
+                let mut builder = Response::build();
+                builder.status(Status::NotModified);
+                cache.apply(builder).ok()
+            }
+            ConfigResponse::RateLimited { retry_after } => Response::build()
+                .status(Status::TooManyRequests)
+                .raw_header("Retry-After", retry_after.to_string())
+                .ok(),
+        }
+    }
+}
 
 /// Retrieves configuration data for a specific device from the database
-/// 
-/// This function queries the Cosmos DB container for all configuration
-/// records associated with the given device ID. It uses the device_id
-/// as the partition key for efficient querying.
-/// 
+///
+/// This function queries the Cosmos DB container for the currently active
+/// configuration record associated with the given device ID. Versions
+/// scheduled for a future `apply_at` time are not returned until they
+/// become active. It uses the device_id as the partition key for efficient querying.
+/// Devices without a configuration of their own are served the fleet-wide
+/// defaults, flagged with `is_default`.
+/// Secret values are decrypted, and when a signing key is configured, each
+/// configuration is signed over its plaintext before being returned. If a
+/// firmware version is assigned to the device or the fleet, its manifest
+/// is attached under `firmware`. Each configuration carries its version
+/// number, when it took effect and who stored it.
+///
 /// # Arguments
 /// * `state` - Application state containing the database client
 /// * `device_id` - The unique identifier of the device
-/// 
+///
 /// # Returns
-/// * `Result<Vec<Config>, ConfigError>` - List of configuration records or an error
-async fn get_config(state: &AppState, device_id: String) -> Result<Vec<Config>, ConfigError> {
+/// * `Result<(Vec<Config>, Option<DateTime<Utc>>), ConfigError>` - List of configuration
+///   records and when they or the firmware assignment last changed, or an error
+async fn get_config(state: &AppState, device_id: &str) -> Result<(Vec<Config>, Option<DateTime<Utc>>), ConfigError> {
     info!("Getting config: {:?}", device_id);
 
     // Query the database for configuration data for the specified device
-    let config = state.cosmos_client.read_config(&device_id)
+    let mut config = state.cosmos_client.read_config(device_id)
         .await
         .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
 
-    // Return 404 if no configuration data is found for the device
+    // Fall back to the fleet-wide defaults for devices without a configuration
+    if config.is_empty() && device_id != DEFAULT_CONFIG_ID {
+        config = state.cosmos_client.read_config(DEFAULT_CONFIG_ID)
+            .await
+            .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .map(|defaults| defaults.as_default_for(device_id))
+            .collect();
+    }
+
+    // Return 404 if neither the device nor the fleet has a configuration
     if config.is_empty() {
-        return Err(ConfigError::DeviceNotFound(device_id));
+        return Err(ConfigError::DeviceNotFound(device_id.to_string()));
+    }
+
+    // Decrypt secret values for the authorized caller
+    for item in &mut config {
+        item.config = state.cipher.decrypt_config(&item.config)
+            .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    }
+
+    // Tell the device which firmware it should be running
+    let firmware = firmware_manifest(state, device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    for item in &mut config {
+        item.last_modified = item.effective_at;
+    }
+    let mut last_modified = config.iter().filter_map(|item| item.effective_at).max();
+    if let Some((manifest, assigned_at)) = firmware {
+        last_modified = last_modified.max(Some(assigned_at));
+        for item in &mut config {
+            item.firmware = Some(manifest.clone());
+        }
+    }
+
+    // Sign the served configurations so devices can verify them
+    if let Some(signer) = &state.signer {
+        for item in &mut config {
+            item.signature = Some(signer.sign(item));
+        }
     }
 
     info!("Config retrieved successfully");
-    Ok(config)
+    Ok((config, last_modified))
+}
+
+/// Records that a device fetched its configuration
+///
+/// Tracking is best effort: a failure is logged and does not fail the fetch.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `configs` - The configuration served, or held by the device on a 304
+/// * `source_ip` - Client IP address of the fetch, if known
+async fn record_fetch(state: &AppState, device_id: &str, configs: &[Config], source_ip: Option<String>) {
+    let version = configs
+        .first()
+        .filter(|config| !config.is_default)
+        .and_then(|config| config.version.clone());
+    let fetch = DeviceFetch::new(device_id.to_string(), source_ip, version);
+
+    if let Err(e) = state.cosmos_client.upsert_device_fetch(&fetch).await {
+        warn!("Failed to record config fetch for device {}: {}", device_id, e);
+    }
+}
+
+/// Retrieves the configuration unless the caller already holds it
+///
+/// If the current configuration matches one of the caller's tags and a
+/// wait time was given, the request is held until the configuration
+/// changes or the wait time elapses.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `known` - Entity tags the caller already holds
+/// * `wait` - Seconds to wait for a change (capped at `MAX_WAIT_SECS`)
+/// * `source_ip` - Client IP address of the caller, recorded with the fetch
+///
+/// # Returns
+/// * `Result<ConfigResponse, ConfigError>` - The configuration, a not-modified marker, or an error
+async fn get_config_if_changed(
+    state: &AppState,
+    device_id: &str,
+    known: &IfNoneMatch,
+    wait: Option<u64>,
+    source_ip: Option<String>,
+) -> Result<ConfigResponse, ConfigError> {
+    let deadline = Instant::now() + Duration::from_secs(wait.unwrap_or(0).min(MAX_WAIT_SECS));
+
+    loop {
+        let (configs, last_modified) = get_config(state, device_id).await?;
+        let has_secrets = configs
+            .iter()
+            .any(|config| config.config.keys().any(|key| state.cipher.is_secret(key)));
+        let cache = CacheHeaders {
+            etag: config_etag(&configs),
+            cache_control: state.cache_policy.cache_control(has_secrets, state.auth.is_enabled()),
+            last_modified,
+        };
+
+        if !known.matches(&cache.etag) {
+            record_fetch(state, device_id, &configs, source_ip).await;
+            return Ok(ConfigResponse::Modified { configs, cache });
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            record_fetch(state, device_id, &configs, source_ip).await;
+            return Ok(ConfigResponse::NotModified { cache });
+        }
+
+        state.notifier
+            .wait_for_change(device_id, remaining.min(RECHECK_INTERVAL))
+            .await;
+    }
 }
 
 /// GET endpoint for retrieving device configuration data
-/// 
+///
 /// This endpoint retrieves all configuration data for a specific device
 /// from the database. The endpoint expects a device ID as a path parameter
-/// and returns a JSON array of configuration records.
-/// 
+/// and returns a JSON array of configuration records with an `ETag` header.
+///
+/// Conditional requests:
+/// - `If-None-Match: "<etag>"` or `?version=<etag>` returns 304 Not Modified
+///   when the configuration is unchanged
+/// - `?wait=<seconds>` (max 60) additionally holds the request open until
+///   the configuration changes, returning 304 if it does not
+///
+/// Each device may fetch `CONFIG_FETCH_RATE_LIMIT` times per
+/// `CONFIG_FETCH_RATE_WINDOW_SECS` (60 per minute by default); beyond that
+/// the endpoint answers 429 Too Many Requests with a `Retry-After` header.
+///
+/// The time, client IP and version of every 200 or 304 answer is recorded
+/// and reported by the device listing.
+///
 /// # Arguments
 /// * `state` - Application state injected by Rocket
 /// * `device_id` - The device identifier from the URL path
-/// 
+/// * `version` - Entity tag the caller already holds, as an alternative to If-None-Match
+/// * `wait` - Long-poll time in seconds
+/// * `if_none_match` - Entity tags from the If-None-Match header
+/// * `client_ip` - Client IP address, recorded with the fetch
+///
 /// # Returns
-/// * `Result<Json<Vec<Config>>, Status>` - JSON array of configurations or HTTP error status
-/// 
+/// * `Result<ConfigResponse, Status>` - JSON array of configurations, 304, 429, or HTTP error status
+///
 /// # Example Request
 /// ```bash
-/// GET /device-config/get/sensor-001
+/// GET /device-config/get/sensor-001?wait=30
+/// If-None-Match: "9f3c1a2b4d5e6f70"
 /// ```
-/// 
+///
 /// # Example Response
 /// ```json
 /// [
@@ -66,22 +286,56 @@ async fn get_config(state: &AppState, device_id: String) -> Result<Vec<Config>,
 ///     "config": {
 ///       "sampling_rate": "1000",
 ///       "threshold": "25.5"
+///     },
+///     "version_number": 7,
+///     "last_modified": "2026-01-01T00:00:00Z",
+///     "modified_by": "ci-pipeline",
+///     "signature": {
+///       "algorithm": "ed25519",
+///       "key_id": "3b6a27bcceb6a42d",
+///       "signature": "q2V1...Ag=="
+///     },
+///     "firmware": {
+///       "version": "1.4.0",
+///       "url": "https://firmware.example.com/rp-rot/1.4.0.bin",
+///       "sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+///       "signature": "3q2+...Bw=="
 ///     }
 ///   }
 /// ]
 /// ```
-#[get("/get/<device_id>")]
+///
+/// A device without a configuration of its own receives the fleet-wide
+/// defaults with `"is_default": true`, or 404 if no defaults are stored.
+///
+/// Requires the device's own key (see `DEVICE_KEYS`) or the read-only role
+/// or higher.
+#[get("/get/<device_id>?<version>&<wait>")]
 pub async fn get_config_route(
-    state: &State<AppState>, 
-    device_id: String
-) -> Result<Json<Vec<Config>>, Status> {
+    state: &State<AppState>,
+    _access: DeviceAccess,
+    if_none_match: IfNoneMatch,
+    client_ip: Option<IpAddr>,
+    device_id: String,
+    version: Option<String>,
+    wait: Option<u64>,
+) -> Result<ConfigResponse, Status> {
     info!("Received config request for device: {:?}", device_id);
 
+    // Reject devices polling faster than allowed before touching the database
+    if let Err(retry_after) = state.fetch_limiter.check(&device_id) {
+        warn!("Rate limited config request for device {:?}, retry after {}s", device_id, retry_after);
+        return Ok(ConfigResponse::RateLimited { retry_after });
+    }
+
+    let known = if_none_match.with(version);
+
     // Retrieve the configuration data and handle any errors
-    match get_config(state.inner(), device_id).await {
-        Ok(config) => {
+    let source_ip = client_ip.map(|ip| ip.to_string());
+    match get_config_if_changed(state.inner(), &device_id, &known, wait, source_ip).await {
+        Ok(response) => {
             info!("Successfully retrieved configuration data");
-            Ok(Json(config))
+            Ok(response)
         }
         Err(e) => {
             error!("Error retrieving configuration: {}", e);
@@ -89,4 +343,4 @@ pub async fn get_config_route(
             Err(e.into())
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,237 @@
+// Configuration History Route Handlers
+//
+// This module handles the GET /device-config/history/<device_id> endpoint
+// for listing the stored versions of a device's configuration, and the
+// POST /device-config/history/<device_id>/<version_number>/rollback
+// endpoint for storing an earlier version again.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::{AuditAction, AuditActor};
+use crate::domain::config::{Config, ConfigError, DEFAULT_CONFIG_ID};
+use crate::domain::history::{config_history, ConfigVersion};
+use crate::app_state::AppState;
+use crate::routes::approvals::submit_config;
+use crate::routes::update_config::{apply_config, UpdateResponse};
+use crate::utils::auth_guard::{OperatorAccess, ReadAccess};
+
+/// Number of versions returned when no limit is given
+const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// Upper bound on the number of versions returned by a single query
+const MAX_HISTORY_LIMIT: usize = 100;
+
+/// Retrieves the stored versions of a device's configuration
+///
+/// This function performs the following steps:
+/// 1. Reads one version more than requested, so the last listed version
+///    can be compared to the one before it
+/// 2. Decrypts secret values to compute the changes of each version
+/// 3. Flags the version currently served to the device
+/// 4. Redacts secret values from the returned versions and changes
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `limit` - Maximum number of versions to return (capped at 100)
+///
+/// # Returns
+/// * `Result<Vec<ConfigVersion>, ConfigError>` - The versions, newest first, or an error
+async fn get_history(
+    state: &AppState,
+    device_id: &str,
+    limit: Option<usize>,
+) -> Result<Vec<ConfigVersion>, ConfigError> {
+    info!("Getting config history for device: {}", device_id);
+
+    let limit = limit.unwrap_or(DEFAULT_HISTORY_LIMIT).clamp(1, MAX_HISTORY_LIMIT);
+
+    let mut versions = state.cosmos_client.read_config_history(device_id, limit + 1)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    for version in versions.iter_mut() {
+        version.config = state.cipher.decrypt_config(&version.config)
+            .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    }
+
+    let active = state.cosmos_client.read_config(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .next()
+        .and_then(|current| current.version_number);
+
+    let history: Vec<ConfigVersion> = config_history(&versions, limit, active)
+        .into_iter()
+        .map(|mut version| {
+            version.config = state.cipher.redact_config(&version.config);
+            version.changes = state.cipher.redact_changes(version.changes);
+            version
+        })
+        .collect();
+
+    info!("Config history retrieved successfully ({} versions)", history.len());
+    Ok(history)
+}
+
+/// Reads a stored version of a device's configuration, with secret values decrypted
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `version_number` - The number of the version to roll back to
+///
+/// # Returns
+/// * `Result<Config, ConfigError>` - The version as a new configuration to store, or
+///   a not found error if the version does not exist
+async fn read_rollback_target(
+    state: &AppState,
+    device_id: &str,
+    version_number: u64,
+) -> Result<Config, ConfigError> {
+    let version = state.cosmos_client.read_config_version(device_id, version_number)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ConfigError::DeviceNotFound(format!("{} version {}", device_id, version_number)))?;
+
+    let values = state.cipher.decrypt_config(&version.config)
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    Ok(Config::new(device_id.to_string(), values))
+}
+
+/// GET endpoint for listing the stored versions of a device's configuration
+///
+/// Returns the versions newest first, including ones scheduled to become
+/// active later, each with the keys it changed compared to the version
+/// stored before it. Secret values are redacted. An empty array is
+/// returned when the device has no stored configuration.
+///
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `device_id` - The unique identifier of the device from the URL path
+/// * `limit` - Optional maximum number of versions (default 20, max 100)
+///
+/// # Returns
+/// * `Result<Json<Vec<ConfigVersion>>, Status>` - JSON array of versions or HTTP error status
+///
+/// # Example Request
+/// ```bash
+/// GET /device-config/history/sensor-001?limit=10
+/// ```
+///
+/// # Example Response
+/// ```json
+/// [
+///   {
+///     "version_number": 2,
+///     "config": { "LED": "on", "wifi_password": "********" },
+///     "effective_at": "2025-01-02T00:00:00Z",
+///     "modified_by": "operator@example.com",
+///     "active": true,
+///     "changes": [
+///       { "key": "LED", "old_value": "off", "new_value": "on" }
+///     ]
+///   }
+/// ]
+/// ```
+///
+/// Requires the read role or higher.
+#[get("/history/<device_id>?<limit>")]
+pub async fn get_history_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    device_id: String,
+    limit: Option<usize>,
+) -> Result<Json<Vec<ConfigVersion>>, Status> {
+    info!("Received config history request for device: {}", device_id);
+
+    match get_history(state.inner(), &device_id, limit).await {
+        Ok(history) => Ok(Json(history)),
+        Err(e) => {
+            error!("Error retrieving config history: {}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// POST endpoint for rolling a device back to an earlier configuration version
+///
+/// Stores the values of the given version again as a new version, active
+/// immediately, so the history keeps every change. Secret values are
+/// carried over from the stored version. The rollback is recorded in the
+/// audit log and published like any other update. Returns 404 if the
+/// version does not exist or was deleted.
+///
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `actor` - Caller identity and origin, recorded in the audit log
+/// * `device_id` - The unique identifier of the device from the URL path
+/// * `version_number` - The number of the version to roll back to
+///
+/// # Returns
+/// * `Result<UpdateResponse, Status>` - Success message, pending change or HTTP error status
+///
+/// # Example Request
+/// ```bash
+/// POST /device-config/history/sensor-001/3/rollback
+/// ```
+///
+/// # Example Response
+/// ```text
+/// Config rolled back
+/// ```
+///
+/// When `CONFIG_REQUIRE_APPROVAL` is set, the rollback is held for
+/// approval like an update and returned with 202 Accepted.
+///
+/// The reserved device ID `_default` is rejected with 400.
+///
+/// Requires the operator role or higher.
+#[post("/history/<device_id>/<version_number>/rollback")]
+pub async fn rollback_config_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    device_id: String,
+    version_number: u64,
+) -> Result<UpdateResponse, Status> {
+    info!("Received rollback request for device {} to version {}", device_id, version_number);
+
+    // The fleet-wide defaults are managed through PUT /device-config/defaults
+    if device_id == DEFAULT_CONFIG_ID {
+        error!("Rejected configuration rollback for the reserved device ID {}", DEFAULT_CONFIG_ID);
+        return Err(Status::BadRequest);
+    }
+
+    let config = match read_rollback_target(state.inner(), &device_id, version_number).await {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Error reading configuration version: {}", e);
+            return Err(e.into());
+        }
+    };
+
+    // Hold the rollback for review when four-eyes approval is required
+    if state.approval_required {
+        return match submit_config(state.inner(), actor, config).await {
+            Ok(approval) => Ok(UpdateResponse::Pending(Box::new(Json(approval)))),
+            Err(e) => {
+                error!("Error submitting rollback for approval: {}", e);
+                Err(e.into())
+            }
+        };
+    }
+
+    match apply_config(state.inner(), actor, AuditAction::ConfigRollback, Json(config)).await {
+        Ok(_) => {
+            info!("Rolled back device {} to version {}", device_id, version_number);
+            Ok(UpdateResponse::Applied("Config rolled back"))
+        }
+        Err(e) => {
+            error!("Error rolling back configuration: {}", e);
+            Err(e.into())
+        }
+    }
+}
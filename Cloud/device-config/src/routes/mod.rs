@@ -5,7 +5,35 @@
 
 pub mod update_config;
 pub mod get_config;
+pub mod rollouts;
+pub mod audit;
+pub mod signing_key;
+pub mod devices;
+pub mod defaults;
+pub mod validate;
+pub mod bulk;
+pub mod firmware;
+pub mod overrides;
+pub mod approvals;
+pub mod effective;
+pub mod delete_config;
+pub mod history;
+pub mod reported;
 
 // Re-export route handlers for convenient access
 pub use update_config::*;
-pub use get_config::*;
\ No newline at end of file
+pub use get_config::*;
+pub use rollouts::*;
+pub use audit::*;
+pub use signing_key::*;
+pub use devices::*;
+pub use defaults::*;
+pub use validate::*;
+pub use bulk::*;
+pub use firmware::*;
+pub use overrides::*;
+pub use approvals::*;
+pub use effective::*;
+pub use delete_config::*;
+pub use history::*;
+pub use reported::*;
\ No newline at end of file
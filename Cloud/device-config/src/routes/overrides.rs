@@ -0,0 +1,336 @@
+// Temporary Configuration Override Route Handlers
+//
+// This module handles the /device-config/overrides endpoints, which apply
+// configuration values for a limited time and revert them early, and the
+// background sweep that reverts overrides once they expire.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use chrono::Utc;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, warn, error};
+
+use crate::domain::audit::{AuditAction, AuditActor};
+use crate::domain::config::{Config, ConfigError};
+use crate::domain::overrides::{revert_values, ConfigOverride, OverrideError, OverrideRequest, OverrideStatus};
+use crate::app_state::AppState;
+use crate::routes::update_config::apply_config;
+use crate::utils::auth_guard::{OperatorAccess, ReadAccess};
+
+/// How often expired overrides are looked for
+pub const OVERRIDE_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Subject recorded in the audit log for reverts on expiry
+const EXPIRY_SUBJECT: &str = "system:override-expiry";
+
+/// Converts an error from the configuration update path into an override error
+fn config_error(error: ConfigError) -> OverrideError {
+    match error {
+        ConfigError::DatabaseError(msg) => OverrideError::DatabaseError(msg),
+        ConfigError::ApprovalRequired => OverrideError::ApprovalRequired,
+        other => OverrideError::ConfigRejected(other.to_string()),
+    }
+}
+
+/// Decrypts the secret values of a configuration
+fn decrypt(state: &AppState, config: &HashMap<String, String>) -> Result<HashMap<String, String>, OverrideError> {
+    state.cipher.decrypt_config(config)
+        .map_err(|e| OverrideError::DatabaseError(e.to_string()))
+}
+
+/// Reads a device's active configuration in plaintext
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<Option<HashMap<String, String>>, OverrideError>` - The configuration, if any, or an error
+async fn read_current(state: &AppState, device_id: &str) -> Result<Option<HashMap<String, String>>, OverrideError> {
+    let current = state.cosmos_client.read_config(device_id)
+        .await
+        .map_err(|e| OverrideError::DatabaseError(e.to_string()))?;
+
+    current
+        .first()
+        .map(|config| decrypt(state, &config.config))
+        .transpose()
+}
+
+/// Reads the overrides of a device, newest first
+async fn read_overrides(state: &AppState, device_id: &str) -> Result<Vec<ConfigOverride>, OverrideError> {
+    state.cosmos_client.read_overrides(device_id)
+        .await
+        .map_err(|e| OverrideError::DatabaseError(e.to_string()))
+}
+
+/// Applies a temporary override to a device's configuration
+///
+/// This function performs the following steps:
+/// 1. Validates the request and merges the override values into the
+///    device's current configuration
+/// 2. Remembers the values the overridden keys had, so they can be restored
+/// 3. Stores the merged configuration through the regular update path,
+///    so it is encrypted, audited and published as usual
+/// 4. Persists the override, secret values encrypted
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who applied the override, for the audit log
+/// * `request` - The override request
+///
+/// # Returns
+/// * `Result<ConfigOverride, OverrideError>` - The stored override or an error
+async fn create_override(
+    state: &AppState,
+    actor: AuditActor,
+    request: OverrideRequest,
+) -> Result<ConfigOverride, OverrideError> {
+    // Overrides would change the served configuration without review
+    if state.approval_required {
+        return Err(OverrideError::ApprovalRequired);
+    }
+
+    let ttl = request.validate()?;
+    info!("Overriding {} key(s) of device {} for {}s", request.config.len(), request.device_id, request.ttl_secs);
+
+    let current = read_current(state, &request.device_id)
+        .await?
+        .ok_or_else(|| OverrideError::DeviceNotFound(request.device_id.clone()))?;
+
+    // Overlapping overrides could not all be reverted correctly
+    for active in read_overrides(state, &request.device_id).await? {
+        if active.status != OverrideStatus::Active {
+            continue;
+        }
+        let mut keys: Vec<&String> = request.config.keys().filter(|key| active.values.contains_key(*key)).collect();
+        keys.sort();
+        if let Some(key) = keys.first() {
+            return Err(OverrideError::KeyOverridden(key.to_string()));
+        }
+    }
+
+    let previous: HashMap<String, String> = request.config
+        .keys()
+        .filter_map(|key| current.get(key).map(|value| (key.clone(), value.clone())))
+        .collect();
+    let mut merged = current;
+    merged.extend(request.config.clone());
+    Config::parse(request.device_id.clone(), merged.clone(), &state.limits).map_err(config_error)?;
+
+    let encrypt = |config: &HashMap<String, String>| state.cipher
+        .encrypt_config(config)
+        .map_err(|e| OverrideError::DatabaseError(e.to_string()));
+    let item = ConfigOverride::new(
+        request.device_id.clone(),
+        encrypt(&request.config)?,
+        encrypt(&previous)?,
+        actor.subject.clone(),
+        ttl,
+    );
+
+    apply_config(state, actor, AuditAction::OverrideCreate, Json(Config::new(request.device_id, merged)))
+        .await
+        .map_err(config_error)?;
+
+    state.cosmos_client.upsert_override(&item)
+        .await
+        .map_err(|e| OverrideError::DatabaseError(e.to_string()))?;
+
+    info!("Override {} applied until {}", item.id, item.expires_at);
+    Ok(item)
+}
+
+/// Reverts an override, restoring the previous value of every overridden
+/// key that still holds the override's value
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who reverted the override, for the audit log
+/// * `item` - The override to revert
+/// * `status` - `Expired` or `Reverted`
+///
+/// # Returns
+/// * `Result<ConfigOverride, OverrideError>` - The finished override or an error
+async fn revert_override(
+    state: &AppState,
+    actor: AuditActor,
+    mut item: ConfigOverride,
+    status: OverrideStatus,
+) -> Result<ConfigOverride, OverrideError> {
+    if item.status != OverrideStatus::Active {
+        return Err(OverrideError::NotActive(item.id));
+    }
+
+    if let Some(current) = read_current(state, &item.device_id).await? {
+        let values = decrypt(state, &item.values)?;
+        let previous = decrypt(state, &item.previous)?;
+        let (reverted, restored) = revert_values(&current, &values, &previous);
+
+        if restored.len() < values.len() {
+            warn!("Override {} keys changed since it was applied were kept", item.id);
+        }
+        if !restored.is_empty() {
+            apply_config(state, actor, AuditAction::OverrideRevert, Json(Config::new(item.device_id.clone(), reverted)))
+                .await
+                .map_err(config_error)?;
+        }
+    }
+
+    item.finish(status, Utc::now())?;
+    state.cosmos_client.upsert_override(&item)
+        .await
+        .map_err(|e| OverrideError::DatabaseError(e.to_string()))?;
+
+    info!("Override {} is now {:?}", item.id, item.status);
+    Ok(item)
+}
+
+/// Reverts every active override past its expiry time
+///
+/// An override that cannot be reverted is logged and retried on the next
+/// sweep; it does not stop the others from being reverted.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+///
+/// # Returns
+/// * `Result<usize, OverrideError>` - The number of overrides reverted, or an error if they could not be listed
+pub async fn revert_expired_overrides(state: &AppState) -> Result<usize, OverrideError> {
+    let now = Utc::now();
+    let expired: Vec<ConfigOverride> = state.cosmos_client.read_active_overrides()
+        .await
+        .map_err(|e| OverrideError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .filter(|item| item.is_expired_at(now))
+        .collect();
+
+    let actor = AuditActor {
+        subject: EXPIRY_SUBJECT.to_string(),
+        source_ip: None,
+        user_agent: None,
+    };
+
+    let mut reverted = 0;
+    for item in expired {
+        let id = item.id.clone();
+        match revert_override(state, actor.clone(), item, OverrideStatus::Expired).await {
+            Ok(_) => reverted += 1,
+            Err(e) => error!("Error reverting expired override {}: {}", id, e),
+        }
+    }
+
+    Ok(reverted)
+}
+
+/// Reverts expired overrides every `OVERRIDE_SWEEP_INTERVAL`, forever
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+pub async fn run_override_expiry(state: AppState) {
+    let mut interval = tokio::time::interval(OVERRIDE_SWEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        match revert_expired_overrides(&state).await {
+            Ok(0) => {}
+            Ok(count) => info!("Reverted {} expired override(s)", count),
+            Err(e) => error!("Error looking for expired overrides: {}", e),
+        }
+    }
+}
+
+/// Converts an override result into a route response, logging the outcome
+fn respond<T>(result: Result<T, OverrideError>) -> Result<Json<T>, Status> {
+    match result {
+        Ok(value) => Ok(Json(value)),
+        Err(e) => {
+            error!("Override request failed: {}", e);
+            // Convert the override error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
+
+/// POST endpoint for applying a temporary override
+///
+/// The values are merged into the device's configuration right away and
+/// reverted automatically once `ttl_secs` have passed (at most 7 days).
+/// Keys changed by someone else in the meantime keep their newer value.
+/// A key can only be held by one active override at a time; overriding
+/// it again returns `409 Conflict`. Overrides are refused with
+/// `409 Conflict` while `CONFIG_REQUIRE_APPROVAL` is set.
+///
+/// # Example Request
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "config": { "LED": "on" },
+///   "ttl_secs": 3600
+/// }
+/// ```
+///
+/// # Example Response
+/// ```json
+/// {
+///   "id": "8b1d...",
+///   "device_id": "sensor-001",
+///   "values": { "LED": "on" },
+///   "previous": { "LED": "off" },
+///   "status": "active",
+///   "created_by": "alice",
+///   "created_at": "2026-01-01T00:00:00Z",
+///   "expires_at": "2026-01-01T01:00:00Z"
+/// }
+/// ```
+///
+/// Requires the operator role or higher.
+#[post("/overrides", format = "json", data = "<request>")]
+pub async fn create_override_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    request: Json<OverrideRequest>,
+) -> Result<Json<ConfigOverride>, Status> {
+    info!("Received override request for device: {}", request.device_id);
+    respond(create_override(state.inner(), actor, request.into_inner()).await)
+}
+
+/// GET endpoint for listing the overrides of a device, newest first
+///
+/// Requires the read-only role or higher.
+#[get("/overrides/<device_id>")]
+pub async fn list_overrides_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    device_id: String,
+) -> Result<Json<Vec<ConfigOverride>>, Status> {
+    respond(read_overrides(state.inner(), &device_id).await)
+}
+
+/// POST endpoint for reverting an override before it expires
+///
+/// Requires the operator role or higher.
+#[post("/overrides/<device_id>/<override_id>/revert")]
+pub async fn revert_override_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    device_id: String,
+    override_id: String,
+) -> Result<Json<ConfigOverride>, Status> {
+    info!("Received revert request for override {} of device {}", override_id, device_id);
+
+    let state = state.inner();
+    let item = read_overrides(state, &device_id)
+        .await
+        .and_then(|items| items
+            .into_iter()
+            .find(|item| item.id == override_id)
+            .ok_or(OverrideError::OverrideNotFound(override_id)));
+    let result = match item {
+        Ok(item) => revert_override(state, actor, item, OverrideStatus::Reverted).await,
+        Err(e) => Err(e),
+    };
+    respond(result)
+}
@@ -0,0 +1,134 @@
+// Reported Configuration Route Handlers
+//
+// This module handles the /device-config/reported/<device_id> endpoints.
+// Devices report the configuration they applied after every change, and
+// the dashboard reads the report back to compare it with the configuration
+// the device is served.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::config::ConfigError;
+use crate::domain::device::{ReportedConfig, ReportedConfigRequest};
+use crate::app_state::AppState;
+use crate::utils::auth_guard::{DeviceReportAccess, ReadAccess};
+
+/// Records the configuration a device reports having applied
+///
+/// The reported values are checked against the configuration limits and
+/// secret values are redacted before the report is stored, replacing the
+/// device's previous one.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `request` - The reported configuration
+///
+/// # Returns
+/// * `Result<ReportedConfig, ConfigError>` - The stored report or an error
+async fn report_config(
+    state: &AppState,
+    device_id: &str,
+    request: ReportedConfigRequest,
+) -> Result<ReportedConfig, ConfigError> {
+    if device_id.trim().is_empty() {
+        return Err(ConfigError::InvalidDeviceId);
+    }
+    state.limits.check(&request.config)?;
+
+    let mut reported = ReportedConfig::new(device_id.to_string(), request);
+    reported.config = state.cipher.redact_config(&reported.config);
+
+    state.cosmos_client.upsert_reported_config(&reported)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    info!("Stored reported configuration of device {:?}", device_id);
+    Ok(reported)
+}
+
+/// Reads the configuration a device last reported having applied
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+///
+/// # Returns
+/// * `Result<ReportedConfig, ConfigError>` - The report, `DeviceNotFound` if the
+///   device has not reported one, or an error
+async fn read_reported(state: &AppState, device_id: &str) -> Result<ReportedConfig, ConfigError> {
+    state.cosmos_client.read_reported_config(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ConfigError::DeviceNotFound(device_id.to_string()))
+}
+
+/// POST endpoint for a device's report of the configuration it applied
+///
+/// Devices call this after applying a new configuration. `applied_at`
+/// defaults to the time of the report, since devices usually have no
+/// clock.
+///
+/// # Example Request
+/// ```json
+/// {
+///   "config": { "LED": "on" },
+///   "version_number": 3
+/// }
+/// ```
+///
+/// Accepts the device's own key from `DEVICE_KEYS`, or the operator role
+/// or higher.
+#[post("/reported/<device_id>", format = "json", data = "<request>")]
+pub async fn report_config_route(
+    state: &State<AppState>,
+    _access: DeviceReportAccess,
+    device_id: String,
+    request: Json<ReportedConfigRequest>,
+) -> Result<Json<ReportedConfig>, Status> {
+    info!("Received configuration report from device: {:?}", device_id);
+
+    match report_config(state.inner(), &device_id, request.into_inner()).await {
+        Ok(reported) => Ok(Json(reported)),
+        Err(e) => {
+            error!("Error storing reported configuration: {}", e);
+            // Convert the configuration error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
+
+/// GET endpoint for the configuration a device last reported having applied
+///
+/// Returns 404 if the device has not reported a configuration yet.
+///
+/// # Example Response
+/// ```json
+/// {
+///   "id": "sensor-001",
+///   "device_id": "sensor-001",
+///   "config": { "LED": "on", "wifi_password": "********" },
+///   "version_number": 3,
+///   "applied_at": "2026-01-01T00:05:00Z"
+/// }
+/// ```
+///
+/// Requires the read-only role or higher.
+#[get("/reported/<device_id>")]
+pub async fn get_reported_config_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    device_id: String,
+) -> Result<Json<ReportedConfig>, Status> {
+    info!("Received reported configuration request for device: {:?}", device_id);
+
+    match read_reported(state.inner(), &device_id).await {
+        Ok(reported) => Ok(Json(reported)),
+        Err(e) => {
+            error!("Error reading reported configuration: {}", e);
+            // Convert the configuration error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
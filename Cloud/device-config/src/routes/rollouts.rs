@@ -0,0 +1,446 @@
+// Staged Rollout Route Handlers
+//
+// This module handles the /device-config/rollouts endpoints for creating
+// canary rollouts, inspecting their status, and pausing, resuming,
+// promoting or aborting them.
+
+use std::collections::HashMap;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::audit::{diff_configs, AuditAction, AuditActor, AuditEntry};
+use crate::domain::config::Config;
+use crate::domain::event::ConfigChangeEvent;
+use crate::domain::rollout::{CanaryRevert, Rollout, RolloutAction, RolloutError, RolloutRequest, RolloutStatus};
+use crate::app_state::AppState;
+use crate::utils::auth_guard::{OperatorAccess, ReadAccess};
+
+/// Stores a configuration version for a single device, audits the change
+/// and publishes a change event
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who triggered the change, for the audit log
+/// * `rollout_id` - The rollout causing the change
+/// * `action` - The rollout action causing the change
+/// * `device_id` - The device to configure
+/// * `config` - The configuration parameters to store, secret values already encrypted
+///
+/// # Returns
+/// * `Result<(Option<HashMap<String, String>>, String), RolloutError>` - The device's previous
+///   configuration as stored (secret values encrypted), if any, and the id of the stored version
+async fn push_config(
+    state: &AppState,
+    actor: &AuditActor,
+    rollout_id: &str,
+    action: AuditAction,
+    device_id: &str,
+    config: &HashMap<String, String>,
+) -> Result<(Option<HashMap<String, String>>, String), RolloutError> {
+    let previous = state.cosmos_client.read_config(device_id)
+        .await
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .next()
+        .map(|current| current.config);
+
+    // Secret values are compared in plaintext and redacted from the audit log
+    let decrypt = |config: &HashMap<String, String>| state.cipher
+        .decrypt_config(config)
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()));
+    let before = previous.as_ref().map(decrypt).transpose()?;
+    let changes = state.cipher.redact_changes(diff_configs(before.as_ref(), &decrypt(config)?));
+
+    let document = serde_json::to_value(Config::new(device_id.to_string(), config.clone()).stored_by(&actor.subject))
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+
+    let version = state.cosmos_client.insert_config(&document)
+        .await
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+
+    let entry = AuditEntry::new(
+        device_id.to_string(),
+        action,
+        actor.clone(),
+        changes,
+    )
+    .for_rollout(rollout_id);
+    record_audit(state, &entry).await?;
+    state.events.publish(ConfigChangeEvent::from_audit(&entry, version.clone()));
+    state.notifier.notify(device_id);
+
+    Ok((previous, version))
+}
+
+/// Soft-deletes the version a rollout stored on a device that had no
+/// configuration before, so it falls back to the fleet-wide defaults again
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who aborted the rollout, for the audit log
+/// * `rollout_id` - The aborted rollout
+/// * `device_id` - The canary device
+/// * `version` - The version the rollout stored
+/// * `config` - The rollout's configuration, secret values encrypted
+async fn withdraw_config(
+    state: &AppState,
+    actor: &AuditActor,
+    rollout_id: &str,
+    device_id: &str,
+    version: &str,
+    config: &HashMap<String, String>,
+) -> Result<(), RolloutError> {
+    let deleted = state.cosmos_client.delete_config_version(device_id, version, chrono::Utc::now())
+        .await
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+    if !deleted {
+        return Ok(());
+    }
+
+    let removed = state.cipher.decrypt_config(config)
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+    let changes = state.cipher.redact_changes(diff_configs(Some(&removed), &HashMap::new()));
+    let entry = AuditEntry::new(device_id.to_string(), AuditAction::RolloutAbort, actor.clone(), changes)
+        .for_rollout(rollout_id);
+    record_audit(state, &entry).await?;
+    state.events.publish(ConfigChangeEvent::from_audit(&entry, version.to_string()));
+    state.notifier.notify(device_id);
+
+    Ok(())
+}
+
+/// Appends an entry to the audit log
+async fn record_audit(state: &AppState, entry: &AuditEntry) -> Result<(), RolloutError> {
+    state.cosmos_client.insert_audit_entry(entry)
+        .await
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))
+}
+
+/// Maps a rollout action to the audit action recorded for it
+fn audit_action(action: RolloutAction) -> AuditAction {
+    match action {
+        RolloutAction::Pause => AuditAction::RolloutPause,
+        RolloutAction::Resume => AuditAction::RolloutResume,
+        RolloutAction::Promote => AuditAction::RolloutPromote,
+        RolloutAction::Abort => AuditAction::RolloutAbort,
+    }
+}
+
+/// Loads a rollout by id, returning a not found error if it does not exist
+async fn load_rollout(state: &AppState, rollout_id: &str) -> Result<Rollout, RolloutError> {
+    state.cosmos_client.read_rollout(rollout_id)
+        .await
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| RolloutError::RolloutNotFound(rollout_id.to_string()))
+}
+
+/// Creates a rollout and applies its configuration to the canary devices
+///
+/// This function performs the following steps:
+/// 1. Validates the request, resolves the canary devices and encrypts
+///    secret values
+/// 2. Persists the rollout in the canary state, before any device is
+///    changed, so a failure part way through leaves a rollout to abort
+/// 3. Stores the new configuration for each canary device, persisting the
+///    configuration it replaced after each one so an abort can revert it
+/// 4. Records the creation in the audit log
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who created the rollout, for the audit log
+/// * `request` - The rollout creation request
+///
+/// # Returns
+/// * `Result<Rollout, RolloutError>` - The created rollout or an error
+async fn create_rollout(
+    state: &AppState,
+    actor: AuditActor,
+    request: RolloutRequest,
+) -> Result<Rollout, RolloutError> {
+    info!("Creating rollout {:?} for {} device(s)", request.name, request.devices.len());
+
+    // Rollouts store configurations without review
+    if state.approval_required {
+        return Err(RolloutError::ApprovalRequired);
+    }
+
+    let mut rollout = Rollout::parse(uuid::Uuid::new_v4().to_string(), request, &state.limits)?;
+
+    // Secret values stay encrypted in the rollout and every version it stores
+    rollout.config = state.cipher.encrypt_config(&rollout.config)
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+
+    state.cosmos_client.upsert_rollout(&rollout)
+        .await
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+
+    for device_id in rollout.canary_devices.clone() {
+        let (previous, version) = push_config(
+            state, &actor, &rollout.id, AuditAction::RolloutCreate, &device_id, &rollout.config,
+        ).await?;
+        rollout.record_canary(&device_id, previous, version);
+
+        state.cosmos_client.upsert_rollout(&rollout)
+            .await
+            .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+    }
+
+    let entry = AuditEntry::new(rollout.id.clone(), AuditAction::RolloutCreate, actor, Vec::new())
+        .for_rollout(&rollout.id);
+    record_audit(state, &entry).await?;
+
+    info!("Rollout {} created with {} canary device(s)", rollout.id, rollout.canary_devices.len());
+    Ok(rollout)
+}
+
+/// Applies an operator action to an existing rollout
+///
+/// Promoting applies the configuration to the remaining devices of the
+/// group. Aborting restores the previous configuration of every canary
+/// device, or withdraws the rollout's version from canaries that had none,
+/// unless the device was reconfigured after the rollout reached it.
+///
+/// The new state is stored with a write conditioned on the rollout's
+/// entity tag before any device is configured, so of several actions
+/// applied at once only the first takes effect; the others fail with
+/// `ConcurrentUpdate`. If configuring the devices fails, the rollout is
+/// returned to its previous state.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who applied the action, for the audit log
+/// * `rollout_id` - The rollout to update
+/// * `action` - The action to apply
+///
+/// # Returns
+/// * `Result<Rollout, RolloutError>` - The updated rollout or an error
+async fn transition_rollout(
+    state: &AppState,
+    actor: AuditActor,
+    rollout_id: String,
+    action: RolloutAction,
+) -> Result<Rollout, RolloutError> {
+    info!("Applying {:?} to rollout {}", action, rollout_id);
+
+    // Promoting stores configurations without review; pausing and aborting stay possible
+    if state.approval_required && action == RolloutAction::Promote {
+        return Err(RolloutError::ApprovalRequired);
+    }
+
+    let mut rollout = load_rollout(state, &rollout_id).await?;
+    let previous = rollout.clone();
+    rollout.apply(action)?;
+    claim_transition(state, &rollout).await?;
+
+    if let Err(e) = apply_transition(state, &actor, &rollout, action).await {
+        // Return the rollout to its previous state so the action can be retried
+        state.cosmos_client.upsert_rollout(&previous)
+            .await
+            .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+        return Err(e);
+    }
+
+    let entry = AuditEntry::new(rollout.id.clone(), audit_action(action), actor, Vec::new())
+        .for_rollout(&rollout.id);
+    record_audit(state, &entry).await?;
+
+    info!("Rollout {} is now {:?}", rollout.id, rollout.status);
+    Ok(rollout)
+}
+
+/// Stores a rollout's new state, failing with `ConcurrentUpdate` if another
+/// action replaced it first
+async fn claim_transition(state: &AppState, rollout: &Rollout) -> Result<(), RolloutError> {
+    let claimed = state.cosmos_client.replace_rollout(rollout)
+        .await
+        .map_err(|e| RolloutError::DatabaseError(e.to_string()))?;
+    if !claimed {
+        return Err(RolloutError::ConcurrentUpdate(rollout.id.clone()));
+    }
+    Ok(())
+}
+
+/// Configures the devices affected by a rollout's new state
+///
+/// Promoting applies the configuration to the remaining devices of the
+/// group; aborting reverts the canary devices.
+async fn apply_transition(
+    state: &AppState,
+    actor: &AuditActor,
+    rollout: &Rollout,
+    action: RolloutAction,
+) -> Result<(), RolloutError> {
+    match rollout.status {
+        RolloutStatus::Promoted => {
+            for device_id in rollout.remaining_devices() {
+                push_config(
+                    state, actor, &rollout.id, audit_action(action), &device_id, &rollout.config,
+                ).await?;
+            }
+        }
+        RolloutStatus::Aborted => {
+            for device_id in &rollout.canary_devices {
+                let current = state.cosmos_client.read_config(device_id)
+                    .await
+                    .map_err(|e| RolloutError::DatabaseError(e.to_string()))?
+                    .into_iter()
+                    .next()
+                    .and_then(|current| current.version);
+
+                match rollout.revert_for(device_id, current.as_deref()) {
+                    CanaryRevert::Restore(previous) => {
+                        push_config(
+                            state, actor, &rollout.id, audit_action(action), device_id, &previous,
+                        ).await?;
+                    }
+                    CanaryRevert::Withdraw(version) => {
+                        withdraw_config(state, actor, &rollout.id, device_id, &version, &rollout.config).await?;
+                    }
+                    CanaryRevert::Reconfigured => {
+                        info!("Device {} was reconfigured since rollout {}; keeping its configuration", device_id, rollout.id);
+                    }
+                    CanaryRevert::Untouched => {}
+                }
+            }
+        }
+        RolloutStatus::Canary | RolloutStatus::Paused => {}
+    }
+    Ok(())
+}
+
+/// Converts a rollout result into a route response, logging the outcome
+fn respond(result: Result<Rollout, RolloutError>) -> Result<Json<Rollout>, Status> {
+    match result {
+        Ok(rollout) => Ok(Json(rollout)),
+        Err(e) => {
+            error!("Rollout request failed: {}", e);
+            // Convert the rollout error to an appropriate HTTP status
+            Err(e.into())
+        }
+    }
+}
+
+/// POST endpoint for creating a staged rollout
+///
+/// The configuration is applied immediately to the canary devices only.
+/// The canary set is either a percentage of the group, selected
+/// deterministically, or an explicit list of devices from the group.
+///
+/// # Example Request
+/// ```json
+/// {
+///   "name": "raise-sampling-rate",
+///   "devices": ["sensor-001", "sensor-002", "sensor-003"],
+///   "canary": { "percentage": 10 },
+///   "config": { "sampling_rate": "2000" }
+/// }
+/// ```
+///
+/// # Example Response
+/// ```json
+/// {
+///   "id": "5f0c...",
+///   "name": "raise-sampling-rate",
+///   "devices": ["sensor-001", "sensor-002", "sensor-003"],
+///   "canary": { "percentage": 10 },
+///   "canary_devices": ["sensor-002"],
+///   "config": { "sampling_rate": "2000" },
+///   "status": "canary",
+///   "previous_configs": { "sensor-002": { "sampling_rate": "1000" } },
+///   "canary_versions": { "sensor-002": "\"sensor-002\"-2026-01-01T00:00:00+00:00" },
+///   "created_at": "2026-01-01T00:00:00Z",
+///   "updated_at": "2026-01-01T00:00:00Z"
+/// }
+/// ```
+///
+/// Refused with `409 Conflict` while `CONFIG_REQUIRE_APPROVAL` is set.
+///
+/// Requires the operator role or higher.
+#[post("/rollouts", format = "json", data = "<request>")]
+pub async fn create_rollout_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    request: Json<RolloutRequest>,
+) -> Result<Json<Rollout>, Status> {
+    info!("Received rollout creation request");
+    respond(create_rollout(state.inner(), actor, request.into_inner()).await)
+}
+
+/// GET endpoint for retrieving the status of a rollout
+///
+/// # Example Request
+/// ```bash
+/// GET /device-config/rollouts/5f0c...
+/// ```
+///
+/// Requires the read-only role or higher.
+#[get("/rollouts/<rollout_id>")]
+pub async fn get_rollout_route(
+    state: &State<AppState>,
+    _access: ReadAccess,
+    rollout_id: String,
+) -> Result<Json<Rollout>, Status> {
+    info!("Received rollout status request: {:?}", rollout_id);
+    respond(load_rollout(state.inner(), &rollout_id).await)
+}
+
+/// POST endpoint for pausing a rollout in the canary stage
+///
+/// Requires the operator role or higher.
+#[post("/rollouts/<rollout_id>/pause")]
+pub async fn pause_rollout_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    rollout_id: String,
+) -> Result<Json<Rollout>, Status> {
+    respond(transition_rollout(state.inner(), actor, rollout_id, RolloutAction::Pause).await)
+}
+
+/// POST endpoint for resuming a paused rollout
+///
+/// Requires the operator role or higher.
+#[post("/rollouts/<rollout_id>/resume")]
+pub async fn resume_rollout_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    rollout_id: String,
+) -> Result<Json<Rollout>, Status> {
+    respond(transition_rollout(state.inner(), actor, rollout_id, RolloutAction::Resume).await)
+}
+
+/// POST endpoint for promoting a rollout to the whole device group
+///
+/// Refused with `409 Conflict` while `CONFIG_REQUIRE_APPROVAL` is set.
+///
+/// Requires the operator role or higher.
+#[post("/rollouts/<rollout_id>/promote")]
+pub async fn promote_rollout_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    rollout_id: String,
+) -> Result<Json<Rollout>, Status> {
+    respond(transition_rollout(state.inner(), actor, rollout_id, RolloutAction::Promote).await)
+}
+
+/// POST endpoint for aborting a rollout and reverting its canary devices
+///
+/// Canary devices get their previous configuration back; those that had
+/// none have the rollout's version soft-deleted and fall back to the
+/// fleet-wide defaults. Devices reconfigured after the rollout reached
+/// them keep their current configuration.
+///
+/// Requires the operator role or higher.
+#[post("/rollouts/<rollout_id>/abort")]
+pub async fn abort_rollout_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    actor: AuditActor,
+    rollout_id: String,
+) -> Result<Json<Rollout>, Status> {
+    respond(transition_rollout(state.inner(), actor, rollout_id, RolloutAction::Abort).await)
+}
@@ -0,0 +1,46 @@
+// Signing Key Route Handler
+//
+// This module handles the GET /device-config/signing-key endpoint, which
+// publishes the public key devices use to verify configuration signatures.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, warn};
+
+use crate::domain::signing::SigningKeyInfo;
+use crate::app_state::AppState;
+
+/// GET endpoint for retrieving the configuration signing public key
+///
+/// Devices verify the `signature` of a served configuration against the
+/// canonical JSON form of its `device_id` and `config` using this key.
+/// The key is public, so the endpoint requires no credentials.
+///
+/// # Returns
+/// * `Result<Json<SigningKeyInfo>, Status>` - The public key, or 404 if signing is not configured
+///
+/// # Example Request
+/// ```bash
+/// GET /device-config/signing-key
+/// ```
+///
+/// # Example Response
+/// ```json
+/// {
+///   "algorithm": "ed25519",
+///   "key_id": "3b6a27bcceb6a42d",
+///   "public_key": "O2onvM62pC1io6jQKm8Nc2UyFXcd4kOmOsBIoYtZ2ik="
+/// }
+/// ```
+#[get("/signing-key")]
+pub async fn get_signing_key_route(state: &State<AppState>) -> Result<Json<SigningKeyInfo>, Status> {
+    info!("Received signing key request");
+
+    match &state.signer {
+        Some(signer) => Ok(Json(signer.key_info())),
+        None => {
+            warn!("Signing key requested but no CONFIG_SIGNING_KEY is configured");
+            Err(Status::NotFound)
+        }
+    }
+}
@@ -1,72 +1,219 @@
 // Configuration Update Route Handler
 // 
 // This module handles the POST /device-config/update endpoint for
-// updating device configuration data in the database.
+// updating device configuration data in the database, or holding the
+// update for approval when four-eyes review is required.
 
 use rocket::serde::json::Json;
 use rocket::{State, http::Status};
 use tracing::{info, error};
 
+use crate::domain::approval::ConfigApproval;
 use crate::domain::config::Config;
-use crate::domain::config::ConfigError;
+use crate::domain::config::{ConfigError, DefaultConfigRequest};
+use crate::domain::audit::{diff_configs, AuditAction, AuditActor, AuditEntry};
+use crate::domain::event::ConfigChangeEvent;
 use crate::app_state::AppState;
+use crate::routes::approvals::submit_config;
+use crate::utils::auth_guard::OperatorAccess;
 
 /// Processes and stores configuration data in the database
 /// 
 /// This function validates the incoming configuration data and stores it
 /// in the Cosmos DB database. It performs the following steps:
-/// 1. Validates the configuration data using domain validation rules
-/// 2. Converts the validated data to JSON format for storage
+/// 1. Validates the configuration data using domain validation rules and
+///    the configured key and value limits
+/// 2. Encrypts secret values and converts the data to JSON format for storage
 /// 3. Inserts the data into the Cosmos DB container
+/// 4. Records the change in the audit log, with secret values redacted
+/// 5. Publishes a change event to the registered webhooks
 /// 
 /// # Arguments
 /// * `state` - Application state containing the database client
+/// * `actor` - Who made the change, for the audit log
 /// * `config` - The configuration data to be processed and stored
 /// 
 /// # Returns
 /// * `Result<(), ConfigError>` - Success or an appropriate error
-async fn update_config(state: &AppState, config: Json<Config>) -> Result<(), ConfigError> {
-    info!("Updating config: {:?}", config);
+pub(crate) async fn update_config(state: &AppState, actor: AuditActor, config: Json<Config>) -> Result<(), ConfigError> {
+    apply_config(state, actor, AuditAction::ConfigUpdate, config).await
+}
 
-    // Parse and validate the configuration data using domain validation rules
+/// Stores a configuration like `update_config`, recording it in the audit
+/// log under the given action
+/// 
+/// Every configuration write goes through here or `update_defaults`, so
+/// the reserved `_default` device ID is rejected here, and while approval
+/// is required only approved changes and override reverts are stored;
+/// other writes are refused with `ApprovalRequired` and must be submitted
+/// with `submit_config` instead.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who made the change, for the audit log
+/// * `action` - The kind of mutation to record
+/// * `config` - The configuration data to be processed and stored
+/// 
+/// # Returns
+/// * `Result<(), ConfigError>` - Success or an appropriate error
+pub(crate) async fn apply_config(
+    state: &AppState,
+    actor: AuditActor,
+    action: AuditAction,
+    config: Json<Config>,
+) -> Result<(), ConfigError> {
+    info!("Updating config for device: {}", config.device_id);
+
+    // Parse and validate the configuration data using domain validation rules;
+    // the reserved `_default` device ID is rejected here
     let document = Config::parse(
         config.device_id.clone(),
         config.config.clone(),
+        &state.limits,
+    )?
+    // Carry over the optional activation time for scheduled rollouts
+    .scheduled(config.apply_at)
+    .stored_by(&actor.subject);
+
+    store_config(state, actor, action, document).await
+}
+
+/// Stores an approved change like `apply_config`
+/// 
+/// The stored version records the caller who submitted the change as its
+/// author and the approving caller in `approved_by`; the approval itself
+/// is recorded in the audit log under the approver.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who approved the change, for the audit log
+/// * `requested_by` - Who submitted the change
+/// * `config` - The approved configuration data
+/// 
+/// # Returns
+/// * `Result<(), ConfigError>` - Success or an appropriate error
+pub(crate) async fn apply_approved_config(
+    state: &AppState,
+    actor: AuditActor,
+    requested_by: &str,
+    config: Config,
+) -> Result<(), ConfigError> {
+    info!("Updating config for device {} as approved", config.device_id);
+
+    let document = Config::parse(config.device_id, config.config, &state.limits)?
+        .scheduled(config.apply_at)
+        .stored_by(requested_by)
+        .approved_by(&actor.subject);
+
+    store_config(state, actor, AuditAction::ConfigApprove, document).await
+}
+
+/// Stores the fleet-wide default configuration like `update_config`
+/// 
+/// This is the only write path for the reserved device ID `_default`.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `actor` - Who made the change, for the audit log
+/// * `request` - The default configuration and its optional activation time
+/// 
+/// # Returns
+/// * `Result<(), ConfigError>` - Success or an appropriate error
+pub(crate) async fn update_defaults(
+    state: &AppState,
+    actor: AuditActor,
+    request: DefaultConfigRequest,
+) -> Result<(), ConfigError> {
+    info!("Updating the fleet-wide default config");
+
+    let document = Config::parse_defaults(request.config, &state.limits)?
+        .scheduled(request.apply_at)
+        .stored_by(&actor.subject);
 
-    ).map_err(|e| match e {
-        // Map domain validation errors to configuration errors
-        crate::domain::config::ConfigError::InvalidDeviceId => ConfigError::InvalidDeviceId,
-        crate::domain::config::ConfigError::InvalidConfig => ConfigError::InvalidConfig,
-        crate::domain::config::ConfigError::DatabaseError(e) => ConfigError::DatabaseError(e),
-        crate::domain::config::ConfigError::DeviceNotFound(device_id) => ConfigError::DeviceNotFound(device_id),
-    })?;
+    store_config(state, actor, AuditAction::ConfigUpdate, document).await
+}
+
+/// Encrypts, stores, audits and publishes a validated configuration
+/// 
+/// The configuration's author must already be recorded with `stored_by`.
+async fn store_config(
+    state: &AppState,
+    actor: AuditActor,
+    action: AuditAction,
+    document: Config,
+) -> Result<(), ConfigError> {
+    if state.approval_required && action.requires_approval() {
+        return Err(ConfigError::ApprovalRequired);
+    }
+
+    // Look up the currently active configuration to record what changed
+    let previous = state.cosmos_client.read_config(&document.device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    let previous = previous
+        .first()
+        .map(|current| state.cipher.decrypt_config(&current.config))
+        .transpose()
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    let changes = state.cipher.redact_changes(diff_configs(previous.as_ref(), &document.config));
 
-    // Convert the validated configuration to JSON format for database storage
-    let inserted_document = serde_json::to_value(&document)
+    // Encrypt secret values and convert the configuration to JSON format for database storage
+    let mut stored = document.clone();
+    stored.config = state.cipher.encrypt_config(&document.config)
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    let inserted_document = serde_json::to_value(&stored)
         .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
 
     // Insert the configuration data into the Cosmos DB container
-    state.cosmos_client.insert_config(&inserted_document)
+    let version = state.cosmos_client.insert_config(&inserted_document)
         .await
         .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
 
+    // Record the mutation in the audit log, with secret values redacted
+    let entry = AuditEntry::new(
+        document.device_id.clone(),
+        action,
+        actor,
+        changes,
+    );
+    state.cosmos_client.insert_audit_entry(&entry)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+
+    // Notify external systems and long-polling devices of the change
+    state.events.publish(ConfigChangeEvent::from_audit(&entry, version));
+    state.notifier.notify(&document.device_id);
+
     info!("Configuration updated successfully");
     Ok(())
 }
 
+/// Response of the configuration update endpoint
+#[derive(Debug, Responder)]
+pub enum UpdateResponse {
+    /// The configuration was stored (200)
+    #[response(status = 200)]
+    Applied(&'static str),
+    /// The configuration is held for approval (202)
+    #[response(status = 202)]
+    Pending(Box<Json<ConfigApproval>>),
+}
+
 /// POST endpoint for updating device configuration data
 /// 
 /// This endpoint receives configuration data for a device and stores it
 /// in the database. The endpoint expects a JSON payload containing:
 /// - device_id: Unique identifier for the IoT device
 /// - config: Key-value pairs of configuration parameters
+/// - apply_at: Optional RFC3339 time at which the configuration becomes active
 /// 
 /// # Arguments
 /// * `state` - Application state injected by Rocket
+/// * `actor` - Caller identity and origin, recorded in the audit log
 /// * `config` - JSON payload containing the configuration data
 /// 
 /// # Returns
-/// * `Result<&'static str, Status>` - Success message or HTTP error status
+/// * `Result<UpdateResponse, Status>` - Success message, pending change or HTTP error status
 /// 
 /// # Example Request
 /// ```json
@@ -76,7 +223,8 @@ async fn update_config(state: &AppState, config: Json<Config>) -> Result<(), Con
 ///     "sampling_rate": "1000",
 ///     "threshold": "25.5",
 ///     "wifi_ssid": "MyNetwork"
-///   }
+///   },
+///   "apply_at": "2025-01-01T02:00:00Z"
 /// }
 /// ```
 /// 
@@ -84,18 +232,40 @@ async fn update_config(state: &AppState, config: Json<Config>) -> Result<(), Con
 /// ```text
 /// Config ingested
 /// ```
+/// 
+/// When `CONFIG_REQUIRE_APPROVAL` is set, the configuration is not stored
+/// but held as a pending change, returned with 202 Accepted, until an
+/// admin other than the caller approves it.
+/// 
+/// The reserved device ID `_default` is rejected with 400; use
+/// `PUT /device-config/defaults` to change the fleet-wide defaults.
+/// 
+/// Requires the operator role or higher.
 #[post("/update", data = "<config>")]
 pub async fn update_config_route(
     state: &State<AppState>, 
+    _access: OperatorAccess,
+    actor: AuditActor,
     config: Json<Config>
-) -> Result<&'static str, Status> {
-    info!("Received configuration update request: {:?}", config);
+) -> Result<UpdateResponse, Status> {
+    info!("Received configuration update request for device: {}", config.device_id);
+
+    // Hold the change for review when four-eyes approval is required
+    if state.approval_required {
+        return match submit_config(state.inner(), actor, config.into_inner()).await {
+            Ok(approval) => Ok(UpdateResponse::Pending(Box::new(Json(approval)))),
+            Err(e) => {
+                error!("Error submitting configuration for approval: {}", e);
+                Err(e.into())
+            }
+        };
+    }
 
     // Process the configuration data and handle any errors
-    match update_config(state.inner(), config).await {
+    match update_config(state.inner(), actor, config).await {
         Ok(_) => {
             info!("Successfully processed configuration update");
-            Ok("Config ingested")
+            Ok(UpdateResponse::Applied("Config ingested"))
         }
         Err(e) => {
             error!("Error updating configuration: {}", e);
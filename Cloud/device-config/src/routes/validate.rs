@@ -0,0 +1,113 @@
+// Configuration Validation Route Handler
+// 
+// This module handles the POST /device-config/validate endpoint, a dry run
+// of POST /device-config/update that reports what would happen without
+// storing anything.
+
+use chrono::Utc;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::config::{Config, ConfigError, DEFAULT_CONFIG_ID};
+use crate::domain::validation::{validate_config, ValidationReport};
+use crate::app_state::AppState;
+use crate::utils::auth_guard::OperatorAccess;
+
+/// Reads the active configuration stored under an ID, in plaintext
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device ID, or the defaults ID
+/// 
+/// # Returns
+/// * `Result<Option<Config>, ConfigError>` - The active configuration, if any, or an error
+async fn read_active_config(state: &AppState, device_id: &str) -> Result<Option<Config>, ConfigError> {
+    let Some(mut config) = state.cosmos_client.read_config(device_id)
+        .await
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .next()
+    else {
+        return Ok(None);
+    };
+
+    config.config = state.cipher.decrypt_config(&config.config)
+        .map_err(|e| ConfigError::DatabaseError(e.to_string()))?;
+    Ok(Some(config))
+}
+
+/// Validates a configuration against what the device is served now
+/// 
+/// The device's active configuration and the fleet defaults are only read,
+/// never written.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `config` - The configuration to validate
+/// 
+/// # Returns
+/// * `Result<ValidationReport, ConfigError>` - The report, or a database error
+async fn validate(state: &AppState, config: &Config) -> Result<ValidationReport, ConfigError> {
+    // Nothing to compare against for configurations that fail before lookup
+    if config.device_id.trim().is_empty() || config.device_id == DEFAULT_CONFIG_ID {
+        return Ok(validate_config(config, None, None, &state.cipher, &state.limits, Utc::now()));
+    }
+
+    let current = read_active_config(state, &config.device_id).await?;
+    let defaults = match current {
+        Some(_) => None,
+        None => read_active_config(state, DEFAULT_CONFIG_ID).await?,
+    };
+
+    Ok(validate_config(config, current.as_ref(), defaults.as_ref(), &state.cipher, &state.limits, Utc::now()))
+}
+
+/// POST endpoint for validating a configuration without storing it
+/// 
+/// Accepts the same body as POST /device-config/update and always answers
+/// 200 with a report; check `valid` to see whether the update would be
+/// accepted. Nothing is stored, audited or published.
+/// 
+/// # Arguments
+/// * `state` - Application state injected by Rocket
+/// * `config` - JSON payload containing the configuration data
+/// 
+/// # Returns
+/// * `Result<Json<ValidationReport>, Status>` - The validation report or HTTP error status
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "valid": true,
+///   "errors": [],
+///   "warnings": ["Keys removed from the active configuration: threshold"],
+///   "resolved": {
+///     "device_id": "sensor-001",
+///     "config": { "LED": "on", "wifi_password": "********" }
+///   },
+///   "etag": "9f3c2a1b7d5e4f60",
+///   "changes": [{ "key": "LED", "old_value": "off", "new_value": "on" }]
+/// }
+/// ```
+/// 
+/// Requires the operator role or higher.
+#[post("/validate", data = "<config>")]
+pub async fn validate_config_route(
+    state: &State<AppState>,
+    _access: OperatorAccess,
+    config: Json<Config>,
+) -> Result<Json<ValidationReport>, Status> {
+    info!("Received configuration validation request for device: {}", config.device_id);
+
+    match validate(state.inner(), &config).await {
+        Ok(report) => {
+            info!("Validated configuration: valid={}, warnings={}", report.valid, report.warnings.len());
+            Ok(Json(report))
+        }
+        Err(e) => {
+            error!("Error validating configuration: {}", e);
+            Err(e.into())
+        }
+    }
+}
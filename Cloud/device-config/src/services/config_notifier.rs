@@ -0,0 +1,116 @@
+// Configuration Change Notifier
+// 
+// This module lets long-polling `get_config` requests wake up as soon as a
+// configuration is stored by this instance, instead of waiting for their
+// next periodic re-check.
+
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::domain::config::DEFAULT_CONFIG_ID;
+
+/// Number of change notifications buffered for slow waiters
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Broadcasts the IDs of devices whose configuration changed
+#[derive(Clone)]
+pub struct ConfigNotifier {
+    sender: broadcast::Sender<String>,
+}
+
+impl Default for ConfigNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigNotifier {
+    /// Creates a notifier with no waiters
+    /// 
+    /// # Returns
+    /// * `Self` - A new ConfigNotifier instance
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        ConfigNotifier { sender }
+    }
+
+    /// Wakes every request waiting on the given device
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device whose configuration changed
+    pub fn notify(&self, device_id: &str) {
+        // Sending only fails when nobody is waiting
+        let _ = self.sender.send(device_id.to_string());
+    }
+
+    /// Waits until the given device changes or the timeout elapses
+    /// 
+    /// A change to the fleet-wide defaults wakes every waiter, since any
+    /// device without a configuration of its own is served them.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device to wait on
+    /// * `timeout` - The longest time to wait
+    /// 
+    /// # Returns
+    /// * `bool` - True if a change may have happened, false on timeout
+    pub async fn wait_for_change(&self, device_id: &str, timeout: Duration) -> bool {
+        let mut receiver = self.sender.subscribe();
+
+        let wait = async {
+            loop {
+                match receiver.recv().await {
+                    Ok(changed) if changed == device_id || changed == DEFAULT_CONFIG_ID => return true,
+                    Ok(_) => continue,
+                    // Notifications were dropped; one of them may have been ours
+                    Err(RecvError::Lagged(_)) => return true,
+                    Err(RecvError::Closed) => return false,
+                }
+            }
+        };
+
+        tokio::time::timeout(timeout, wait).await.unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_for_change_wakes_on_matching_device() {
+        let notifier = ConfigNotifier::new();
+
+        let waiter = {
+            let notifier = notifier.clone();
+            tokio::spawn(async move { notifier.wait_for_change("device-1", Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        notifier.notify("device-2");
+        notifier.notify("device-1");
+
+        assert!(waiter.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_change_wakes_on_default_change() {
+        let notifier = ConfigNotifier::new();
+
+        let waiter = {
+            let notifier = notifier.clone();
+            tokio::spawn(async move { notifier.wait_for_change("device-1", Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        notifier.notify(DEFAULT_CONFIG_ID);
+
+        assert!(waiter.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_change_times_out() {
+        let notifier = ConfigNotifier::new();
+
+        assert!(!notifier.wait_for_change("device-1", Duration::from_millis(20)).await);
+    }
+}
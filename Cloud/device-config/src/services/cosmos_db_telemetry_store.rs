@@ -6,9 +6,18 @@
 
 use super::AzureAuth;
 use crate::domain::config::Config;
+use crate::domain::rollout::Rollout;
+use crate::domain::audit::AuditEntry;
+use crate::domain::device::{DeviceFetch, DeviceSummary, ReportedConfig};
+use crate::domain::firmware::{FirmwareAssignment, FirmwareImage};
+use crate::domain::overrides::ConfigOverride;
+use crate::domain::approval::{ApprovalStatus, ConfigApproval};
 use azure_data_cosmos::clients::ContainerClient;
-use azure_data_cosmos::CosmosClient;
+use azure_core::credentials::Secret;
+use azure_core::http::{Etag, StatusCode};
+use azure_data_cosmos::{CosmosClient, ItemOptions, QueryPartitionStrategy};
 use futures::StreamExt;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Cosmos DB client for device configuration storage and retrieval
@@ -23,6 +32,61 @@ pub struct CosmosDbTelemetryStore {
     /// This client is used for all database operations and is shared
     /// across multiple request handlers.
     pub container_client: Arc<ContainerClient>,
+
+    /// Container client for staged rollout documents
+    /// 
+    /// Rollouts live in a sibling container named `<container_name>-rollouts`
+    /// and are partitioned by rollout id.
+    pub rollouts_client: Arc<ContainerClient>,
+
+    /// Container client for the configuration audit trail
+    /// 
+    /// Audit entries live in a sibling container named `<container_name>-audit`
+    /// and are partitioned by their target (device id or rollout id).
+    pub audit_client: Arc<ContainerClient>,
+
+    /// Container client for the device index
+    /// 
+    /// One summary per configured device lives in a sibling container named
+    /// `<container_name>-devices`, partitioned by device id.
+    pub devices_client: Arc<ContainerClient>,
+
+    /// Container client for registered firmware images
+    /// 
+    /// Images live in a sibling container named `<container_name>-firmware`,
+    /// partitioned by version.
+    pub firmware_client: Arc<ContainerClient>,
+
+    /// Container client for firmware version assignments
+    /// 
+    /// One assignment per device (and one for the fleet under `_default`)
+    /// lives in a sibling container named `<container_name>-firmware-assignments`,
+    /// partitioned by device id.
+    pub firmware_assignments_client: Arc<ContainerClient>,
+
+    /// Container client for temporary configuration overrides
+    /// 
+    /// Overrides live in a sibling container named `<container_name>-overrides`
+    /// and are partitioned by device id.
+    pub overrides_client: Arc<ContainerClient>,
+
+    /// Container client for the last configuration fetch of each device
+    /// 
+    /// One record per device lives in a sibling container named
+    /// `<container_name>-fetches`, partitioned by device id.
+    pub fetches_client: Arc<ContainerClient>,
+
+    /// Container client for the configuration each device reports having applied
+    /// 
+    /// One record per device lives in a sibling container named
+    /// `<container_name>-reported`, partitioned by device id.
+    pub reported_client: Arc<ContainerClient>,
+
+    /// Container client for configuration changes submitted for approval
+    /// 
+    /// Changes live in a sibling container named `<container_name>-approvals`
+    /// and are partitioned by change id.
+    pub approvals_client: Arc<ContainerClient>,
 }
 
 impl CosmosDbTelemetryStore {
@@ -56,14 +120,68 @@ impl CosmosDbTelemetryStore {
         // Create the Cosmos DB client with authentication
         let cosmos_client = CosmosClient::new(&cosmos_endpoint, azure_credential, None)?;
 
-        // Create a container client for the specified database and container
-        let container_client = cosmos_client
-            .database_client(&database_name)
-            .container_client(&container_name);
+        Ok(Self::from_client(&cosmos_client, &database_name, &container_name))
+    }
+
+    /// Creates a Cosmos DB configuration store client authenticated with an account key
+    /// 
+    /// Used against the Cosmos DB emulator and in tests, where no service
+    /// principal is available. No request is sent until the store is used.
+    /// 
+    /// # Arguments
+    /// * `endpoint` - The Cosmos DB endpoint URL
+    /// * `key` - The account's primary or secondary key
+    /// * `database_name` - The name of the Cosmos DB database
+    /// * `container_name` - The name of the container within the database
+    /// 
+    /// # Returns
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The configured client or an error
+    pub fn with_key(
+        endpoint: &str,
+        key: String,
+        database_name: &str,
+        container_name: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let cosmos_client = CosmosClient::with_key(endpoint, Secret::new(key), None)?;
+        Ok(Self::from_client(&cosmos_client, database_name, container_name))
+    }
+
+    /// Creates the container clients of the store from an authenticated Cosmos DB client
+    fn from_client(cosmos_client: &CosmosClient, database_name: &str, container_name: &str) -> Self {
+        // Create container clients for the specified database and containers
+        let database_client = cosmos_client.database_client(database_name);
+        let container_client = database_client.container_client(container_name);
+        let rollouts_client =
+            database_client.container_client(&format!("{}-rollouts", container_name));
+        let audit_client =
+            database_client.container_client(&format!("{}-audit", container_name));
+        let devices_client =
+            database_client.container_client(&format!("{}-devices", container_name));
+        let firmware_client =
+            database_client.container_client(&format!("{}-firmware", container_name));
+        let firmware_assignments_client =
+            database_client.container_client(&format!("{}-firmware-assignments", container_name));
+        let overrides_client =
+            database_client.container_client(&format!("{}-overrides", container_name));
+        let fetches_client =
+            database_client.container_client(&format!("{}-fetches", container_name));
+        let reported_client =
+            database_client.container_client(&format!("{}-reported", container_name));
+        let approvals_client =
+            database_client.container_client(&format!("{}-approvals", container_name));
 
-        Ok(CosmosDbTelemetryStore {
+        CosmosDbTelemetryStore {
             container_client: Arc::new(container_client),
-        })
+            rollouts_client: Arc::new(rollouts_client),
+            audit_client: Arc::new(audit_client),
+            devices_client: Arc::new(devices_client),
+            firmware_client: Arc::new(firmware_client),
+            firmware_assignments_client: Arc::new(firmware_assignments_client),
+            overrides_client: Arc::new(overrides_client),
+            fetches_client: Arc::new(fetches_client),
+            reported_client: Arc::new(reported_client),
+            approvals_client: Arc::new(approvals_client),
+        }
     }
 
     /// Inserts a new configuration document into the Cosmos DB container
@@ -71,16 +189,20 @@ impl CosmosDbTelemetryStore {
     /// This method creates a new document in the database with a unique ID
     /// generated from the device ID and current timestamp. The device_id
     /// is used as the partition key for efficient storage and querying.
+    /// Versions are numbered per device in the order they are stored: the
+    /// number is claimed by a conditional write of the device's entry in the
+    /// device index, so versions stored at once never share a number. Devices
+    /// without an entry continue from their highest stored version number.
     /// 
     /// # Arguments
     /// * `document` - The configuration data as a JSON value
     /// 
     /// # Returns
-    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    /// * `Result<String, Box<dyn std::error::Error>>` - The id of the stored version or an error
     pub async fn insert_config(
         &self,
         document: &serde_json::Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<String, Box<dyn std::error::Error>> {
         // Create a copy of the document and add a unique ID and timestamp
         let mut document_with_id = document.clone();
         let timestamp = chrono::Utc::now();
@@ -92,23 +214,337 @@ impl CosmosDbTelemetryStore {
         document_with_id["id"] = serde_json::Value::String(id.clone());
         document_with_id["timestamp"] = serde_json::Value::String(timestamp.to_rfc3339());
 
+        // Record when this version becomes active: the scheduled apply_at time
+        // if one was given and has not passed, otherwise immediately. A
+        // fixed-width format keeps string comparisons in Cosmos queries
+        // chronologically correct.
+        let apply_at = document["apply_at"]
+            .as_str()
+            .and_then(|apply_at| chrono::DateTime::parse_from_rfc3339(apply_at).ok())
+            .map(|apply_at| apply_at.with_timezone(&chrono::Utc));
+        let effective_at = Config::effective_at(apply_at, timestamp);
+        document_with_id["effective_at"] = serde_json::Value::String(format_query_time(effective_at));
+
         // Extract device_id for use as partition key
         let device_id = document["device_id"].as_str().unwrap().to_string();
-        
-        // Insert the document into the Cosmos DB container
+
+        // Claim the next version number by pointing the device index at the
+        // new version; the entry is replaced only if no other version was
+        // stored since it was read, and the number is taken afresh otherwise
+        let config: HashMap<String, String> = serde_json::from_value(document["config"].clone()).unwrap_or_default();
+        let version_number = loop {
+            let current = self.read_device_summary(&device_id).await?;
+            let (latest, etag) = match current {
+                Some(current) => (current.version_number, current.etag),
+                None => (self.read_max_version_number(&device_id).await?, None),
+            };
+            let mut summary = DeviceSummary::new(device_id.clone(), id.clone(), timestamp, config.clone());
+            summary.version_number = latest + 1;
+            summary.etag = etag;
+            if self.write_device_summary(&summary).await? {
+                break summary.version_number;
+            }
+        };
+        document_with_id["version_number"] = serde_json::Value::from(version_number);
+
+        // Insert the document into the Cosmos DB container; should this fail,
+        // the claimed number is skipped and the next version replaces the index entry
         self.container_client
             .create_item(&device_id, &document_with_id, None)
             .await?;
 
+        Ok(id)
+    }
+
+    /// Retrieves the summary of a device from the device index
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Option<DeviceSummary>, Box<dyn std::error::Error>>` - The summary if the device
+    ///   has a stored configuration, or an error
+    pub async fn read_device_summary(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<DeviceSummary>, Box<dyn std::error::Error>> {
+        let query = format!("SELECT * FROM c WHERE c.id = '{}'", device_id);
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .devices_client
+            .query_items::<DeviceSummary>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Stores a device's entry in the device index unless another writer got there first
+    /// 
+    /// A summary read from the index replaces the stored one only if that
+    /// still has the summary's entity tag; a summary without one is created
+    /// only if the device has no entry yet.
+    /// 
+    /// # Arguments
+    /// * `summary` - The summary to store
+    /// 
+    /// # Returns
+    /// * `Result<bool, Box<dyn std::error::Error>>` - True if the summary was stored, false if
+    ///   the entry was changed or created since it was read, or an error
+    async fn write_device_summary(
+        &self,
+        summary: &DeviceSummary,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let result = match &summary.etag {
+            Some(etag) => {
+                let options = ItemOptions {
+                    if_match_etag: Some(Etag::from(etag.clone())),
+                    ..Default::default()
+                };
+                self.devices_client
+                    .replace_item(&summary.device_id, &summary.id, summary, Some(options))
+                    .await
+            }
+            None => self.devices_client.create_item(&summary.device_id, summary, None).await,
+        };
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if is_precondition_failed(&e) || is_conflict(&e) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Changes a device's entry in the device index, if it has one
+    /// 
+    /// The change is applied to a freshly read entry and retried whenever
+    /// the entry changes in between, so it never rolls back a version
+    /// stored at the same time.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `change` - Applies the change, returning false if the entry should be left as it is
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    async fn update_device_summary(
+        &self,
+        device_id: &str,
+        change: impl Fn(&mut DeviceSummary) -> bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        loop {
+            let Some(mut summary) = self.read_device_summary(device_id).await? else {
+                return Ok(());
+            };
+            if !change(&mut summary) || self.write_device_summary(&summary).await? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Retrieves the highest version number stored for a device, deleted versions included
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<u64, Box<dyn std::error::Error>>` - The highest version number, 0 if the
+    ///   device has no numbered versions, or an error
+    async fn read_max_version_number(
+        &self,
+        device_id: &str,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT VALUE MAX(c.version_number) FROM c WHERE c.device_id = '{}'",
+            device_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .container_client
+            .query_items::<u64>(query, partition_key, None)?;
+
+        // MAX yields no value at all when no document has a version number
+        let mut max = 0;
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            max = page.items().iter().copied().fold(max, u64::max);
+        }
+
+        Ok(max)
+    }
+
+    /// Retrieves the summary of every configured device
+    /// 
+    /// This is a cross-partition query over the device index, which holds a
+    /// single small document per device.
+    /// 
+    /// # Returns
+    /// * `Result<Vec<DeviceSummary>, Box<dyn std::error::Error>>` - All device summaries or an error
+    pub async fn read_device_summaries(
+        &self,
+    ) -> Result<Vec<DeviceSummary>, Box<dyn std::error::Error>> {
+        let query = "SELECT * FROM c";
+
+        let mut pager = self
+            .devices_client
+            .query_items::<DeviceSummary>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Adds the devices configured before the device index existed to it
+    /// 
+    /// Every device with stored configuration versions but no entry in the
+    /// index gets one for its most recently stored version. Entries written
+    /// meanwhile by a configuration update are kept, so the backfill can run
+    /// while updates are served, and again at every start.
+    /// 
+    /// # Returns
+    /// * `Result<usize, Box<dyn std::error::Error>>` - The number of devices added or an error
+    pub async fn backfill_device_index(
+        &self,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let indexed: HashSet<String> = self.read_device_summaries()
+            .await?
+            .into_iter()
+            .map(|summary| summary.device_id)
+            .collect();
+
+        let query = "SELECT DISTINCT VALUE c.device_id FROM c";
+        let mut pager = self
+            .container_client
+            .query_items::<String>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut device_ids = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            device_ids.extend(page.items().iter().filter(|id| !indexed.contains(*id)).cloned());
+        }
+
+        let mut added = 0;
+        for device_id in device_ids {
+            let query = format!(
+                "SELECT TOP 1 * FROM c WHERE c.device_id = '{}' ORDER BY c.timestamp DESC",
+                device_id
+            );
+            let latest = self.read_config_documents(&device_id, query).await?;
+            let Some(summary) = latest.first().and_then(DeviceSummary::from_document) else {
+                continue;
+            };
+            if self.write_device_summary(&summary).await? {
+                added += 1;
+            }
+        }
+
+        Ok(added)
+    }
+
+    /// Records the latest configuration fetch of a device, replacing the previous one
+    /// 
+    /// # Arguments
+    /// * `fetch` - The fetch to record
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_device_fetch(
+        &self,
+        fetch: &DeviceFetch,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.fetches_client
+            .upsert_item(&fetch.device_id, fetch, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the latest configuration fetch of every device
+    /// 
+    /// # Returns
+    /// * `Result<Vec<DeviceFetch>, Box<dyn std::error::Error>>` - All fetch records or an error
+    pub async fn read_device_fetches(
+        &self,
+    ) -> Result<Vec<DeviceFetch>, Box<dyn std::error::Error>> {
+        let query = "SELECT * FROM c";
+
+        let mut pager = self
+            .fetches_client
+            .query_items::<DeviceFetch>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Records the configuration a device reports having applied, replacing its previous report
+    /// 
+    /// # Arguments
+    /// * `reported` - The report to record
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_reported_config(
+        &self,
+        reported: &ReportedConfig,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.reported_client
+            .upsert_item(&reported.device_id, reported, None)
+            .await?;
+
         Ok(())
     }
 
-    /// Retrieves the latest configuration data for a specific device
+    /// Retrieves the configuration a device last reported having applied
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Option<ReportedConfig>, Box<dyn std::error::Error>>` - The report if the device
+    ///   has made one, or an error
+    pub async fn read_reported_config(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<ReportedConfig>, Box<dyn std::error::Error>> {
+        let query = format!("SELECT * FROM c WHERE c.id = '{}'", device_id);
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .reported_client
+            .query_items::<ReportedConfig>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Retrieves the currently active configuration data for a specific device
     /// 
-    /// This method queries the Cosmos DB container for the most recent
-    /// configuration record associated with the given device ID. It uses
-    /// the device_id as the partition key for efficient querying and
-    /// orders by timestamp to get the latest configuration.
+    /// This method queries the Cosmos DB container for the most recently
+    /// activated configuration record associated with the given device ID.
+    /// Versions scheduled with an `apply_at` time in the future are skipped
+    /// until that time passes, and deleted versions are skipped until they
+    /// are restored. It uses the device_id as the partition key
+    /// for efficient querying and orders by activation time.
     /// 
     /// # Arguments
     /// * `device_id` - The unique identifier of the device
@@ -119,10 +555,15 @@ impl CosmosDbTelemetryStore {
         &self,
         device_id: &str,
     ) -> Result<Vec<Config>, Box<dyn std::error::Error>> {
-        // Build SQL query to find the latest configuration for the specified device
+        // Build SQL query to find the latest active configuration for the specified device
         let query = format!(
-            "SELECT TOP 1 * FROM c WHERE c.device_id = '{}' ORDER BY c.timestamp DESC",
-            device_id
+            "SELECT TOP 1 * FROM c WHERE c.device_id = '{}' \
+             AND (NOT IS_DEFINED(c.effective_at) OR c.effective_at <= '{}') \
+             AND {} \
+             ORDER BY c.effective_at DESC",
+            device_id,
+            format_query_time(chrono::Utc::now()),
+            NOT_DELETED
         );
         let partition_key = device_id.to_string();
         
@@ -140,4 +581,738 @@ impl CosmosDbTelemetryStore {
 
         Ok(items)
     }
+
+    /// Retrieves the versions of a device's configuration scheduled to become
+    /// active later, soonest first
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Config>, Box<dyn std::error::Error>>` - The scheduled versions or an error
+    pub async fn read_scheduled_configs(
+        &self,
+        device_id: &str,
+    ) -> Result<Vec<Config>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' AND c.effective_at > '{}' AND {} \
+             ORDER BY c.effective_at ASC",
+            device_id,
+            format_query_time(chrono::Utc::now()),
+            NOT_DELETED
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .container_client
+            .query_items::<Config>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves the stored versions of a device's configuration, newest first
+    /// 
+    /// Versions are listed in the order they were stored, including ones
+    /// scheduled to become active later; deleted versions are skipped.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `limit` - Maximum number of versions to return
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Config>, Box<dyn std::error::Error>>` - The versions or an error
+    pub async fn read_config_history(
+        &self,
+        device_id: &str,
+        limit: usize,
+    ) -> Result<Vec<Config>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT TOP {} * FROM c WHERE c.device_id = '{}' AND {} \
+             ORDER BY c.timestamp DESC",
+            limit,
+            device_id,
+            NOT_DELETED
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .container_client
+            .query_items::<Config>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves a stored version of a device's configuration by its number
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `version_number` - The number of the version
+    /// 
+    /// # Returns
+    /// * `Result<Option<Config>, Box<dyn std::error::Error>>` - The version if it exists and
+    ///   is not deleted, or an error
+    pub async fn read_config_version(
+        &self,
+        device_id: &str,
+        version_number: u64,
+    ) -> Result<Option<Config>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT TOP 1 * FROM c WHERE c.device_id = '{}' AND c.version_number = {} AND {}",
+            device_id,
+            version_number,
+            NOT_DELETED
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .container_client
+            .query_items::<Config>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Marks every stored version of a device's configuration as deleted
+    /// 
+    /// Versions are kept with a `deleted` tombstone flag and the time of
+    /// deletion, so they are skipped by `read_config` but can be restored.
+    /// The device's entry in the device index is marked deleted too.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `deleted_at` - When the configuration was deleted
+    /// 
+    /// # Returns
+    /// * `Result<usize, Box<dyn std::error::Error>>` - The number of versions deleted or an error
+    pub async fn delete_configs(
+        &self,
+        device_id: &str,
+        deleted_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' AND {}",
+            device_id, NOT_DELETED
+        );
+        let documents = self.read_config_documents(device_id, query).await?;
+
+        for mut document in documents.iter().cloned() {
+            document["deleted"] = serde_json::Value::Bool(true);
+            document["deleted_at"] = serde_json::Value::String(format_query_time(deleted_at));
+            self.container_client
+                .upsert_item(device_id, &document, None)
+                .await?;
+        }
+
+        self.update_device_summary(device_id, |summary| {
+            summary.deleted_at = Some(deleted_at);
+            true
+        }).await?;
+
+        Ok(documents.len())
+    }
+
+    /// Marks a single stored version of a device's configuration as deleted
+    /// 
+    /// Used to withdraw a version that should never have been served, so the
+    /// device falls back to its previous version or the fleet-wide defaults.
+    /// If it was the device's latest version, the device's entry in the
+    /// device index is marked deleted too.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `version` - Identifier of the version to delete
+    /// * `deleted_at` - When the version was deleted
+    /// 
+    /// # Returns
+    /// * `Result<bool, Box<dyn std::error::Error>>` - True if the version was deleted, false if it
+    ///   does not exist or was already deleted, or an error
+    pub async fn delete_config_version(
+        &self,
+        device_id: &str,
+        version: &str,
+        deleted_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.id = '{}' AND {}",
+            version, NOT_DELETED
+        );
+        let Some(mut document) = self.read_config_documents(device_id, query).await?.into_iter().next() else {
+            return Ok(false);
+        };
+
+        document["deleted"] = serde_json::Value::Bool(true);
+        document["deleted_at"] = serde_json::Value::String(format_query_time(deleted_at));
+        self.container_client
+            .upsert_item(device_id, &document, None)
+            .await?;
+
+        self.update_device_summary(device_id, |summary| {
+            summary.deleted_at = Some(deleted_at);
+            summary.version == version
+        }).await?;
+
+        Ok(true)
+    }
+
+    /// Clears the tombstone from every deleted version of a device's configuration
+    /// 
+    /// The device's entry in the device index is restored too, so the
+    /// device is listed and served its latest active version again.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<usize, Box<dyn std::error::Error>>` - The number of versions restored or an error
+    pub async fn restore_configs(
+        &self,
+        device_id: &str,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' AND c.deleted = true",
+            device_id
+        );
+        let documents = self.read_config_documents(device_id, query).await?;
+
+        for mut document in documents.iter().cloned() {
+            if let Some(fields) = document.as_object_mut() {
+                fields.remove("deleted");
+                fields.remove("deleted_at");
+            }
+            self.container_client
+                .upsert_item(device_id, &document, None)
+                .await?;
+        }
+
+        self.update_device_summary(device_id, |summary| {
+            summary.deleted_at = None;
+            true
+        }).await?;
+
+        Ok(documents.len())
+    }
+
+    /// Runs a query over a device's stored configuration documents, returning
+    /// them as raw JSON so every stored field is preserved on rewrite
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `query` - The query to run within the device's partition
+    /// 
+    /// # Returns
+    /// * `Result<Vec<serde_json::Value>, Box<dyn std::error::Error>>` - The matching documents or an error
+    async fn read_config_documents(
+        &self,
+        device_id: &str,
+        query: String,
+    ) -> Result<Vec<serde_json::Value>, Box<dyn std::error::Error>> {
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .container_client
+            .query_items::<serde_json::Value>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Creates or replaces a rollout document
+    /// 
+    /// Rollouts are written as a whole on every state change, using the
+    /// rollout id as both the document id and the partition key.
+    /// 
+    /// # Arguments
+    /// * `rollout` - The rollout to store
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_rollout(
+        &self,
+        rollout: &Rollout,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.rollouts_client
+            .upsert_item(&rollout.id, rollout, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replaces a rollout, provided it was not changed since it was read
+    /// 
+    /// The write is conditioned on the rollout's entity tag, so of several
+    /// actions applied to the same rollout at once only the first succeeds.
+    /// 
+    /// # Arguments
+    /// * `rollout` - The updated rollout, carrying the tag it was read with
+    /// 
+    /// # Returns
+    /// * `Result<bool, Box<dyn std::error::Error>>` - False if the rollout was changed meanwhile, or an error
+    pub async fn replace_rollout(
+        &self,
+        rollout: &Rollout,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let options = ItemOptions {
+            if_match_etag: rollout.etag.clone().map(Etag::from),
+            ..Default::default()
+        };
+
+        match self.rollouts_client
+            .replace_item(&rollout.id, &rollout.id, rollout, Some(options))
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if is_precondition_failed(&e) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Retrieves a rollout by id
+    /// 
+    /// # Arguments
+    /// * `rollout_id` - The unique identifier of the rollout
+    /// 
+    /// # Returns
+    /// * `Result<Option<Rollout>, Box<dyn std::error::Error>>` - The rollout if found, or an error
+    pub async fn read_rollout(
+        &self,
+        rollout_id: &str,
+    ) -> Result<Option<Rollout>, Box<dyn std::error::Error>> {
+        let query = format!("SELECT * FROM c WHERE c.id = '{}'", rollout_id);
+        let partition_key = rollout_id.to_string();
+
+        let mut pager = self
+            .rollouts_client
+            .query_items::<Rollout>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Retrieves the rollouts whose device group includes a device, newest first
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Rollout>, Box<dyn std::error::Error>>` - The matching rollouts or an error
+    pub async fn read_device_rollouts(
+        &self,
+        device_id: &str,
+    ) -> Result<Vec<Rollout>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE ARRAY_CONTAINS(c.devices, '{}') ORDER BY c.created_at DESC",
+            device_id
+        );
+
+        let mut pager = self
+            .rollouts_client
+            .query_items::<Rollout>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Appends an entry to the audit trail
+    /// 
+    /// Entries are created with `create_item` and never replaced, so an
+    /// existing entry cannot be overwritten through this store.
+    /// 
+    /// # Arguments
+    /// * `entry` - The audit entry to record
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn insert_audit_entry(
+        &self,
+        entry: &AuditEntry,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.audit_client
+            .create_item(&entry.target, entry, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the most recent audit entries for a device or rollout
+    /// 
+    /// # Arguments
+    /// * `target` - The device ID or rollout ID
+    /// * `limit` - Maximum number of entries to return
+    /// 
+    /// # Returns
+    /// * `Result<Vec<AuditEntry>, Box<dyn std::error::Error>>` - Entries, newest first, or an error
+    pub async fn read_audit_entries(
+        &self,
+        target: &str,
+        limit: usize,
+    ) -> Result<Vec<AuditEntry>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT TOP {} * FROM c WHERE c.target = '{}' ORDER BY c.timestamp DESC",
+            limit, target
+        );
+        let partition_key = target.to_string();
+
+        let mut pager = self
+            .audit_client
+            .query_items::<AuditEntry>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Registers a firmware image
+    /// 
+    /// Images are created with `create_item`, using the version as both the
+    /// document id and the partition key, so a registered image cannot be
+    /// overwritten through this store.
+    /// 
+    /// # Arguments
+    /// * `image` - The image to register
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn insert_firmware_image(
+        &self,
+        image: &FirmwareImage,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.firmware_client
+            .create_item(&image.id, image, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves a firmware image by version
+    /// 
+    /// # Arguments
+    /// * `version` - The version of the image
+    /// 
+    /// # Returns
+    /// * `Result<Option<FirmwareImage>, Box<dyn std::error::Error>>` - The image if registered, or an error
+    pub async fn read_firmware_image(
+        &self,
+        version: &str,
+    ) -> Result<Option<FirmwareImage>, Box<dyn std::error::Error>> {
+        let query = format!("SELECT * FROM c WHERE c.id = '{}'", version);
+        let partition_key = version.to_string();
+
+        let mut pager = self
+            .firmware_client
+            .query_items::<FirmwareImage>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Retrieves every registered firmware image, newest first
+    /// 
+    /// # Returns
+    /// * `Result<Vec<FirmwareImage>, Box<dyn std::error::Error>>` - All images or an error
+    pub async fn read_firmware_images(
+        &self,
+    ) -> Result<Vec<FirmwareImage>, Box<dyn std::error::Error>> {
+        let query = "SELECT * FROM c ORDER BY c.created_at DESC";
+
+        let mut pager = self
+            .firmware_client
+            .query_items::<FirmwareImage>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Creates or replaces the firmware assignment of a device
+    /// 
+    /// # Arguments
+    /// * `assignment` - The assignment to store
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_firmware_assignment(
+        &self,
+        assignment: &FirmwareAssignment,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.firmware_assignments_client
+            .upsert_item(&assignment.device_id, assignment, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the firmware assignment of a device
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device identifier, or `_default` for the fleet-wide target
+    /// 
+    /// # Returns
+    /// * `Result<Option<FirmwareAssignment>, Box<dyn std::error::Error>>` - The assignment if any, or an error
+    pub async fn read_firmware_assignment(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<FirmwareAssignment>, Box<dyn std::error::Error>> {
+        let query = format!("SELECT * FROM c WHERE c.id = '{}'", device_id);
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .firmware_assignments_client
+            .query_items::<FirmwareAssignment>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Creates or replaces a temporary override
+    /// 
+    /// Overrides are written as a whole on every state change, using the
+    /// device id as the partition key.
+    /// 
+    /// # Arguments
+    /// * `item` - The override to store
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_override(
+        &self,
+        item: &ConfigOverride,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.overrides_client
+            .upsert_item(&item.device_id, item, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the overrides of a device, newest first
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Vec<ConfigOverride>, Box<dyn std::error::Error>>` - The device's overrides or an error
+    pub async fn read_overrides(
+        &self,
+        device_id: &str,
+    ) -> Result<Vec<ConfigOverride>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' ORDER BY c.created_at DESC",
+            device_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self
+            .overrides_client
+            .query_items::<ConfigOverride>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves the active overrides of every device
+    /// 
+    /// # Returns
+    /// * `Result<Vec<ConfigOverride>, Box<dyn std::error::Error>>` - All active overrides or an error
+    pub async fn read_active_overrides(
+        &self,
+    ) -> Result<Vec<ConfigOverride>, Box<dyn std::error::Error>> {
+        let query = "SELECT * FROM c WHERE c.status = 'active'";
+
+        let mut pager = self
+            .overrides_client
+            .query_items::<ConfigOverride>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Creates or replaces a configuration change submitted for approval
+    /// 
+    /// # Arguments
+    /// * `approval` - The change to store
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_approval(
+        &self,
+        approval: &ConfigApproval,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.approvals_client
+            .upsert_item(&approval.id, approval, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replaces a configuration change, provided nobody else replaced it
+    /// since it was read
+    /// 
+    /// The write is conditioned on the entity tag the change was read with,
+    /// so of two concurrent reviews only the first one succeeds.
+    /// 
+    /// # Arguments
+    /// * `approval` - The reviewed change, carrying the entity tag it was read with
+    /// 
+    /// # Returns
+    /// * `Result<bool, Box<dyn std::error::Error>>` - True if the change was replaced, false if it
+    ///   changed in the meantime, or an error
+    pub async fn replace_approval(
+        &self,
+        approval: &ConfigApproval,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let options = ItemOptions {
+            if_match_etag: approval.etag.clone().map(Etag::from),
+            ..Default::default()
+        };
+
+        match self.approvals_client
+            .replace_item(&approval.id, &approval.id, approval, Some(options))
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if is_precondition_failed(&e) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Retrieves a configuration change submitted for approval by id
+    /// 
+    /// # Arguments
+    /// * `approval_id` - The unique identifier of the change
+    /// 
+    /// # Returns
+    /// * `Result<Option<ConfigApproval>, Box<dyn std::error::Error>>` - The change if found, or an error
+    pub async fn read_approval(
+        &self,
+        approval_id: &str,
+    ) -> Result<Option<ConfigApproval>, Box<dyn std::error::Error>> {
+        let query = format!("SELECT * FROM c WHERE c.id = '{}'", approval_id);
+        let partition_key = approval_id.to_string();
+
+        let mut pager = self
+            .approvals_client
+            .query_items::<ConfigApproval>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Retrieves the configuration changes in a review state, oldest first
+    /// 
+    /// # Arguments
+    /// * `status` - The review state to list
+    /// 
+    /// # Returns
+    /// * `Result<Vec<ConfigApproval>, Box<dyn std::error::Error>>` - The matching changes or an error
+    pub async fn read_approvals(
+        &self,
+        status: ApprovalStatus,
+    ) -> Result<Vec<ConfigApproval>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.status = '{}' ORDER BY c.requested_at ASC",
+            status.as_str()
+        );
+
+        let mut pager = self
+            .approvals_client
+            .query_items::<ConfigApproval>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().into_iter().cloned());
+        }
+
+        Ok(items)
+    }
+}
+
+/// Query condition matching configuration documents that are not deleted
+/// 
+/// Documents stored before soft deletion existed have no `deleted` field.
+const NOT_DELETED: &str = "(NOT IS_DEFINED(c.deleted) OR c.deleted = false)";
+
+/// Formats a timestamp for storage and comparison in Cosmos DB queries
+/// 
+/// Uses a fixed-width RFC3339 representation (microsecond precision, `Z` suffix)
+/// so that lexicographic string comparison matches chronological order.
+fn format_query_time(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+/// True if a conditional write failed because the document was changed
+/// since it was read (412 Precondition Failed)
+fn is_precondition_failed(error: &azure_core::Error) -> bool {
+    error.http_status() == Some(StatusCode::PreconditionFailed)
+}
+
+/// True if a document could not be created because one with the same id
+/// already exists (409 Conflict)
+fn is_conflict(error: &azure_core::Error) -> bool {
+    error.http_status() == Some(StatusCode::Conflict)
 }
@@ -0,0 +1,170 @@
+// Configuration Change Event Publisher
+// 
+// This module delivers configuration change events to the webhooks and
+// the Azure Event Grid topic registered through the environment. Delivery
+// happens in the background so a slow or unavailable subscriber never
+// fails a configuration update.
+
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::domain::event::{ConfigChangeEvent, EventGridEvent};
+
+/// Number of delivery attempts per destination before an event is dropped
+const MAX_ATTEMPTS: u32 = 3;
+
+/// An Azure Event Grid custom topic
+#[derive(Clone)]
+struct EventGridTopic {
+    /// Topic endpoint, e.g. `https://<topic>.<region>-1.eventgrid.azure.net/api/events`
+    endpoint: String,
+    /// Topic access key sent in the `aeg-sas-key` header
+    key: String,
+}
+
+/// A destination events are delivered to
+#[derive(Clone)]
+enum Destination {
+    /// Receives the event as a JSON POST
+    Webhook(String),
+    /// Receives the event wrapped in the Event Grid event schema
+    EventGrid(EventGridTopic),
+}
+
+impl Destination {
+    /// URL the event is posted to, for logging
+    fn url(&self) -> &str {
+        match self {
+            Destination::Webhook(url) => url,
+            Destination::EventGrid(topic) => &topic.endpoint,
+        }
+    }
+}
+
+/// Publishes configuration change events to registered webhooks and Event Grid
+#[derive(Clone, Default)]
+pub struct EventPublisher {
+    /// Webhooks and topics that receive every event
+    destinations: Vec<Destination>,
+    /// HTTP client shared by all deliveries
+    client: reqwest::Client,
+}
+
+impl EventPublisher {
+    /// Creates a publisher delivering to the given webhooks
+    /// 
+    /// # Arguments
+    /// * `webhooks` - URLs to POST events to
+    /// 
+    /// # Returns
+    /// * `Self` - A new EventPublisher instance
+    pub fn new(webhooks: Vec<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        EventPublisher {
+            destinations: webhooks.into_iter().map(Destination::Webhook).collect(),
+            client,
+        }
+    }
+
+    /// Additionally publishes events to an Azure Event Grid topic
+    /// 
+    /// # Arguments
+    /// * `endpoint` - The topic endpoint URL
+    /// * `key` - The topic access key
+    /// 
+    /// # Returns
+    /// * `Self` - The EventPublisher with the topic registered
+    pub fn with_event_grid(mut self, endpoint: String, key: String) -> Self {
+        self.destinations.push(Destination::EventGrid(EventGridTopic { endpoint, key }));
+        self
+    }
+
+    /// Loads the registered webhooks and Event Grid topic from the environment
+    /// 
+    /// # Environment Variables
+    /// * `CONFIG_EVENT_WEBHOOKS` - Comma-separated list of webhook URLs
+    /// * `CONFIG_EVENT_GRID_ENDPOINT` - Event Grid topic endpoint
+    /// * `CONFIG_EVENT_GRID_KEY` - Event Grid topic access key
+    pub fn from_env() -> Self {
+        let webhooks = std::env::var("CONFIG_EVENT_WEBHOOKS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let publisher = Self::new(webhooks);
+
+        let endpoint = std::env::var("CONFIG_EVENT_GRID_ENDPOINT").ok().filter(|s| !s.is_empty());
+        let key = std::env::var("CONFIG_EVENT_GRID_KEY").ok().filter(|s| !s.is_empty());
+        match (endpoint, key) {
+            (Some(endpoint), Some(key)) => publisher.with_event_grid(endpoint, key),
+            (Some(_), None) => {
+                warn!("CONFIG_EVENT_GRID_ENDPOINT is set without CONFIG_EVENT_GRID_KEY - Event Grid publishing is disabled");
+                publisher
+            }
+            _ => publisher,
+        }
+    }
+
+    /// Delivers an event to every registered destination in the background
+    /// 
+    /// Each destination is retried with a short backoff; failures are
+    /// logged and otherwise ignored.
+    /// 
+    /// # Arguments
+    /// * `event` - The event to deliver
+    pub fn publish(&self, event: ConfigChangeEvent) {
+        for destination in &self.destinations {
+            let client = self.client.clone();
+            let destination = destination.clone();
+            let event = event.clone();
+
+            tokio::spawn(async move {
+                let url = destination.url();
+                for attempt in 1..=MAX_ATTEMPTS {
+                    match deliver(&client, &destination, &event).await {
+                        Ok(()) => {
+                            info!("Delivered config change event {} to {}", event.id, url);
+                            return;
+                        }
+                        Err(e) => {
+                            warn!("Config change event {} to {} failed (attempt {}): {}", event.id, url, attempt, e);
+                            if attempt < MAX_ATTEMPTS {
+                                tokio::time::sleep(Duration::from_millis(500 * 2u64.pow(attempt - 1))).await;
+                            }
+                        }
+                    }
+                }
+                warn!("Dropping config change event {} for {}", event.id, url);
+            });
+        }
+    }
+}
+
+/// Posts an event to a single destination, treating non-2xx responses as errors
+async fn deliver(
+    client: &reqwest::Client,
+    destination: &Destination,
+    event: &ConfigChangeEvent,
+) -> Result<(), reqwest::Error> {
+    let request = match destination {
+        Destination::Webhook(url) => client.post(url).json(event),
+        Destination::EventGrid(topic) => client
+            .post(&topic.endpoint)
+            .header("aeg-sas-key", &topic.key)
+            .json(&[EventGridEvent::from(event.clone())]),
+    };
+
+    request
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+// Configuration Fetch Rate Limiter
+//
+// This module limits how often each device may fetch its configuration, so
+// a device stuck polling in a tight loop cannot overload the service and
+// Cosmos DB. Each device gets a token bucket: it may burst up to the limit,
+// and regains tokens evenly over the window.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default number of fetches allowed per device and window
+pub const DEFAULT_FETCH_LIMIT: u32 = 60;
+
+/// Default length of the rate limit window, in seconds
+pub const DEFAULT_FETCH_WINDOW_SECS: u64 = 60;
+
+/// Number of tracked devices above which idle buckets are dropped
+const PRUNE_THRESHOLD: usize = 4096;
+
+/// Fetch allowance of a single device
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    /// Fetches the device may still make right now
+    tokens: f64,
+    /// When `tokens` was last brought up to date
+    updated: Instant,
+}
+
+/// Per-device configuration fetch rate limiter
+///
+/// The limiter is shared between request handlers; clones share the same
+/// buckets. Limits are enforced per service instance.
+#[derive(Clone)]
+pub struct FetchRateLimiter {
+    /// Fetches allowed per window; 0 disables the limiter
+    limit: u32,
+    /// Window over which `limit` fetches are allowed
+    window: Duration,
+    /// Allowance of every device that fetched recently
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl Default for FetchRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_FETCH_LIMIT, Duration::from_secs(DEFAULT_FETCH_WINDOW_SECS))
+    }
+}
+
+impl FetchRateLimiter {
+    /// Creates a rate limiter
+    ///
+    /// # Arguments
+    /// * `limit` - Fetches allowed per device and window (0 for no limit)
+    /// * `window` - The window the limit applies to
+    ///
+    /// # Returns
+    /// * `Self` - A new FetchRateLimiter instance
+    pub fn new(limit: u32, window: Duration) -> Self {
+        FetchRateLimiter {
+            limit,
+            window,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Creates a rate limiter that allows every fetch
+    pub fn disabled() -> Self {
+        Self::new(0, Duration::from_secs(DEFAULT_FETCH_WINDOW_SECS))
+    }
+
+    /// Loads the rate limit from the environment
+    ///
+    /// # Environment Variables
+    /// * `CONFIG_FETCH_RATE_LIMIT` - Fetches allowed per device and window (0 disables the limit)
+    /// * `CONFIG_FETCH_RATE_WINDOW_SECS` - Length of the window in seconds
+    ///
+    /// # Returns
+    /// * `Result<Self, String>` - The rate limiter, or a description of the invalid variable
+    pub fn from_env() -> Result<Self, String> {
+        let limit = match std::env::var("CONFIG_FETCH_RATE_LIMIT") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse()
+                .map_err(|_| format!("CONFIG_FETCH_RATE_LIMIT must be a non-negative integer, got {:?}", value))?,
+            _ => DEFAULT_FETCH_LIMIT,
+        };
+        let window_secs = match std::env::var("CONFIG_FETCH_RATE_WINDOW_SECS") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .ok_or_else(|| format!("CONFIG_FETCH_RATE_WINDOW_SECS must be a positive integer, got {:?}", value))?,
+            _ => DEFAULT_FETCH_WINDOW_SECS,
+        };
+
+        Ok(Self::new(limit, Duration::from_secs(window_secs)))
+    }
+
+    /// Returns whether fetches are limited at all
+    pub fn is_enabled(&self) -> bool {
+        self.limit > 0
+    }
+
+    /// Records a fetch by a device, if it is within its allowance
+    ///
+    /// # Arguments
+    /// * `device_id` - The device fetching its configuration
+    ///
+    /// # Returns
+    /// * `Result<(), u64>` - Success, or the number of seconds to wait before retrying
+    pub fn check(&self, device_id: &str) -> Result<(), u64> {
+        self.check_at(device_id, Instant::now())
+    }
+
+    /// Records a fetch by a device at the given time
+    fn check_at(&self, device_id: &str, now: Instant) -> Result<(), u64> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let capacity = f64::from(self.limit);
+        let per_second = capacity / self.window.as_secs_f64();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Buckets idle for a whole window are full again, so forgetting them changes nothing
+        if buckets.len() >= PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| now.saturating_duration_since(bucket.updated) < self.window);
+        }
+
+        let bucket = buckets
+            .entry(device_id.to_string())
+            .or_insert(Bucket { tokens: capacity, updated: now });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err((((1.0 - bucket.tokens) / per_second).ceil() as u64).max(1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_burst_then_limits() {
+        let limiter = FetchRateLimiter::new(3, Duration::from_secs(60));
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at("device-1", now).is_ok());
+        }
+        assert_eq!(limiter.check_at("device-1", now), Err(20));
+
+        // Other devices have their own allowance
+        assert!(limiter.check_at("device-2", now).is_ok());
+    }
+
+    #[test]
+    fn test_refills_over_the_window() {
+        let limiter = FetchRateLimiter::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+
+        assert!(limiter.check_at("device-1", now).is_ok());
+        assert!(limiter.check_at("device-1", now).is_ok());
+        assert_eq!(limiter.check_at("device-1", now + Duration::from_secs(1)), Err(4));
+        assert!(limiter.check_at("device-1", now + Duration::from_secs(6)).is_ok());
+        assert!(limiter.check_at("device-1", now + Duration::from_secs(6)).is_err());
+    }
+
+    #[test]
+    fn test_disabled_limiter_allows_everything() {
+        let limiter = FetchRateLimiter::disabled();
+        let now = Instant::now();
+
+        assert!(!limiter.is_enabled());
+        for _ in 0..1000 {
+            assert!(limiter.check_at("device-1", now).is_ok());
+        }
+    }
+}
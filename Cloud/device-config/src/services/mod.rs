@@ -6,7 +6,15 @@
 
 pub mod cosmos_db_telemetry_store;
 pub mod azure_auth;
+pub mod event_publisher;
+pub mod config_notifier;
+pub mod fetch_limiter;
+pub mod jwks;
 
 // Re-export service types for convenient access
 pub use azure_auth::AzureAuth;
-pub use cosmos_db_telemetry_store::CosmosDbTelemetryStore;
\ No newline at end of file
+pub use cosmos_db_telemetry_store::CosmosDbTelemetryStore;
+pub use event_publisher::EventPublisher;
+pub use config_notifier::ConfigNotifier;
+pub use fetch_limiter::FetchRateLimiter;
+pub use jwks::JwksCache;
\ No newline at end of file
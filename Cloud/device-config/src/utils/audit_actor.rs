@@ -0,0 +1,31 @@
+// Audit Actor Request Guard
+//
+// This module extracts the identity and origin of the caller from an
+// incoming request so configuration mutations can be recorded in the
+// audit log.
+
+use rocket::request::{FromRequest, Outcome, Request};
+
+use crate::domain::audit::AuditActor;
+use crate::domain::auth::AuthError;
+use crate::utils::auth_guard::verified_principal;
+
+/// Resolves the caller of a request for the audit log
+///
+/// The subject is the caller verified by the route's access guard: the
+/// `sub` claim of a verified bearer token, the name of the API key, the
+/// device of a device key, or `anonymous` while authorization is disabled.
+/// Unverified credentials are never recorded; a request whose credentials
+/// fail verification is rejected like by the access guards.
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuditActor {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        verified_principal(request).await.map(|principal| AuditActor {
+            subject: principal.subject,
+            source_ip: request.client_ip().map(|ip| ip.to_string()),
+            user_agent: request.headers().get_one("User-Agent").map(str::to_string),
+        })
+    }
+}
@@ -0,0 +1,221 @@
+// Authorization Request Guards
+// 
+// This module provides Rocket request guards that enforce the role
+// required by each configuration endpoint. A route declares the access it
+// needs by taking one of `ReadAccess`, `OperatorAccess` or `AdminAccess`
+// as an argument. The device configuration fetch takes `DeviceAccess`
+// instead, and the applied-configuration report `DeviceReportAccess`,
+// which also admit the requested device's own key. The caller verified by
+// these guards is kept for the request, so the audit log records it.
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use tracing::warn;
+
+use crate::app_state::AppState;
+use crate::domain::auth::{AuthConfig, AuthError, Principal, Role};
+
+/// Authenticates the request and checks the caller holds the required role
+/// 
+/// Credentials are read from `Authorization: Bearer <jwt>` or `X-API-Key`.
+/// Failures are mapped to 401 Unauthorized or 403 Forbidden.
+async fn authorize(request: &Request<'_>, required: Role) -> Outcome<Principal, AuthError> {
+    check(request, |auth, bearer, api_key| auth.authorize(bearer, api_key, required)).await
+}
+
+/// Authenticates the request as the device named by the route's
+/// `<device_id>` segment, or as a caller holding the required role
+async fn authorize_device(request: &Request<'_>, required: Role) -> Outcome<Principal, AuthError> {
+    let device_id = device_param(request).unwrap_or_default();
+    check(request, |auth, bearer, api_key| auth.authorize_device(bearer, api_key, device_id, required)).await
+}
+
+/// Returns the `<device_id>` path segment of the matched route, if it has one
+fn device_param<'r>(request: &'r Request<'_>) -> Option<&'r str> {
+    let route = request.route()?;
+    let index = route.uri.unmounted_origin.path().segments().position(|segment| segment == "<device_id>")?;
+    request.routed_segment(index)
+}
+
+/// The caller verified by the first access guard of a request
+struct VerifiedPrincipal(Option<Principal>);
+
+/// Returns the verified caller of a request
+/// 
+/// Routes take their access guard before the audit actor, so the caller
+/// verified by that guard is returned; a request no guard has verified yet
+/// is authenticated here with the lowest role.
+pub(crate) async fn verified_principal(request: &Request<'_>) -> Outcome<Principal, AuthError> {
+    if let Some(principal) = &request.local_cache(|| VerifiedPrincipal(None)).0 {
+        return Outcome::Success(principal.clone());
+    }
+    authorize(request, Role::Device).await
+}
+
+/// Reads the request's credentials and runs the given check against them
+/// 
+/// The Azure AD signing key a bearer token names is fetched first if it is
+/// not cached yet. The first caller verified is kept for `verified_principal`.
+async fn check<F>(request: &Request<'_>, verify: F) -> Outcome<Principal, AuthError>
+where
+    F: FnOnce(&AuthConfig, Option<&str>, Option<&str>) -> Result<Principal, AuthError>,
+{
+    let state = match request.rocket().state::<AppState>() {
+        Some(state) => state,
+        None => return Outcome::Error((Status::InternalServerError, AuthError::MissingCredentials)),
+    };
+
+    let headers = request.headers();
+    let bearer = headers
+        .get_one("Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let api_key = headers.get_one("X-API-Key");
+
+    state.auth.load_signing_key(bearer).await;
+    match verify(&state.auth, bearer, api_key) {
+        Ok(principal) => {
+            request.local_cache(|| VerifiedPrincipal(Some(principal.clone())));
+            Outcome::Success(principal)
+        }
+        Err(e) => {
+            warn!("Authorization failed for {}: {}", request.uri(), e);
+            Outcome::Error((Status::from(&e), e))
+        }
+    }
+}
+
+/// Grants access to callers with at least the read-only role
+pub struct ReadAccess(pub Principal);
+
+/// Grants access to callers with at least the operator role
+pub struct OperatorAccess(pub Principal);
+
+/// Grants access to callers with the admin role
+pub struct AdminAccess(pub Principal);
+
+/// Grants access to the device named by the route's `<device_id>` segment,
+/// or to callers with at least the read-only role
+pub struct DeviceAccess(pub Principal);
+
+/// Grants access to the device named by the route's `<device_id>` segment,
+/// or to callers with at least the operator role
+pub struct DeviceReportAccess(pub Principal);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ReadAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize(request, Role::ReadOnly).await.map(ReadAccess)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for OperatorAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize(request, Role::Operator).await.map(OperatorAccess)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize(request, Role::Admin).await.map(AdminAccess)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for DeviceAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize_device(request, Role::ReadOnly).await.map(DeviceAccess)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for DeviceReportAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize_device(request, Role::Operator).await.map(DeviceReportAccess)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::http::Header;
+    use rocket::local::asynchronous::Client;
+
+    use crate::domain::auth::{ApiKey, DeviceKey};
+    use crate::services::CosmosDbTelemetryStore;
+
+    #[get("/get/<device_id>")]
+    fn fetch(_access: DeviceAccess, device_id: &str) -> String {
+        device_id.to_string()
+    }
+
+    #[post("/reported/<device_id>")]
+    fn report(_access: DeviceReportAccess, device_id: &str) -> String {
+        device_id.to_string()
+    }
+
+    /// Builds a client for the guarded test routes; the store is never contacted
+    async fn client() -> Client {
+        let store = CosmosDbTelemetryStore::with_key(
+            "https://localhost:8081",
+            "dGVzdA==".to_string(),
+            "test-device-data",
+            "test-config",
+        )
+        .unwrap();
+        let auth = AuthConfig::new(
+            vec![ApiKey { name: "dashboard".to_string(), key: "reader-key".to_string(), role: "readonly".to_string() }],
+            None,
+        )
+        .with_device_keys(vec![DeviceKey { device_id: "sensor-001".to_string(), key: "device-key".to_string() }]);
+        let state = AppState::new(store).with_auth_config(auth);
+
+        let rocket = rocket::build().manage(state).mount("/device-config", routes![fetch, report]);
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[rocket::async_test]
+    async fn test_device_key_is_checked_against_device_id_segment() {
+        let client = client().await;
+
+        let own = client.get("/device-config/get/sensor-001")
+            .header(Header::new("X-API-Key", "device-key"))
+            .dispatch()
+            .await;
+        assert_eq!(own.status(), Status::Ok);
+
+        let other = client.get("/device-config/get/sensor-002")
+            .header(Header::new("X-API-Key", "device-key"))
+            .dispatch()
+            .await;
+        assert_eq!(other.status(), Status::Forbidden);
+    }
+
+    #[rocket::async_test]
+    async fn test_device_key_reports_for_its_own_device() {
+        let client = client().await;
+
+        let own = client.post("/device-config/reported/sensor-001")
+            .header(Header::new("X-API-Key", "device-key"))
+            .dispatch()
+            .await;
+        assert_eq!(own.status(), Status::Ok);
+
+        let reader = client.post("/device-config/reported/sensor-001")
+            .header(Header::new("X-API-Key", "reader-key"))
+            .dispatch()
+            .await;
+        assert_eq!(reader.status(), Status::Forbidden);
+    }
+}
@@ -0,0 +1,167 @@
+// Conditional Request Guard
+// 
+// This module parses the `If-None-Match` header so `get_config` can answer
+// 304 Not Modified when the caller already has the current configuration,
+// and builds the caching headers that let a CDN or reverse proxy answer
+// fleet polling on the service's behalf.
+
+use chrono::{DateTime, Utc};
+use rocket::request::{FromRequest, Outcome, Request};
+
+/// Default number of seconds shared caches may serve a configuration without revalidating
+pub const DEFAULT_CACHE_MAX_AGE: u64 = 0;
+
+/// How long served configurations may be cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Seconds a cache may serve a configuration without revalidating it
+    pub max_age: u64,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        CachePolicy { max_age: DEFAULT_CACHE_MAX_AGE }
+    }
+}
+
+impl CachePolicy {
+    /// Loads the cache lifetime from the environment
+    /// 
+    /// # Environment Variables
+    /// * `CONFIG_CACHE_MAX_AGE` - Seconds a cache may serve a configuration without revalidating it
+    /// 
+    /// # Returns
+    /// * `Result<Self, String>` - The policy, or a description of the invalid variable
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("CONFIG_CACHE_MAX_AGE") {
+            Ok(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse()
+                .map(|max_age| CachePolicy { max_age })
+                .map_err(|_| format!("CONFIG_CACHE_MAX_AGE must be a non-negative integer, got {:?}", value)),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Returns the `Cache-Control` header value for a served configuration
+    /// 
+    /// Configurations holding secret values are never stored by shared
+    /// caches. Neither are configurations served only to authenticated
+    /// callers: a shared cache does not key its entries on credentials, so
+    /// it would hand them to anyone. Otherwise they are marked `public` so
+    /// a CDN can answer fleet polling.
+    /// 
+    /// # Arguments
+    /// * `has_secrets` - Whether the response holds decrypted secret values
+    /// * `requires_auth` - Whether reading the configuration requires credentials
+    pub fn cache_control(&self, has_secrets: bool, requires_auth: bool) -> String {
+        let scope = if requires_auth { "private" } else { "public" };
+        if has_secrets {
+            "private, no-cache".to_string()
+        } else if self.max_age == 0 {
+            format!("{}, no-cache", scope)
+        } else {
+            format!("{}, max-age={}, must-revalidate", scope, self.max_age)
+        }
+    }
+}
+
+/// Formats a time as an HTTP date, e.g. for `Last-Modified`
+/// 
+/// # Arguments
+/// * `time` - The time to format
+/// 
+/// # Returns
+/// * `String` - e.g. "Wed, 01 Jan 2025 00:00:00 GMT"
+pub fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Entity tags the caller already holds, from `If-None-Match`
+/// 
+/// The guard never fails; a missing header yields an empty tag list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IfNoneMatch(pub Vec<String>);
+
+impl IfNoneMatch {
+    /// Parses an `If-None-Match` header value
+    /// 
+    /// Accepts a comma-separated list of quoted, optionally weak (`W/`),
+    /// tags, or `*`.
+    /// 
+    /// # Arguments
+    /// * `value` - The raw header value
+    /// 
+    /// # Returns
+    /// * `Self` - The unquoted tags
+    pub fn parse(value: &str) -> Self {
+        IfNoneMatch(
+            value
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/").trim_matches('"').to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+        )
+    }
+
+    /// Adds a tag given another way, e.g. as a query parameter
+    pub fn with(mut self, tag: Option<String>) -> Self {
+        self.0.extend(tag.filter(|tag| !tag.is_empty()));
+        self
+    }
+
+    /// Returns true if the caller already holds the given tag
+    pub fn matches(&self, etag: &str) -> bool {
+        self.0.iter().any(|tag| tag == etag || tag == "*")
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfNoneMatch {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(
+            request
+                .headers()
+                .get_one("If-None-Match")
+                .map(IfNoneMatch::parse)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_if_none_match() {
+        let tags = IfNoneMatch::parse(r#""abc", W/"def""#);
+
+        assert_eq!(tags, IfNoneMatch(vec!["abc".to_string(), "def".to_string()]));
+        assert!(tags.matches("def"));
+        assert!(!tags.matches("xyz"));
+        assert!(IfNoneMatch::parse("*").matches("anything"));
+        assert!(IfNoneMatch::default().with(Some("abc".to_string())).matches("abc"));
+    }
+
+    #[test]
+    fn test_cache_control() {
+        assert_eq!(CachePolicy::default().cache_control(false, false), "public, no-cache");
+        assert_eq!(CachePolicy { max_age: 30 }.cache_control(false, false), "public, max-age=30, must-revalidate");
+        assert_eq!(CachePolicy { max_age: 30 }.cache_control(true, false), "private, no-cache");
+
+        // Authenticated responses are never stored by shared caches
+        assert_eq!(CachePolicy::default().cache_control(false, true), "private, no-cache");
+        assert_eq!(CachePolicy { max_age: 30 }.cache_control(false, true), "private, max-age=30, must-revalidate");
+        assert_eq!(CachePolicy { max_age: 30 }.cache_control(true, true), "private, no-cache");
+    }
+
+    #[test]
+    fn test_http_date() {
+        let time = DateTime::parse_from_rfc3339("2025-01-01T08:05:03.250Z").unwrap().with_timezone(&Utc);
+
+        assert_eq!(http_date(time), "Wed, 01 Jan 2025 08:05:03 GMT");
+    }
+}
@@ -4,6 +4,9 @@
 // the device configuration service, including logging and tracing utilities.
 
 pub mod tracing;
+pub mod audit_actor;
+pub mod auth_guard;
+pub mod conditional;
 
 // Re-export all tracing utilities for convenient access
 pub use tracing::*;
\ No newline at end of file
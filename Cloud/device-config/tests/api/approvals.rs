@@ -0,0 +1,251 @@
+// Configuration Approval API Integration Tests
+// 
+// This module contains integration tests for the four-eyes approval flow:
+// updates held as pending changes and the /device-config/approvals endpoints.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType, Header};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::approval::{ApprovalStatus, ConfigApproval};
+use device_config::domain::auth::{ApiKey, AuthConfig};
+use device_config::domain::config::Config;
+
+/// API key of the operator submitting changes
+const OPERATOR_KEY: &str = "approval-operator-0001";
+
+/// API keys of two admins; their last four characters differ, so the audit
+/// log tells them apart
+const ADMIN_KEY: &str = "approval-admin-0002";
+const OTHER_ADMIN_KEY: &str = "approval-admin-0003";
+
+/// Creates a test app that requires approval, with one operator and two admins
+async fn approval_app() -> TestApp {
+    let key = |name: &str, key: &str, role: &str| ApiKey {
+        name: name.to_string(),
+        key: key.to_string(),
+        role: role.to_string(),
+    };
+    let auth = AuthConfig::new(
+        vec![
+            key("operator", OPERATOR_KEY, "operator"),
+            key("admin", ADMIN_KEY, "admin"),
+            key("other-admin", OTHER_ADMIN_KEY, "admin"),
+        ],
+        None,
+    );
+
+    TestApp::with_state(|state| state.with_auth_config(auth).with_approval_required(true))
+        .await
+        .expect("Failed to create test app")
+}
+
+/// Generates a device ID without configuration left over from earlier test runs
+fn unique_device_id() -> String {
+    format!("test_approval_{}", &uuid::Uuid::new_v4().simple().to_string()[..12])
+}
+
+/// Submits a configuration update as the given caller
+async fn submit(client: &Client, api_key: &str, device_id: &str, led: &str) -> ConfigApproval {
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", api_key.to_string()))
+        .body(serde_json::json!({ "device_id": device_id, "config": { "LED": led } }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Accepted);
+    response.into_json().await.expect("Invalid pending change")
+}
+
+/// Reads the device's own configuration served to it, if any
+async fn served_config(client: &Client, device_id: &str) -> Option<Config> {
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .header(Header::new("X-API-Key", ADMIN_KEY))
+        .dispatch()
+        .await;
+    if response.status() != Status::Ok {
+        return None;
+    }
+    let configs: Vec<Config> = response.into_json().await.expect("Invalid configuration");
+    configs.into_iter().find(|config| !config.is_default)
+}
+
+/// Reads the configuration served to a device, if any
+async fn served_led(client: &Client, device_id: &str) -> Option<String> {
+    served_config(client, device_id)
+        .await
+        .and_then(|config| config.config.get("LED").cloned())
+}
+
+/// Test that an approved change reaches the device
+/// 
+/// This test verifies that:
+/// - Updates are held as pending changes and not served to the device
+/// - The author of a change cannot approve it, and operators cannot review
+/// - A second admin's approval stores the configuration, once, keeping the
+///   submitter as its author and recording the approver
+#[tokio::test]
+async fn test_approved_change_is_applied() {
+    dotenv().ok();
+
+    let app = approval_app().await;
+    let client: &Client = &app.client;
+    let device_id = unique_device_id();
+
+    let pending = submit(client, OPERATOR_KEY, &device_id, "on").await;
+    assert_eq!(pending.status, ApprovalStatus::Pending);
+    assert_eq!(pending.device_id, device_id);
+    assert_eq!(served_led(client, &device_id).await, None);
+
+    let response = client
+        .get("/device-config/approvals")
+        .header(Header::new("X-API-Key", OPERATOR_KEY))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let listed: Vec<ConfigApproval> = response.into_json().await.expect("Invalid approvals");
+    assert!(listed.iter().any(|change| change.id == pending.id));
+
+    let approve_url = format!("/device-config/approvals/{}/approve", pending.id);
+    let response = client
+        .post(approve_url.clone())
+        .header(Header::new("X-API-Key", OPERATOR_KEY))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    // An admin's own change needs another admin
+    let own = submit(client, ADMIN_KEY, &unique_device_id(), "off").await;
+    let response = client
+        .post(format!("/device-config/approvals/{}/approve", own.id))
+        .header(Header::new("X-API-Key", ADMIN_KEY))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .post(approve_url.clone())
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", ADMIN_KEY))
+        .body(serde_json::json!({ "reason": "Scheduled maintenance" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let approved: ConfigApproval = response.into_json().await.expect("Invalid approval");
+    assert_eq!(approved.status, ApprovalStatus::Approved);
+    assert_eq!(approved.reason.as_deref(), Some("Scheduled maintenance"));
+    let served = served_config(client, &device_id).await.expect("Approved change not served");
+    assert_eq!(served.config.get("LED").map(String::as_str), Some("on"));
+    assert_eq!(served.modified_by.as_deref(), Some("operator"));
+    assert_eq!(served.approved_by.as_deref(), Some("admin"));
+
+    let response = client
+        .post(approve_url)
+        .header(Header::new("X-API-Key", OTHER_ADMIN_KEY))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+}
+
+/// Test that a rejected change is discarded
+/// 
+/// This test verifies that a rejected change is never served and is
+/// listed among the rejected changes.
+#[tokio::test]
+async fn test_rejected_change_is_discarded() {
+    dotenv().ok();
+
+    let app = approval_app().await;
+    let client: &Client = &app.client;
+    let device_id = unique_device_id();
+
+    let pending = submit(client, OPERATOR_KEY, &device_id, "on").await;
+
+    let response = client
+        .post(format!("/device-config/approvals/{}/reject", pending.id))
+        .header(Header::new("X-API-Key", OTHER_ADMIN_KEY))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/approvals/{}", pending.id))
+        .header(Header::new("X-API-Key", OPERATOR_KEY))
+        .dispatch()
+        .await;
+    let rejected: ConfigApproval = response.into_json().await.expect("Invalid approval");
+    assert_eq!(rejected.status, ApprovalStatus::Rejected);
+    assert!(rejected.reviewed_by.is_some());
+    assert_eq!(served_led(client, &device_id).await, None);
+
+    let response = client
+        .get("/device-config/approvals?status=unknown")
+        .header(Header::new("X-API-Key", OPERATOR_KEY))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test that writes which cannot be held for review are refused
+/// 
+/// This test verifies that while approval is required, rollouts,
+/// overrides, imports and the fleet defaults return 409 Conflict instead
+/// of storing a configuration, and that import dry runs are still allowed.
+#[tokio::test]
+async fn test_unreviewed_writes_are_refused() {
+    dotenv().ok();
+
+    let app = approval_app().await;
+    let client: &Client = &app.client;
+    let device_id = unique_device_id();
+
+    let rollout = serde_json::json!({
+        "name": "approval-bypass",
+        "devices": [device_id],
+        "canary": { "percentage": 100 },
+        "config": { "LED": "on" }
+    });
+    let override_request = serde_json::json!({ "device_id": device_id, "config": { "LED": "on" }, "ttl_secs": 60 });
+    let bundle = serde_json::json!({
+        "format_version": 1,
+        "exported_at": "2025-01-01T00:00:00Z",
+        "configs": [{ "device_id": device_id, "config": { "LED": "on" } }]
+    });
+    let defaults = serde_json::json!({ "config": { "LED": "on" } });
+
+    for (url, body) in [
+        ("/device-config/rollouts", rollout),
+        ("/device-config/overrides", override_request),
+        ("/device-config/import", bundle.clone()),
+    ] {
+        let response = client
+            .post(url)
+            .header(ContentType::JSON)
+            .header(Header::new("X-API-Key", ADMIN_KEY))
+            .body(body.to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Conflict, "{}", url);
+    }
+
+    let response = client
+        .put("/device-config/defaults")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", ADMIN_KEY))
+        .body(defaults.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+    assert_eq!(served_led(client, &device_id).await, None);
+
+    let response = client
+        .post("/device-config/import?dry_run=true")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", ADMIN_KEY))
+        .body(bundle.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
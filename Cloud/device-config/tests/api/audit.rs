@@ -0,0 +1,72 @@
+// Configuration Audit API Integration Tests
+// 
+// This module contains integration tests for the GET /device-config/audit
+// endpoint of the device configuration service.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType, Header};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::auth::{ApiKey, AuthConfig};
+
+/// Test that a configuration update is recorded in the audit trail
+/// 
+/// This test verifies that the caller, as named by its verified API key,
+/// and the action and changed keys of an update are returned by the audit
+/// endpoint.
+#[tokio::test]
+async fn test_audit_records_config_update() {
+    dotenv().ok();
+
+    let auth = AuthConfig::new(
+        vec![ApiKey { name: "ci-pipeline".to_string(), key: "test-key-abcd".to_string(), role: "admin".to_string() }],
+        None,
+    );
+    let app = TestApp::with_auth_config(auth).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "test-key-abcd"))
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/audit/{}", device_id))
+        .header(Header::new("X-API-Key", "test-key-abcd"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let entries: serde_json::Value = response.into_json().await.unwrap();
+    let entry = &entries[0];
+    assert_eq!(entry["action"], "config_update");
+    assert_eq!(entry["actor"]["subject"], "ci-pipeline");
+    assert_eq!(entry["changes"].as_array().unwrap().len(), 4);
+}
+
+/// Test querying the audit trail of a device without changes
+/// 
+/// This test verifies that an empty array is returned rather than an error.
+#[tokio::test]
+async fn test_audit_empty_for_unknown_device() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/device-config/audit/{}", device_id))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_string().await.unwrap();
+    assert_eq!(body, "[]");
+}
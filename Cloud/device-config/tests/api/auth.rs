@@ -0,0 +1,225 @@
+// Authorization API Integration Tests
+// 
+// This module contains integration tests for the role-based access checks
+// on the device configuration service endpoints.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType, Header};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::auth::{ApiKey, AuthConfig, DeviceKey};
+
+/// Creates an authorization configuration with one key per role
+fn test_auth_config() -> AuthConfig {
+    let key = |name: &str, role: &str| ApiKey {
+        name: name.to_string(),
+        key: format!("{}-key", name),
+        role: role.to_string(),
+    };
+
+    AuthConfig::new(
+        vec![key("reader", "read_only"), key("operator", "operator"), key("admin", "admin")],
+        None,
+    )
+}
+
+/// Test that requests without credentials are rejected
+/// 
+/// This test verifies that the API returns 401 Unauthorized when
+/// authorization is configured and no credentials are sent.
+#[tokio::test]
+async fn test_missing_credentials_unauthorized() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+/// Test that a read-only key cannot update configuration
+/// 
+/// This test verifies that the API returns 403 Forbidden for a caller
+/// whose role is below operator.
+#[tokio::test]
+async fn test_read_only_cannot_update() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "reader-key"))
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+/// Test that an operator key can update and read configuration
+/// 
+/// This test verifies that a caller with the operator role passes both
+/// the operator and read-only checks, but not the admin check.
+#[tokio::test]
+async fn test_operator_can_update_and_read() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "operator-key"))
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .header(Header::new("X-API-Key", "operator-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The audit trail is restricted to admins
+    let response = client
+        .get(format!("/device-config/audit/{}", device_id))
+        .header(Header::new("X-API-Key", "operator-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+/// Test that an operator key cannot replace the fleet defaults
+/// 
+/// This test verifies that the API returns 403 Forbidden, since the
+/// defaults apply to every unconfigured device and require the admin role.
+#[tokio::test]
+async fn test_operator_cannot_update_defaults() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .put("/device-config/defaults")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "operator-key"))
+        .body(serde_json::json!({ "config": { "LED": "off" } }).to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+/// Test that an operator key cannot export configurations
+/// 
+/// This test verifies that the API returns 403 Forbidden, since the export
+/// holds every device's configuration and requires the admin role.
+#[tokio::test]
+async fn test_operator_cannot_export_configs() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .get("/device-config/export")
+        .header(Header::new("X-API-Key", "operator-key"))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+/// Test that operators cannot register firmware images
+/// 
+/// This test verifies that the API returns 403 Forbidden, since a
+/// registered image can be installed on any device and requires the admin role.
+#[tokio::test]
+async fn test_operator_cannot_register_firmware() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .post("/device-config/firmware")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "operator-key"))
+        .body(serde_json::json!({
+            "version": "1.4.0",
+            "url": "https://firmware.example.com/rp-rot/1.4.0.bin",
+            "sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+            "signature": "c2ln"
+        }).to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+/// Test that a device can fetch its own configuration with its device key
+/// 
+/// This test verifies that the configuration fetch accepts the requested
+/// device's key while authorization is enabled, and rejects it for any
+/// other device.
+#[tokio::test]
+async fn test_device_key_fetches_own_config() {
+    dotenv().ok();
+
+    let device_id = format!("test_device_key_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let auth = test_auth_config().with_device_keys(vec![DeviceKey {
+        device_id: device_id.clone(),
+        key: "device-key".to_string(),
+    }]);
+    let app = TestApp::with_auth_config(auth).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "operator-key"))
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .header(Header::new("X-API-Key", "device-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The key does not cover other devices or any other endpoint
+    let response = client
+        .get(format!("/device-config/get/{}", app.generate_test_device_id()))
+        .header(Header::new("X-API-Key", "device-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .get(format!("/device-config/history/{}", device_id))
+        .header(Header::new("X-API-Key", "device-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
@@ -0,0 +1,226 @@
+// Bulk Export/Import API Integration Tests
+// 
+// This module contains integration tests for the GET /device-config/export
+// and POST /device-config/import endpoints of the device configuration service.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::{Config, ConfigBundle, ImportOutcome, ImportReport, DEFAULT_CONFIG_ID};
+
+/// Builds a bundle document holding the given configurations
+fn bundle(configs: serde_json::Value) -> String {
+    serde_json::json!({
+        "format_version": 1,
+        "exported_at": "2025-01-01T00:00:00Z",
+        "configs": configs
+    })
+    .to_string()
+}
+
+/// Test that stored configurations are exported
+/// 
+/// This test verifies that the JSON export holds the active configuration
+/// of a stored device and that YAML is served with a YAML content type.
+#[tokio::test]
+async fn test_export_includes_stored_config() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(&device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/device-config/export").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let exported: ConfigBundle = response.into_json().await.expect("Invalid export");
+    let config = exported.configs
+        .iter()
+        .find(|config| config.device_id == device_id)
+        .expect("Stored device missing from export");
+    assert_eq!(config.config["sampling_rate"], "1000");
+
+    let response = client.get("/device-config/export?format=yaml").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::new("application", "yaml")));
+}
+
+/// Test a dry-run import
+/// 
+/// This test verifies that:
+/// - New, changed and unchanged devices are reported as created, updated and skipped
+/// - Invalid and duplicate entries are reported as failed
+/// - Nothing is stored
+#[tokio::test]
+async fn test_import_dry_run() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let existing_device = app.generate_test_device_id();
+    let unchanged_device = app.generate_test_device_id();
+    let new_device = app.generate_test_device_id();
+
+    for device_id in [&existing_device, &unchanged_device] {
+        let response = client
+            .post("/device-config/update")
+            .json(&serde_json::json!({ "device_id": device_id, "config": { "LED": "off" } }))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client
+        .post("/device-config/import?dry_run=true")
+        .body(bundle(serde_json::json!([
+            { "device_id": existing_device, "config": { "LED": "on" } },
+            { "device_id": unchanged_device, "config": { "LED": "off" } },
+            { "device_id": new_device, "config": { "LED": "on" } },
+            { "device_id": new_device, "config": { "LED": "off" } },
+            { "device_id": "", "config": { "LED": "on" } }
+        ])))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let report: ImportReport = response.into_json().await.expect("Invalid import report");
+    assert!(report.dry_run);
+    assert_eq!((report.created, report.updated, report.skipped, report.failed), (1, 1, 1, 2));
+    assert_eq!(report.items[0].outcome, ImportOutcome::Updated);
+
+    let response = client
+        .get(format!("/device-config/get/{}", new_device))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test a YAML import that skips conflicting devices
+/// 
+/// This test verifies that new devices are stored while devices with a
+/// different configuration are left alone.
+#[tokio::test]
+async fn test_import_yaml_skip_conflicts() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let existing_device = app.generate_test_device_id();
+    let new_device = app.generate_test_device_id();
+
+    let response = client
+        .post("/device-config/update")
+        .json(&serde_json::json!({ "device_id": existing_device, "config": { "LED": "off" } }))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let document = format!(
+        "format_version: 1\nexported_at: 2025-01-01T00:00:00Z\nconfigs:\n\
+         - device_id: {}\n  config:\n    LED: 'on'\n\
+         - device_id: {}\n  config:\n    LED: 'on'\n",
+        existing_device, new_device
+    );
+    let response = client
+        .post("/device-config/import?format=yaml&on_conflict=skip")
+        .body(document)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let report: ImportReport = response.into_json().await.expect("Invalid import report");
+    assert_eq!((report.created, report.skipped), (1, 1));
+
+    let response = client
+        .get(format!("/device-config/get/{}", existing_device))
+        .dispatch()
+        .await;
+    let configs: Vec<device_config::domain::Config> = response.into_json().await.expect("Invalid config response");
+    assert_eq!(configs[0].config["LED"], "off");
+
+    let response = client
+        .get(format!("/device-config/get/{}", new_device))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+/// Test importing a bundle in an unknown format version
+/// 
+/// This test verifies that the API returns 400 Bad Request and stores nothing.
+#[tokio::test]
+async fn test_import_unsupported_version() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .post("/device-config/import")
+        .body(r#"{"format_version": 99, "exported_at": "2025-01-01T00:00:00Z", "configs": []}"#)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test that exported fleet defaults are restored by an import
+/// 
+/// This test verifies that the `_default` entry of an export is imported
+/// through the defaults write path instead of being rejected, so an
+/// export/import round trip restores the fleet defaults.
+#[tokio::test]
+async fn test_export_import_round_trips_defaults() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let marker = app.generate_test_device_id();
+
+    let response = client
+        .put("/device-config/defaults")
+        .json(&serde_json::json!({ "config": { "LED": "off", "sampling_rate": marker } }))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/device-config/export").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let exported: ConfigBundle = response.into_json().await.expect("Invalid export");
+    let defaults = exported.configs
+        .into_iter()
+        .find(|config| config.device_id == DEFAULT_CONFIG_ID)
+        .expect("Defaults missing from export");
+
+    // Change the defaults, then restore the exported ones
+    let response = client
+        .put("/device-config/defaults")
+        .json(&serde_json::json!({ "config": { "LED": "on" } }))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post("/device-config/import")
+        .body(bundle(serde_json::json!([defaults])))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: ImportReport = response.into_json().await.expect("Invalid import report");
+    assert_eq!((report.updated, report.failed), (1, 0));
+    assert_eq!(report.items[0].device_id, DEFAULT_CONFIG_ID);
+
+    let response = client.get("/device-config/defaults").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let restored: Config = response.into_json().await.expect("Invalid defaults response");
+    assert_eq!(restored.config["sampling_rate"], marker);
+    assert_eq!(restored.config["LED"], "off");
+}
@@ -0,0 +1,96 @@
+// Configuration Deletion API Integration Tests
+// 
+// This module contains integration tests for the
+// DELETE /device-config/devices/<device_id> and
+// POST /device-config/devices/<device_id>/restore endpoints.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::config::ConfigDeletion;
+use device_config::domain::device::DevicePage;
+
+/// Test that a deleted configuration is hidden and can be restored
+/// 
+/// This test verifies that:
+/// - Deleting reports every stored version and hides the device from
+///   GET requests and the device listing
+/// - Deleting again returns 404, since nothing is left to delete
+/// - Restoring serves the latest version again
+#[tokio::test]
+async fn test_delete_and_restore_config() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_delete_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for led in ["off", "on"] {
+        let response = client
+            .post("/device-config/update")
+            .header(ContentType::JSON)
+            .body(serde_json::json!({ "device_id": device_id, "config": { "LED": led, "listing_marker": device_id } }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client.delete(format!("/device-config/devices/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let deletion: ConfigDeletion = response.into_json().await.expect("Invalid deletion");
+    assert_eq!(deletion.versions, 2);
+    assert!(deletion.deleted_at.is_some());
+
+    // The device is served the fleet-wide defaults, if any, instead
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    if response.status() == Status::Ok {
+        let body: serde_json::Value = response.into_json().await.expect("Invalid configuration");
+        assert_eq!(body[0]["is_default"], true);
+    } else {
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    let listing_url = format!("/device-config/devices?key=listing_marker&value={}", device_id);
+    let page: DevicePage = client.get(&listing_url).dispatch().await.into_json().await.expect("Invalid device page");
+    assert_eq!(page.total, 0);
+
+    let response = client.delete(format!("/device-config/devices/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client.post(format!("/device-config/devices/{}/restore", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let restored: ConfigDeletion = response.into_json().await.expect("Invalid restore");
+    assert_eq!(restored.versions, 2);
+    assert!(restored.deleted_at.is_none());
+
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid configuration");
+    assert_eq!(body[0]["config"]["LED"], "on");
+    assert_eq!(body[0]["version_number"], 2);
+
+    let page: DevicePage = client.get(&listing_url).dispatch().await.into_json().await.expect("Invalid device page");
+    assert_eq!(page.total, 1);
+}
+
+/// Test that restoring a configuration that was never deleted returns 404
+#[tokio::test]
+async fn test_restore_without_deletion_returns_404() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(&device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.post(format!("/device-config/devices/{}/restore", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+}
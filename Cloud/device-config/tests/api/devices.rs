@@ -0,0 +1,124 @@
+// Device Listing API Integration Tests
+// 
+// This module contains integration tests for the GET /device-config/devices
+// endpoint of the device configuration service.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::device::DevicePage;
+
+/// Test that an updated device appears in the filtered listing
+/// 
+/// This test stores a configuration with a unique value and verifies the
+/// device is listed when filtering on that key and value.
+#[tokio::test]
+async fn test_list_devices_with_filter() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = serde_json::json!({
+        "device_id": device_id,
+        "config": { "listing_marker": device_id }
+    });
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/devices?key=listing_marker&value={}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let page: DevicePage = response.into_json().await.expect("Invalid device page");
+    assert_eq!(page.total, 1);
+    assert_eq!(page.devices[0].device_id, device_id);
+    assert!(!page.devices[0].version.is_empty());
+}
+
+/// Test paging parameters of the device listing
+/// 
+/// This test verifies that the page size is honoured and echoed back.
+#[tokio::test]
+async fn test_list_devices_paging() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .get("/device-config/devices?page=1&page_size=1")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let page: DevicePage = response.into_json().await.expect("Invalid device page");
+    assert_eq!(page.page, 1);
+    assert_eq!(page.page_size, 1);
+    assert!(page.devices.len() <= 1);
+}
+
+/// Test that the device listing reports configuration fetches
+/// 
+/// This test verifies that a device is flagged until it fetches its latest
+/// configuration version, and that the time and client IP of the fetch are
+/// reported.
+#[tokio::test]
+async fn test_list_devices_reports_fetches() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    // Fetch records outlive test runs, so the device must be new
+    let device_id = format!("test_fetch_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let listing_url = format!("/device-config/devices?key=listing_marker&value={}", device_id);
+
+    let store = |sampling_rate: &str| serde_json::json!({
+        "device_id": device_id,
+        "config": { "listing_marker": device_id, "sampling_rate": sampling_rate }
+    });
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(store("1000").to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let page: DevicePage = client.get(listing_url.clone()).dispatch().await.into_json().await.expect("Invalid device page");
+    assert!(page.devices[0].last_fetched_at.is_none());
+    assert!(!page.devices[0].fetched_latest);
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .remote("203.0.113.7:4000".parse().unwrap())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let page: DevicePage = client.get(listing_url.clone()).dispatch().await.into_json().await.expect("Invalid device page");
+    assert!(page.devices[0].last_fetched_at.is_some());
+    assert_eq!(page.devices[0].last_fetch_ip.as_deref(), Some("203.0.113.7"));
+    assert!(page.devices[0].fetched_latest);
+
+    // A new version is flagged until the device fetches it
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(store("2000").to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let page: DevicePage = client.get(listing_url).dispatch().await.into_json().await.expect("Invalid device page");
+    assert!(!page.devices[0].fetched_latest);
+}
@@ -0,0 +1,83 @@
+// Effective Configuration API Integration Tests
+// 
+// This module contains integration tests for the
+// GET /device-config/effective/<device_id> endpoint.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::effective::{EffectiveConfig, ValueSource};
+use device_config::domain::secrets::REDACTED_VALUE;
+
+/// Generates a device ID without configuration left over from earlier test runs
+fn unique_device_id() -> String {
+    format!("test_effective_{}", &uuid::Uuid::new_v4().simple().to_string()[..12])
+}
+
+/// Test the provenance of a device's effective configuration
+/// 
+/// This test verifies that:
+/// - Values set by an active override are attributed to it
+/// - Other values are attributed to the device, secret values redacted
+/// - Versions scheduled for later are listed with the keys they change
+#[tokio::test]
+async fn test_effective_config_provenance() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = unique_device_id();
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(&device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post("/device-config/overrides")
+        .header(ContentType::JSON)
+        .body(serde_json::json!({
+            "device_id": device_id,
+            "config": { "sampling_rate": "100" },
+            "ttl_secs": 3600
+        }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let apply_at = chrono::Utc::now() + chrono::Duration::days(1);
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(serde_json::json!({
+            "device_id": device_id,
+            "config": { "sampling_rate": "2000", "threshold": "25.5" },
+            "apply_at": apply_at.to_rfc3339()
+        }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/effective/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let effective: EffectiveConfig = response.into_json().await.expect("Invalid effective config");
+
+    assert!(!effective.is_default);
+    assert!(effective.version.is_some());
+    assert_eq!(effective.config["sampling_rate"].value, "100");
+    assert_eq!(effective.config["sampling_rate"].source, ValueSource::Override);
+    assert!(effective.config["sampling_rate"].expires_at.is_some());
+    assert_eq!(effective.config["threshold"].source, ValueSource::Device);
+    assert_eq!(effective.config["wifi_password"].value, REDACTED_VALUE);
+
+    assert_eq!(effective.scheduled.len(), 1);
+    let changed: Vec<&str> = effective.scheduled[0].changes.iter().map(|change| change.key.as_str()).collect();
+    assert_eq!(changed, vec!["sampling_rate", "wifi_password", "wifi_ssid"]);
+}
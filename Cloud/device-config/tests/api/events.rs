@@ -0,0 +1,154 @@
+// Configuration Change Event Integration Tests
+// 
+// This module contains integration tests for the configuration change
+// events delivered to registered webhooks and Event Grid topics.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::auth::AuthConfig;
+use device_config::domain::event::{ConfigChangeEvent, EventGridEvent};
+use device_config::services::EventPublisher;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Accepts a single HTTP request and returns its headers and body
+async fn receive_webhook(listener: TcpListener) -> (String, String) {
+    let (mut socket, _) = listener.accept().await.expect("No webhook delivery");
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+
+    // Read until the full body (per Content-Length) has arrived
+    loop {
+        let n = socket.read(&mut buf).await.expect("Failed to read webhook");
+        request.extend_from_slice(&buf[..n]);
+
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+            let length = headers
+                .lines()
+                .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0);
+            if body.len() >= length || n == 0 {
+                socket.write_all(b"HTTP/1.1 204 No Content\r\n\r\n").await.ok();
+                return (headers.to_string(), body.to_string());
+            }
+        }
+    }
+}
+
+/// Test that a configuration update is published to a registered webhook
+/// 
+/// This test registers a local webhook, updates a configuration, and
+/// verifies the delivered event describes the change.
+#[tokio::test]
+async fn test_update_config_publishes_event() {
+    dotenv().ok();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind webhook listener");
+    let webhook = format!("http://{}/events", listener.local_addr().unwrap());
+
+    let app = TestApp::with_state(|state| {
+        state
+            .with_auth_config(AuthConfig::disabled())
+            .with_event_publisher(EventPublisher::new(vec![webhook]))
+    })
+    .await
+    .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let (_, body) = tokio::time::timeout(std::time::Duration::from_secs(10), receive_webhook(listener))
+        .await
+        .expect("Timed out waiting for webhook");
+    let event: ConfigChangeEvent = serde_json::from_str(&body).expect("Invalid event payload");
+
+    assert_eq!(event.event_type, "device_config.changed");
+    assert_eq!(event.device_id, device_id);
+    assert!(!event.version.is_empty());
+    assert!(event.changed_keys.contains(&"sampling_rate".to_string()));
+    assert_eq!(event.actor, "anonymous");
+}
+
+/// Test that a configuration update is published to an Event Grid topic
+/// 
+/// This test registers a local endpoint as the Event Grid topic and
+/// verifies the event arrives in the Event Grid schema with the topic key.
+#[tokio::test]
+async fn test_update_config_publishes_event_grid_event() {
+    dotenv().ok();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("Failed to bind topic listener");
+    let endpoint = format!("http://{}/api/events", listener.local_addr().unwrap());
+
+    let app = TestApp::with_state(|state| {
+        state
+            .with_auth_config(AuthConfig::disabled())
+            .with_event_publisher(EventPublisher::new(Vec::new()).with_event_grid(endpoint, "topic-key".to_string()))
+    })
+    .await
+    .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let (headers, body) = tokio::time::timeout(std::time::Duration::from_secs(10), receive_webhook(listener))
+        .await
+        .expect("Timed out waiting for Event Grid delivery");
+    let events: Vec<EventGridEvent> = serde_json::from_str(&body).expect("Invalid Event Grid payload");
+
+    assert!(headers.to_ascii_lowercase().contains("aeg-sas-key: topic-key"));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type, "device_config.changed");
+    assert_eq!(events[0].subject, format!("devices/{}/config", device_id));
+    assert_eq!(events[0].data.device_id, device_id);
+    assert!(!events[0].data.version.is_empty());
+}
+
+/// Test that an unreachable webhook does not fail the update
+/// 
+/// This test verifies that event delivery happens in the background and
+/// never affects the response of the configuration update.
+#[tokio::test]
+async fn test_update_config_with_unreachable_webhook() {
+    dotenv().ok();
+
+    let app = TestApp::with_state(|state| {
+        state
+            .with_auth_config(AuthConfig::disabled())
+            .with_event_publisher(EventPublisher::new(vec!["http://127.0.0.1:9/events".to_string()]))
+    })
+    .await
+    .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+}
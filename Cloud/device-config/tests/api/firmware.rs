@@ -0,0 +1,143 @@
+// Firmware Update API Integration Tests
+// 
+// This module contains integration tests for the /device-config/firmware
+// endpoints and the firmware manifest served with device configurations.
+
+use crate::helper::TestApp;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::config::Config;
+use device_config::domain::firmware::{FirmwareAssignment, FirmwareImage};
+
+/// Generates a firmware version that no other test run has registered
+fn unique_version() -> String {
+    format!("0.0.1-test.{}", &uuid::Uuid::new_v4().simple().to_string()[..12])
+}
+
+/// Builds a valid image registration request for a version
+fn image_request(version: &str) -> serde_json::Value {
+    serde_json::json!({
+        "version": version,
+        "url": format!("https://firmware.example.com/rp-rot/{}.bin", version),
+        "sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+        "signature": STANDARD.encode([7u8; 64])
+    })
+}
+
+/// Test registering and retrieving a firmware image
+/// 
+/// This test verifies that:
+/// - A valid image is registered and can be read back
+/// - Registering the same version again returns 409 Conflict
+/// - An image with a malformed digest returns 400 Bad Request
+#[tokio::test]
+async fn test_register_firmware_image() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let version = unique_version();
+
+    let response = client
+        .post("/device-config/firmware")
+        .header(ContentType::JSON)
+        .body(image_request(&version).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get(format!("/device-config/firmware/{}", version)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let image: FirmwareImage = response.into_json().await.expect("Invalid image");
+    assert_eq!(image.version, version);
+
+    let response = client
+        .post("/device-config/firmware")
+        .header(ContentType::JSON)
+        .body(image_request(&version).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    let mut invalid = image_request(&unique_version());
+    invalid["sha256"] = serde_json::json!("not-a-digest");
+    let response = client
+        .post("/device-config/firmware")
+        .header(ContentType::JSON)
+        .body(invalid.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test assigning firmware to a device
+/// 
+/// This test verifies that:
+/// - Assigning an unregistered version returns 404 Not Found
+/// - The assigned manifest is served with the device's configuration
+/// - The entity tag changes, so polling devices learn about the update
+#[tokio::test]
+async fn test_assigned_firmware_is_served_with_config() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("firmware-test-{}", uuid::Uuid::new_v4().simple());
+    let version = unique_version();
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(&device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    let etag = response.headers().get_one("ETag").expect("Missing ETag").to_string();
+    let configs: Vec<Config> = response.into_json().await.expect("Invalid config");
+    assert!(configs[0].firmware.is_none());
+
+    let assignment = serde_json::json!({ "version": version, "devices": [device_id] });
+    let response = client
+        .post("/device-config/firmware/assign")
+        .header(ContentType::JSON)
+        .body(assignment.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client
+        .post("/device-config/firmware")
+        .header(ContentType::JSON)
+        .body(image_request(&version).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post("/device-config/firmware/assign")
+        .header(ContentType::JSON)
+        .body(assignment.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_ne!(response.headers().get_one("ETag"), Some(etag.as_str()));
+    let configs: Vec<Config> = response.into_json().await.expect("Invalid config");
+    let firmware = configs[0].firmware.as_ref().expect("Missing firmware manifest");
+    assert_eq!(firmware.version, version);
+    assert!(firmware.url.ends_with(".bin"));
+
+    let response = client
+        .get(format!("/device-config/firmware/assignments/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let assignment: FirmwareAssignment = response.into_json().await.expect("Invalid assignment");
+    assert_eq!(assignment.version, version);
+}
@@ -0,0 +1,91 @@
+// Fuzz Corpus Regression Tests
+// 
+// This module replays the committed cargo-fuzz corpus (fuzz/corpus) through
+// the same deserializers and validation as the fuzz targets, so inputs that
+// once crashed them are checked on every test run. No database is needed.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use chrono::Utc;
+use device_config::domain::{config_etag, Config, ConfigLimits, DefaultConfigRequest, Rollout, RolloutError, RolloutRequest};
+
+/// Reads every input in a fuzz target's corpus directory
+/// 
+/// # Arguments
+/// * `target` - Name of the fuzz target
+/// 
+/// # Returns
+/// * `Vec<(PathBuf, Vec<u8>)>` - Each input file with its contents
+fn corpus(target: &str) -> Vec<(PathBuf, Vec<u8>)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus").join(target);
+    let mut inputs: Vec<(PathBuf, Vec<u8>)> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Failed to read corpus {}: {}", dir.display(), e))
+        .map(|entry| {
+            let path = entry.expect("Failed to read corpus entry").path();
+            let data = fs::read(&path).expect("Failed to read corpus input");
+            (path, data)
+        })
+        .collect();
+    inputs.sort();
+
+    assert!(!inputs.is_empty(), "Corpus {} is empty", dir.display());
+    inputs
+}
+
+/// Test that the configuration corpus is handled without panicking
+/// 
+/// This test verifies that:
+/// - Every input either fails to deserialize or passes through validation
+/// - The entity tag of an accepted configuration survives a storage round trip
+#[test]
+fn test_config_corpus() {
+    for (path, data) in corpus("config_json") {
+        if let Ok(config) = serde_json::from_slice::<Config>(&data) {
+            let _ = Config::parse(config.device_id.clone(), config.config.clone(), &ConfigLimits::default());
+            let _ = config.is_active_at(Utc::now());
+
+            let stored = serde_json::to_vec(&config).expect("Failed to serialize config");
+            let reordered: Config = serde_json::from_slice(&stored)
+                .unwrap_or_else(|e| panic!("{}: stored config does not deserialize: {}", path.display(), e));
+            assert_eq!(config_etag(&[config]), config_etag(&[reordered]), "{}", path.display());
+        }
+
+        if let Ok(request) = serde_json::from_slice::<DefaultConfigRequest>(&data) {
+            if let Ok(defaults) = Config::parse_defaults(request.config, &ConfigLimits::default()) {
+                let _ = config_etag(&[defaults.scheduled(request.apply_at).as_default_for("sensor-001")]);
+            }
+        }
+    }
+}
+
+/// Test that the rollout request corpus is handled without panicking
+/// 
+/// This test verifies that every accepted rollout only picks distinct canary
+/// devices from its targeted group, that groups listing a device twice are
+/// rejected, and that the duplicate-device inputs are never accepted.
+#[test]
+fn test_rollout_request_corpus() {
+    for (path, data) in corpus("rollout_request_json") {
+        let Ok(request) = serde_json::from_slice::<RolloutRequest>(&data) else {
+            continue;
+        };
+
+        let result = Rollout::parse("fuzz-rollout".to_string(), request, &ConfigLimits::default());
+        if path.file_name().is_some_and(|name| name.to_string_lossy().starts_with("duplicate_")) {
+            assert!(matches!(result, Err(RolloutError::DuplicateDevice(_))), "{}", path.display());
+        }
+
+        if let Ok(rollout) = result {
+            assert!(
+                rollout.canary_devices.iter().all(|d| rollout.devices.contains(d)),
+                "{}", path.display()
+            );
+            let distinct: HashSet<&String> = rollout.devices.iter().collect();
+            assert_eq!(distinct.len(), rollout.devices.len(), "{}", path.display());
+            let distinct: HashSet<&String> = rollout.canary_devices.iter().collect();
+            assert_eq!(distinct.len(), rollout.canary_devices.len(), "{}", path.display());
+            let _ = rollout.remaining_devices();
+        }
+    }
+}
@@ -4,9 +4,12 @@
 // endpoint of the device configuration service.
 
 use crate::helper::TestApp;
-use rocket::http::{Status, ContentType};
+use rocket::http::{Status, ContentType, Header};
 use rocket::local::asynchronous::Client;
 use dotenvy::dotenv;
+use device_config::services::FetchRateLimiter;
+use device_config::domain::auth::{ApiKey, AuthConfig};
+use device_config::utils::conditional::CachePolicy;
 
 /// Test getting configuration for a device that doesn't exist
 /// 
@@ -158,4 +161,322 @@ async fn test_get_config_unsupported_methods() {
 
     // Should return 404 for unsupported methods
     assert_eq!(response.status(), Status::NotFound);
-} 
\ No newline at end of file
+}
+
+/// Test that a configuration scheduled for the future is not served yet
+/// 
+/// This test verifies that a configuration update with an `apply_at` time
+/// in the future is stored but not returned by the GET endpoint until
+/// that time has passed.
+#[tokio::test]
+async fn test_get_config_scheduled_config_not_yet_active() {
+    dotenv().ok();
+    
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    // Schedule a configuration one day in the future
+    let mut config_data = app.create_test_config(&device_id);
+    config_data["apply_at"] = serde_json::json!(
+        (chrono::Utc::now() + chrono::Duration::days(1)).to_rfc3339()
+    );
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+
+    // The scheduled configuration should not be active yet
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test conditional requests with If-None-Match and the version parameter
+/// 
+/// This test verifies that the endpoint returns an ETag and answers 304
+/// Not Modified when the caller already holds the current configuration.
+#[tokio::test]
+async fn test_get_config_not_modified() {
+    dotenv().ok();
+    
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").expect("Missing ETag").to_string();
+
+    // Unchanged configuration via If-None-Match
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotModified);
+
+    // Unchanged configuration via the version query parameter
+    let response = client
+        .get(format!("/device-config/get/{}?version={}", device_id, etag.trim_matches('"')))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotModified);
+
+    // A stale tag returns the configuration
+    let response = client
+        .get(format!("/device-config/get/{}?version=stale", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+/// Test that a long-polling request returns as soon as the configuration changes
+/// 
+/// This test holds a request open with `wait` and updates the configuration
+/// while it is waiting, expecting the new configuration well before the
+/// wait time elapses.
+#[tokio::test]
+async fn test_get_config_long_poll_wakes_on_change() {
+    dotenv().ok();
+    
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let mut config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    let etag = response.headers().get_one("ETag").expect("Missing ETag").to_string();
+
+    config_data["config"]["sampling_rate"] = serde_json::json!("2000");
+    let started = std::time::Instant::now();
+
+    let poll = client
+        .get(format!("/device-config/get/{}?wait=30", device_id))
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch();
+    let update = async {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        client
+            .post("/device-config/update")
+            .header(ContentType::JSON)
+            .body(config_data.to_string())
+            .dispatch()
+            .await
+    };
+    let (response, update_response) = tokio::join!(poll, update);
+
+    assert_eq!(update_response.status(), Status::Ok);
+    assert_eq!(response.status(), Status::Ok);
+    assert_ne!(response.headers().get_one("ETag"), Some(etag.as_str()));
+    assert!(started.elapsed() < std::time::Duration::from_secs(30));
+}
+
+/// Test that devices polling too often are rate limited
+/// 
+/// This test verifies that:
+/// - Fetches beyond the per-device limit return 429 with a Retry-After header
+/// - Other devices keep their own allowance
+#[tokio::test]
+async fn test_get_config_rate_limited() {
+    dotenv().ok();
+
+    let limiter = FetchRateLimiter::new(2, std::time::Duration::from_secs(60));
+    let app = TestApp::with_state(|state| state.with_fetch_limiter(limiter))
+        .await
+        .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let other_device_id = app.generate_test_device_id();
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(&device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    for _ in 0..2 {
+        let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::TooManyRequests);
+    let retry_after: u64 = response.headers()
+        .get_one("Retry-After")
+        .expect("Missing Retry-After")
+        .parse()
+        .expect("Retry-After is not a number of seconds");
+    assert!((1..=30).contains(&retry_after));
+
+    let response = client.get(format!("/device-config/get/{}", other_device_id)).dispatch().await;
+    assert_ne!(response.status(), Status::TooManyRequests);
+}
+
+/// Test the caching headers of configuration responses
+/// 
+/// This test verifies that responses carry `Cache-Control`, `ETag` and
+/// `Last-Modified`, that configurations holding secrets are never cacheable
+/// by shared caches, and that the configured max age is applied otherwise.
+#[tokio::test]
+async fn test_get_config_cache_headers() {
+    dotenv().ok();
+
+    let app = TestApp::with_state(|state| state.with_cache_policy(CachePolicy { max_age: 30 }))
+        .await
+        .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let secret_device_id = app.generate_test_device_id();
+    let public_device_id = app.generate_test_device_id();
+
+    // The default test configuration includes wifi_password, a secret key
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(&secret_device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let public_config = serde_json::json!({
+        "device_id": public_device_id,
+        "config": { "LED": "on", "sampling_rate": "1000" }
+    });
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(public_config.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get(format!("/device-config/get/{}", secret_device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("private, no-cache"));
+
+    let response = client.get(format!("/device-config/get/{}", public_device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("public, max-age=30, must-revalidate"));
+    let last_modified = response.headers().get_one("Last-Modified").expect("Missing Last-Modified");
+    assert!(last_modified.ends_with(" GMT"), "Unexpected Last-Modified: {}", last_modified);
+    let etag = response.headers().get_one("ETag").expect("Missing ETag").to_string();
+
+    // Revalidation keeps the caching headers so caches can refresh their copy
+    let response = client
+        .get(format!("/device-config/get/{}", public_device_id))
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some(etag.as_str()));
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("public, max-age=30, must-revalidate"));
+    assert!(response.headers().get_one("Last-Modified").is_some());
+}
+
+/// Test the version metadata of configuration responses
+/// 
+/// This test verifies that served configurations carry their version
+/// number, when they took effect and who stored them, next to the raw
+/// `config` map, and that the number increases with every stored version.
+#[tokio::test]
+async fn test_get_config_version_metadata() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_version_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for led in ["off", "on"] {
+        let response = client
+            .post("/device-config/update")
+            .header(ContentType::JSON)
+            .body(serde_json::json!({ "device_id": device_id, "config": { "LED": led } }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid configuration");
+    let served = &body[0];
+
+    assert_eq!(served["config"], serde_json::json!({ "LED": "on" }));
+    assert_eq!(served["version_number"], 2);
+    assert_eq!(served["modified_by"], "anonymous");
+    assert!(served["last_modified"].as_str().is_some_and(|time| time.parse::<chrono::DateTime<chrono::Utc>>().is_ok()));
+}
+
+/// Test the caching headers of configuration responses when authorization is enabled
+/// 
+/// This test verifies that configurations read with credentials are marked
+/// `private`, so shared caches never serve them to other callers, while
+/// the configured max age still applies to the caller's own cache.
+#[tokio::test]
+async fn test_get_config_cache_headers_with_auth() {
+    dotenv().ok();
+
+    let auth = AuthConfig::new(
+        vec![ApiKey { name: "admin".to_string(), key: "cache-admin-key".to_string(), role: "admin".to_string() }],
+        None,
+    );
+    let app = TestApp::with_state(|state| state.with_auth_config(auth).with_cache_policy(CachePolicy { max_age: 30 }))
+        .await
+        .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let config = serde_json::json!({
+        "device_id": device_id,
+        "config": { "LED": "on", "sampling_rate": "1000" }
+    });
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "cache-admin-key"))
+        .body(config.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .header(Header::new("X-API-Key", "cache-admin-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Cache-Control"), Some("private, max-age=30, must-revalidate"));
+}
@@ -10,7 +10,7 @@ use rocket::{
     serde::json::Json,
 };
 use rocket_cors::{AllowedOrigins, CorsOptions};
-use device_config::{app_state::AppState, services::CosmosDbTelemetryStore};
+use device_config::{app_state::AppState, domain::auth::AuthConfig, services::CosmosDbTelemetryStore};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Global counter for generating unique test device IDs
@@ -45,6 +45,22 @@ fn bad_request() -> Json<ErrorResponse> {
     })
 }
 
+#[rocket::catch(401)]
+fn unauthorized() -> Json<ErrorResponse> {
+    Json(ErrorResponse {
+        error: "Unauthorized".to_string(),
+        message: "Missing or invalid credentials".to_string(),
+    })
+}
+
+#[rocket::catch(403)]
+fn forbidden() -> Json<ErrorResponse> {
+    Json(ErrorResponse {
+        error: "Forbidden".to_string(),
+        message: "The caller's role does not allow this operation".to_string(),
+    })
+}
+
 #[rocket::catch(500)]
 fn internal_server_error() -> Json<ErrorResponse> {
     Json(ErrorResponse {
@@ -96,7 +112,33 @@ impl TestApp {
     /// - Uses test container: "test-config"
     /// - Uses hardcoded secret key for testing
     /// - Binds to 0.0.0.0:8000
+    /// - Authorization disabled, regardless of the environment
     pub async fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_auth_config(AuthConfig::disabled()).await
+    }
+
+    /// Creates a new test application instance enforcing the given credentials
+    /// 
+    /// # Arguments
+    /// * `auth` - The API keys and JWT secret the test app accepts
+    /// 
+    /// # Returns
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The configured test app or an error
+    pub async fn with_auth_config(auth: AuthConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_state(|state| state.with_auth_config(auth)).await
+    }
+
+    /// Creates a new test application instance with customised application state
+    /// 
+    /// # Arguments
+    /// * `configure` - Adjusts the application state before the server is built
+    /// 
+    /// # Returns
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The configured test app or an error
+    pub async fn with_state<F>(configure: F) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        F: FnOnce(AppState) -> AppState,
+    {
         // Create test cosmos client with test database/container names
         // This ensures tests don't interfere with production data
         let cosmos_client = CosmosDbTelemetryStore::new(
@@ -105,7 +147,7 @@ impl TestApp {
         ).await?;
         
         // Create application state with the test database client
-        let app_state = AppState::new(cosmos_client);
+        let app_state = configure(AppState::new(cosmos_client));
 
         // Configure CORS for test requests (allows all origins for testing)
         let cors = CorsOptions {
@@ -126,12 +168,45 @@ impl TestApp {
             .register("/", rocket::catchers![
                 unprocessable_entity,
                 bad_request,
+                unauthorized,
+                forbidden,
                 internal_server_error,
                 not_found,
             ])
             .mount("/device-config", routes![
                 device_config::routes::get_config::get_config_route,
                 device_config::routes::update_config::update_config_route,
+                device_config::routes::rollouts::create_rollout_route,
+                device_config::routes::rollouts::get_rollout_route,
+                device_config::routes::rollouts::pause_rollout_route,
+                device_config::routes::rollouts::resume_rollout_route,
+                device_config::routes::rollouts::promote_rollout_route,
+                device_config::routes::rollouts::abort_rollout_route,
+                device_config::routes::audit::get_audit_route,
+                device_config::routes::signing_key::get_signing_key_route,
+                device_config::routes::devices::list_devices_route,
+                device_config::routes::defaults::get_defaults_route,
+                device_config::routes::defaults::put_defaults_route,
+                device_config::routes::validate::validate_config_route,
+                device_config::routes::bulk::export_configs_route,
+                device_config::routes::bulk::import_configs_route,
+                device_config::routes::firmware::register_firmware_route,
+                device_config::routes::firmware::list_firmware_route,
+                device_config::routes::firmware::get_firmware_route,
+                device_config::routes::firmware::assign_firmware_route,
+                device_config::routes::firmware::get_firmware_assignment_route,
+                device_config::routes::overrides::create_override_route,
+                device_config::routes::overrides::list_overrides_route,
+                device_config::routes::overrides::revert_override_route,
+                device_config::routes::approvals::list_approvals_route,
+                device_config::routes::approvals::get_approval_route,
+                device_config::routes::approvals::approve_change_route,
+                device_config::routes::approvals::reject_change_route,
+                device_config::routes::effective::get_effective_config_route,
+                device_config::routes::delete_config::delete_config_route,
+                device_config::routes::delete_config::restore_config_route,
+                device_config::routes::history::get_history_route,
+                device_config::routes::history::rollback_config_route,
             ]);
 
         // Create a tracked client for making requests to the test server
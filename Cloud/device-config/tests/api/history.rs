@@ -0,0 +1,83 @@
+// Configuration History API Integration Tests
+//
+// This module contains integration tests for the
+// GET /device-config/history/<device_id> and
+// POST /device-config/history/<device_id>/<version_number>/rollback endpoints.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::history::ConfigVersion;
+
+/// Test that stored versions are listed and can be rolled back to
+///
+/// This test verifies that:
+/// - The history lists every version newest first, with the keys each
+///   version changed and the served version flagged
+/// - Rolling back stores the old values as a new, active version
+/// - The rollback is recorded in the audit trail
+#[tokio::test]
+async fn test_history_and_rollback() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    for led in ["off", "on"] {
+        let response = client
+            .post("/device-config/update")
+            .header(ContentType::JSON)
+            .body(serde_json::json!({ "device_id": device_id, "config": { "LED": led } }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = client.get(format!("/device-config/history/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let history: Vec<ConfigVersion> = response.into_json().await.expect("Invalid history");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].version_number, Some(2));
+    assert!(history[0].active);
+    assert_eq!(history[0].changes[0].old_value.as_deref(), Some("off"));
+    assert_eq!(history[0].changes[0].new_value.as_deref(), Some("on"));
+    assert!(!history[1].active);
+
+    let response = client
+        .post(format!("/device-config/history/{}/1/rollback", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    let body: serde_json::Value = response.into_json().await.expect("Invalid configuration");
+    assert_eq!(body[0]["config"]["LED"], "off");
+    assert_eq!(body[0]["version_number"], 3);
+
+    let entries: serde_json::Value = client
+        .get(format!("/device-config/audit/{}", device_id))
+        .dispatch()
+        .await
+        .into_json()
+        .await
+        .expect("Invalid audit trail");
+    assert_eq!(entries[0]["action"], "config_rollback");
+}
+
+/// Test that rolling back to a version that does not exist returns 404
+#[tokio::test]
+async fn test_rollback_to_unknown_version_returns_404() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post(format!("/device-config/history/{}/7/rollback", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
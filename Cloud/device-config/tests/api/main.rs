@@ -5,4 +5,21 @@
 
 mod helper;
 mod get_config;
-mod update_config; 
\ No newline at end of file
+mod update_config;
+mod rollouts;
+mod audit;
+mod auth;
+mod signing;
+mod events;
+mod devices;
+mod secrets;
+mod fuzz_corpus;
+mod validate;
+mod bulk;
+mod firmware;
+mod overrides;
+mod approvals;
+mod effective;
+mod delete_config;
+mod history;
+mod reported;
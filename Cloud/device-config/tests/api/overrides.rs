@@ -0,0 +1,168 @@
+// Temporary Override API Integration Tests
+// 
+// This module contains integration tests for the /device-config/overrides
+// endpoints and the reverting of expired overrides.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::config::Config;
+use device_config::domain::overrides::{ConfigOverride, OverrideStatus};
+use device_config::routes::overrides::revert_expired_overrides;
+
+/// Generates a device ID without overrides left over from earlier test runs
+fn unique_device_id() -> String {
+    format!("test_override_{}", &uuid::Uuid::new_v4().simple().to_string()[..12])
+}
+
+/// Stores the standard test configuration for a device
+async fn store_config(app: &TestApp, device_id: &str) {
+    let response = app.client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+/// Reads the configuration currently served to a device
+async fn served_config(client: &Client, device_id: &str) -> Config {
+    let response = client.get(format!("/device-config/get/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let configs: Vec<Config> = response.into_json().await.expect("Invalid configuration");
+    configs.into_iter().next().expect("No configuration served")
+}
+
+/// Applies an override and returns the response
+async fn post_override<'c>(client: &'c Client, body: serde_json::Value) -> rocket::local::asynchronous::LocalResponse<'c> {
+    client
+        .post("/device-config/overrides")
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await
+}
+
+/// Test an override being applied and reverted on expiry
+/// 
+/// This test verifies that:
+/// - Override values are merged into the served configuration
+/// - A key held by an active override cannot be overridden again
+/// - Once expired, overridden keys get their previous value back and
+///   added keys are removed
+#[tokio::test]
+async fn test_override_reverts_on_expiry() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = unique_device_id();
+    store_config(&app, &device_id).await;
+
+    let response = post_override(client, serde_json::json!({
+        "device_id": device_id,
+        "config": { "sampling_rate": "100", "debug": "true" },
+        "ttl_secs": 1
+    })).await;
+    assert_eq!(response.status(), Status::Ok);
+    let item: ConfigOverride = response.into_json().await.expect("Invalid override");
+    assert_eq!(item.status, OverrideStatus::Active);
+    assert_eq!(item.previous.get("sampling_rate").map(String::as_str), Some("1000"));
+
+    let config = served_config(client, &device_id).await;
+    assert_eq!(config.config.get("sampling_rate").map(String::as_str), Some("100"));
+    assert_eq!(config.config.get("debug").map(String::as_str), Some("true"));
+    assert_eq!(config.config.get("wifi_ssid").map(String::as_str), Some("TestNetwork"));
+
+    let response = post_override(client, serde_json::json!({
+        "device_id": device_id,
+        "config": { "debug": "false" },
+        "ttl_secs": 60
+    })).await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    revert_expired_overrides(&app.app_state).await.expect("Failed to revert overrides");
+
+    let config = served_config(client, &device_id).await;
+    assert_eq!(config.config.get("sampling_rate").map(String::as_str), Some("1000"));
+    assert!(!config.config.contains_key("debug"));
+
+    let response = client.get(format!("/device-config/overrides/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let items: Vec<ConfigOverride> = response.into_json().await.expect("Invalid overrides");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].status, OverrideStatus::Expired);
+    assert!(items[0].reverted_at.is_some());
+}
+
+/// Test reverting an override early and invalid override requests
+/// 
+/// This test verifies that:
+/// - An operator can revert an override before it expires, once
+/// - Values changed since the override was applied are kept
+/// - Invalid TTLs, unknown devices and unknown overrides are rejected
+#[tokio::test]
+async fn test_revert_override_early() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = unique_device_id();
+    store_config(&app, &device_id).await;
+
+    let response = post_override(client, serde_json::json!({
+        "device_id": device_id,
+        "config": { "sampling_rate": "100", "threshold": "99" },
+        "ttl_secs": 3600
+    })).await;
+    assert_eq!(response.status(), Status::Ok);
+    let item: ConfigOverride = response.into_json().await.expect("Invalid override");
+
+    // Someone changes the threshold while the override is active
+    let mut update = app.create_test_config(&device_id);
+    update["config"]["sampling_rate"] = serde_json::json!("100");
+    update["config"]["threshold"] = serde_json::json!("30");
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(update.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let revert_url = format!("/device-config/overrides/{}/{}/revert", device_id, item.id);
+    let response = client.post(revert_url.clone()).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let reverted: ConfigOverride = response.into_json().await.expect("Invalid override");
+    assert_eq!(reverted.status, OverrideStatus::Reverted);
+
+    let config = served_config(client, &device_id).await;
+    assert_eq!(config.config.get("sampling_rate").map(String::as_str), Some("1000"));
+    assert_eq!(config.config.get("threshold").map(String::as_str), Some("30"));
+
+    let response = client.post(revert_url).dispatch().await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    let response = client
+        .post(format!("/device-config/overrides/{}/unknown-override/revert", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = post_override(client, serde_json::json!({
+        "device_id": device_id,
+        "config": { "LED": "on" },
+        "ttl_secs": 0
+    })).await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = post_override(client, serde_json::json!({
+        "device_id": unique_device_id(),
+        "config": { "LED": "on" },
+        "ttl_secs": 60
+    })).await;
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -0,0 +1,79 @@
+// Reported Configuration API Integration Tests
+// 
+// This module contains integration tests for the
+// /device-config/reported/<device_id> endpoints.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType, Header};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::auth::{ApiKey, AuthConfig, DeviceKey};
+use device_config::domain::device::ReportedConfig;
+
+/// Test that a device reports its applied configuration and readers see it
+/// 
+/// This test verifies that:
+/// - A device without a report yet returns 404
+/// - The device's own key may report its applied configuration, but not
+///   another device's
+/// - Readers get the last report back, with its version number
+#[tokio::test]
+async fn test_device_reports_applied_config() {
+    dotenv().ok();
+
+    let device_id = format!("test_reported_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let auth = AuthConfig::new(
+        vec![ApiKey { name: "reader".to_string(), key: "reader-key".to_string(), role: "read_only".to_string() }],
+        None,
+    )
+    .with_device_keys(vec![DeviceKey { device_id: device_id.clone(), key: "device-key".to_string() }]);
+    let app = TestApp::with_auth_config(auth).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .get(format!("/device-config/reported/{}", device_id))
+        .header(Header::new("X-API-Key", "reader-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let report = serde_json::json!({ "config": { "LED": "on" }, "version_number": 3 });
+    let response = client
+        .post(format!("/device-config/reported/{}", device_id))
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "device-key"))
+        .body(report.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The key does not cover other devices, and readers may not report
+    let response = client
+        .post(format!("/device-config/reported/{}", app.generate_test_device_id()))
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "device-key"))
+        .body(report.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .post(format!("/device-config/reported/{}", device_id))
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "reader-key"))
+        .body(report.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .get(format!("/device-config/reported/{}", device_id))
+        .header(Header::new("X-API-Key", "reader-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let reported: ReportedConfig = response.into_json().await.expect("Invalid reported config");
+    assert_eq!(reported.device_id, device_id);
+    assert_eq!(reported.config.get("LED").map(String::as_str), Some("on"));
+    assert_eq!(reported.version_number, Some(3));
+}
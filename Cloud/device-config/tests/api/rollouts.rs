@@ -0,0 +1,173 @@
+// Staged Rollout API Integration Tests
+// 
+// This module contains integration tests for the /device-config/rollouts
+// endpoints of the device configuration service.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+
+/// Test a rollout from creation through promotion
+/// 
+/// This test verifies that the canary device receives the new configuration
+/// immediately, the rest of the group only after promotion, and that a
+/// promoted rollout can no longer be aborted.
+#[tokio::test]
+async fn test_rollout_canary_then_promote() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let canary_device = app.generate_test_device_id();
+    let other_device = app.generate_test_device_id();
+
+    let request = serde_json::json!({
+        "name": "test-rollout",
+        "devices": [canary_device, other_device],
+        "canary": { "devices": [canary_device] },
+        "config": { "sampling_rate": "2000" }
+    });
+
+    let response = client
+        .post("/device-config/rollouts")
+        .header(ContentType::JSON)
+        .body(request.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let rollout: serde_json::Value = response.into_json().await.unwrap();
+    let rollout_id = rollout["id"].as_str().unwrap().to_string();
+    assert_eq!(rollout["status"], "canary");
+
+    // Only the canary device has the new configuration so far
+    let response = client.get(format!("/device-config/get/{}", canary_device)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = client.get(format!("/device-config/get/{}", other_device)).dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client
+        .post(format!("/device-config/rollouts/{}/promote", rollout_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // After promotion the remaining device is configured too
+    let response = client.get(format!("/device-config/get/{}", other_device)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // Promoted rollouts cannot be aborted
+    let response = client
+        .post(format!("/device-config/rollouts/{}/abort", rollout_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+}
+
+/// Test creating a rollout with a canary device outside the group
+/// 
+/// This test verifies that the API rejects canary selections that name
+/// devices which are not part of the rollout group.
+#[tokio::test]
+async fn test_rollout_unknown_canary_device() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let request = serde_json::json!({
+        "name": "test-rollout",
+        "devices": [device_id],
+        "canary": { "devices": ["not-in-group"] },
+        "config": { "sampling_rate": "2000" }
+    });
+
+    let response = client
+        .post("/device-config/rollouts")
+        .header(ContentType::JSON)
+        .body(request.to_string())
+        .dispatch()
+        .await;
+
+    // Should return 400 Bad Request for an invalid canary selection
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test retrieving a rollout that does not exist
+#[tokio::test]
+async fn test_get_rollout_not_found() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client.get("/device-config/rollouts/does-not-exist").dispatch().await;
+
+    // Should return 404 Not Found for an unknown rollout
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test aborting a rollout
+/// 
+/// This test verifies that aborting withdraws the rollout's version from a
+/// canary device that had no configuration before, and leaves a canary
+/// device alone that was reconfigured after the rollout reached it.
+#[tokio::test]
+async fn test_rollout_abort_reverts_canaries() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let new_device = app.generate_test_device_id();
+    let reconfigured_device = app.generate_test_device_id();
+
+    let request = serde_json::json!({
+        "name": "test-rollout-abort",
+        "devices": [new_device, reconfigured_device],
+        "canary": { "percentage": 100 },
+        "config": { "sampling_rate": "2000" }
+    });
+    let response = client
+        .post("/device-config/rollouts")
+        .header(ContentType::JSON)
+        .body(request.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let rollout: serde_json::Value = response.into_json().await.unwrap();
+    let rollout_id = rollout["id"].as_str().unwrap().to_string();
+    assert!(rollout["previous_configs"][&new_device].is_null());
+
+    // Reconfigure one canary after the rollout reached it
+    let update = serde_json::json!({ "device_id": reconfigured_device, "config": { "sampling_rate": "500" } });
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(update.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post(format!("/device-config/rollouts/{}/abort", rollout_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The device without a configuration of its own is no longer served the rollout's
+    let response = client.get(format!("/device-config/get/{}", new_device)).dispatch().await;
+    if response.status() == Status::Ok {
+        let body: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(body[0]["is_default"], true);
+    } else {
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    // The reconfigured device keeps its newer configuration
+    let response = client.get(format!("/device-config/get/{}", reconfigured_device)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.unwrap();
+    assert_eq!(body[0]["config"]["sampling_rate"], "500");
+}
@@ -0,0 +1,75 @@
+// Secret Configuration Value Integration Tests
+// 
+// This module contains integration tests for the encryption of secret
+// configuration values at rest.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::auth::AuthConfig;
+use device_config::domain::config::Config;
+use device_config::domain::secrets::{is_encrypted, ConfigCipher, REDACTED_VALUE};
+
+/// Test that secret values are encrypted in storage but served in plaintext
+/// 
+/// This test stores a configuration containing a Wi-Fi password, checks
+/// the stored document and audit entry do not contain it, and verifies
+/// the configuration endpoint returns the original value.
+#[tokio::test]
+async fn test_secret_values_encrypted_at_rest() {
+    dotenv().ok();
+
+    let app = TestApp::with_state(|state| {
+        state
+            .with_auth_config(AuthConfig::disabled())
+            .with_cipher(ConfigCipher::new([9u8; 32], Some("test-kek".to_string())))
+    })
+    .await
+    .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The stored document holds the password encrypted
+    let stored = app.app_state.cosmos_client
+        .read_config(&device_id)
+        .await
+        .expect("Failed to read stored config");
+    let password = &stored.first().expect("No stored configuration").config["wifi_password"];
+    assert!(is_encrypted(password));
+    assert!(!password.contains("testpass123"));
+
+    // The served configuration holds the plaintext
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let configs: Vec<Config> = response.into_json().await.expect("Invalid config response");
+    assert_eq!(configs[0].config["wifi_password"], "testpass123");
+    assert_eq!(configs[0].config["wifi_ssid"], "TestNetwork");
+
+    // The audit entry records that the password changed, but not its value
+    let response = client
+        .get(format!("/device-config/audit/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let entries: serde_json::Value = response.into_json().await.unwrap();
+    let change = entries[0]["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|change| change["key"] == "wifi_password")
+        .expect("Password change not audited");
+    assert_eq!(change["new_value"], REDACTED_VALUE);
+}
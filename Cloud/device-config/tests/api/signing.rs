@@ -0,0 +1,89 @@
+// Configuration Signing API Integration Tests
+// 
+// This module contains integration tests for signed configuration payloads
+// and the GET /device-config/signing-key endpoint.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use device_config::domain::auth::AuthConfig;
+use device_config::domain::signing::{canonical_json, ConfigSigner, SigningKeyInfo};
+use device_config::domain::config::Config;
+
+/// Creates a test app that signs configurations with a fixed key
+async fn signing_app() -> TestApp {
+    TestApp::with_state(|state| {
+        state
+            .with_auth_config(AuthConfig::disabled())
+            .with_signer(Some(ConfigSigner::new([42u8; 32], Some("test-key".to_string()))))
+    })
+    .await
+    .expect("Failed to create test app")
+}
+
+/// Test that served configurations carry a verifiable signature
+/// 
+/// This test stores a configuration, fetches it back, and verifies the
+/// signature against the published public key and the canonical form.
+#[tokio::test]
+async fn test_get_config_is_signed() {
+    dotenv().ok();
+
+    let app = signing_app().await;
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+    let config_data = app.create_test_config(&device_id);
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/device-config/signing-key").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let key_info: SigningKeyInfo = response.into_json().await.expect("Invalid key response");
+    assert_eq!(key_info.key_id, "test-key");
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let configs: Vec<Config> = response.into_json().await.expect("Invalid config response");
+    let config = configs.first().expect("No configuration returned");
+    let signature = config.signature.as_ref().expect("Configuration is not signed");
+    assert_eq!(signature.algorithm, "ed25519");
+    assert_eq!(signature.key_id, "test-key");
+
+    // Verify the way a device would: rebuild the canonical form and check it
+    let public_key: [u8; 32] = STANDARD.decode(&key_info.public_key).unwrap().try_into().unwrap();
+    let signature_bytes: [u8; 64] = STANDARD.decode(&signature.signature).unwrap().try_into().unwrap();
+    let verifying_key = VerifyingKey::from_bytes(&public_key).unwrap();
+    let payload = canonical_json(&config.device_id, &config.config);
+
+    assert!(verifying_key.verify(payload.as_bytes(), &Signature::from_bytes(&signature_bytes)).is_ok());
+}
+
+/// Test the signing key endpoint when no key is configured
+/// 
+/// This test verifies that the API returns 404 Not Found when
+/// configuration signing is disabled.
+#[tokio::test]
+async fn test_signing_key_not_configured() {
+    dotenv().ok();
+
+    let app = TestApp::with_state(|state| state.with_auth_config(AuthConfig::disabled()).with_signer(None))
+        .await
+        .expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client.get("/device-config/signing-key").dispatch().await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}
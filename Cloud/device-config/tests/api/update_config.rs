@@ -7,6 +7,7 @@ use crate::helper::TestApp;
 use rocket::http::{Status, ContentType};
 use rocket::local::asynchronous::Client;
 use dotenvy::dotenv;
+use device_config::domain::limits::ConfigLimits;
 
 /// Test updating configuration with valid data
 /// 
@@ -288,4 +289,135 @@ async fn test_update_config_complex_data() {
     // Verify the response body contains the success message
     let body = response.into_string().await.unwrap();
     assert_eq!(body, "Config ingested");
-} 
\ No newline at end of file
+} 
+
+/// Test updating the reserved fleet default device ID
+/// 
+/// This test verifies that the defaults cannot be changed through the
+/// per-device update endpoint.
+#[tokio::test]
+async fn test_update_config_rejects_default_device_id() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let config_data = app.create_test_config("_default");
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(config_data.to_string())
+        .dispatch()
+        .await;
+
+    // Should return 400 Bad Request for the reserved device ID
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test updating configuration with keys or values devices cannot hold
+/// 
+/// This test verifies that the API rejects oversized values, keys with
+/// characters outside the allowed set and reserved keys under the default limits.
+#[tokio::test]
+async fn test_update_config_rejects_values_outside_limits() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let invalid_configs = [
+        serde_json::json!({ "LED": "x".repeat(ConfigLimits::default().max_value_length + 1) }),
+        serde_json::json!({ "wifi ssid": "MyNetwork" }),
+        serde_json::json!({ "device_id": "other-device" }),
+    ];
+
+    for config in invalid_configs {
+        let response = client
+            .post("/device-config/update")
+            .header(ContentType::JSON)
+            .body(serde_json::json!({ "device_id": device_id, "config": config }).to_string())
+            .dispatch()
+            .await;
+
+        // Should return 400 Bad Request before anything is stored
+        assert_eq!(response.status(), Status::BadRequest, "{}", config);
+    }
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test updating configuration with values devices cannot interpret
+/// 
+/// This test verifies that values rejected by the built-in validators
+/// return 400 before anything is stored.
+#[tokio::test]
+async fn test_update_config_rejects_invalid_values() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let invalid_configs = [
+        serde_json::json!({ "LED": "blinking" }),
+        serde_json::json!({ "LED": "on", "interval": "30s" }),
+        serde_json::json!({ "interval": "0" }),
+    ];
+
+    for config in invalid_configs {
+        let response = client
+            .post("/device-config/update")
+            .header(ContentType::JSON)
+            .body(serde_json::json!({ "device_id": device_id, "config": config }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest, "{}", config);
+    }
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test updating configuration with limits set for constrained devices
+/// 
+/// This test verifies that limits replaced on the application state are
+/// enforced instead of the defaults.
+#[tokio::test]
+async fn test_update_config_enforces_configured_limits() {
+    dotenv().ok();
+
+    let limits = ConfigLimits {
+        max_keys: 1,
+        reserved_keys: vec!["firmware_url".to_string()],
+        ..ConfigLimits::default()
+    };
+    let app = TestApp::with_state(|state| state.with_limits(limits))
+        .await
+        .expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let update = |config: serde_json::Value| {
+        client
+            .post("/device-config/update")
+            .header(ContentType::JSON)
+            .body(serde_json::json!({ "device_id": device_id, "config": config }).to_string())
+            .dispatch()
+    };
+
+    // Too many keys, and a key reserved by the configured list
+    assert_eq!(update(serde_json::json!({ "LED": "on", "mode": "eco" })).await.status(), Status::BadRequest);
+    assert_eq!(update(serde_json::json!({ "firmware_url": "https://example.com" })).await.status(), Status::BadRequest);
+
+    // A configuration within the limits is still accepted
+    assert_eq!(update(serde_json::json!({ "LED": "on" })).await.status(), Status::Ok);
+}
@@ -0,0 +1,90 @@
+// Configuration Validation API Integration Tests
+// 
+// This module contains integration tests for the POST /device-config/validate
+// dry-run endpoint of the device configuration service.
+
+use crate::helper::TestApp;
+use rocket::http::{Status, ContentType};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_config::domain::{Config, ValidationReport};
+
+/// Test validating a change to a stored configuration
+/// 
+/// This test verifies that:
+/// - The report resolves the new configuration with secret values redacted
+/// - Changes and removed keys are reported against the stored configuration
+/// - Nothing is stored
+#[tokio::test]
+async fn test_validate_reports_changes_without_storing() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post("/device-config/update")
+        .header(ContentType::JSON)
+        .body(app.create_test_config(&device_id).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post("/device-config/validate")
+        .json(&serde_json::json!({
+            "device_id": device_id,
+            "config": { "sampling_rate": "500", "wifi_password": "newpass456" }
+        }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let report: ValidationReport = response.into_json().await.expect("Invalid validation report");
+    assert!(report.valid);
+    let resolved = report.resolved.expect("Missing resolved configuration");
+    assert_eq!(resolved.config["sampling_rate"], "500");
+    assert_eq!(resolved.config["wifi_password"], "********");
+    assert!(report.changes.iter().any(|c| c.key == "sampling_rate" && c.old_value.as_deref() == Some("1000")));
+    assert!(report.warnings.iter().any(|w| w.contains("threshold") && w.contains("wifi_ssid")));
+
+    // The stored configuration is unchanged
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    let configs: Vec<Config> = response.into_json().await.expect("Invalid config response");
+    assert_eq!(configs[0].config["sampling_rate"], "1000");
+}
+
+/// Test validating an invalid configuration
+/// 
+/// This test verifies that the endpoint answers 200 with the reasons the
+/// update would be rejected, and that nothing is stored.
+#[tokio::test]
+async fn test_validate_invalid_config() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post("/device-config/validate")
+        .json(&serde_json::json!({ "device_id": device_id, "config": {} }))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let report: ValidationReport = response.into_json().await.expect("Invalid validation report");
+    assert!(!report.valid);
+    assert!(!report.errors.is_empty());
+    assert!(report.resolved.is_none());
+
+    let response = client
+        .get(format!("/device-config/get/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
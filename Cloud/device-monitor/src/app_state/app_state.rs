@@ -3,7 +3,46 @@
 // This module defines the shared application state that is injected into
 // all request handlers via Rocket's state management system.
 
-use crate::services::CosmosDbTelemetryStore;
+use std::time::Duration;
+
+use crate::domain::aggregate::TagAggregation;
+use crate::domain::archive::ArchivePolicy;
+use crate::domain::auth::AuthConfig;
+use crate::domain::device::StatusThresholds;
+use crate::domain::fleet::FleetSummary;
+use crate::domain::health::{ErrorMetrics, FleetHealth};
+use crate::domain::retention::RetentionPolicy;
+use crate::domain::tags::DeviceTags;
+use crate::domain::units::MetricUnits;
+use crate::services::{
+    AlertEvaluator, ArchiveJob, BlobContainer, CosmosDbTelemetryStore, QueryCache, RetentionJob, TelemetryFeed,
+};
+use crate::utils::concurrent_reads::DEFAULT_READ_CONCURRENCY;
+use crate::utils::ttl_cache::TtlCache;
+
+/// Default lifetime of cached aggregation results, in seconds
+const DEFAULT_AGGREGATE_CACHE_TTL_SECS: u64 = 60;
+
+/// Default lifetime of cached telemetry query results, in seconds
+const DEFAULT_QUERY_CACHE_TTL_SECS: u64 = 30;
+
+/// Default interval between polls for live telemetry, in seconds
+const DEFAULT_STREAM_POLL_INTERVAL_SECS: u64 = 2;
+
+/// Default age of the last telemetry after which a device is stale, in seconds
+const DEFAULT_DEVICE_STALE_AFTER_SECS: i64 = 5 * 60;
+
+/// Default age of the last telemetry after which a device is offline, in seconds
+const DEFAULT_DEVICE_OFFLINE_AFTER_SECS: i64 = 60 * 60;
+
+/// Default interval between reloads of the alert rules, in seconds
+const DEFAULT_ALERT_RULE_REFRESH_SECS: u64 = 30;
+
+/// Default interval between telemetry retention cleanups, in seconds
+const DEFAULT_RETENTION_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Default interval between telemetry archival runs, in seconds
+const DEFAULT_ARCHIVE_INTERVAL_SECS: u64 = 60 * 60;
 
 /// Application state containing shared resources and dependencies
 /// 
@@ -19,17 +58,218 @@ pub struct AppState {
     /// This client is used by telemetry monitoring handlers to retrieve
     /// device telemetry data from the Cosmos DB database.
     pub cosmos_client: CosmosDbTelemetryStore,
+
+    /// Credentials and device scopes used by the authentication request guards
+    /// 
+    /// Loaded from the `API_KEYS` and `JWT_SECRET` environment variables by
+    /// default; without either, every caller may read every device.
+    pub auth: AuthConfig,
+
+    /// Tags attached to each device, used for group-by-tag aggregation
+    /// 
+    /// Loaded from the `DEVICE_TAGS` environment variable by default.
+    pub device_tags: DeviceTags,
+
+    /// Units each metric is reported in, used to convert values on request
+    /// 
+    /// Loaded from the `METRIC_UNITS` environment variable on top of the
+    /// defaults (Celsius temperature, hectopascal pressure, volt voltage).
+    pub metric_units: MetricUnits,
+
+    /// Cache of group-by-tag aggregation results
+    /// 
+    /// Entries expire after `AGGREGATE_CACHE_TTL_SECS` seconds (default 60).
+    pub tag_aggregate_cache: TtlCache<TagAggregation>,
+
+    /// Cache of fleet summaries, keyed by window
+    /// 
+    /// Entries expire after `AGGREGATE_CACHE_TTL_SECS` seconds, like the
+    /// aggregation cache, so many open overview dashboards share one fan-out.
+    pub fleet_summary_cache: TtlCache<FleetSummary>,
+
+    /// Cache of fleet health scores, keyed by breach window
+    /// 
+    /// Entries expire after `AGGREGATE_CACHE_TTL_SECS` seconds, like the
+    /// fleet summary cache.
+    pub fleet_health_cache: TtlCache<FleetHealth>,
+
+    /// Cache of telemetry read and aggregation queries
+    /// 
+    /// Entries expire after `QUERY_CACHE_TTL_SECS` seconds (default 30, 0
+    /// disables the cache) and are dropped once the device stores new
+    /// telemetry, which is checked every `STREAM_POLL_INTERVAL_SECS` seconds.
+    pub query_cache: QueryCache,
+
+    /// Live feed of new telemetry records for streaming clients
+    /// 
+    /// Watched devices are polled every `STREAM_POLL_INTERVAL_SECS` seconds (default 2).
+    pub telemetry_feed: TelemetryFeed,
+
+    /// Thresholds for classifying devices as online, stale or offline
+    /// 
+    /// Loaded from `DEVICE_STALE_AFTER_SECS` (default 300) and
+    /// `DEVICE_OFFLINE_AFTER_SECS` (default 3600).
+    pub status_thresholds: StatusThresholds,
+
+    /// Metrics holding the error counters devices report
+    /// 
+    /// Loaded from `HEALTH_ERROR_METRICS` (default `errors,error_count`) and
+    /// used to score device health.
+    pub error_metrics: ErrorMetrics,
+
+    /// Background evaluator of the alert rules
+    /// 
+    /// Rules are reloaded every `ALERT_RULE_REFRESH_SECS` seconds (default 30),
+    /// and alerts are POSTed to `ALERT_WEBHOOK_URL` if it is set.
+    pub alert_evaluator: AlertEvaluator,
+
+    /// Background cleanup of telemetry older than its retention period
+    /// 
+    /// Retention is configured with `TELEMETRY_RETENTION_DAYS` and
+    /// `TELEMETRY_RETENTION_OVERRIDES` (unset by default, keeping everything),
+    /// and cleanups run every `RETENTION_INTERVAL_SECS` seconds (default 3600).
+    pub retention_job: RetentionJob,
+
+    /// Background archival of old telemetry to Azure Blob Storage
+    /// 
+    /// Archival is configured with `ARCHIVE_AFTER_DAYS`, `ARCHIVE_FORMAT`,
+    /// `ARCHIVE_DELETE` and `ARCHIVE_CONTAINER_URL` (unset by default,
+    /// archiving nothing), and runs every `ARCHIVE_INTERVAL_SECS` seconds
+    /// (default 3600).
+    pub archive_job: ArchiveJob,
+
+    /// Maximum number of store reads a fleet-wide or multi-device route runs at once
+    /// 
+    /// Loaded from `READ_CONCURRENCY` (default 16).
+    pub read_concurrency: usize,
 }
 
 impl AppState {
     /// Creates a new application state instance
     /// 
+    /// The authentication configuration, device tags, metric units, the
+    /// aggregation and query cache lifetimes, the live telemetry poll
+    /// interval, the device status thresholds, the error counter metrics,
+    /// the alert settings, the retention policy, the archive policy and the
+    /// store read concurrency are loaded from the environment; use `with_auth_config` and
+    /// `with_device_tags` to override the credentials and the tags.
+    /// 
     /// # Arguments
     /// * `cosmos_client` - The configured Cosmos DB telemetry store client
     /// 
     /// # Returns
     /// * `Self` - A new AppState instance with the provided dependencies
     pub fn new(cosmos_client: CosmosDbTelemetryStore) -> Self {
-        Self { cosmos_client }
+        let cache_ttl = std::env::var("AGGREGATE_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|ttl| ttl.parse().ok())
+            .unwrap_or(DEFAULT_AGGREGATE_CACHE_TTL_SECS);
+        let query_cache_ttl = std::env::var("QUERY_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|ttl| ttl.parse().ok())
+            .unwrap_or(DEFAULT_QUERY_CACHE_TTL_SECS);
+        let poll_interval = std::env::var("STREAM_POLL_INTERVAL_SECS")
+            .ok()
+            .and_then(|interval| interval.parse().ok())
+            .filter(|interval| *interval > 0)
+            .unwrap_or(DEFAULT_STREAM_POLL_INTERVAL_SECS);
+        let stale_after_secs = std::env::var("DEVICE_STALE_AFTER_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_DEVICE_STALE_AFTER_SECS);
+        // A device is stale before it is offline
+        let offline_after_secs = std::env::var("DEVICE_OFFLINE_AFTER_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .filter(|secs| *secs >= stale_after_secs)
+            .unwrap_or(DEFAULT_DEVICE_OFFLINE_AFTER_SECS.max(stale_after_secs));
+        let alert_refresh = std::env::var("ALERT_RULE_REFRESH_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_ALERT_RULE_REFRESH_SECS);
+        let alert_webhook_url = std::env::var("ALERT_WEBHOOK_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty());
+        let retention_interval = std::env::var("RETENTION_INTERVAL_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_RETENTION_INTERVAL_SECS);
+        let archive_interval = std::env::var("ARCHIVE_INTERVAL_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_ARCHIVE_INTERVAL_SECS);
+        let read_concurrency = std::env::var("READ_CONCURRENCY")
+            .ok()
+            .and_then(|limit| limit.parse().ok())
+            .filter(|limit| *limit > 0)
+            .unwrap_or(DEFAULT_READ_CONCURRENCY);
+        let telemetry_feed = TelemetryFeed::new(cosmos_client.clone(), Duration::from_secs(poll_interval));
+        let query_cache = QueryCache::new(
+            cosmos_client.clone(),
+            Duration::from_secs(query_cache_ttl),
+            Duration::from_secs(poll_interval),
+        );
+
+        Self {
+            alert_evaluator: AlertEvaluator::new(
+                cosmos_client.clone(),
+                telemetry_feed.clone(),
+                Duration::from_secs(alert_refresh),
+                alert_webhook_url,
+            ),
+            retention_job: RetentionJob::new(
+                cosmos_client.clone(),
+                query_cache.clone(),
+                RetentionPolicy::from_env(),
+                Duration::from_secs(retention_interval),
+            ),
+            archive_job: ArchiveJob::new(
+                cosmos_client.clone(),
+                query_cache.clone(),
+                BlobContainer::from_env(),
+                ArchivePolicy::from_env(),
+                Duration::from_secs(archive_interval),
+            ),
+            query_cache,
+            telemetry_feed,
+            cosmos_client,
+            auth: AuthConfig::from_env(),
+            device_tags: DeviceTags::from_env(),
+            metric_units: MetricUnits::from_env(),
+            tag_aggregate_cache: TtlCache::new(Duration::from_secs(cache_ttl)),
+            fleet_summary_cache: TtlCache::new(Duration::from_secs(cache_ttl)),
+            fleet_health_cache: TtlCache::new(Duration::from_secs(cache_ttl)),
+            status_thresholds: StatusThresholds { stale_after_secs, offline_after_secs },
+            error_metrics: ErrorMetrics::from_env(),
+            read_concurrency,
+        }
+    }
+
+    /// Replaces the authentication configuration of this application state
+    /// 
+    /// # Arguments
+    /// * `auth` - The credentials and device scopes to enforce
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given authentication configuration
+    pub fn with_auth_config(mut self, auth: AuthConfig) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Replaces the device tags of this application state
+    /// 
+    /// # Arguments
+    /// * `device_tags` - Tags for each device
+    /// 
+    /// # Returns
+    /// * `Self` - The AppState with the given device tags
+    pub fn with_device_tags(mut self, device_tags: DeviceTags) -> Self {
+        self.device_tags = device_tags;
+        self
     }
 }
\ No newline at end of file
@@ -0,0 +1,461 @@
+// Telemetry Aggregation Domain Logic
+// 
+// This module defines the aggregation functions that can be applied to a
+// numeric telemetry metric, the time buckets a device's samples can be
+// grouped into, and the result types returned by aggregation queries.
+// Buckets are aligned in the local time of an IANA time zone, so daily
+// buckets start at the operator's midnight rather than UTC midnight.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Aggregation function applied to the samples of a metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateFn {
+    Avg,
+    Min,
+    Max,
+    Sum,
+    Count,
+}
+
+impl AggregateFn {
+    /// Parses an aggregation function name (case-insensitive)
+    /// 
+    /// # Arguments
+    /// * `name` - One of "avg", "min", "max", "sum" or "count"
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The function or an invalid query error
+    pub fn parse(name: &str) -> Result<Self, ApiError> {
+        match name.to_ascii_lowercase().as_str() {
+            "avg" => Ok(AggregateFn::Avg),
+            "min" => Ok(AggregateFn::Min),
+            "max" => Ok(AggregateFn::Max),
+            "sum" => Ok(AggregateFn::Sum),
+            "count" => Ok(AggregateFn::Count),
+            other => Err(ApiError::InvalidQuery(format!("Unknown aggregation function: {}", other))),
+        }
+    }
+
+    /// Applies the function to a set of samples
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - The aggregate, or None if there are no samples
+    ///   (except for count, which is 0)
+    pub fn apply(&self, values: &[f64]) -> Option<f64> {
+        if values.is_empty() {
+            return match self {
+                AggregateFn::Count => Some(0.0),
+                _ => None,
+            };
+        }
+
+        Some(match self {
+            AggregateFn::Avg => values.iter().sum::<f64>() / values.len() as f64,
+            AggregateFn::Min => values.iter().cloned().fold(f64::INFINITY, f64::min),
+            AggregateFn::Max => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            AggregateFn::Sum => values.iter().sum(),
+            AggregateFn::Count => values.len() as f64,
+        })
+    }
+}
+
+/// Running aggregate of a metric's samples
+/// 
+/// Samples are folded in as they are read, so aggregating a long history
+/// keeps only these totals in memory instead of every sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleFold {
+    /// Number of samples folded in
+    count: usize,
+    /// Sum of the samples
+    sum: f64,
+    /// Smallest sample
+    min: f64,
+    /// Largest sample
+    max: f64,
+}
+
+impl Default for SampleFold {
+    fn default() -> Self {
+        SampleFold { count: 0, sum: 0.0, min: f64::INFINITY, max: f64::NEG_INFINITY }
+    }
+}
+
+impl SampleFold {
+    /// Folds in samples
+    pub fn extend(&mut self, values: impl IntoIterator<Item = f64>) {
+        for value in values {
+            self.count += 1;
+            self.sum += value;
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+    }
+
+    /// Folds in the samples of another fold
+    pub fn merge(&mut self, other: &SampleFold) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Returns the number of samples folded in
+    pub fn samples(&self) -> usize {
+        self.count
+    }
+
+    /// Applies an aggregation function to the samples folded in
+    /// 
+    /// # Returns
+    /// * `Option<f64>` - The same aggregate as `AggregateFn::apply` over the samples
+    pub fn value(&self, function: AggregateFn) -> Option<f64> {
+        if self.count == 0 {
+            return function.apply(&[]);
+        }
+
+        Some(match function {
+            AggregateFn::Avg => self.sum / self.count as f64,
+            AggregateFn::Min => self.min,
+            AggregateFn::Max => self.max,
+            AggregateFn::Sum => self.sum,
+            AggregateFn::Count => self.count as f64,
+        })
+    }
+}
+
+/// Extracts the numeric samples of a metric from telemetry records
+/// 
+/// Records without the metric, or with a non-numeric value, are skipped.
+/// 
+/// # Arguments
+/// * `telemetry` - The telemetry records
+/// * `metric` - The metric name (e.g. "temperature")
+/// 
+/// # Returns
+/// * `Vec<f64>` - The numeric samples
+pub fn metric_values(telemetry: &[Telemetry], metric: &str) -> Vec<f64> {
+    telemetry
+        .iter()
+        .filter_map(|t| t.telemetry_data.get(metric))
+        .filter_map(|value| value.trim().parse::<f64>().ok())
+        .filter(|value| value.is_finite())
+        .collect()
+}
+
+/// Aggregate of a metric over all devices sharing one tag value
+#[derive(Debug, Clone, Serialize)]
+pub struct TagGroupAggregate {
+    /// The tag value shared by the devices in this group (e.g. "london")
+    pub tag_value: String,
+    /// The aggregated value, or null if the group has no samples
+    pub value: Option<f64>,
+    /// Number of devices in the group
+    pub devices: usize,
+    /// Number of samples the aggregate was computed from
+    pub samples: usize,
+}
+
+/// Result of a group-by-tag aggregation query
+#[derive(Debug, Clone, Serialize)]
+pub struct TagAggregation {
+    /// The tag the devices were grouped by
+    pub tag: String,
+    /// The metric that was aggregated
+    pub metric: String,
+    /// The aggregation function that was applied
+    #[serde(rename = "fn")]
+    pub function: AggregateFn,
+    /// Earliest Unix timestamp aggregated
+    pub from: i64,
+    /// Latest Unix timestamp aggregated
+    pub to: i64,
+    /// One entry per tag value, sorted by tag value
+    pub groups: Vec<TagGroupAggregate>,
+}
+
+/// Largest accepted bucket width, in seconds (31 days)
+pub const MAX_BUCKET_SECS: i64 = 31 * 24 * 60 * 60;
+
+/// Parses a bucket width such as "30s", "15m", "1h" or "1d"
+/// 
+/// # Arguments
+/// * `bucket` - A positive whole number followed by `s`, `m`, `h` or `d`
+/// 
+/// # Returns
+/// * `Result<i64, ApiError>` - The width in seconds or an invalid query error
+pub fn parse_bucket(bucket: &str) -> Result<i64, ApiError> {
+    let invalid = || ApiError::InvalidQuery(format!("Invalid bucket: {} (expected e.g. 30s, 15m, 1h or 1d)", bucket));
+
+    let bucket = bucket.trim();
+    let unit = match bucket.chars().last() {
+        Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 60 * 60,
+        Some('d') => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    let count: i64 = bucket[..bucket.len() - 1].parse().map_err(|_| invalid())?;
+    if count <= 0 {
+        return Err(invalid());
+    }
+
+    let seconds = count.checked_mul(unit).filter(|seconds| *seconds <= MAX_BUCKET_SECS);
+    seconds.ok_or_else(|| ApiError::InvalidQuery(format!("Bucket {} is longer than 31 days", bucket)))
+}
+
+/// Parses an IANA time zone name such as "Europe/Berlin"
+/// 
+/// # Arguments
+/// * `tz` - The time zone name, or None for UTC
+/// 
+/// # Returns
+/// * `Result<Tz, ApiError>` - The time zone or an invalid query error
+pub fn parse_timezone(tz: Option<&str>) -> Result<Tz, ApiError> {
+    match tz.map(str::trim).filter(|tz| !tz.is_empty()) {
+        None => Ok(Tz::UTC),
+        Some(name) => name.parse().map_err(|_| {
+            ApiError::InvalidQuery(format!("Unknown time zone: {} (expected e.g. Europe/Berlin)", name))
+        }),
+    }
+}
+
+/// Returns the start of the bucket holding a timestamp
+/// 
+/// Buckets are multiples of the width since the Unix epoch in the time
+/// zone's local time, so a `1d` bucket starts at local midnight and a `1h`
+/// bucket on the local hour. The start is converted back with the offset in
+/// effect at that time, so buckets spanning a daylight saving change are an
+/// hour shorter or longer than the width.
+/// 
+/// # Arguments
+/// * `timestamp` - A Unix timestamp
+/// * `bucket_secs` - Width of each bucket, in seconds
+/// * `tz` - The time zone the buckets are aligned in
+/// 
+/// # Returns
+/// * `i64` - The Unix timestamp the bucket starts at
+pub fn bucket_start(timestamp: i64, bucket_secs: i64, tz: Tz) -> i64 {
+    let Some(utc) = DateTime::from_timestamp(timestamp, 0) else {
+        return timestamp.div_euclid(bucket_secs) * bucket_secs;
+    };
+    let offset = i64::from(tz.offset_from_utc_datetime(&utc.naive_utc()).fix().local_minus_utc());
+    let local_start = (timestamp + offset).div_euclid(bucket_secs) * bucket_secs;
+
+    let local = DateTime::from_timestamp(local_start, 0).map(|start| start.naive_utc());
+    match local.and_then(|local| tz.from_local_datetime(&local).earliest()) {
+        Some(start) => start.timestamp(),
+        // The local start was skipped by a daylight saving change
+        None => local_start - offset,
+    }
+}
+
+/// Aggregate of a metric over one time bucket
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BucketAggregate {
+    /// Start of the bucket as a Unix timestamp, a multiple of the bucket width in local time
+    pub start: i64,
+    /// The requested aggregate of the bucket's samples
+    pub value: Option<f64>,
+    /// Smallest sample in the bucket
+    pub min: Option<f64>,
+    /// Largest sample in the bucket
+    pub max: Option<f64>,
+    /// Mean of the bucket's samples
+    pub avg: Option<f64>,
+    /// Number of samples in the bucket
+    pub count: usize,
+}
+
+/// Result of a time-bucketed aggregation query for one device
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceAggregation {
+    /// The device whose telemetry was aggregated
+    pub device_id: String,
+    /// The metric that was aggregated
+    pub metric: String,
+    /// The aggregation function applied to produce each bucket's `value`
+    #[serde(rename = "fn")]
+    pub function: AggregateFn,
+    /// Width of each bucket, in seconds
+    pub bucket_secs: i64,
+    /// The IANA time zone the buckets are aligned in
+    pub tz: String,
+    /// Buckets with at least one sample, oldest first
+    pub buckets: Vec<BucketAggregate>,
+}
+
+/// Groups the numeric samples of a metric into fixed-width time buckets
+/// 
+/// Buckets are aligned to multiples of the width since the Unix epoch in
+/// the time zone's local time (see `bucket_start`), so the same query always
+/// produces the same boundaries. Records without a
+/// timestamp, or without a numeric value for the metric, are skipped, and
+/// buckets without samples are left out.
+/// 
+/// # Arguments
+/// * `telemetry` - The telemetry records
+/// * `metric` - The metric name (e.g. "temperature")
+/// * `bucket_secs` - Width of each bucket, in seconds
+/// * `tz` - The time zone the buckets are aligned in
+/// * `function` - The aggregation function producing each bucket's `value`
+/// 
+/// # Returns
+/// * `Vec<BucketAggregate>` - The non-empty buckets, oldest first
+pub fn aggregate_buckets(
+    telemetry: &[Telemetry],
+    metric: &str,
+    bucket_secs: i64,
+    tz: Tz,
+    function: AggregateFn,
+) -> Vec<BucketAggregate> {
+    let mut buckets: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+    for record in telemetry {
+        let Some(timestamp) = record.timestamp else {
+            continue;
+        };
+        if let Some(value) = metric_values(std::slice::from_ref(record), metric).pop() {
+            buckets.entry(bucket_start(timestamp, bucket_secs, tz)).or_default().push(value);
+        }
+    }
+
+    buckets
+        .into_iter()
+        .map(|(start, values)| BucketAggregate {
+            start,
+            value: function.apply(&values),
+            min: AggregateFn::Min.apply(&values),
+            max: AggregateFn::Max.apply(&values),
+            avg: AggregateFn::Avg.apply(&values),
+            count: values.len(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_functions() {
+        let values = [20.0, 22.0, 27.0];
+
+        assert_eq!(AggregateFn::Avg.apply(&values), Some(23.0));
+        assert_eq!(AggregateFn::Min.apply(&values), Some(20.0));
+        assert_eq!(AggregateFn::Max.apply(&values), Some(27.0));
+        assert_eq!(AggregateFn::Sum.apply(&values), Some(69.0));
+        assert_eq!(AggregateFn::Count.apply(&values), Some(3.0));
+    }
+
+    #[test]
+    fn test_aggregate_empty_samples() {
+        assert_eq!(AggregateFn::Avg.apply(&[]), None);
+        assert_eq!(AggregateFn::Count.apply(&[]), Some(0.0));
+    }
+
+    #[test]
+    fn test_sample_fold_matches_apply() {
+        let mut fold = SampleFold::default();
+        assert_eq!(fold.value(AggregateFn::Avg), None);
+        assert_eq!(fold.value(AggregateFn::Count), Some(0.0));
+
+        fold.extend([20.0, 22.0]);
+        let mut other = SampleFold::default();
+        other.extend([27.0]);
+        fold.merge(&other);
+        fold.merge(&SampleFold::default());
+
+        let values = [20.0, 22.0, 27.0];
+        for function in [AggregateFn::Avg, AggregateFn::Min, AggregateFn::Max, AggregateFn::Sum, AggregateFn::Count] {
+            assert_eq!(fold.value(function), function.apply(&values));
+        }
+        assert_eq!(fold.samples(), 3);
+    }
+
+    #[test]
+    fn test_aggregate_fn_parse() {
+        assert_eq!(AggregateFn::parse("AVG").unwrap(), AggregateFn::Avg);
+        assert!(matches!(AggregateFn::parse("median"), Err(ApiError::InvalidQuery(_))));
+    }
+
+    fn record(timestamp: i64, temperature: &str) -> Telemetry {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        Telemetry::new("sensor-001".to_string(), data, timestamp)
+    }
+
+    #[test]
+    fn test_parse_bucket() {
+        assert_eq!(parse_bucket("30s").unwrap(), 30);
+        assert_eq!(parse_bucket("15m").unwrap(), 900);
+        assert_eq!(parse_bucket("1h").unwrap(), 3600);
+        assert_eq!(parse_bucket("1d").unwrap(), 86400);
+
+        for invalid in ["", "h", "0h", "-1h", "1.5h", "1w", "32d"] {
+            assert!(matches!(parse_bucket(invalid), Err(ApiError::InvalidQuery(_))), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_buckets() {
+        let telemetry = [
+            record(7200, "20"),
+            record(3600, "22"),
+            record(3660, "26"),
+            record(3700, "n/a"),
+            record(7300, "30"),
+        ];
+
+        let buckets = aggregate_buckets(&telemetry, "temperature", 3600, Tz::UTC, AggregateFn::Max);
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0], BucketAggregate {
+            start: 3600,
+            value: Some(26.0),
+            min: Some(22.0),
+            max: Some(26.0),
+            avg: Some(24.0),
+            count: 2,
+        });
+        assert_eq!(buckets[1].start, 7200);
+        assert_eq!(buckets[1].avg, Some(25.0));
+        assert!(aggregate_buckets(&telemetry, "humidity", 3600, Tz::UTC, AggregateFn::Avg).is_empty());
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(parse_timezone(None).unwrap(), Tz::UTC);
+        assert_eq!(parse_timezone(Some("America/New_York")).unwrap(), Tz::America__New_York);
+        assert!(matches!(parse_timezone(Some("Mars/Olympus")), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_daily_buckets_start_at_local_midnight() {
+        let berlin = parse_timezone(Some("Europe/Berlin")).unwrap();
+        let day = 24 * 60 * 60;
+
+        // 2022-01-01T23:30:00Z is already 2022-01-02 in Berlin (UTC+1)
+        assert_eq!(bucket_start(1641079800, day, berlin), 1641078000);
+        assert_eq!(bucket_start(1641079800, day, Tz::UTC), 1640995200);
+
+        // 2022-03-27, when Berlin moves to UTC+2, starts at 23:00 UTC and lasts 23 hours
+        assert_eq!(bucket_start(1648370000, day, berlin), 1648335600);
+        assert_eq!(bucket_start(1648335600 + 23 * 3600, day, berlin), 1648335600 + 23 * 3600);
+
+        // Hourly buckets follow the half-hour offset of India (UTC+5:30)
+        let kolkata = parse_timezone(Some("Asia/Kolkata")).unwrap();
+        assert_eq!(bucket_start(1640995200, 3600, kolkata), 1640993400);
+
+        let telemetry = [record(1641079800, "20"), record(1641081600, "22")];
+        let buckets = aggregate_buckets(&telemetry, "temperature", day, berlin, AggregateFn::Avg);
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].start, 1641078000);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,433 @@
+// Alert Rule Domain Model
+// 
+// This module defines threshold alert rules, such as "temperature above 30
+// for 5 minutes", the alerts they raise, and the evaluation of a device's
+// new telemetry against its rules. A rule raises one alert per breach: it
+// fires once the condition has held for the rule's duration and re-arms
+// when a reading no longer meets the condition. Operators acknowledge an
+// alert when they take it on and resolve it once it is dealt with.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::telemetry::Telemetry;
+
+/// Longest duration a condition can be required to hold for, in seconds (one day)
+pub const MAX_ALERT_DURATION_SECS: i64 = 24 * 60 * 60;
+
+/// Comparison of a reading with a rule's threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    /// Reading above the threshold
+    Gt,
+    /// Reading at or above the threshold
+    Gte,
+    /// Reading below the threshold
+    Lt,
+    /// Reading at or below the threshold
+    Lte,
+    /// Reading equal to the threshold
+    Eq,
+    /// Reading different from the threshold
+    Ne,
+}
+
+impl Operator {
+    /// Returns true if the reading meets the condition
+    /// 
+    /// # Arguments
+    /// * `reading` - The metric's value in a telemetry record
+    /// * `threshold` - The rule's threshold
+    pub fn holds(self, reading: f64, threshold: f64) -> bool {
+        match self {
+            Operator::Gt => reading > threshold,
+            Operator::Gte => reading >= threshold,
+            Operator::Lt => reading < threshold,
+            Operator::Lte => reading <= threshold,
+            Operator::Eq => reading == threshold,
+            Operator::Ne => reading != threshold,
+        }
+    }
+}
+
+/// A threshold rule on one metric of a device
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertRule {
+    /// Unique identifier of the rule (also the Cosmos DB document id)
+    pub id: String,
+    /// The device the rule watches (partition key)
+    pub device_id: String,
+    /// The metric compared with the threshold (e.g. "temperature")
+    pub metric: String,
+    /// How the metric is compared with the threshold
+    pub operator: Operator,
+    /// The threshold
+    pub value: f64,
+    /// How long, in seconds, the condition must hold before an alert is raised
+    pub duration_secs: i64,
+    /// When the rule was created
+    pub created_at: DateTime<Utc>,
+    /// When the rule was last edited
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of a request creating or replacing an alert rule
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertRuleRequest {
+    /// The metric compared with the threshold
+    pub metric: String,
+    /// How the metric is compared with the threshold
+    pub operator: Operator,
+    /// The threshold
+    pub value: f64,
+    /// How long, in seconds, the condition must hold (omit to alert on the first reading)
+    #[serde(default)]
+    pub duration_secs: i64,
+}
+
+/// Error types that can occur while validating an alert rule
+#[derive(Debug, Serialize, PartialEq)]
+pub enum AlertRuleError {
+    /// No metric was given
+    MissingMetric,
+    /// The threshold is not a finite number
+    InvalidValue,
+    /// The duration is negative or too long
+    InvalidDuration,
+}
+
+impl std::fmt::Display for AlertRuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertRuleError::MissingMetric => write!(f, "Alert rule metric cannot be empty"),
+            AlertRuleError::InvalidValue => write!(f, "Alert rule value must be a finite number"),
+            AlertRuleError::InvalidDuration => write!(
+                f, "Alert rule duration must be between 0 and {} seconds", MAX_ALERT_DURATION_SECS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AlertRuleError {}
+
+impl AlertRuleRequest {
+    /// Validates the request
+    fn validate(&self) -> Result<(), AlertRuleError> {
+        if self.metric.trim().is_empty() {
+            return Err(AlertRuleError::MissingMetric);
+        }
+
+        if !self.value.is_finite() {
+            return Err(AlertRuleError::InvalidValue);
+        }
+
+        if !(0..=MAX_ALERT_DURATION_SECS).contains(&self.duration_secs) {
+            return Err(AlertRuleError::InvalidDuration);
+        }
+
+        Ok(())
+    }
+}
+
+impl AlertRule {
+    /// Creates a new alert rule with validation
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device to watch
+    /// * `request` - The rule's condition
+    /// 
+    /// # Returns
+    /// * `Result<Self, AlertRuleError>` - The new rule or a validation error
+    pub fn parse(device_id: String, request: AlertRuleRequest) -> Result<Self, AlertRuleError> {
+        request.validate()?;
+
+        let now = Utc::now();
+        Ok(AlertRule {
+            id: uuid::Uuid::new_v4().to_string(),
+            device_id,
+            metric: request.metric.trim().to_string(),
+            operator: request.operator,
+            value: request.value,
+            duration_secs: request.duration_secs,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Replaces the condition of this rule with validation
+    /// 
+    /// The id, device and creation time are kept.
+    /// 
+    /// # Arguments
+    /// * `request` - The new condition
+    /// 
+    /// # Returns
+    /// * `Result<Self, AlertRuleError>` - The updated rule or a validation error
+    pub fn update(self, request: AlertRuleRequest) -> Result<Self, AlertRuleError> {
+        request.validate()?;
+
+        Ok(AlertRule {
+            metric: request.metric.trim().to_string(),
+            operator: request.operator,
+            value: request.value,
+            duration_secs: request.duration_secs,
+            updated_at: Utc::now(),
+            ..self
+        })
+    }
+}
+
+/// Who acknowledged or resolved an alert, and when
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertAction {
+    /// The caller that took the action (JWT subject or API key name)
+    pub by: String,
+    /// When the action was taken
+    pub at: DateTime<Utc>,
+}
+
+/// Where an alert is in its handling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    /// Raised and not yet looked at
+    Open,
+    /// Taken on by an operator
+    Acknowledged,
+    /// Dealt with
+    Resolved,
+}
+
+/// Error types that can occur while acknowledging or resolving an alert
+#[derive(Debug, Serialize, PartialEq)]
+pub enum AlertActionError {
+    /// The alert was already resolved, so it cannot be acknowledged
+    AlreadyResolved,
+}
+
+impl std::fmt::Display for AlertActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertActionError::AlreadyResolved => write!(f, "Alert is already resolved"),
+        }
+    }
+}
+
+impl std::error::Error for AlertActionError {}
+
+/// An alert raised by a rule
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Alert {
+    /// Unique identifier of the alert (also the Cosmos DB document id)
+    pub id: String,
+    /// The rule that raised the alert
+    pub rule_id: String,
+    /// The device the alert is about (partition key)
+    pub device_id: String,
+    /// The metric that met the condition
+    pub metric: String,
+    /// The rule's comparison
+    pub operator: Operator,
+    /// The rule's threshold
+    pub threshold: f64,
+    /// The reading that raised the alert
+    pub value: f64,
+    /// Unix timestamp of the first reading of the breach
+    pub breach_started: i64,
+    /// Unix timestamp of the reading that raised the alert
+    pub triggered_at: i64,
+    /// Who acknowledged the alert, if anyone did
+    #[serde(default)]
+    pub acknowledged: Option<AlertAction>,
+    /// Who resolved the alert, if anyone did
+    #[serde(default)]
+    pub resolved: Option<AlertAction>,
+}
+
+impl Alert {
+    /// Returns where the alert is in its handling
+    pub fn status(&self) -> AlertStatus {
+        if self.resolved.is_some() {
+            AlertStatus::Resolved
+        } else if self.acknowledged.is_some() {
+            AlertStatus::Acknowledged
+        } else {
+            AlertStatus::Open
+        }
+    }
+
+    /// Records that an operator took the alert on
+    /// 
+    /// Acknowledging an acknowledged alert keeps the first acknowledgement.
+    /// 
+    /// # Arguments
+    /// * `by` - The caller acknowledging the alert
+    /// 
+    /// # Returns
+    /// * `Result<Self, AlertActionError>` - The acknowledged alert, or an
+    ///   error if it was already resolved
+    pub fn acknowledge(self, by: &str) -> Result<Self, AlertActionError> {
+        if self.resolved.is_some() {
+            return Err(AlertActionError::AlreadyResolved);
+        }
+
+        Ok(Alert {
+            acknowledged: self.acknowledged.clone().or_else(|| Some(AlertAction { by: by.to_string(), at: Utc::now() })),
+            ..self
+        })
+    }
+
+    /// Records that the alert was dealt with
+    /// 
+    /// An alert can be resolved without being acknowledged first; resolving
+    /// a resolved alert keeps the first resolution.
+    /// 
+    /// # Arguments
+    /// * `by` - The caller resolving the alert
+    pub fn resolve(self, by: &str) -> Self {
+        Alert {
+            resolved: self.resolved.clone().or_else(|| Some(AlertAction { by: by.to_string(), at: Utc::now() })),
+            ..self
+        }
+    }
+}
+
+/// The progress of one rule through a device's telemetry
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RuleState {
+    /// Timestamp of the first reading of the current breach, if the condition holds
+    breach_started: Option<i64>,
+    /// Whether the current breach already raised an alert
+    fired: bool,
+}
+
+impl RuleState {
+    /// Evaluates a new telemetry record against a rule
+    /// 
+    /// Records must be observed oldest first. Records without a numeric
+    /// value for the rule's metric are ignored.
+    /// 
+    /// # Arguments
+    /// * `rule` - The rule to evaluate
+    /// * `record` - The device's next telemetry record
+    /// 
+    /// # Returns
+    /// * `Option<Alert>` - The alert, if this record completes a breach of the
+    ///   rule's duration that has not raised one yet
+    pub fn observe(&mut self, rule: &AlertRule, record: &Telemetry) -> Option<Alert> {
+        let timestamp = record.timestamp?;
+        let reading = record.telemetry_data.get(&rule.metric)?.trim().parse::<f64>().ok()?;
+
+        if !rule.operator.holds(reading, rule.value) {
+            *self = RuleState::default();
+            return None;
+        }
+
+        let breach_started = *self.breach_started.get_or_insert(timestamp);
+        if self.fired || timestamp - breach_started < rule.duration_secs {
+            return None;
+        }
+
+        self.fired = true;
+        Some(Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            rule_id: rule.id.clone(),
+            device_id: rule.device_id.clone(),
+            metric: rule.metric.clone(),
+            operator: rule.operator,
+            threshold: rule.value,
+            value: reading,
+            breach_started,
+            triggered_at: timestamp,
+            acknowledged: None,
+            resolved: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(operator: Operator, value: f64, duration_secs: i64) -> AlertRule {
+        let request = AlertRuleRequest { metric: "temperature".to_string(), operator, value, duration_secs };
+        AlertRule::parse("sensor-001".to_string(), request).unwrap()
+    }
+
+    fn record(temperature: &str, timestamp: i64) -> Telemetry {
+        Telemetry::new(
+            "sensor-001".to_string(),
+            [("temperature".to_string(), temperature.to_string())].into(),
+            timestamp,
+        )
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_requests() {
+        let request = |metric: &str, value: f64, duration_secs: i64| AlertRuleRequest {
+            metric: metric.to_string(),
+            operator: Operator::Gt,
+            value,
+            duration_secs,
+        };
+        let device_id = || "sensor-001".to_string();
+
+        assert_eq!(AlertRule::parse(device_id(), request(" ", 30.0, 0)).unwrap_err(), AlertRuleError::MissingMetric);
+        assert_eq!(AlertRule::parse(device_id(), request("temperature", f64::NAN, 0)).unwrap_err(), AlertRuleError::InvalidValue);
+        assert_eq!(AlertRule::parse(device_id(), request("temperature", 30.0, -1)).unwrap_err(), AlertRuleError::InvalidDuration);
+        assert!(serde_json::from_str::<AlertRuleRequest>(r#"{"metric": "t", "operator": "above", "value": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_alert_after_duration() {
+        let rule = rule(Operator::Gt, 30.0, 300);
+        let mut state = RuleState::default();
+
+        assert!(state.observe(&rule, &record("31", 1000)).is_none());
+        assert!(state.observe(&rule, &record("online", 1100)).is_none());
+        assert!(state.observe(&rule, &record("32", 1200)).is_none());
+
+        let alert = state.observe(&rule, &record("33", 1300)).unwrap();
+        assert_eq!(alert.breach_started, 1000);
+        assert_eq!(alert.triggered_at, 1300);
+        assert_eq!(alert.value, 33.0);
+
+        // One alert per breach, re-armed once the condition stops holding
+        assert!(state.observe(&rule, &record("34", 1400)).is_none());
+        assert!(state.observe(&rule, &record("29", 1500)).is_none());
+        assert!(state.observe(&rule, &record("31", 1600)).is_none());
+        assert!(state.observe(&rule, &record("31", 1900)).is_some());
+    }
+
+    #[test]
+    fn test_acknowledge_and_resolve() {
+        let rule = rule(Operator::Gt, 30.0, 0);
+        let alert = RuleState::default().observe(&rule, &record("31", 1000)).unwrap();
+        assert_eq!(alert.status(), AlertStatus::Open);
+
+        let alert = alert.acknowledge("alice").unwrap();
+        assert_eq!(alert.status(), AlertStatus::Acknowledged);
+        let alert = alert.acknowledge("bob").unwrap();
+        assert_eq!(alert.acknowledged.as_ref().unwrap().by, "alice");
+
+        let alert = alert.resolve("bob").resolve("carol");
+        assert_eq!(alert.status(), AlertStatus::Resolved);
+        assert_eq!(alert.resolved.as_ref().unwrap().by, "bob");
+        assert_eq!(alert.acknowledge("alice").unwrap_err(), AlertActionError::AlreadyResolved);
+
+        // Alerts stored before they could be handled are open
+        let stored = r#"{"id": "a", "rule_id": "r", "device_id": "d", "metric": "m", "operator": "gt",
+            "threshold": 1.0, "value": 2.0, "breach_started": 1, "triggered_at": 2}"#;
+        assert_eq!(serde_json::from_str::<Alert>(stored).unwrap().status(), AlertStatus::Open);
+    }
+
+    #[test]
+    fn test_alert_without_duration() {
+        let rule = rule(Operator::Lte, 3.0, 0);
+        let mut state = RuleState::default();
+
+        assert!(state.observe(&rule, &record("3.1", 1000)).is_none());
+        assert_eq!(state.observe(&rule, &record("3.0", 1060)).unwrap().triggered_at, 1060);
+    }
+}
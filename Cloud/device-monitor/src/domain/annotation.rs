@@ -0,0 +1,204 @@
+// Telemetry Annotation Domain Model
+// 
+// This module defines the manual notes operators attach to a device's
+// telemetry timeline, such as "sensor relocated" or "HVAC serviced", to
+// explain shifts in the data. An annotation covers a time range (or a
+// single instant) and records who wrote it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of an annotation text, in characters
+pub const MAX_ANNOTATION_TEXT_LEN: usize = 1000;
+
+/// A note attached to a device's telemetry timeline
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Annotation {
+    /// Unique identifier of the annotation (also the Cosmos DB document id)
+    pub id: String,
+    /// The device the annotation belongs to (partition key)
+    pub device_id: String,
+    /// Unix timestamp at which the annotated period starts
+    pub start: i64,
+    /// Unix timestamp at which the annotated period ends (same as `start` for an instant)
+    pub end: i64,
+    /// The note itself
+    pub text: String,
+    /// Who wrote the note
+    pub author: String,
+    /// When the annotation was created
+    pub created_at: DateTime<Utc>,
+    /// When the annotation was last edited
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of a request creating or replacing an annotation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnnotationRequest {
+    /// Unix timestamp at which the annotated period starts
+    pub start: i64,
+    /// Unix timestamp at which the annotated period ends (omit for an instant)
+    #[serde(default)]
+    pub end: Option<i64>,
+    /// The note itself
+    pub text: String,
+    /// Who wrote the note
+    pub author: String,
+}
+
+/// Error types that can occur while validating an annotation
+#[derive(Debug, Serialize, PartialEq)]
+pub enum AnnotationError {
+    /// The time range is negative or ends before it starts
+    InvalidRange,
+    /// The text is empty or too long
+    InvalidText,
+    /// No author was given
+    MissingAuthor,
+}
+
+impl std::fmt::Display for AnnotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnnotationError::InvalidRange => write!(f, "Annotation must end at or after its start"),
+            AnnotationError::InvalidText => write!(
+                f, "Annotation text must be between 1 and {} characters", MAX_ANNOTATION_TEXT_LEN
+            ),
+            AnnotationError::MissingAuthor => write!(f, "Annotation author cannot be empty"),
+        }
+    }
+}
+
+impl std::error::Error for AnnotationError {}
+
+impl AnnotationRequest {
+    /// Validates the request
+    fn validate(&self) -> Result<(), AnnotationError> {
+        let end = self.end.unwrap_or(self.start);
+        if self.start < 0 || end < self.start {
+            return Err(AnnotationError::InvalidRange);
+        }
+
+        let text = self.text.trim();
+        if text.is_empty() || text.chars().count() > MAX_ANNOTATION_TEXT_LEN {
+            return Err(AnnotationError::InvalidText);
+        }
+
+        if self.author.trim().is_empty() {
+            return Err(AnnotationError::MissingAuthor);
+        }
+
+        Ok(())
+    }
+}
+
+impl Annotation {
+    /// Creates a new annotation with validation
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device to annotate
+    /// * `request` - The annotation contents
+    /// 
+    /// # Returns
+    /// * `Result<Self, AnnotationError>` - The new annotation or a validation error
+    pub fn parse(device_id: String, request: AnnotationRequest) -> Result<Self, AnnotationError> {
+        request.validate()?;
+
+        let now = Utc::now();
+        Ok(Annotation {
+            id: uuid::Uuid::new_v4().to_string(),
+            device_id,
+            start: request.start,
+            end: request.end.unwrap_or(request.start),
+            text: request.text.trim().to_string(),
+            author: request.author.trim().to_string(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Replaces the contents of this annotation with validation
+    /// 
+    /// The id, device and creation time are kept.
+    /// 
+    /// # Arguments
+    /// * `request` - The new annotation contents
+    /// 
+    /// # Returns
+    /// * `Result<Self, AnnotationError>` - The updated annotation or a validation error
+    pub fn update(self, request: AnnotationRequest) -> Result<Self, AnnotationError> {
+        request.validate()?;
+
+        Ok(Annotation {
+            start: request.start,
+            end: request.end.unwrap_or(request.start),
+            text: request.text.trim().to_string(),
+            author: request.author.trim().to_string(),
+            updated_at: Utc::now(),
+            ..self
+        })
+    }
+
+    /// Returns true if the annotation overlaps the given time range
+    /// 
+    /// # Arguments
+    /// * `from` - Start of the range (unbounded if None)
+    /// * `to` - End of the range (unbounded if None)
+    pub fn overlaps(&self, from: Option<i64>, to: Option<i64>) -> bool {
+        from.is_none_or(|from| self.end >= from) && to.is_none_or(|to| self.start <= to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(start: i64, end: Option<i64>, text: &str) -> AnnotationRequest {
+        AnnotationRequest { start, end, text: text.to_string(), author: "alice".to_string() }
+    }
+
+    #[test]
+    fn test_parse_instant_annotation() {
+        let annotation = Annotation::parse("sensor-001".to_string(), request(100, None, " HVAC serviced ")).unwrap();
+
+        assert_eq!(annotation.start, 100);
+        assert_eq!(annotation.end, 100);
+        assert_eq!(annotation.text, "HVAC serviced");
+        assert_eq!(annotation.created_at, annotation.updated_at);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_requests() {
+        let device_id = || "sensor-001".to_string();
+
+        assert_eq!(Annotation::parse(device_id(), request(200, Some(100), "x")).unwrap_err(), AnnotationError::InvalidRange);
+        assert_eq!(Annotation::parse(device_id(), request(100, None, "  ")).unwrap_err(), AnnotationError::InvalidText);
+
+        let mut anonymous = request(100, None, "x");
+        anonymous.author = String::new();
+        assert_eq!(Annotation::parse(device_id(), anonymous).unwrap_err(), AnnotationError::MissingAuthor);
+    }
+
+    #[test]
+    fn test_update_keeps_identity() {
+        let annotation = Annotation::parse("sensor-001".to_string(), request(100, None, "relocated")).unwrap();
+
+        let updated = annotation.clone().update(request(100, Some(300), "relocated to hall")).unwrap();
+
+        assert_eq!(updated.id, annotation.id);
+        assert_eq!(updated.created_at, annotation.created_at);
+        assert_eq!(updated.end, 300);
+        assert_eq!(updated.text, "relocated to hall");
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let annotation = Annotation::parse("sensor-001".to_string(), request(100, Some(200), "x")).unwrap();
+
+        assert!(annotation.overlaps(None, None));
+        assert!(annotation.overlaps(Some(150), Some(400)));
+        assert!(annotation.overlaps(Some(200), None));
+        assert!(!annotation.overlaps(Some(201), None));
+        assert!(!annotation.overlaps(None, Some(99)));
+    }
+}
@@ -0,0 +1,241 @@
+// Anomaly Detection Domain Logic
+// 
+// This module flags readings of a metric that deviate from the metric's
+// recent behaviour, so failing sensors are caught without hand-set
+// thresholds. Each reading is scored against the readings before it, either
+// with a rolling z-score over a window of readings or with an exponentially
+// weighted moving average (EWMA) band.
+
+use serde::Serialize;
+
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Default number of readings in the rolling window
+pub const DEFAULT_WINDOW: usize = 30;
+
+/// Largest accepted rolling window
+pub const MAX_WINDOW: usize = 1000;
+
+/// Default score above which a reading is anomalous, in standard deviations
+pub const DEFAULT_THRESHOLD: f64 = 3.0;
+
+/// Default weight of the newest reading in the EWMA
+pub const DEFAULT_ALPHA: f64 = 0.3;
+
+/// Number of earlier readings needed before a reading is scored
+pub const MIN_HISTORY: usize = 5;
+
+/// How a reading's expected value and spread are estimated
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AnomalyMethod {
+    /// Mean and standard deviation of the previous `window` readings
+    ZScore {
+        /// Number of readings in the window
+        window: usize,
+    },
+    /// Exponentially weighted mean and standard deviation of all previous readings
+    Ewma {
+        /// Weight of the newest reading, between 0 and 1
+        alpha: f64,
+    },
+}
+
+impl AnomalyMethod {
+    /// Parses the `method`, `window` and `alpha` query parameters
+    /// 
+    /// # Arguments
+    /// * `method` - "zscore" (default) or "ewma" (case-insensitive)
+    /// * `window` - Rolling window of the z-score method (default `DEFAULT_WINDOW`)
+    /// * `alpha` - Weight of the EWMA method (default `DEFAULT_ALPHA`)
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The method or an invalid query error
+    pub fn parse(method: Option<&str>, window: Option<usize>, alpha: Option<f64>) -> Result<Self, ApiError> {
+        match method.unwrap_or("zscore").to_ascii_lowercase().as_str() {
+            "zscore" => {
+                let window = window.unwrap_or(DEFAULT_WINDOW);
+                if !(MIN_HISTORY..=MAX_WINDOW).contains(&window) {
+                    return Err(ApiError::InvalidQuery(format!(
+                        "window must be between {} and {}",
+                        MIN_HISTORY, MAX_WINDOW
+                    )));
+                }
+                Ok(AnomalyMethod::ZScore { window })
+            }
+            "ewma" => {
+                let alpha = alpha.unwrap_or(DEFAULT_ALPHA);
+                if !(alpha > 0.0 && alpha < 1.0) {
+                    return Err(ApiError::InvalidQuery("alpha must be between 0 and 1".to_string()));
+                }
+                Ok(AnomalyMethod::Ewma { alpha })
+            }
+            other => Err(ApiError::InvalidQuery(format!("Unknown anomaly detection method: {}", other))),
+        }
+    }
+}
+
+/// Validates the `threshold` query parameter
+/// 
+/// # Returns
+/// * `Result<f64, ApiError>` - The threshold, `DEFAULT_THRESHOLD` if not given, or an
+///   invalid query error if it is not a positive number
+pub fn parse_threshold(threshold: Option<f64>) -> Result<f64, ApiError> {
+    let threshold = threshold.unwrap_or(DEFAULT_THRESHOLD);
+    if !(threshold.is_finite() && threshold > 0.0) {
+        return Err(ApiError::InvalidQuery("threshold must be a positive number".to_string()));
+    }
+    Ok(threshold)
+}
+
+/// A reading flagged as anomalous
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Anomaly {
+    /// Unix timestamp of the reading
+    pub timestamp: i64,
+    /// The reading
+    pub value: f64,
+    /// The value expected from the earlier readings
+    pub expected: f64,
+    /// Deviation from the expected value, in standard deviations (negative below it)
+    pub score: f64,
+}
+
+/// Result of an anomaly detection query for one device
+#[derive(Debug, Clone, Serialize)]
+pub struct AnomalyReport {
+    /// The device whose telemetry was analyzed
+    pub device_id: String,
+    /// The metric that was analyzed
+    pub metric: String,
+    /// The method and its parameter
+    pub method: AnomalyMethod,
+    /// The score above which readings were flagged
+    pub threshold: f64,
+    /// Number of numeric readings analyzed
+    pub points: usize,
+    /// The anomalous readings, oldest first
+    pub anomalies: Vec<Anomaly>,
+}
+
+/// Flags the readings of a metric that deviate beyond the threshold
+/// 
+/// Records without a timestamp, or without a numeric value for the metric,
+/// are skipped. The first `MIN_HISTORY` readings, and readings compared with
+/// earlier readings that do not vary at all, are not scored. Anomalous
+/// readings still count towards the expected value of later ones.
+/// 
+/// # Arguments
+/// * `telemetry` - The telemetry records, oldest first
+/// * `metric` - The metric name (e.g. "temperature")
+/// * `method` - How the expected value and spread are estimated
+/// * `threshold` - The absolute score above which a reading is anomalous
+/// 
+/// # Returns
+/// * `(usize, Vec<Anomaly>)` - The number of readings analyzed and the anomalies, oldest first
+pub fn detect_anomalies(
+    telemetry: &[Telemetry],
+    metric: &str,
+    method: AnomalyMethod,
+    threshold: f64,
+) -> (usize, Vec<Anomaly>) {
+    let readings: Vec<(i64, f64)> = telemetry
+        .iter()
+        .filter_map(|record| {
+            let value = record.telemetry_data.get(metric)?.trim().parse::<f64>().ok()?;
+            Some((record.timestamp?, value))
+        })
+        .collect();
+
+    let mut anomalies = Vec::new();
+    let mut ewma: Option<(f64, f64)> = None;
+    for (i, &(timestamp, value)) in readings.iter().enumerate() {
+        // The expected value and variance before this reading
+        let estimate = match method {
+            AnomalyMethod::ZScore { window } => {
+                let history = &readings[i.saturating_sub(window)..i];
+                (history.len() >= MIN_HISTORY).then(|| {
+                    let mean = history.iter().map(|(_, v)| v).sum::<f64>() / history.len() as f64;
+                    let variance = history.iter().map(|(_, v)| (v - mean).powi(2)).sum::<f64>() / history.len() as f64;
+                    (mean, variance)
+                })
+            }
+            AnomalyMethod::Ewma { alpha } => {
+                let estimate = ewma.filter(|_| i >= MIN_HISTORY);
+                let (mean, variance) = ewma.unwrap_or((value, 0.0));
+                let deviation = value - mean;
+                ewma = Some((mean + alpha * deviation, (1.0 - alpha) * (variance + alpha * deviation * deviation)));
+                estimate
+            }
+        };
+
+        let Some((expected, variance)) = estimate else {
+            continue;
+        };
+        if variance <= f64::EPSILON {
+            continue;
+        }
+
+        let score = (value - expected) / variance.sqrt();
+        if score.abs() > threshold {
+            anomalies.push(Anomaly { timestamp, value, expected, score });
+        }
+    }
+
+    (readings.len(), anomalies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[f64]) -> Vec<Telemetry> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                Telemetry::new(
+                    "sensor-001".to_string(),
+                    [("temperature".to_string(), value.to_string())].into(),
+                    1640995200 + i as i64 * 60,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_method() {
+        assert_eq!(AnomalyMethod::parse(None, None, None).unwrap(), AnomalyMethod::ZScore { window: DEFAULT_WINDOW });
+        assert_eq!(AnomalyMethod::parse(Some("EWMA"), None, Some(0.5)).unwrap(), AnomalyMethod::Ewma { alpha: 0.5 });
+        assert!(AnomalyMethod::parse(Some("zscore"), Some(2), None).is_err());
+        assert!(AnomalyMethod::parse(Some("ewma"), None, Some(1.0)).is_err());
+        assert!(AnomalyMethod::parse(Some("iqr"), None, None).is_err());
+        assert!(parse_threshold(Some(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_detects_spike() {
+        let mut values = vec![20.0, 20.5, 19.5, 20.2, 19.8, 20.1, 19.9, 20.3, 19.7, 20.0];
+        values.push(35.0);
+        values.extend([20.1, 19.9]);
+        let telemetry = series(&values);
+
+        for method in [AnomalyMethod::ZScore { window: 10 }, AnomalyMethod::Ewma { alpha: 0.3 }] {
+            let (points, anomalies) = detect_anomalies(&telemetry, "temperature", method, 3.0);
+            assert_eq!(points, values.len());
+            assert_eq!(anomalies.len(), 1, "{:?}", method);
+            assert_eq!(anomalies[0].value, 35.0);
+            assert_eq!(anomalies[0].timestamp, 1640995200 + 10 * 60);
+            assert!(anomalies[0].score > 3.0);
+        }
+    }
+
+    #[test]
+    fn test_flat_series_has_no_anomalies() {
+        let telemetry = series(&[20.0; 12]);
+
+        let (_, anomalies) = detect_anomalies(&telemetry, "temperature", AnomalyMethod::ZScore { window: 5 }, 3.0);
+        assert!(anomalies.is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+// Telemetry Archive Domain Model
+// 
+// This module defines how telemetry older than a configurable age is
+// archived to Azure Blob Storage: one blob per device and UTC day, written
+// as JSON Lines or Parquet. Archived days are recorded in an index, so the
+// archives can be listed, and can optionally be deleted from Cosmos DB to
+// keep hot storage small while preserving the device's history.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::domain::export::{metric_columns, write_parquet};
+use crate::domain::telemetry::Telemetry;
+
+/// Number of seconds in a day
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// File format of archived telemetry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// One JSON telemetry record per line
+    #[default]
+    Jsonl,
+    /// A typed, columnar file with the same columns as the Parquet export
+    Parquet,
+}
+
+impl ArchiveFormat {
+    /// Returns the file extension of archive blobs
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::Jsonl => "jsonl",
+            ArchiveFormat::Parquet => "parquet",
+        }
+    }
+
+    /// Returns the content type archive blobs are stored with
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ArchiveFormat::Jsonl => "application/x-ndjson",
+            ArchiveFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// Encodes one day of telemetry records in this format
+    /// 
+    /// # Arguments
+    /// * `telemetry` - The records of the day, oldest first
+    /// 
+    /// # Returns
+    /// * `Result<Vec<u8>, String>` - The contents of the archive blob or an encoding error
+    pub fn encode(self, telemetry: &[Telemetry]) -> Result<Vec<u8>, String> {
+        match self {
+            ArchiveFormat::Jsonl => {
+                let mut contents = Vec::new();
+                for record in telemetry {
+                    serde_json::to_writer(&mut contents, record).map_err(|e| e.to_string())?;
+                    contents.push(b'\n');
+                }
+                Ok(contents)
+            }
+            ArchiveFormat::Parquet => {
+                write_parquet(telemetry, &metric_columns(telemetry)).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// Which telemetry is archived, in which format, and whether it is then deleted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ArchivePolicy {
+    /// Age in days after which telemetry is archived, or None to archive nothing
+    pub after_days: Option<i64>,
+    /// File format of the archive blobs
+    pub format: ArchiveFormat,
+    /// Whether archived telemetry is deleted from Cosmos DB
+    pub delete_archived: bool,
+}
+
+impl ArchivePolicy {
+    /// Loads the policy from the `ARCHIVE_AFTER_DAYS`, `ARCHIVE_FORMAT` and
+    /// `ARCHIVE_DELETE` environment variables
+    /// 
+    /// `ARCHIVE_FORMAT` is `jsonl` (the default) or `parquet`, and archived
+    /// telemetry is only deleted if `ARCHIVE_DELETE` is `true`. Unset, invalid
+    /// and non-positive ages are ignored, so nothing is archived.
+    pub fn from_env() -> Self {
+        let after_days = std::env::var("ARCHIVE_AFTER_DAYS")
+            .ok()
+            .and_then(|days| days.parse().ok())
+            .filter(|days| *days > 0);
+
+        let format = match std::env::var("ARCHIVE_FORMAT") {
+            Ok(format) => serde_json::from_value(serde_json::Value::String(format.to_lowercase()))
+                .unwrap_or_else(|_| {
+                    warn!("Ignoring invalid ARCHIVE_FORMAT {}, archiving as JSON Lines", format);
+                    ArchiveFormat::Jsonl
+                }),
+            Err(_) => ArchiveFormat::Jsonl,
+        };
+
+        let delete_archived = std::env::var("ARCHIVE_DELETE")
+            .is_ok_and(|delete| delete.eq_ignore_ascii_case("true"));
+
+        ArchivePolicy { after_days, format, delete_archived }
+    }
+
+    /// Returns true if any telemetry is archived
+    pub fn is_enabled(&self) -> bool {
+        self.after_days.is_some()
+    }
+
+    /// Returns the end of the telemetry to archive
+    /// 
+    /// Only whole days are archived, so the cutoff is the start of the UTC
+    /// day the configured age falls on.
+    /// 
+    /// # Arguments
+    /// * `now` - The current Unix timestamp
+    /// 
+    /// # Returns
+    /// * `Option<i64>` - Records before this Unix timestamp are archived, or None if archival is disabled
+    pub fn cutoff(&self, now: i64) -> Option<i64> {
+        let days = self.after_days?;
+        Some((now - days * SECS_PER_DAY).div_euclid(SECS_PER_DAY) * SECS_PER_DAY)
+    }
+}
+
+/// Returns the UTC day of a Unix timestamp
+pub fn day_of(timestamp: i64) -> NaiveDate {
+    DateTime::from_timestamp(timestamp, 0).unwrap_or_default().date_naive()
+}
+
+/// Returns the Unix timestamp a UTC day starts at
+pub fn day_start(day: NaiveDate) -> i64 {
+    day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp()
+}
+
+/// Returns the name of the blob holding a device's telemetry of one day
+/// 
+/// # Arguments
+/// * `device_id` - The unique identifier of the device
+/// * `day` - The UTC day of the telemetry
+/// * `format` - The file format of the blob
+/// 
+/// # Returns
+/// * `String` - `<device_id>/<YYYY-MM-DD>.<extension>`
+pub fn blob_name(device_id: &str, day: NaiveDate, format: ArchiveFormat) -> String {
+    format!("{}/{}.{}", device_id, day.format("%Y-%m-%d"), format.extension())
+}
+
+/// A day of a device's telemetry archived to Blob Storage
+/// 
+/// Stored in the archive index, partitioned by device id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Archive {
+    /// Unique identifier of the archive (also the Cosmos DB document id), `<device_id>-<YYYY-MM-DD>`
+    pub id: String,
+    /// The device whose telemetry was archived (partition key)
+    pub device_id: String,
+    /// The UTC day of the archived telemetry
+    pub day: NaiveDate,
+    /// Name of the blob within the archive container
+    pub blob_name: String,
+    /// File format of the blob
+    pub format: ArchiveFormat,
+    /// Number of records archived
+    pub records: usize,
+    /// Size of the blob, in bytes
+    pub bytes: usize,
+    /// Unix timestamp of the oldest archived record
+    pub first_timestamp: i64,
+    /// Unix timestamp of the newest archived record
+    pub last_timestamp: i64,
+    /// Whether the archived records were deleted from Cosmos DB
+    pub deleted: bool,
+    /// When the telemetry was archived
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Groups telemetry records, read oldest first, into whole days
+/// 
+/// Records are pushed one at a time as pages are read, so only one day of
+/// a device's telemetry is held in memory.
+#[derive(Debug, Default)]
+pub struct DayBatches {
+    day: Option<NaiveDate>,
+    records: Vec<Telemetry>,
+}
+
+impl DayBatches {
+    /// Adds the next record
+    /// 
+    /// Records without a timestamp cannot be assigned to a day and are skipped.
+    /// 
+    /// # Returns
+    /// * `Option<(NaiveDate, Vec<Telemetry>)>` - The previous day and its records,
+    ///   once the record starts a new day
+    pub fn push(&mut self, record: Telemetry) -> Option<(NaiveDate, Vec<Telemetry>)> {
+        let day = day_of(record.timestamp?);
+        let completed = match self.day {
+            Some(current) if current != day => Some((current, std::mem::take(&mut self.records))),
+            _ => None,
+        };
+        self.day = Some(day);
+        self.records.push(record);
+        completed
+    }
+
+    /// Returns the last day and its records, if any records were pushed
+    pub fn finish(self) -> Option<(NaiveDate, Vec<Telemetry>)> {
+        self.day.map(|day| (day, self.records))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(timestamp: i64) -> Telemetry {
+        let data = [("temperature".to_string(), "21.5".to_string())].into();
+        Telemetry::parse("sensor-001".to_string(), data, Some(timestamp)).unwrap()
+    }
+
+    #[test]
+    fn test_cutoff_and_blob_name() {
+        let policy = ArchivePolicy { after_days: Some(30), ..Default::default() };
+        // 2022-03-10T15:30:00Z
+        let now = 1646926200;
+
+        let cutoff = policy.cutoff(now).unwrap();
+        assert_eq!(day_of(cutoff), NaiveDate::from_ymd_opt(2022, 2, 8).unwrap());
+        assert_eq!(cutoff, day_start(day_of(cutoff)));
+        assert_eq!(ArchivePolicy::default().cutoff(now), None);
+
+        assert_eq!(blob_name("sensor-001", day_of(now), ArchiveFormat::Jsonl), "sensor-001/2022-03-10.jsonl");
+        assert_eq!(blob_name("sensor-001", day_of(now), ArchiveFormat::Parquet), "sensor-001/2022-03-10.parquet");
+    }
+
+    #[test]
+    fn test_day_batches() {
+        // 2022-01-01T00:00:00Z
+        let midnight = 1640995200;
+        let mut batches = DayBatches::default();
+
+        assert!(batches.push(record(midnight - 60)).is_none());
+        let (day, records) = batches.push(record(midnight)).unwrap();
+        assert_eq!(day, NaiveDate::from_ymd_opt(2021, 12, 31).unwrap());
+        assert_eq!(records.len(), 1);
+        assert!(batches.push(record(midnight + 3600)).is_none());
+
+        let (day, records) = batches.finish().unwrap();
+        assert_eq!(day, NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+        assert_eq!(records.len(), 2);
+        assert!(DayBatches::default().finish().is_none());
+    }
+
+    #[test]
+    fn test_encode_jsonl() {
+        let contents = ArchiveFormat::Jsonl.encode(&[record(1), record(2)]).unwrap();
+        let lines: Vec<Telemetry> = String::from_utf8(contents)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].timestamp, Some(2));
+    }
+}
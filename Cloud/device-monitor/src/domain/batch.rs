@@ -0,0 +1,90 @@
+// Batch Read Domain Model
+// 
+// This module defines the body of a batch telemetry read, which reads the
+// telemetry of several devices with shared parameters in one request, so a
+// fleet dashboard does not fan out one HTTP request per device.
+
+use serde::Deserialize;
+
+use crate::domain::error::ApiError;
+
+/// Largest number of devices read in one batch
+pub const MAX_BATCH_DEVICES: usize = 50;
+
+/// Body of a batch telemetry read
+/// 
+/// Apart from `device_ids`, the fields are the read endpoint's query
+/// parameters and are applied to every device.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BatchReadRequest {
+    /// The devices to read
+    pub device_ids: Vec<String>,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+    /// Maximum number of records to return per device
+    pub limit: Option<usize>,
+    /// Comma-separated metrics to return from each record
+    pub metrics: Option<String>,
+    /// "asc" for oldest first or "desc" for newest first (default)
+    pub order: Option<String>,
+    /// Comma-separated units to convert metric values to (e.g. "fahrenheit,kpa")
+    pub unit: Option<String>,
+    /// Comma-separated conditions on metric values records must meet (e.g. "temperature>30")
+    #[serde(rename = "where")]
+    pub filter: Option<String>,
+}
+
+impl BatchReadRequest {
+    /// Validates the requested devices
+    /// 
+    /// Blank entries and repeated devices are dropped, keeping the first occurrence.
+    /// 
+    /// # Returns
+    /// * `Result<Vec<String>, ApiError>` - The device IDs in the given order, or an invalid
+    ///   query error if none or more than `MAX_BATCH_DEVICES` were given
+    pub fn devices(&self) -> Result<Vec<String>, ApiError> {
+        let mut devices: Vec<String> = Vec::new();
+        for device_id in self.device_ids.iter().map(|device_id| device_id.trim()) {
+            if !device_id.is_empty() && !devices.iter().any(|device| device == device_id) {
+                devices.push(device_id.to_string());
+            }
+        }
+
+        if devices.is_empty() {
+            return Err(ApiError::InvalidQuery("device_ids is required".to_string()));
+        }
+        if devices.len() > MAX_BATCH_DEVICES {
+            return Err(ApiError::InvalidQuery(format!(
+                "At most {} devices can be read in one batch",
+                MAX_BATCH_DEVICES
+            )));
+        }
+        Ok(devices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_devices() {
+        let request: BatchReadRequest = serde_json::from_str(
+            r#"{ "device_ids": [" a", "b", "", "a "], "limit": 10, "where": "temperature>30" }"#,
+        )
+        .unwrap();
+        assert_eq!(request.devices().unwrap(), vec!["a", "b"]);
+        assert_eq!(request.filter.as_deref(), Some("temperature>30"));
+
+        let empty = BatchReadRequest { device_ids: vec![" ".to_string()], ..Default::default() };
+        assert!(matches!(empty.devices(), Err(ApiError::InvalidQuery(_))));
+
+        let too_many = BatchReadRequest {
+            device_ids: (0..=MAX_BATCH_DEVICES).map(|i| format!("sensor-{}", i)).collect(),
+            ..Default::default()
+        };
+        assert!(matches!(too_many.devices(), Err(ApiError::InvalidQuery(_))));
+    }
+}
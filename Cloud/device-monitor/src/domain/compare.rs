@@ -0,0 +1,165 @@
+// Multi-Device Comparison Domain Logic
+// 
+// This module defines the comparison of one metric across several devices.
+// Each device's samples are aggregated into the same time buckets and the
+// series are aligned on a shared list of bucket starts, so a chart can
+// overlay them without matching timestamps itself.
+
+use std::collections::BTreeSet;
+
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use crate::domain::aggregate::{aggregate_buckets, AggregateFn};
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Largest number of devices compared in one request
+pub const MAX_COMPARE_DEVICES: usize = 20;
+
+/// Parses the comma-separated `devices` query parameter
+/// 
+/// Blank entries and repeated devices are dropped, keeping the first occurrence.
+/// 
+/// # Arguments
+/// * `devices` - The device IDs, e.g. "sensor-001,sensor-002"
+/// 
+/// # Returns
+/// * `Result<Vec<String>, ApiError>` - The device IDs in the given order, or an invalid
+///   query error if none or more than `MAX_COMPARE_DEVICES` were given
+pub fn parse_devices(devices: &str) -> Result<Vec<String>, ApiError> {
+    let mut parsed: Vec<String> = Vec::new();
+    for device_id in devices.split(',').map(str::trim).filter(|device_id| !device_id.is_empty()) {
+        if !parsed.iter().any(|parsed| parsed == device_id) {
+            parsed.push(device_id.to_string());
+        }
+    }
+
+    if parsed.is_empty() {
+        return Err(ApiError::InvalidQuery("devices is required".to_string()));
+    }
+    if parsed.len() > MAX_COMPARE_DEVICES {
+        return Err(ApiError::InvalidQuery(format!(
+            "At most {} devices can be compared",
+            MAX_COMPARE_DEVICES
+        )));
+    }
+    Ok(parsed)
+}
+
+/// A device's series, aligned on the comparison's timestamps
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceSeries {
+    /// The device the series belongs to
+    pub device_id: String,
+    /// One aggregate per timestamp, or null where the device has no sample
+    pub values: Vec<Option<f64>>,
+}
+
+/// Result of comparing a metric across devices
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    /// The metric that was compared
+    pub metric: String,
+    /// The aggregation function applied within each bucket
+    #[serde(rename = "fn")]
+    pub function: AggregateFn,
+    /// Width of each bucket, in seconds
+    pub bucket_secs: i64,
+    /// The IANA time zone the buckets are aligned in
+    pub tz: String,
+    /// Start of every bucket in which any device has a sample, oldest first
+    pub timestamps: Vec<i64>,
+    /// One series per device, in the requested order
+    pub series: Vec<DeviceSeries>,
+}
+
+/// Aggregates a metric of several devices into aligned time buckets
+/// 
+/// # Arguments
+/// * `telemetry` - Each device's ID and telemetry records
+/// * `metric` - The metric name (e.g. "temperature")
+/// * `bucket_secs` - Width of each bucket, in seconds
+/// * `tz` - The time zone the buckets are aligned in
+/// * `function` - The aggregation function applied within each bucket
+/// 
+/// # Returns
+/// * `Comparison` - The shared timestamps and one value per timestamp for each device
+pub fn compare_series(
+    telemetry: &[(String, Vec<Telemetry>)],
+    metric: &str,
+    bucket_secs: i64,
+    tz: Tz,
+    function: AggregateFn,
+) -> Comparison {
+    let buckets: Vec<_> = telemetry
+        .iter()
+        .map(|(device_id, records)| (device_id, aggregate_buckets(records, metric, bucket_secs, tz, function)))
+        .collect();
+
+    let timestamps: Vec<i64> = buckets
+        .iter()
+        .flat_map(|(_, buckets)| buckets.iter().map(|bucket| bucket.start))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let series = buckets
+        .into_iter()
+        .map(|(device_id, buckets)| {
+            // Both lists are sorted, so each device's buckets are walked once
+            let mut buckets = buckets.into_iter().peekable();
+            let values = timestamps
+                .iter()
+                .map(|timestamp| buckets.next_if(|bucket| bucket.start == *timestamp).and_then(|bucket| bucket.value))
+                .collect();
+            DeviceSeries { device_id: device_id.clone(), values }
+        })
+        .collect();
+
+    Comparison {
+        metric: metric.to_string(),
+        function,
+        bucket_secs,
+        tz: tz.name().to_string(),
+        timestamps,
+        series,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(device_id: &str, timestamp: i64, temperature: &str) -> Telemetry {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        Telemetry::new(device_id.to_string(), data, timestamp)
+    }
+
+    #[test]
+    fn test_parse_devices() {
+        assert_eq!(parse_devices(" a, b,,a ,c").unwrap(), vec!["a", "b", "c"]);
+        assert!(matches!(parse_devices(" , "), Err(ApiError::InvalidQuery(_))));
+
+        let too_many: Vec<String> = (0..=MAX_COMPARE_DEVICES).map(|i| i.to_string()).collect();
+        assert!(matches!(parse_devices(&too_many.join(",")), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_compare_series_aligns_buckets() {
+        let telemetry = vec![
+            ("a".to_string(), vec![record("a", 0, "20"), record("a", 30, "22"), record("a", 120, "25")]),
+            ("b".to_string(), vec![record("b", 60, "18"), record("b", 130, "19")]),
+            ("c".to_string(), vec![]),
+        ];
+
+        let comparison = compare_series(&telemetry, "temperature", 60, Tz::UTC, AggregateFn::Avg);
+
+        assert_eq!(comparison.timestamps, vec![0, 60, 120]);
+        assert_eq!(comparison.series, vec![
+            DeviceSeries { device_id: "a".to_string(), values: vec![Some(21.0), None, Some(25.0)] },
+            DeviceSeries { device_id: "b".to_string(), values: vec![None, Some(18.0), Some(19.0)] },
+            DeviceSeries { device_id: "c".to_string(), values: vec![None, None, None] },
+        ]);
+    }
+}
@@ -0,0 +1,142 @@
+// Device Discovery Domain Model
+// 
+// This module defines the devices listed for discovery, e.g. by the
+// frontend's device picker. Devices are read from the device index that
+// the device-comms service maintains as it stores telemetry, rather than
+// by scanning the telemetry container. It also classifies a device's health
+// from how long ago it last reported.
+
+use serde::{Deserialize, Serialize};
+
+/// A device that has reported telemetry, and when it was last seen
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DeviceListing {
+    /// Unique identifier of the device
+    pub device_id: String,
+    /// Unix timestamp of the device's most recent reading or heartbeat
+    #[serde(alias = "timestamp")]
+    pub last_seen: i64,
+    /// Gateway that forwarded the most recent record, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_id: Option<String>,
+}
+
+/// A listed device with its status, as returned by the discovery endpoint
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DeviceSummary {
+    /// The device and when it was last seen
+    #[serde(flatten)]
+    pub device: DeviceListing,
+    /// The device's status, judged by when it was last seen
+    pub status: DeviceStatus,
+}
+
+/// Whether a device is reporting, judged by the age of its last telemetry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceStatus {
+    /// Reported within the stale threshold
+    Online,
+    /// Reported within the offline threshold, but not the stale one
+    Stale,
+    /// Has not reported within the offline threshold
+    Offline,
+}
+
+/// How long after its last telemetry a device becomes stale, then offline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusThresholds {
+    /// Seconds without telemetry after which a device is stale
+    pub stale_after_secs: i64,
+    /// Seconds without telemetry after which a device is offline
+    pub offline_after_secs: i64,
+}
+
+impl StatusThresholds {
+    /// Classifies a device by the age of its last telemetry
+    /// 
+    /// # Arguments
+    /// * `last_seen` - Unix timestamp of the device's latest record
+    /// * `now` - The current Unix timestamp
+    /// 
+    /// # Returns
+    /// * `DeviceStatus` - Online up to `stale_after_secs`, stale up to
+    ///   `offline_after_secs`, offline afterwards; records timestamped in the
+    ///   future count as online
+    pub fn classify(&self, last_seen: i64, now: i64) -> DeviceStatus {
+        let age = now - last_seen;
+        if age <= self.stale_after_secs {
+            DeviceStatus::Online
+        } else if age <= self.offline_after_secs {
+            DeviceStatus::Stale
+        } else {
+            DeviceStatus::Offline
+        }
+    }
+}
+
+/// The health of a device, as returned by the status endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceHealth {
+    /// Unique identifier of the device
+    pub device_id: String,
+    /// The device's status
+    pub status: DeviceStatus,
+    /// Unix timestamp of the device's latest record
+    pub last_seen: i64,
+    /// Seconds elapsed since the latest record
+    pub seconds_since_last_seen: i64,
+    /// The stale threshold applied, in seconds
+    pub stale_after_secs: i64,
+    /// The offline threshold applied, in seconds
+    pub offline_after_secs: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_listing_from_index_document() {
+        let document = serde_json::json!({
+            "id": "sensor-001",
+            "device_id": "sensor-001",
+            "timestamp": 1640995200,
+            "heartbeat": false,
+        });
+
+        let listing: DeviceListing = serde_json::from_value(document).unwrap();
+        assert_eq!(listing, DeviceListing {
+            device_id: "sensor-001".to_string(),
+            last_seen: 1640995200,
+            gateway_id: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&listing).unwrap(),
+            serde_json::json!({ "device_id": "sensor-001", "last_seen": 1640995200 })
+        );
+    }
+
+    #[test]
+    fn test_classify_status() {
+        let thresholds = StatusThresholds { stale_after_secs: 300, offline_after_secs: 3600 };
+        let now = 1640995200;
+
+        assert_eq!(thresholds.classify(now, now), DeviceStatus::Online);
+        assert_eq!(thresholds.classify(now + 10, now), DeviceStatus::Online);
+        assert_eq!(thresholds.classify(now - 300, now), DeviceStatus::Online);
+        assert_eq!(thresholds.classify(now - 301, now), DeviceStatus::Stale);
+        assert_eq!(thresholds.classify(now - 3600, now), DeviceStatus::Stale);
+        assert_eq!(thresholds.classify(now - 3601, now), DeviceStatus::Offline);
+        assert_eq!(serde_json::to_value(DeviceStatus::Stale).unwrap(), "stale");
+
+        let summary = DeviceSummary {
+            device: DeviceListing { device_id: "sensor-001".to_string(), last_seen: now - 400, gateway_id: None },
+            status: thresholds.classify(now - 400, now),
+        };
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap(),
+            serde_json::json!({ "device_id": "sensor-001", "last_seen": now - 400, "status": "stale" })
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// Telemetry Downsampling Domain Logic
+//
+// This module implements largest-triangle-three-buckets (LTTB) downsampling,
+// which reduces a long series of one metric to a requested number of points
+// while keeping its visual shape: peaks, troughs and trends survive, so a
+// chart of the reduced series looks like a chart of the raw one.
+
+use crate::domain::aggregate::metric_values;
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Smallest number of points a series can be reduced to (first, one, last)
+pub const MIN_POINTS: usize = 3;
+
+/// Largest number of points a series can be reduced to
+pub const MAX_POINTS: usize = 10_000;
+
+/// A requested reduction of the series of one metric
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Downsample {
+    /// The metric whose series is reduced
+    pub metric: String,
+    /// The number of points to keep
+    pub points: usize,
+}
+
+impl Downsample {
+    /// Builds a downsampling request from the `metric` and `points` query parameters
+    ///
+    /// # Arguments
+    /// * `metric` - The metric whose series to reduce
+    /// * `points` - The number of points to keep
+    ///
+    /// # Returns
+    /// * `Result<Option<Self>, ApiError>` - The request, None if `points` was not
+    ///   given, or an invalid query error
+    pub fn parse(metric: Option<&str>, points: Option<usize>) -> Result<Option<Self>, ApiError> {
+        let Some(points) = points else {
+            return Ok(None);
+        };
+
+        let metric = metric
+            .map(str::trim)
+            .filter(|metric| !metric.is_empty())
+            .ok_or_else(|| ApiError::InvalidQuery("points requires a metric".to_string()))?;
+        if !(MIN_POINTS..=MAX_POINTS).contains(&points) {
+            return Err(ApiError::InvalidQuery(format!(
+                "points must be between {} and {}",
+                MIN_POINTS, MAX_POINTS
+            )));
+        }
+
+        Ok(Some(Downsample { metric: metric.to_string(), points }))
+    }
+
+    /// Reduces telemetry records to those selected by LTTB on the metric
+    ///
+    /// Records without a timestamp or a numeric value for the metric are
+    /// dropped, since they are not part of the series. The records must be
+    /// ordered by timestamp.
+    ///
+    /// # Arguments
+    /// * `telemetry` - The telemetry records, oldest first
+    ///
+    /// # Returns
+    /// * `Vec<Telemetry>` - At most `points` records, oldest first
+    pub fn apply(&self, telemetry: Vec<Telemetry>) -> Vec<Telemetry> {
+        let (records, series): (Vec<Telemetry>, Vec<(f64, f64)>) = telemetry
+            .into_iter()
+            .filter_map(|record| {
+                let timestamp = record.timestamp?;
+                let value = metric_values(std::slice::from_ref(&record), &self.metric).pop()?;
+                Some((record, (timestamp as f64, value)))
+            })
+            .unzip();
+
+        let mut keep = lttb(&series, self.points).into_iter().peekable();
+        records
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| keep.next_if_eq(index).is_some())
+            .map(|(_, record)| record)
+            .collect()
+    }
+}
+
+/// Selects the points of a series to keep with largest-triangle-three-buckets
+///
+/// The first and last points are always kept. The points in between are
+/// split into `threshold - 2` buckets, and from each bucket the point
+/// forming the largest triangle with the previously kept point and the
+/// average of the next bucket is kept.
+///
+/// # Arguments
+/// * `data` - The series as (x, y) pairs, ordered by x
+/// * `threshold` - The number of points to keep
+///
+/// # Returns
+/// * `Vec<usize>` - The indices of the kept points, ascending; every index if
+///   the series already has at most `threshold` points
+pub fn lttb(data: &[(f64, f64)], threshold: usize) -> Vec<usize> {
+    let len = data.len();
+    if threshold >= len || threshold < MIN_POINTS {
+        return (0..len).collect();
+    }
+
+    let every = (len - 2) as f64 / (threshold - 2) as f64;
+    let bucket_start = |bucket: usize| ((bucket as f64 * every).floor() as usize + 1).min(len - 1);
+
+    let mut kept = Vec::with_capacity(threshold);
+    let mut previous = 0;
+    kept.push(previous);
+
+    for bucket in 0..threshold - 2 {
+        // Average of the next bucket, or the last point after the final bucket
+        let (start, end) = (bucket_start(bucket + 1), bucket_start(bucket + 2));
+        let next = &data[start..end.max(start + 1)];
+        let avg_x = next.iter().map(|point| point.0).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|point| point.1).sum::<f64>() / next.len() as f64;
+
+        let (ax, ay) = data[previous];
+        let mut max_area = -1.0;
+        let (start, end) = (bucket_start(bucket), bucket_start(bucket + 1));
+        for (index, &(bx, by)) in data.iter().enumerate().take(end).skip(start) {
+            let area = ((ax - avg_x) * (by - ay) - (ax - bx) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                previous = index;
+            }
+        }
+        kept.push(previous);
+    }
+
+    kept.push(len - 1);
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lttb_keeps_endpoints_and_peaks() {
+        // A flat series with a single spike in the middle
+        let data: Vec<(f64, f64)> = (0..100)
+            .map(|x| (x as f64, if x == 57 { 100.0 } else { 1.0 }))
+            .collect();
+
+        let kept = lttb(&data, 10);
+
+        assert_eq!(kept.len(), 10);
+        assert_eq!(kept.first(), Some(&0));
+        assert_eq!(kept.last(), Some(&99));
+        assert!(kept.contains(&57));
+        assert!(kept.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_lttb_short_series_is_unchanged() {
+        let data = [(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)];
+
+        assert_eq!(lttb(&data, 3), vec![0, 1, 2]);
+        assert_eq!(lttb(&data, 500), vec![0, 1, 2]);
+        assert!(lttb(&[], 500).is_empty());
+    }
+
+    #[test]
+    fn test_downsample_parse_and_apply() {
+        assert_eq!(Downsample::parse(Some("temperature"), None).unwrap(), None);
+        assert!(matches!(Downsample::parse(None, Some(500)), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(Downsample::parse(Some("temperature"), Some(2)), Err(ApiError::InvalidQuery(_))));
+
+        let downsample = Downsample::parse(Some("temperature"), Some(3)).unwrap().unwrap();
+        let telemetry = (0..10)
+            .map(|second| {
+                let value = if second == 4 { "n/a".to_string() } else { (second * second).to_string() };
+                Telemetry::new("sensor-001".to_string(), [("temperature".to_string(), value)].into(), second)
+            })
+            .collect();
+
+        let reduced = downsample.apply(telemetry);
+        let timestamps: Vec<Option<i64>> = reduced.iter().map(|record| record.timestamp).collect();
+        assert_eq!(timestamps.len(), 3);
+        assert_eq!(timestamps.first(), Some(&Some(0)));
+        assert_eq!(timestamps.last(), Some(&Some(9)));
+        assert!(!timestamps.contains(&Some(4)));
+    }
+}
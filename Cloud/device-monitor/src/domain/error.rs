@@ -6,6 +6,8 @@
 use std::fmt;
 use rocket::http::Status;
 
+use crate::domain::auth::AuthError;
+
 /// API error types that can occur during request processing
 /// 
 /// These errors are mapped to appropriate HTTP status codes and
@@ -22,13 +24,41 @@ pub enum ApiError {
     /// Individual telemetry value is invalid or empty
     InvalidTelemetryValue(String),
 
+    // Query errors
+    /// Query parameters are missing or invalid
+    InvalidQuery(String),
+
+    // Annotation errors
+    /// Annotation request failed validation
+    InvalidAnnotation(String),
+    /// Requested annotation does not exist for the device
+    AnnotationNotFound(String),
+
+    // Alert errors
+    /// Alert rule request failed validation
+    InvalidAlertRule(String),
+    /// Requested alert rule does not exist for the device
+    AlertRuleNotFound(String),
+    /// Requested alert does not exist for the device
+    AlertNotFound(String),
+    /// The alert cannot be acknowledged or resolved in its current status
+    AlertConflict(String),
+
     // Database errors
     /// Generic database operation error with details
     DatabaseError(String),
 
+    // Export errors
+    /// Telemetry could not be encoded in the requested export format
+    ExportError(String),
+
     // Resource errors
     /// Requested device telemetry not found in database
     DeviceNotFound(String),
+
+    // Authorization errors
+    /// The caller's credentials do not cover the requested device
+    DeviceForbidden(String),
 }
 
 impl fmt::Display for ApiError {
@@ -38,8 +68,17 @@ impl fmt::Display for ApiError {
             ApiError::InvalidTimestamp => write!(f, "Invalid timestamp format"),
             ApiError::EmptyTelemetryData => write!(f, "Telemetry data cannot be empty"),
             ApiError::InvalidTelemetryValue(msg) => write!(f, "Invalid telemetry value: {}", msg),
+            ApiError::InvalidQuery(msg) => write!(f, "Invalid query: {}", msg),
+            ApiError::InvalidAnnotation(msg) => write!(f, "Invalid annotation: {}", msg),
+            ApiError::AnnotationNotFound(id) => write!(f, "Annotation not found: {}", id),
+            ApiError::InvalidAlertRule(msg) => write!(f, "Invalid alert rule: {}", msg),
+            ApiError::AlertRuleNotFound(id) => write!(f, "Alert rule not found: {}", id),
+            ApiError::AlertNotFound(id) => write!(f, "Alert not found: {}", id),
+            ApiError::AlertConflict(msg) => write!(f, "Alert conflict: {}", msg),
             ApiError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+            ApiError::ExportError(msg) => write!(f, "Export error: {}", msg),
             ApiError::DeviceNotFound(device_id) => write!(f, "No telemetry found for device {}", device_id),
+            ApiError::DeviceForbidden(device_id) => write!(f, "Access to device {} is not permitted", device_id),
         }
     }
 }
@@ -52,7 +91,9 @@ impl std::error::Error for ApiError {}
 /// HTTP status codes for proper REST API error handling:
 /// - Validation errors -> 400 Bad Request
 /// - Not found errors -> 404 Not Found  
-/// - Database errors -> 500 Internal Server Error
+/// - Actions the resource's current state does not allow -> 409 Conflict
+/// - Devices outside the caller's scope -> 403 Forbidden
+/// - Database and export errors -> 500 Internal Server Error
 impl From<ApiError> for rocket::http::Status {
     fn from(error: ApiError) -> Self {
         match error {
@@ -60,13 +101,44 @@ impl From<ApiError> for rocket::http::Status {
             ApiError::InvalidDeviceId | 
             ApiError::InvalidTimestamp | 
             ApiError::EmptyTelemetryData | 
-            ApiError::InvalidTelemetryValue(_) => Status::BadRequest,
+            ApiError::InvalidTelemetryValue(_) |
+            ApiError::InvalidQuery(_) |
+            ApiError::InvalidAnnotation(_) |
+            ApiError::InvalidAlertRule(_) => Status::BadRequest,
             
             // Not found errors (4xx) - resource doesn't exist
-            ApiError::DeviceNotFound(_) => Status::NotFound,
+            ApiError::DeviceNotFound(_) |
+            ApiError::AnnotationNotFound(_) |
+            ApiError::AlertRuleNotFound(_) |
+            ApiError::AlertNotFound(_) => Status::NotFound,
+
+            // Conflict errors (4xx) - the resource's state does not allow the action
+            ApiError::AlertConflict(_) => Status::Conflict,
+
+            // Authorization errors (4xx) - device outside the caller's scope
+            ApiError::DeviceForbidden(_) => Status::Forbidden,
             
             // Server errors (5xx) - internal processing failure
-            ApiError::DatabaseError(_) => Status::InternalServerError,
+            ApiError::DatabaseError(_) |
+            ApiError::ExportError(_) => Status::InternalServerError,
+        }
+    }
+}
+
+/// Converts authentication errors to appropriate HTTP status codes
+/// 
+/// - Missing or invalid credentials -> 401 Unauthorized
+/// - Authenticated caller outside the device scope -> 403 Forbidden
+impl From<&AuthError> for Status {
+    fn from(error: &AuthError) -> Self {
+        match error {
+            AuthError::MissingCredentials |
+            AuthError::InvalidToken(_) |
+            AuthError::InvalidApiKey => Status::Unauthorized,
+
+            AuthError::DeviceNotPermitted(_) |
+            AuthError::FleetAccessRequired |
+            AuthError::OperatorRequired => Status::Forbidden,
         }
     }
 }
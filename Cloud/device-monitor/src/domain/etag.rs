@@ -0,0 +1,76 @@
+// Telemetry Entity Tag Domain Logic
+// 
+// This module defines the weak entity tags of telemetry responses, which
+// let auto-refreshing dashboards revalidate a read with `If-None-Match`
+// instead of downloading unchanged telemetry again. A device's tag is
+// derived from the timestamp of its latest record, so it changes whenever
+// the device reports.
+
+use std::fmt;
+
+/// A weak entity tag of a device's telemetry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityTag {
+    /// The opaque tag, without the `W/` prefix and quotes
+    opaque: String,
+}
+
+impl EntityTag {
+    /// Builds the tag of a device's telemetry from its latest record
+    /// 
+    /// # Arguments
+    /// * `latest_timestamp` - Unix timestamp of the device's most recent record
+    /// 
+    /// # Returns
+    /// * `Self` - The weak entity tag
+    pub fn from_latest(latest_timestamp: i64) -> Self {
+        EntityTag { opaque: format!("t{}", latest_timestamp) }
+    }
+
+    /// Returns whether an `If-None-Match` header value matches this tag
+    /// 
+    /// Tags are compared weakly, ignoring any `W/` prefix, and `*` matches
+    /// every tag.
+    /// 
+    /// # Arguments
+    /// * `if_none_match` - The header value, e.g. `W/"t1640995260", W/"t1640995200"`
+    /// 
+    /// # Returns
+    /// * `bool` - True if the client already holds the current representation
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        if_none_match.split(',').map(str::trim).any(|tag| {
+            tag == "*"
+                || tag
+                    .trim_start_matches("W/")
+                    .strip_prefix('"')
+                    .and_then(|tag| tag.strip_suffix('"'))
+                    .is_some_and(|opaque| opaque == self.opaque)
+        })
+    }
+}
+
+impl fmt::Display for EntityTag {
+    /// Formats the tag as the value of an `ETag` header
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "W/\"{}\"", self.opaque)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_tag_matching() {
+        let etag = EntityTag::from_latest(1640995260);
+        assert_eq!(etag.to_string(), "W/\"t1640995260\"");
+
+        assert!(etag.matches(&etag.to_string()));
+        assert!(etag.matches("\"t1640995260\""));
+        assert!(etag.matches("W/\"t1640995200\", W/\"t1640995260\""));
+        assert!(etag.matches("*"));
+        assert!(!etag.matches("W/\"t1640995200\""));
+        assert!(!etag.matches("t1640995260"));
+        assert!(!etag.matches(""));
+    }
+}
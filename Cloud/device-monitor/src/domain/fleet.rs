@@ -0,0 +1,160 @@
+// Fleet Summary Domain Logic
+// 
+// This module defines the fleet overview: how many devices there are, how
+// many reported recently, and fleet-wide statistics of each metric in
+// their latest records, so an overview dashboard needs a single call.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::domain::aggregate::{metric_values, AggregateFn};
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Default window, in minutes, within which a device counts as reporting
+pub const DEFAULT_WINDOW_MINUTES: i64 = 15;
+
+/// Largest accepted window, in minutes (one day)
+pub const MAX_WINDOW_MINUTES: i64 = 24 * 60;
+
+/// Validates the `minutes` query parameter
+/// 
+/// # Arguments
+/// * `minutes` - The requested window, in minutes
+/// 
+/// # Returns
+/// * `Result<i64, ApiError>` - The window, `DEFAULT_WINDOW_MINUTES` if not given, or an
+///   invalid query error if it is not between 1 and `MAX_WINDOW_MINUTES`
+pub fn parse_window(minutes: Option<i64>) -> Result<i64, ApiError> {
+    let minutes = minutes.unwrap_or(DEFAULT_WINDOW_MINUTES);
+    if !(1..=MAX_WINDOW_MINUTES).contains(&minutes) {
+        return Err(ApiError::InvalidQuery(format!(
+            "minutes must be between 1 and {}",
+            MAX_WINDOW_MINUTES
+        )));
+    }
+    Ok(minutes)
+}
+
+/// Fleet-wide statistics of one metric
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricSummary {
+    /// The metric name (e.g. "temperature")
+    pub metric: String,
+    /// Smallest sample across the fleet
+    pub min: Option<f64>,
+    /// Largest sample across the fleet
+    pub max: Option<f64>,
+    /// Mean of all samples across the fleet
+    pub avg: Option<f64>,
+    /// Number of samples
+    pub samples: usize,
+    /// Number of devices that reported the metric
+    pub devices: usize,
+}
+
+/// Overview of the whole fleet
+#[derive(Debug, Clone, Serialize)]
+pub struct FleetSummary {
+    /// Number of devices that have ever reported telemetry
+    pub devices: usize,
+    /// Number of devices seen within the window
+    pub reporting_devices: usize,
+    /// The window, in minutes
+    pub window_minutes: i64,
+    /// Unix timestamp the window ends at, i.e. when the summary was computed
+    pub as_of: i64,
+    /// Statistics of each numeric metric in the reporting devices' latest records, sorted by name
+    pub metrics: Vec<MetricSummary>,
+}
+
+/// Computes fleet-wide statistics of every numeric metric
+/// 
+/// Non-numeric values are skipped; a metric without any numeric sample is
+/// left out.
+/// 
+/// # Arguments
+/// * `telemetry` - The telemetry records of each device
+/// 
+/// # Returns
+/// * `Vec<MetricSummary>` - One summary per metric, sorted by name
+pub fn summarize_metrics(telemetry: &[Vec<Telemetry>]) -> Vec<MetricSummary> {
+    let mut samples: BTreeMap<&str, (Vec<f64>, usize)> = BTreeMap::new();
+    for records in telemetry {
+        let metrics: BTreeSet<&str> = records
+            .iter()
+            .flat_map(|record| record.telemetry_data.keys().map(String::as_str))
+            .collect();
+        for metric in metrics {
+            let values = metric_values(records, metric);
+            if values.is_empty() {
+                continue;
+            }
+            let (all, devices) = samples.entry(metric).or_default();
+            all.extend(values);
+            *devices += 1;
+        }
+    }
+
+    samples
+        .into_iter()
+        .map(|(metric, (values, devices))| MetricSummary {
+            metric: metric.to_string(),
+            min: AggregateFn::Min.apply(&values),
+            max: AggregateFn::Max.apply(&values),
+            avg: AggregateFn::Avg.apply(&values),
+            samples: values.len(),
+            devices,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(device_id: &str, data: &[(&str, &str)]) -> Telemetry {
+        let data = data.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        Telemetry::new(device_id.to_string(), data, 1640995200)
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window(None).unwrap(), DEFAULT_WINDOW_MINUTES);
+        assert_eq!(parse_window(Some(60)).unwrap(), 60);
+        assert!(matches!(parse_window(Some(0)), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(parse_window(Some(MAX_WINDOW_MINUTES + 1)), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_summarize_metrics() {
+        let telemetry = vec![
+            vec![
+                record("sensor-001", &[("temperature", "20"), ("status", "online")]),
+                record("sensor-001", &[("temperature", "22")]),
+            ],
+            vec![record("sensor-002", &[("temperature", "27"), ("voltage", "3.3")])],
+        ];
+
+        let metrics = summarize_metrics(&telemetry);
+        assert_eq!(metrics, vec![
+            MetricSummary {
+                metric: "temperature".to_string(),
+                min: Some(20.0),
+                max: Some(27.0),
+                avg: Some(23.0),
+                samples: 3,
+                devices: 2,
+            },
+            MetricSummary {
+                metric: "voltage".to_string(),
+                min: Some(3.3),
+                max: Some(3.3),
+                avg: Some(3.3),
+                samples: 1,
+                devices: 1,
+            },
+        ]);
+    }
+}
@@ -0,0 +1,165 @@
+// Telemetry Gap Detection Domain Logic
+// 
+// This module finds the time ranges in which a device sent no telemetry
+// for longer than its expected reporting interval, so operators can
+// quantify connectivity dropouts rather than infer them from sparse charts.
+// Timestamps are observed one at a time, oldest first, so a long history
+// never has to be held in memory.
+
+use serde::Serialize;
+
+use crate::domain::aggregate::parse_bucket;
+use crate::domain::error::ApiError;
+
+/// Parses the expected reporting interval, such as "30s", "5m" or "1h"
+/// 
+/// # Arguments
+/// * `interval` - A positive whole number followed by `s`, `m`, `h` or `d`
+/// 
+/// # Returns
+/// * `Result<i64, ApiError>` - The interval in seconds or an invalid query error
+pub fn parse_interval(interval: &str) -> Result<i64, ApiError> {
+    parse_bucket(interval).map_err(|_| {
+        ApiError::InvalidQuery(format!(
+            "Invalid interval: {} (expected e.g. 30s, 5m or 1h, up to 31 days)",
+            interval
+        ))
+    })
+}
+
+/// A time range in which a device sent no telemetry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Gap {
+    /// Timestamp of the last record before the gap, or the start of the requested range
+    pub start: i64,
+    /// Timestamp of the first record after the gap, or the end of the requested range
+    pub end: i64,
+    /// Length of the gap, in seconds
+    pub duration_secs: i64,
+}
+
+/// Gaps in a device's telemetry over a time range
+#[derive(Debug, Clone, Serialize)]
+pub struct GapReport {
+    /// The device whose telemetry was checked
+    pub device_id: String,
+    /// The expected reporting interval, in seconds
+    pub interval_secs: i64,
+    /// Start of the checked range, or null if it starts at the device's first record
+    pub from: Option<i64>,
+    /// End of the checked range
+    pub to: i64,
+    /// Number of records in the range
+    pub records: usize,
+    /// Total length of all gaps, in seconds
+    pub total_gap_secs: i64,
+    /// Length of the longest gap, in seconds
+    pub longest_gap_secs: i64,
+    /// The gaps, oldest first
+    pub gaps: Vec<Gap>,
+}
+
+/// Finds gaps while a device's timestamps are observed, oldest first
+#[derive(Debug)]
+pub struct GapDetector {
+    interval_secs: i64,
+    /// The previous timestamp, or the start of the range before any record
+    last: Option<i64>,
+    records: usize,
+    gaps: Vec<Gap>,
+}
+
+impl GapDetector {
+    /// Creates a detector for a range
+    /// 
+    /// # Arguments
+    /// * `interval_secs` - Silences longer than this are gaps
+    /// * `from` - Start of the range, so a silence before the first record counts as a gap
+    pub fn new(interval_secs: i64, from: Option<i64>) -> Self {
+        GapDetector { interval_secs, last: from, records: 0, gaps: Vec::new() }
+    }
+
+    /// Records the gap between `start` and `end` if it is longer than the interval
+    fn check(&mut self, start: i64, end: i64) {
+        if end - start > self.interval_secs {
+            self.gaps.push(Gap { start, end, duration_secs: end - start });
+        }
+    }
+
+    /// Observes the next timestamp, which must not be older than the previous one
+    pub fn observe(&mut self, timestamp: i64) {
+        if let Some(last) = self.last {
+            self.check(last, timestamp);
+        }
+        self.last = Some(timestamp);
+        self.records += 1;
+    }
+
+    /// Returns the number of timestamps observed
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Finishes the report, counting a silence up to the end of the range as a gap
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device whose telemetry was observed
+    /// * `from` - Start of the range, as given to `new`
+    /// * `to` - End of the range
+    pub fn finish(mut self, device_id: &str, from: Option<i64>, to: i64) -> GapReport {
+        if let Some(last) = self.last {
+            self.check(last, to);
+        }
+
+        GapReport {
+            device_id: device_id.to_string(),
+            interval_secs: self.interval_secs,
+            from,
+            to,
+            records: self.records,
+            total_gap_secs: self.gaps.iter().map(|gap| gap.duration_secs).sum(),
+            longest_gap_secs: self.gaps.iter().map(|gap| gap.duration_secs).max().unwrap_or(0),
+            gaps: self.gaps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("5m").unwrap(), 300);
+        assert!(matches!(parse_interval("5"), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_gaps_within_range() {
+        let mut detector = GapDetector::new(60, Some(0));
+        for timestamp in [30, 90, 150, 600, 660] {
+            detector.observe(timestamp);
+        }
+
+        let report = detector.finish("sensor-001", Some(0), 1000);
+
+        assert_eq!(report.records, 5);
+        assert_eq!(report.gaps, vec![
+            Gap { start: 150, end: 600, duration_secs: 450 },
+            Gap { start: 660, end: 1000, duration_secs: 340 },
+        ]);
+        assert_eq!(report.total_gap_secs, 790);
+        assert_eq!(report.longest_gap_secs, 450);
+    }
+
+    #[test]
+    fn test_gaps_without_records() {
+        let report = GapDetector::new(60, Some(100)).finish("sensor-001", Some(100), 400);
+        assert_eq!(report.gaps, vec![Gap { start: 100, end: 400, duration_secs: 300 }]);
+
+        // Without a start, nothing is known before the first record
+        let mut detector = GapDetector::new(60, None);
+        detector.observe(500);
+        assert!(detector.finish("sensor-001", None, 530).gaps.is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+// Fleet Health Domain Logic
+// 
+// This module scores the health of each device from 0 to 100 by combining
+// how recently it reported, the error counters in its latest record and the
+// threshold alerts it raised recently, and ranks the fleet worst first, so
+// operators get a single prioritized list of the devices needing attention.
+
+use serde::Serialize;
+use tracing::warn;
+
+use crate::domain::device::{DeviceListing, DeviceStatus, StatusThresholds};
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Default window, in hours, within which raised alerts count as breaches
+pub const DEFAULT_BREACH_WINDOW_HOURS: i64 = 24;
+
+/// Largest accepted breach window, in hours (one week)
+pub const MAX_BREACH_WINDOW_HOURS: i64 = 7 * 24;
+
+/// Score of a device without any problem
+pub const MAX_SCORE: u32 = 100;
+
+/// Points lost by a stale device
+const STALE_PENALTY: u32 = 30;
+
+/// Points lost by an offline device
+const OFFLINE_PENALTY: u32 = 60;
+
+/// Points lost per error counted in the latest record
+const ERROR_PENALTY: u32 = 5;
+
+/// Most points lost to errors
+const MAX_ERROR_PENALTY: u32 = 30;
+
+/// Points lost per threshold breach within the window
+const BREACH_PENALTY: u32 = 10;
+
+/// Most points lost to threshold breaches
+const MAX_BREACH_PENALTY: u32 = 40;
+
+/// Metrics read as error counters when `HEALTH_ERROR_METRICS` is unset
+const DEFAULT_ERROR_METRICS: [&str; 2] = ["errors", "error_count"];
+
+/// Validates the `hours` query parameter
+/// 
+/// # Arguments
+/// * `hours` - The requested breach window, in hours
+/// 
+/// # Returns
+/// * `Result<i64, ApiError>` - The window, `DEFAULT_BREACH_WINDOW_HOURS` if not given, or an
+///   invalid query error if it is not between 1 and `MAX_BREACH_WINDOW_HOURS`
+pub fn parse_breach_window(hours: Option<i64>) -> Result<i64, ApiError> {
+    let hours = hours.unwrap_or(DEFAULT_BREACH_WINDOW_HOURS);
+    if !(1..=MAX_BREACH_WINDOW_HOURS).contains(&hours) {
+        return Err(ApiError::InvalidQuery(format!(
+            "hours must be between 1 and {}",
+            MAX_BREACH_WINDOW_HOURS
+        )));
+    }
+    Ok(hours)
+}
+
+/// The metrics devices report their error counters in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorMetrics {
+    metrics: Vec<String>,
+}
+
+impl Default for ErrorMetrics {
+    fn default() -> Self {
+        ErrorMetrics::new(DEFAULT_ERROR_METRICS.iter().map(|metric| metric.to_string()).collect())
+    }
+}
+
+impl ErrorMetrics {
+    /// Creates a new set of error counter metrics
+    /// 
+    /// # Arguments
+    /// * `metrics` - The names of the metrics holding error counts
+    /// 
+    /// # Returns
+    /// * `Self` - A new ErrorMetrics instance
+    pub fn new(metrics: Vec<String>) -> Self {
+        ErrorMetrics { metrics }
+    }
+
+    /// Loads the error counter metrics from the `HEALTH_ERROR_METRICS` environment variable
+    /// 
+    /// The variable holds comma-separated metric names. When it is unset or
+    /// names no metric, `errors` and `error_count` are used.
+    pub fn from_env() -> Self {
+        let Ok(list) = std::env::var("HEALTH_ERROR_METRICS") else {
+            return Self::default();
+        };
+
+        let metrics: Vec<String> = list
+            .split(',')
+            .map(str::trim)
+            .filter(|metric| !metric.is_empty())
+            .map(str::to_string)
+            .collect();
+        if metrics.is_empty() {
+            warn!("Ignoring empty HEALTH_ERROR_METRICS");
+            return Self::default();
+        }
+        ErrorMetrics::new(metrics)
+    }
+
+    /// Counts the errors a telemetry record reports
+    /// 
+    /// Values that are missing, not numbers or negative count as no errors.
+    /// 
+    /// # Arguments
+    /// * `record` - The telemetry record
+    /// 
+    /// # Returns
+    /// * `f64` - The sum of the record's error counters
+    pub fn count(&self, record: &Telemetry) -> f64 {
+        self.metrics
+            .iter()
+            .filter_map(|metric| record.telemetry_data.get(metric))
+            .filter_map(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| value.is_finite() && *value > 0.0)
+            .sum()
+    }
+}
+
+/// The health score of one device and what it is based on
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceScore {
+    /// Unique identifier of the device
+    pub device_id: String,
+    /// Health score from 0 (needs attention first) to 100 (no problem found)
+    pub score: u32,
+    /// The device's status, judged by the age of its last telemetry
+    pub status: DeviceStatus,
+    /// Unix timestamp of the device's most recent reading or heartbeat
+    pub last_seen: i64,
+    /// Sum of the error counters in the device's latest record
+    pub errors: f64,
+    /// Number of alerts the device raised within the breach window
+    pub breaches: usize,
+}
+
+/// Scores the health of a device
+/// 
+/// The score starts at 100 and loses 30 points if the device is stale or 60
+/// if it is offline, 5 points per error counted in its latest record (at
+/// most 30), and 10 points per threshold breach (at most 40), down to 0.
+/// 
+/// # Arguments
+/// * `device` - The device's entry in the device index
+/// * `latest` - The device's latest telemetry record, if any
+/// * `breaches` - The number of alerts the device raised within the window
+/// * `thresholds` - The thresholds classifying the device's status
+/// * `error_metrics` - The metrics holding error counters
+/// * `now` - The current Unix timestamp
+/// 
+/// # Returns
+/// * `DeviceScore` - The device's score and its inputs
+pub fn score_device(
+    device: &DeviceListing,
+    latest: Option<&Telemetry>,
+    breaches: usize,
+    thresholds: &StatusThresholds,
+    error_metrics: &ErrorMetrics,
+    now: i64,
+) -> DeviceScore {
+    let status = thresholds.classify(device.last_seen, now);
+    let errors = latest.map(|record| error_metrics.count(record)).unwrap_or_default();
+
+    let recency_penalty = match status {
+        DeviceStatus::Online => 0,
+        DeviceStatus::Stale => STALE_PENALTY,
+        DeviceStatus::Offline => OFFLINE_PENALTY,
+    };
+    // Saturating float-to-int casts, so huge counters simply hit the cap
+    let error_penalty = (errors.ceil() as u32).saturating_mul(ERROR_PENALTY).min(MAX_ERROR_PENALTY);
+    let breach_penalty = (breaches as u32).saturating_mul(BREACH_PENALTY).min(MAX_BREACH_PENALTY);
+
+    DeviceScore {
+        device_id: device.device_id.clone(),
+        score: MAX_SCORE.saturating_sub(recency_penalty + error_penalty + breach_penalty),
+        status,
+        last_seen: device.last_seen,
+        errors,
+        breaches,
+    }
+}
+
+/// Health of the whole fleet
+#[derive(Debug, Clone, Serialize)]
+pub struct FleetHealth {
+    /// Mean score of all devices, or None if the fleet is empty
+    pub score: Option<u32>,
+    /// Number of devices scored
+    pub devices: usize,
+    /// Number of devices scoring below 100
+    pub devices_needing_attention: usize,
+    /// The breach window, in hours
+    pub breach_window_hours: i64,
+    /// Unix timestamp the scores were computed at
+    pub as_of: i64,
+    /// The score of each device, lowest first
+    pub scores: Vec<DeviceScore>,
+}
+
+/// Ranks device scores into the fleet's health
+/// 
+/// # Arguments
+/// * `scores` - The score of each device
+/// * `breach_window_hours` - The breach window the scores were computed over
+/// * `as_of` - Unix timestamp the scores were computed at
+/// 
+/// # Returns
+/// * `FleetHealth` - The fleet score and the devices sorted by score, then device id
+pub fn rank_fleet(mut scores: Vec<DeviceScore>, breach_window_hours: i64, as_of: i64) -> FleetHealth {
+    scores.sort_by(|a, b| a.score.cmp(&b.score).then_with(|| a.device_id.cmp(&b.device_id)));
+
+    let total: u64 = scores.iter().map(|device| u64::from(device.score)).sum();
+    let score = (!scores.is_empty()).then(|| (total as f64 / scores.len() as f64).round() as u32);
+
+    FleetHealth {
+        score,
+        devices: scores.len(),
+        devices_needing_attention: scores.iter().filter(|device| device.score < MAX_SCORE).count(),
+        breach_window_hours,
+        as_of,
+        scores,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1640995200;
+    const THRESHOLDS: StatusThresholds = StatusThresholds { stale_after_secs: 300, offline_after_secs: 3600 };
+
+    fn device(device_id: &str, last_seen: i64) -> DeviceListing {
+        DeviceListing { device_id: device_id.to_string(), last_seen, gateway_id: None }
+    }
+
+    fn record(data: &[(&str, &str)]) -> Telemetry {
+        let data = data.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        Telemetry::new("sensor-001".to_string(), data, NOW)
+    }
+
+    #[test]
+    fn test_parse_breach_window() {
+        assert_eq!(parse_breach_window(None).unwrap(), DEFAULT_BREACH_WINDOW_HOURS);
+        assert_eq!(parse_breach_window(Some(1)).unwrap(), 1);
+        assert!(matches!(parse_breach_window(Some(0)), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(parse_breach_window(Some(MAX_BREACH_WINDOW_HOURS + 1)), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_count_errors() {
+        let metrics = ErrorMetrics::default();
+        assert_eq!(metrics.count(&record(&[("errors", "2"), ("error_count", " 1 "), ("temperature", "21")])), 3.0);
+        assert_eq!(metrics.count(&record(&[("errors", "none"), ("error_count", "-4")])), 0.0);
+        assert_eq!(ErrorMetrics::new(vec!["faults".to_string()]).count(&record(&[("faults", "1.5")])), 1.5);
+    }
+
+    #[test]
+    fn test_score_device() {
+        let metrics = ErrorMetrics::default();
+
+        let healthy = score_device(&device("sensor-001", NOW), Some(&record(&[("errors", "0")])), 0, &THRESHOLDS, &metrics, NOW);
+        assert_eq!((healthy.score, healthy.status), (MAX_SCORE, DeviceStatus::Online));
+
+        let stale = score_device(&device("sensor-002", NOW - 600), Some(&record(&[("errors", "2")])), 1, &THRESHOLDS, &metrics, NOW);
+        assert_eq!(stale.score, 100 - 30 - 10 - 10);
+        assert_eq!((stale.errors, stale.breaches), (2.0, 1));
+
+        // Penalties are capped, and the score does not go below zero
+        let failing = score_device(&device("sensor-003", NOW - 7200), Some(&record(&[("errors", "1e9")])), 50, &THRESHOLDS, &metrics, NOW);
+        assert_eq!((failing.score, failing.status), (0, DeviceStatus::Offline));
+        let silent = score_device(&device("sensor-004", NOW - 7200), None, 0, &THRESHOLDS, &metrics, NOW);
+        assert_eq!(silent.score, 40);
+    }
+
+    #[test]
+    fn test_rank_fleet() {
+        let metrics = ErrorMetrics::default();
+        let scores = vec![
+            score_device(&device("sensor-b", NOW), None, 0, &THRESHOLDS, &metrics, NOW),
+            score_device(&device("sensor-c", NOW - 600), None, 0, &THRESHOLDS, &metrics, NOW),
+            score_device(&device("sensor-a", NOW), None, 0, &THRESHOLDS, &metrics, NOW),
+        ];
+
+        let health = rank_fleet(scores, 24, NOW);
+        let ranked: Vec<&str> = health.scores.iter().map(|device| device.device_id.as_str()).collect();
+        assert_eq!(ranked, ["sensor-c", "sensor-a", "sensor-b"]);
+        assert_eq!(health.score, Some(90));
+        assert_eq!((health.devices, health.devices_needing_attention), (3, 1));
+        assert_eq!(rank_fleet(Vec::new(), 24, NOW).score, None);
+    }
+}
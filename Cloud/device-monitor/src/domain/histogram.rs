@@ -0,0 +1,197 @@
+// Metric Histogram Domain Logic
+// 
+// This module computes the distribution of a metric's values as a
+// histogram, so sensor behaviour that time-series charts hide, such as a
+// reading that flips between two levels, shows up as separate peaks.
+
+use serde::Serialize;
+
+use crate::domain::error::ApiError;
+
+/// Number of bins when neither a bin width nor a bin count is given
+pub const DEFAULT_BINS: usize = 20;
+
+/// Largest number of bins a histogram may have
+pub const MAX_BINS: usize = 1000;
+
+/// How the range of values is divided into bins
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Binning {
+    /// Bins of a fixed width, aligned to multiples of the width
+    Width(f64),
+    /// A fixed number of equal bins spanning the smallest to the largest value
+    Count(usize),
+}
+
+impl Binning {
+    /// Builds the binning from the `width` and `bins` query parameters
+    /// 
+    /// # Arguments
+    /// * `width` - The width of each bin, in the metric's unit
+    /// * `bins` - The number of bins
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The binning, `DEFAULT_BINS` bins if neither is given,
+    ///   or an invalid query error
+    pub fn parse(width: Option<f64>, bins: Option<usize>) -> Result<Self, ApiError> {
+        match (width, bins) {
+            (Some(_), Some(_)) => Err(ApiError::InvalidQuery("width cannot be combined with bins".to_string())),
+            (Some(width), None) if width.is_finite() && width > 0.0 => Ok(Binning::Width(width)),
+            (Some(_), None) => Err(ApiError::InvalidQuery("width must be a positive number".to_string())),
+            (None, Some(bins)) if (1..=MAX_BINS).contains(&bins) => Ok(Binning::Count(bins)),
+            (None, Some(_)) => Err(ApiError::InvalidQuery(format!("bins must be between 1 and {}", MAX_BINS))),
+            (None, None) => Ok(Binning::Count(DEFAULT_BINS)),
+        }
+    }
+}
+
+/// Number of samples within one bin
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bin {
+    /// Smallest value in the bin (inclusive)
+    pub lower: f64,
+    /// Largest value in the bin (exclusive, except for the last bin)
+    pub upper: f64,
+    /// Number of samples in the bin
+    pub count: usize,
+}
+
+/// Distribution of a device's metric over a time range
+#[derive(Debug, Clone, Serialize)]
+pub struct Histogram {
+    /// The device whose telemetry was binned
+    pub device_id: String,
+    /// The metric that was binned
+    pub metric: String,
+    /// Number of numeric samples
+    pub count: usize,
+    /// Smallest sample
+    pub min: Option<f64>,
+    /// Largest sample
+    pub max: Option<f64>,
+    /// Width of each bin
+    pub bin_width: Option<f64>,
+    /// Every bin from the smallest to the largest sample, including empty ones
+    pub bins: Vec<Bin>,
+}
+
+/// Bins a metric's samples
+/// 
+/// # Arguments
+/// * `device_id` - The device the samples belong to
+/// * `metric` - The metric name (e.g. "temperature")
+/// * `values` - The numeric samples, in any order
+/// * `binning` - How to divide the range of values into bins
+/// 
+/// # Returns
+/// * `Result<Histogram, ApiError>` - The histogram, without bins if there are no samples,
+///   or an invalid query error if the width would need more than `MAX_BINS` bins
+pub fn histogram(device_id: &str, metric: &str, values: &[f64], binning: Binning) -> Result<Histogram, ApiError> {
+    let min = values.iter().copied().reduce(f64::min);
+    let max = values.iter().copied().reduce(f64::max);
+
+    let (start, width, count) = match (min, max, binning) {
+        (Some(min), Some(max), Binning::Width(width)) => {
+            let start = (min / width).floor() * width;
+            let count = ((max - start) / width).floor() as usize + 1;
+            if count > MAX_BINS {
+                return Err(ApiError::InvalidQuery(format!(
+                    "width {} would need {} bins, more than {}",
+                    width, count, MAX_BINS
+                )));
+            }
+            (start, width, count)
+        }
+        // All samples equal: one bin holding them all
+        (Some(min), Some(max), Binning::Count(_)) if min == max => (min, 0.0, 1),
+        (Some(min), Some(max), Binning::Count(bins)) => (min, (max - min) / bins as f64, bins),
+        _ => {
+            return Ok(Histogram {
+                device_id: device_id.to_string(),
+                metric: metric.to_string(),
+                count: 0,
+                min: None,
+                max: None,
+                bin_width: None,
+                bins: Vec::new(),
+            })
+        }
+    };
+
+    let mut bins: Vec<Bin> = (0..count)
+        .map(|i| Bin {
+            lower: start + i as f64 * width,
+            upper: start + (i + 1) as f64 * width,
+            count: 0,
+        })
+        .collect();
+    for value in values {
+        let index = if width > 0.0 { ((value - start) / width).floor() as usize } else { 0 };
+        // The largest value closes the last bin
+        bins[index.min(count - 1)].count += 1;
+    }
+
+    Ok(Histogram {
+        device_id: device_id.to_string(),
+        metric: metric.to_string(),
+        count: values.len(),
+        min,
+        max,
+        bin_width: Some(width),
+        bins,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(histogram: &Histogram) -> Vec<usize> {
+        histogram.bins.iter().map(|bin| bin.count).collect()
+    }
+
+    #[test]
+    fn test_parse_binning() {
+        assert_eq!(Binning::parse(None, None).unwrap(), Binning::Count(DEFAULT_BINS));
+        assert_eq!(Binning::parse(Some(0.5), None).unwrap(), Binning::Width(0.5));
+        assert_eq!(Binning::parse(None, Some(10)).unwrap(), Binning::Count(10));
+
+        assert!(matches!(Binning::parse(Some(1.0), Some(10)), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(Binning::parse(Some(0.0), None), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(Binning::parse(None, Some(0)), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(Binning::parse(None, Some(MAX_BINS + 1)), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_histogram_by_count() {
+        let values = [1.0, 1.5, 2.0, 9.0, 9.5, 10.0];
+
+        let result = histogram("sensor-001", "temperature", &values, Binning::Count(3)).unwrap();
+
+        assert_eq!(result.bin_width, Some(3.0));
+        assert_eq!(result.bins[0], Bin { lower: 1.0, upper: 4.0, count: 3 });
+        assert_eq!(counts(&result), vec![3, 0, 3]);
+    }
+
+    #[test]
+    fn test_histogram_by_width() {
+        let values = [21.2, 21.9, 23.1, 25.0];
+
+        let result = histogram("sensor-001", "temperature", &values, Binning::Width(1.0)).unwrap();
+
+        assert_eq!(result.bins.first().unwrap().lower, 21.0);
+        assert_eq!(result.bins.last().unwrap().lower, 25.0);
+        assert_eq!(counts(&result), vec![2, 0, 1, 0, 1]);
+        assert!(histogram("sensor-001", "temperature", &[0.0, 1e9], Binning::Width(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_histogram_degenerate_samples() {
+        let same = histogram("sensor-001", "temperature", &[4.0, 4.0], Binning::Count(10)).unwrap();
+        assert_eq!(counts(&same), vec![2]);
+
+        let empty = histogram("sensor-001", "temperature", &[], Binning::Count(10)).unwrap();
+        assert_eq!(empty.count, 0);
+        assert!(empty.bins.is_empty());
+    }
+}
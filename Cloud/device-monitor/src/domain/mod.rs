@@ -6,6 +6,34 @@
 
 pub mod telemetry;
 pub mod error;
+pub mod auth;
+pub mod tags;
+pub mod units;
+pub mod aggregate;
+pub mod annotation;
+pub mod alert;
+pub mod device;
+pub mod fleet;
+pub mod health;
+pub mod compare;
+pub mod paging;
+pub mod batch;
+pub mod time_range;
+pub mod value_filter;
+pub mod sort_order;
+pub mod etag;
+pub mod downsample;
+pub mod anomaly;
+pub mod gaps;
+pub mod uptime;
+pub mod stats;
+pub mod histogram;
+pub mod rate;
+pub mod retention;
+pub mod archive;
+pub mod prometheus;
+pub mod export;
+pub mod subscription;
 
 // Re-export all telemetry-related types for convenient access
 pub use telemetry::*;
\ No newline at end of file
@@ -0,0 +1,224 @@
+// Telemetry Paging Domain Logic
+//
+// This module defines how telemetry reads are split into pages. Pages follow
+// the requested sort order; each page that is followed by more records
+// carries an opaque continuation token from which the next page is read.
+// Tokens hold the timestamp and id of the page's last record, so the next
+// page is found with a keyset condition rather than by skipping records: its
+// cost does not grow with the page number, and records stored while a client
+// pages through a device's history neither shift nor repeat later pages.
+
+use crate::domain::error::ApiError;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::telemetry::Telemetry;
+
+/// Number of records per page when only a continuation token is given
+pub const DEFAULT_PAGE_LIMIT: usize = 100;
+
+/// Upper bound on the number of records per page
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// Prefix identifying the continuation token format
+const TOKEN_PREFIX: &str = "k";
+
+/// The last record of a page, after which the next page starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageCursor {
+    /// Timestamp of the record
+    pub timestamp: i64,
+    /// Id of the record, breaking ties between records with the same timestamp
+    pub id: String,
+}
+
+impl PageCursor {
+    /// Renders the cursor as a continuation token
+    ///
+    /// The id is hex encoded, so the token is safe to pass in a query string.
+    fn to_token(&self) -> String {
+        let id: String = self.id.bytes().map(|byte| format!("{:02x}", byte)).collect();
+        format!("{}{}.{}", TOKEN_PREFIX, self.timestamp, id)
+    }
+
+    /// Parses a cursor from a continuation token
+    fn from_token(token: &str) -> Option<Self> {
+        let (timestamp, id) = token.strip_prefix(TOKEN_PREFIX)?.split_once('.')?;
+        if id.is_empty() || id.len() % 2 != 0 {
+            return None;
+        }
+        let id = (0..id.len())
+            .step_by(2)
+            .map(|index| id.get(index..index + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+            .collect::<Option<Vec<u8>>>()?;
+        Some(PageCursor {
+            timestamp: timestamp.parse().ok()?,
+            id: String::from_utf8(id).ok()?,
+        })
+    }
+}
+
+/// A requested page of telemetry records
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Last record of the previous page, in the requested sort order, or None for the first page
+    pub after: Option<PageCursor>,
+    /// Maximum number of records to return
+    pub limit: usize,
+}
+
+impl PageRequest {
+    /// Builds a page request from the `limit` and `continuation` query parameters
+    ///
+    /// The limit is clamped to `MAX_PAGE_LIMIT`; if only a continuation
+    /// token is given, `DEFAULT_PAGE_LIMIT` records are returned.
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum number of records per page
+    /// * `continuation` - The token returned with the previous page
+    ///
+    /// # Returns
+    /// * `Result<Option<Self>, ApiError>` - The page, None if neither parameter was
+    ///   given (read everything), or an invalid query error
+    pub fn parse(limit: Option<usize>, continuation: Option<&str>) -> Result<Option<Self>, ApiError> {
+        if limit.is_none() && continuation.is_none() {
+            return Ok(None);
+        }
+
+        let limit = match limit {
+            Some(0) => return Err(ApiError::InvalidQuery("limit must be at least 1".to_string())),
+            Some(limit) => limit.min(MAX_PAGE_LIMIT),
+            None => DEFAULT_PAGE_LIMIT,
+        };
+
+        let after = match continuation {
+            Some(token) => Some(
+                PageCursor::from_token(token)
+                    .ok_or_else(|| ApiError::InvalidQuery(format!("Invalid continuation token: {}", token)))?,
+            ),
+            None => None,
+        };
+
+        Ok(Some(PageRequest { after, limit }))
+    }
+
+    /// Renders the page as Cosmos DB query clauses
+    ///
+    /// Records are ordered by timestamp and then by id, so records sharing a
+    /// timestamp are never split unpredictably across pages. One record more
+    /// than the page holds is selected, so callers can tell whether another
+    /// page follows.
+    ///
+    /// # Arguments
+    /// * `order` - The order the pages follow
+    ///
+    /// # Returns
+    /// * `String` - The ` AND ...` keyset condition, if a page precedes this one,
+    ///   followed by the `ORDER BY` and `OFFSET 0 LIMIT` clauses
+    pub fn query_clause(&self, order: SortOrder) -> String {
+        let (comparison, direction) = match order {
+            SortOrder::Asc => (">", "ASC"),
+            SortOrder::Desc => ("<", "DESC"),
+        };
+        let condition = self.after.as_ref().map_or_else(String::new, |after| {
+            let id = after.id.replace('\\', "\\\\").replace('\'', "\\'");
+            format!(
+                " AND (c.timestamp {0} {1} OR (c.timestamp = {1} AND c.id {0} '{2}'))",
+                comparison, after.timestamp, id
+            )
+        });
+        format!(
+            "{0} ORDER BY c.timestamp {1}, c.id {1} OFFSET 0 LIMIT {2}",
+            condition,
+            direction,
+            self.limit + 1
+        )
+    }
+
+    /// Returns the token for the page after this one
+    ///
+    /// # Arguments
+    /// * `more` - Whether more records follow the ones returned
+    /// * `last` - The last record returned
+    ///
+    /// # Returns
+    /// * `Option<String>` - The continuation token, or None on the last page
+    pub fn next_token(&self, more: bool, last: Option<&Telemetry>) -> Option<String> {
+        let last = last.filter(|_| more)?;
+        let cursor = PageCursor { timestamp: last.timestamp?, id: last.id.clone()? };
+        Some(cursor.to_token())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, timestamp: i64) -> Telemetry {
+        let data = [("temperature".to_string(), "21.5".to_string())].into();
+        let mut record = Telemetry::parse("sensor-001".to_string(), data, Some(timestamp)).unwrap();
+        record.id = Some(id.to_string());
+        record
+    }
+
+    #[test]
+    fn test_parse_page_request() {
+        assert_eq!(PageRequest::parse(None, None).unwrap(), None);
+        assert_eq!(
+            PageRequest::parse(Some(50), None).unwrap(),
+            Some(PageRequest { after: None, limit: 50 })
+        );
+        assert_eq!(
+            PageRequest::parse(Some(5000), Some("k1700000000.612d31")).unwrap(),
+            Some(PageRequest {
+                after: Some(PageCursor { timestamp: 1700000000, id: "a-1".to_string() }),
+                limit: MAX_PAGE_LIMIT,
+            })
+        );
+        assert_eq!(PageRequest::parse(None, Some("k5.61")).unwrap().unwrap().limit, DEFAULT_PAGE_LIMIT);
+
+        assert!(matches!(PageRequest::parse(Some(0), None), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(PageRequest::parse(Some(10), Some("o200")), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(PageRequest::parse(Some(10), Some("k200")), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(PageRequest::parse(Some(10), Some("k200.")), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(PageRequest::parse(Some(10), Some("k200.6")), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(PageRequest::parse(Some(10), Some("kx.61")), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(PageRequest::parse(Some(10), Some("k200.zz")), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_next_token_round_trips() {
+        let page = PageRequest::parse(Some(25), None).unwrap().unwrap();
+        let last = record("sensor-001-1700000000 'x'", 1700000000);
+
+        let token = page.next_token(true, Some(&last)).unwrap();
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || c == '.'));
+        let next = PageRequest::parse(Some(25), Some(&token)).unwrap().unwrap();
+        assert_eq!(next.after, Some(PageCursor { timestamp: 1700000000, id: last.id.clone().unwrap() }));
+
+        assert_eq!(page.next_token(false, Some(&last)), None);
+        assert_eq!(page.next_token(true, None), None);
+    }
+
+    #[test]
+    fn test_query_clause_follows_the_sort_order() {
+        let first = PageRequest { after: None, limit: 10 };
+        assert_eq!(
+            first.query_clause(SortOrder::Desc),
+            " ORDER BY c.timestamp DESC, c.id DESC OFFSET 0 LIMIT 11"
+        );
+
+        let next = PageRequest {
+            after: Some(PageCursor { timestamp: 1700000000, id: "it's".to_string() }),
+            limit: 10,
+        };
+        assert_eq!(
+            next.query_clause(SortOrder::Desc),
+            " AND (c.timestamp < 1700000000 OR (c.timestamp = 1700000000 AND c.id < 'it\\'s')) \
+             ORDER BY c.timestamp DESC, c.id DESC OFFSET 0 LIMIT 11"
+        );
+        assert_eq!(
+            next.query_clause(SortOrder::Asc),
+            " AND (c.timestamp > 1700000000 OR (c.timestamp = 1700000000 AND c.id > 'it\\'s')) \
+             ORDER BY c.timestamp ASC, c.id ASC OFFSET 0 LIMIT 11"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+// Prometheus Exposition Domain Logic
+// 
+// This module renders the latest telemetry of each device in the Prometheus
+// text exposition format, so existing Prometheus, Grafana and Alertmanager
+// stacks can scrape the fleet. Each numeric metric becomes a gauge named
+// after it, labeled with the device it came from.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::domain::device::DeviceListing;
+use crate::domain::telemetry::Telemetry;
+
+/// Prefix of every gauge name, keeping names apart from other exporters
+const METRIC_PREFIX: &str = "device_";
+
+/// Name of the gauge holding each device's last-seen time
+const LAST_SEEN_METRIC: &str = "device_last_seen_timestamp_seconds";
+
+/// Converts a telemetry metric name to a valid Prometheus metric name
+/// 
+/// Characters Prometheus does not allow are replaced with underscores, so
+/// e.g. "battery-voltage" becomes "device_battery_voltage".
+/// 
+/// # Arguments
+/// * `metric` - The telemetry metric name
+/// 
+/// # Returns
+/// * `String` - The prefixed, sanitized gauge name
+pub fn gauge_name(metric: &str) -> String {
+    let sanitized: String = metric
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' })
+        .collect();
+    format!("{}{}", METRIC_PREFIX, sanitized)
+}
+
+/// Escapes a label value as required by the text exposition format
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Renders the devices' last-seen times and latest numeric telemetry as Prometheus gauges
+/// 
+/// Non-numeric values are skipped. Gauges are sorted by name and series by
+/// device, and no sample timestamps are written, as Prometheus rejects
+/// samples much older than the scrape.
+/// 
+/// # Arguments
+/// * `devices` - The devices from the device index
+/// * `latest` - The most recent telemetry record of each device that has one
+/// 
+/// # Returns
+/// * `String` - The exposition text
+pub fn render_device_metrics(devices: &[DeviceListing], latest: &[Telemetry]) -> String {
+    // Metric names that only differ in sanitized characters share a gauge
+    let mut gauges: BTreeMap<String, (String, BTreeMap<&str, f64>)> = BTreeMap::new();
+    for telemetry in latest {
+        for (metric, value) in &telemetry.telemetry_data {
+            let Some(value) = value.trim().parse::<f64>().ok().filter(|value| value.is_finite()) else {
+                continue;
+            };
+            gauges
+                .entry(gauge_name(metric))
+                .or_insert_with(|| (metric.clone(), BTreeMap::new()))
+                .1
+                .insert(telemetry.device_id.as_str(), value);
+        }
+    }
+
+    let mut output = String::new();
+    if !devices.is_empty() {
+        let _ = writeln!(output, "# HELP {} Unix time the device was last seen", LAST_SEEN_METRIC);
+        let _ = writeln!(output, "# TYPE {} gauge", LAST_SEEN_METRIC);
+        let mut devices: Vec<&DeviceListing> = devices.iter().collect();
+        devices.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+        for device in devices {
+            let _ = writeln!(
+                output,
+                "{}{{device_id=\"{}\"}} {}",
+                LAST_SEEN_METRIC, escape_label(&device.device_id), device.last_seen
+            );
+        }
+    }
+
+    for (name, (metric, series)) in gauges {
+        let _ = writeln!(output, "# HELP {} Latest {} reported by the device", name, metric.replace('\n', " "));
+        let _ = writeln!(output, "# TYPE {} gauge", name);
+        for (device_id, value) in series {
+            let _ = writeln!(output, "{}{{device_id=\"{}\"}} {}", name, escape_label(device_id), value);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_device_metrics() {
+        let devices = vec![
+            DeviceListing { device_id: "4321".to_string(), last_seen: 1640995260, gateway_id: None },
+            DeviceListing { device_id: "sensor \"a\"".to_string(), last_seen: 1640995200, gateway_id: None },
+        ];
+        let latest = vec![
+            Telemetry::parse(
+                "4321".to_string(),
+                [("temperature".to_string(), "21.5".to_string()), ("status".to_string(), "ok".to_string())].into(),
+                Some(1640995260),
+            )
+            .unwrap(),
+            Telemetry::parse(
+                "sensor \"a\"".to_string(),
+                [("battery-voltage".to_string(), "3.7".to_string())].into(),
+                Some(1640995200),
+            )
+            .unwrap(),
+        ];
+
+        let output = render_device_metrics(&devices, &latest);
+        assert_eq!(output, "\
+# HELP device_last_seen_timestamp_seconds Unix time the device was last seen
+# TYPE device_last_seen_timestamp_seconds gauge
+device_last_seen_timestamp_seconds{device_id=\"4321\"} 1640995260
+device_last_seen_timestamp_seconds{device_id=\"sensor \\\"a\\\"\"} 1640995200
+# HELP device_battery_voltage Latest battery-voltage reported by the device
+# TYPE device_battery_voltage gauge
+device_battery_voltage{device_id=\"sensor \\\"a\\\"\"} 3.7
+# HELP device_temperature Latest temperature reported by the device
+# TYPE device_temperature gauge
+device_temperature{device_id=\"4321\"} 21.5
+");
+    }
+}
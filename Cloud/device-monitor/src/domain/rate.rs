@@ -0,0 +1,212 @@
+// Rate of Change Domain Logic
+// 
+// This module derives the rate of change (the first derivative) of a
+// metric from consecutive readings, e.g. how fast a temperature rises per
+// minute, optionally smoothed so sensor noise does not hide the trend.
+// Threshold alerts on a raw value miss fast ramps that stay below the
+// threshold; the rate shows them.
+
+use serde::Serialize;
+
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Default number of rates in the moving average
+pub const DEFAULT_SMOOTHING_WINDOW: usize = 5;
+
+/// Largest accepted moving average window
+pub const MAX_SMOOTHING_WINDOW: usize = 1000;
+
+/// Default weight of the newest rate in the EWMA
+pub const DEFAULT_SMOOTHING_ALPHA: f64 = 0.3;
+
+/// Parses the time unit the rate is expressed per
+/// 
+/// # Arguments
+/// * `per` - "second", "minute" (default) or "hour" (case-insensitive)
+/// 
+/// # Returns
+/// * `Result<i64, ApiError>` - The unit in seconds or an invalid query error
+pub fn parse_per(per: Option<&str>) -> Result<i64, ApiError> {
+    match per.unwrap_or("minute").to_ascii_lowercase().as_str() {
+        "second" => Ok(1),
+        "minute" => Ok(60),
+        "hour" => Ok(60 * 60),
+        other => Err(ApiError::InvalidQuery(format!("Unknown rate unit: {} (expected second, minute or hour)", other))),
+    }
+}
+
+/// How the rates are smoothed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Smoothing {
+    /// The raw rate between each pair of consecutive readings
+    None,
+    /// Mean of the last `window` rates
+    Sma {
+        /// Number of rates averaged
+        window: usize,
+    },
+    /// Exponentially weighted mean of all rates so far
+    Ewma {
+        /// Weight of the newest rate, between 0 and 1
+        alpha: f64,
+    },
+}
+
+impl Smoothing {
+    /// Parses the `smoothing`, `window` and `alpha` query parameters
+    /// 
+    /// # Arguments
+    /// * `smoothing` - "none" (default), "sma" or "ewma" (case-insensitive)
+    /// * `window` - Window of the moving average (default `DEFAULT_SMOOTHING_WINDOW`)
+    /// * `alpha` - Weight of the EWMA (default `DEFAULT_SMOOTHING_ALPHA`)
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The smoothing or an invalid query error
+    pub fn parse(smoothing: Option<&str>, window: Option<usize>, alpha: Option<f64>) -> Result<Self, ApiError> {
+        match smoothing.unwrap_or("none").to_ascii_lowercase().as_str() {
+            "none" => Ok(Smoothing::None),
+            "sma" => {
+                let window = window.unwrap_or(DEFAULT_SMOOTHING_WINDOW);
+                if !(1..=MAX_SMOOTHING_WINDOW).contains(&window) {
+                    return Err(ApiError::InvalidQuery(format!(
+                        "window must be between 1 and {}",
+                        MAX_SMOOTHING_WINDOW
+                    )));
+                }
+                Ok(Smoothing::Sma { window })
+            }
+            "ewma" => {
+                let alpha = alpha.unwrap_or(DEFAULT_SMOOTHING_ALPHA);
+                if !(alpha > 0.0 && alpha <= 1.0) {
+                    return Err(ApiError::InvalidQuery("alpha must be greater than 0 and at most 1".to_string()));
+                }
+                Ok(Smoothing::Ewma { alpha })
+            }
+            other => Err(ApiError::InvalidQuery(format!("Unknown smoothing: {}", other))),
+        }
+    }
+}
+
+/// The rate of change at one reading
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RatePoint {
+    /// Timestamp of the reading the rate leads up to
+    pub timestamp: i64,
+    /// Change of the metric per unit of time since the previous reading, after smoothing
+    pub rate: f64,
+}
+
+/// Rate of change of a device's metric
+#[derive(Debug, Clone, Serialize)]
+pub struct RateSeries {
+    /// The device whose telemetry was differentiated
+    pub device_id: String,
+    /// The metric that was differentiated
+    pub metric: String,
+    /// The time unit the rates are expressed per, in seconds
+    pub per_secs: i64,
+    /// How the rates were smoothed
+    pub smoothing: Smoothing,
+    /// One rate per reading after the first, oldest first
+    pub points: Vec<RatePoint>,
+}
+
+/// Extracts the timestamped numeric readings of a metric
+/// 
+/// Records without a timestamp or a numeric value for the metric are skipped.
+/// 
+/// # Arguments
+/// * `telemetry` - The telemetry records, oldest first
+/// * `metric` - The metric name (e.g. "temperature")
+/// 
+/// # Returns
+/// * `Vec<(i64, f64)>` - The timestamp and value of each reading
+pub fn metric_readings(telemetry: &[Telemetry], metric: &str) -> Vec<(i64, f64)> {
+    telemetry
+        .iter()
+        .filter_map(|record| {
+            let value = record.telemetry_data.get(metric)?.trim().parse::<f64>().ok().filter(|value| value.is_finite())?;
+            Some((record.timestamp?, value))
+        })
+        .collect()
+}
+
+/// Computes the smoothed rate of change between consecutive readings
+/// 
+/// Readings sharing a timestamp with the previous one are skipped, as no
+/// time passed between them.
+/// 
+/// # Arguments
+/// * `readings` - The timestamp and value of each reading, oldest first
+/// * `per_secs` - The time unit the rates are expressed per, in seconds
+/// * `smoothing` - How the rates are smoothed
+/// 
+/// # Returns
+/// * `Vec<RatePoint>` - One rate per reading after the first
+pub fn rate_of_change(readings: &[(i64, f64)], per_secs: i64, smoothing: Smoothing) -> Vec<RatePoint> {
+    let mut points = Vec::new();
+    let mut raw: Vec<f64> = Vec::new();
+    let mut ewma: Option<f64> = None;
+    let mut previous: Option<(i64, f64)> = None;
+
+    for &(timestamp, value) in readings {
+        let Some((last_timestamp, last_value)) = previous.filter(|(last, _)| timestamp > *last) else {
+            previous = previous.or(Some((timestamp, value)));
+            continue;
+        };
+        previous = Some((timestamp, value));
+
+        let rate = (value - last_value) / (timestamp - last_timestamp) as f64 * per_secs as f64;
+        let rate = match smoothing {
+            Smoothing::None => rate,
+            Smoothing::Sma { window } => {
+                raw.push(rate);
+                let recent = &raw[raw.len().saturating_sub(window)..];
+                recent.iter().sum::<f64>() / recent.len() as f64
+            }
+            Smoothing::Ewma { alpha } => {
+                let smoothed = ewma.map_or(rate, |mean| mean + alpha * (rate - mean));
+                ewma = Some(smoothed);
+                smoothed
+            }
+        };
+        points.push(RatePoint { timestamp, rate });
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_options() {
+        assert_eq!(parse_per(None).unwrap(), 60);
+        assert_eq!(parse_per(Some("Hour")).unwrap(), 3600);
+        assert!(matches!(parse_per(Some("day")), Err(ApiError::InvalidQuery(_))));
+
+        assert_eq!(Smoothing::parse(None, None, None).unwrap(), Smoothing::None);
+        assert_eq!(Smoothing::parse(Some("sma"), None, None).unwrap(), Smoothing::Sma { window: DEFAULT_SMOOTHING_WINDOW });
+        assert!(matches!(Smoothing::parse(Some("sma"), Some(0), None), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(Smoothing::parse(Some("ewma"), None, Some(1.5)), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(Smoothing::parse(Some("median"), None, None), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_rate_of_change() {
+        let readings = [(0, 20.0), (60, 21.0), (60, 30.0), (180, 25.0), (240, 28.0)];
+
+        let rates: Vec<f64> = rate_of_change(&readings, 60, Smoothing::None).iter().map(|p| p.rate).collect();
+        assert_eq!(rates, vec![1.0, 2.0, 3.0]);
+
+        let smoothed = rate_of_change(&readings, 60, Smoothing::Sma { window: 2 });
+        assert_eq!(smoothed[1], RatePoint { timestamp: 180, rate: 1.5 });
+        assert_eq!(smoothed[2].rate, 2.5);
+
+        let ewma = rate_of_change(&readings, 3600, Smoothing::Ewma { alpha: 0.5 });
+        assert_eq!(ewma[0].rate, 60.0);
+        assert_eq!(ewma[1].rate, 90.0);
+    }
+}
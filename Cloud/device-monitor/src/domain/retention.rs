@@ -0,0 +1,156 @@
+// Telemetry Retention Domain Model
+// 
+// This module defines how long each device's telemetry is kept, and the
+// statistics of the cleanup runs that delete older telemetry. A default
+// retention period applies to every device, and single devices can keep
+// their telemetry for longer or shorter. Without any retention configured,
+// telemetry is kept forever.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::warn;
+
+/// Number of seconds in a day
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// How many days of telemetry to keep, by default and per device
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RetentionPolicy {
+    /// Days of telemetry kept for devices without an override, or None to keep everything
+    pub default_days: Option<i64>,
+    /// Days of telemetry kept for single devices, keyed by device ID
+    pub device_days: HashMap<String, i64>,
+}
+
+impl RetentionPolicy {
+    /// Loads the policy from the `TELEMETRY_RETENTION_DAYS` and
+    /// `TELEMETRY_RETENTION_OVERRIDES` environment variables
+    /// 
+    /// `TELEMETRY_RETENTION_DAYS` holds the default number of days, and
+    /// `TELEMETRY_RETENTION_OVERRIDES` a JSON object mapping device IDs to
+    /// their number of days, e.g. `{"sensor-001": 365}`. Unset, invalid and
+    /// non-positive values are ignored, so their telemetry is kept.
+    pub fn from_env() -> Self {
+        let default_days = std::env::var("TELEMETRY_RETENTION_DAYS")
+            .ok()
+            .and_then(|days| days.parse().ok())
+            .filter(|days| *days > 0);
+
+        let device_days = match std::env::var("TELEMETRY_RETENTION_OVERRIDES") {
+            Ok(json) => serde_json::from_str::<HashMap<String, i64>>(&json).unwrap_or_else(|e| {
+                warn!("Ignoring invalid TELEMETRY_RETENTION_OVERRIDES: {}", e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+
+        RetentionPolicy { default_days, device_days }.without_invalid_overrides()
+    }
+
+    /// Drops overrides that do not keep telemetry for at least one day
+    fn without_invalid_overrides(mut self) -> Self {
+        self.device_days.retain(|device_id, days| {
+            let valid = *days > 0;
+            if !valid {
+                warn!("Ignoring retention of {} days for device {}", days, device_id);
+            }
+            valid
+        });
+        self
+    }
+
+    /// Returns true if telemetry of any device is ever deleted
+    pub fn is_enabled(&self) -> bool {
+        self.default_days.is_some() || !self.device_days.is_empty()
+    }
+
+    /// Returns the oldest timestamp of a device's telemetry to keep
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `now` - The current Unix timestamp
+    /// 
+    /// # Returns
+    /// * `Option<i64>` - The cutoff, or None if the device's telemetry is kept forever
+    pub fn cutoff(&self, device_id: &str, now: i64) -> Option<i64> {
+        let days = self.device_days.get(device_id).copied().or(self.default_days)?;
+        Some(now - days * SECS_PER_DAY)
+    }
+}
+
+/// Telemetry deleted from one device by a cleanup run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeviceCleanup {
+    /// The device whose telemetry was deleted
+    pub device_id: String,
+    /// Records older than this Unix timestamp were deleted
+    pub cutoff: i64,
+    /// Number of records deleted
+    pub records_deleted: usize,
+}
+
+/// Statistics of one cleanup run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetentionRun {
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// How long the run took, in milliseconds
+    pub duration_ms: i64,
+    /// Number of devices with a retention period
+    pub devices_checked: usize,
+    /// Number of records deleted across all devices
+    pub records_deleted: usize,
+    /// Number of devices whose cleanup failed
+    pub devices_failed: usize,
+    /// The devices that had records deleted
+    pub devices: Vec<DeviceCleanup>,
+}
+
+/// Retention policy and cleanup statistics, as returned by the retention endpoint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetentionStatus {
+    /// The configured retention periods
+    pub policy: RetentionPolicy,
+    /// Interval between cleanup runs, in seconds
+    pub interval_secs: u64,
+    /// Number of cleanup runs since the service started
+    pub runs: u64,
+    /// Number of records deleted since the service started
+    pub total_records_deleted: u64,
+    /// Statistics of the most recent run, if any
+    pub last_run: Option<RetentionRun>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff_per_device() {
+        let policy = RetentionPolicy {
+            default_days: Some(30),
+            device_days: [("sensor-001".to_string(), 365), ("sensor-002".to_string(), 0)].into(),
+        }
+        .without_invalid_overrides();
+        let now = 1640995200;
+
+        assert!(policy.is_enabled());
+        assert_eq!(policy.cutoff("sensor-001", now), Some(now - 365 * SECS_PER_DAY));
+        assert_eq!(policy.cutoff("sensor-002", now), Some(now - 30 * SECS_PER_DAY));
+        assert_eq!(policy.cutoff("sensor-003", now), Some(now - 30 * SECS_PER_DAY));
+    }
+
+    #[test]
+    fn test_overrides_without_default() {
+        let policy = RetentionPolicy {
+            default_days: None,
+            device_days: [("sensor-001".to_string(), 7)].into(),
+        };
+
+        assert_eq!(policy.cutoff("sensor-001", 1640995200), Some(1640995200 - 7 * SECS_PER_DAY));
+        assert_eq!(policy.cutoff("sensor-002", 1640995200), None);
+        assert!(!RetentionPolicy::default().is_enabled());
+    }
+}
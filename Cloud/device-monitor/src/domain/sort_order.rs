@@ -0,0 +1,59 @@
+// Telemetry Sort Order Domain Logic
+// 
+// This module defines the order in which telemetry records are read. The
+// order is applied as a Cosmos DB `ORDER BY` clause, so sorting happens in
+// the database rather than in the service.
+
+use crate::domain::error::ApiError;
+
+/// Order of telemetry records by timestamp
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Oldest first
+    Asc,
+    /// Newest first
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    /// Builds a sort order from the `order` query parameter
+    /// 
+    /// # Arguments
+    /// * `order` - "asc" or "desc" (case-insensitive), newest first if not given
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The order, or an invalid query error for any other value
+    pub fn parse(order: Option<&str>) -> Result<Self, ApiError> {
+        match order.map(str::to_ascii_lowercase).as_deref() {
+            None => Ok(SortOrder::default()),
+            Some("asc") => Ok(SortOrder::Asc),
+            Some("desc") => Ok(SortOrder::Desc),
+            Some(other) => Err(ApiError::InvalidQuery(format!(
+                "order must be asc or desc, not {}",
+                other
+            ))),
+        }
+    }
+
+    /// Renders the order as a Cosmos DB `ORDER BY` clause on `c.timestamp`
+    pub fn query_clause(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => " ORDER BY c.timestamp ASC",
+            SortOrder::Desc => " ORDER BY c.timestamp DESC",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sort_order() {
+        assert_eq!(SortOrder::parse(None).unwrap(), SortOrder::Desc);
+        assert_eq!(SortOrder::parse(Some("asc")).unwrap(), SortOrder::Asc);
+        assert_eq!(SortOrder::parse(Some("DESC")).unwrap(), SortOrder::Desc);
+        assert!(matches!(SortOrder::parse(Some("newest")), Err(ApiError::InvalidQuery(_))));
+    }
+}
@@ -0,0 +1,117 @@
+// Metric Statistics Domain Logic
+// 
+// This module computes summary statistics of a metric's samples (count,
+// mean, standard deviation, extremes, percentiles and latest sample) server-side, so SLO
+// reports do not download every raw reading to compute them in the browser.
+
+use serde::Serialize;
+
+/// Summary statistics of a device's metric over a time range
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricStats {
+    /// The device whose telemetry was summarized
+    pub device_id: String,
+    /// The metric that was summarized
+    pub metric: String,
+    /// Number of numeric samples
+    pub count: usize,
+    /// Mean of the samples
+    pub mean: Option<f64>,
+    /// Population standard deviation of the samples
+    pub stddev: Option<f64>,
+    /// Smallest sample
+    pub min: Option<f64>,
+    /// Largest sample
+    pub max: Option<f64>,
+    /// Median
+    pub p50: Option<f64>,
+    /// 95th percentile
+    pub p95: Option<f64>,
+    /// 99th percentile
+    pub p99: Option<f64>,
+    /// Most recent sample
+    pub latest: Option<f64>,
+}
+
+/// Returns a percentile of sorted samples, interpolating linearly between the closest ranks
+/// 
+/// # Arguments
+/// * `sorted` - The samples, sorted ascending
+/// * `percentile` - The percentile, from 0 to 100
+/// 
+/// # Returns
+/// * `Option<f64>` - The percentile, or None if there are no samples
+pub fn percentile(sorted: &[f64], percentile: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = percentile / 100.0 * last as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64))
+}
+
+/// Computes the summary statistics of a metric's samples
+/// 
+/// # Arguments
+/// * `device_id` - The device the samples belong to
+/// * `metric` - The metric name (e.g. "temperature")
+/// * `values` - The numeric samples, oldest first
+/// 
+/// # Returns
+/// * `MetricStats` - The statistics, with null values if there are no samples
+pub fn metric_stats(device_id: &str, metric: &str, mut values: Vec<f64>) -> MetricStats {
+    let latest = values.last().copied();
+    values.sort_by(f64::total_cmp);
+
+    let count = values.len();
+    let mean = (count > 0).then(|| values.iter().sum::<f64>() / count as f64);
+    let stddev = mean.map(|mean| {
+        let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / count as f64;
+        variance.sqrt()
+    });
+
+    MetricStats {
+        device_id: device_id.to_string(),
+        metric: metric.to_string(),
+        count,
+        mean,
+        stddev,
+        min: values.first().copied(),
+        max: values.last().copied(),
+        p50: percentile(&values, 50.0),
+        p95: percentile(&values, 95.0),
+        p99: percentile(&values, 99.0),
+        latest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_interpolates() {
+        let sorted = [10.0, 20.0, 30.0, 40.0, 50.0];
+
+        assert_eq!(percentile(&sorted, 0.0), Some(10.0));
+        assert_eq!(percentile(&sorted, 50.0), Some(30.0));
+        assert_eq!(percentile(&sorted, 95.0), Some(48.0));
+        assert_eq!(percentile(&sorted, 100.0), Some(50.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_metric_stats() {
+        let stats = metric_stats("sensor-001", "temperature", vec![4.0, 2.0, 9.0, 4.0, 5.0, 5.0, 7.0, 4.0]);
+
+        assert_eq!(stats.count, 8);
+        assert_eq!(stats.mean, Some(5.0));
+        assert_eq!(stats.stddev, Some(2.0));
+        assert_eq!(stats.min, Some(2.0));
+        assert_eq!(stats.max, Some(9.0));
+        assert_eq!(stats.p50, Some(4.5));
+        assert_eq!(stats.latest, Some(4.0));
+
+        let empty = metric_stats("sensor-001", "temperature", Vec::new());
+        assert_eq!(empty.count, 0);
+        assert_eq!((empty.mean, empty.stddev, empty.p99, empty.latest), (None, None, None, None));
+    }
+}
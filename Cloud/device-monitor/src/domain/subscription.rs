@@ -0,0 +1,209 @@
+// Live Telemetry Subscription Domain Logic
+//
+// This module defines the JSON messages of the WebSocket subscription API
+// and the subscriptions of one connection. A client subscribes to devices,
+// optionally narrowed to some of their metrics, and can change what it is
+// subscribed to at any time without reconnecting.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Largest number of devices one connection can subscribe to
+pub const MAX_SUBSCRIBED_DEVICES: usize = 100;
+
+/// A message sent by a client
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Subscribes to devices, replacing the metrics of devices already subscribed to
+    Subscribe {
+        /// The devices to subscribe to
+        device_ids: Vec<String>,
+        /// The metrics to receive, or every metric if empty
+        #[serde(default)]
+        metrics: Vec<String>,
+    },
+    /// Unsubscribes from devices
+    Unsubscribe {
+        /// The devices to unsubscribe from
+        device_ids: Vec<String>,
+    },
+}
+
+/// A message sent to a client
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Acknowledges a change, listing every current subscription
+    Subscribed {
+        /// The metrics received per device; empty for every metric
+        subscriptions: BTreeMap<String, Vec<String>>,
+    },
+    /// A new telemetry record of a subscribed device
+    Telemetry {
+        /// The record, with only the subscribed metrics
+        telemetry: Box<Telemetry>,
+    },
+    /// A client message could not be applied
+    Error {
+        /// What was wrong with the message
+        message: String,
+    },
+}
+
+/// The devices added and removed by applying a client message
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SubscriptionChange {
+    /// Devices that were not subscribed to before
+    pub added: Vec<String>,
+    /// Devices that are no longer subscribed to
+    pub removed: Vec<String>,
+}
+
+/// The subscriptions of one connection: the metrics received per device
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+    devices: HashMap<String, Vec<String>>,
+}
+
+impl Subscriptions {
+    /// Applies a subscribe or unsubscribe message
+    ///
+    /// # Arguments
+    /// * `message` - The message sent by the client
+    ///
+    /// # Returns
+    /// * `Result<SubscriptionChange, ApiError>` - The devices added and removed, or an
+    ///   invalid query error if the message names no valid device or exceeds the
+    ///   device limit, in which case nothing is changed
+    pub fn apply(&mut self, message: ClientMessage) -> Result<SubscriptionChange, ApiError> {
+        let device_ids = match &message {
+            ClientMessage::Subscribe { device_ids, .. } | ClientMessage::Unsubscribe { device_ids } => device_ids,
+        };
+        if device_ids.is_empty() || device_ids.iter().any(|device_id| device_id.trim().is_empty()) {
+            return Err(ApiError::InvalidQuery("device_ids must name at least one device".to_string()));
+        }
+
+        let mut change = SubscriptionChange::default();
+        match message {
+            ClientMessage::Subscribe { device_ids, metrics } => {
+                let new = device_ids.iter().filter(|device_id| !self.devices.contains_key(*device_id)).count();
+                if self.devices.len() + new > MAX_SUBSCRIBED_DEVICES {
+                    return Err(ApiError::InvalidQuery(format!(
+                        "at most {} devices can be subscribed to",
+                        MAX_SUBSCRIBED_DEVICES
+                    )));
+                }
+                for device_id in device_ids {
+                    if self.devices.insert(device_id.clone(), metrics.clone()).is_none() {
+                        change.added.push(device_id);
+                    }
+                }
+            }
+            ClientMessage::Unsubscribe { device_ids } => {
+                for device_id in device_ids {
+                    if self.devices.remove(&device_id).is_some() {
+                        change.removed.push(device_id);
+                    }
+                }
+            }
+        }
+        Ok(change)
+    }
+
+    /// Removes a device, e.g. after subscribing to its feed failed
+    pub fn remove(&mut self, device_id: &str) {
+        self.devices.remove(device_id);
+    }
+
+    /// Returns every current subscription, for acknowledging a change
+    pub fn summary(&self) -> BTreeMap<String, Vec<String>> {
+        self.devices.iter().map(|(device_id, metrics)| (device_id.clone(), metrics.clone())).collect()
+    }
+
+    /// Narrows a telemetry record to what the connection is subscribed to
+    ///
+    /// # Arguments
+    /// * `telemetry` - A new record of some device
+    ///
+    /// # Returns
+    /// * `Option<Telemetry>` - The record with only the subscribed metrics, or None if
+    ///   the device is not subscribed to or the record has none of its metrics
+    pub fn filter(&self, telemetry: Telemetry) -> Option<Telemetry> {
+        let metrics = self.devices.get(&telemetry.device_id)?;
+        if metrics.is_empty() {
+            return Some(telemetry);
+        }
+
+        telemetry.select_metrics(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(device_id: &str) -> Telemetry {
+        Telemetry::new(
+            device_id.to_string(),
+            [
+                ("temperature".to_string(), "23.5".to_string()),
+                ("voltage".to_string(), "3.3".to_string()),
+            ]
+            .into(),
+            1640995200,
+        )
+    }
+
+    #[test]
+    fn test_subscribe_and_filter() {
+        let mut subscriptions = Subscriptions::default();
+
+        let message: ClientMessage = serde_json::from_str(
+            r#"{"type": "subscribe", "device_ids": ["sensor-001", "sensor-002"], "metrics": ["temperature"]}"#,
+        )
+        .unwrap();
+        let change = subscriptions.apply(message).unwrap();
+        assert_eq!(change.added.len(), 2);
+
+        let filtered = subscriptions.filter(record("sensor-001")).unwrap();
+        assert_eq!(filtered.telemetry_data.keys().collect::<Vec<_>>(), vec!["temperature"]);
+        assert!(subscriptions.filter(record("sensor-003")).is_none());
+
+        // Subscribing again changes the metrics without adding the device
+        let change = subscriptions
+            .apply(ClientMessage::Subscribe { device_ids: vec!["sensor-001".to_string()], metrics: vec![] })
+            .unwrap();
+        assert!(change.added.is_empty());
+        assert_eq!(subscriptions.filter(record("sensor-001")).unwrap().telemetry_data.len(), 2);
+        assert!(subscriptions.filter(Telemetry::new("sensor-002".to_string(), [("humidity".to_string(), "40".to_string())].into(), 1640995200)).is_none());
+
+        let change = subscriptions
+            .apply(ClientMessage::Unsubscribe { device_ids: vec!["sensor-002".to_string(), "sensor-009".to_string()] })
+            .unwrap();
+        assert_eq!(change.removed, vec!["sensor-002".to_string()]);
+        assert_eq!(subscriptions.summary().keys().collect::<Vec<_>>(), vec!["sensor-001"]);
+    }
+
+    #[test]
+    fn test_invalid_subscriptions() {
+        let mut subscriptions = Subscriptions::default();
+
+        assert!(matches!(
+            subscriptions.apply(ClientMessage::Unsubscribe { device_ids: vec![] }),
+            Err(ApiError::InvalidQuery(_))
+        ));
+        assert!(serde_json::from_str::<ClientMessage>(r#"{"type": "resubscribe", "device_ids": ["a"]}"#).is_err());
+
+        let too_many = (0..=MAX_SUBSCRIBED_DEVICES).map(|device| format!("sensor-{}", device)).collect();
+        assert!(matches!(
+            subscriptions.apply(ClientMessage::Subscribe { device_ids: too_many, metrics: vec![] }),
+            Err(ApiError::InvalidQuery(_))
+        ));
+        assert!(subscriptions.summary().is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+// Device Tag Domain Model
+// 
+// This module defines the tags attached to devices (e.g. site, floor,
+// customer). Tags are used to group devices for fleet-level aggregation
+// queries such as "average temperature per site".
+
+use std::collections::{BTreeMap, HashMap};
+use tracing::warn;
+
+/// Tags for every known device, keyed by device ID
+/// 
+/// Example: `{"sensor-001": {"site": "london"}, "sensor-002": {"site": "paris"}}`
+#[derive(Debug, Clone, Default)]
+pub struct DeviceTags {
+    devices: HashMap<String, HashMap<String, String>>,
+}
+
+impl DeviceTags {
+    /// Creates a tag registry from a device ID to tags mapping
+    /// 
+    /// # Arguments
+    /// * `devices` - Tags for each device
+    /// 
+    /// # Returns
+    /// * `Self` - A new DeviceTags instance
+    pub fn new(devices: HashMap<String, HashMap<String, String>>) -> Self {
+        DeviceTags { devices }
+    }
+
+    /// Loads device tags from the `DEVICE_TAGS` environment variable
+    /// 
+    /// The variable holds a JSON object mapping device IDs to their tags.
+    /// When it is unset or invalid no device has tags.
+    pub fn from_env() -> Self {
+        match std::env::var("DEVICE_TAGS") {
+            Ok(json) => serde_json::from_str(&json).map(Self::new).unwrap_or_else(|e| {
+                warn!("Ignoring invalid DEVICE_TAGS: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Groups devices by their value for the given tag
+    /// 
+    /// Devices that do not have the tag are left out.
+    /// 
+    /// # Arguments
+    /// * `tag` - The tag name to group by (e.g. "site")
+    /// 
+    /// # Returns
+    /// * `BTreeMap<String, Vec<String>>` - Device IDs for each tag value, sorted by tag value
+    pub fn group_by(&self, tag: &str) -> BTreeMap<String, Vec<String>> {
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (device_id, tags) in &self.devices {
+            if let Some(value) = tags.get(tag) {
+                groups.entry(value.clone()).or_default().push(device_id.clone());
+            }
+        }
+        for devices in groups.values_mut() {
+            devices.sort();
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_tag() {
+        let tags: HashMap<String, HashMap<String, String>> = serde_json::from_str(r#"{
+            "sensor-2": {"site": "london"},
+            "sensor-1": {"site": "london"},
+            "sensor-3": {"site": "paris"},
+            "sensor-4": {"floor": "2"}
+        }"#).unwrap();
+
+        let groups = DeviceTags::new(tags).group_by("site");
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["london"], vec!["sensor-1", "sensor-2"]);
+        assert_eq!(groups["paris"], vec!["sensor-3"]);
+    }
+}
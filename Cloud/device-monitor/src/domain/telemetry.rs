@@ -71,6 +71,12 @@ pub struct Telemetry {
     /// Uses custom deserializer to handle multiple timestamp formats
     #[serde(deserialize_with = "deserialize_timestamp", default)]
     pub timestamp: Option<i64>,
+
+    /// Random identifier of the device boot session that sent this telemetry
+    /// 
+    /// Lets all traffic from a single boot be grouped, e.g. to spot reboot loops
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
     
     // Cosmos DB metadata fields (not part of business logic)
     #[serde(rename = "_rid", skip_serializing_if = "Option::is_none")]
@@ -136,6 +142,7 @@ impl Telemetry {
             etag: None,
             attachments: None,
             timestamp: Some(timestamp),
+            session_id: None,
         }
     }
 
@@ -189,6 +196,20 @@ impl Telemetry {
             etag: None,
             attachments: None,
             timestamp: Some(timestamp),
+            session_id: None,
         })
     }
+
+    /// Narrows the record to some of its metrics
+    /// 
+    /// # Arguments
+    /// * `metrics` - The metric names to keep
+    /// 
+    /// # Returns
+    /// * `Option<Self>` - The record with only those metrics, or None if it
+    ///   reported none of them
+    pub fn select_metrics(mut self, metrics: &[String]) -> Option<Self> {
+        self.telemetry_data.retain(|metric, _| metrics.contains(metric));
+        (!self.telemetry_data.is_empty()).then_some(self)
+    }
 }
@@ -0,0 +1,106 @@
+// Telemetry Time Range Domain Logic
+//
+// This module defines the time window a telemetry read is restricted to.
+// The window is applied as a Cosmos DB query filter, so only the records a
+// client asked for are read from the database.
+
+use crate::domain::error::ApiError;
+
+/// An inclusive window of Unix timestamps, unbounded on either side if not given
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    /// Earliest timestamp to include
+    pub from: Option<i64>,
+    /// Latest timestamp to include
+    pub to: Option<i64>,
+}
+
+impl TimeRange {
+    /// Builds a time range from the `from` and `to` query parameters
+    ///
+    /// # Arguments
+    /// * `from` - Earliest Unix timestamp to include
+    /// * `to` - Latest Unix timestamp to include
+    ///
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The range, or an invalid query error if it ends before it starts
+    pub fn parse(from: Option<i64>, to: Option<i64>) -> Result<Self, ApiError> {
+        if let (Some(from), Some(to)) = (from, to) {
+            if to < from {
+                return Err(ApiError::InvalidQuery(format!(
+                    "to ({}) must not be before from ({})",
+                    to, from
+                )));
+            }
+        }
+        Ok(TimeRange { from, to })
+    }
+
+    /// Returns true if neither bound is set
+    pub fn is_unbounded(&self) -> bool {
+        self.from.is_none() && self.to.is_none()
+    }
+
+    /// Tells an empty window apart from an unknown device
+    ///
+    /// A device that reported nothing within a bounded window is not an error:
+    /// the window is simply empty. Over an unbounded range, no records at all
+    /// means the device has never reported.
+    ///
+    /// # Arguments
+    /// * `device_id` - The device that was read
+    /// * `records` - How many records were read for it within this range
+    ///
+    /// # Returns
+    /// * `Result<(), ApiError>` - Ok, or a device not found error if the device has no telemetry at all
+    pub fn check_device_found(&self, device_id: &str, records: usize) -> Result<(), ApiError> {
+        if records == 0 && self.is_unbounded() {
+            return Err(ApiError::DeviceNotFound(device_id.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Renders the range as conditions to append to a Cosmos DB `WHERE` clause
+    ///
+    /// # Returns
+    /// * `String` - ` AND ...` conditions on `c.timestamp`, empty if unbounded
+    pub fn query_filter(&self) -> String {
+        let mut filter = String::new();
+        if let Some(from) = self.from {
+            filter.push_str(&format!(" AND c.timestamp >= {}", from));
+        }
+        if let Some(to) = self.to {
+            filter.push_str(&format!(" AND c.timestamp <= {}", to));
+        }
+        filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_range() {
+        let range = TimeRange::parse(Some(1640995200), Some(1641081600)).unwrap();
+        assert_eq!(range.query_filter(), " AND c.timestamp >= 1640995200 AND c.timestamp <= 1641081600");
+        assert!(!range.is_unbounded());
+
+        let open = TimeRange::parse(None, Some(1641081600)).unwrap();
+        assert_eq!(open.query_filter(), " AND c.timestamp <= 1641081600");
+
+        assert!(TimeRange::parse(None, None).unwrap().is_unbounded());
+        assert_eq!(TimeRange::default().query_filter(), "");
+        assert!(matches!(TimeRange::parse(Some(10), Some(5)), Err(ApiError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn test_check_device_found() {
+        assert!(TimeRange::default().check_device_found("device-1", 3).is_ok());
+        assert!(TimeRange { from: Some(1640995200), to: None }.check_device_found("device-1", 0).is_ok());
+        assert!(matches!(
+            TimeRange::default().check_device_found("device-1", 0),
+            Err(ApiError::DeviceNotFound(device_id)) if device_id == "device-1"
+        ));
+    }
+}
@@ -0,0 +1,282 @@
+// Metric Unit Domain Model
+// 
+// This module defines the units telemetry metrics are reported in, and the
+// conversion of their values to other units of the same quantity, so that
+// clients in different locales can read e.g. temperatures in Fahrenheit
+// without converting them themselves.
+
+use std::collections::HashMap;
+
+use tracing::warn;
+
+use crate::domain::error::ApiError;
+use crate::domain::telemetry::Telemetry;
+
+/// Decimal places kept in converted values, hiding floating point noise
+const CONVERTED_DECIMALS: i32 = 6;
+
+/// A physical quantity whose units can be converted into each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quantity {
+    Temperature,
+    Pressure,
+    Voltage,
+}
+
+/// A unit a metric can be reported or converted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Pascal,
+    Hectopascal,
+    Kilopascal,
+    Bar,
+    Psi,
+    Volt,
+    Millivolt,
+}
+
+impl Unit {
+    /// Parses a unit from its name or symbol, ignoring case
+    /// 
+    /// # Arguments
+    /// * `unit` - The unit name (e.g. "fahrenheit") or symbol (e.g. "f")
+    /// 
+    /// # Returns
+    /// * `Option<Self>` - The unit, or None if it is unknown
+    pub fn parse(unit: &str) -> Option<Self> {
+        match unit.trim().to_ascii_lowercase().as_str() {
+            "celsius" | "c" => Some(Unit::Celsius),
+            "fahrenheit" | "f" => Some(Unit::Fahrenheit),
+            "kelvin" | "k" => Some(Unit::Kelvin),
+            "pascal" | "pa" => Some(Unit::Pascal),
+            "hectopascal" | "hpa" => Some(Unit::Hectopascal),
+            "kilopascal" | "kpa" => Some(Unit::Kilopascal),
+            "bar" => Some(Unit::Bar),
+            "psi" => Some(Unit::Psi),
+            "volt" | "v" => Some(Unit::Volt),
+            "millivolt" | "mv" => Some(Unit::Millivolt),
+            _ => None,
+        }
+    }
+
+    /// Returns the quantity the unit measures
+    pub fn quantity(self) -> Quantity {
+        match self {
+            Unit::Celsius | Unit::Fahrenheit | Unit::Kelvin => Quantity::Temperature,
+            Unit::Pascal | Unit::Hectopascal | Unit::Kilopascal | Unit::Bar | Unit::Psi => Quantity::Pressure,
+            Unit::Volt | Unit::Millivolt => Quantity::Voltage,
+        }
+    }
+
+    /// Converts a value in this unit to the base unit of its quantity
+    /// (Celsius, pascal or volt)
+    fn to_base(self, value: f64) -> f64 {
+        match self {
+            Unit::Celsius | Unit::Pascal | Unit::Volt => value,
+            Unit::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+            Unit::Kelvin => value - 273.15,
+            Unit::Hectopascal => value * 100.0,
+            Unit::Kilopascal => value * 1_000.0,
+            Unit::Bar => value * 100_000.0,
+            Unit::Psi => value * 6_894.757_293_168,
+            Unit::Millivolt => value / 1_000.0,
+        }
+    }
+
+    /// Converts a value in the base unit of this unit's quantity to this unit
+    fn in_unit(self, value: f64) -> f64 {
+        match self {
+            Unit::Celsius | Unit::Pascal | Unit::Volt => value,
+            Unit::Fahrenheit => value * 9.0 / 5.0 + 32.0,
+            Unit::Kelvin => value + 273.15,
+            Unit::Hectopascal => value / 100.0,
+            Unit::Kilopascal => value / 1_000.0,
+            Unit::Bar => value / 100_000.0,
+            Unit::Psi => value / 6_894.757_293_168,
+            Unit::Millivolt => value * 1_000.0,
+        }
+    }
+
+    /// Converts a value in this unit to another unit of the same quantity
+    /// 
+    /// # Arguments
+    /// * `value` - The value in this unit
+    /// * `to` - The unit to convert to
+    /// 
+    /// # Returns
+    /// * `f64` - The value in `to`, rounded to hide floating point noise
+    pub fn convert(self, value: f64, to: Unit) -> f64 {
+        let scale = 10f64.powi(CONVERTED_DECIMALS);
+        (to.in_unit(self.to_base(value)) * scale).round() / scale
+    }
+}
+
+/// The unit each metric is reported in, keyed by metric name
+/// 
+/// Example: `{"temperature": "celsius", "pressure": "hpa"}`
+#[derive(Debug, Clone)]
+pub struct MetricUnits {
+    metrics: HashMap<String, Unit>,
+}
+
+impl Default for MetricUnits {
+    /// The units the device firmware reports its standard metrics in
+    fn default() -> Self {
+        MetricUnits::new(HashMap::from([
+            ("temperature".to_string(), Unit::Celsius),
+            ("pressure".to_string(), Unit::Hectopascal),
+            ("voltage".to_string(), Unit::Volt),
+        ]))
+    }
+}
+
+impl MetricUnits {
+    /// Creates a unit registry from a metric name to unit mapping
+    /// 
+    /// # Arguments
+    /// * `metrics` - The unit of each metric
+    /// 
+    /// # Returns
+    /// * `Self` - A new MetricUnits instance
+    pub fn new(metrics: HashMap<String, Unit>) -> Self {
+        MetricUnits { metrics }
+    }
+
+    /// Loads metric units from the `METRIC_UNITS` environment variable
+    /// 
+    /// The variable holds a JSON object mapping metric names to unit names,
+    /// which are added to or replace the defaults. When it is unset or
+    /// invalid the defaults are used, and unknown units are ignored.
+    pub fn from_env() -> Self {
+        let mut units = Self::default();
+        let Ok(json) = std::env::var("METRIC_UNITS") else {
+            return units;
+        };
+
+        match serde_json::from_str::<HashMap<String, String>>(&json) {
+            Ok(metrics) => {
+                for (metric, unit) in metrics {
+                    match Unit::parse(&unit) {
+                        Some(unit) => {
+                            units.metrics.insert(metric, unit);
+                        }
+                        None => warn!("Ignoring unknown unit {} of metric {}", unit, metric),
+                    }
+                }
+            }
+            Err(e) => warn!("Ignoring invalid METRIC_UNITS: {}", e),
+        }
+        units
+    }
+}
+
+/// A requested conversion of metric values to other units
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitConversion {
+    /// The reported and requested unit of each metric to convert
+    metrics: HashMap<String, (Unit, Unit)>,
+}
+
+impl UnitConversion {
+    /// Builds a conversion from the `unit` query parameter
+    /// 
+    /// The parameter holds comma-separated units, at most one per quantity.
+    /// Every metric reported in another unit of a requested unit's quantity
+    /// is converted to it, e.g. `unit=fahrenheit` converts all temperatures.
+    /// 
+    /// # Arguments
+    /// * `unit` - The requested units
+    /// * `units` - The unit each metric is reported in
+    /// 
+    /// # Returns
+    /// * `Result<Option<Self>, ApiError>` - The conversion, None if no unit was
+    ///   requested, or an invalid query error
+    pub fn parse(unit: Option<&str>, units: &MetricUnits) -> Result<Option<Self>, ApiError> {
+        let Some(unit) = unit.filter(|unit| !unit.trim().is_empty()) else {
+            return Ok(None);
+        };
+
+        let mut targets: HashMap<Quantity, Unit> = HashMap::new();
+        for name in unit.split(',') {
+            let target = Unit::parse(name)
+                .ok_or_else(|| ApiError::InvalidQuery(format!("Unknown unit: {}", name.trim())))?;
+            if targets.insert(target.quantity(), target).is_some_and(|other| other != target) {
+                return Err(ApiError::InvalidQuery(format!("Only one unit per quantity can be requested: {}", unit)));
+            }
+        }
+
+        let metrics = units
+            .metrics
+            .iter()
+            .filter_map(|(metric, &from)| {
+                let to = *targets.get(&from.quantity())?;
+                (from != to).then(|| (metric.clone(), (from, to)))
+            })
+            .collect();
+        Ok(Some(UnitConversion { metrics }))
+    }
+
+    /// Converts the values of a record's metrics to the requested units
+    /// 
+    /// Values that are not numbers are left unchanged.
+    /// 
+    /// # Arguments
+    /// * `record` - The telemetry record in reported units
+    /// 
+    /// # Returns
+    /// * `Telemetry` - The record in the requested units
+    pub fn apply(&self, mut record: Telemetry) -> Telemetry {
+        for (metric, value) in record.telemetry_data.iter_mut() {
+            let Some(&(from, to)) = self.metrics.get(metric) else {
+                continue;
+            };
+            if let Some(reported) = value.trim().parse::<f64>().ok().filter(|v| v.is_finite()) {
+                *value = from.convert(reported, to).to_string();
+            }
+        }
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_units() {
+        assert_eq!(Unit::Celsius.convert(23.5, Unit::Fahrenheit), 74.3);
+        assert_eq!(Unit::Celsius.convert(-40.0, Unit::Fahrenheit), -40.0);
+        assert_eq!(Unit::Fahrenheit.convert(212.0, Unit::Kelvin), 373.15);
+        assert_eq!(Unit::Hectopascal.convert(1013.25, Unit::Kilopascal), 101.325);
+        assert_eq!(Unit::Bar.convert(1.0, Unit::Psi), 14.503774);
+        assert_eq!(Unit::Volt.convert(3.3, Unit::Millivolt), 3300.0);
+        assert_eq!(Unit::parse(" KPa "), Some(Unit::Kilopascal));
+        assert_eq!(Unit::parse("furlong"), None);
+    }
+
+    #[test]
+    fn test_apply_conversion() {
+        let units = MetricUnits::default();
+        let conversion = UnitConversion::parse(Some("fahrenheit,mv"), &units).unwrap().unwrap();
+        let data = [
+            ("temperature".to_string(), "20".to_string()),
+            ("voltage".to_string(), "3.3".to_string()),
+            ("pressure".to_string(), "1013.2".to_string()),
+            ("humidity".to_string(), "45".to_string()),
+        ]
+        .into();
+        let record = conversion.apply(Telemetry::new("sensor-001".to_string(), data, 1640995200));
+
+        assert_eq!(record.telemetry_data["temperature"], "68");
+        assert_eq!(record.telemetry_data["voltage"], "3300");
+        assert_eq!(record.telemetry_data["pressure"], "1013.2");
+        assert_eq!(record.telemetry_data["humidity"], "45");
+
+        assert_eq!(UnitConversion::parse(None, &units).unwrap(), None);
+        assert!(matches!(UnitConversion::parse(Some("furlong"), &units), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(UnitConversion::parse(Some("f,k"), &units), Err(ApiError::InvalidQuery(_))));
+    }
+}
@@ -0,0 +1,209 @@
+// Uptime Report Domain Logic
+// 
+// This module turns the gaps in a device's telemetry into an availability
+// percentage over a reporting period, for customer-facing SLA reports. A
+// device counts as up for one expected reporting interval after each
+// record, so only the part of a gap beyond that interval is downtime.
+// Reports can be rendered as CSV for spreadsheets.
+
+use serde::Serialize;
+
+use crate::domain::error::ApiError;
+use crate::domain::export::escape_field;
+use crate::domain::gaps::GapReport;
+
+/// Columns of a CSV uptime report
+const CSV_COLUMNS: [&str; 7] = [
+    "device_id",
+    "from",
+    "to",
+    "interval_secs",
+    "records",
+    "downtime_secs",
+    "availability_percent",
+];
+
+/// Format of an uptime report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UptimeFormat {
+    /// A JSON object
+    Json,
+    /// A CSV file with one line per device
+    Csv,
+}
+
+impl UptimeFormat {
+    /// Parses the `format` query parameter
+    /// 
+    /// # Arguments
+    /// * `format` - `json` or `csv`, case-insensitive
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The format, JSON if not given, or an invalid query error
+    pub fn parse(format: Option<&str>) -> Result<Self, ApiError> {
+        match format.map(str::to_lowercase).as_deref() {
+            None | Some("json") => Ok(UptimeFormat::Json),
+            Some("csv") => Ok(UptimeFormat::Csv),
+            Some(other) => Err(ApiError::InvalidQuery(format!(
+                "Invalid format: {} (expected json or csv)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Availability of one device over the reporting period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceUptime {
+    /// Unique identifier of the device
+    pub device_id: String,
+    /// Number of records in the period
+    pub records: usize,
+    /// Seconds the device was down, i.e. silent for longer than the interval
+    pub downtime_secs: i64,
+    /// Percentage of the period the device was up, rounded to three decimals
+    pub availability_percent: f64,
+}
+
+impl DeviceUptime {
+    /// Computes a device's availability from the gaps in its telemetry
+    /// 
+    /// # Arguments
+    /// * `report` - The device's gaps over the period, detected with a start
+    ///   so a silence at the beginning of the period counts
+    /// 
+    /// # Returns
+    /// * `Self` - The device's downtime and availability
+    pub fn from_gaps(report: &GapReport) -> Self {
+        let period_secs = report.to - report.from.unwrap_or(report.to);
+        // A device without any record in the period was never seen up
+        let downtime_secs = if report.records == 0 {
+            period_secs
+        } else {
+            report
+                .gaps
+                .iter()
+                .map(|gap| gap.duration_secs - report.interval_secs)
+                .sum::<i64>()
+                .clamp(0, period_secs)
+        };
+
+        let availability = if period_secs > 0 {
+            100.0 * (period_secs - downtime_secs) as f64 / period_secs as f64
+        } else {
+            100.0
+        };
+
+        DeviceUptime {
+            device_id: report.device_id.clone(),
+            records: report.records,
+            downtime_secs,
+            availability_percent: (availability * 1000.0).round() / 1000.0,
+        }
+    }
+}
+
+/// Availability of a set of devices over a reporting period
+#[derive(Debug, Clone, Serialize)]
+pub struct UptimeReport {
+    /// Start of the period
+    pub from: i64,
+    /// End of the period
+    pub to: i64,
+    /// The expected reporting interval, in seconds
+    pub interval_secs: i64,
+    /// Mean availability of the devices, or None if no device was reported on
+    pub availability_percent: Option<f64>,
+    /// The availability of each device, sorted by device ID
+    pub devices: Vec<DeviceUptime>,
+}
+
+impl UptimeReport {
+    /// Collects the devices' availabilities into a report
+    /// 
+    /// # Arguments
+    /// * `from` - Start of the period
+    /// * `to` - End of the period
+    /// * `interval_secs` - The expected reporting interval, in seconds
+    /// * `devices` - The availability of each device
+    pub fn new(from: i64, to: i64, interval_secs: i64, mut devices: Vec<DeviceUptime>) -> Self {
+        devices.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+
+        let availability_percent = (!devices.is_empty()).then(|| {
+            let mean = devices.iter().map(|device| device.availability_percent).sum::<f64>() / devices.len() as f64;
+            (mean * 1000.0).round() / 1000.0
+        });
+
+        UptimeReport { from, to, interval_secs, availability_percent, devices }
+    }
+
+    /// Renders the report as CSV lines, one per device after the header
+    /// 
+    /// # Returns
+    /// * `Vec<String>` - The lines, each including its line break
+    pub fn csv_lines(&self) -> Vec<String> {
+        let rows = self.devices.iter().map(|device| {
+            format!(
+                "{},{},{},{},{},{},{}\r\n",
+                escape_field(&device.device_id),
+                self.from,
+                self.to,
+                self.interval_secs,
+                device.records,
+                device.downtime_secs,
+                device.availability_percent
+            )
+        });
+        std::iter::once(format!("{}\r\n", CSV_COLUMNS.join(",")))
+            .chain(rows)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::gaps::GapDetector;
+
+    fn uptime(timestamps: &[i64], from: i64, to: i64) -> DeviceUptime {
+        let mut detector = GapDetector::new(60, Some(from));
+        for timestamp in timestamps {
+            detector.observe(*timestamp);
+        }
+        DeviceUptime::from_gaps(&detector.finish("sensor-001", Some(from), to))
+    }
+
+    #[test]
+    fn test_availability_from_gaps() {
+        // Silent from 100 to 460: down for the 300 seconds after the first interval
+        let device = uptime(&[0, 60, 100, 460, 520, 580], 0, 600);
+        assert_eq!(device.downtime_secs, 300);
+        assert_eq!(device.availability_percent, 50.0);
+        assert_eq!(device.records, 6);
+
+        // A device that never reported in the period was down for all of it
+        assert_eq!(uptime(&[], 0, 600).availability_percent, 0.0);
+        assert_eq!(uptime(&[0, 30], 0, 90).availability_percent, 100.0);
+        assert_eq!(uptime(&[0], 0, 180).availability_percent, 33.333);
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let healthy = DeviceUptime {
+            device_id: "sensor,000".to_string(),
+            records: 10,
+            downtime_secs: 0,
+            availability_percent: 100.0,
+        };
+        let report = UptimeReport::new(0, 600, 60, vec![uptime(&[], 0, 600), healthy]);
+
+        assert_eq!(report.availability_percent, Some(50.0));
+        assert_eq!(report.csv_lines(), vec![
+            "device_id,from,to,interval_secs,records,downtime_secs,availability_percent\r\n",
+            "\"sensor,000\",0,600,60,10,0,100\r\n",
+            "sensor-001,0,600,60,0,600,0\r\n",
+        ]);
+        assert_eq!(UptimeFormat::parse(Some("CSV")).unwrap(), UptimeFormat::Csv);
+        assert!(matches!(UptimeFormat::parse(Some("xml")), Err(ApiError::InvalidQuery(_))));
+    }
+}
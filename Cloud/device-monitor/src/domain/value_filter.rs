@@ -0,0 +1,194 @@
+// Telemetry Value Filter Domain Logic
+// 
+// This module defines filters on metric values, such as
+// `temperature>30`, that restrict a telemetry read to the readings that
+// breached a limit. Filters are parsed and validated server-side and only
+// then rendered into the Cosmos DB query, so client input never reaches
+// the query text unchecked.
+
+use crate::domain::error::ApiError;
+
+/// Largest number of conditions a filter may combine
+const MAX_CONDITIONS: usize = 10;
+
+/// Comparison of a metric's value with a threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    /// Operators in the order they are matched, longest first
+    const OPERATORS: [(&'static str, Comparison); 7] = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        ("!=", Comparison::NotEqual),
+        ("==", Comparison::Equal),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+        ("=", Comparison::Equal),
+    ];
+
+    /// Renders the comparison as a Cosmos DB SQL operator
+    fn query_operator(self) -> &'static str {
+        match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Equal => "=",
+            Comparison::NotEqual => "!=",
+        }
+    }
+}
+
+/// A condition on the value of one metric
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    /// The metric whose value is compared
+    pub metric: String,
+    /// How the value is compared with the threshold
+    pub comparison: Comparison,
+    /// The threshold
+    pub value: f64,
+}
+
+impl Condition {
+    /// Parses a condition such as `temperature>30`
+    fn parse(condition: &str) -> Result<Self, ApiError> {
+        let invalid = || ApiError::InvalidQuery(format!("Invalid where condition: {}", condition.trim()));
+
+        let start = condition.find(['<', '>', '=', '!']).ok_or_else(invalid)?;
+        let (metric, rest) = condition.split_at(start);
+        let (operator, comparison) = Comparison::OPERATORS
+            .iter()
+            .find(|(operator, _)| rest.starts_with(operator))
+            .ok_or_else(invalid)?;
+
+        // Metric names are rendered into the query, so only plain names are accepted
+        let metric = metric.trim();
+        let plain = metric.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if metric.is_empty() || !plain {
+            return Err(invalid());
+        }
+        let value = rest[operator.len()..]
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(invalid)?;
+
+        Ok(Condition { metric: metric.to_string(), comparison: *comparison, value })
+    }
+}
+
+/// Conditions on metric values a record must all meet, or none to keep every record
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValueFilter {
+    conditions: Vec<Condition>,
+}
+
+impl ValueFilter {
+    /// A filter keeping every record
+    pub const NONE: ValueFilter = ValueFilter { conditions: Vec::new() };
+
+    /// Builds a filter from the `where` query parameter
+    /// 
+    /// # Arguments
+    /// * `filter` - Comma-separated conditions combined with AND, each a
+    ///   metric, one of `>`, `>=`, `<`, `<=`, `=` and `!=`, and a number,
+    ///   e.g. "temperature>30,humidity<=20"
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The filter, empty if none was given, or an invalid query error
+    pub fn parse(filter: Option<&str>) -> Result<Self, ApiError> {
+        let Some(filter) = filter.filter(|filter| !filter.trim().is_empty()) else {
+            return Ok(Self::default());
+        };
+
+        let conditions = filter.split(',').map(Condition::parse).collect::<Result<Vec<_>, _>>()?;
+        if conditions.len() > MAX_CONDITIONS {
+            return Err(ApiError::InvalidQuery(format!(
+                "at most {} where conditions can be combined",
+                MAX_CONDITIONS
+            )));
+        }
+        Ok(ValueFilter { conditions })
+    }
+
+    /// Returns true if the filter keeps every record
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Renders the filter as conditions to append to a Cosmos DB `WHERE` clause
+    /// 
+    /// Metric values are stored as strings, so they are converted to numbers
+    /// first; records whose value is missing or not a number are left out.
+    /// 
+    /// # Returns
+    /// * `String` - ` AND ...` conditions on `c.telemetry_data`, empty if the filter is empty
+    pub fn query_filter(&self) -> String {
+        self.conditions
+            .iter()
+            .map(|condition| {
+                format!(
+                    " AND StringToNumber(c.telemetry_data[\"{}\"]) {} {}",
+                    condition.metric,
+                    condition.comparison.query_operator(),
+                    condition.value
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value_filter() {
+        let filter = ValueFilter::parse(Some("temperature>30, humidity <= 20.5,status!=0")).unwrap();
+        assert_eq!(
+            filter.query_filter(),
+            " AND StringToNumber(c.telemetry_data[\"temperature\"]) > 30 \
+             AND StringToNumber(c.telemetry_data[\"humidity\"]) <= 20.5 \
+             AND StringToNumber(c.telemetry_data[\"status\"]) != 0"
+        );
+        assert_eq!(ValueFilter::parse(Some("pressure==-1.5")).unwrap().query_filter(),
+            " AND StringToNumber(c.telemetry_data[\"pressure\"]) = -1.5");
+
+        assert!(ValueFilter::parse(None).unwrap().is_empty());
+        assert_eq!(ValueFilter::NONE.query_filter(), "");
+    }
+
+    #[test]
+    fn test_reject_unsafe_or_invalid_conditions() {
+        for filter in [
+            "temperature",
+            ">30",
+            "temperature>",
+            "temperature>hot",
+            "temperature>NaN",
+            "temperature=>30",
+            "temp\"] OR 1=1 --<5",
+            "c.id<>1",
+            "temperature>30 OR true",
+        ] {
+            assert!(
+                matches!(ValueFilter::parse(Some(filter)), Err(ApiError::InvalidQuery(_))),
+                "accepted {}",
+                filter
+            );
+        }
+
+        let too_many = ["temperature>1"; MAX_CONDITIONS + 1].join(",");
+        assert!(matches!(ValueFilter::parse(Some(&too_many)), Err(ApiError::InvalidQuery(_))));
+    }
+}
@@ -0,0 +1,10 @@
+// GraphQL API Module
+// 
+// This module exposes devices, telemetry and aggregates through a GraphQL
+// schema, so dashboard and integration consumers can fetch exactly the
+// fields they need in one round trip instead of chaining REST calls.
+
+pub mod schema;
+
+// Re-export the schema for convenient access
+pub use schema::{build_schema, TelemetrySchema};
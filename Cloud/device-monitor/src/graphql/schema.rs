@@ -0,0 +1,454 @@
+// GraphQL Schema
+// 
+// This module defines the GraphQL schema of the device monitoring service.
+// Devices come from the device index, and their telemetry and aggregates
+// are resolved with the same Cosmos DB queries and domain logic as the
+// REST endpoints, so both APIs return the same data.
+
+use std::collections::HashMap;
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Enum, Error, ErrorExtensions, Json, Object,
+    Result, Schema, SimpleObject,
+};
+use chrono::Utc;
+use chrono_tz::Tz;
+use rocket::http::Status;
+
+use crate::app_state::AppState;
+use crate::domain::aggregate::{aggregate_buckets, parse_bucket, parse_timezone, BucketAggregate};
+use crate::domain::auth::Principal;
+use crate::domain::device::DeviceListing;
+use crate::domain::error::ApiError;
+use crate::domain::export::parse_metrics;
+use crate::domain::paging::PageRequest;
+use crate::domain::telemetry::Telemetry;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+
+/// Deepest selection a query may nest, which bounds how many reads one query fans out to
+const MAX_QUERY_DEPTH: usize = 8;
+
+/// The GraphQL schema served at /iot/graphql
+pub type TelemetrySchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Builds the GraphQL schema
+/// 
+/// # Arguments
+/// * `state` - Application state the resolvers read through
+/// 
+/// # Returns
+/// * `TelemetrySchema` - The schema, ready to execute requests
+pub fn build_schema(state: AppState) -> TelemetrySchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+/// Converts an API error into a GraphQL error
+/// 
+/// The HTTP status the REST API would answer with is kept in the `code`
+/// extension, so clients can tell invalid arguments from server failures.
+fn graphql_error(error: ApiError) -> Error {
+    let message = error.to_string();
+    let status: Status = error.into();
+    Error::new(message).extend_with(|_, extensions| extensions.set("code", status.code))
+}
+
+/// Checks that the caller executing the query may read a device
+/// 
+/// The caller is attached to each request by the route, after the
+/// authentication guard has run.
+fn authorize(ctx: &Context<'_>, device_id: &str) -> Result<()> {
+    let caller = ctx.data::<Principal>()?;
+    if caller.can_read(device_id) {
+        Ok(())
+    } else {
+        Err(graphql_error(ApiError::DeviceForbidden(device_id.to_string())))
+    }
+}
+
+/// Order of telemetry records by timestamp
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "crate::domain::sort_order::SortOrder")]
+pub enum SortOrder {
+    /// Oldest first
+    Asc,
+    /// Newest first
+    Desc,
+}
+
+/// Aggregation function applied to the samples of each bucket
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "crate::domain::aggregate::AggregateFn")]
+pub enum AggregateFn {
+    Avg,
+    Min,
+    Max,
+    Sum,
+    Count,
+}
+
+/// Whether a device is reporting, judged by the age of its last telemetry
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "crate::domain::device::DeviceStatus")]
+pub enum DeviceStatus {
+    /// Reported within the stale threshold
+    Online,
+    /// Reported within the offline threshold, but not the stale one
+    Stale,
+    /// Has not reported within the offline threshold
+    Offline,
+}
+
+/// Filters of a telemetry query
+struct TelemetryFilter {
+    from: Option<i64>,
+    to: Option<i64>,
+    metrics: Option<Vec<String>>,
+    order: Option<SortOrder>,
+    limit: Option<usize>,
+}
+
+/// Reads a device's telemetry as requested by the filters
+/// 
+/// Unlike the REST read endpoint, a device without telemetry is not an
+/// error but an empty list, as usual for GraphQL list fields.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `filter` - The time range, metrics, order and limit
+/// 
+/// # Returns
+/// * `Result<Vec<TelemetryRecord>, ApiError>` - The matching records or an error
+async fn read_telemetry(
+    state: &AppState,
+    device_id: &str,
+    filter: TelemetryFilter,
+) -> Result<Vec<TelemetryRecord>, ApiError> {
+    let range = TimeRange::parse(filter.from, filter.to)?;
+    let page = PageRequest::parse(filter.limit, None)?;
+    let order = filter.order.map(Into::into).unwrap_or_default();
+
+    let mut telemetry = state.query_cache.query_telemetry(device_id, &range, &ValueFilter::NONE, order, page.as_ref())
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    if let Some(page) = page {
+        telemetry.truncate(page.limit);
+    }
+
+    let metrics = filter.metrics.map(|metrics| metrics.join(","));
+    let telemetry = match parse_metrics(metrics.as_deref()) {
+        Some(metrics) => telemetry
+            .into_iter()
+            .filter_map(|record| record.select_metrics(&metrics))
+            .collect(),
+        None => telemetry,
+    };
+
+    Ok(telemetry.into_iter().map(TelemetryRecord).collect())
+}
+
+/// Aggregates a device's metric into time buckets
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to aggregate
+/// * `query` - The validated metric, bucket width, function, time zone and time range
+/// 
+/// # Returns
+/// * `Result<Aggregation, ApiError>` - The per-bucket aggregates or an error
+async fn aggregate(state: &AppState, device_id: &str, query: AggregateQuery) -> Result<Aggregation, ApiError> {
+    let AggregateQuery { metric, bucket_secs, function, tz, range } = query;
+    let telemetry = state.query_cache
+        .query_telemetry(device_id, &range, &ValueFilter::NONE, crate::domain::sort_order::SortOrder::Asc, None)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    range.check_device_found(device_id, telemetry.len())?;
+
+    let buckets = aggregate_buckets(&telemetry, &metric, bucket_secs, tz, function.into());
+
+    Ok(Aggregation {
+        device_id: device_id.to_string(),
+        metric,
+        function,
+        bucket_secs,
+        tz: tz.name().to_string(),
+        buckets: buckets.into_iter().map(Bucket::from).collect(),
+    })
+}
+
+/// Validated arguments of an aggregation
+struct AggregateQuery {
+    metric: String,
+    bucket_secs: i64,
+    function: AggregateFn,
+    tz: Tz,
+    range: TimeRange,
+}
+
+impl AggregateQuery {
+    /// Validates the arguments of an aggregation before any data is read
+    /// 
+    /// # Arguments
+    /// * `metric` - The metric to aggregate
+    /// * `bucket` - Bucket width, e.g. "15m" or "1h" (default "1h")
+    /// * `function` - The function producing each bucket's `value` (default avg)
+    /// * `tz` - IANA time zone the buckets are aligned in (default UTC)
+    /// * `from` / `to` - The timestamps to include
+    /// 
+    /// # Returns
+    /// * `Result<Self, ApiError>` - The validated arguments or an invalid query error
+    fn parse(
+        metric: String,
+        bucket: Option<&str>,
+        function: Option<AggregateFn>,
+        tz: Option<&str>,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Self, ApiError> {
+        if metric.trim().is_empty() {
+            return Err(ApiError::InvalidQuery("metric is required".to_string()));
+        }
+        Ok(AggregateQuery {
+            metric,
+            bucket_secs: parse_bucket(bucket.unwrap_or("1h"))?,
+            function: function.unwrap_or(AggregateFn::Avg),
+            tz: parse_timezone(tz)?,
+            range: TimeRange::parse(from, to)?,
+        })
+    }
+}
+
+/// The root of every GraphQL query
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Every device that has reported telemetry and the caller may read, sorted by ID
+    async fn devices(&self, ctx: &Context<'_>) -> Result<Vec<Device>> {
+        let state = ctx.data::<AppState>()?;
+        let caller = ctx.data::<Principal>()?;
+        let mut devices = state.cosmos_client.read_devices()
+            .await
+            .map_err(|e| graphql_error(ApiError::DatabaseError(e.to_string())))?;
+
+        devices.retain(|device| caller.can_read(&device.device_id));
+        devices.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+        Ok(devices.into_iter().map(Device).collect())
+    }
+
+    /// One device, or null if it never reported telemetry or the caller may not read it
+    async fn device(&self, ctx: &Context<'_>, id: String) -> Result<Option<Device>> {
+        let state = ctx.data::<AppState>()?;
+        if !ctx.data::<Principal>()?.can_read(&id) {
+            return Ok(None);
+        }
+        let devices = state.cosmos_client.read_devices()
+            .await
+            .map_err(|e| graphql_error(ApiError::DatabaseError(e.to_string())))?;
+
+        Ok(devices.into_iter().find(|device| device.device_id == id).map(Device))
+    }
+
+    /// A device's telemetry, newest first unless `order` is ASC
+    #[allow(clippy::too_many_arguments)]
+    async fn telemetry(
+        &self,
+        ctx: &Context<'_>,
+        device_id: String,
+        #[graphql(desc = "Earliest Unix timestamp to include")] from: Option<i64>,
+        #[graphql(desc = "Latest Unix timestamp to include")] to: Option<i64>,
+        #[graphql(desc = "Metrics to return from each record; records with none of them are left out")] metrics: Option<Vec<String>>,
+        order: Option<SortOrder>,
+        #[graphql(desc = "Maximum number of records to return (at most 1000)")] limit: Option<usize>,
+    ) -> Result<Vec<TelemetryRecord>> {
+        let filter = TelemetryFilter { from, to, metrics, order, limit };
+        authorize(ctx, &device_id)?;
+        let state = ctx.data::<AppState>()?;
+        read_telemetry(state, &device_id, filter).await.map_err(graphql_error)
+    }
+
+    /// A device's metric aggregated into time buckets
+    #[allow(clippy::too_many_arguments)]
+    async fn aggregate(
+        &self,
+        ctx: &Context<'_>,
+        device_id: String,
+        metric: String,
+        #[graphql(desc = "Bucket width, e.g. \"15m\" or \"1h\" (default \"1h\")")] bucket: Option<String>,
+        #[graphql(desc = "Function producing each bucket's value (default AVG)")] function: Option<AggregateFn>,
+        #[graphql(desc = "IANA time zone the buckets are aligned in, e.g. \"Europe/Berlin\" (default UTC)")] tz: Option<String>,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Aggregation> {
+        let query = AggregateQuery::parse(metric, bucket.as_deref(), function, tz.as_deref(), from, to).map_err(graphql_error)?;
+        authorize(ctx, &device_id)?;
+        let state = ctx.data::<AppState>()?;
+        aggregate(state, &device_id, query).await.map_err(graphql_error)
+    }
+}
+
+/// A device from the device index
+pub struct Device(DeviceListing);
+
+#[Object]
+impl Device {
+    /// Unique identifier of the device
+    async fn device_id(&self) -> &str {
+        &self.0.device_id
+    }
+
+    /// Unix timestamp of the device's most recent reading or heartbeat
+    async fn last_seen(&self) -> i64 {
+        self.0.last_seen
+    }
+
+    /// Gateway that forwarded the most recent record, if any
+    async fn gateway_id(&self) -> Option<&str> {
+        self.0.gateway_id.as_deref()
+    }
+
+    /// Whether the device is online, stale or offline
+    async fn status(&self, ctx: &Context<'_>) -> Result<DeviceStatus> {
+        let state = ctx.data::<AppState>()?;
+        Ok(state.status_thresholds.classify(self.0.last_seen, Utc::now().timestamp()).into())
+    }
+
+    /// The device's telemetry, newest first unless `order` is ASC
+    async fn telemetry(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Earliest Unix timestamp to include")] from: Option<i64>,
+        #[graphql(desc = "Latest Unix timestamp to include")] to: Option<i64>,
+        #[graphql(desc = "Metrics to return from each record; records with none of them are left out")] metrics: Option<Vec<String>>,
+        order: Option<SortOrder>,
+        #[graphql(desc = "Maximum number of records to return (at most 1000)")] limit: Option<usize>,
+    ) -> Result<Vec<TelemetryRecord>> {
+        let filter = TelemetryFilter { from, to, metrics, order, limit };
+        let state = ctx.data::<AppState>()?;
+        read_telemetry(state, &self.0.device_id, filter).await.map_err(graphql_error)
+    }
+
+    /// The device's metric aggregated into time buckets
+    #[allow(clippy::too_many_arguments)]
+    async fn aggregate(
+        &self,
+        ctx: &Context<'_>,
+        metric: String,
+        #[graphql(desc = "Bucket width, e.g. \"15m\" or \"1h\" (default \"1h\")")] bucket: Option<String>,
+        #[graphql(desc = "Function producing each bucket's value (default AVG)")] function: Option<AggregateFn>,
+        #[graphql(desc = "IANA time zone the buckets are aligned in, e.g. \"Europe/Berlin\" (default UTC)")] tz: Option<String>,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Aggregation> {
+        let query = AggregateQuery::parse(metric, bucket.as_deref(), function, tz.as_deref(), from, to).map_err(graphql_error)?;
+        let state = ctx.data::<AppState>()?;
+        aggregate(state, &self.0.device_id, query).await.map_err(graphql_error)
+    }
+}
+
+/// A telemetry record
+pub struct TelemetryRecord(Telemetry);
+
+#[Object]
+impl TelemetryRecord {
+    /// The device that sent the record
+    async fn device_id(&self) -> &str {
+        &self.0.device_id
+    }
+
+    /// Unix timestamp of the reading
+    async fn timestamp(&self) -> Option<i64> {
+        self.0.timestamp
+    }
+
+    /// Identifier of the device boot session that sent the record
+    async fn session_id(&self) -> Option<&str> {
+        self.0.session_id.as_deref()
+    }
+
+    /// Every reading of the record, as an object of metric names to values
+    async fn data(&self) -> Json<HashMap<String, String>> {
+        Json(self.0.telemetry_data.clone())
+    }
+
+    /// The reading of one metric, or null if the record does not carry it
+    async fn value(&self, metric: String) -> Option<&str> {
+        self.0.telemetry_data.get(&metric).map(String::as_str)
+    }
+}
+
+/// A device's metric aggregated into time buckets
+#[derive(SimpleObject)]
+pub struct Aggregation {
+    /// The device whose telemetry was aggregated
+    pub device_id: String,
+    /// The metric that was aggregated
+    pub metric: String,
+    /// The function applied to produce each bucket's `value`
+    pub function: AggregateFn,
+    /// Width of each bucket, in seconds
+    pub bucket_secs: i64,
+    /// The IANA time zone the buckets are aligned in
+    pub tz: String,
+    /// Buckets with at least one sample, oldest first
+    pub buckets: Vec<Bucket>,
+}
+
+/// Aggregate of a metric over one time bucket
+#[derive(SimpleObject)]
+pub struct Bucket {
+    /// Start of the bucket as a Unix timestamp
+    pub start: i64,
+    /// The requested aggregate of the bucket's samples
+    pub value: Option<f64>,
+    /// Smallest sample in the bucket
+    pub min: Option<f64>,
+    /// Largest sample in the bucket
+    pub max: Option<f64>,
+    /// Mean of the bucket's samples
+    pub avg: Option<f64>,
+    /// Number of samples in the bucket
+    pub count: usize,
+}
+
+impl From<BucketAggregate> for Bucket {
+    fn from(bucket: BucketAggregate) -> Self {
+        Bucket {
+            start: bucket.start,
+            value: bucket.value,
+            min: bucket.min,
+            max: bucket.max,
+            avg: bucket.avg,
+            count: bucket.count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_invalid_arguments_are_rejected_before_reading() {
+        // No application state is attached, so the query fails unless it is
+        // rejected during validation
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish();
+
+        let response = schema
+            .execute(r#"{ aggregate(deviceId: "sensor-001", metric: "temperature", bucket: "1w") { metric } }"#)
+            .await;
+        assert_eq!(response.errors.len(), 1);
+        let extensions = serde_json::to_value(&response.errors[0].extensions).unwrap();
+        assert_eq!(extensions["code"], 400);
+
+        let response = schema
+            .execute(r#"{ telemetry(deviceId: "sensor-001", order: NEWEST) { timestamp } }"#)
+            .await;
+        assert!(!response.errors.is_empty());
+    }
+}
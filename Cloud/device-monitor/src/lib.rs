@@ -9,7 +9,7 @@
 use dotenvy::dotenv;
 use rocket::{
     routes,
-    fairing::{Fairing, Info, Kind},
+    fairing::{AdHoc, Fairing, Info, Kind},
     Request, Response,
 };
 use rocket_cors::{AllowedOrigins, CorsOptions};
@@ -22,9 +22,11 @@ pub mod routes;      // API route handlers
 pub mod services;    // External service integrations (Cosmos DB, Azure Auth)
 pub mod domain;      // Domain models and business logic
 pub mod app_state;   // Application state management
+pub mod graphql;     // GraphQL schema over devices, telemetry and aggregates
 pub mod utils;       // Utility functions and helpers
 
 use crate::app_state::AppState;
+use crate::utils::compression::Compression;
 use crate::utils::tracing::{make_span_with_request_id, on_request, on_response};
 
 /// Rocket fairing for request/response tracing and observability
@@ -106,6 +108,8 @@ impl Application {
         // Configure CORS to allow all origins (for development - should be restricted in production)
         let cors = CorsOptions {
             allowed_origins: AllowedOrigins::All,
+            // Let browser clients read the token for the next telemetry page and the entity tag
+            expose_headers: [routes::read_telemetry::CONTINUATION_HEADER.to_string(), "ETag".to_string()].into(),
             ..Default::default()
         }
         .to_cors()?;
@@ -117,15 +121,84 @@ impl Application {
                 .merge(("secret_key", std::env::var("SECRET_KEY").unwrap()))
                 .merge(("address", "0.0.0.0"))
                 .merge(("port", 8001)))
+            // Attach the GraphQL schema, which reads through the application state
+            .manage(graphql::build_schema(app_state.clone()))
             // Attach application state for dependency injection
             .manage(app_state)
             // Enable CORS for cross-origin requests
             .attach(cors)
             // Add request/response tracing for observability
             .attach(TracingFairing)
+            // Gzip large JSON and CSV responses for clients that accept it
+            .attach(Compression)
+            // Evaluate alert rules in the background once the server is running
+            .attach(AdHoc::on_liftoff("Alert Evaluator", |rocket| Box::pin(async move {
+                if let Some(state) = rocket.state::<AppState>() {
+                    tokio::spawn(state.alert_evaluator.clone().run());
+                }
+            })))
+            // Drop cached query results of devices that store new telemetry
+            .attach(AdHoc::on_liftoff("Query Cache Invalidation", |rocket| Box::pin(async move {
+                if let Some(state) = rocket.state::<AppState>() {
+                    tokio::spawn(state.query_cache.clone().run());
+                }
+            })))
+            // Delete telemetry older than its retention period
+            .attach(AdHoc::on_liftoff("Retention Cleanup", |rocket| Box::pin(async move {
+                if let Some(state) = rocket.state::<AppState>() {
+                    tokio::spawn(state.retention_job.clone().run());
+                }
+            })))
+            // Archive old telemetry to Blob Storage
+            .attach(AdHoc::on_liftoff("Telemetry Archival", |rocket| Box::pin(async move {
+                if let Some(state) = rocket.state::<AppState>() {
+                    tokio::spawn(state.archive_job.clone().run());
+                }
+            })))
             // Mount the telemetry monitoring endpoint
             .mount("/iot/data", routes![
-                routes::read_telemetry::read
+                routes::read_telemetry::read,
+                routes::batch_read::read_batch_route,
+                routes::latest_telemetry::latest_telemetry_route,
+                routes::aggregate_by_tag::aggregate_by_tag_route,
+                routes::aggregate::aggregate_route,
+                routes::compare::compare_route,
+                routes::anomalies::anomalies_route,
+                routes::gaps::gaps_route,
+                routes::stats::stats_route,
+                routes::histogram::histogram_route,
+                routes::rate::rate_route,
+                routes::count::count_route,
+                routes::archives::list_archives_route,
+                routes::export::export_route,
+                routes::stream_telemetry::stream_telemetry_route,
+                routes::subscribe_telemetry::subscribe_telemetry_route,
+                routes::annotations::create_annotation_route,
+                routes::annotations::list_annotations_route,
+                routes::annotations::update_annotation_route,
+                routes::annotations::delete_annotation_route,
+                routes::alerts::create_alert_rule_route,
+                routes::alerts::list_alert_rules_route,
+                routes::alerts::update_alert_rule_route,
+                routes::alerts::delete_alert_rule_route,
+                routes::alerts::list_alerts_route,
+                routes::alerts::acknowledge_alert_route,
+                routes::alerts::resolve_alert_route,
+            ])
+            // Mount the fleet-wide endpoints
+            .mount("/iot", routes![
+                routes::devices::list_devices_route,
+                routes::devices::device_status_route,
+                routes::fleet_summary::fleet_summary_route,
+                routes::fleet_health::fleet_health_route,
+                routes::uptime::uptime_route,
+                routes::graphql::graphql_route,
+                routes::graphql::graphiql_route,
+                routes::retention::retention_route,
+            ])
+            // Mount the Prometheus scrape endpoints
+            .mount("/metrics", routes![
+                routes::device_metrics::device_metrics_route,
             ]);
 
         // Log the server startup information
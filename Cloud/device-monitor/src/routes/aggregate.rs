@@ -0,0 +1,141 @@
+// Time-Bucketed Aggregation Route Handler
+// 
+// This module handles the GET /iot/data/aggregate/<device_id> endpoint for
+// aggregating a device's telemetry metric into fixed-width time buckets,
+// so long ranges can be charted without transferring every raw record.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::aggregate::{aggregate_buckets, parse_bucket, parse_timezone, AggregateFn, DeviceAggregation};
+use crate::domain::error::ApiError;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Query parameters for time-bucketed aggregation
+#[derive(Debug, FromForm)]
+pub struct BucketAggregateQuery {
+    /// The telemetry metric to aggregate (e.g. "temperature")
+    pub metric: String,
+    /// Bucket width, e.g. "15m" or "1h" (default "1h")
+    pub bucket: Option<String>,
+    /// Aggregation function: avg (default), min, max, sum or count
+    #[field(name = "fn")]
+    pub function: Option<String>,
+    /// IANA time zone the buckets are aligned in, e.g. "Europe/Berlin" (default UTC)
+    pub tz: Option<String>,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+}
+
+/// Aggregates a device's metric into time buckets
+/// 
+/// This function performs the following steps:
+/// 1. Validates the metric, bucket width, function, time zone and time range
+/// 2. Reads the device's telemetry within the range
+/// 3. Groups the metric's numeric samples into buckets aligned to the
+///    bucket width in the time zone's local time and aggregates each bucket
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to aggregate
+/// * `query` - The metric, bucket width, function, time zone and time range
+/// 
+/// # Returns
+/// * `Result<DeviceAggregation, ApiError>` - The per-bucket aggregates or an error
+async fn aggregate(
+    state: &AppState,
+    device_id: &str,
+    query: &BucketAggregateQuery,
+) -> Result<DeviceAggregation, ApiError> {
+    info!("Aggregating {} for device {}", query.metric, device_id);
+
+    if query.metric.trim().is_empty() {
+        return Err(ApiError::InvalidQuery("metric is required".to_string()));
+    }
+    let bucket_secs = parse_bucket(query.bucket.as_deref().unwrap_or("1h"))?;
+    let function = AggregateFn::parse(query.function.as_deref().unwrap_or("avg"))?;
+    let tz = parse_timezone(query.tz.as_deref())?;
+    let range = TimeRange::parse(query.from, query.to)?;
+
+    let telemetry = state.query_cache.query_telemetry(device_id, &range, &ValueFilter::NONE, SortOrder::Asc, None)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    range.check_device_found(device_id, telemetry.len())?;
+
+    let buckets = aggregate_buckets(&telemetry, &query.metric, bucket_secs, tz, function);
+
+    info!("Aggregated {} record(s) into {} bucket(s)", telemetry.len(), buckets.len());
+    Ok(DeviceAggregation {
+        device_id: device_id.to_string(),
+        metric: query.metric.clone(),
+        function,
+        bucket_secs,
+        tz: tz.name().to_string(),
+        buckets,
+    })
+}
+
+/// GET endpoint for aggregating a device's metric into time buckets
+/// 
+/// The device's telemetry within the optional `from`/`to` range is read
+/// server-side and the metric's samples are grouped into buckets of the
+/// requested width, aligned in the local time of `tz` so `1d` buckets start
+/// at local midnight. Every bucket reports the min, max, avg and count of
+/// its samples, and `value` holds the result of `fn`. Buckets without
+/// samples are left out.
+/// 
+/// # Arguments
+/// * `device_id` - The device identifier from the URL path
+/// * `query` - The `metric`, `bucket`, `fn`, `tz`, `from` and `to` query parameters
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<DeviceAggregation>, Status>` - Per-bucket aggregates or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/aggregate/sensor-001?metric=temperature&bucket=1h&fn=avg
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "metric": "temperature",
+///   "fn": "avg",
+///   "bucket_secs": 3600,
+///   "tz": "UTC",
+///   "buckets": [
+///     { "start": 1640995200, "value": 23.1, "min": 22.4, "max": 24.0, "avg": 23.1, "count": 60 },
+///     { "start": 1640998800, "value": 23.8, "min": 23.0, "max": 25.2, "avg": 23.8, "count": 60 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/aggregate/<device_id>?<query..>")]
+pub async fn aggregate_route(
+    device_id: &str,
+    query: BucketAggregateQuery,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<DeviceAggregation>, Status> {
+    info!("Received aggregation request for device: {}", device_id);
+
+    match aggregate(state.inner(), device_id, &query).await {
+        Ok(aggregation) => Ok(Json(aggregation)),
+        Err(e) => {
+            error!("Error aggregating telemetry: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
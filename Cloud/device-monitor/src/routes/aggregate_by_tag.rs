@@ -0,0 +1,236 @@
+// Group-By-Tag Aggregation Route Handler
+// 
+// This module handles the GET /iot/data/aggregate/by-tag/<tag> endpoint for
+// aggregating a telemetry metric across devices grouped by a device tag,
+// e.g. the average temperature per site.
+
+use chrono::Utc;
+use futures::StreamExt;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::aggregate::{metric_values, AggregateFn, SampleFold, TagAggregation, TagGroupAggregate};
+use crate::domain::error::ApiError;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::FleetAccess;
+use crate::utils::concurrent_reads::read_all;
+
+/// Query parameters for group-by-tag aggregation
+#[derive(Debug, FromForm)]
+pub struct TagAggregateQuery {
+    /// The telemetry metric to aggregate (e.g. "temperature")
+    pub metric: String,
+    /// Aggregation function: avg (default), min, max, sum or count
+    #[field(name = "fn")]
+    pub function: Option<String>,
+    /// Earliest Unix timestamp to include (default 24 hours before `to`)
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include (default now)
+    pub to: Option<i64>,
+}
+
+/// Window aggregated when `from` is not given, in seconds (24 hours)
+const DEFAULT_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// Longest window that may be aggregated, in seconds (31 days)
+const MAX_WINDOW_SECS: i64 = 31 * 24 * 60 * 60;
+
+/// Resolves the window to aggregate over
+/// 
+/// Every device's telemetry in the window is read, so the window always
+/// has both bounds and may span at most `MAX_WINDOW_SECS`.
+/// 
+/// # Arguments
+/// * `from` - Earliest Unix timestamp to include, 24 hours before `to` if not given
+/// * `to` - Latest Unix timestamp to include, now if not given
+/// 
+/// # Returns
+/// * `Result<TimeRange, ApiError>` - The bounded window or an invalid query error
+fn aggregation_window(from: Option<i64>, to: Option<i64>) -> Result<TimeRange, ApiError> {
+    let to = to.unwrap_or_else(|| Utc::now().timestamp());
+    let from = from.unwrap_or(to.saturating_sub(DEFAULT_WINDOW_SECS));
+    let range = TimeRange::parse(Some(from), Some(to))?;
+
+    if to.saturating_sub(from) > MAX_WINDOW_SECS {
+        return Err(ApiError::InvalidQuery(format!(
+            "The window may span at most {} days",
+            MAX_WINDOW_SECS / (24 * 60 * 60)
+        )));
+    }
+    Ok(range)
+}
+
+/// Folds the samples of a device's metric within a window
+/// 
+/// The telemetry is streamed and folded page by page, so only the running
+/// totals are kept in memory however much the device reported.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to read
+/// * `metric` - The metric name (e.g. "temperature")
+/// * `range` - The window to read
+/// 
+/// # Returns
+/// * `Result<SampleFold, Box<dyn std::error::Error>>` - The folded samples or an error
+async fn fold_device(
+    state: &AppState,
+    device_id: &str,
+    metric: &str,
+    range: &TimeRange,
+) -> Result<SampleFold, Box<dyn std::error::Error>> {
+    let mut pages = state.cosmos_client.stream_telemetry(device_id, range, &ValueFilter::NONE, SortOrder::Asc)?;
+    let mut fold = SampleFold::default();
+    while let Some(page) = pages.next().await {
+        fold.extend(metric_values(&page?, metric));
+    }
+    Ok(fold)
+}
+
+/// Aggregates a metric over all devices grouped by a tag
+/// 
+/// This function performs the following steps:
+/// 1. Returns a cached result if an identical query ran recently
+/// 2. Groups the known devices by their value for the tag
+/// 3. Streams the telemetry of every tagged device within the window,
+///    `READ_CONCURRENCY` devices at a time, folding the metric's samples
+///    page by page
+/// 4. Aggregates the folded samples per group and caches the result
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client and device tags
+/// * `tag` - The tag name to group by
+/// * `query` - The metric, aggregation function and window
+/// 
+/// # Returns
+/// * `Result<TagAggregation, ApiError>` - The per-group aggregates or an error
+async fn aggregate_by_tag(
+    state: &AppState,
+    tag: &str,
+    query: &TagAggregateQuery,
+) -> Result<TagAggregation, ApiError> {
+    info!("Aggregating {} by tag {}", query.metric, tag);
+
+    if query.metric.trim().is_empty() {
+        return Err(ApiError::InvalidQuery("metric is required".to_string()));
+    }
+    let function = AggregateFn::parse(query.function.as_deref().unwrap_or("avg"))?;
+    let range = aggregation_window(query.from, query.to)?;
+
+    // Serve identical queries from the cache
+    let cache_key = format!("{}|{}|{:?}|{:?}|{:?}", tag, query.metric, function, query.from, query.to);
+    if let Some(cached) = state.tag_aggregate_cache.get(&cache_key) {
+        info!("Serving tag aggregation from cache");
+        return Ok(cached);
+    }
+
+    let groups = state.device_tags.group_by(tag);
+    if groups.is_empty() {
+        return Err(ApiError::InvalidQuery(format!("No devices are tagged with {}", tag)));
+    }
+
+    // Fan out one telemetry stream per device, whatever its group, so the
+    // read concurrency limit applies to the whole query
+    let members: Vec<(usize, &String)> = groups
+        .values()
+        .enumerate()
+        .flat_map(|(group, devices)| devices.iter().map(move |device_id| (group, device_id)))
+        .collect();
+    let range = &range;
+    let folds = read_all(
+        &members,
+        |(_, device_id)| fold_device(state, device_id, &query.metric, range),
+        state.read_concurrency,
+    )
+    .await?;
+
+    let mut group_folds = vec![SampleFold::default(); groups.len()];
+    for ((group, _), fold) in members.iter().zip(&folds) {
+        group_folds[*group].merge(fold);
+    }
+
+    let results: Vec<TagGroupAggregate> = groups
+        .into_iter()
+        .zip(group_folds)
+        .map(|((tag_value, devices), fold)| TagGroupAggregate {
+            tag_value,
+            value: fold.value(function),
+            devices: devices.len(),
+            samples: fold.samples(),
+        })
+        .collect();
+
+    let aggregation = TagAggregation {
+        tag: tag.to_string(),
+        metric: query.metric.clone(),
+        function,
+        from: range.from.unwrap_or_default(),
+        to: range.to.unwrap_or_default(),
+        groups: results,
+    };
+
+    state.tag_aggregate_cache.insert(cache_key, aggregation.clone());
+
+    info!("Aggregated {} group(s) for tag {}", aggregation.groups.len(), tag);
+    Ok(aggregation)
+}
+
+/// GET endpoint for aggregating a metric across devices grouped by tag
+/// 
+/// Devices are grouped by their value for the given tag (tags are configured
+/// through `DEVICE_TAGS`), the telemetry of each device within the window is
+/// streamed server-side, and the metric is aggregated per group. The window
+/// defaults to the 24 hours before `to` (default now) and may span at most
+/// 31 days. Results are cached briefly.
+/// 
+/// # Arguments
+/// * `tag` - The tag name from the URL path
+/// * `query` - The `metric` and optional `fn`, `from` and `to` query parameters
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<TagAggregation>, Status>` - Per-group aggregates or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/aggregate/by-tag/site?metric=temperature&fn=avg&from=1640995200&to=1641081600
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "tag": "site",
+///   "metric": "temperature",
+///   "fn": "avg",
+///   "from": 1640995200,
+///   "to": 1641081600,
+///   "groups": [
+///     { "tag_value": "london", "value": 21.4, "devices": 2, "samples": 240 },
+///     { "tag_value": "paris", "value": 23.1, "devices": 1, "samples": 120 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read every device.
+#[get("/aggregate/by-tag/<tag>?<query..>")]
+pub async fn aggregate_by_tag_route(
+    tag: &str,
+    query: TagAggregateQuery,
+    state: &State<AppState>,
+    _access: FleetAccess,
+) -> Result<Json<TagAggregation>, Status> {
+    info!("Received tag aggregation request for tag: {}", tag);
+
+    match aggregate_by_tag(state.inner(), tag, &query).await {
+        Ok(aggregation) => Ok(Json(aggregation)),
+        Err(e) => {
+            error!("Error aggregating telemetry by tag: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
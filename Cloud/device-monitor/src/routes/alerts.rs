@@ -0,0 +1,335 @@
+// Alert Route Handlers
+// 
+// This module handles the /iot/data/alerts/rules/<device_id> endpoints for
+// creating, listing, editing and deleting a device's threshold alert rules,
+// the /iot/data/alerts/<device_id> endpoint listing the alerts they raised,
+// and the endpoints acknowledging and resolving an alert. Rules are
+// evaluated in the background by the alert evaluator.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::alert::{Alert, AlertActionError, AlertRule, AlertRuleRequest};
+use crate::domain::error::ApiError;
+use crate::domain::time_range::TimeRange;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::{DeviceAccess, DeviceWriteAccess};
+
+/// Loads an alert rule, returning a not found error if it does not exist
+async fn load_rule(state: &AppState, device_id: &str, rule_id: &str) -> Result<AlertRule, ApiError> {
+    state.cosmos_client.read_alert_rule(device_id, rule_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApiError::AlertRuleNotFound(rule_id.to_string()))
+}
+
+/// Validates and stores a new alert rule
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device to watch
+/// * `request` - The rule's condition
+/// 
+/// # Returns
+/// * `Result<AlertRule, ApiError>` - The stored rule or an error
+async fn create_rule(
+    state: &AppState,
+    device_id: &str,
+    request: AlertRuleRequest,
+) -> Result<AlertRule, ApiError> {
+    if device_id.trim().is_empty() {
+        return Err(ApiError::InvalidDeviceId);
+    }
+
+    let rule = AlertRule::parse(device_id.to_string(), request)
+        .map_err(|e| ApiError::InvalidAlertRule(e.to_string()))?;
+
+    state.cosmos_client.upsert_alert_rule(&rule)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Alert rule {} created for device {}", rule.id, device_id);
+    Ok(rule)
+}
+
+/// Retrieves the alert rules of a device
+async fn list_rules(state: &AppState, device_id: &str) -> Result<Vec<AlertRule>, ApiError> {
+    state.cosmos_client.read_alert_rules(device_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))
+}
+
+/// Replaces the condition of an existing alert rule
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device the rule belongs to
+/// * `rule_id` - The rule to edit
+/// * `request` - The new condition
+/// 
+/// # Returns
+/// * `Result<AlertRule, ApiError>` - The updated rule or an error
+async fn update_rule(
+    state: &AppState,
+    device_id: &str,
+    rule_id: &str,
+    request: AlertRuleRequest,
+) -> Result<AlertRule, ApiError> {
+    let rule = load_rule(state, device_id, rule_id)
+        .await?
+        .update(request)
+        .map_err(|e| ApiError::InvalidAlertRule(e.to_string()))?;
+
+    state.cosmos_client.upsert_alert_rule(&rule)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Alert rule {} updated for device {}", rule.id, device_id);
+    Ok(rule)
+}
+
+/// Deletes an existing alert rule
+async fn delete_rule(state: &AppState, device_id: &str, rule_id: &str) -> Result<(), ApiError> {
+    load_rule(state, device_id, rule_id).await?;
+
+    state.cosmos_client.delete_alert_rule(device_id, rule_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Alert rule {} deleted for device {}", rule_id, device_id);
+    Ok(())
+}
+
+/// Retrieves the alerts raised for a device within a time range
+async fn list_alerts(state: &AppState, device_id: &str, from: Option<i64>, to: Option<i64>) -> Result<Vec<Alert>, ApiError> {
+    let range = TimeRange::parse(from, to)?;
+
+    state.cosmos_client.read_alerts(device_id, &range)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))
+}
+
+/// Acknowledges or resolves an alert
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device the alert is about
+/// * `alert_id` - The alert to handle
+/// * `handle` - Applies the action to the stored alert
+/// 
+/// # Returns
+/// * `Result<Alert, ApiError>` - The updated alert or an error
+async fn handle_alert(
+    state: &AppState,
+    device_id: &str,
+    alert_id: &str,
+    handle: impl FnOnce(Alert) -> Result<Alert, AlertActionError>,
+) -> Result<Alert, ApiError> {
+    let alert = state.cosmos_client.read_alert(device_id, alert_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApiError::AlertNotFound(alert_id.to_string()))?;
+
+    let alert = handle(alert).map_err(|e| ApiError::AlertConflict(e.to_string()))?;
+
+    state.cosmos_client.insert_alert(&alert)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Alert {} of device {} is now {:?}", alert_id, device_id, alert.status());
+    Ok(alert)
+}
+
+/// Converts an alert result into a route response, logging failures
+fn respond<T>(result: Result<T, ApiError>) -> Result<T, Status> {
+    result.map_err(|e| {
+        error!("Alert request failed: {}", e);
+        // Convert the API error to an appropriate HTTP status code
+        e.into()
+    })
+}
+
+/// POST endpoint for creating a threshold alert rule on a device
+/// 
+/// `operator` is one of `gt`, `gte`, `lt`, `lte`, `eq` and `ne`. An alert is
+/// raised once the metric has met the condition for `duration_secs` seconds
+/// (default 0, at most one day), and again only after it stopped meeting it.
+/// 
+/// # Example Request
+/// ```json
+/// {
+///   "metric": "temperature",
+///   "operator": "gt",
+///   "value": 30.0,
+///   "duration_secs": 300
+/// }
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "id": "8b2e...",
+///   "device_id": "sensor-001",
+///   "metric": "temperature",
+///   "operator": "gt",
+///   "value": 30.0,
+///   "duration_secs": 300,
+///   "created_at": "2025-01-01T00:00:00Z",
+///   "updated_at": "2025-01-01T00:00:00Z"
+/// }
+/// ```
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[post("/alerts/rules/<device_id>", format = "json", data = "<request>")]
+pub async fn create_alert_rule_route(
+    state: &State<AppState>,
+    _access: DeviceWriteAccess,
+    device_id: &str,
+    request: Json<AlertRuleRequest>,
+) -> Result<Json<AlertRule>, Status> {
+    info!("Received alert rule for device: {}", device_id);
+    respond(create_rule(state.inner(), device_id, request.into_inner()).await).map(Json)
+}
+
+/// GET endpoint for listing a device's alert rules, oldest first
+/// 
+/// An empty array is returned when the device has none.
+/// 
+/// Requires credentials that may read the device.
+#[get("/alerts/rules/<device_id>")]
+pub async fn list_alert_rules_route(
+    state: &State<AppState>,
+    _access: DeviceAccess,
+    device_id: &str,
+) -> Result<Json<Vec<AlertRule>>, Status> {
+    info!("Received alert rule listing request for device: {}", device_id);
+    respond(list_rules(state.inner(), device_id).await).map(Json)
+}
+
+/// PUT endpoint for editing an alert rule
+/// 
+/// Takes the same body as creation and replaces the condition. A breach
+/// in progress starts over under the new condition.
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[put("/alerts/rules/<device_id>/<rule_id>", format = "json", data = "<request>")]
+pub async fn update_alert_rule_route(
+    state: &State<AppState>,
+    _access: DeviceWriteAccess,
+    device_id: &str,
+    rule_id: &str,
+    request: Json<AlertRuleRequest>,
+) -> Result<Json<AlertRule>, Status> {
+    info!("Received alert rule update for device {}: {}", device_id, rule_id);
+    respond(update_rule(state.inner(), device_id, rule_id, request.into_inner()).await).map(Json)
+}
+
+/// DELETE endpoint for removing an alert rule
+/// 
+/// Returns 204 No Content on success and 404 if the rule does not exist.
+/// Alerts the rule already raised are kept.
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[delete("/alerts/rules/<device_id>/<rule_id>")]
+pub async fn delete_alert_rule_route(
+    state: &State<AppState>,
+    _access: DeviceWriteAccess,
+    device_id: &str,
+    rule_id: &str,
+) -> Result<Status, Status> {
+    info!("Received alert rule deletion for device {}: {}", device_id, rule_id);
+    respond(delete_rule(state.inner(), device_id, rule_id).await).map(|()| Status::NoContent)
+}
+
+/// GET endpoint for listing the alerts raised for a device
+/// 
+/// Returns the alerts triggered within the optional `from`/`to` range (Unix
+/// timestamps), newest first. `acknowledged` and `resolved` record who
+/// handled an alert and when, and are `null` until it is.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/alerts/sensor-001?from=1640995200
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// [
+///   {
+///     "id": "f41c...",
+///     "rule_id": "8b2e...",
+///     "device_id": "sensor-001",
+///     "metric": "temperature",
+///     "operator": "gt",
+///     "threshold": 30.0,
+///     "value": 31.2,
+///     "breach_started": 1640995200,
+///     "triggered_at": 1640995500,
+///     "acknowledged": { "by": "alice", "at": "2022-01-01T00:10:00Z" },
+///     "resolved": null
+///   }
+/// ]
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/alerts/<device_id>?<from>&<to>")]
+pub async fn list_alerts_route(
+    state: &State<AppState>,
+    _access: DeviceAccess,
+    device_id: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Json<Vec<Alert>>, Status> {
+    info!("Received alert listing request for device: {}", device_id);
+    respond(list_alerts(state.inner(), device_id, from, to).await).map(Json)
+}
+
+/// POST endpoint for acknowledging an alert
+/// 
+/// Records that the caller (JWT subject or API key name) took the alert
+/// on. Acknowledging an acknowledged alert keeps the first
+/// acknowledgement. Returns the updated alert, 404 if it does not exist
+/// and 409 if it was already resolved.
+/// 
+/// # Example Request
+/// ```bash
+/// POST /iot/data/alerts/sensor-001/f41c.../acknowledge
+/// ```
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[post("/alerts/<device_id>/<alert_id>/acknowledge")]
+pub async fn acknowledge_alert_route(
+    state: &State<AppState>,
+    access: DeviceWriteAccess,
+    device_id: &str,
+    alert_id: &str,
+) -> Result<Json<Alert>, Status> {
+    info!("Received alert acknowledgement for device {}: {}", device_id, alert_id);
+    let by = access.0.subject;
+    respond(handle_alert(state.inner(), device_id, alert_id, |alert| alert.acknowledge(&by)).await).map(Json)
+}
+
+/// POST endpoint for resolving an alert
+/// 
+/// Records that the caller dealt with the alert; it need not have been
+/// acknowledged first. Resolving a resolved alert keeps the first
+/// resolution. Returns the updated alert, or 404 if it does not exist.
+/// 
+/// # Example Request
+/// ```bash
+/// POST /iot/data/alerts/sensor-001/f41c.../resolve
+/// ```
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[post("/alerts/<device_id>/<alert_id>/resolve")]
+pub async fn resolve_alert_route(
+    state: &State<AppState>,
+    access: DeviceWriteAccess,
+    device_id: &str,
+    alert_id: &str,
+) -> Result<Json<Alert>, Status> {
+    info!("Received alert resolution for device {}: {}", device_id, alert_id);
+    let by = access.0.subject;
+    respond(handle_alert(state.inner(), device_id, alert_id, |alert| Ok(alert.resolve(&by))).await).map(Json)
+}
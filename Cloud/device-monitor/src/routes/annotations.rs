@@ -0,0 +1,222 @@
+// Telemetry Annotation Route Handlers
+// 
+// This module handles the /iot/data/annotations/<device_id> endpoints for
+// creating, listing, editing and deleting manual notes on a device's
+// telemetry timeline.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::annotation::{Annotation, AnnotationRequest};
+use crate::domain::error::ApiError;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::{DeviceAccess, DeviceWriteAccess};
+
+/// Retrieves the annotations of a device that overlap a time range
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose annotations to read
+/// * `from` - Start of the range as a Unix timestamp (unbounded if None)
+/// * `to` - End of the range as a Unix timestamp (unbounded if None)
+/// 
+/// # Returns
+/// * `Result<Vec<Annotation>, ApiError>` - Annotations ordered by start time, or an error
+pub(crate) async fn read_annotations(
+    state: &AppState,
+    device_id: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Vec<Annotation>, ApiError> {
+    let annotations = state.cosmos_client.read_annotations(device_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    Ok(annotations
+        .into_iter()
+        .filter(|annotation| annotation.overlaps(from, to))
+        .collect())
+}
+
+/// Loads an annotation, returning a not found error if it does not exist
+async fn load_annotation(state: &AppState, device_id: &str, annotation_id: &str) -> Result<Annotation, ApiError> {
+    state.cosmos_client.read_annotation(device_id, annotation_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApiError::AnnotationNotFound(annotation_id.to_string()))
+}
+
+/// Validates and stores a new annotation
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device to annotate
+/// * `request` - The annotation contents
+/// 
+/// # Returns
+/// * `Result<Annotation, ApiError>` - The stored annotation or an error
+async fn create_annotation(
+    state: &AppState,
+    device_id: &str,
+    request: AnnotationRequest,
+) -> Result<Annotation, ApiError> {
+    if device_id.trim().is_empty() {
+        return Err(ApiError::InvalidDeviceId);
+    }
+
+    let annotation = Annotation::parse(device_id.to_string(), request)
+        .map_err(|e| ApiError::InvalidAnnotation(e.to_string()))?;
+
+    state.cosmos_client.upsert_annotation(&annotation)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Annotation {} created for device {}", annotation.id, device_id);
+    Ok(annotation)
+}
+
+/// Replaces the contents of an existing annotation
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device the annotation belongs to
+/// * `annotation_id` - The annotation to edit
+/// * `request` - The new annotation contents
+/// 
+/// # Returns
+/// * `Result<Annotation, ApiError>` - The updated annotation or an error
+async fn update_annotation(
+    state: &AppState,
+    device_id: &str,
+    annotation_id: &str,
+    request: AnnotationRequest,
+) -> Result<Annotation, ApiError> {
+    let annotation = load_annotation(state, device_id, annotation_id)
+        .await?
+        .update(request)
+        .map_err(|e| ApiError::InvalidAnnotation(e.to_string()))?;
+
+    state.cosmos_client.upsert_annotation(&annotation)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Annotation {} updated for device {}", annotation.id, device_id);
+    Ok(annotation)
+}
+
+/// Deletes an existing annotation
+async fn delete_annotation(state: &AppState, device_id: &str, annotation_id: &str) -> Result<(), ApiError> {
+    load_annotation(state, device_id, annotation_id).await?;
+
+    state.cosmos_client.delete_annotation(device_id, annotation_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Annotation {} deleted for device {}", annotation_id, device_id);
+    Ok(())
+}
+
+/// Converts an annotation result into a route response, logging failures
+fn respond<T>(result: Result<T, ApiError>) -> Result<T, Status> {
+    result.map_err(|e| {
+        error!("Annotation request failed: {}", e);
+        // Convert the API error to an appropriate HTTP status code
+        e.into()
+    })
+}
+
+/// POST endpoint for annotating a device's telemetry timeline
+/// 
+/// # Example Request
+/// ```json
+/// {
+///   "start": 1640995200,
+///   "end": 1640998800,
+///   "text": "HVAC serviced",
+///   "author": "alice"
+/// }
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "id": "3c1f...",
+///   "device_id": "sensor-001",
+///   "start": 1640995200,
+///   "end": 1640998800,
+///   "text": "HVAC serviced",
+///   "author": "alice",
+///   "created_at": "2025-01-01T00:00:00Z",
+///   "updated_at": "2025-01-01T00:00:00Z"
+/// }
+/// ```
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[post("/annotations/<device_id>", format = "json", data = "<request>")]
+pub async fn create_annotation_route(
+    state: &State<AppState>,
+    _access: DeviceWriteAccess,
+    device_id: &str,
+    request: Json<AnnotationRequest>,
+) -> Result<Json<Annotation>, Status> {
+    info!("Received annotation for device: {}", device_id);
+    respond(create_annotation(state.inner(), device_id, request.into_inner()).await).map(Json)
+}
+
+/// GET endpoint for listing a device's annotations
+/// 
+/// Returns the annotations overlapping the optional `from`/`to` range (Unix
+/// timestamps), ordered by start time. An empty array is returned when the
+/// device has none.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/annotations/sensor-001?from=1640995200&to=1641081600
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/annotations/<device_id>?<from>&<to>")]
+pub async fn list_annotations_route(
+    state: &State<AppState>,
+    _access: DeviceAccess,
+    device_id: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Json<Vec<Annotation>>, Status> {
+    info!("Received annotation listing request for device: {}", device_id);
+    respond(read_annotations(state.inner(), device_id, from, to).await).map(Json)
+}
+
+/// PUT endpoint for editing an annotation
+/// 
+/// Takes the same body as creation and replaces the range, text and author.
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[put("/annotations/<device_id>/<annotation_id>", format = "json", data = "<request>")]
+pub async fn update_annotation_route(
+    state: &State<AppState>,
+    _access: DeviceWriteAccess,
+    device_id: &str,
+    annotation_id: &str,
+    request: Json<AnnotationRequest>,
+) -> Result<Json<Annotation>, Status> {
+    info!("Received annotation update for device {}: {}", device_id, annotation_id);
+    respond(update_annotation(state.inner(), device_id, annotation_id, request.into_inner()).await).map(Json)
+}
+
+/// DELETE endpoint for removing an annotation
+/// 
+/// Returns 204 No Content on success and 404 if the annotation does not exist.
+/// 
+/// Requires the operator role and credentials that may read the device.
+#[delete("/annotations/<device_id>/<annotation_id>")]
+pub async fn delete_annotation_route(
+    state: &State<AppState>,
+    _access: DeviceWriteAccess,
+    device_id: &str,
+    annotation_id: &str,
+) -> Result<Status, Status> {
+    info!("Received annotation deletion for device {}: {}", device_id, annotation_id);
+    respond(delete_annotation(state.inner(), device_id, annotation_id).await).map(|()| Status::NoContent)
+}
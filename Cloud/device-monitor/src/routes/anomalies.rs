@@ -0,0 +1,132 @@
+// Anomaly Detection Route Handler
+// 
+// This module handles the GET /iot/data/anomalies/<device_id> endpoint,
+// which flags readings of a device's metric that deviate from its recent
+// behaviour, to catch failing sensors without hand-set thresholds.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::anomaly::{detect_anomalies, parse_threshold, AnomalyMethod, AnomalyReport};
+use crate::domain::error::ApiError;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Query parameters for anomaly detection
+#[derive(Debug, FromForm)]
+pub struct AnomalyQuery {
+    /// The telemetry metric to analyze (e.g. "temperature")
+    pub metric: String,
+    /// Detection method: zscore (default) or ewma
+    pub method: Option<String>,
+    /// Number of earlier readings in the z-score window (default 30)
+    pub window: Option<usize>,
+    /// Weight of the newest reading in the EWMA (default 0.3)
+    pub alpha: Option<f64>,
+    /// Score above which a reading is anomalous, in standard deviations (default 3)
+    pub threshold: Option<f64>,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+}
+
+/// Flags the anomalous readings of a device's metric
+/// 
+/// This function performs the following steps:
+/// 1. Validates the metric, method, threshold and time range
+/// 2. Reads the device's telemetry within the range, oldest first
+/// 3. Scores each reading against the readings before it and keeps those
+///    beyond the threshold
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to analyze
+/// * `query` - The metric, method parameters, threshold and time range
+/// 
+/// # Returns
+/// * `Result<AnomalyReport, ApiError>` - The anomalous readings or an error
+async fn find_anomalies(
+    state: &AppState,
+    device_id: &str,
+    query: &AnomalyQuery,
+) -> Result<AnomalyReport, ApiError> {
+    info!("Detecting anomalies in {} for device {}", query.metric, device_id);
+
+    if query.metric.trim().is_empty() {
+        return Err(ApiError::InvalidQuery("metric is required".to_string()));
+    }
+    let method = AnomalyMethod::parse(query.method.as_deref(), query.window, query.alpha)?;
+    let threshold = parse_threshold(query.threshold)?;
+    let range = TimeRange::parse(query.from, query.to)?;
+
+    let telemetry = state.cosmos_client.query_telemetry(device_id, &range, &ValueFilter::NONE, SortOrder::Asc, None)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    range.check_device_found(device_id, telemetry.len())?;
+
+    let (points, anomalies) = detect_anomalies(&telemetry, &query.metric, method, threshold);
+
+    info!("Found {} anomalies in {} reading(s)", anomalies.len(), points);
+    Ok(AnomalyReport {
+        device_id: device_id.to_string(),
+        metric: query.metric.clone(),
+        method,
+        threshold,
+        points,
+        anomalies,
+    })
+}
+
+/// GET endpoint for detecting anomalous readings of a device's metric
+/// 
+/// Each numeric reading within the optional `from`/`to` range is scored
+/// by how many standard deviations it lies from the value expected from
+/// the readings before it: the mean of the previous `window` readings
+/// (`method=zscore`) or an exponentially weighted moving average
+/// (`method=ewma`). Readings scoring beyond `threshold` in either
+/// direction are returned. The first 5 readings are not scored.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/anomalies/sensor-001?metric=temperature&method=zscore&window=30&threshold=3
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "metric": "temperature",
+///   "method": { "type": "zscore", "window": 30 },
+///   "threshold": 3.0,
+///   "points": 1440,
+///   "anomalies": [
+///     { "timestamp": 1641020400, "value": 41.7, "expected": 22.3, "score": 9.8 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/anomalies/<device_id>?<query..>")]
+pub async fn anomalies_route(
+    device_id: &str,
+    query: AnomalyQuery,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<AnomalyReport>, Status> {
+    info!("Received anomaly detection request for device: {}", device_id);
+
+    match find_anomalies(state.inner(), device_id, &query).await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            error!("Error detecting anomalies: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Telemetry Archives Route Handler
+// 
+// This module handles the GET /iot/data/archives/<device_id> endpoint,
+// which lists the days of a device's telemetry that were archived to Azure
+// Blob Storage, so history moved out of Cosmos DB can still be found.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::archive::Archive;
+use crate::domain::error::ApiError;
+use crate::domain::time_range::TimeRange;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Lists a device's archives
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose archives to list
+/// * `from` / `to` - The telemetry timestamps the archives must cover part of
+/// 
+/// # Returns
+/// * `Result<Vec<Archive>, ApiError>` - The archives, oldest first, or an error
+async fn list_archives(
+    state: &AppState,
+    device_id: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Vec<Archive>, ApiError> {
+    info!("Listing archives for device {}", device_id);
+
+    let range = TimeRange::parse(from, to)?;
+    let archives = state.cosmos_client.read_archives(device_id, &range)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Found {} archive(s) for device {}", archives.len(), device_id);
+    Ok(archives)
+}
+
+/// GET endpoint listing the archived days of a device's telemetry
+/// 
+/// Each archive is one blob in the archive container holding one UTC day
+/// of the device's telemetry, as JSON Lines or Parquet. `deleted` tells
+/// whether the day's records were removed from Cosmos DB, and so can only be
+/// read from the blob. A device without archives returns an empty list.
+/// 
+/// # Arguments
+/// * `device_id` - The device identifier from the URL path
+/// * `from` - Only archives with telemetry at or after this Unix timestamp
+/// * `to` - Only archives with telemetry at or before this Unix timestamp
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<Vec<Archive>>, Status>` - The archives, oldest first, or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/archives/sensor-001?from=1640995200
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// [
+///   {
+///     "id": "sensor-001-2022-01-01",
+///     "device_id": "sensor-001",
+///     "day": "2022-01-01",
+///     "blob_name": "sensor-001/2022-01-01.jsonl",
+///     "format": "jsonl",
+///     "records": 1440,
+///     "bytes": 151200,
+///     "first_timestamp": 1640995200,
+///     "last_timestamp": 1641081540,
+///     "deleted": true,
+///     "archived_at": "2022-04-01T00:00:00Z"
+///   }
+/// ]
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/archives/<device_id>?<from>&<to>")]
+pub async fn list_archives_route(
+    device_id: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<Vec<Archive>>, Status> {
+    info!("Received archive listing request for device: {}", device_id);
+
+    match list_archives(state.inner(), device_id, from, to).await {
+        Ok(archives) => Ok(Json(archives)),
+        Err(e) => {
+            error!("Error listing archives: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
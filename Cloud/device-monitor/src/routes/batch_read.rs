@@ -0,0 +1,137 @@
+// Batch Telemetry Retrieval Route Handler
+// 
+// This module handles the POST /iot/data/read/batch endpoint, which reads
+// the telemetry of several devices with shared query parameters in one
+// request and returns each device's records keyed by device ID, so a fleet
+// dashboard needs one round trip instead of one per device.
+
+use std::collections::BTreeMap;
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::auth::Principal;
+use crate::domain::batch::BatchReadRequest;
+use crate::domain::error::ApiError;
+use crate::domain::export::parse_metrics;
+use crate::domain::paging::{PageRequest, DEFAULT_PAGE_LIMIT};
+use crate::domain::sort_order::SortOrder;
+use crate::domain::telemetry::Telemetry;
+use crate::domain::time_range::TimeRange;
+use crate::domain::units::UnitConversion;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::ReadAccess;
+use crate::utils::concurrent_reads::read_all;
+
+/// Reads the telemetry of every requested device
+/// 
+/// This function performs the following steps:
+/// 1. Validates the devices and the shared parameters, and that the caller
+///    may read every device
+/// 2. Reads the first `limit` records of each device concurrently, through
+///    the query cache
+/// 3. Narrows and converts each device's records like the read endpoint
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `request` - The devices and the shared query parameters
+/// * `caller` - The authenticated caller, whose scope must cover every device
+/// 
+/// # Returns
+/// * `Result<BTreeMap<String, Vec<Telemetry>>, ApiError>` - Each device's records, keyed by
+///   device ID, or an error
+async fn read_batch(
+    state: &AppState,
+    request: &BatchReadRequest,
+    caller: &Principal,
+) -> Result<BTreeMap<String, Vec<Telemetry>>, ApiError> {
+    let devices = request.devices()?;
+    if let Some(device_id) = devices.iter().find(|device_id| !caller.can_read(device_id)) {
+        return Err(ApiError::DeviceForbidden(device_id.clone()));
+    }
+    let range = TimeRange::parse(request.from, request.to)?;
+    let page = PageRequest::parse(Some(request.limit.unwrap_or(DEFAULT_PAGE_LIMIT)), None)?;
+    let order = SortOrder::parse(request.order.as_deref())?;
+    let conversion = UnitConversion::parse(request.unit.as_deref(), &state.metric_units)?;
+    let filter = ValueFilter::parse(request.filter.as_deref())?;
+    let metrics = parse_metrics(request.metrics.as_deref());
+
+    info!("Reading telemetry of {} device(s) in one batch", devices.len());
+
+    let page = page.as_ref();
+    let reads = read_all(&devices, |device_id| {
+        state.query_cache.query_telemetry(device_id, &range, &filter, order, page)
+    }, state.read_concurrency)
+    .await?;
+
+    let mut results = BTreeMap::new();
+    for (device_id, mut telemetry) in devices.iter().cloned().zip(reads) {
+        // A page is read with one extra record to tell whether another follows
+        if let Some(page) = page {
+            telemetry.truncate(page.limit);
+        }
+        if let Some(metrics) = &metrics {
+            telemetry = telemetry
+                .into_iter()
+                .filter_map(|record| record.select_metrics(metrics))
+                .collect();
+        }
+        if let Some(conversion) = &conversion {
+            telemetry = telemetry.into_iter().map(|record| conversion.apply(record)).collect();
+        }
+        results.insert(device_id, telemetry);
+    }
+
+    Ok(results)
+}
+
+/// POST endpoint reading the telemetry of several devices in one request
+/// 
+/// `device_ids` lists up to 50 devices; the other fields are the read
+/// endpoint's `from`, `to`, `limit`, `metrics`, `order`, `unit` and `where`
+/// parameters and apply to every device. Each device's newest records are
+/// returned (oldest with `order` "asc"), at most `limit` per device
+/// (default 100, at most 1000). The response maps each device ID to its
+/// records; a device without matching telemetry maps to an empty array.
+/// Returns 400 for invalid parameters and 403 if the caller's credentials
+/// may not read one of the devices.
+/// 
+/// # Example Request
+/// ```json
+/// {
+///   "device_ids": ["sensor-001", "sensor-002"],
+///   "from": 1640995200,
+///   "limit": 1,
+///   "metrics": "temperature",
+///   "unit": "fahrenheit"
+/// }
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "sensor-001": [
+///     { "device_id": "sensor-001", "telemetry_data": { "temperature": "74.3" }, "timestamp": 1640995260 }
+///   ],
+///   "sensor-002": []
+/// }
+/// ```
+#[post("/read/batch", format = "json", data = "<request>")]
+pub async fn read_batch_route(
+    state: &State<AppState>,
+    access: ReadAccess,
+    request: Json<BatchReadRequest>,
+) -> Result<Json<BTreeMap<String, Vec<Telemetry>>>, Status> {
+    info!("Received batch telemetry request");
+
+    match read_batch(state.inner(), &request, &access.0).await {
+        Ok(results) => Ok(Json(results)),
+        Err(e) => {
+            error!("Error reading telemetry batch: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Multi-Device Comparison Route Handler
+// 
+// This module handles the GET /iot/data/compare endpoint, which returns one
+// metric of several devices as series aligned on shared timestamps, so a
+// chart can overlay them from a single request.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::aggregate::{parse_bucket, parse_timezone, AggregateFn};
+use crate::domain::compare::{compare_series, parse_devices, Comparison};
+use crate::domain::error::ApiError;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::domain::auth::Principal;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::ReadAccess;
+use crate::utils::concurrent_reads::read_all;
+
+/// Query parameters for comparing devices
+#[derive(Debug, FromForm)]
+pub struct CompareQuery {
+    /// Comma-separated devices to compare
+    pub devices: String,
+    /// The telemetry metric to compare (e.g. "temperature")
+    pub metric: String,
+    /// Bucket width the series are aligned on, e.g. "1m" or "1h" (default "5m")
+    pub bucket: Option<String>,
+    /// Aggregation function within each bucket: avg (default), min, max, sum or count
+    #[field(name = "fn")]
+    pub function: Option<String>,
+    /// IANA time zone the buckets are aligned in, e.g. "Europe/Berlin" (default UTC)
+    pub tz: Option<String>,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+}
+
+/// Compares a metric across several devices
+/// 
+/// This function performs the following steps:
+/// 1. Validates the devices, metric, bucket width, function, time zone and time range,
+///    and that the caller may read every device
+/// 2. Reads each device's telemetry within the range concurrently
+/// 3. Aggregates each device's samples into the same buckets and aligns the series
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `query` - The devices, metric, bucket width, function, time zone and time range
+/// * `caller` - The authenticated caller, whose scope must cover every device
+/// 
+/// # Returns
+/// * `Result<Comparison, ApiError>` - The aligned series or an error
+async fn compare(state: &AppState, query: &CompareQuery, caller: &Principal) -> Result<Comparison, ApiError> {
+    let devices = parse_devices(&query.devices)?;
+    if let Some(device_id) = devices.iter().find(|device_id| !caller.can_read(device_id)) {
+        return Err(ApiError::DeviceForbidden(device_id.clone()));
+    }
+    if query.metric.trim().is_empty() {
+        return Err(ApiError::InvalidQuery("metric is required".to_string()));
+    }
+    let bucket_secs = parse_bucket(query.bucket.as_deref().unwrap_or("5m"))?;
+    let function = AggregateFn::parse(query.function.as_deref().unwrap_or("avg"))?;
+    let tz = parse_timezone(query.tz.as_deref())?;
+    let range = TimeRange::parse(query.from, query.to)?;
+
+    info!("Comparing {} across {} device(s)", query.metric, devices.len());
+
+    let range = &range;
+    let telemetry = read_all(&devices, |device_id| async move {
+        state.query_cache.query_telemetry(device_id, range, &ValueFilter::NONE, SortOrder::Asc, None)
+            .await
+            .map(|telemetry| (device_id.clone(), telemetry))
+    }, state.read_concurrency)
+    .await?;
+
+    for (device_id, records) in &telemetry {
+        range.check_device_found(device_id, records.len())?;
+    }
+
+    let comparison = compare_series(&telemetry, &query.metric, bucket_secs, tz, function);
+
+    info!("Aligned {} device(s) on {} bucket(s)", comparison.series.len(), comparison.timestamps.len());
+    Ok(comparison)
+}
+
+/// GET endpoint comparing a metric across several devices
+/// 
+/// Each device's samples of `metric` within the optional `from`/`to` range
+/// are aggregated with `fn` into buckets of the requested width, aligned
+/// in the local time of `tz`. The
+/// series share one list of bucket starts, covering every bucket in which
+/// any device has a sample, and hold null where a device has none. Up to
+/// 20 devices can be compared; returns 404 if a device has no telemetry at
+/// all, and 403 if the caller's credentials may not read one of them.
+/// 
+/// # Arguments
+/// * `query` - The `devices`, `metric`, `bucket`, `fn`, `tz`, `from` and `to` query parameters
+/// * `state` - Application state injected by Rocket
+/// * `access` - The authenticated caller
+/// 
+/// # Returns
+/// * `Result<Json<Comparison>, Status>` - The aligned series or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/compare?devices=sensor-001,sensor-002&metric=temperature&bucket=1h&from=1640995200&to=1641002400
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "metric": "temperature",
+///   "fn": "avg",
+///   "bucket_secs": 3600,
+///   "tz": "UTC",
+///   "timestamps": [1640995200, 1640998800],
+///   "series": [
+///     { "device_id": "sensor-001", "values": [23.1, 23.8] },
+///     { "device_id": "sensor-002", "values": [21.4, null] }
+///   ]
+/// }
+/// ```
+#[get("/compare?<query..>")]
+pub async fn compare_route(
+    query: CompareQuery,
+    state: &State<AppState>,
+    access: ReadAccess,
+) -> Result<Json<Comparison>, Status> {
+    info!("Received comparison request for devices: {}", query.devices);
+
+    match compare(state.inner(), &query, &access.0).await {
+        Ok(comparison) => Ok(Json(comparison)),
+        Err(e) => {
+            error!("Error comparing devices: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
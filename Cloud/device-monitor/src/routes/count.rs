@@ -0,0 +1,105 @@
+// Telemetry Record Count Route Handler
+// 
+// This module handles the GET /iot/data/count/<device_id> endpoint, which
+// reports how many telemetry records a device stored, optionally within a
+// time window, without reading the records themselves.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use serde::{Deserialize, Serialize};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::time_range::TimeRange;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Number of telemetry records a device stored within a time range
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TelemetryCount {
+    /// The device whose records were counted
+    pub device_id: String,
+    /// Earliest Unix timestamp included, if bounded
+    pub from: Option<i64>,
+    /// Latest Unix timestamp included, if bounded
+    pub to: Option<i64>,
+    /// Number of matching records
+    pub count: u64,
+}
+
+/// Counts a device's telemetry records
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose records to count
+/// * `from` / `to` - The timestamps to include
+/// 
+/// # Returns
+/// * `Result<TelemetryCount, ApiError>` - The number of records or an error
+async fn count_telemetry(
+    state: &AppState,
+    device_id: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<TelemetryCount, ApiError> {
+    info!("Counting telemetry for device {}", device_id);
+
+    let range = TimeRange::parse(from, to)?;
+    let count = state.cosmos_client.count_telemetry(device_id, &range)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    info!("Device {} has {} matching record(s)", device_id, count);
+    Ok(TelemetryCount { device_id: device_id.to_string(), from, to, count })
+}
+
+/// GET endpoint returning how many telemetry records a device stored
+/// 
+/// The records are counted by the database, so totals for paginators and
+/// retention tooling stay cheap however long the history is. A device
+/// without telemetry has a count of 0 rather than being an error.
+/// 
+/// # Arguments
+/// * `device_id` - The device identifier from the URL path
+/// * `from` - Earliest Unix timestamp to include
+/// * `to` - Latest Unix timestamp to include
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<TelemetryCount>, Status>` - The record count or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/count/sensor-001?from=1640995200&to=1641081600
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "from": 1640995200,
+///   "to": 1641081600,
+///   "count": 1440
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/count/<device_id>?<from>&<to>")]
+pub async fn count_route(
+    device_id: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<TelemetryCount>, Status> {
+    info!("Received record count request for device: {}", device_id);
+
+    match count_telemetry(state.inner(), device_id, from, to).await {
+        Ok(count) => Ok(Json(count)),
+        Err(e) => {
+            error!("Error counting telemetry: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
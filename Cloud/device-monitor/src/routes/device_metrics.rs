@@ -0,0 +1,92 @@
+// Prometheus Device Metrics Route Handler
+// 
+// This module handles the GET /metrics/devices endpoint, which exposes the
+// latest numeric telemetry of every device as Prometheus gauges for
+// scraping by existing Prometheus and Grafana stacks.
+
+use rocket::http::{ContentType, Status};
+use rocket::State;
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::prometheus::render_device_metrics;
+use crate::domain::auth::Principal;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::ReadAccess;
+use crate::utils::concurrent_reads::read_all;
+
+/// Renders the latest telemetry of every device the caller may read as Prometheus gauges
+/// 
+/// This function performs the following steps:
+/// 1. Reads every device the caller may read from the device index
+/// 2. Reads the most recent telemetry record of each device concurrently
+/// 3. Renders the last-seen times and numeric values in the text exposition format
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `caller` - The authenticated caller, whose scope limits the devices
+/// 
+/// # Returns
+/// * `Result<String, ApiError>` - The exposition text or an error
+async fn device_metrics(state: &AppState, caller: &Principal) -> Result<String, ApiError> {
+    let mut devices = state.cosmos_client.read_devices()
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    devices.retain(|device| caller.can_read(&device.device_id));
+
+    let latest: Vec<_> = read_all(
+        &devices,
+        |device| state.cosmos_client.read_latest_telemetry(&device.device_id),
+        state.read_concurrency,
+    )
+    .await?
+    .into_iter()
+    .flatten()
+    .collect();
+
+    info!("Exporting latest telemetry of {} device(s)", latest.len());
+    Ok(render_device_metrics(&devices, &latest))
+}
+
+/// GET endpoint exposing each device's latest telemetry for Prometheus
+/// 
+/// Every numeric metric of a device's most recent record becomes a gauge
+/// named `device_<metric>` with a `device_id` label; characters Prometheus
+/// does not allow in names are replaced with underscores. Non-numeric
+/// values are skipped. Each device's last-seen time is exposed as
+/// `device_last_seen_timestamp_seconds`, for alerting on silent devices.
+/// Only devices the caller's credentials may read are exposed.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /metrics/devices
+/// ```
+/// 
+/// # Example Response
+/// ```text
+/// # HELP device_last_seen_timestamp_seconds Unix time the device was last seen
+/// # TYPE device_last_seen_timestamp_seconds gauge
+/// device_last_seen_timestamp_seconds{device_id="4321"} 1640995260
+/// # HELP device_temperature Latest temperature reported by the device
+/// # TYPE device_temperature gauge
+/// device_temperature{device_id="4321"} 24.1
+/// ```
+#[get("/devices")]
+pub async fn device_metrics_route(
+    state: &State<AppState>,
+    access: ReadAccess,
+) -> Result<(ContentType, String), Status> {
+    info!("Received Prometheus device metrics request");
+
+    match device_metrics(state.inner(), &access.0).await {
+        Ok(metrics) => Ok((
+            ContentType::new("text", "plain").with_params([("version", "0.0.4"), ("charset", "utf-8")]),
+            metrics,
+        )),
+        Err(e) => {
+            error!("Error exporting device metrics: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
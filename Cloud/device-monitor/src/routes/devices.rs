@@ -0,0 +1,170 @@
+// Device Discovery Route Handler
+// 
+// This module handles the GET /iot/devices endpoint, which lists every
+// device that has reported telemetry together with when it was last seen
+// and its status, e.g. for the frontend's devices page, and the
+// GET /iot/devices/<device_id>/status endpoint, which says whether a device
+// is online, stale or offline.
+
+use chrono::Utc;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::device::{DeviceHealth, DeviceSummary};
+use crate::domain::error::ApiError;
+use crate::domain::auth::Principal;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::{DeviceAccess, ReadAccess};
+
+/// Reads every device the caller may read from the device index, sorted by device ID
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client and status thresholds
+/// * `caller` - The authenticated caller, whose scope limits the devices
+/// 
+/// # Returns
+/// * `Result<Vec<DeviceSummary>, ApiError>` - The devices with their status, or an error
+async fn list_devices(state: &AppState, caller: &Principal) -> Result<Vec<DeviceSummary>, ApiError> {
+    let mut devices = state.cosmos_client.read_devices()
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    devices.retain(|device| caller.can_read(&device.device_id));
+
+    devices.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+    info!("Found {} device(s)", devices.len());
+
+    let now = Utc::now().timestamp();
+    Ok(devices
+        .into_iter()
+        .map(|device| DeviceSummary {
+            status: state.status_thresholds.classify(device.last_seen, now),
+            device,
+        })
+        .collect())
+}
+
+/// GET endpoint listing the devices that have reported telemetry
+/// 
+/// Devices are read from the device index the device-comms service
+/// maintains as it stores telemetry, so listing them does not scan the
+/// telemetry container. Each device's status is judged by its last-seen
+/// time like the status endpoint. Devices are sorted by ID; an empty fleet
+/// returns an empty array. Only devices the caller's credentials may read
+/// are listed.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/devices
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// [
+///   { "device_id": "sensor-001", "last_seen": 1640995260, "status": "online" },
+///   { "device_id": "sensor-002", "last_seen": 1640991590, "gateway_id": "gateway-01", "status": "stale" }
+/// ]
+/// ```
+#[get("/devices")]
+pub async fn list_devices_route(
+    state: &State<AppState>,
+    access: ReadAccess,
+) -> Result<Json<Vec<DeviceSummary>>, Status> {
+    info!("Received device discovery request");
+
+    match list_devices(state.inner(), &access.0).await {
+        Ok(devices) => Ok(Json(devices)),
+        Err(e) => {
+            error!("Error listing devices: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
+
+/// Classifies a device by when it was last seen
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client and status thresholds
+/// * `device_id` - The unique identifier of the device
+/// 
+/// # Returns
+/// * `Result<DeviceHealth, ApiError>` - The device's status, or a not found error
+///   if it has no telemetry
+async fn device_status(state: &AppState, device_id: &str) -> Result<DeviceHealth, ApiError> {
+    if device_id.trim().is_empty() {
+        return Err(ApiError::DeviceNotFound(device_id.to_string()));
+    }
+
+    // The device index counts heartbeats; devices missing from it fall back
+    // to their latest telemetry
+    let indexed = state.cosmos_client.read_device(device_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .map(|device| device.last_seen);
+    let last_seen = match indexed {
+        Some(last_seen) => Some(last_seen),
+        None => state.cosmos_client.read_latest_telemetry(device_id)
+            .await
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+            .and_then(|telemetry| telemetry.timestamp),
+    }
+    .ok_or_else(|| ApiError::DeviceNotFound(device_id.to_string()))?;
+
+    let now = Utc::now().timestamp();
+    let thresholds = state.status_thresholds;
+    let status = thresholds.classify(last_seen, now);
+    info!("Device {} is {:?}", device_id, status);
+
+    Ok(DeviceHealth {
+        device_id: device_id.to_string(),
+        status,
+        last_seen,
+        seconds_since_last_seen: (now - last_seen).max(0),
+        stale_after_secs: thresholds.stale_after_secs,
+        offline_after_secs: thresholds.offline_after_secs,
+    })
+}
+
+/// GET endpoint classifying a device as online, stale or offline
+/// 
+/// A device is `online` if it was last seen, by a reading or a heartbeat,
+/// at most `DEVICE_STALE_AFTER_SECS` ago (default 300), `stale` if at most
+/// `DEVICE_OFFLINE_AFTER_SECS` ago (default 3600), and `offline` otherwise.
+/// Returns 404 if the device has no telemetry.
+/// 
+/// Requires credentials that may read the device.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/devices/sensor-001/status
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "status": "stale",
+///   "last_seen": 1640995260,
+///   "seconds_since_last_seen": 912,
+///   "stale_after_secs": 300,
+///   "offline_after_secs": 3600
+/// }
+/// ```
+#[get("/devices/<device_id>/status")]
+pub async fn device_status_route(
+    state: &State<AppState>,
+    _access: DeviceAccess,
+    device_id: &str,
+) -> Result<Json<DeviceHealth>, Status> {
+    info!("Received device status request for device: {}", device_id);
+
+    match device_status(state.inner(), device_id).await {
+        Ok(health) => Ok(Json(health)),
+        Err(e) => {
+            error!("Error reading device status: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
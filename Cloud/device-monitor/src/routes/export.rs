@@ -0,0 +1,309 @@
+// Telemetry Export Route Handler
+//
+// This module handles the GET /iot/data/export/<device_id>.csv and
+// GET /iot/data/export/<device_id>.parquet endpoints, which download a
+// device's telemetry as a spreadsheet-ready CSV file or as a typed,
+// columnar Parquet file for pandas, Polars and other analytics tools.
+
+use std::collections::BTreeSet;
+use std::io::Cursor;
+
+use futures::StreamExt;
+use rocket::http::{ContentType, Status};
+use rocket::response::stream::TextStream;
+use rocket::response::{self, Responder, Response};
+use rocket::{Either, Request, State};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::export::{csv_header, csv_row, metric_columns, parse_metrics, write_parquet};
+use crate::domain::telemetry::Telemetry;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::services::TelemetryPages;
+use crate::utils::auth_guard::ReadAccess;
+
+/// Query parameters of the export endpoint
+#[derive(Debug, Default, FromForm)]
+pub struct ExportQuery {
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+    /// Comma-separated metrics to export as columns
+    pub metrics: Option<String>,
+}
+
+/// A CSV file download, written to the client page by page
+///
+/// If reading a page fails mid-response, the error is logged and the file
+/// ends after the last complete row.
+pub struct CsvFile {
+    /// Name suggested to the client for saving the file
+    pub filename: String,
+    /// The metric columns, in order
+    metrics: Vec<String>,
+    /// The pages of records to write, one row per record
+    pages: TelemetryPages,
+}
+
+impl<'r> Responder<'r, 'r> for CsvFile {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        let header = csv_header(&self.metrics);
+        let rows = futures::stream::unfold((self.pages, self.metrics), |(mut pages, metrics)| async move {
+            match pages.next().await {
+                Some(Ok(page)) => {
+                    let chunk: String = page.iter().map(|record| csv_row(record, &metrics)).collect();
+                    Some((chunk, (pages, metrics)))
+                }
+                Some(Err(e)) => {
+                    error!("Error streaming telemetry export: {}", e);
+                    None
+                }
+                None => None,
+            }
+        });
+        let body = futures::stream::once(async move { header }).chain(rows);
+
+        Response::build_from(TextStream(body).respond_to(request)?)
+            .header(ContentType::CSV)
+            .raw_header(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", self.filename),
+            )
+            .ok()
+    }
+}
+
+/// A Parquet file download
+pub struct ParquetFile {
+    /// Name suggested to the client for saving the file
+    pub filename: String,
+    /// The encoded Parquet file
+    pub data: Vec<u8>,
+}
+
+impl<'r> Responder<'r, 'static> for ParquetFile {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(ContentType::new("application", "vnd.apache.parquet"))
+            .raw_header(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", self.filename),
+            )
+            .sized_body(self.data.len(), Cursor::new(self.data))
+            .ok()
+    }
+}
+
+/// Returns a file name for a device's export that is safe to put in a header
+///
+/// # Arguments
+/// * `device_id` - The unique identifier of the device
+/// * `extension` - The file extension, without the dot
+///
+/// # Returns
+/// * `String` - The device ID with anything but ASCII letters, digits, `-`
+///   and `_` replaced by `_`, followed by the extension
+pub fn export_filename(device_id: &str, extension: &str) -> String {
+    let name: String = device_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("{}.{}", name, extension)
+}
+
+/// Reads a device's telemetry and the metric columns to export
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `query` - The query parameters of the request
+///
+/// # Returns
+/// * `Result<(Vec<Telemetry>, Vec<String>), ApiError>` - The records, oldest first, and
+///   either the requested metrics or every metric reported in the range, or an error
+async fn read_export(
+    state: &AppState,
+    device_id: &str,
+    query: &ExportQuery,
+) -> Result<(Vec<Telemetry>, Vec<String>), ApiError> {
+    let range = TimeRange::parse(query.from, query.to)?;
+    if device_id.trim().is_empty() {
+        return Err(ApiError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let telemetry = state.cosmos_client.query_telemetry(device_id, &range, &ValueFilter::NONE, SortOrder::Asc, None)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    range.check_device_found(device_id, telemetry.len())?;
+
+    let metrics = parse_metrics(query.metrics.as_deref()).unwrap_or_else(|| metric_columns(&telemetry));
+    info!("Exporting {} telemetry record(s) for device: {}", telemetry.len(), device_id);
+    Ok((telemetry, metrics))
+}
+
+/// Opens a stream of a device's telemetry within a range, oldest first
+fn open_pages(state: &AppState, device_id: &str, range: &TimeRange) -> Result<TelemetryPages, ApiError> {
+    state.cosmos_client.stream_telemetry(device_id, range, &ValueFilter::NONE, SortOrder::Asc)
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))
+}
+
+/// Collects every metric a device reported within a range, sorted by name
+///
+/// Only the metric names are kept while the pages are read, so this first
+/// pass over the telemetry needs no more memory than the columns.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `range` - The timestamps to include
+///
+/// # Returns
+/// * `Result<Vec<String>, ApiError>` - The metric names or an error
+async fn scan_metric_columns(state: &AppState, device_id: &str, range: &TimeRange) -> Result<Vec<String>, ApiError> {
+    let mut pages = open_pages(state, device_id, range)?;
+    let mut metrics = BTreeSet::new();
+    while let Some(page) = pages.next().await {
+        let page = page.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        metrics.extend(metric_columns(&page));
+    }
+    Ok(metrics.into_iter().collect())
+}
+
+/// Starts streaming a device's telemetry as CSV
+///
+/// The metric columns are the requested metrics or, without any, those
+/// found by a first pass over the telemetry; the rows are then written
+/// from a second pass as the response is sent, so no records are held in
+/// memory. The first page is read before responding, so a device without
+/// any telemetry is still reported as not found.
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `query` - The query parameters of the request
+///
+/// # Returns
+/// * `Result<CsvFile, ApiError>` - The CSV file or an error
+async fn export_csv(state: &AppState, device_id: &str, query: &ExportQuery) -> Result<CsvFile, ApiError> {
+    info!("Exporting telemetry as CSV for device: {}", device_id);
+
+    let range = TimeRange::parse(query.from, query.to)?;
+    if device_id.trim().is_empty() {
+        return Err(ApiError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let metrics = match parse_metrics(query.metrics.as_deref()) {
+        Some(metrics) => metrics,
+        None => scan_metric_columns(state, device_id, &range).await?,
+    };
+
+    let mut pages = open_pages(state, device_id, &range)?;
+    let mut first = Vec::new();
+    while first.is_empty() {
+        match pages.next().await {
+            Some(page) => first = page.map_err(|e| ApiError::DatabaseError(e.to_string()))?,
+            None => break,
+        }
+    }
+    range.check_device_found(device_id, first.len())?;
+
+    Ok(CsvFile {
+        filename: export_filename(device_id, "csv"),
+        metrics,
+        pages: futures::stream::iter([Ok(first)]).chain(pages).boxed(),
+    })
+}
+
+/// Reads a device's telemetry and encodes it as Parquet
+///
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `query` - The query parameters of the request
+///
+/// # Returns
+/// * `Result<ParquetFile, ApiError>` - The Parquet file or an error
+async fn export_parquet(state: &AppState, device_id: &str, query: &ExportQuery) -> Result<ParquetFile, ApiError> {
+    info!("Exporting telemetry as Parquet for device: {}", device_id);
+
+    let (telemetry, metrics) = read_export(state, device_id, query).await?;
+    let data = write_parquet(&telemetry, &metrics)
+        .map_err(|e| ApiError::ExportError(e.to_string()))?;
+
+    Ok(ParquetFile {
+        filename: export_filename(device_id, "parquet"),
+        data,
+    })
+}
+
+/// GET endpoint for downloading a device's telemetry as CSV or Parquet
+///
+/// The format is chosen by the extension of the path. Both formats have
+/// the columns `timestamp` (Unix), `time` and `device_id`, followed by one
+/// column per metric; values a record did not report are left empty (CSV)
+/// or null (Parquet). Without `metrics`, every metric reported in the
+/// range is exported, sorted by name. CSV files are streamed page by page;
+/// Parquet files are encoded in memory before they are sent.
+///
+/// - `.csv` responds with `text/csv`; `time` is written as RFC 3339
+/// - `.parquet` responds with `application/vnd.apache.parquet`; `time` is
+///   a UTC timestamp, and metrics whose values are all numbers are doubles
+///
+/// Both set a `Content-Disposition` header, so browsers save the file as
+/// `<device_id>.<extension>`. Returns 404 for other extensions or, without
+/// a time range, if the device has no telemetry, and 403 if the caller's
+/// credentials may not read the device.
+///
+/// # Example Request
+/// ```bash
+/// GET /iot/data/export/sensor-001.csv?from=1640995200&to=1641081600&metrics=temperature,humidity
+/// GET /iot/data/export/sensor-001.parquet?from=1640995200&to=1641081600
+/// ```
+///
+/// # Example Response
+/// ```text
+/// timestamp,time,device_id,temperature,humidity
+/// 1640995200,2022-01-01T00:00:00+00:00,sensor-001,23.5,45.2
+/// 1640995260,2022-01-01T00:01:00+00:00,sensor-001,24.1,44.8
+/// ```
+#[get("/export/<file>?<query..>")]
+pub async fn export_route(
+    state: &State<AppState>,
+    access: ReadAccess,
+    file: &str,
+    query: ExportQuery,
+) -> Result<Either<CsvFile, ParquetFile>, Status> {
+    info!("Received telemetry export request for: {}", file);
+
+    // The device is only known once the extension is stripped, so no guard can check it
+    let device_id = file.rsplit_once('.').map_or(file, |(device_id, _)| device_id);
+    if !access.0.can_read(device_id) {
+        let e = ApiError::DeviceForbidden(device_id.to_string());
+        error!("Error exporting telemetry: {}", e);
+        return Err(e.into());
+    }
+
+    // Rocket path parameters cannot share a segment with the extension
+    let result = if let Some(device_id) = file.strip_suffix(".csv") {
+        export_csv(state.inner(), device_id, &query).await.map(Either::Left)
+    } else if let Some(device_id) = file.strip_suffix(".parquet") {
+        export_parquet(state.inner(), device_id, &query).await.map(Either::Right)
+    } else {
+        error!("Unsupported export format: {}", file);
+        return Err(Status::NotFound);
+    };
+
+    match result {
+        Ok(export) => Ok(export),
+        Err(e) => {
+            error!("Error exporting telemetry: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Fleet Health Route Handler
+// 
+// This module handles the GET /iot/fleet/health endpoint, which scores every
+// device by how recently it reported, the error counters in its latest
+// record and its recent threshold breaches, and lists the fleet worst first
+// so operators see which devices need attention.
+
+use chrono::Utc;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::health::{parse_breach_window, rank_fleet, score_device, FleetHealth};
+use crate::domain::time_range::TimeRange;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::FleetAccess;
+use crate::utils::concurrent_reads::read_all;
+
+/// Scores the health of every device in the fleet
+/// 
+/// This function performs the following steps:
+/// 1. Returns cached scores if some for the same breach window were computed recently
+/// 2. Reads every device from the device index
+/// 3. Reads the latest record and the alerts raised within the window of
+///    every device concurrently
+/// 4. Scores each device, ranks the fleet and caches the result
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client and scoring settings
+/// * `hours` - The requested breach window, in hours
+/// 
+/// # Returns
+/// * `Result<FleetHealth, ApiError>` - The fleet's health or an error
+async fn fleet_health(state: &AppState, hours: Option<i64>) -> Result<FleetHealth, ApiError> {
+    let breach_window_hours = parse_breach_window(hours)?;
+
+    // Serve scores of the same window from the cache
+    let cache_key = breach_window_hours.to_string();
+    if let Some(cached) = state.fleet_health_cache.get(&cache_key) {
+        info!("Serving fleet health from cache");
+        return Ok(cached);
+    }
+
+    let devices = state.cosmos_client.read_devices()
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    let as_of = Utc::now().timestamp();
+    let window = &TimeRange { from: Some(as_of - breach_window_hours * 3600), to: None };
+
+    let scores = read_all(&devices, |device| async move {
+        let latest = state.cosmos_client.read_latest_telemetry(&device.device_id).await?;
+        let alerts = state.cosmos_client.read_alerts(&device.device_id, window).await?;
+        Ok(score_device(
+            device,
+            latest.as_ref(),
+            alerts.len(),
+            &state.status_thresholds,
+            &state.error_metrics,
+            as_of,
+        ))
+    }, state.read_concurrency)
+    .await?;
+
+    let health = rank_fleet(scores, breach_window_hours, as_of);
+    state.fleet_health_cache.insert(cache_key, health.clone());
+
+    info!(
+        "Scored {} device(s), {} needing attention",
+        health.devices, health.devices_needing_attention
+    );
+    Ok(health)
+}
+
+/// GET endpoint scoring the health of every device, worst first
+/// 
+/// Each device starts at 100 and loses points for being stale (30) or
+/// offline (60), for errors in the error counter metrics of its latest
+/// record (5 each, at most 30), and for alerts raised within the last
+/// `hours` hours (default 24, at most 168; 10 each, at most 40). Devices are
+/// listed lowest score first, so the ones needing attention come first, and
+/// the fleet score is the mean of all device scores. Scores are cached
+/// briefly, like fleet summaries. Returns 400 for an invalid window.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/fleet/health?hours=24
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "score": 83,
+///   "devices": 3,
+///   "devices_needing_attention": 1,
+///   "breach_window_hours": 24,
+///   "as_of": 1640995260,
+///   "scores": [
+///     { "device_id": "sensor-002", "score": 50, "status": "stale", "last_seen": 1640994600, "errors": 2.0, "breaches": 1 },
+///     { "device_id": "sensor-001", "score": 100, "status": "online", "last_seen": 1640995255, "errors": 0.0, "breaches": 0 },
+///     { "device_id": "sensor-003", "score": 100, "status": "online", "last_seen": 1640995250, "errors": 0.0, "breaches": 0 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read every device.
+#[get("/fleet/health?<hours>")]
+pub async fn fleet_health_route(
+    hours: Option<i64>,
+    state: &State<AppState>,
+    _access: FleetAccess,
+) -> Result<Json<FleetHealth>, Status> {
+    info!("Received fleet health request");
+
+    match fleet_health(state.inner(), hours).await {
+        Ok(health) => Ok(Json(health)),
+        Err(e) => {
+            error!("Error scoring fleet health: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Fleet Summary Route Handler
+// 
+// This module handles the GET /iot/fleet/summary endpoint, which gives an
+// overview dashboard the size of the fleet, how many devices reported
+// recently, and fleet-wide statistics of their current values in a single
+// call.
+
+use chrono::Utc;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::fleet::{parse_window, summarize_metrics, FleetSummary};
+use crate::app_state::AppState;
+use crate::utils::auth_guard::FleetAccess;
+use crate::utils::concurrent_reads::read_all;
+
+/// Summarizes the fleet over a recent window
+/// 
+/// This function performs the following steps:
+/// 1. Returns a cached summary if one for the same window was computed recently
+/// 2. Reads every device from the device index and counts those seen within the window
+/// 3. Reads the latest record of every reporting device concurrently, one
+///    lookup per device
+/// 4. Computes fleet-wide statistics per metric and caches the summary
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `minutes` - The requested window, in minutes
+/// 
+/// # Returns
+/// * `Result<FleetSummary, ApiError>` - The fleet summary or an error
+async fn fleet_summary(state: &AppState, minutes: Option<i64>) -> Result<FleetSummary, ApiError> {
+    let window_minutes = parse_window(minutes)?;
+
+    // Serve summaries of the same window from the cache
+    let cache_key = window_minutes.to_string();
+    if let Some(cached) = state.fleet_summary_cache.get(&cache_key) {
+        info!("Serving fleet summary from cache");
+        return Ok(cached);
+    }
+
+    let devices = state.cosmos_client.read_devices()
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    let as_of = Utc::now().timestamp();
+    let since = as_of - window_minutes * 60;
+    let reporting: Vec<&str> = devices
+        .iter()
+        .filter(|device| device.last_seen >= since)
+        .map(|device| device.device_id.as_str())
+        .collect();
+
+    let telemetry = read_all(&reporting, |device_id| async move {
+        state.cosmos_client.read_latest_telemetry(device_id)
+            .await
+            .map(|latest| latest.into_iter().collect::<Vec<_>>())
+    }, state.read_concurrency)
+    .await?;
+
+    let summary = FleetSummary {
+        devices: devices.len(),
+        reporting_devices: reporting.len(),
+        window_minutes,
+        as_of,
+        metrics: summarize_metrics(&telemetry),
+    };
+
+    state.fleet_summary_cache.insert(cache_key, summary.clone());
+
+    info!(
+        "Summarized {} device(s), {} reporting in the last {} minute(s)",
+        summary.devices, summary.reporting_devices, window_minutes
+    );
+    Ok(summary)
+}
+
+/// GET endpoint summarizing the whole fleet for overview dashboards
+/// 
+/// Devices come from the device index; a device is reporting if it was
+/// seen within the last `minutes` minutes (default 15, at most 1440).
+/// Metric statistics cover the current value of each reporting device,
+/// taken from its latest record, so the summary costs one lookup per device
+/// however much telemetry the fleet sends. Summaries are cached briefly, like aggregations.
+/// Returns 400 for an invalid window.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/fleet/summary?minutes=15
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "devices": 12,
+///   "reporting_devices": 9,
+///   "window_minutes": 15,
+///   "as_of": 1640995260,
+///   "metrics": [
+///     { "metric": "temperature", "min": 18.2, "max": 27.9, "avg": 22.4, "samples": 9, "devices": 9 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read every device.
+#[get("/fleet/summary?<minutes>")]
+pub async fn fleet_summary_route(
+    minutes: Option<i64>,
+    state: &State<AppState>,
+    _access: FleetAccess,
+) -> Result<Json<FleetSummary>, Status> {
+    info!("Received fleet summary request");
+
+    match fleet_summary(state.inner(), minutes).await {
+        Ok(summary) => Ok(Json(summary)),
+        Err(e) => {
+            error!("Error summarizing fleet: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
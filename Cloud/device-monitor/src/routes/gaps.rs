@@ -0,0 +1,133 @@
+// Telemetry Gap Detection Route Handler
+// 
+// This module handles the GET /iot/data/gaps/<device_id> endpoint, which
+// reports the time ranges in which a device sent no telemetry for longer
+// than its expected reporting interval.
+
+use chrono::Utc;
+use futures::StreamExt;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::gaps::{parse_interval, GapDetector, GapReport};
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Query parameters for gap detection
+#[derive(Debug, FromForm)]
+pub struct GapQuery {
+    /// Expected reporting interval, e.g. "30s" or "5m"
+    pub interval: String,
+    /// Earliest Unix timestamp to check
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to check (default now)
+    pub to: Option<i64>,
+}
+
+/// Finds the gaps in a device's telemetry
+/// 
+/// This function performs the following steps:
+/// 1. Validates the interval and time range
+/// 2. Streams the device's telemetry within the range, oldest first
+/// 3. Reports every silence between records, and at either end of the
+///    range, longer than the interval
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to check
+/// * `query` - The interval and time range
+/// 
+/// # Returns
+/// * `Result<GapReport, ApiError>` - The gaps or an error
+async fn find_gaps(state: &AppState, device_id: &str, query: &GapQuery) -> Result<GapReport, ApiError> {
+    info!("Detecting telemetry gaps for device {}", device_id);
+
+    let interval_secs = parse_interval(&query.interval)?;
+    let range = TimeRange::parse(query.from, query.to)?;
+    // A range ending in the future is only checked up to now
+    let to = query.to.unwrap_or(i64::MAX).min(Utc::now().timestamp());
+    if query.from.is_some_and(|from| from > to) {
+        return Err(ApiError::InvalidQuery("from must not be in the future".to_string()));
+    }
+
+    // Only timestamps are kept, so the history is streamed rather than collected
+    let mut pages = state.cosmos_client.stream_telemetry(device_id, &range, &ValueFilter::NONE, SortOrder::Asc)
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    let mut detector = GapDetector::new(interval_secs, query.from);
+    while let Some(page) = pages.next().await {
+        let page = page.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        for timestamp in page.iter().filter_map(|record| record.timestamp) {
+            detector.observe(timestamp);
+        }
+    }
+
+    range.check_device_found(device_id, detector.records())?;
+
+    let report = detector.finish(device_id, query.from, to);
+
+    info!("Found {} gap(s) in {} record(s)", report.gaps.len(), report.records);
+    Ok(report)
+}
+
+/// GET endpoint reporting the gaps in a device's telemetry
+/// 
+/// A gap is any silence longer than `interval` between two records, or
+/// between `from` and the first record. The silence after the last record
+/// up to `to` (default now) counts too, so a device that stopped reporting
+/// has an open gap. Without `from`, the check starts at the device's first
+/// record. Returns 404 if the device has no telemetry at all.
+/// 
+/// # Arguments
+/// * `device_id` - The device identifier from the URL path
+/// * `query` - The `interval`, `from` and `to` query parameters
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<GapReport>, Status>` - The gaps or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/gaps/sensor-001?interval=5m&from=1640995200&to=1641081600
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "interval_secs": 300,
+///   "from": 1640995200,
+///   "to": 1641081600,
+///   "records": 1402,
+///   "total_gap_secs": 5400,
+///   "longest_gap_secs": 3600,
+///   "gaps": [
+///     { "start": 1641020400, "end": 1641024000, "duration_secs": 3600 },
+///     { "start": 1641060000, "end": 1641061800, "duration_secs": 1800 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/gaps/<device_id>?<query..>")]
+pub async fn gaps_route(
+    device_id: &str,
+    query: GapQuery,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<GapReport>, Status> {
+    info!("Received gap detection request for device: {}", device_id);
+
+    match find_gaps(state.inner(), device_id, &query).await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            error!("Error detecting telemetry gaps: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
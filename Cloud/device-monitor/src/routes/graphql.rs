@@ -0,0 +1,68 @@
+// GraphQL Route Handlers
+// 
+// This module handles the /iot/graphql endpoint, which executes GraphQL
+// queries over devices, telemetry and aggregates, and serves the GraphiQL
+// explorer for writing them.
+
+use async_graphql::http::GraphiQLSource;
+use rocket::response::content::RawHtml;
+use rocket::serde::json::Json;
+use rocket::State;
+use tracing::{info, error};
+
+use crate::graphql::TelemetrySchema;
+use crate::utils::auth_guard::ReadAccess;
+
+/// POST endpoint executing a GraphQL query
+/// 
+/// The body is a standard GraphQL request with `query` and optional
+/// `variables` and `operationName`. As usual for GraphQL, the response is
+/// 200 OK even when the query fails, and failures are listed in `errors`,
+/// each with the HTTP status the REST API would return in its `code`
+/// extension.
+/// 
+/// Devices the caller's credentials may not read are left out of `devices`,
+/// `device` returns null for them, and `telemetry` and `aggregate` fail
+/// with code 403.
+/// 
+/// # Example Request
+/// ```json
+/// {
+///   "query": "{ devices { deviceId status telemetry(limit: 1) { timestamp value(metric: \"temperature\") } } }"
+/// }
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "data": {
+///     "devices": [
+///       {
+///         "deviceId": "sensor-001",
+///         "status": "ONLINE",
+///         "telemetry": [{ "timestamp": 1640995260, "value": "24.1" }]
+///       }
+///     ]
+///   }
+/// }
+/// ```
+#[post("/graphql", format = "json", data = "<request>")]
+pub async fn graphql_route(
+    access: ReadAccess,
+    schema: &State<TelemetrySchema>,
+    request: Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    info!("Received GraphQL request");
+
+    let response = schema.execute(request.into_inner().data(access.0)).await;
+    for e in &response.errors {
+        error!("Error executing GraphQL query: {}", e.message);
+    }
+    Json(response)
+}
+
+/// GET endpoint serving the GraphiQL explorer for the GraphQL endpoint
+#[get("/graphql")]
+pub fn graphiql_route() -> RawHtml<String> {
+    RawHtml(GraphiQLSource::build().endpoint("/iot/graphql").finish())
+}
@@ -0,0 +1,123 @@
+// Metric Histogram Route Handler
+// 
+// This module handles the GET /iot/data/histogram/<device_id> endpoint,
+// which returns the distribution of a device's metric over a time range as
+// bins of values, to reveal e.g. bimodal sensor behaviour.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::histogram::{histogram, Binning, Histogram};
+use crate::domain::time_range::TimeRange;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+use crate::routes::stats::read_metric_values;
+
+/// Query parameters for metric histograms
+#[derive(Debug, FromForm)]
+pub struct HistogramQuery {
+    /// The telemetry metric to bin (e.g. "temperature")
+    pub metric: String,
+    /// Width of each bin, in the metric's unit
+    pub width: Option<f64>,
+    /// Number of bins (default 20)
+    pub bins: Option<usize>,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+}
+
+/// Bins the values of a device's metric
+/// 
+/// This function performs the following steps:
+/// 1. Validates the metric, binning and time range
+/// 2. Streams the device's telemetry within the range, keeping only the
+///    metric's numeric samples
+/// 3. Counts the samples in each bin
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to bin
+/// * `query` - The metric, binning and time range
+/// 
+/// # Returns
+/// * `Result<Histogram, ApiError>` - The histogram or an error
+async fn metric_histogram(
+    state: &AppState,
+    device_id: &str,
+    query: &HistogramQuery,
+) -> Result<Histogram, ApiError> {
+    info!("Computing histogram of {} for device {}", query.metric, device_id);
+
+    if query.metric.trim().is_empty() {
+        return Err(ApiError::InvalidQuery("metric is required".to_string()));
+    }
+    let binning = Binning::parse(query.width, query.bins)?;
+    let range = TimeRange::parse(query.from, query.to)?;
+
+    let values = read_metric_values(state, device_id, &query.metric, &range).await?;
+    histogram(device_id, &query.metric, &values, binning)
+}
+
+/// GET endpoint returning the distribution of a device's metric
+/// 
+/// The numeric samples of `metric` within the optional `from`/`to` range
+/// are counted in bins, either `bins` equal bins spanning the smallest to
+/// the largest sample (default 20), or bins of `width` aligned to
+/// multiples of the width. Every bin in between is returned, including
+/// empty ones, up to 1000 bins. Without any numeric sample, no bins are
+/// returned. Returns 404 if the device has no telemetry at all.
+/// 
+/// # Arguments
+/// * `device_id` - The device identifier from the URL path
+/// * `query` - The `metric`, `width`, `bins`, `from` and `to` query parameters
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<Histogram>, Status>` - The histogram or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/histogram/sensor-001?metric=temperature&width=0.5&from=1640995200&to=1641081600
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "metric": "temperature",
+///   "count": 1440,
+///   "min": 21.2,
+///   "max": 22.9,
+///   "bin_width": 0.5,
+///   "bins": [
+///     { "lower": 21.0, "upper": 21.5, "count": 610 },
+///     { "lower": 21.5, "upper": 22.0, "count": 12 },
+///     { "lower": 22.0, "upper": 22.5, "count": 25 },
+///     { "lower": 22.5, "upper": 23.0, "count": 793 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/histogram/<device_id>?<query..>")]
+pub async fn histogram_route(
+    device_id: &str,
+    query: HistogramQuery,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<Histogram>, Status> {
+    info!("Received histogram request for device: {}", device_id);
+
+    match metric_histogram(state.inner(), device_id, &query).await {
+        Ok(histogram) => Ok(Json(histogram)),
+        Err(e) => {
+            error!("Error computing histogram: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Latest Telemetry Route Handler
+// 
+// This module handles the GET /iot/data/latest/<device_id> endpoint for
+// retrieving only the most recent telemetry record of a device, so
+// dashboards showing current values do not download the full history,
+// optionally converted to other units and revalidated with a weak ETag.
+
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::etag::EntityTag;
+use crate::domain::telemetry::Telemetry;
+use crate::domain::units::UnitConversion;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+use crate::utils::conditional::{Conditional, IfNoneMatch};
+
+/// Retrieves the most recent telemetry record of a device
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device
+/// * `unit` - Comma-separated units to convert metric values to, if any
+/// 
+/// # Returns
+/// * `Result<Telemetry, ApiError>` - The record with the latest timestamp, or a not found error
+async fn read_latest(state: &AppState, device_id: &str, unit: Option<&str>) -> Result<Telemetry, ApiError> {
+    info!("Reading latest telemetry for device: {}", device_id);
+
+    if device_id.trim().is_empty() {
+        return Err(ApiError::DeviceNotFound(device_id.to_string()));
+    }
+    let conversion = UnitConversion::parse(unit, &state.metric_units)?;
+
+    let telemetry = state.cosmos_client.read_latest_telemetry(device_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApiError::DeviceNotFound(device_id.to_string()))?;
+    Ok(match conversion {
+        Some(conversion) => conversion.apply(telemetry),
+        None => telemetry,
+    })
+}
+
+/// GET endpoint for the most recent telemetry record of a device
+/// 
+/// Reads the device's document in the latest-record container instead of
+/// the device's full history. Returns 404 if the device has no
+/// telemetry. With `unit`, metric values are converted like in the read
+/// endpoint. The response carries a weak `ETag` derived from the record's
+/// timestamp, and 304 Not Modified is returned when `If-None-Match` matches it.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/latest/sensor-001
+/// GET /iot/data/latest/sensor-001?unit=fahrenheit
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "telemetry_data": {
+///     "temperature": "24.1",
+///     "humidity": "44.8"
+///   },
+///   "timestamp": 1640995260
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/latest/<device_id>?<unit>")]
+pub async fn latest_telemetry_route(
+    state: &State<AppState>,
+    _access: DeviceAccess,
+    device_id: &str,
+    unit: Option<&str>,
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<Json<Telemetry>>, Status> {
+    info!("Received latest telemetry request for device: {}", device_id);
+
+    match read_latest(state.inner(), device_id, unit).await {
+        Ok(telemetry) => {
+            let etag = telemetry.timestamp.map(EntityTag::from_latest);
+            match etag {
+                Some(etag) if if_none_match.matches(&etag) => Ok(Conditional::NotModified(etag)),
+                etag => Ok(Conditional::Modified(etag, Json(telemetry))),
+            }
+        }
+        Err(e) => {
+            error!("Error reading latest telemetry: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
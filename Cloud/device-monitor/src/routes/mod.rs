@@ -4,4 +4,28 @@
 // monitoring service API endpoints.
 
 pub mod read_telemetry;
+pub mod batch_read;
+pub mod aggregate_by_tag;
+pub mod annotations;
+pub mod latest_telemetry;
+pub mod aggregate;
+pub mod export;
+pub mod stream_telemetry;
+pub mod subscribe_telemetry;
+pub mod devices;
 
+pub mod fleet_summary;
+pub mod fleet_health;
+pub mod alerts;
+pub mod anomalies;
+pub mod graphql;
+pub mod retention;
+pub mod device_metrics;
+pub mod compare;
+pub mod gaps;
+pub mod uptime;
+pub mod stats;
+pub mod histogram;
+pub mod rate;
+pub mod count;
+pub mod archives;
@@ -0,0 +1,146 @@
+// Rate of Change Route Handler
+// 
+// This module handles the GET /iot/data/rate/<device_id> endpoint, which
+// returns the rate of change of a device's metric, e.g. how fast a
+// temperature rises per minute, as a derived series for charting and
+// spotting fast ramps.
+
+use futures::StreamExt;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::error::ApiError;
+use crate::domain::rate::{metric_readings, parse_per, rate_of_change, RateSeries, Smoothing};
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Query parameters for rate-of-change series
+#[derive(Debug, FromForm)]
+pub struct RateQuery {
+    /// The telemetry metric to differentiate (e.g. "temperature")
+    pub metric: String,
+    /// Time unit of the rate: second, minute (default) or hour
+    pub per: Option<String>,
+    /// Smoothing: none (default), sma or ewma
+    pub smoothing: Option<String>,
+    /// Number of rates in the moving average (default 5)
+    pub window: Option<usize>,
+    /// Weight of the newest rate in the EWMA (default 0.3)
+    pub alpha: Option<f64>,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+}
+
+/// Computes the rate of change of a device's metric
+/// 
+/// This function performs the following steps:
+/// 1. Validates the metric, unit, smoothing and time range
+/// 2. Streams the device's telemetry within the range, oldest first,
+///    keeping only the metric's timestamped readings
+/// 3. Computes the smoothed rate between each pair of consecutive readings
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to differentiate
+/// * `query` - The metric, unit, smoothing and time range
+/// 
+/// # Returns
+/// * `Result<RateSeries, ApiError>` - The rate series or an error
+async fn rate(state: &AppState, device_id: &str, query: &RateQuery) -> Result<RateSeries, ApiError> {
+    info!("Computing rate of change of {} for device {}", query.metric, device_id);
+
+    if query.metric.trim().is_empty() {
+        return Err(ApiError::InvalidQuery("metric is required".to_string()));
+    }
+    let per_secs = parse_per(query.per.as_deref())?;
+    let smoothing = Smoothing::parse(query.smoothing.as_deref(), query.window, query.alpha)?;
+    let range = TimeRange::parse(query.from, query.to)?;
+
+    // Only the readings are kept, so the history is streamed rather than collected
+    let mut pages = state.cosmos_client.stream_telemetry(device_id, &range, &ValueFilter::NONE, SortOrder::Asc)
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    let mut records = 0;
+    let mut readings = Vec::new();
+    while let Some(page) = pages.next().await {
+        let page = page.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        records += page.len();
+        readings.extend(metric_readings(&page, &query.metric));
+    }
+
+    range.check_device_found(device_id, records)?;
+
+    let points = rate_of_change(&readings, per_secs, smoothing);
+
+    info!("Computed {} rate(s) from {} reading(s)", points.len(), readings.len());
+    Ok(RateSeries {
+        device_id: device_id.to_string(),
+        metric: query.metric.clone(),
+        per_secs,
+        smoothing,
+        points,
+    })
+}
+
+/// GET endpoint returning the rate of change of a device's metric
+/// 
+/// For each reading of `metric` within the optional `from`/`to` range
+/// after the first, the change since the previous reading is divided by the
+/// time between them and expressed `per` second, minute or hour. With
+/// `smoothing=sma`, each rate is the mean of the last `window` rates; with
+/// `smoothing=ewma`, an exponentially weighted mean with weight `alpha`.
+/// Readings sharing the previous reading's timestamp are skipped. Returns
+/// 404 if the device has no telemetry at all.
+/// 
+/// # Arguments
+/// * `device_id` - The device identifier from the URL path
+/// * `query` - The `metric`, `per`, `smoothing`, `window`, `alpha`, `from` and `to`
+///   query parameters
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<RateSeries>, Status>` - The rate series or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/rate/sensor-001?metric=temperature&per=minute&smoothing=sma&window=5
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "metric": "temperature",
+///   "per_secs": 60,
+///   "smoothing": { "type": "sma", "window": 5 },
+///   "points": [
+///     { "timestamp": 1640995260, "rate": 0.6 },
+///     { "timestamp": 1640995320, "rate": 0.45 }
+///   ]
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/rate/<device_id>?<query..>")]
+pub async fn rate_route(
+    device_id: &str,
+    query: RateQuery,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<RateSeries>, Status> {
+    info!("Received rate of change request for device: {}", device_id);
+
+    match rate(state.inner(), device_id, &query).await {
+        Ok(series) => Ok(Json(series)),
+        Err(e) => {
+            error!("Error computing rate of change: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
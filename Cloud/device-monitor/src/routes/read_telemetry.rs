@@ -1,31 +1,250 @@
 // Telemetry Retrieval Route Handler
 // 
 // This module handles the GET /iot/data/read/<device_id> endpoint for
-// retrieving telemetry data from IoT devices for monitoring purposes.
+// retrieving telemetry data from IoT devices for monitoring purposes,
+// optionally restricted to a time window, to readings meeting value
+// conditions and to some metrics, paged or downsampled for charting,
+// converted to other units, and together with the device's timeline
+// annotations. Full histories are streamed to the response page by page
+// rather than collected in memory, and unchanged telemetry is revalidated
+// with a weak ETag instead of being sent again.
 
+use futures::StreamExt;
+use rocket::http::ContentType;
+use rocket::response::stream::TextStream;
+use rocket::response::{self, Responder, Response};
 use rocket::serde::json::Json;
-use rocket::{State, http::Status};
+use rocket::{Either, Request, State, http::Status};
+use serde::Serialize;
 use tracing::{info, error};
+use crate::domain::annotation::Annotation;
+use crate::domain::downsample::Downsample;
+use crate::domain::etag::EntityTag;
+use crate::domain::export::parse_metrics;
+use crate::domain::paging::PageRequest;
+use crate::domain::sort_order::SortOrder;
 use crate::domain::telemetry::Telemetry;
+use crate::domain::time_range::TimeRange;
+use crate::domain::units::UnitConversion;
+use crate::domain::value_filter::ValueFilter;
 use crate::domain::error::ApiError;
 use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+use crate::utils::conditional::{Conditional, IfNoneMatch};
+use crate::routes::annotations::read_annotations;
+use crate::services::TelemetryPages;
+
+/// Response of the telemetry endpoint
+/// 
+/// Without `?annotations=true` the plain telemetry array is returned, so
+/// existing clients are unaffected.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ReadResponse {
+    /// Telemetry records only
+    Telemetry(Vec<Telemetry>),
+    /// Telemetry records and the annotations overlapping them
+    WithAnnotations {
+        telemetry: Vec<Telemetry>,
+        annotations: Vec<Annotation>,
+    },
+}
+
+/// Query parameters of the telemetry endpoint
+#[derive(Debug, Default, FromForm)]
+pub struct ReadQuery {
+    /// When true, also return the annotations overlapping the telemetry
+    pub annotations: Option<bool>,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+    /// Maximum number of records to return
+    pub limit: Option<usize>,
+    /// Token returned with the previous page
+    pub continuation: Option<String>,
+    /// Metric whose series `points` reduces
+    pub metric: Option<String>,
+    /// Number of records to reduce the series of `metric` to
+    pub points: Option<usize>,
+    /// Comma-separated metrics to return from each record
+    pub metrics: Option<String>,
+    /// "asc" for oldest first or "desc" for newest first (default)
+    pub order: Option<String>,
+    /// Comma-separated units to convert metric values to (e.g. "fahrenheit,kpa")
+    pub unit: Option<String>,
+    /// Comma-separated conditions on metric values records must meet (e.g. "temperature>30")
+    #[field(name = "where")]
+    pub filter: Option<String>,
+}
+
+/// Name of the response header carrying the token for the next page
+pub const CONTINUATION_HEADER: &str = "X-Continuation-Token";
+
+/// A telemetry response, with the token for the next page if one follows
+#[derive(Debug)]
+pub struct ReadPage {
+    /// The response body
+    pub body: ReadResponse,
+    /// Token to pass as `continuation` to read the next page
+    pub continuation: Option<String>,
+}
+
+impl<'r> Responder<'r, 'r> for ReadPage {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        let mut builder = Response::build_from(Json(self.body).respond_to(request)?);
+        if let Some(token) = self.continuation {
+            builder.raw_header(CONTINUATION_HEADER, token);
+        }
+        builder.ok()
+    }
+}
+
+/// A device's telemetry, written to the response as a JSON array page by page
+/// 
+/// If reading a page fails mid-response, the error is logged and the array
+/// is left unterminated, so clients fail to parse it rather than mistaking
+/// it for the full history.
+pub struct TelemetryStream {
+    /// The pages of records to write
+    pages: TelemetryPages,
+    /// The metrics to keep in each record, or None for every metric
+    metrics: Option<Vec<String>>,
+    /// The units to convert metric values to, or None to keep the reported units
+    conversion: Option<UnitConversion>,
+}
+
+/// Progress of writing a `TelemetryStream`
+struct JsonArrayState {
+    stream: TelemetryStream,
+    /// Whether no record has been written yet
+    empty: bool,
+}
+
+impl<'r> Responder<'r, 'r> for TelemetryStream {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        let state = JsonArrayState { stream: self, empty: true };
+        let elements = futures::stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            match state.stream.pages.next().await {
+                Some(Ok(page)) => {
+                    let mut chunk = String::new();
+                    for record in page {
+                        let record = match &state.stream.metrics {
+                            Some(metrics) => match record.select_metrics(metrics) {
+                                Some(record) => record,
+                                None => continue,
+                            },
+                            None => record,
+                        };
+                        let record = match &state.stream.conversion {
+                            Some(conversion) => conversion.apply(record),
+                            None => record,
+                        };
+                        if !std::mem::take(&mut state.empty) {
+                            chunk.push(',');
+                        }
+                        chunk.push_str(&serde_json::to_string(&record).unwrap_or_default());
+                    }
+                    Some((chunk, Some(state)))
+                }
+                Some(Err(e)) => {
+                    error!("Error streaming telemetry: {}", e);
+                    None
+                }
+                None => Some(("]".to_string(), None)),
+            }
+        });
+        let body = futures::stream::once(async { "[".to_string() }).chain(elements);
+
+        Response::build_from(TextStream(body).respond_to(request)?)
+            .header(ContentType::JSON)
+            .ok()
+    }
+}
+
+/// Starts streaming the telemetry of a specific device from the database
+/// 
+/// The first page is read before responding, so a device without any
+/// telemetry is still reported as not found.
+/// 
+/// # Arguments
+/// * `device_id` - The unique identifier of the device to monitor
+/// * `range` - The timestamps to include
+/// * `filter` - The conditions on metric values records must meet
+/// * `order` - Whether to read the oldest or the newest records first
+/// * `metrics` - The metrics to keep in each record, or None for every metric
+/// * `conversion` - The units to convert metric values to, or None to keep the reported units
+/// * `state` - Application state containing the database client
+/// 
+/// # Returns
+/// * `Result<TelemetryStream, ApiError>` - The stream of telemetry records or an error
+async fn stream_telemetry(
+    device_id: &str,
+    range: TimeRange,
+    filter: &ValueFilter,
+    order: SortOrder,
+    metrics: Option<Vec<String>>,
+    conversion: Option<UnitConversion>,
+    state: &State<AppState>,
+) -> Result<TelemetryStream, ApiError> {
+    info!("Streaming telemetry for device: {}", device_id);
+
+    if device_id.trim().is_empty() {
+        error!("Empty device ID provided");
+        return Err(ApiError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let mut pages = state.query_cache.stream_telemetry(device_id, &range, filter, order)
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    let mut first = Vec::new();
+    while first.is_empty() {
+        match pages.next().await {
+            Some(page) => first = page.map_err(|e| {
+                error!("Database error reading telemetry: {}", e);
+                ApiError::DatabaseError(e.to_string())
+            })?,
+            None => break,
+        }
+    }
+
+    // A time window or value filter without records is just empty
+    if first.is_empty() && range.is_unbounded() && filter.is_empty() {
+        info!("No telemetry found for device: {}", device_id);
+        return Err(ApiError::DeviceNotFound(device_id.to_string()));
+    }
+
+    let pages = futures::stream::iter([Ok(first)]).chain(pages).boxed();
+    Ok(TelemetryStream { pages, metrics, conversion })
+}
 
 /// Retrieves telemetry data for a specific device from the database
 /// 
-/// This function queries the Cosmos DB container for all telemetry
-/// records associated with the given device ID. It performs validation
-/// and error handling for the monitoring use case.
+/// This function queries the Cosmos DB container for the telemetry
+/// records associated with the given device ID within a time range, in the
+/// requested order, or for one page of them if a page is requested. It performs
+/// validation and error handling for the monitoring use case.
 /// 
 /// # Arguments
 /// * `device_id` - The unique identifier of the device to monitor
+/// * `range` - The timestamps to include
+/// * `filter` - The conditions on metric values records must meet
+/// * `order` - Whether to read the oldest or the newest records first
+/// * `page` - The page to read, or None to read every matching record
 /// * `state` - Application state containing the database client
 /// 
 /// # Returns
-/// * `Result<Json<Vec<Telemetry>>, ApiError>` - List of telemetry records or an error
+/// * `Result<(Vec<Telemetry>, Option<String>), ApiError>` - List of telemetry records and the
+///   continuation token for the next page, or an error
 async fn read_telemetry(
     device_id: &str,
+    range: TimeRange,
+    filter: &ValueFilter,
+    order: SortOrder,
+    page: Option<PageRequest>,
     state: &State<AppState>,
-) -> Result<Json<Vec<Telemetry>>, ApiError> {
+) -> Result<(Vec<Telemetry>, Option<String>), ApiError> {
     info!("Reading telemetry for device: {}", device_id);
 
     // Validate device_id is not empty
@@ -34,25 +253,154 @@ async fn read_telemetry(
         return Err(ApiError::DeviceNotFound(device_id.to_string()));
     }
 
-    // Get a clone of the Cosmos DB client for database operations
-    let cosmos_client = state.inner().cosmos_client.clone();
-    
-    // Query the database for telemetry data for the specified device
-    let container = cosmos_client.read_telemetry(device_id)
+    // Query the database, or the cache of recent queries, for telemetry data for the specified device
+    let mut container = state.query_cache.query_telemetry(device_id, &range, filter, order, page.as_ref())
         .await
         .map_err(|e| {
             error!("Database error reading telemetry: {}", e);
             ApiError::DatabaseError(e.to_string())
         })?;
 
-    // Check if any telemetry data was found for the device
-    if container.is_empty() {
+    // Check if any telemetry data was found for the device; a time window or
+    // value filter without records or a page past the last record is just empty
+    if container.is_empty() && range.is_unbounded() && filter.is_empty() && page.as_ref().is_none_or(|page| page.after.is_none()) {
         info!("No telemetry found for device: {}", device_id);
         return Err(ApiError::DeviceNotFound(device_id.to_string()));
     }
 
+    // A page is read with one extra record to tell whether another follows
+    let continuation = page.and_then(|page| {
+        let more = container.len() > page.limit;
+        container.truncate(page.limit);
+        page.next_token(more, container.last())
+    });
+
     info!("Found {} telemetry entries for device: {}", container.len(), device_id);
-    Ok(Json(container))
+    Ok((container, continuation))
+}
+
+/// Computes the entity tag of a read from the device's latest record
+/// 
+/// Reads with annotations are not tagged, as annotations can change
+/// without new telemetry. Cached query results older than the latest
+/// record are dropped, so the tagged response is never older than its tag.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device to monitor
+/// * `query` - The query parameters of the request
+/// 
+/// # Returns
+/// * `Result<Option<EntityTag>, ApiError>` - The tag, None if the read cannot be tagged, or an error
+async fn read_etag(state: &AppState, device_id: &str, query: &ReadQuery) -> Result<Option<EntityTag>, ApiError> {
+    if query.annotations.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let latest = state.cosmos_client.read_latest_telemetry(device_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .and_then(|record| record.timestamp);
+    if let Some(latest) = latest {
+        state.query_cache.revalidate(device_id, latest);
+    }
+    Ok(latest.map(EntityTag::from_latest))
+}
+
+/// Reads telemetry as requested unless the client's copy is current
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device to monitor
+/// * `query` - The query parameters of the request
+/// * `if_none_match` - The request's `If-None-Match` header
+/// 
+/// # Returns
+/// * `Result<Conditional<Either<ReadPage, TelemetryStream>>, ApiError>` - Not modified, the
+///   tagged response, or an error
+async fn conditional_read(
+    state: &State<AppState>,
+    device_id: &str,
+    query: &ReadQuery,
+    if_none_match: &IfNoneMatch,
+) -> Result<Conditional<Either<ReadPage, TelemetryStream>>, ApiError> {
+    let etag = read_etag(state.inner(), device_id, query).await?;
+    if let Some(etag) = etag.as_ref().filter(|etag| if_none_match.matches(etag)) {
+        info!("Telemetry of device {} not modified", device_id);
+        return Ok(Conditional::NotModified(etag.clone()));
+    }
+
+    let response = read_query(state, device_id, query).await?;
+    Ok(Conditional::Modified(etag, response))
+}
+
+/// Reads, filters, downsamples, narrows, converts and annotates telemetry as requested by the query parameters
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The unique identifier of the device to monitor
+/// * `query` - The query parameters of the request
+/// 
+/// # Returns
+/// * `Result<Either<ReadPage, TelemetryStream>, ApiError>` - The response body and continuation
+///   token, the streamed records of an unpaged read, or an error
+async fn read_query(
+    state: &State<AppState>,
+    device_id: &str,
+    query: &ReadQuery,
+) -> Result<Either<ReadPage, TelemetryStream>, ApiError> {
+    let range = TimeRange::parse(query.from, query.to)?;
+    let page = PageRequest::parse(query.limit, query.continuation.as_deref())?;
+    let order = SortOrder::parse(query.order.as_deref())?;
+    let downsample = Downsample::parse(query.metric.as_deref(), query.points)?;
+    if downsample.is_some() && page.is_some() {
+        return Err(ApiError::InvalidQuery("points cannot be combined with limit or continuation".to_string()));
+    }
+    let conversion = UnitConversion::parse(query.unit.as_deref(), &state.metric_units)?;
+    let filter = ValueFilter::parse(query.filter.as_deref())?;
+
+    // Plain reads of every matching record are streamed instead of collected
+    let with_annotations = query.annotations.unwrap_or(false);
+    if page.is_none() && downsample.is_none() && !with_annotations {
+        let metrics = parse_metrics(query.metrics.as_deref());
+        return stream_telemetry(device_id, range, &filter, order, metrics, conversion, state).await.map(Either::Right);
+    }
+
+    // Downsampling works on the series oldest first, so it is read in that
+    // order and put in the requested order afterwards
+    let read_order = if downsample.is_some() { SortOrder::Asc } else { order };
+    let (mut telemetry, continuation) = read_telemetry(device_id, range, &filter, read_order, page, state).await?;
+    if let Some(downsample) = downsample {
+        let total = telemetry.len();
+        telemetry = downsample.apply(telemetry);
+        if order == SortOrder::Desc {
+            telemetry.reverse();
+        }
+        info!("Downsampled {} record(s) to {} on {}", total, telemetry.len(), downsample.metric);
+    }
+    if let Some(metrics) = parse_metrics(query.metrics.as_deref()) {
+        telemetry = telemetry
+            .into_iter()
+            .filter_map(|record| record.select_metrics(&metrics))
+            .collect();
+    }
+    if let Some(conversion) = conversion {
+        telemetry = telemetry.into_iter().map(|record| conversion.apply(record)).collect();
+    }
+
+    if !with_annotations {
+        return Ok(Either::Left(ReadPage { body: ReadResponse::Telemetry(telemetry), continuation }));
+    }
+
+    // Only annotations overlapping the requested window, or else the
+    // returned telemetry, are relevant
+    let from = query.from.or_else(|| telemetry.iter().filter_map(|t| t.timestamp).min());
+    let to = query.to.or_else(|| telemetry.iter().filter_map(|t| t.timestamp).max());
+    let annotations = read_annotations(state.inner(), device_id, from, to).await?;
+    Ok(Either::Left(ReadPage {
+        body: ReadResponse::WithAnnotations { telemetry, annotations },
+        continuation,
+    }))
 }
 
 /// GET endpoint for retrieving device telemetry data for monitoring
@@ -61,16 +409,50 @@ async fn read_telemetry(
 /// from the database. The endpoint expects a device ID as a path parameter
 /// and returns a JSON array of telemetry records for monitoring purposes.
 /// 
+/// Records are returned newest first, or oldest first with `order=asc`.
+/// With `from` and/or `to` (inclusive Unix timestamps), only the records
+/// in that window are read, and an empty window returns an empty array.
+/// With `limit` (at most 1000) and/or `continuation`, one page of records
+/// is returned instead. If more records follow, the token for the next
+/// page is sent in the `X-Continuation-Token` header; it must be used with
+/// the same `order`. Without `limit`, `continuation`, `points` or
+/// `annotations`, the records are streamed from Cosmos DB to the response
+/// page by page, so a long history is never held in memory at once.
+/// With `metric` and `points`, the series of that metric is reduced to at
+/// most `points` records with largest-triangle-three-buckets downsampling,
+/// which keeps the shape of the chart; records without the metric are
+/// left out.
+/// With `metrics` (comma-separated), each record only carries those keys
+/// of `telemetry_data`, and records reporting none of them are left out.
+/// With `unit` (comma-separated, at most one per quantity, e.g.
+/// `fahrenheit,kpa`), the values of every metric whose unit in
+/// `METRIC_UNITS` measures the same quantity are converted to it.
+/// With `where` (comma-separated conditions combined with AND, e.g.
+/// `temperature>30,humidity<=20`), only records whose metric values meet
+/// every condition are read; the conditions are validated and applied in
+/// the Cosmos DB query, and records without a numeric value are left out.
+/// Responses without annotations carry a weak `ETag` derived from the
+/// device's latest record; when `If-None-Match` matches it, 304 Not
+/// Modified is returned without reading the telemetry.
+/// 
 /// # Arguments
 /// * `device_id` - The device identifier from the URL path
+/// * `query` - The `annotations`, `from`, `to`, `limit`, `continuation`,
+///   `metric`, `points`, `metrics`, `order`, `unit` and `where` query parameters
 /// * `state` - Application state injected by Rocket
+/// * `if_none_match` - The `If-None-Match` header, if any
 /// 
 /// # Returns
-/// * `Result<Json<Vec<Telemetry>>, Status>` - JSON array of telemetry records or HTTP error status
+/// * `Result<Conditional<Either<ReadPage, TelemetryStream>>, Status>` - JSON array of telemetry
+///   records (or an object with `telemetry` and `annotations`), 304 Not Modified, or HTTP error status
 /// 
 /// # Example Request
 /// ```bash
-/// GET /iot/data/read/sensor-001
+/// GET /iot/data/read/sensor-001?from=1640995200&to=1641081600&limit=2&order=asc
+/// GET /iot/data/read/sensor-001?metric=temperature&points=500
+/// GET /iot/data/read/sensor-001?metrics=temperature,humidity
+/// GET /iot/data/read/sensor-001?unit=fahrenheit
+/// GET /iot/data/read/sensor-001?where=temperature%3E30
 /// ```
 /// 
 /// # Example Response
@@ -94,18 +476,23 @@ async fn read_telemetry(
 ///   }
 /// ]
 /// ```
-#[get("/read/<device_id>")]
+/// 
+/// Requires credentials that may read the device.
+#[get("/read/<device_id>?<query..>")]
 pub async fn read(
     device_id: &str,
+    query: ReadQuery,
     state: &State<AppState>,
-) -> Result<Json<Vec<Telemetry>>, Status> {
+    _access: DeviceAccess,
+    if_none_match: IfNoneMatch,
+) -> Result<Conditional<Either<ReadPage, TelemetryStream>>, Status> {
     info!("Received telemetry monitoring request for device: {}", device_id);
     
-    // Retrieve the telemetry data and handle any errors
-    match read_telemetry(device_id, state).await {
-        Ok(telemetry) => {
+    // Retrieve the telemetry data (and annotations if requested) and handle any errors
+    match conditional_read(state, device_id, &query, &if_none_match).await {
+        Ok(response) => {
             info!("Successfully retrieved telemetry for device: {}", device_id);
-            Ok(telemetry)
+            Ok(response)
         }
         Err(e) => {
             error!("Error reading telemetry: {}", e);
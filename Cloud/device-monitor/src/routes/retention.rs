@@ -0,0 +1,52 @@
+// Retention Route Handler
+// 
+// This module handles the GET /iot/retention endpoint, which reports the
+// telemetry retention policy and how many records the background cleanup
+// has reclaimed.
+
+use rocket::serde::json::Json;
+use rocket::State;
+use tracing::info;
+
+use crate::domain::retention::RetentionStatus;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::FleetAccess;
+
+/// GET endpoint reporting the retention policy and cleanup statistics
+/// 
+/// Retention periods are in days; `default_days` is null when telemetry is
+/// kept forever unless a device has an override. Statistics cover the runs
+/// since the service started, and `last_run` is null until the first run.
+/// 
+/// Requires credentials that may read every device.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/retention
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "policy": { "default_days": 90, "device_days": { "sensor-001": 365 } },
+///   "interval_secs": 3600,
+///   "runs": 3,
+///   "total_records_deleted": 5120,
+///   "last_run": {
+///     "started_at": "2022-01-01T00:00:00Z",
+///     "duration_ms": 840,
+///     "devices_checked": 12,
+///     "records_deleted": 1440,
+///     "devices_failed": 0,
+///     "devices": [
+///       { "device_id": "sensor-002", "cutoff": 1633219200, "records_deleted": 1440 }
+///     ]
+///   }
+/// }
+/// ```
+#[get("/retention")]
+pub fn retention_route(state: &State<AppState>, _access: FleetAccess) -> Json<RetentionStatus> {
+    info!("Received retention status request");
+
+    Json(state.retention_job.status())
+}
@@ -0,0 +1,151 @@
+// Metric Statistics Route Handler
+// 
+// This module handles the GET /iot/data/stats/<device_id> endpoint, which
+// summarizes a device's metric over a time range with its count, mean,
+// standard deviation, extremes, percentiles and latest sample.
+
+use futures::StreamExt;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::aggregate::metric_values;
+use crate::domain::error::ApiError;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::stats::{metric_stats, MetricStats};
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Query parameters for metric statistics
+#[derive(Debug, FromForm)]
+pub struct StatsQuery {
+    /// The telemetry metric to summarize (e.g. "temperature")
+    pub metric: String,
+    /// Earliest Unix timestamp to include
+    pub from: Option<i64>,
+    /// Latest Unix timestamp to include
+    pub to: Option<i64>,
+}
+
+/// Reads the numeric samples of a device's metric within a time range
+/// 
+/// Only the samples are kept, so the history is streamed rather than collected.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to read
+/// * `metric` - The metric name (e.g. "temperature")
+/// * `range` - The timestamps to include
+/// 
+/// # Returns
+/// * `Result<Vec<f64>, ApiError>` - The samples, oldest first, or a not found error
+///   if the range is unbounded and the device has no telemetry
+pub async fn read_metric_values(
+    state: &AppState,
+    device_id: &str,
+    metric: &str,
+    range: &TimeRange,
+) -> Result<Vec<f64>, ApiError> {
+    let mut pages = state.cosmos_client.stream_telemetry(device_id, range, &ValueFilter::NONE, SortOrder::Asc)
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    let mut records = 0;
+    let mut values = Vec::new();
+    while let Some(page) = pages.next().await {
+        let page = page.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        records += page.len();
+        values.extend(metric_values(&page, metric));
+    }
+
+    range.check_device_found(device_id, records)?;
+
+    info!("Read {} sample(s) from {} record(s)", values.len(), records);
+    Ok(values)
+}
+
+/// Summarizes a device's metric
+/// 
+/// This function performs the following steps:
+/// 1. Validates the metric and time range
+/// 2. Streams the device's telemetry within the range, keeping only the
+///    metric's numeric samples
+/// 3. Computes the statistics of the samples
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device whose telemetry to summarize
+/// * `query` - The metric and time range
+/// 
+/// # Returns
+/// * `Result<MetricStats, ApiError>` - The statistics or an error
+async fn stats(state: &AppState, device_id: &str, query: &StatsQuery) -> Result<MetricStats, ApiError> {
+    info!("Computing statistics of {} for device {}", query.metric, device_id);
+
+    if query.metric.trim().is_empty() {
+        return Err(ApiError::InvalidQuery("metric is required".to_string()));
+    }
+    let range = TimeRange::parse(query.from, query.to)?;
+
+    let values = read_metric_values(state, device_id, &query.metric, &range).await?;
+    Ok(metric_stats(device_id, &query.metric, values))
+}
+
+/// GET endpoint summarizing a device's metric for SLO reporting
+/// 
+/// The numeric samples of `metric` within the optional `from`/`to` range
+/// are summarized with their count, mean, population standard deviation,
+/// min, max, 50th, 95th and 99th percentiles and most recent sample
+/// (`latest`). Percentiles interpolate linearly between the closest
+/// samples. Without any numeric sample, the count is 0 and every statistic
+/// is null. Returns 404 if the device has no telemetry at all.
+/// 
+/// # Arguments
+/// * `device_id` - The device identifier from the URL path
+/// * `query` - The `metric`, `from` and `to` query parameters
+/// * `state` - Application state injected by Rocket
+/// 
+/// # Returns
+/// * `Result<Json<MetricStats>, Status>` - The statistics or HTTP error status
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/data/stats/sensor-001?metric=temperature&from=1640995200&to=1641081600
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "device_id": "sensor-001",
+///   "metric": "temperature",
+///   "count": 1440,
+///   "mean": 23.4,
+///   "stddev": 1.2,
+///   "min": 20.1,
+///   "max": 27.9,
+///   "p50": 23.3,
+///   "p95": 25.6,
+///   "p99": 26.8,
+///   "latest": 24.1
+/// }
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/stats/<device_id>?<query..>")]
+pub async fn stats_route(
+    device_id: &str,
+    query: StatsQuery,
+    state: &State<AppState>,
+    _access: DeviceAccess,
+) -> Result<Json<MetricStats>, Status> {
+    info!("Received statistics request for device: {}", device_id);
+
+    match stats(state.inner(), device_id, &query).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(e) => {
+            error!("Error computing statistics: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
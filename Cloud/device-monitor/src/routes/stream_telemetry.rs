@@ -0,0 +1,78 @@
+// Live Telemetry Stream Route Handler
+//
+// This module handles the GET /iot/data/stream/<device_id> endpoint, which
+// pushes a device's new telemetry records to the client as server-sent
+// events, so dashboards update live instead of polling.
+
+use rocket::response::stream::{Event, EventStream};
+use rocket::tokio::select;
+use rocket::tokio::sync::broadcast::error::RecvError;
+use rocket::{Shutdown, State, http::Status};
+use tracing::{info, warn, error};
+
+use crate::domain::error::ApiError;
+use crate::app_state::AppState;
+use crate::utils::auth_guard::DeviceAccess;
+
+/// Name of the server-sent event carrying a telemetry record
+pub const TELEMETRY_EVENT: &str = "telemetry";
+
+/// GET endpoint streaming a device's new telemetry as server-sent events
+///
+/// Every record stored after the stream was opened is sent as a
+/// `telemetry` event whose data is the record as JSON. Records are picked
+/// up within `STREAM_POLL_INTERVAL_SECS` seconds of being stored; a
+/// heartbeat comment keeps idle connections open. A client that falls too
+/// far behind skips the records it missed. The stream ends when the server
+/// shuts down.
+///
+/// # Example Request
+/// ```bash
+/// GET /iot/data/stream/sensor-001
+/// ```
+///
+/// # Example Response
+/// ```text
+/// event: telemetry
+/// data: {"device_id":"sensor-001","telemetry_data":{"temperature":"24.1"},"timestamp":1640995260}
+/// ```
+/// 
+/// Requires credentials that may read the device.
+#[get("/stream/<device_id>")]
+pub async fn stream_telemetry_route(
+    state: &State<AppState>,
+    _access: DeviceAccess,
+    device_id: &str,
+    mut shutdown: Shutdown,
+) -> Result<EventStream![], Status> {
+    info!("Received live telemetry stream request for device: {}", device_id);
+
+    let mut receiver = match state.telemetry_feed.subscribe(device_id).await {
+        Ok(receiver) => receiver,
+        Err(e) => {
+            let e = ApiError::DatabaseError(e.to_string());
+            error!("Error subscribing to live telemetry: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            return Err(e.into());
+        }
+    };
+
+    let device_id = device_id.to_string();
+    Ok(EventStream! {
+        loop {
+            let telemetry = select! {
+                received = receiver.recv() => match received {
+                    Ok(telemetry) => telemetry,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Live telemetry client for device {} skipped {} record(s)", device_id, skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = &mut shutdown => break,
+            };
+            yield Event::json(&telemetry).event(TELEMETRY_EVENT);
+        }
+        info!("Closed live telemetry stream for device: {}", device_id);
+    })
+}
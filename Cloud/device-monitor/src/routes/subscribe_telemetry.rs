@@ -0,0 +1,310 @@
+// Live Telemetry Subscription Route Handler
+//
+// This module handles the GET /iot/data/ws WebSocket endpoint. Unlike the
+// server-sent events stream, a WebSocket client can change which devices
+// and metrics it receives mid-stream by sending subscribe and unsubscribe
+// messages over the same connection.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use rocket::data::{IoHandler, IoStream};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::{self, Responder, Response};
+use rocket::tokio::io;
+use rocket::State;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tracing::{info, warn, error};
+
+use crate::domain::auth::Principal;
+use crate::domain::error::ApiError;
+use crate::domain::subscription::{ClientMessage, ServerMessage, Subscriptions};
+use crate::domain::telemetry::Telemetry;
+use crate::app_state::AppState;
+use crate::services::TelemetryFeed;
+use crate::utils::auth_guard::ReadAccess;
+
+/// How often the server pings the client
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a client may stay silent, not even answering pings, before it is disconnected
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(75);
+
+/// Number of records waiting to be sent before the connection's feeds wait
+const CHANNEL_CAPACITY: usize = 256;
+
+/// The key of a WebSocket opening handshake
+///
+/// Fails with 400 Bad Request unless the request asks to upgrade to
+/// version 13 of the WebSocket protocol.
+pub struct WebSocketKey(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebSocketKey {
+    type Error = ApiError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let headers = request.headers();
+        let upgrade = headers
+            .get("Upgrade")
+            .flat_map(|value| value.split(','))
+            .any(|protocol| protocol.trim().eq_ignore_ascii_case("websocket"));
+        let key = headers.get_one("Sec-WebSocket-Key");
+
+        match key {
+            Some(key) if upgrade && headers.get_one("Sec-WebSocket-Version") == Some("13") => {
+                Outcome::Success(WebSocketKey(key.to_string()))
+            }
+            _ => Outcome::Error((
+                Status::BadRequest,
+                ApiError::InvalidQuery("expected a WebSocket upgrade request".to_string()),
+            )),
+        }
+    }
+}
+
+/// Accepts a WebSocket handshake and serves the subscription protocol on the connection
+pub struct SubscriptionSocket {
+    accept: String,
+    feed: TelemetryFeed,
+    caller: Principal,
+}
+
+impl<'r> Responder<'r, 'static> for SubscriptionSocket {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
+        // Rocket switches protocols and sets the Connection and Upgrade headers
+        Response::build()
+            .raw_header("Sec-WebSocket-Accept", self.accept)
+            .upgrade("websocket", SubscriptionConnection { feed: self.feed, caller: self.caller })
+            .ok()
+    }
+}
+
+/// One upgraded connection of the subscription API
+struct SubscriptionConnection {
+    feed: TelemetryFeed,
+    caller: Principal,
+}
+
+#[rocket::async_trait]
+impl IoHandler for SubscriptionConnection {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        let socket = WebSocketStream::from_raw_socket(io, Role::Server, None).await;
+        serve(&self.feed, &self.caller, socket).await.map_err(io::Error::other)
+    }
+}
+
+/// Forwards the new records of a device to a connection until it unsubscribes
+///
+/// # Arguments
+/// * `feed` - The live telemetry feed
+/// * `device_id` - The unique identifier of the device
+/// * `sender` - The connection's channel of records to send
+///
+/// # Returns
+/// * `Result<JoinHandle<()>, ApiError>` - The forwarding task, to abort on unsubscribe, or an error
+async fn forward(feed: &TelemetryFeed, device_id: &str, sender: mpsc::Sender<Telemetry>) -> Result<JoinHandle<()>, ApiError> {
+    let mut receiver = feed.subscribe(device_id)
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    let device_id = device_id.to_string();
+    Ok(tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(telemetry) => {
+                    if sender.send(telemetry).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("WebSocket client of device {} skipped {} record(s)", device_id, skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }))
+}
+
+/// Rejects subscriptions to devices the caller may not read
+///
+/// # Arguments
+/// * `caller` - The authenticated caller
+/// * `message` - The client message
+///
+/// # Returns
+/// * `Result<ClientMessage, ApiError>` - The message, or a forbidden error naming the first
+///   device outside the caller's scope
+fn authorize(caller: &Principal, message: ClientMessage) -> Result<ClientMessage, ApiError> {
+    if let ClientMessage::Subscribe { device_ids, .. } = &message {
+        if let Some(device_id) = device_ids.iter().find(|device_id| !caller.can_read(device_id)) {
+            return Err(ApiError::DeviceForbidden(device_id.clone()));
+        }
+    }
+    Ok(message)
+}
+
+/// Sends a message to the client as a JSON text frame
+async fn send<S>(socket: &mut WebSocketStream<S>, message: &ServerMessage) -> Result<(), tokio_tungstenite::tungstenite::Error>
+where
+    S: io::AsyncRead + io::AsyncWrite + Unpin,
+{
+    let json = serde_json::to_string(message).expect("server messages serialize to JSON");
+    socket.send(Message::Text(json)).await
+}
+
+/// Serves the subscription protocol until the client disconnects or times out
+///
+/// This function performs the following steps in a loop:
+/// 1. Applies subscribe messages for devices the caller may read and
+///    unsubscribe messages, starting or stopping the forwarding of each
+///    device's records, and acknowledges every change
+///    with the current subscriptions
+/// 2. Sends each new record of a subscribed device, narrowed to the
+///    subscribed metrics
+/// 3. Pings the client every `PING_INTERVAL` and disconnects it after
+///    `CLIENT_TIMEOUT` without any frame, including pongs
+///
+/// # Arguments
+/// * `feed` - The live telemetry feed
+/// * `caller` - The authenticated caller, whose scope limits the devices
+/// * `socket` - The WebSocket connection
+///
+/// # Returns
+/// * `Result<(), tokio_tungstenite::tungstenite::Error>` - Success or a connection error
+async fn serve<S>(feed: &TelemetryFeed, caller: &Principal, mut socket: WebSocketStream<S>) -> Result<(), tokio_tungstenite::tungstenite::Error>
+where
+    S: io::AsyncRead + io::AsyncWrite + Unpin,
+{
+    let (sender, mut records) = mpsc::channel(CHANNEL_CAPACITY);
+    let mut forwarders: HashMap<String, JoinHandle<()>> = HashMap::new();
+    let mut subscriptions = Subscriptions::default();
+    let mut keepalive = tokio::time::interval(PING_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    let result = loop {
+        tokio::select! {
+            frame = socket.next() => {
+                let message = match frame {
+                    Some(Ok(message)) => message,
+                    Some(Err(e)) => break Err(e),
+                    None => break Ok(()),
+                };
+                last_seen = Instant::now();
+
+                // Pings are answered and pongs only keep the connection alive
+                let text = match message {
+                    Message::Text(text) => text,
+                    Message::Close(_) => break Ok(()),
+                    _ => continue,
+                };
+
+                let change = serde_json::from_str::<ClientMessage>(&text)
+                    .map_err(|e| ApiError::InvalidQuery(e.to_string()))
+                    .and_then(|message| authorize(caller, message))
+                    .and_then(|message| subscriptions.apply(message));
+                let reply = match change {
+                    Ok(change) => {
+                        for device_id in change.removed {
+                            if let Some(forwarder) = forwarders.remove(&device_id) {
+                                forwarder.abort();
+                            }
+                        }
+                        let mut failed = None;
+                        for device_id in change.added {
+                            match forward(feed, &device_id, sender.clone()).await {
+                                Ok(forwarder) => {
+                                    forwarders.insert(device_id, forwarder);
+                                }
+                                Err(e) => {
+                                    error!("Error subscribing to device {}: {}", device_id, e);
+                                    subscriptions.remove(&device_id);
+                                    failed = Some(e);
+                                }
+                            }
+                        }
+                        match failed {
+                            Some(e) => ServerMessage::Error { message: e.to_string() },
+                            None => ServerMessage::Subscribed { subscriptions: subscriptions.summary() },
+                        }
+                    }
+                    Err(e) => ServerMessage::Error { message: e.to_string() },
+                };
+                if let Err(e) = send(&mut socket, &reply).await {
+                    break Err(e);
+                }
+            }
+            Some(telemetry) = records.recv() => {
+                // Records already queued for a device unsubscribed from are dropped here
+                if let Some(telemetry) = subscriptions.filter(telemetry) {
+                    if let Err(e) = send(&mut socket, &ServerMessage::Telemetry { telemetry: Box::new(telemetry) }).await {
+                        break Err(e);
+                    }
+                }
+            }
+            _ = keepalive.tick() => {
+                if last_seen.elapsed() > CLIENT_TIMEOUT {
+                    warn!("Closing unresponsive WebSocket client");
+                    break socket.close(None).await;
+                }
+                if let Err(e) = socket.send(Message::Ping(Vec::new())).await {
+                    break Err(e);
+                }
+            }
+        }
+    };
+
+    for forwarder in forwarders.into_values() {
+        forwarder.abort();
+    }
+    info!("Closed WebSocket subscription");
+    result
+}
+
+/// GET endpoint upgrading to a WebSocket for live telemetry subscriptions
+///
+/// After the upgrade, the client sends JSON text frames to choose what it
+/// receives, and may change it at any time:
+///
+/// ```json
+/// {"type": "subscribe", "device_ids": ["sensor-001", "sensor-002"], "metrics": ["temperature"]}
+/// {"type": "unsubscribe", "device_ids": ["sensor-002"]}
+/// ```
+///
+/// Omitting `metrics` subscribes to every metric; subscribing to a device
+/// again replaces its metrics. At most 100 devices can be subscribed to.
+/// Every change is acknowledged with the current subscriptions, and each
+/// new record of a subscribed device is sent with only its subscribed
+/// metrics:
+///
+/// ```json
+/// {"type": "subscribed", "subscriptions": {"sensor-001": ["temperature"]}}
+/// {"type": "telemetry", "telemetry": {"device_id": "sensor-001", "telemetry_data": {"temperature": "24.1"}, "timestamp": 1640995260}}
+/// {"type": "error", "message": "Invalid query: device_ids must name at least one device"}
+/// ```
+///
+/// The server pings every 30 seconds and disconnects clients that send
+/// nothing, not even a pong, for 75 seconds. Records are picked up as by
+/// the server-sent events stream. Returns 400 if the request is not a
+/// WebSocket upgrade. Subscribing to a device the caller's credentials may
+/// not read is answered with an error message.
+#[get("/ws")]
+pub fn subscribe_telemetry_route(state: &State<AppState>, access: ReadAccess, key: WebSocketKey) -> SubscriptionSocket {
+    info!("Received WebSocket subscription request");
+
+    SubscriptionSocket {
+        accept: derive_accept_key(key.0.as_bytes()),
+        feed: state.telemetry_feed.clone(),
+        caller: access.0,
+    }
+}
@@ -0,0 +1,207 @@
+// Uptime Report Route Handler
+// 
+// This module handles the GET /iot/uptime endpoint, which reports each
+// device's availability over a period, derived from the gaps in its
+// telemetry, as JSON or as a CSV file for customer-facing SLA reports.
+
+use chrono::Utc;
+use futures::future::join_all;
+use futures::StreamExt;
+use rocket::serde::json::Json;
+use rocket::{Either, State, http::Status};
+use tracing::{info, error};
+
+use crate::domain::auth::Principal;
+use crate::domain::error::ApiError;
+use crate::domain::gaps::{parse_interval, GapDetector};
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::uptime::{DeviceUptime, UptimeFormat, UptimeReport};
+use crate::domain::value_filter::ValueFilter;
+use crate::app_state::AppState;
+use crate::routes::export::CsvFile;
+use crate::utils::auth_guard::ReadAccess;
+
+/// Query parameters of the uptime report
+#[derive(Debug, FromForm)]
+pub struct UptimeQuery {
+    /// Expected reporting interval, e.g. "30s" or "5m"
+    pub interval: String,
+    /// Start of the period, as a Unix timestamp
+    pub from: i64,
+    /// End of the period, as a Unix timestamp (default now)
+    pub to: Option<i64>,
+    /// Comma-separated devices to report on (default every device the caller may read)
+    pub devices: Option<String>,
+    /// `json` (default) or `csv`
+    pub format: Option<String>,
+}
+
+/// Computes one device's availability over the period
+/// 
+/// Only timestamps are kept, so the device's telemetry is streamed rather
+/// than collected.
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `device_id` - The device to report on
+/// * `interval_secs` - The expected reporting interval, in seconds
+/// * `range` - The period, with both bounds set
+/// 
+/// # Returns
+/// * `Result<DeviceUptime, ApiError>` - The device's availability or an error
+async fn device_uptime(
+    state: &AppState,
+    device_id: &str,
+    interval_secs: i64,
+    range: &TimeRange,
+) -> Result<DeviceUptime, ApiError> {
+    let mut pages = state.cosmos_client.stream_telemetry(device_id, range, &ValueFilter::NONE, SortOrder::Asc)
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    let mut detector = GapDetector::new(interval_secs, range.from);
+    while let Some(page) = pages.next().await {
+        let page = page.map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        for timestamp in page.iter().filter_map(|record| record.timestamp) {
+            detector.observe(timestamp);
+        }
+    }
+
+    let to = range.to.unwrap_or_default();
+    Ok(DeviceUptime::from_gaps(&detector.finish(device_id, range.from, to)))
+}
+
+/// Reports the availability of the requested devices over a period
+/// 
+/// This function performs the following steps:
+/// 1. Validates the interval and period
+/// 2. Reads the devices from the device index, keeping the requested ones
+///    the caller may read
+/// 3. Streams each device's telemetry within the period concurrently and
+///    derives its downtime from the gaps longer than the interval
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `caller` - The authenticated caller, whose scope limits the devices
+/// * `query` - The query parameters of the request
+/// 
+/// # Returns
+/// * `Result<UptimeReport, ApiError>` - The report or an error
+async fn uptime_report(state: &AppState, caller: &Principal, query: &UptimeQuery) -> Result<UptimeReport, ApiError> {
+    let interval_secs = parse_interval(&query.interval)?;
+    // A period ending in the future is only reported up to now
+    let to = query.to.unwrap_or(i64::MAX).min(Utc::now().timestamp());
+    if query.from >= to {
+        return Err(ApiError::InvalidQuery("from must be before to and not in the future".to_string()));
+    }
+    let range = &TimeRange::parse(Some(query.from), Some(to))?;
+
+    let mut devices: Vec<String> = state.cosmos_client.read_devices()
+        .await
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|device| device.device_id)
+        .filter(|device_id| caller.can_read(device_id))
+        .collect();
+
+    if let Some(requested) = &query.devices {
+        let requested: Vec<&str> = requested.split(',').map(str::trim).filter(|id| !id.is_empty()).collect();
+        if let Some(device_id) = requested.iter().find(|device_id| !caller.can_read(device_id)) {
+            return Err(ApiError::DeviceForbidden(device_id.to_string()));
+        }
+        if let Some(device_id) = requested.iter().find(|device_id| !devices.iter().any(|id| id == *device_id)) {
+            return Err(ApiError::DeviceNotFound(device_id.to_string()));
+        }
+        devices.retain(|device_id| requested.contains(&device_id.as_str()));
+    }
+
+    let reads = join_all(devices.iter().map(|device_id| device_uptime(state, device_id, interval_secs, range))).await;
+    let devices = reads.into_iter().collect::<Result<Vec<_>, _>>()?;
+
+    let report = UptimeReport::new(query.from, to, interval_secs, devices);
+    info!("Reported the uptime of {} device(s)", report.devices.len());
+    Ok(report)
+}
+
+/// Reports the availability of the requested devices in the requested format
+/// 
+/// # Arguments
+/// * `state` - Application state containing the database client
+/// * `caller` - The authenticated caller, whose scope limits the devices
+/// * `query` - The query parameters of the request
+/// 
+/// # Returns
+/// * `Result<Either<Json<UptimeReport>, CsvFile>, ApiError>` - The JSON report or CSV file, or an error
+async fn render_uptime_report(
+    state: &AppState,
+    caller: &Principal,
+    query: &UptimeQuery,
+) -> Result<Either<Json<UptimeReport>, CsvFile>, ApiError> {
+    let format = UptimeFormat::parse(query.format.as_deref())?;
+    let report = uptime_report(state, caller, query).await?;
+
+    Ok(match format {
+        UptimeFormat::Json => Either::Left(Json(report)),
+        UptimeFormat::Csv => Either::Right(CsvFile {
+            filename: format!("uptime-{}-{}.csv", report.from, report.to),
+            lines: report.csv_lines(),
+        }),
+    })
+}
+
+/// GET endpoint reporting each device's availability over a period
+/// 
+/// A device counts as up for one `interval` after each record, so its
+/// downtime is the part of every gap in its telemetry beyond the interval,
+/// including silences at either end of the period; a device without any
+/// record in the period was down throughout. Availability is the
+/// percentage of the period from `from` to `to` (default now) the device
+/// was up, and the report's availability is the mean over its devices.
+/// 
+/// Without `devices`, every device in the device index the caller's
+/// credentials may read is reported on. With `format=csv` the report is
+/// downloaded as `uptime-<from>-<to>.csv` with one line per device.
+/// Returns 400 for an invalid interval, period or format, 403 if a
+/// requested device may not be read, and 404 if it is not in the index.
+/// 
+/// # Example Request
+/// ```bash
+/// GET /iot/uptime?interval=5m&from=1638316800&to=1640995200
+/// GET /iot/uptime?interval=5m&from=1638316800&to=1640995200&devices=sensor-001,sensor-002&format=csv
+/// ```
+/// 
+/// # Example Response
+/// ```json
+/// {
+///   "from": 1638316800,
+///   "to": 1640995200,
+///   "interval_secs": 300,
+///   "availability_percent": 99.55,
+///   "devices": [
+///     { "device_id": "sensor-001", "records": 8912, "downtime_secs": 3600, "availability_percent": 99.866 },
+///     { "device_id": "sensor-002", "records": 8790, "downtime_secs": 20520, "availability_percent": 99.234 }
+///   ]
+/// }
+/// ```
+/// 
+/// ```text
+/// device_id,from,to,interval_secs,records,downtime_secs,availability_percent
+/// sensor-001,1638316800,1640995200,300,8912,3600,99.866
+/// sensor-002,1638316800,1640995200,300,8790,20520,99.234
+/// ```
+#[get("/uptime?<query..>")]
+pub async fn uptime_route(
+    query: UptimeQuery,
+    state: &State<AppState>,
+    access: ReadAccess,
+) -> Result<Either<Json<UptimeReport>, CsvFile>, Status> {
+    info!("Received uptime report request");
+
+    match render_uptime_report(state.inner(), &access.0, &query).await {
+        Ok(report) => Ok(report),
+        Err(e) => {
+            error!("Error reporting uptime: {}", e);
+            // Convert the API error to an appropriate HTTP status code
+            Err(e.into())
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// Alert Evaluator Service
+// 
+// This module runs the alert rules in the background. It reloads every
+// rule periodically, watches the new telemetry of each device that has
+// rules through the live telemetry feed, and records and dispatches the
+// alerts the rules raise, so monitoring no longer needs someone watching
+// the charts.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, error};
+
+use crate::domain::alert::{Alert, AlertRule, RuleState};
+use crate::services::{CosmosDbTelemetryStore, TelemetryFeed};
+
+/// How long a webhook may take to accept an alert
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Evaluates alert rules against new telemetry and dispatches the alerts they raise
+#[derive(Clone)]
+pub struct AlertEvaluator {
+    store: CosmosDbTelemetryStore,
+    feed: TelemetryFeed,
+    refresh_interval: Duration,
+    webhook_url: Option<String>,
+    http_client: reqwest::Client,
+}
+
+/// The evaluation task of one device and the channel updating its rules
+struct DeviceEvaluation {
+    rules: watch::Sender<Vec<AlertRule>>,
+    task: JoinHandle<()>,
+}
+
+impl AlertEvaluator {
+    /// Creates an evaluator
+    /// 
+    /// # Arguments
+    /// * `store` - The Cosmos DB store holding rules and alerts
+    /// * `feed` - The live telemetry feed delivering new records
+    /// * `refresh_interval` - How often rules are reloaded, so edits take effect
+    /// * `webhook_url` - Where to POST each alert as JSON, if anywhere
+    pub fn new(
+        store: CosmosDbTelemetryStore,
+        feed: TelemetryFeed,
+        refresh_interval: Duration,
+        webhook_url: Option<String>,
+    ) -> Self {
+        AlertEvaluator {
+            store,
+            feed,
+            refresh_interval,
+            webhook_url,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Evaluates the alert rules until the runtime shuts down
+    /// 
+    /// Every `refresh_interval`, the rules are reloaded: devices that gained
+    /// rules start being watched, devices that lost all of them stop, and
+    /// the others pick up the edited rules. Only telemetry stored after a
+    /// device started being watched is evaluated.
+    pub async fn run(self) {
+        info!("Starting alert evaluation");
+        let mut devices: HashMap<String, DeviceEvaluation> = HashMap::new();
+        let mut interval = tokio::time::interval(self.refresh_interval);
+
+        loop {
+            interval.tick().await;
+
+            let rules = match self.store.read_all_alert_rules().await {
+                Ok(rules) => rules,
+                Err(e) => {
+                    error!("Error reading alert rules: {}", e);
+                    continue;
+                }
+            };
+
+            let mut rules_by_device: HashMap<String, Vec<AlertRule>> = HashMap::new();
+            for rule in rules {
+                rules_by_device.entry(rule.device_id.clone()).or_default().push(rule);
+            }
+
+            devices.retain(|device_id, evaluation| {
+                let keep = rules_by_device.contains_key(device_id) && !evaluation.task.is_finished();
+                if !keep {
+                    evaluation.task.abort();
+                }
+                keep
+            });
+
+            for (device_id, rules) in rules_by_device {
+                if let Some(evaluation) = devices.get(&device_id) {
+                    evaluation.rules.send_replace(rules);
+                    continue;
+                }
+                match self.watch(&device_id, rules).await {
+                    Ok(evaluation) => {
+                        devices.insert(device_id, evaluation);
+                    }
+                    // Retried at the next refresh
+                    Err(e) => error!("Error watching device {} for alerts: {}", device_id, e),
+                }
+            }
+        }
+    }
+
+    /// Starts evaluating a device's rules against its new telemetry
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `rules` - The device's alert rules
+    /// 
+    /// # Returns
+    /// * `Result<DeviceEvaluation, String>` - The evaluation task or an error
+    async fn watch(&self, device_id: &str, rules: Vec<AlertRule>) -> Result<DeviceEvaluation, String> {
+        let mut records = self.feed.subscribe(device_id).await.map_err(|e| e.to_string())?;
+        let (sender, receiver) = watch::channel(rules);
+
+        info!("Evaluating alert rules for device: {}", device_id);
+        let evaluator = self.clone();
+        let device_id = device_id.to_string();
+        let task = tokio::spawn(async move {
+            let mut states: HashMap<String, (DateTime<Utc>, RuleState)> = HashMap::new();
+            loop {
+                let record = match records.recv().await {
+                    Ok(record) => record,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Alert evaluation of device {} skipped {} record(s)", device_id, skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                let rules = receiver.borrow().clone();
+                // Edited and deleted rules start over
+                states.retain(|rule_id, (updated_at, _)| {
+                    rules.iter().any(|rule| &rule.id == rule_id && rule.updated_at == *updated_at)
+                });
+                for rule in &rules {
+                    let (_, state) = states
+                        .entry(rule.id.clone())
+                        .or_insert_with(|| (rule.updated_at, RuleState::default()));
+                    if let Some(alert) = state.observe(rule, &record) {
+                        evaluator.dispatch(&alert).await;
+                    }
+                }
+            }
+        });
+
+        Ok(DeviceEvaluation { rules: sender, task })
+    }
+
+    /// Records an alert and sends it to the webhook, if one is configured
+    /// 
+    /// Failures are logged rather than returned, so one failed alert does
+    /// not stop the evaluation.
+    /// 
+    /// # Arguments
+    /// * `alert` - The alert raised by a rule
+    async fn dispatch(&self, alert: &Alert) {
+        warn!(
+            "Alert {} for device {}: {} {:?} {} (reading {})",
+            alert.rule_id, alert.device_id, alert.metric, alert.operator, alert.threshold, alert.value
+        );
+
+        if let Err(e) = self.store.insert_alert(alert).await.map_err(|e| e.to_string()) {
+            error!("Error storing alert {}: {}", alert.id, e);
+        }
+
+        if let Some(url) = &self.webhook_url {
+            let result = self.http_client
+                .post(url)
+                .timeout(WEBHOOK_TIMEOUT)
+                .json(alert)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                error!("Error sending alert {} to webhook: {}", alert.id, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+// Telemetry Archive Job Service
+// 
+// This module archives telemetry older than the archive policy's age to
+// Azure Blob Storage in the background, one blob per device and UTC day.
+// Devices are taken from the device index, and each device is archived from
+// the day after its last archived day, so every day is archived once.
+// Archived telemetry is optionally deleted from Cosmos DB afterwards.
+
+use std::time::Duration;
+
+use chrono::{NaiveDate, Utc};
+use futures::StreamExt;
+use tracing::{info, warn, error};
+
+use crate::domain::archive::{blob_name, day_start, Archive, ArchivePolicy, DayBatches};
+use crate::domain::sort_order::SortOrder;
+use crate::domain::telemetry::Telemetry;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::services::{BlobContainer, CosmosDbTelemetryStore, QueryCache};
+
+/// Number of seconds in a day
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Periodically archives telemetry older than the archive policy allows
+#[derive(Clone)]
+pub struct ArchiveJob {
+    store: CosmosDbTelemetryStore,
+    query_cache: QueryCache,
+    container: Option<BlobContainer>,
+    policy: ArchivePolicy,
+    interval: Duration,
+}
+
+impl ArchiveJob {
+    /// Creates a job that has not run yet
+    /// 
+    /// # Arguments
+    /// * `store` - The Cosmos DB store holding the telemetry, device index and archive index
+    /// * `query_cache` - The query cache to drop devices with deleted telemetry from
+    /// * `container` - The Blob Storage container archives are written to, if configured
+    /// * `policy` - Which telemetry is archived and how
+    /// * `interval` - How often old telemetry is archived
+    pub fn new(
+        store: CosmosDbTelemetryStore,
+        query_cache: QueryCache,
+        container: Option<BlobContainer>,
+        policy: ArchivePolicy,
+        interval: Duration,
+    ) -> Self {
+        ArchiveJob {
+            store,
+            query_cache,
+            container,
+            policy,
+            interval,
+        }
+    }
+
+    /// Archives old telemetry every `interval` until the runtime shuts down
+    /// 
+    /// Returns at once if no archive age or no archive container is configured.
+    pub async fn run(self) {
+        if !self.policy.is_enabled() {
+            info!("No telemetry archival configured");
+            return;
+        }
+        if self.container.is_none() {
+            warn!("ARCHIVE_AFTER_DAYS is set but ARCHIVE_CONTAINER_URL is not, archiving nothing");
+            return;
+        }
+
+        info!("Starting telemetry archival");
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.run_once().await {
+                error!("Error running telemetry archival: {}", e);
+            }
+        }
+    }
+
+    /// Archives the telemetry of every device recorded before the policy's cutoff
+    /// 
+    /// A device whose archival fails is retried from its last archived day
+    /// at the next run, while the other devices are still archived.
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Archive>, String>` - The days archived by the run, or an error if
+    ///   archival is not configured or the device index could not be read
+    pub async fn run_once(&self) -> Result<Vec<Archive>, String> {
+        let now = Utc::now().timestamp();
+        let (Some(cutoff), Some(container)) = (self.policy.cutoff(now), &self.container) else {
+            return Err("telemetry archival is not configured".to_string());
+        };
+
+        let devices = self.store.read_devices().await.map_err(|e| e.to_string())?;
+
+        let mut archives = Vec::new();
+        let mut devices_failed = 0;
+        for device in &devices {
+            if let Err(e) = self.archive_device(container, &device.device_id, cutoff, &mut archives).await {
+                error!("Error archiving telemetry of device {}: {}", device.device_id, e);
+                devices_failed += 1;
+            }
+        }
+
+        info!(
+            "Archival wrote {} day(s) of telemetry of {} device(s), {} failed",
+            archives.len(), devices.len(), devices_failed
+        );
+        Ok(archives)
+    }
+
+    /// Archives one device's unarchived days before the cutoff, oldest first
+    async fn archive_device(
+        &self,
+        container: &BlobContainer,
+        device_id: &str,
+        cutoff: i64,
+        archives: &mut Vec<Archive>,
+    ) -> Result<(), String> {
+        let latest = self.store.read_latest_archive(device_id).await.map_err(|e| e.to_string())?;
+        let range = TimeRange {
+            from: latest.map(|archive| day_start(archive.day) + SECS_PER_DAY),
+            to: Some(cutoff - 1),
+        };
+
+        let mut pages = self
+            .store
+            .stream_telemetry(device_id, &range, &ValueFilter::NONE, SortOrder::Asc)
+            .map_err(|e| e.to_string())?;
+
+        let mut batches = DayBatches::default();
+        while let Some(page) = pages.next().await {
+            for record in page.map_err(|e| e.to_string())? {
+                if let Some((day, records)) = batches.push(record) {
+                    archives.push(self.archive_day(container, device_id, day, records).await?);
+                }
+            }
+        }
+        if let Some((day, records)) = batches.finish() {
+            archives.push(self.archive_day(container, device_id, day, records).await?);
+        }
+
+        Ok(())
+    }
+
+    /// Uploads one day of a device's telemetry, deletes it if configured, and indexes it
+    /// 
+    /// The day is only indexed once its records are deleted, so a failed
+    /// deletion archives the day again at the next run.
+    async fn archive_day(
+        &self,
+        container: &BlobContainer,
+        device_id: &str,
+        day: NaiveDate,
+        records: Vec<Telemetry>,
+    ) -> Result<Archive, String> {
+        let format = self.policy.format;
+        let name = blob_name(device_id, day, format);
+        let contents = format.encode(&records)?;
+        let bytes = contents.len();
+
+        container
+            .put_blob(&name, contents, format.content_type())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if self.policy.delete_archived {
+            let range = TimeRange { from: Some(day_start(day)), to: Some(day_start(day) + SECS_PER_DAY - 1) };
+            self.store.delete_telemetry_in(device_id, &range).await.map_err(|e| e.to_string())?;
+            self.query_cache.invalidate(device_id);
+        }
+
+        let timestamps = records.iter().filter_map(|record| record.timestamp);
+        let archive = Archive {
+            id: format!("{}-{}", device_id, day.format("%Y-%m-%d")),
+            device_id: device_id.to_string(),
+            day,
+            blob_name: name,
+            format,
+            records: records.len(),
+            bytes,
+            first_timestamp: timestamps.clone().min().unwrap_or_default(),
+            last_timestamp: timestamps.max().unwrap_or_default(),
+            deleted: self.policy.delete_archived,
+            archived_at: Utc::now(),
+        };
+        self.store.upsert_archive(&archive).await.map_err(|e| e.to_string())?;
+
+        Ok(archive)
+    }
+}
@@ -0,0 +1,107 @@
+// Azure Blob Storage Container Service
+// 
+// This module uploads blobs to an Azure Blob Storage container through the
+// Blob service REST API. The container is addressed by a URL carrying a
+// shared access signature (SAS), so no storage account key is handled by
+// the service; the signature must allow creating and writing blobs.
+
+use std::time::Duration;
+
+use reqwest::Url;
+use tracing::warn;
+
+/// Version of the Blob service REST API requests are made against
+const BLOB_API_VERSION: &str = "2021-08-06";
+
+/// How long uploading a single blob may take
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Client for one Blob Storage container
+#[derive(Clone)]
+pub struct BlobContainer {
+    http_client: reqwest::Client,
+    url: Url,
+}
+
+impl BlobContainer {
+    /// Creates a client for a container
+    /// 
+    /// # Arguments
+    /// * `url` - The container URL, e.g.
+    ///   `https://<account>.blob.core.windows.net/<container>?<sas>`
+    /// 
+    /// # Returns
+    /// * `Option<Self>` - The client, or None if the URL cannot address blobs
+    pub fn new(url: Url) -> Option<Self> {
+        if url.cannot_be_a_base() || !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        Some(BlobContainer { http_client: reqwest::Client::new(), url })
+    }
+
+    /// Creates a client for the container in the `ARCHIVE_CONTAINER_URL` environment variable
+    /// 
+    /// # Returns
+    /// * `Option<Self>` - The client, or None if the variable is unset or not a container URL
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("ARCHIVE_CONTAINER_URL").ok().filter(|url| !url.trim().is_empty())?;
+        let container = Url::parse(url.trim()).ok().and_then(Self::new);
+        if container.is_none() {
+            warn!("Ignoring invalid ARCHIVE_CONTAINER_URL");
+        }
+        container
+    }
+
+    /// Returns the URL of a blob, keeping the container's shared access signature
+    /// 
+    /// # Arguments
+    /// * `name` - The blob name; `/` separates virtual directories
+    pub fn blob_url(&self, name: &str) -> Url {
+        let mut url = self.url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().extend(name.split('/'));
+        }
+        url
+    }
+
+    /// Creates or replaces a block blob
+    /// 
+    /// # Arguments
+    /// * `name` - The blob name
+    /// * `contents` - The contents of the blob
+    /// * `content_type` - The content type the blob is served with
+    /// 
+    /// # Returns
+    /// * `Result<(), reqwest::Error>` - Success, or an error if the upload was not accepted
+    pub async fn put_blob(&self, name: &str, contents: Vec<u8>, content_type: &str) -> Result<(), reqwest::Error> {
+        self.http_client
+            .put(self.blob_url(name))
+            .header("x-ms-blob-type", "BlockBlob")
+            .header("x-ms-version", BLOB_API_VERSION)
+            .header("Content-Type", content_type)
+            .timeout(UPLOAD_TIMEOUT)
+            .body(contents)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_url() {
+        let url = Url::parse("https://account.blob.core.windows.net/archive/?sv=2021-08-06&sig=abc%2B").unwrap();
+        let container = BlobContainer::new(url).unwrap();
+
+        assert_eq!(
+            container.blob_url("sensor 001/2022-01-01.jsonl").as_str(),
+            "https://account.blob.core.windows.net/archive/sensor%20001/2022-01-01.jsonl?sv=2021-08-06&sig=abc%2B"
+        );
+        assert!(BlobContainer::new(Url::parse("mailto:ops@example.com").unwrap()).is_none());
+    }
+}
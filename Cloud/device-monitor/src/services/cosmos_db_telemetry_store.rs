@@ -5,12 +5,40 @@
 // monitoring service.
 
 use super::AzureAuth;
-use azure_data_cosmos::CosmosClient;
+use azure_core::http::StatusCode;
+use azure_data_cosmos::{CosmosClient, QueryPartitionStrategy};
 use azure_data_cosmos::clients::ContainerClient;
+use futures::stream::BoxStream;
 use futures::StreamExt;
+use crate::domain::alert::{Alert, AlertRule};
+use crate::domain::annotation::Annotation;
+use crate::domain::archive::Archive;
+use crate::domain::device::DeviceListing;
+use crate::domain::paging::PageRequest;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
 use crate::domain::telemetry::Telemetry;
 use std::sync::Arc;
 
+/// A device's telemetry, read from Cosmos DB one page at a time
+/// 
+/// Each item is one page of records as returned by Cosmos DB; the next page
+/// is only requested, with the previous page's continuation token, once the
+/// stream is polled again.
+pub type TelemetryPages = BoxStream<'static, Result<Vec<Telemetry>, azure_core::Error>>;
+
+/// Builds the query selecting a device's telemetry within a time range and meeting a filter, in order
+fn telemetry_query(device_id: &str, range: &TimeRange, filter: &ValueFilter, order: SortOrder) -> String {
+    format!(
+        "SELECT * FROM c WHERE c.device_id = '{}'{}{}{}",
+        device_id,
+        range.query_filter(),
+        filter.query_filter(),
+        order.query_clause()
+    )
+}
+
 /// Cosmos DB client for telemetry data storage and retrieval
 /// 
 /// This struct provides a thread-safe interface to Azure Cosmos DB for
@@ -23,6 +51,44 @@ pub struct CosmosDbTelemetryStore {
     /// This client is used for all database operations and is shared
     /// across multiple request handlers.
     pub container_client: Arc<ContainerClient>,
+
+    /// Container client for telemetry annotations
+    /// 
+    /// Annotations live in a sibling container named
+    /// `<container_name>-annotations` and are partitioned by device id.
+    pub annotations_client: Arc<ContainerClient>,
+
+    /// Container client for the device index
+    /// 
+    /// The device-comms service keeps one document per device, holding when
+    /// it was last seen, in a sibling container named `<container_name>-devices`.
+    pub devices_client: Arc<ContainerClient>,
+
+    /// Container client for alert rules
+    /// 
+    /// Rules live in a sibling container named `<container_name>-alert-rules`
+    /// and are partitioned by device id.
+    pub alert_rules_client: Arc<ContainerClient>,
+
+    /// Container client for raised alerts
+    /// 
+    /// Alerts live in a sibling container named `<container_name>-alerts`
+    /// and are partitioned by device id.
+    pub alerts_client: Arc<ContainerClient>,
+
+    /// Container client for the archive index
+    /// 
+    /// Each day of telemetry archived to Blob Storage is recorded in a
+    /// sibling container named `<container_name>-archives`, partitioned by
+    /// device id.
+    pub archives_client: Arc<ContainerClient>,
+
+    /// Container client for the latest record of each device
+    /// 
+    /// The device-comms service upserts each device's newest record into a
+    /// sibling container named `<container_name>-latest`, with the device id
+    /// as document id, so current values are read without a scan.
+    pub latest_client: Arc<ContainerClient>,
 }
 
 impl CosmosDbTelemetryStore {
@@ -56,13 +122,30 @@ impl CosmosDbTelemetryStore {
         // Create the Cosmos DB client with authentication
         let cosmos_client = CosmosClient::new(&cosmos_endpoint, azure_credential, None)?;
         
-        // Create a container client for the specified database and container
-        let container_client = cosmos_client
-            .database_client(&database_name)
-            .container_client(&container_name);
+        // Create container clients for the specified database and containers
+        let database_client = cosmos_client.database_client(&database_name);
+        let container_client = database_client.container_client(&container_name);
+        let annotations_client =
+            database_client.container_client(&format!("{}-annotations", container_name));
+        let devices_client =
+            database_client.container_client(&format!("{}-devices", container_name));
+        let alert_rules_client =
+            database_client.container_client(&format!("{}-alert-rules", container_name));
+        let alerts_client =
+            database_client.container_client(&format!("{}-alerts", container_name));
+        let archives_client =
+            database_client.container_client(&format!("{}-archives", container_name));
+        let latest_client =
+            database_client.container_client(&format!("{}-latest", container_name));
 
         Ok(CosmosDbTelemetryStore {
             container_client: Arc::new(container_client),
+            annotations_client: Arc::new(annotations_client),
+            devices_client: Arc::new(devices_client),
+            alert_rules_client: Arc::new(alert_rules_client),
+            alerts_client: Arc::new(alerts_client),
+            archives_client: Arc::new(archives_client),
+            latest_client: Arc::new(latest_client),
         })
     }
 
@@ -132,4 +215,684 @@ impl CosmosDbTelemetryStore {
 
         Ok(items)
     }
+
+    /// Retrieves telemetry data for a specific device within a time range
+    /// 
+    /// The range and the value filter are applied as query filters and the
+    /// records are sorted by timestamp in the database. If a page is requested, one
+    /// record more than the page holds is returned, so callers can tell
+    /// whether another page follows. Pages start after the previous page's
+    /// last record, found by its timestamp and id rather than by skipping
+    /// records, so later pages cost no more than the first.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The timestamps to include
+    /// * `filter` - The conditions on metric values records must meet
+    /// * `order` - Whether to return the oldest or the newest records first
+    /// * `page` - The previous page's last record and the page size, or None for every matching record
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Telemetry>, Box<dyn std::error::Error>>` - The matching records or an error
+    pub async fn query_telemetry(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+        filter: &ValueFilter,
+        order: SortOrder,
+        page: Option<&PageRequest>,
+    ) -> Result<Vec<Telemetry>, Box<dyn std::error::Error>> {
+        let query = match page {
+            Some(page) => format!(
+                "SELECT * FROM c WHERE c.device_id = '{}'{}{}{}",
+                device_id,
+                range.query_filter(),
+                filter.query_filter(),
+                page.query_clause(order)
+            ),
+            None => telemetry_query(device_id, range, filter, order),
+        };
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.container_client.query_items::<Telemetry>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Streams telemetry data for a specific device within a time range
+    /// 
+    /// Unlike `query_telemetry`, the records are not collected in memory:
+    /// pages are read from Cosmos DB as the returned stream is polled, so a
+    /// device's full history can be written to a response page by page.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The timestamps to include
+    /// * `filter` - The conditions on metric values records must meet
+    /// * `order` - Whether to return the oldest or the newest records first
+    /// 
+    /// # Returns
+    /// * `Result<TelemetryPages, Box<dyn std::error::Error>>` - The pages of matching records or an error
+    pub fn stream_telemetry(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+        filter: &ValueFilter,
+        order: SortOrder,
+    ) -> Result<TelemetryPages, Box<dyn std::error::Error>> {
+        let query = telemetry_query(device_id, range, filter, order);
+        let partition_key = device_id.to_string();
+
+        let pager = self.container_client.query_items::<Telemetry>(query, partition_key, None)?;
+
+        Ok(pager.map(|page_response| page_response.map(|page| page.items().to_vec())).boxed())
+    }
+
+    /// Counts a device's telemetry records within a time range
+    /// 
+    /// The records are counted by Cosmos DB with a `COUNT` aggregate, so no
+    /// documents are transferred however many match.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The timestamps to include
+    /// 
+    /// # Returns
+    /// * `Result<u64, Box<dyn std::error::Error>>` - The number of matching records or an error
+    pub async fn count_telemetry(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT VALUE COUNT(1) FROM c WHERE c.device_id = '{}'{}",
+            device_id,
+            range.query_filter()
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.container_client.query_items::<u64>(query, partition_key, None)?;
+
+        // Partial counts may be returned over several pages
+        let mut count = 0;
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            count += page.items().iter().sum::<u64>();
+        }
+
+        Ok(count)
+    }
+
+    /// Retrieves the most recent telemetry record of a specific device
+    /// 
+    /// The record is looked up in the latest-record container by device id,
+    /// which holds each device's newest reading; synthetic heartbeats are not
+    /// recorded there, so use `read_device` for when a device was last seen.
+    /// Devices without a document there, such as devices that have not
+    /// reported since the container was introduced, fall back to a
+    /// `TOP 1 ... ORDER BY timestamp DESC` query over their telemetry.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Option<Telemetry>, Box<dyn std::error::Error>>` - The record with the latest
+    ///   timestamp, None if the device has no telemetry, or an error
+    pub async fn read_latest_telemetry(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<Telemetry>, Box<dyn std::error::Error>> {
+        if let Some(latest) = self.read_materialized_latest(device_id).await? {
+            return Ok(Some(latest));
+        }
+
+        let query = format!(
+            "SELECT TOP 1 * FROM c WHERE c.device_id = '{}' ORDER BY c.timestamp DESC",
+            device_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.container_client.query_items::<Telemetry>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Retrieves a device's document in the latest-record container
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Option<Telemetry>, Box<dyn std::error::Error>>` - The device's newest record
+    ///   as recorded at ingest, None if there is none, or an error
+    async fn read_materialized_latest(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<Telemetry>, Box<dyn std::error::Error>> {
+        // The device id doubles as the document id, so this is a point read
+        match self.latest_client.read_item::<Telemetry>(device_id, device_id, None).await {
+            Ok(response) => Ok(Some(response.into_body().await?)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Deletes a device's telemetry recorded before a cutoff
+    /// 
+    /// The ids of the old records are queried first and each record is
+    /// then deleted within the device's partition.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `cutoff` - Records with an earlier Unix timestamp are deleted
+    /// 
+    /// # Returns
+    /// * `Result<usize, Box<dyn std::error::Error>>` - The number of records deleted or an error
+    pub async fn delete_telemetry_before(
+        &self,
+        device_id: &str,
+        cutoff: i64,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT VALUE c.id FROM c WHERE c.device_id = '{}' AND c.timestamp < {}",
+            device_id, cutoff
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.container_client.query_items::<String>(query, partition_key, None)?;
+
+        let mut ids = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            ids.extend(page.items().iter().cloned());
+        }
+
+        for id in &ids {
+            self.container_client
+                .delete_item(device_id, id, None)
+                .await?;
+        }
+
+        Ok(ids.len())
+    }
+
+    /// Deletes a device's telemetry within a time range
+    /// 
+    /// Like `delete_telemetry_before`, the ids of the records are queried
+    /// first and each record is then deleted within the device's partition.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The timestamps of the records to delete
+    /// 
+    /// # Returns
+    /// * `Result<usize, Box<dyn std::error::Error>>` - The number of records deleted or an error
+    pub async fn delete_telemetry_in(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT VALUE c.id FROM c WHERE c.device_id = '{}'{}",
+            device_id,
+            range.query_filter()
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.container_client.query_items::<String>(query, partition_key, None)?;
+
+        let mut ids = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            ids.extend(page.items().iter().cloned());
+        }
+
+        for id in &ids {
+            self.container_client
+                .delete_item(device_id, id, None)
+                .await?;
+        }
+
+        Ok(ids.len())
+    }
+
+    /// Creates or replaces an annotation document
+    /// 
+    /// # Arguments
+    /// * `annotation` - The annotation to store
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_annotation(
+        &self,
+        annotation: &Annotation,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.annotations_client
+            .upsert_item(&annotation.device_id, annotation, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves all annotations of a device, ordered by start time
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Annotation>, Box<dyn std::error::Error>>` - The device's annotations or an error
+    pub async fn read_annotations(
+        &self,
+        device_id: &str,
+    ) -> Result<Vec<Annotation>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' ORDER BY c.start ASC",
+            device_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.annotations_client.query_items::<Annotation>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves a single annotation of a device
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device the annotation belongs to
+    /// * `annotation_id` - The unique identifier of the annotation
+    /// 
+    /// # Returns
+    /// * `Result<Option<Annotation>, Box<dyn std::error::Error>>` - The annotation if found, or an error
+    pub async fn read_annotation(
+        &self,
+        device_id: &str,
+        annotation_id: &str,
+    ) -> Result<Option<Annotation>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' AND c.id = '{}'",
+            device_id, annotation_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.annotations_client.query_items::<Annotation>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Deletes an annotation
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device the annotation belongs to
+    /// * `annotation_id` - The unique identifier of the annotation
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn delete_annotation(
+        &self,
+        device_id: &str,
+        annotation_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.annotations_client
+            .delete_item(device_id, annotation_id, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves every device in the device index
+    /// 
+    /// This is a cross-partition query over the device index, which holds a
+    /// single small document per device, instead of a scan of the telemetry.
+    /// 
+    /// # Returns
+    /// * `Result<Vec<DeviceListing>, Box<dyn std::error::Error>>` - All devices or an error
+    pub async fn read_devices(
+        &self,
+    ) -> Result<Vec<DeviceListing>, Box<dyn std::error::Error>> {
+        let query = "SELECT c.device_id, c.timestamp AS last_seen, c.gateway_id FROM c";
+
+        let mut pager = self
+            .devices_client
+            .query_items::<DeviceListing>(query, QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves a device's entry in the device index
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Option<DeviceListing>, Box<dyn std::error::Error>>` - When the device was last
+    ///   seen, None if it is not in the index, or an error
+    pub async fn read_device(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<DeviceListing>, Box<dyn std::error::Error>> {
+        // The device id doubles as the document id, so this is a point read
+        match self.devices_client.read_item::<DeviceListing>(device_id, device_id, None).await {
+            Ok(response) => Ok(Some(response.into_body().await?)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Creates or replaces an alert rule document
+    /// 
+    /// # Arguments
+    /// * `rule` - The alert rule to store
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_alert_rule(
+        &self,
+        rule: &AlertRule,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.alert_rules_client
+            .upsert_item(&rule.device_id, rule, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves all alert rules of a device, oldest first
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Vec<AlertRule>, Box<dyn std::error::Error>>` - The device's rules or an error
+    pub async fn read_alert_rules(
+        &self,
+        device_id: &str,
+    ) -> Result<Vec<AlertRule>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' ORDER BY c.created_at ASC",
+            device_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.alert_rules_client.query_items::<AlertRule>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves the alert rules of every device
+    /// 
+    /// This is a cross-partition query, used by the background alert
+    /// evaluator to refresh the rules it applies.
+    /// 
+    /// # Returns
+    /// * `Result<Vec<AlertRule>, Box<dyn std::error::Error>>` - All alert rules or an error
+    pub async fn read_all_alert_rules(
+        &self,
+    ) -> Result<Vec<AlertRule>, Box<dyn std::error::Error>> {
+        let mut pager = self
+            .alert_rules_client
+            .query_items::<AlertRule>("SELECT * FROM c", QueryPartitionStrategy::CrossPartition, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves a single alert rule of a device
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device the rule belongs to
+    /// * `rule_id` - The unique identifier of the rule
+    /// 
+    /// # Returns
+    /// * `Result<Option<AlertRule>, Box<dyn std::error::Error>>` - The rule if found, or an error
+    pub async fn read_alert_rule(
+        &self,
+        device_id: &str,
+        rule_id: &str,
+    ) -> Result<Option<AlertRule>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' AND c.id = '{}'",
+            device_id, rule_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.alert_rules_client.query_items::<AlertRule>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Deletes an alert rule
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device the rule belongs to
+    /// * `rule_id` - The unique identifier of the rule
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn delete_alert_rule(
+        &self,
+        device_id: &str,
+        rule_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.alert_rules_client
+            .delete_item(device_id, rule_id, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Stores a raised alert, or replaces it once it is acknowledged or resolved
+    /// 
+    /// # Arguments
+    /// * `alert` - The alert to store
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn insert_alert(
+        &self,
+        alert: &Alert,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.alerts_client
+            .upsert_item(&alert.device_id, alert, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves a single alert raised for a device
+    /// 
+    /// # Arguments
+    /// * `device_id` - The device the alert is about
+    /// * `alert_id` - The unique identifier of the alert
+    /// 
+    /// # Returns
+    /// * `Result<Option<Alert>, Box<dyn std::error::Error>>` - The alert if found, or an error
+    pub async fn read_alert(
+        &self,
+        device_id: &str,
+        alert_id: &str,
+    ) -> Result<Option<Alert>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT * FROM c WHERE c.device_id = '{}' AND c.id = '{}'",
+            device_id, alert_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.alerts_client.query_items::<Alert>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+
+    /// Retrieves the alerts raised for a device within a time range, newest first
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The trigger timestamps to include
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Alert>, Box<dyn std::error::Error>>` - The matching alerts or an error
+    pub async fn read_alerts(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+    ) -> Result<Vec<Alert>, Box<dyn std::error::Error>> {
+        let mut query = format!("SELECT * FROM c WHERE c.device_id = '{}'", device_id);
+        if let Some(from) = range.from {
+            query.push_str(&format!(" AND c.triggered_at >= {}", from));
+        }
+        if let Some(to) = range.to {
+            query.push_str(&format!(" AND c.triggered_at <= {}", to));
+        }
+        query.push_str(" ORDER BY c.triggered_at DESC");
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.alerts_client.query_items::<Alert>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Records a day of telemetry archived to Blob Storage in the archive index
+    /// 
+    /// # Arguments
+    /// * `archive` - The archive to record
+    /// 
+    /// # Returns
+    /// * `Result<(), Box<dyn std::error::Error>>` - Success or an error
+    pub async fn upsert_archive(
+        &self,
+        archive: &Archive,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.archives_client
+            .upsert_item(&archive.device_id, archive, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Retrieves the archives of a device overlapping a time range, oldest first
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The telemetry timestamps the archives must cover part of
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Archive>, Box<dyn std::error::Error>>` - The matching archives or an error
+    pub async fn read_archives(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+    ) -> Result<Vec<Archive>, Box<dyn std::error::Error>> {
+        let mut query = format!("SELECT * FROM c WHERE c.device_id = '{}'", device_id);
+        if let Some(from) = range.from {
+            query.push_str(&format!(" AND c.last_timestamp >= {}", from));
+        }
+        if let Some(to) = range.to {
+            query.push_str(&format!(" AND c.first_timestamp <= {}", to));
+        }
+        query.push_str(" ORDER BY c.day ASC");
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.archives_client.query_items::<Archive>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items)
+    }
+
+    /// Retrieves the archive of a device's most recent archived day
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// 
+    /// # Returns
+    /// * `Result<Option<Archive>, Box<dyn std::error::Error>>` - The latest archive, None if
+    ///   the device has none, or an error
+    pub async fn read_latest_archive(
+        &self,
+        device_id: &str,
+    ) -> Result<Option<Archive>, Box<dyn std::error::Error>> {
+        let query = format!(
+            "SELECT TOP 1 * FROM c WHERE c.device_id = '{}' ORDER BY c.day DESC",
+            device_id
+        );
+        let partition_key = device_id.to_string();
+
+        let mut pager = self.archives_client.query_items::<Archive>(query, partition_key, None)?;
+
+        let mut items = Vec::new();
+        while let Some(page_response) = pager.next().await {
+            let page = page_response?;
+            items.extend(page.items().iter().cloned());
+        }
+
+        Ok(items.into_iter().next())
+    }
+}
+
+/// True if a document does not exist (404 Not Found)
+fn is_not_found(error: &azure_core::Error) -> bool {
+    error.http_status() == Some(StatusCode::NotFound)
 }
@@ -0,0 +1,121 @@
+// Azure AD Signing Key Cache
+// 
+// This module fetches the JSON Web Key Set that Azure AD signs access
+// tokens with and keeps the keys in memory by key ID. Azure AD rotates its
+// signing keys, so the set is fetched again whenever a token names a key
+// that is not cached, at most once per refresh interval so tokens with
+// made-up key IDs cannot flood the key endpoint.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::DecodingKey;
+use tracing::{info, warn};
+
+/// Minimum time between two fetches of the key set
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The signing keys of an Azure AD tenant, by key ID
+#[derive(Clone)]
+pub struct JwksCache {
+    /// The tenant's key set URL
+    url: String,
+    /// HTTP client used to fetch the key set
+    client: reqwest::Client,
+    /// Decoding keys by key ID
+    keys: Arc<RwLock<HashMap<String, DecodingKey>>>,
+    /// When the key set was last fetched
+    last_fetch: Arc<Mutex<Option<Instant>>>,
+}
+
+impl JwksCache {
+    /// Creates an empty cache for a key set URL
+    /// 
+    /// # Arguments
+    /// * `url` - The key set URL, e.g. `https://login.microsoftonline.com/<tenant>/discovery/v2.0/keys`
+    /// 
+    /// # Returns
+    /// * `Self` - A new JwksCache instance; keys are fetched on first use
+    pub fn new(url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        JwksCache {
+            url,
+            client,
+            keys: Arc::new(RwLock::new(HashMap::new())),
+            last_fetch: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Creates a cache holding a fixed key set, which is never fetched again
+    /// 
+    /// # Arguments
+    /// * `set` - The signing keys
+    /// 
+    /// # Returns
+    /// * `Self` - A JwksCache instance holding the keys
+    pub fn from_key_set(set: &JwkSet) -> Self {
+        let cache = Self::new(String::new());
+        cache.insert(set);
+        *cache.last_fetch.lock().unwrap() = Some(Instant::now());
+        cache
+    }
+
+    /// Returns the decoding key with the given key ID, if cached
+    pub fn key(&self, kid: &str) -> Option<DecodingKey> {
+        self.keys.read().unwrap().get(kid).cloned()
+    }
+
+    /// Fetches the key set if the key ID is not cached
+    /// 
+    /// Failures are logged; the token is then rejected as signed with an
+    /// unknown key.
+    /// 
+    /// # Arguments
+    /// * `kid` - The key ID named by a token
+    pub async fn ensure_key(&self, kid: &str) {
+        if self.key(kid).is_some() || self.url.is_empty() {
+            return;
+        }
+
+        {
+            let mut last_fetch = self.last_fetch.lock().unwrap();
+            if last_fetch.is_some_and(|at| at.elapsed() < MIN_REFRESH_INTERVAL) {
+                return;
+            }
+            *last_fetch = Some(Instant::now());
+        }
+
+        info!("Fetching signing keys from {} for key {}", self.url, kid);
+        match self.fetch().await {
+            Ok(set) => self.insert(&set),
+            Err(e) => warn!("Failed to fetch signing keys from {}: {}", self.url, e),
+        }
+    }
+
+    /// Downloads the key set
+    async fn fetch(&self) -> Result<JwkSet, reqwest::Error> {
+        self.client.get(&self.url).send().await?.error_for_status()?.json().await
+    }
+
+    /// Replaces the cached keys with the usable keys of a set
+    fn insert(&self, set: &JwkSet) {
+        let keys = set
+            .keys
+            .iter()
+            .filter_map(|jwk| {
+                let kid = jwk.common.key_id.clone()?;
+                DecodingKey::from_jwk(jwk)
+                    .map_err(|e| warn!("Ignoring signing key {}: {}", kid, e))
+                    .ok()
+                    .map(|key| (kid, key))
+            })
+            .collect();
+        *self.keys.write().unwrap() = keys;
+    }
+}
@@ -6,7 +6,21 @@
 
 pub mod cosmos_db_telemetry_store;
 pub mod azure_auth;
+pub mod telemetry_feed;
+pub mod alert_evaluator;
+pub mod query_cache;
+pub mod retention_job;
+pub mod blob_container;
+pub mod archive_job;
+pub mod jwks;
 
 // Re-export service types for convenient access
 pub use azure_auth::AzureAuth;
-pub use cosmos_db_telemetry_store::CosmosDbTelemetryStore;
\ No newline at end of file
+pub use cosmos_db_telemetry_store::{CosmosDbTelemetryStore, TelemetryPages};
+pub use telemetry_feed::TelemetryFeed;
+pub use alert_evaluator::AlertEvaluator;
+pub use query_cache::QueryCache;
+pub use retention_job::RetentionJob;
+pub use blob_container::BlobContainer;
+pub use archive_job::ArchiveJob;
+pub use jwks::JwksCache;
\ No newline at end of file
@@ -0,0 +1,277 @@
+// Telemetry Query Cache Service
+// 
+// This module caches the results of recent telemetry queries in process,
+// so dashboards auto-refreshing every few seconds do not re-issue identical
+// Cosmos DB queries. Results are keyed by device, time range, value filter,
+// order and page, expire after a short TTL, and are dropped as soon as the device
+// stores new telemetry. New telemetry is noticed through the device index,
+// which the device-comms service updates on every ingest, as the Cosmos DB
+// SDK offers no change feed. Streamed reads are cached once fully written,
+// and results too large to keep in memory are not cached at all.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::StreamExt;
+use tracing::{info, error};
+
+use crate::domain::paging::PageRequest;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::telemetry::Telemetry;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::services::{CosmosDbTelemetryStore, TelemetryPages};
+use crate::utils::ttl_cache::TtlCache;
+
+/// Largest number of records a cached result may hold
+const MAX_CACHED_RECORDS: usize = 10_000;
+
+/// Cache of telemetry query results, invalidated when a device stores new telemetry
+/// 
+/// Cloning the cache shares the underlying entries.
+#[derive(Clone)]
+pub struct QueryCache {
+    store: CosmosDbTelemetryStore,
+    entries: Option<TtlCache<Vec<Telemetry>>>,
+    interval: Duration,
+    /// Incremented on every invalidation, so results read before it are not cached after it
+    generation: Arc<AtomicU64>,
+    /// Timestamp of each device's latest record, as last passed to `revalidate`
+    latest: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+/// A streamed read that is cached once the stream ends
+struct CachingStream {
+    cache: QueryCache,
+    key: String,
+    generation: u64,
+    pages: TelemetryPages,
+    /// The records streamed so far, or None once the result is too large or failed
+    records: Option<Vec<Telemetry>>,
+}
+
+/// Builds the cache key of a query
+/// 
+/// The key starts with the device, so all entries of a device can be dropped at once.
+fn cache_key(
+    device_id: &str,
+    range: &TimeRange,
+    filter: &ValueFilter,
+    order: SortOrder,
+    page: Option<&PageRequest>,
+) -> String {
+    format!("{}/{:?}/{:?}/{:?}/{:?}", device_id, range, filter, order, page)
+}
+
+impl QueryCache {
+    /// Creates an empty cache
+    /// 
+    /// # Arguments
+    /// * `store` - The Cosmos DB telemetry store to read through
+    /// * `ttl` - How long a result stays valid at most; zero disables caching
+    /// * `interval` - How often the device index is checked for new telemetry
+    pub fn new(store: CosmosDbTelemetryStore, ttl: Duration, interval: Duration) -> Self {
+        QueryCache {
+            store,
+            entries: (!ttl.is_zero()).then(|| TtlCache::new(ttl)),
+            interval,
+            generation: Arc::new(AtomicU64::new(0)),
+            latest: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Caches a result unless it is too large or a device was invalidated since it was read
+    fn insert(&self, key: String, generation: u64, telemetry: Vec<Telemetry>) {
+        if let Some(entries) = &self.entries {
+            if telemetry.len() <= MAX_CACHED_RECORDS && self.generation.load(Ordering::SeqCst) == generation {
+                entries.insert(key, telemetry);
+            }
+        }
+    }
+
+    /// Retrieves telemetry like `CosmosDbTelemetryStore::query_telemetry`, from the cache if possible
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The timestamps to include
+    /// * `filter` - The conditions on metric values records must meet
+    /// * `order` - Whether to return the oldest or the newest records first
+    /// * `page` - The previous page's last record and the page size, or None for every matching record
+    /// 
+    /// # Returns
+    /// * `Result<Vec<Telemetry>, Box<dyn std::error::Error>>` - The matching records or an error
+    pub async fn query_telemetry(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+        filter: &ValueFilter,
+        order: SortOrder,
+        page: Option<&PageRequest>,
+    ) -> Result<Vec<Telemetry>, Box<dyn std::error::Error>> {
+        let Some(entries) = &self.entries else {
+            return self.store.query_telemetry(device_id, range, filter, order, page).await;
+        };
+
+        let key = cache_key(device_id, range, filter, order, page);
+        if let Some(telemetry) = entries.get(&key) {
+            info!("Serving telemetry of device {} from cache", device_id);
+            return Ok(telemetry);
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let telemetry = self.store.query_telemetry(device_id, range, filter, order, page).await?;
+        self.insert(key, generation, telemetry.clone());
+        Ok(telemetry)
+    }
+
+    /// Streams telemetry like `CosmosDbTelemetryStore::stream_telemetry`, from the cache if possible
+    /// 
+    /// A result read from Cosmos DB is cached once the stream has been read
+    /// to the end, unless it holds more than `MAX_CACHED_RECORDS` records.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `range` - The timestamps to include
+    /// * `filter` - The conditions on metric values records must meet
+    /// * `order` - Whether to return the oldest or the newest records first
+    /// 
+    /// # Returns
+    /// * `Result<TelemetryPages, Box<dyn std::error::Error>>` - The pages of matching records or an error
+    pub fn stream_telemetry(
+        &self,
+        device_id: &str,
+        range: &TimeRange,
+        filter: &ValueFilter,
+        order: SortOrder,
+    ) -> Result<TelemetryPages, Box<dyn std::error::Error>> {
+        let Some(entries) = &self.entries else {
+            return self.store.stream_telemetry(device_id, range, filter, order);
+        };
+
+        let key = cache_key(device_id, range, filter, order, None);
+        if let Some(telemetry) = entries.get(&key) {
+            info!("Serving telemetry of device {} from cache", device_id);
+            return Ok(futures::stream::iter([Ok(telemetry)]).boxed());
+        }
+
+        let state = CachingStream {
+            cache: self.clone(),
+            key,
+            generation: self.generation.load(Ordering::SeqCst),
+            pages: self.store.stream_telemetry(device_id, range, filter, order)?,
+            records: Some(Vec::new()),
+        };
+        let pages = futures::stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            match state.pages.next().await {
+                Some(Ok(page)) => {
+                    if let Some(records) = &mut state.records {
+                        if records.len() + page.len() <= MAX_CACHED_RECORDS {
+                            records.extend(page.iter().cloned());
+                        } else {
+                            state.records = None;
+                        }
+                    }
+                    Some((Ok(page), Some(state)))
+                }
+                Some(Err(e)) => Some((Err(e), None)),
+                None => {
+                    if let Some(records) = state.records {
+                        state.cache.insert(state.key, state.generation, records);
+                    }
+                    None
+                }
+            }
+        });
+        Ok(pages.boxed())
+    }
+
+    /// Drops every cached result of a device
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    pub fn invalidate(&self, device_id: &str) {
+        if let Some(entries) = &self.entries {
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            let prefix = format!("{}/", device_id);
+            entries.invalidate(|key| key.starts_with(&prefix));
+        }
+    }
+
+    /// Drops a device's cached results if they may predate its latest record
+    /// 
+    /// Responses tagged with the latest record's timestamp must not be served
+    /// from results read before that record was stored, which the periodic
+    /// invalidation only catches up with after `interval`. Results are
+    /// dropped whenever the timestamp differs from the one last passed in.
+    /// 
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `latest_timestamp` - Unix timestamp of the device's most recent record
+    pub fn revalidate(&self, device_id: &str, latest_timestamp: i64) {
+        if self.entries.is_none() {
+            return;
+        }
+        let previous = self.latest.lock().unwrap().insert(device_id.to_string(), latest_timestamp);
+        if previous != Some(latest_timestamp) {
+            self.invalidate(device_id);
+        }
+    }
+
+    /// Drops the results of devices that store new telemetry until the runtime shuts down
+    /// 
+    /// Every `interval`, the device index is read while anything is cached,
+    /// and the results of each device whose last-seen time changed are dropped.
+    pub async fn run(self) {
+        let Some(entries) = self.entries.clone() else {
+            return;
+        };
+
+        info!("Starting query cache invalidation");
+        let mut last_seen: HashMap<String, i64> = HashMap::new();
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if entries.is_empty() {
+                continue;
+            }
+
+            let devices = match self.store.read_devices().await {
+                Ok(devices) => devices,
+                Err(e) => {
+                    error!("Error reading device index for query cache invalidation: {}", e);
+                    continue;
+                }
+            };
+
+            for device in devices {
+                if last_seen.insert(device.device_id.clone(), device.last_seen) != Some(device.last_seen) {
+                    self.invalidate(&device.device_id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_separates_queries() {
+        let range = TimeRange { from: Some(1640995200), to: None };
+        let page = PageRequest { after: None, limit: 100 };
+
+        let filter = ValueFilter::parse(Some("temperature>30")).unwrap();
+
+        let key = cache_key("sensor-001", &range, &ValueFilter::NONE, SortOrder::Desc, Some(&page));
+        assert!(key.starts_with("sensor-001/"));
+        assert_ne!(key, cache_key("sensor-001", &range, &ValueFilter::NONE, SortOrder::Asc, Some(&page)));
+        assert_ne!(key, cache_key("sensor-001", &range, &ValueFilter::NONE, SortOrder::Desc, None));
+        assert_ne!(key, cache_key("sensor-001", &TimeRange::default(), &ValueFilter::NONE, SortOrder::Desc, Some(&page)));
+        assert_ne!(key, cache_key("sensor-001", &range, &filter, SortOrder::Desc, Some(&page)));
+        assert!(!cache_key("sensor-0011", &range, &ValueFilter::NONE, SortOrder::Desc, None).starts_with("sensor-001/"));
+    }
+}
@@ -0,0 +1,145 @@
+// Telemetry Retention Job Service
+// 
+// This module deletes telemetry older than each device's retention period
+// in the background, so the telemetry container does not have to be
+// cleaned up by hand. Devices are taken from the device index, and the
+// records reclaimed by each run are kept for the retention endpoint.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use tracing::{info, error};
+
+use crate::domain::retention::{DeviceCleanup, RetentionPolicy, RetentionRun, RetentionStatus};
+use crate::services::{CosmosDbTelemetryStore, QueryCache};
+
+/// Cleanup statistics since the service started
+#[derive(Debug, Default)]
+struct RetentionStats {
+    runs: u64,
+    total_records_deleted: u64,
+    last_run: Option<RetentionRun>,
+}
+
+/// Periodically deletes telemetry older than the retention policy allows
+/// 
+/// Cloning the job shares its statistics.
+#[derive(Clone)]
+pub struct RetentionJob {
+    store: CosmosDbTelemetryStore,
+    query_cache: QueryCache,
+    policy: RetentionPolicy,
+    interval: Duration,
+    stats: Arc<Mutex<RetentionStats>>,
+}
+
+impl RetentionJob {
+    /// Creates a job that has not run yet
+    /// 
+    /// # Arguments
+    /// * `store` - The Cosmos DB store holding the telemetry and device index
+    /// * `query_cache` - The query cache to drop cleaned-up devices from
+    /// * `policy` - How long each device's telemetry is kept
+    /// * `interval` - How often old telemetry is deleted
+    pub fn new(
+        store: CosmosDbTelemetryStore,
+        query_cache: QueryCache,
+        policy: RetentionPolicy,
+        interval: Duration,
+    ) -> Self {
+        RetentionJob {
+            store,
+            query_cache,
+            policy,
+            interval,
+            stats: Arc::new(Mutex::new(RetentionStats::default())),
+        }
+    }
+
+    /// Deletes old telemetry every `interval` until the runtime shuts down
+    /// 
+    /// Returns at once if no retention period is configured.
+    pub async fn run(self) {
+        if !self.policy.is_enabled() {
+            info!("No telemetry retention configured, keeping all telemetry");
+            return;
+        }
+
+        info!("Starting telemetry retention cleanup");
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.run_once().await {
+                error!("Error running telemetry retention cleanup: {}", e);
+            }
+        }
+    }
+
+    /// Deletes the telemetry of every device that is older than its retention period
+    /// 
+    /// A device whose cleanup fails is counted and retried at the next run,
+    /// while the other devices are still cleaned up.
+    /// 
+    /// # Returns
+    /// * `Result<RetentionRun, String>` - The statistics of the run, or an error if the
+    ///   device index could not be read
+    pub async fn run_once(&self) -> Result<RetentionRun, String> {
+        let started_at = Utc::now();
+        let start = Instant::now();
+
+        let devices = self.store.read_devices().await.map_err(|e| e.to_string())?;
+
+        let mut run = RetentionRun {
+            started_at,
+            duration_ms: 0,
+            devices_checked: 0,
+            records_deleted: 0,
+            devices_failed: 0,
+            devices: Vec::new(),
+        };
+        for device in devices {
+            let Some(cutoff) = self.policy.cutoff(&device.device_id, started_at.timestamp()) else {
+                continue;
+            };
+            run.devices_checked += 1;
+
+            match self.store.delete_telemetry_before(&device.device_id, cutoff).await.map_err(|e| e.to_string()) {
+                Ok(0) => {}
+                Ok(records_deleted) => {
+                    self.query_cache.invalidate(&device.device_id);
+                    run.records_deleted += records_deleted;
+                    run.devices.push(DeviceCleanup { device_id: device.device_id, cutoff, records_deleted });
+                }
+                Err(e) => {
+                    error!("Error deleting old telemetry of device {}: {}", device.device_id, e);
+                    run.devices_failed += 1;
+                }
+            }
+        }
+        run.duration_ms = start.elapsed().as_millis() as i64;
+
+        info!(
+            "Retention cleanup deleted {} record(s) from {} of {} device(s) in {} ms",
+            run.records_deleted, run.devices.len(), run.devices_checked, run.duration_ms
+        );
+
+        let mut stats = self.stats.lock().unwrap();
+        stats.runs += 1;
+        stats.total_records_deleted += run.records_deleted as u64;
+        stats.last_run = Some(run.clone());
+        Ok(run)
+    }
+
+    /// Returns the retention policy and the cleanup statistics so far
+    pub fn status(&self) -> RetentionStatus {
+        let stats = self.stats.lock().unwrap();
+        RetentionStatus {
+            policy: self.policy.clone(),
+            interval_secs: self.interval.as_secs(),
+            runs: stats.runs,
+            total_records_deleted: stats.total_records_deleted,
+            last_run: stats.last_run.clone(),
+        }
+    }
+}
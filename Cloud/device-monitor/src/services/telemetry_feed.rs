@@ -0,0 +1,129 @@
+// Live Telemetry Feed Service
+//
+// This module pushes new telemetry records to live subscribers. Telemetry
+// is written by the device-comms service, so the monitor polls Cosmos DB for
+// records newer than the last one seen. Each device is polled by a single
+// background task however many clients watch it, and new records are fanned
+// out to the clients through an internal broadcast channel.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tracing::{info, error};
+
+use crate::domain::telemetry::Telemetry;
+use crate::domain::sort_order::SortOrder;
+use crate::domain::time_range::TimeRange;
+use crate::domain::value_filter::ValueFilter;
+use crate::services::CosmosDbTelemetryStore;
+
+/// Number of records a slow subscriber can fall behind before missing some
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Broadcasts new telemetry records of each watched device to its subscribers
+///
+/// Cloning the feed shares the underlying channels.
+#[derive(Clone)]
+pub struct TelemetryFeed {
+    store: CosmosDbTelemetryStore,
+    interval: Duration,
+    channels: Arc<Mutex<HashMap<String, broadcast::Sender<Telemetry>>>>,
+}
+
+impl TelemetryFeed {
+    /// Creates a feed without subscribers
+    ///
+    /// # Arguments
+    /// * `store` - The Cosmos DB telemetry store to poll
+    /// * `interval` - How often a watched device is polled for new records
+    pub fn new(store: CosmosDbTelemetryStore, interval: Duration) -> Self {
+        TelemetryFeed {
+            store,
+            interval,
+            channels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Subscribes to the records of a device stored from now on
+    ///
+    /// The first subscriber of a device starts its polling task, which
+    /// stops once the last subscriber has gone.
+    ///
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    ///
+    /// # Returns
+    /// * `Result<broadcast::Receiver<Telemetry>, Box<dyn std::error::Error>>` - The receiver of
+    ///   new records, oldest first, or an error
+    pub async fn subscribe(&self, device_id: &str) -> Result<broadcast::Receiver<Telemetry>, Box<dyn std::error::Error>> {
+        if let Some(receiver) = self.join(device_id) {
+            return Ok(receiver);
+        }
+
+        // Only records newer than the latest one stored so far are pushed
+        let latest = self.store.read_latest_telemetry(device_id).await?.and_then(|t| t.timestamp);
+
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(sender) = channels.get(device_id).filter(|sender| sender.receiver_count() > 0) {
+            return Ok(sender.subscribe());
+        }
+        let (sender, receiver) = broadcast::channel(CHANNEL_CAPACITY);
+        channels.insert(device_id.to_string(), sender.clone());
+
+        info!("Starting live telemetry polling for device: {}", device_id);
+        tokio::spawn(self.clone().poll(device_id.to_string(), latest, sender));
+        Ok(receiver)
+    }
+
+    /// Subscribes to the channel of a device that is already being polled
+    fn join(&self, device_id: &str) -> Option<broadcast::Receiver<Telemetry>> {
+        let channels = self.channels.lock().unwrap();
+        channels
+            .get(device_id)
+            .filter(|sender| sender.receiver_count() > 0)
+            .map(|sender| sender.subscribe())
+    }
+
+    /// Polls a device for new records until it has no subscribers left
+    ///
+    /// Records are new if their timestamp is after the latest one seen, so a
+    /// record stored later with an already-seen timestamp is not pushed.
+    ///
+    /// # Arguments
+    /// * `device_id` - The unique identifier of the device
+    /// * `latest` - The timestamp of the latest record seen, if any
+    /// * `sender` - The channel to broadcast new records on
+    async fn poll(self, device_id: String, mut latest: Option<i64>, sender: broadcast::Sender<Telemetry>) {
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if sender.receiver_count() == 0 {
+                break;
+            }
+
+            let range = TimeRange { from: latest.map(|timestamp| timestamp + 1), to: None };
+            let telemetry = match self.store.query_telemetry(&device_id, &range, &ValueFilter::NONE, SortOrder::Asc, None).await {
+                Ok(telemetry) => telemetry,
+                Err(e) => {
+                    error!("Error polling live telemetry for device {}: {}", device_id, e);
+                    continue;
+                }
+            };
+
+            for record in telemetry {
+                latest = latest.max(record.timestamp);
+                // Sending only fails once every subscriber has gone
+                let _ = sender.send(record);
+            }
+        }
+
+        // Forget the channel unless a new subscriber replaced it meanwhile
+        let mut channels = self.channels.lock().unwrap();
+        if channels.get(&device_id).is_some_and(|current| current.same_channel(&sender)) {
+            channels.remove(&device_id);
+        }
+        info!("Stopped live telemetry polling for device: {}", device_id);
+    }
+}
@@ -0,0 +1,131 @@
+// Authentication Request Guards
+// 
+// This module provides Rocket request guards that authenticate callers of
+// the monitoring endpoints and enforce the devices they may read. A route
+// declares the access it needs by taking one of `ReadAccess`,
+// `DeviceAccess`, `DeviceWriteAccess` or `FleetAccess` as an argument.
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use tracing::warn;
+
+use crate::app_state::AppState;
+use crate::domain::auth::{AuthError, Principal};
+
+/// Authenticates the request
+/// 
+/// Credentials are read from `Authorization: Bearer <jwt>` or `X-API-Key`.
+/// The Azure AD signing key a bearer token names is fetched first if it is
+/// not cached yet. Failures are mapped to 401 Unauthorized.
+async fn authenticate(request: &Request<'_>) -> Outcome<Principal, AuthError> {
+    let state = match request.rocket().state::<AppState>() {
+        Some(state) => state,
+        None => return Outcome::Error((Status::InternalServerError, AuthError::MissingCredentials)),
+    };
+
+    let headers = request.headers();
+    let bearer = headers
+        .get_one("Authorization")
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let api_key = headers.get_one("X-API-Key");
+
+    state.auth.load_signing_key(bearer).await;
+    match state.auth.authenticate(bearer, api_key) {
+        Ok(principal) => Outcome::Success(principal),
+        Err(e) => reject(request, e),
+    }
+}
+
+/// Returns the `<device_id>` path segment of the matched route, if it has one
+fn device_param<'r>(request: &'r Request<'_>) -> Option<&'r str> {
+    let route = request.route()?;
+    let index = route.uri.unmounted_origin.path().segments().position(|segment| segment == "<device_id>")?;
+    request.routed_segment(index)
+}
+
+/// Logs and rejects a request the caller is not permitted to make
+fn reject<T>(request: &Request<'_>, error: AuthError) -> Outcome<T, AuthError> {
+    warn!("Authorization failed for {}: {}", request.uri(), error);
+    Outcome::Error((Status::from(&error), error))
+}
+
+/// Grants access to any authenticated caller
+/// 
+/// Routes taking it must check or filter the devices they return
+/// themselves with `Principal::can_read`.
+pub struct ReadAccess(pub Principal);
+
+/// Grants access to callers that may read the device of the route
+/// 
+/// Only for routes with a `<device_id>` path segment; callers outside the
+/// device's scope get 403 Forbidden.
+pub struct DeviceAccess(pub Principal);
+
+/// Grants access to operators that may read the device of the route
+/// 
+/// For routes changing a device's alert rules, alerts or annotations;
+/// read-only callers get 403 Forbidden.
+pub struct DeviceWriteAccess(pub Principal);
+
+/// Grants access to callers that may read every device
+/// 
+/// For fleet-wide endpoints, whose results cover devices the caller might
+/// not be scoped to. Scoped callers get 403 Forbidden.
+pub struct FleetAccess(pub Principal);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ReadAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authenticate(request).await.map(ReadAccess)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for DeviceAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let principal = match authenticate(request).await {
+            Outcome::Success(principal) => principal,
+            Outcome::Error(e) => return Outcome::Error(e),
+            Outcome::Forward(status) => return Outcome::Forward(status),
+        };
+
+        let device_id = device_param(request).unwrap_or_default();
+        if principal.can_read(device_id) {
+            Outcome::Success(DeviceAccess(principal))
+        } else {
+            reject(request, AuthError::DeviceNotPermitted(device_id.to_string()))
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for DeviceWriteAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match DeviceAccess::from_request(request).await {
+            Outcome::Success(DeviceAccess(principal)) if !principal.can_write() => {
+                reject(request, AuthError::OperatorRequired)
+            }
+            outcome => outcome.map(|DeviceAccess(principal)| DeviceWriteAccess(principal)),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for FleetAccess {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match authenticate(request).await {
+            Outcome::Success(principal) if !principal.is_fleet_wide() => {
+                reject(request, AuthError::FleetAccessRequired)
+            }
+            outcome => outcome.map(FleetAccess),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Response Compression Fairing
+// 
+// This module provides a Rocket fairing that gzips JSON and CSV responses
+// for clients that accept it. Telemetry reads and exports can run to
+// several megabytes of highly repetitive text, which compresses well.
+// Streamed bodies are compressed as they are written, so large histories
+// are still never held in memory.
+
+use async_compression::tokio::bufread::GzipEncoder;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{ContentType, Header};
+use rocket::{Request, Response};
+use tokio::io::BufReader;
+
+/// Smallest body worth compressing, in bytes, when its size is known up front
+const MIN_COMPRESSED_BYTES: usize = 1024;
+
+/// Returns whether an `Accept-Encoding` header value allows gzip
+/// 
+/// An explicit `gzip` entry decides, otherwise a `*` entry does; entries
+/// with `q=0` refuse the encoding.
+/// 
+/// # Arguments
+/// * `accept_encoding` - The header value, e.g. "gzip, deflate, br"
+/// 
+/// # Returns
+/// * `bool` - True if the response may be gzipped
+pub fn accepts_gzip(accept_encoding: &str) -> bool {
+    let mut wildcard = false;
+    for encoding in accept_encoding.split(',') {
+        let mut parts = encoding.split(';').map(str::trim);
+        let name = parts.next().unwrap_or_default();
+        let allowed = !parts
+            .filter_map(|param| param.strip_prefix("q="))
+            .any(|q| q.parse::<f32>().is_ok_and(|q| q <= 0.0));
+        if name.eq_ignore_ascii_case("gzip") {
+            return allowed;
+        }
+        if name == "*" {
+            wildcard = allowed;
+        }
+    }
+    wildcard
+}
+
+/// Rocket fairing gzipping JSON and CSV responses
+/// 
+/// A response is compressed when the request's `Accept-Encoding` allows
+/// gzip, the response is JSON or CSV, carries no encoding yet, and is not
+/// known to be smaller than `MIN_COMPRESSED_BYTES`. Compressed responses are
+/// sent chunked, as their length is only known once written.
+pub struct Compression;
+
+#[rocket::async_trait]
+impl Fairing for Compression {
+    /// Returns information about this fairing
+    fn info(&self) -> Info {
+        Info {
+            name: "Gzip Compression",
+            kind: Kind::Response,
+        }
+    }
+
+    /// Called when a response is being sent
+    /// 
+    /// Replaces the body with a gzip stream over it if the response qualifies.
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let compressible = response
+            .content_type()
+            .is_some_and(|content_type| content_type.is_json() || content_type == ContentType::CSV);
+        if !compressible {
+            return;
+        }
+
+        // Whether the body is compressed depends on this header, so caches must key on it
+        response.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+
+        let accepted = request.headers().get("Accept-Encoding").any(accepts_gzip);
+        let encoded = response.headers().contains("Content-Encoding");
+        let too_small = response.body().preset_size().is_some_and(|size| size < MIN_COMPRESSED_BYTES);
+        if !accepted || encoded || too_small {
+            return;
+        }
+
+        let body = response.body_mut().take();
+        response.set_header(Header::new("Content-Encoding", "gzip"));
+        response.set_streamed_body(GzipEncoder::new(BufReader::new(body)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepts_gzip() {
+        assert!(accepts_gzip("gzip"));
+        assert!(accepts_gzip("deflate, GZIP;q=0.8, br"));
+        assert!(accepts_gzip("*"));
+        assert!(!accepts_gzip("gzip;q=0"));
+        assert!(!accepts_gzip("*, gzip;q=0"));
+        assert!(!accepts_gzip("deflate, br"));
+        assert!(!accepts_gzip("identity"));
+        assert!(!accepts_gzip(""));
+    }
+}
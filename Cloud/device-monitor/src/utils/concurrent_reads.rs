@@ -0,0 +1,95 @@
+// Concurrent Read Utilities
+//
+// This module runs one telemetry store read per device concurrently, as the
+// fleet-wide and multi-device routes do, and reports the first failure as
+// an API error. At most `READ_CONCURRENCY` reads are in flight at once, so
+// a large fleet does not open thousands of store requests in one go.
+
+use std::future::Future;
+
+use futures::stream::{self, StreamExt};
+
+use crate::domain::error::ApiError;
+
+/// Default number of store reads a route runs at once
+pub const DEFAULT_READ_CONCURRENCY: usize = 16;
+
+/// Runs one store read per item concurrently, at most `limit` at a time
+///
+/// Store errors are boxed `dyn Error`s, which are not `Send`. Each is mapped
+/// to an `ApiError` inside its own read, so only `Send` results are held
+/// while the other reads are awaited and the route's future stays `Send`,
+/// as Rocket requires.
+///
+/// # Arguments
+/// * `items` - The devices, or other keys, to read
+/// * `read` - Starts the read of one item
+/// * `limit` - Maximum number of reads in flight (values below 1 are treated as 1)
+///
+/// # Returns
+/// * `Result<Vec<T>, ApiError>` - The result of every read, in the order of `items`, or the first error
+pub async fn read_all<I, F, Fut, T>(items: I, read: F, limit: usize) -> Result<Vec<T>, ApiError>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future<Output = Result<T, Box<dyn std::error::Error>>>,
+{
+    let reads: Vec<_> = stream::iter(items.into_iter().map(read).map(|read| async move {
+        read.await.map_err(|e| ApiError::DatabaseError(e.to_string()))
+    }))
+    .buffered(limit.max(1))
+    .collect()
+    .await;
+    reads.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_read_all_bounds_reads_in_flight() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items: Vec<usize> = (0..20).collect();
+
+        let results = read_all(
+            &items,
+            |item| {
+                let (in_flight, peak) = (&in_flight, &peak);
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok(item * 2)
+                }
+            },
+            3,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results, (0..20).map(|item| item * 2).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_read_all_reports_errors() {
+        let result = read_all(
+            [1, 2],
+            |item| async move {
+                if item == 2 {
+                    Err("read failed".into())
+                } else {
+                    Ok(item)
+                }
+            },
+            0,
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiError::DatabaseError(message)) if message == "read failed"));
+    }
+}
@@ -0,0 +1,59 @@
+// Conditional Request Utilities
+// 
+// This module provides the request guard and responder behind conditional
+// GETs. A route reads the client's `If-None-Match` header with the
+// `IfNoneMatch` guard and wraps its response in `Conditional`, which sends
+// `304 Not Modified` without a body when the client's copy is current.
+
+use std::convert::Infallible;
+
+use rocket::http::{Header, Status};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::{self, Responder, Response};
+
+use crate::domain::etag::EntityTag;
+
+/// The `If-None-Match` header of a request, if it has one
+pub struct IfNoneMatch(Option<String>);
+
+impl IfNoneMatch {
+    /// Returns whether the client already holds the representation with this tag
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        self.0.as_deref().is_some_and(|if_none_match| etag.matches(if_none_match))
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfNoneMatch {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(IfNoneMatch(request.headers().get_one("If-None-Match").map(str::to_string)))
+    }
+}
+
+/// A response that is only sent if the client's copy is out of date
+pub enum Conditional<R> {
+    /// The client's copy is current; answered with 304 and no body
+    NotModified(EntityTag),
+    /// The full response, tagged if its tag is known
+    Modified(Option<EntityTag>, R),
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Conditional<R> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        match self {
+            Conditional::NotModified(etag) => Response::build()
+                .status(Status::NotModified)
+                .header(Header::new("ETag", etag.to_string()))
+                .ok(),
+            Conditional::Modified(etag, response) => {
+                let mut response = response.respond_to(request)?;
+                if let Some(etag) = etag {
+                    response.set_header(Header::new("ETag", etag.to_string()));
+                }
+                Ok(response)
+            }
+        }
+    }
+}
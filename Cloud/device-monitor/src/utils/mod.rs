@@ -3,7 +3,12 @@
 // This module contains utility functions and helpers used throughout
 // the device monitoring service, including logging and tracing utilities.
 
+pub mod auth_guard;
+pub mod concurrent_reads;
+pub mod compression;
+pub mod conditional;
 pub mod tracing;
+pub mod ttl_cache;
 
 // Re-export all tracing utilities for convenient access
 pub use tracing::*;
\ No newline at end of file
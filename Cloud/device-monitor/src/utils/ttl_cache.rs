@@ -0,0 +1,91 @@
+// Time-To-Live Cache
+// 
+// This module provides a small in-process cache whose entries expire after
+// a fixed duration. It is used to avoid repeating expensive fan-out queries
+// against Cosmos DB for identical requests.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Thread-safe cache of values keyed by string, expiring after a fixed TTL
+/// 
+/// Cloning the cache shares the underlying storage.
+#[derive(Clone)]
+pub struct TtlCache<V: Clone> {
+    ttl: Duration,
+    entries: Arc<Mutex<HashMap<String, (Instant, V)>>>,
+}
+
+impl<V: Clone> TtlCache<V> {
+    /// Creates an empty cache
+    /// 
+    /// # Arguments
+    /// * `ttl` - How long an entry stays valid after insertion
+    pub fn new(ttl: Duration) -> Self {
+        TtlCache {
+            ttl,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns a clone of the cached value if present and not expired
+    pub fn get(&self, key: &str) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((inserted, value)) if inserted.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Stores a value, replacing any previous entry for the key
+    /// 
+    /// Expired entries are dropped on insertion to bound memory use.
+    pub fn insert(&self, key: String, value: V) {
+        let mut entries = self.entries.lock().unwrap();
+        let ttl = self.ttl;
+        entries.retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+        entries.insert(key, (Instant::now(), value));
+    }
+
+    /// Drops every entry whose key matches the predicate
+    /// 
+    /// # Arguments
+    /// * `predicate` - Returns true for the keys to drop
+    pub fn invalidate(&self, predicate: impl Fn(&str) -> bool) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|key, _| !predicate(key));
+    }
+
+    /// Returns true if the cache holds no unexpired entry
+    pub fn is_empty(&self) -> bool {
+        let entries = self.entries.lock().unwrap();
+        entries.values().all(|(inserted, _)| inserted.elapsed() >= self.ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidate_matching_keys() {
+        let cache = TtlCache::new(Duration::from_secs(60));
+        cache.insert("sensor-001/a".to_string(), 1);
+        cache.insert("sensor-001/b".to_string(), 2);
+        cache.insert("sensor-002/a".to_string(), 3);
+
+        cache.invalidate(|key| key.starts_with("sensor-001/"));
+        assert_eq!(cache.get("sensor-001/a"), None);
+        assert_eq!(cache.get("sensor-001/b"), None);
+        assert_eq!(cache.get("sensor-002/a"), Some(3));
+        assert!(!cache.is_empty());
+
+        cache.invalidate(|_| true);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+// Time-Bucketed Aggregation API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/aggregate/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test aggregating a device's metric into hourly buckets
+/// 
+/// This test stores samples in two hours and verifies each bucket reports
+/// the requested aggregate and the min, max, avg and count of its samples.
+#[tokio::test]
+async fn test_aggregate_hourly_buckets() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_aggregate_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (timestamp, temperature) in [(1640995200, "20"), (1640997000, "24"), (1640998800, "30")] {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/aggregate/{}?metric=temperature&bucket=1h&fn=max", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid aggregation");
+
+    assert_eq!(body["fn"], "max");
+    assert_eq!(body["bucket_secs"], 3600);
+    assert_eq!(body["buckets"], serde_json::json!([
+        { "start": 1640995200, "value": 24.0, "min": 20.0, "max": 24.0, "avg": 22.0, "count": 2 },
+        { "start": 1640998800, "value": 30.0, "min": 30.0, "max": 30.0, "avg": 30.0, "count": 1 }
+    ]));
+}
+
+/// Test aggregating with an invalid bucket width
+/// 
+/// This test verifies that the API returns 400 Bad Request.
+#[tokio::test]
+async fn test_aggregate_invalid_bucket() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/aggregate/{}?metric=temperature&bucket=1w", device_id))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test aggregating into daily buckets aligned to local midnight
+/// 
+/// This test stores samples on either side of midnight in Berlin and
+/// verifies they fall into the Berlin days, which differ from the UTC days.
+#[tokio::test]
+async fn test_aggregate_daily_buckets_in_timezone() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_aggregate_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    // 22:30 and 23:30 UTC on 2022-01-01, the second already 2022-01-02 in Berlin
+    for (timestamp, temperature) in [(1641076200, "20"), (1641079800, "24")] {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/aggregate/{}?metric=temperature&bucket=1d&tz=Europe/Berlin", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid aggregation");
+
+    assert_eq!(body["tz"], "Europe/Berlin");
+    let starts: Vec<i64> = body["buckets"].as_array().unwrap().iter().map(|b| b["start"].as_i64().unwrap()).collect();
+    assert_eq!(starts, vec![1640991600, 1641078000]);
+
+    let response = client
+        .get(format!("/iot/data/aggregate/{}?metric=temperature&bucket=1d&tz=Mars/Olympus", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
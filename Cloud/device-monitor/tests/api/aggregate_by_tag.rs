@@ -0,0 +1,67 @@
+// Group-By-Tag Aggregation API Integration Tests
+// 
+// This module contains integration tests for the tag aggregation endpoint
+// of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+
+/// Test aggregating by a tag that no device has
+/// 
+/// This test verifies that the API returns 400 Bad Request when no
+/// configured device carries the requested tag.
+#[tokio::test]
+async fn test_aggregate_by_unknown_tag() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .get("/iot/data/aggregate/by-tag/no-such-tag?metric=temperature")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test aggregating with an unsupported aggregation function
+/// 
+/// This test verifies that the API rejects unknown `fn` values with
+/// 400 Bad Request.
+#[tokio::test]
+async fn test_aggregate_by_tag_invalid_function() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .get("/iot/data/aggregate/by-tag/site?metric=temperature&fn=median")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test aggregating over a window that is too long
+/// 
+/// This test verifies that the API rejects windows spanning more than
+/// 31 days with 400 Bad Request, since every tagged device's telemetry in
+/// the window is read.
+#[tokio::test]
+async fn test_aggregate_by_tag_window_too_long() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .get("/iot/data/aggregate/by-tag/site?metric=temperature&from=0&to=1641081600")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -0,0 +1,185 @@
+// Alert API Integration Tests
+// 
+// This module contains integration tests for the alert rule, alert listing
+// and alert handling endpoints of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::alert::{Alert, AlertRule, AlertStatus, Operator};
+
+/// Test the full alert rule lifecycle
+/// 
+/// This test creates, lists, edits and deletes a rule and verifies each
+/// step is reflected by the listing endpoint.
+#[tokio::test]
+async fn test_alert_rule_lifecycle() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post(format!("/iot/data/alerts/rules/{}", device_id))
+        .header(ContentType::JSON)
+        .body(r#"{"metric": "temperature", "operator": "gt", "value": 30.0, "duration_secs": 300}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let created: AlertRule = response.into_json().await.expect("Invalid alert rule");
+    assert_eq!(created.operator, Operator::Gt);
+
+    let response = client
+        .put(format!("/iot/data/alerts/rules/{}/{}", device_id, created.id))
+        .header(ContentType::JSON)
+        .body(r#"{"metric": "temperature", "operator": "lt", "value": 5.0}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/iot/data/alerts/rules/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let rules: Vec<AlertRule> = response.into_json().await.expect("Invalid alert rule list");
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].operator, Operator::Lt);
+    assert_eq!(rules[0].duration_secs, 0);
+
+    let response = client
+        .delete(format!("/iot/data/alerts/rules/{}/{}", device_id, created.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = client
+        .delete(format!("/iot/data/alerts/rules/{}/{}", device_id, created.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test that invalid rules are rejected
+#[tokio::test]
+async fn test_invalid_alert_rule() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post(format!("/iot/data/alerts/rules/{}", device_id))
+        .header(ContentType::JSON)
+        .body(r#"{"metric": "temperature", "operator": "gt", "value": 30.0, "duration_secs": -5}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test that stored alerts are listed newest first
+#[tokio::test]
+async fn test_list_alerts() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    for triggered_at in [1640995500, 1640999100] {
+        let alert = Alert {
+            id: uuid::Uuid::new_v4().to_string(),
+            rule_id: "rule-1".to_string(),
+            device_id: device_id.clone(),
+            metric: "temperature".to_string(),
+            operator: Operator::Gt,
+            threshold: 30.0,
+            value: 31.2,
+            breach_started: triggered_at - 300,
+            triggered_at,
+            acknowledged: None,
+            resolved: None,
+        };
+        app.app_state.cosmos_client.insert_alert(&alert).await.expect("Failed to store alert");
+    }
+
+    let response = client
+        .get(format!("/iot/data/alerts/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let alerts: Vec<Alert> = response.into_json().await.expect("Invalid alert list");
+    assert_eq!(alerts.iter().map(|alert| alert.triggered_at).collect::<Vec<_>>(), vec![1640999100, 1640995500]);
+
+    let response = client
+        .get(format!("/iot/data/alerts/{}?from=1640999000", device_id))
+        .dispatch()
+        .await;
+    let alerts: Vec<Alert> = response.into_json().await.expect("Invalid alert list");
+    assert_eq!(alerts.len(), 1);
+}
+
+/// Test acknowledging and resolving an alert
+/// 
+/// This test acknowledges and resolves a stored alert, verifies the
+/// listing reflects it, and that a resolved alert cannot be acknowledged.
+#[tokio::test]
+async fn test_acknowledge_and_resolve_alert() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let alert = Alert {
+        id: uuid::Uuid::new_v4().to_string(),
+        rule_id: "rule-1".to_string(),
+        device_id: device_id.clone(),
+        metric: "temperature".to_string(),
+        operator: Operator::Gt,
+        threshold: 30.0,
+        value: 31.2,
+        breach_started: 1640995200,
+        triggered_at: 1640995500,
+        acknowledged: None,
+        resolved: None,
+    };
+    app.app_state.cosmos_client.insert_alert(&alert).await.expect("Failed to store alert");
+
+    let response = client
+        .post(format!("/iot/data/alerts/{}/{}/acknowledge", device_id, alert.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let acknowledged: Alert = response.into_json().await.expect("Invalid alert");
+    assert_eq!(acknowledged.status(), AlertStatus::Acknowledged);
+
+    let response = client
+        .post(format!("/iot/data/alerts/{}/{}/resolve", device_id, alert.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/iot/data/alerts/{}", device_id))
+        .dispatch()
+        .await;
+    let alerts: Vec<Alert> = response.into_json().await.expect("Invalid alert list");
+    assert_eq!(alerts[0].status(), AlertStatus::Resolved);
+    assert_eq!(alerts[0].acknowledged, acknowledged.acknowledged);
+
+    let response = client
+        .post(format!("/iot/data/alerts/{}/{}/acknowledge", device_id, alert.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Conflict);
+
+    let response = client
+        .post(format!("/iot/data/alerts/{}/missing/resolve", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -0,0 +1,104 @@
+// Telemetry Annotation API Integration Tests
+// 
+// This module contains integration tests for the annotation endpoints of
+// the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::annotation::Annotation;
+
+/// Test the full annotation lifecycle
+/// 
+/// This test creates, lists, edits and deletes an annotation and verifies
+/// each step is reflected by the listing endpoint.
+#[tokio::test]
+async fn test_annotation_lifecycle() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post(format!("/iot/data/annotations/{}", device_id))
+        .header(ContentType::JSON)
+        .body(r#"{"start": 1640995200, "text": "Sensor relocated", "author": "alice"}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let created: Annotation = response.into_json().await.expect("Invalid annotation");
+    assert_eq!(created.end, created.start);
+
+    let response = client
+        .put(format!("/iot/data/annotations/{}/{}", device_id, created.id))
+        .header(ContentType::JSON)
+        .body(r#"{"start": 1640995200, "end": 1640998800, "text": "HVAC serviced", "author": "bob"}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .get(format!("/iot/data/annotations/{}?from=1640998000", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let annotations: Vec<Annotation> = response.into_json().await.expect("Invalid annotation list");
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].text, "HVAC serviced");
+    assert_eq!(annotations[0].author, "bob");
+
+    let response = client
+        .delete(format!("/iot/data/annotations/{}/{}", device_id, created.id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = client
+        .get(format!("/iot/data/annotations/{}", device_id))
+        .dispatch()
+        .await;
+    let annotations: Vec<Annotation> = response.into_json().await.expect("Invalid annotation list");
+    assert!(annotations.is_empty());
+}
+
+/// Test creating an annotation that ends before it starts
+/// 
+/// This test verifies that the API returns 400 Bad Request for an invalid range.
+#[tokio::test]
+async fn test_create_annotation_invalid_range() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .post(format!("/iot/data/annotations/{}", device_id))
+        .header(ContentType::JSON)
+        .body(r#"{"start": 200, "end": 100, "text": "Backwards", "author": "alice"}"#)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test deleting an annotation that does not exist
+/// 
+/// This test verifies that the API returns 404 Not Found.
+#[tokio::test]
+async fn test_delete_unknown_annotation() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .delete(format!("/iot/data/annotations/{}/no-such-annotation", device_id))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::NotFound);
+}
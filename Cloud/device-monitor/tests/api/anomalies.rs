@@ -0,0 +1,62 @@
+// Anomaly Detection API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/anomalies/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test that a spike in an otherwise steady series is flagged
+#[tokio::test]
+async fn test_detect_anomalies() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_anomalies_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let temperatures = ["20.0", "20.5", "19.5", "20.2", "19.8", "20.1", "19.9", "20.3", "45.0", "20.0"];
+    for (i, temperature) in temperatures.iter().enumerate() {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200 + i as i64 * 60)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/anomalies/{}?metric=temperature&window=10", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid anomaly report");
+
+    assert_eq!(body["method"], serde_json::json!({ "type": "zscore", "window": 10 }));
+    assert_eq!(body["points"], 10);
+    let anomalies = body["anomalies"].as_array().unwrap();
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0]["timestamp"], 1640995200 + 8 * 60);
+    assert_eq!(anomalies[0]["value"], 45.0);
+}
+
+/// Test that invalid detection parameters are rejected
+#[tokio::test]
+async fn test_anomalies_invalid_parameters() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    for query in ["metric=temperature&method=iqr", "metric=temperature&method=ewma&alpha=2", "metric=temperature&threshold=-1"] {
+        let response = client
+            .get(format!("/iot/data/anomalies/{}?{}", device_id, query))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest, "{}", query);
+    }
+}
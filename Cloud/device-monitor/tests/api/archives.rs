@@ -0,0 +1,72 @@
+// Archives API Integration Tests
+// 
+// This module contains integration tests for the GET
+// /iot/data/archives/<device_id> endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use chrono::{NaiveDate, Utc};
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::archive::{blob_name, day_start, Archive, ArchiveFormat};
+
+/// Test that a device's archives are listed oldest first and filtered by time range
+#[tokio::test]
+async fn test_list_archives() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_archives_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let days = [NaiveDate::from_ymd_opt(2022, 1, 2).unwrap(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()];
+    for day in days {
+        let archive = Archive {
+            id: format!("{}-{}", device_id, day),
+            device_id: device_id.clone(),
+            day,
+            blob_name: blob_name(&device_id, day, ArchiveFormat::Jsonl),
+            format: ArchiveFormat::Jsonl,
+            records: 2,
+            bytes: 200,
+            first_timestamp: day_start(day),
+            last_timestamp: day_start(day) + 3600,
+            deleted: false,
+            archived_at: Utc::now(),
+        };
+        app.app_state.cosmos_client
+            .upsert_archive(&archive)
+            .await
+            .expect("Failed to store archive");
+    }
+
+    let response = client.get(format!("/iot/data/archives/{}", device_id)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let archives: Vec<Archive> = response.into_json().await.expect("Invalid archive list");
+    assert_eq!(archives.len(), 2);
+    assert_eq!(archives[0].day, days[1]);
+    assert_eq!(archives[1].blob_name, format!("{}/2022-01-02.jsonl", device_id));
+
+    let from = day_start(days[0]);
+    let response = client
+        .get(format!("/iot/data/archives/{}?from={}", device_id, from))
+        .dispatch()
+        .await;
+    let archives: Vec<Archive> = response.into_json().await.expect("Invalid archive list");
+    assert_eq!(archives.len(), 1);
+    assert_eq!(archives[0].day, days[0]);
+}
+
+/// Test that a device without archives has an empty list
+#[tokio::test]
+async fn test_list_archives_of_unarchived_device() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client.get("/iot/data/archives/no_such_device").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let archives: Vec<Archive> = response.into_json().await.expect("Invalid archive list");
+    assert!(archives.is_empty());
+}
@@ -0,0 +1,222 @@
+// Authentication API Integration Tests
+// 
+// This module contains integration tests for the credential and device
+// scope checks on the device monitoring service endpoints.
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::auth::{ApiKey, AuthConfig, Role};
+use device_monitor::domain::device::DeviceListing;
+
+/// Creates an authentication configuration with one key per tenant and a fleet-wide key
+/// 
+/// Only tenant A's key holds the operator role.
+fn test_auth_config() -> AuthConfig {
+    let key = |name: &str, devices: Option<&str>, role: Role| ApiKey {
+        name: name.to_string(),
+        key: format!("{}-key", name),
+        devices: devices.map(|pattern| vec![pattern.to_string()]),
+        role,
+    };
+
+    AuthConfig::new(
+        vec![
+            key("tenant-a", Some("test_auth_a_*"), Role::Operator),
+            key("tenant-b", Some("test_auth_b_*"), Role::ReadOnly),
+            key("grafana", None, Role::ReadOnly),
+        ],
+        None,
+    )
+}
+
+/// Generates a unique device ID belonging to a tenant
+fn tenant_device_id(tenant: &str) -> String {
+    format!("test_auth_{}_{}", tenant, &uuid::Uuid::new_v4().simple().to_string()[..12])
+}
+
+/// Test that requests without credentials are rejected
+/// 
+/// This test verifies that the API returns 401 Unauthorized when
+/// credentials are configured and none are sent.
+#[tokio::test]
+async fn test_missing_credentials_unauthorized() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = tenant_device_id("a");
+
+    let response = client
+        .get(format!("/iot/data/latest/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let response = client
+        .get(format!("/iot/data/latest/{}", device_id))
+        .header(Header::new("X-API-Key", "not-a-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+/// Test that a tenant's key cannot read another tenant's devices
+/// 
+/// This test verifies that the API returns 403 Forbidden for a device
+/// outside the key's scope, and serves devices inside it.
+#[tokio::test]
+async fn test_device_scope_enforced() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let own_device = tenant_device_id("a");
+    let other_device = tenant_device_id("b");
+
+    let response = client
+        .get(format!("/iot/data/latest/{}", other_device))
+        .header(Header::new("X-API-Key", "tenant-a-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .get(format!("/iot/data/compare?devices={},{}&metric=temperature", own_device, other_device))
+        .header(Header::new("X-API-Key", "tenant-a-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    // The device is in scope but has not reported anything yet
+    let response = client
+        .get(format!("/iot/data/latest/{}", own_device))
+        .header(Header::new("X-API-Key", "tenant-a-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test that only operators may change a device's annotations
+/// 
+/// This test verifies that a read-only key gets 403 Forbidden when creating
+/// an annotation for a device it may read, and an operator key may create one.
+#[tokio::test]
+async fn test_annotation_requires_operator() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let annotation = serde_json::json!({
+        "start": 1640995200,
+        "end": 1640998800,
+        "text": "HVAC serviced",
+        "author": "alice",
+    });
+
+    let response = client
+        .post(format!("/iot/data/annotations/{}", tenant_device_id("b")))
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "tenant-b-key"))
+        .body(annotation.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .post(format!("/iot/data/annotations/{}", tenant_device_id("a")))
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "tenant-a-key"))
+        .body(annotation.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+/// Test that the device list only includes devices the caller may read
+#[tokio::test]
+async fn test_device_list_filtered_by_scope() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let own_device = tenant_device_id("a");
+    let other_device = tenant_device_id("b");
+
+    for device_id in [&own_device, &other_device] {
+        let document = serde_json::json!({
+            "id": device_id,
+            "device_id": device_id,
+            "timestamp": 1640995260,
+        });
+        app.app_state.cosmos_client.devices_client
+            .upsert_item(device_id, &document, None)
+            .await
+            .expect("Failed to store device index document");
+    }
+
+    let response = client
+        .get("/iot/devices")
+        .header(Header::new("X-API-Key", "tenant-a-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let devices: Vec<DeviceListing> = response.into_json().await.expect("Invalid device list");
+    assert!(devices.iter().any(|device| device.device_id == own_device));
+    assert!(devices.iter().all(|device| device.device_id.starts_with("test_auth_a_")));
+}
+
+/// Test that fleet-wide endpoints require a key scoped to every device
+#[tokio::test]
+async fn test_fleet_summary_requires_fleet_access() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client
+        .get("/iot/fleet/summary")
+        .header(Header::new("X-API-Key", "tenant-a-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .get("/iot/fleet/summary")
+        .header(Header::new("X-API-Key", "grafana-key"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+/// Test that GraphQL queries are limited to the caller's devices
+/// 
+/// This test verifies that reading another tenant's telemetry fails with
+/// code 403.
+#[tokio::test]
+async fn test_graphql_device_scope() {
+    dotenv().ok();
+
+    let app = TestApp::with_auth_config(test_auth_config()).await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let other_device = tenant_device_id("b");
+
+    let query = format!(
+        r#"{{ telemetry(deviceId: "{}") {{ timestamp }} }}"#,
+        other_device
+    );
+    let response = client
+        .post("/iot/graphql")
+        .header(ContentType::JSON)
+        .header(Header::new("X-API-Key", "tenant-a-key"))
+        .body(serde_json::json!({ "query": query }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let body: serde_json::Value = response.into_json().await.expect("Invalid GraphQL response");
+    assert_eq!(body["errors"][0]["extensions"]["code"], 403);
+    assert_eq!(body["errors"][0]["path"][0], "telemetry");
+}
@@ -0,0 +1,84 @@
+// Batch Read API Integration Tests
+// 
+// This module contains integration tests for the POST /iot/data/read/batch
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test reading two devices with shared parameters in one request
+/// 
+/// This test stores readings of one device and verifies the newest one is
+/// returned for it, narrowed to the requested metric, while a device
+/// without telemetry maps to an empty array.
+#[tokio::test]
+async fn test_read_batch() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let first = format!("test_batch_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let second = format!("test_batch_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (timestamp, temperature) in [(1640995200, "20"), (1640995260, "21")] {
+        let data = [
+            ("temperature".to_string(), temperature.to_string()),
+            ("humidity".to_string(), "45".to_string()),
+        ].into();
+        let telemetry = Telemetry::parse(first.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let body = serde_json::json!({
+        "device_ids": [first, second],
+        "limit": 1,
+        "metrics": "temperature",
+    });
+    let response = client
+        .post("/iot/data/read/batch")
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let results: serde_json::Value = response.into_json().await.expect("Invalid batch response");
+    let records = results[&first].as_array().expect("Device missing from batch");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["timestamp"], 1640995260);
+    assert_eq!(records[0]["telemetry_data"], serde_json::json!({ "temperature": "21" }));
+    assert_eq!(results[&second], serde_json::json!([]));
+}
+
+/// Test batch reads without devices or with invalid shared parameters
+/// 
+/// This test verifies that the API returns 400 Bad Request.
+#[tokio::test]
+async fn test_read_batch_invalid_requests() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    for body in [
+        serde_json::json!({ "device_ids": [] }),
+        serde_json::json!({ "device_ids": [device_id], "limit": 0 }),
+        serde_json::json!({ "device_ids": [device_id], "order": "sideways" }),
+        serde_json::json!({ "device_ids": [device_id], "where": "temperature" }),
+    ] {
+        let response = client
+            .post("/iot/data/read/batch")
+            .header(ContentType::JSON)
+            .body(body.to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest, "{}", body);
+    }
+}
@@ -0,0 +1,77 @@
+// Multi-Device Comparison API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/compare
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test comparing two devices on hourly buckets
+/// 
+/// This test stores samples of two devices in different hours and verifies
+/// the series share the timestamps, with null where a device has no sample.
+#[tokio::test]
+async fn test_compare_devices() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let first = format!("test_compare_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let second = format!("test_compare_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (device_id, timestamp, temperature) in [
+        (&first, 1640995200, "20"),
+        (&first, 1640997000, "24"),
+        (&second, 1640995800, "18"),
+        (&second, 1640998800, "21"),
+    ] {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/compare?devices={},{}&metric=temperature&bucket=1h", first, second))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid comparison");
+
+    assert_eq!(body["fn"], "avg");
+    assert_eq!(body["timestamps"], serde_json::json!([1640995200, 1640998800]));
+    assert_eq!(body["series"], serde_json::json!([
+        { "device_id": first, "values": [22.0, null] },
+        { "device_id": second, "values": [18.0, 21.0] }
+    ]));
+}
+
+/// Test comparing with a device that has no telemetry
+/// 
+/// This test verifies that the API returns 404 Not Found, and 400 Bad
+/// Request without any device.
+#[tokio::test]
+async fn test_compare_unknown_device() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/compare?devices={}&metric=temperature", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client
+        .get("/iot/data/compare?devices=,&metric=temperature")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -0,0 +1,82 @@
+// Response Compression API Integration Tests
+// 
+// This module contains integration tests for the gzip compression of
+// telemetry responses by the device monitoring service.
+
+use crate::helper::TestApp;
+use async_compression::tokio::bufread::GzipDecoder;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+use tokio::io::AsyncReadExt;
+
+/// Stores enough telemetry for a device that its read response is worth compressing
+async fn store_telemetry(app: &TestApp, device_id: &str) {
+    for i in 0..50 {
+        let data = [
+            ("temperature".to_string(), format!("{}", 20 + i % 5)),
+            ("humidity".to_string(), "45".to_string()),
+        ]
+        .into();
+        let telemetry = Telemetry::parse(device_id.to_string(), data, Some(1640995200 + i * 60)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+}
+
+/// Test that telemetry is gzipped for clients that accept it
+/// 
+/// This test verifies the Content-Encoding header and that the decoded
+/// body holds every record.
+#[tokio::test]
+async fn test_read_gzipped() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_gzip_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    store_telemetry(&app, &device_id).await;
+
+    let response = client
+        .get(format!("/iot/data/read/{}", device_id))
+        .header(Header::new("Accept-Encoding", "gzip, deflate, br"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+
+    let compressed = response.into_bytes().await.expect("Missing response body");
+    let mut body = Vec::new();
+    GzipDecoder::new(compressed.as_slice())
+        .read_to_end(&mut body)
+        .await
+        .expect("Invalid gzip body");
+    let telemetry: Vec<Telemetry> = serde_json::from_slice(&body).expect("Invalid telemetry");
+    assert_eq!(telemetry.len(), 50);
+}
+
+/// Test that telemetry is sent uncompressed to clients that do not accept gzip
+#[tokio::test]
+async fn test_read_uncompressed_without_accept_encoding() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_gzip_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    store_telemetry(&app, &device_id).await;
+
+    let response = client
+        .get(format!("/iot/data/read/{}", device_id))
+        .header(Header::new("Accept-Encoding", "gzip;q=0, identity"))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Content-Encoding"), None);
+
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(telemetry.len(), 50);
+}
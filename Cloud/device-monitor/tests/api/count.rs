@@ -0,0 +1,74 @@
+// Telemetry Record Count API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/count/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+use device_monitor::routes::count::TelemetryCount;
+
+/// Test counting a device's records, in full and within a time range
+#[tokio::test]
+async fn test_count_records() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_count_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995200, 1640995260, 1640995320, 1640995380] {
+        let data = [("temperature".to_string(), "21".to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/count/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let count: TelemetryCount = response.into_json().await.expect("Invalid record count");
+    assert_eq!(count.device_id, device_id);
+    assert_eq!(count.count, 4);
+
+    let response = client
+        .get(format!("/iot/data/count/{}?from=1640995260&to=1640995320", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let count: TelemetryCount = response.into_json().await.expect("Invalid record count");
+    assert_eq!((count.from, count.to, count.count), (Some(1640995260), Some(1640995320), 2));
+}
+
+/// Test counting records of an unknown device or with an invalid range
+/// 
+/// This test verifies that an unknown device has a count of 0 and that a
+/// range ending before it starts returns 400 Bad Request.
+#[tokio::test]
+async fn test_count_unknown_device_and_invalid_range() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/count/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let count: TelemetryCount = response.into_json().await.expect("Invalid record count");
+    assert_eq!(count.count, 0);
+
+    let response = client
+        .get(format!("/iot/data/count/{}?from=1641081600&to=1640995200", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -0,0 +1,55 @@
+// Prometheus Device Metrics Integration Tests
+// 
+// This module contains integration tests for the GET /metrics/devices
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test that a device's latest numeric telemetry is exposed as labeled gauges
+#[tokio::test]
+async fn test_device_metrics() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_metrics_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let now = chrono::Utc::now().timestamp();
+
+    let document = serde_json::json!({
+        "id": device_id,
+        "device_id": device_id,
+        "timestamp": now,
+        "heartbeat": false,
+    });
+    app.app_state.cosmos_client.devices_client
+        .upsert_item(&device_id, &document, None)
+        .await
+        .expect("Failed to store device index document");
+
+    for (timestamp, temperature) in [(now - 60, "20.5"), (now, "21.5")] {
+        let data = [
+            ("temperature".to_string(), temperature.to_string()),
+            ("status".to_string(), "ok".to_string()),
+        ]
+        .into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to insert telemetry");
+    }
+
+    let response = client.get("/metrics/devices").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type().map(|t| t.to_string()), Some("text/plain; version=0.0.4; charset=utf-8".to_string()));
+
+    let body = response.into_string().await.expect("Missing metrics");
+    assert!(body.contains("# TYPE device_temperature gauge"));
+    assert!(body.contains(&format!("device_temperature{{device_id=\"{}\"}} 21.5\n", device_id)));
+    assert!(body.contains(&format!("device_last_seen_timestamp_seconds{{device_id=\"{}\"}} {}\n", device_id, now)));
+    assert!(!body.contains("device_status{"));
+}
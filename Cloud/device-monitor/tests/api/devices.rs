@@ -0,0 +1,90 @@
+// Device Discovery API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/devices and
+// GET /iot/devices/<device_id>/status endpoints of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::device::{DeviceHealth, DeviceStatus, DeviceSummary};
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test that a device in the device index is listed with its last-seen time and status
+/// 
+/// The index is normally written by the device-comms service, so the test
+/// writes the document the way that service does.
+#[tokio::test]
+async fn test_list_devices() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_devices_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let document = serde_json::json!({
+        "id": device_id,
+        "device_id": device_id,
+        "timestamp": 1640995260,
+        "gateway_id": "gateway-01",
+        "heartbeat": true,
+    });
+    app.app_state.cosmos_client.devices_client
+        .upsert_item(&device_id, &document, None)
+        .await
+        .expect("Failed to store device index document");
+
+    let response = client.get("/iot/devices").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let devices: Vec<DeviceSummary> = response.into_json().await.expect("Invalid device list");
+    assert!(devices.windows(2).all(|pair| pair[0].device.device_id <= pair[1].device.device_id));
+    let summary = devices.iter().find(|summary| summary.device.device_id == device_id).expect("Device not listed");
+    assert_eq!(summary.device.last_seen, 1640995260);
+    assert_eq!(summary.device.gateway_id.as_deref(), Some("gateway-01"));
+    assert_eq!(summary.status, DeviceStatus::Offline);
+}
+
+/// Test that devices are classified by the age of their latest telemetry
+#[tokio::test]
+async fn test_device_status() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let thresholds = app.app_state.status_thresholds;
+    let now = chrono::Utc::now().timestamp();
+
+    for (age, expected) in [
+        (0, DeviceStatus::Online),
+        (thresholds.stale_after_secs + 60, DeviceStatus::Stale),
+        (thresholds.offline_after_secs + 60, DeviceStatus::Offline),
+    ] {
+        let device_id = format!("test_status_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+        let data = [("temperature".to_string(), "23.5".to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(now - age)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to insert telemetry");
+
+        let response = client.get(format!("/iot/devices/{}/status", device_id)).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let health: DeviceHealth = response.into_json().await.expect("Invalid device status");
+        assert_eq!(health.status, expected);
+        assert_eq!(health.last_seen, now - age);
+    }
+}
+
+/// Test that a device without telemetry has no status
+#[tokio::test]
+async fn test_device_status_not_found() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client.get("/iot/devices/test_status_missing_device/status").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+}
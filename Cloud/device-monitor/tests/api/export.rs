@@ -0,0 +1,149 @@
+// Telemetry Export API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/export/<device_id>.csv
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test exporting a time range of selected metrics as CSV
+/// 
+/// Verifies the content type, the download file name, the column order
+/// given by `metrics` and that records outside the range are left out.
+#[tokio::test]
+async fn test_export_csv() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_export_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995200, 1640995260, 1640995320] {
+        let data = [
+            ("temperature".to_string(), "23.5".to_string()),
+            ("voltage".to_string(), "3.3".to_string()),
+        ].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/export/{}.csv?from=1640995260&metrics=voltage,temperature", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::CSV));
+    assert_eq!(
+        response.headers().get_one("Content-Disposition"),
+        Some(format!("attachment; filename=\"{}.csv\"", device_id).as_str())
+    );
+
+    let csv = response.into_string().await.expect("Missing CSV body");
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines, vec![
+        "timestamp,time,device_id,voltage,temperature",
+        &format!("1640995260,2022-01-01T00:01:00+00:00,{},3.3,23.5", device_id),
+        &format!("1640995320,2022-01-01T00:02:00+00:00,{},3.3,23.5", device_id),
+    ]);
+}
+
+/// Test exporting every metric as CSV when none are selected
+/// 
+/// Verifies that without `metrics` the columns are every metric reported
+/// by any record, sorted by name, even if the first record lacks some.
+#[tokio::test]
+async fn test_export_csv_default_columns() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_export_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (timestamp, metric, value) in [(1640995200, "voltage", "3.3"), (1640995260, "humidity", "45.2")] {
+        let data = [(metric.to_string(), value.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/export/{}.csv", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let csv = response.into_string().await.expect("Missing CSV body");
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines, vec![
+        "timestamp,time,device_id,humidity,voltage",
+        &format!("1640995200,2022-01-01T00:00:00+00:00,{},,3.3", device_id),
+        &format!("1640995260,2022-01-01T00:01:00+00:00,{},45.2,", device_id),
+    ]);
+}
+
+/// Test that unknown devices and unsupported formats return 404
+#[tokio::test]
+async fn test_export_not_found() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_export_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let response = client
+        .get(format!("/iot/data/export/{}.csv", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client
+        .get(format!("/iot/data/export/{}.xlsx", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+/// Test exporting a device's telemetry as Parquet
+/// 
+/// Verifies the content type, the download file name and that the body
+/// is a Parquet file, which starts and ends with the `PAR1` magic bytes.
+#[tokio::test]
+async fn test_export_parquet() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_export_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995200, 1640995260] {
+        let data = [("temperature".to_string(), "23.5".to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/export/{}.parquet", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::new("application", "vnd.apache.parquet")));
+    assert_eq!(
+        response.headers().get_one("Content-Disposition"),
+        Some(format!("attachment; filename=\"{}.parquet\"", device_id).as_str())
+    );
+
+    let file = response.into_bytes().await.expect("Missing Parquet body");
+    assert!(file.starts_with(b"PAR1"));
+    assert!(file.ends_with(b"PAR1"));
+}
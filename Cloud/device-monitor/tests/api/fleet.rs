@@ -0,0 +1,148 @@
+// Fleet Summary API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/fleet/summary
+// and GET /iot/fleet/health endpoints of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::alert::{Alert, Operator};
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test that a recently seen device counts as reporting and its metrics are summarized
+#[tokio::test]
+async fn test_fleet_summary() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_fleet_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let now = chrono::Utc::now().timestamp();
+
+    let document = serde_json::json!({
+        "id": device_id,
+        "device_id": device_id,
+        "timestamp": now,
+        "heartbeat": false,
+    });
+    app.app_state.cosmos_client.devices_client
+        .upsert_item(&device_id, &document, None)
+        .await
+        .expect("Failed to store device index document");
+
+    let data = [("fleet_test_metric".to_string(), "42.5".to_string())].into();
+    let telemetry = Telemetry::parse(device_id.clone(), data, Some(now)).unwrap();
+    app.app_state.cosmos_client
+        .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+        .await
+        .expect("Failed to insert telemetry");
+
+    // A window no other test uses, so the summary is not served from the cache
+    let response = client.get("/iot/fleet/summary?minutes=7").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let summary: serde_json::Value = response.into_json().await.expect("Invalid fleet summary");
+    assert_eq!(summary["window_minutes"], 7);
+    assert!(summary["reporting_devices"].as_u64().unwrap() >= 1);
+    assert!(summary["devices"].as_u64().unwrap() >= summary["reporting_devices"].as_u64().unwrap());
+    let metric = summary["metrics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|metric| metric["metric"] == "fleet_test_metric")
+        .expect("Metric not summarized");
+    assert_eq!(metric["max"], 42.5);
+}
+
+/// Test that a window outside the accepted range is rejected
+#[tokio::test]
+async fn test_fleet_summary_invalid_window() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    for minutes in ["0", "1441"] {
+        let response = client.get(format!("/iot/fleet/summary?minutes={}", minutes)).dispatch().await;
+        assert_eq!(response.status(), Status::BadRequest, "minutes={}", minutes);
+    }
+}
+
+/// Test that an offline device with errors and a breach is scored and ranked
+#[tokio::test]
+async fn test_fleet_health() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_health_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let now = chrono::Utc::now().timestamp();
+    let last_seen = now - 2 * 24 * 3600;
+
+    let document = serde_json::json!({
+        "id": device_id,
+        "device_id": device_id,
+        "timestamp": last_seen,
+        "heartbeat": false,
+    });
+    app.app_state.cosmos_client.devices_client
+        .upsert_item(&device_id, &document, None)
+        .await
+        .expect("Failed to store device index document");
+
+    let data = [("errors".to_string(), "2".to_string())].into();
+    let telemetry = Telemetry::parse(device_id.clone(), data, Some(last_seen)).unwrap();
+    app.app_state.cosmos_client
+        .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+        .await
+        .expect("Failed to insert telemetry");
+
+    let alert = Alert {
+        id: uuid::Uuid::new_v4().to_string(),
+        rule_id: "rule-1".to_string(),
+        device_id: device_id.clone(),
+        metric: "temperature".to_string(),
+        operator: Operator::Gt,
+        threshold: 30.0,
+        value: 31.2,
+        breach_started: now - 600,
+        triggered_at: now - 300,
+        acknowledged: None,
+        resolved: None,
+    };
+    app.app_state.cosmos_client.insert_alert(&alert).await.expect("Failed to store alert");
+
+    // A window no other test uses, so the scores are not served from the cache
+    let response = client.get("/iot/fleet/health?hours=5").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let health: serde_json::Value = response.into_json().await.expect("Invalid fleet health");
+    assert_eq!(health["breach_window_hours"], 5);
+    let scores = health["scores"].as_array().unwrap();
+    let device = scores
+        .iter()
+        .find(|device| device["device_id"] == device_id.as_str())
+        .expect("Device not scored");
+    assert_eq!(device["status"], "offline");
+    assert_eq!(device["errors"], 2.0);
+    assert_eq!(device["breaches"], 1);
+    assert_eq!(device["score"], 100 - 60 - 10 - 10);
+
+    let ranked: Vec<u64> = scores.iter().map(|device| device["score"].as_u64().unwrap()).collect();
+    assert!(ranked.windows(2).all(|pair| pair[0] <= pair[1]), "scores not ranked: {:?}", ranked);
+}
+
+/// Test that a breach window outside the accepted range is rejected
+#[tokio::test]
+async fn test_fleet_health_invalid_window() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    for hours in ["0", "169"] {
+        let response = client.get(format!("/iot/fleet/health?hours={}", hours)).dispatch().await;
+        assert_eq!(response.status(), Status::BadRequest, "hours={}", hours);
+    }
+}
@@ -0,0 +1,72 @@
+// Telemetry Gap Detection API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/gaps/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test finding the silences longer than the reporting interval
+/// 
+/// This test stores readings a minute apart with a half-hour dropout and
+/// verifies the dropout and the silence up to the end of the range are reported.
+#[tokio::test]
+async fn test_gaps_report_dropouts() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_gaps_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995200, 1640995260, 1640995320, 1640997120, 1640997180] {
+        let data = [("temperature".to_string(), "21".to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/gaps/{}?interval=2m&from=1640995200&to=1640998800", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid gap report");
+
+    assert_eq!(body["interval_secs"], 120);
+    assert_eq!(body["records"], 5);
+    assert_eq!(body["gaps"], serde_json::json!([
+        { "start": 1640995320, "end": 1640997120, "duration_secs": 1800 },
+        { "start": 1640997180, "end": 1640998800, "duration_secs": 1620 }
+    ]));
+    assert_eq!(body["total_gap_secs"], 3420);
+    assert_eq!(body["longest_gap_secs"], 1800);
+}
+
+/// Test gap detection with an invalid interval or an unknown device
+/// 
+/// This test verifies that the API returns 400 Bad Request and 404 Not Found.
+#[tokio::test]
+async fn test_gaps_invalid_requests() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/gaps/{}?interval=soon", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+        .get(format!("/iot/data/gaps/{}?interval=5m", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -0,0 +1,109 @@
+// GraphQL API Integration Tests
+// 
+// This module contains integration tests for the /iot/graphql endpoint of
+// the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Sends a GraphQL query and returns the response body
+async fn execute(client: &Client, query: &str, variables: serde_json::Value) -> serde_json::Value {
+    let response = client
+        .post("/iot/graphql")
+        .header(ContentType::JSON)
+        .body(serde_json::json!({ "query": query, "variables": variables }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    response.into_json().await.expect("Invalid GraphQL response")
+}
+
+/// Test reading a device's telemetry and aggregate in one query
+#[tokio::test]
+async fn test_graphql_telemetry_and_aggregate() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_graphql_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (timestamp, temperature) in [(1640995200, "20"), (1640995260, "22"), (1640998800, "30")] {
+        let data = [
+            ("temperature".to_string(), temperature.to_string()),
+            ("humidity".to_string(), "45".to_string()),
+        ]
+        .into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let query = r#"
+        query($deviceId: String!) {
+            telemetry(deviceId: $deviceId, metrics: ["temperature"], limit: 2) {
+                timestamp
+                data
+            }
+            aggregate(deviceId: $deviceId, metric: "temperature", bucket: "1h", function: MAX) {
+                function
+                buckets { start value count }
+            }
+        }
+    "#;
+    let body = execute(client, query, serde_json::json!({ "deviceId": device_id })).await;
+    assert!(body["errors"].is_null(), "{}", body);
+
+    // Newest first, with only the selected metric
+    let telemetry = body["data"]["telemetry"].as_array().unwrap();
+    assert_eq!(telemetry.len(), 2);
+    assert_eq!(telemetry[0]["timestamp"], 1640998800);
+    assert_eq!(telemetry[0]["data"], serde_json::json!({ "temperature": "30" }));
+    assert_eq!(telemetry[1]["timestamp"], 1640995260);
+
+    let aggregate = &body["data"]["aggregate"];
+    assert_eq!(aggregate["function"], "MAX");
+    assert_eq!(aggregate["buckets"], serde_json::json!([
+        { "start": 1640995200, "value": 22.0, "count": 2 },
+        { "start": 1640998800, "value": 30.0, "count": 1 },
+    ]));
+}
+
+/// Test that invalid arguments are reported as errors with the REST status code
+#[tokio::test]
+async fn test_graphql_invalid_arguments() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let query = r#"
+        query($deviceId: String!) {
+            telemetry(deviceId: $deviceId, from: 1640995320, to: 1640995200) { timestamp }
+        }
+    "#;
+    let body = execute(client, query, serde_json::json!({ "deviceId": device_id })).await;
+    assert_eq!(body["errors"][0]["extensions"]["code"], 400);
+
+    // Unknown fields are rejected by validation
+    let body = execute(client, "{ devices { serialNumber } }", serde_json::json!({})).await;
+    assert!(!body["errors"].as_array().unwrap().is_empty());
+}
+
+/// Test that the GraphiQL explorer is served
+#[tokio::test]
+async fn test_graphiql() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client.get("/iot/graphql").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+}
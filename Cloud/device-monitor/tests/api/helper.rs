@@ -9,7 +9,7 @@ use rocket::{
     routes,
 };
 use rocket_cors::{AllowedOrigins, CorsOptions};
-use device_monitor::{app_state::AppState, services::CosmosDbTelemetryStore};
+use device_monitor::{app_state::AppState, domain::auth::AuthConfig, graphql::build_schema, services::CosmosDbTelemetryStore};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Global counter for generating unique test device IDs
@@ -52,7 +52,33 @@ impl TestApp {
     /// - Uses test container: "test-telemetry"
     /// - Uses hardcoded secret key for testing
     /// - Binds to 0.0.0.0:8000
+    /// - Authentication disabled, regardless of the environment
     pub async fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_auth_config(AuthConfig::disabled()).await
+    }
+
+    /// Creates a new test application instance enforcing the given credentials
+    /// 
+    /// # Arguments
+    /// * `auth` - The API keys and JWT secret the test app accepts
+    /// 
+    /// # Returns
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The configured test app or an error
+    pub async fn with_auth_config(auth: AuthConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_state(|state| state.with_auth_config(auth)).await
+    }
+
+    /// Creates a new test application instance with customised application state
+    /// 
+    /// # Arguments
+    /// * `configure` - Adjusts the application state before the server is built
+    /// 
+    /// # Returns
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The configured test app or an error
+    pub async fn with_state<F>(configure: F) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        F: FnOnce(AppState) -> AppState,
+    {
         // Create test cosmos client with test database/container names
         // In a real test environment, you might want to use separate test resources
         let cosmos_client = CosmosDbTelemetryStore::new(
@@ -61,7 +87,7 @@ impl TestApp {
         ).await?;
         
         // Create application state with the test database client
-        let app_state = AppState::new(cosmos_client);
+        let app_state = configure(AppState::new(cosmos_client));
 
         // Configure CORS for test requests (allows all origins for testing)
         let cors = CorsOptions {
@@ -76,10 +102,51 @@ impl TestApp {
                 // Use a hardcoded secret key for testing (64 hex characters)
                 .merge(("secret_key", "abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890"))
                 .merge(("address", "0.0.0.0")))
+            .manage(build_schema(app_state.clone())) // Inject the GraphQL schema
             .manage(app_state.clone()) // Inject the test application state
             .attach(cors) // Enable CORS for test requests
+            .attach(device_monitor::utils::compression::Compression) // Gzip responses as in production
             .mount("/iot/data", routes![
                 device_monitor::routes::read_telemetry::read,
+                device_monitor::routes::batch_read::read_batch_route,
+                device_monitor::routes::latest_telemetry::latest_telemetry_route,
+                device_monitor::routes::aggregate_by_tag::aggregate_by_tag_route,
+                device_monitor::routes::aggregate::aggregate_route,
+                device_monitor::routes::compare::compare_route,
+                device_monitor::routes::anomalies::anomalies_route,
+                device_monitor::routes::gaps::gaps_route,
+                device_monitor::routes::stats::stats_route,
+                device_monitor::routes::histogram::histogram_route,
+                device_monitor::routes::rate::rate_route,
+                device_monitor::routes::count::count_route,
+                device_monitor::routes::archives::list_archives_route,
+                device_monitor::routes::export::export_route,
+                device_monitor::routes::stream_telemetry::stream_telemetry_route,
+                device_monitor::routes::subscribe_telemetry::subscribe_telemetry_route,
+                device_monitor::routes::annotations::create_annotation_route,
+                device_monitor::routes::annotations::list_annotations_route,
+                device_monitor::routes::annotations::update_annotation_route,
+                device_monitor::routes::annotations::delete_annotation_route,
+                device_monitor::routes::alerts::create_alert_rule_route,
+                device_monitor::routes::alerts::list_alert_rules_route,
+                device_monitor::routes::alerts::update_alert_rule_route,
+                device_monitor::routes::alerts::delete_alert_rule_route,
+                device_monitor::routes::alerts::list_alerts_route,
+                device_monitor::routes::alerts::acknowledge_alert_route,
+                device_monitor::routes::alerts::resolve_alert_route,
+            ])
+            .mount("/iot", routes![
+                device_monitor::routes::devices::list_devices_route,
+                device_monitor::routes::devices::device_status_route,
+                device_monitor::routes::fleet_summary::fleet_summary_route,
+                device_monitor::routes::fleet_health::fleet_health_route,
+                device_monitor::routes::uptime::uptime_route,
+                device_monitor::routes::graphql::graphql_route,
+                device_monitor::routes::graphql::graphiql_route,
+                device_monitor::routes::retention::retention_route,
+            ])
+            .mount("/metrics", routes![
+                device_monitor::routes::device_metrics::device_metrics_route,
             ]);
 
         // Create a tracked client for making requests to the test server
@@ -0,0 +1,68 @@
+// Metric Histogram API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/histogram/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test binning a bimodal metric
+/// 
+/// This test stores readings around two levels and verifies both peaks,
+/// and the empty bins between them, are returned.
+#[tokio::test]
+async fn test_histogram_bimodal_metric() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_histogram_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (i, voltage) in ["1.1", "1.2", "1.4", "4.6", "4.8", "4.9"].into_iter().enumerate() {
+        let data = [("voltage".to_string(), voltage.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200 + i as i64 * 60)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/histogram/{}?metric=voltage&width=1", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid histogram");
+
+    assert_eq!(body["count"], 6);
+    assert_eq!(body["bin_width"], 1.0);
+    let counts: Vec<u64> = body["bins"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|bin| bin["count"].as_u64().unwrap())
+        .collect();
+    assert_eq!(counts, vec![3, 0, 0, 3]);
+    assert_eq!(body["bins"][0]["lower"], 1.0);
+}
+
+/// Test a histogram with conflicting binning parameters
+/// 
+/// This test verifies that the API returns 400 Bad Request.
+#[tokio::test]
+async fn test_histogram_invalid_binning() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/histogram/{}?metric=voltage&width=1&bins=10", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
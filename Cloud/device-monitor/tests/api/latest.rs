@@ -0,0 +1,118 @@
+// Latest Telemetry API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/latest/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test that only the record with the latest timestamp is returned
+/// 
+/// Records are stored out of order to verify the result is chosen by
+/// timestamp rather than insertion order.
+#[tokio::test]
+async fn test_latest_telemetry() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_latest_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995260, 1640995320, 1640995200] {
+        let data = [("temperature".to_string(), timestamp.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/latest/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let latest: Telemetry = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(latest.timestamp, Some(1640995320));
+    assert_eq!(latest.telemetry_data["temperature"], "1640995320");
+}
+
+/// Test converting the latest temperature to Kelvin
+#[tokio::test]
+async fn test_latest_telemetry_unit_conversion() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_latest_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let data = [("temperature".to_string(), "21.5".to_string())].into();
+    let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200)).unwrap();
+    app.app_state.cosmos_client
+        .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+        .await
+        .expect("Failed to store telemetry");
+
+    let response = client
+        .get(format!("/iot/data/latest/{}?unit=kelvin", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let latest: Telemetry = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(latest.telemetry_data["temperature"], "294.65");
+}
+
+/// Test revalidating the latest telemetry with its ETag
+/// 
+/// This test verifies that sending the ETag back returns 304 Not Modified.
+#[tokio::test]
+async fn test_latest_telemetry_not_modified() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_latest_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let data = [("temperature".to_string(), "21.5".to_string())].into();
+    let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200)).unwrap();
+    app.app_state.cosmos_client
+        .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+        .await
+        .expect("Failed to store telemetry");
+
+    let response = client
+        .get(format!("/iot/data/latest/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").expect("Missing ETag").to_string();
+
+    let response = client
+        .get(format!("/iot/data/latest/{}", device_id))
+        .header(Header::new("If-None-Match", etag))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotModified);
+    assert!(response.into_bytes().await.unwrap_or_default().is_empty());
+}
+
+/// Test the latest telemetry of a device without telemetry
+/// 
+/// This test verifies that the API returns 404 Not Found.
+#[tokio::test]
+async fn test_latest_telemetry_nonexistent_device() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/latest/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -4,4 +4,29 @@
 // Tests are organized by functionality and use helper utilities for common operations.
 
 mod helper;
-mod read;
\ No newline at end of file
+mod read;
+mod aggregate_by_tag;
+mod annotations;
+mod latest;
+mod aggregate;
+mod export;
+mod stream;
+mod subscribe;
+mod devices;
+mod fleet;
+mod alerts;
+mod anomalies;
+
+mod graphql;mod retention;
+mod device_metrics;
+mod compare;
+mod gaps;
+mod stats;
+mod histogram;
+mod rate;
+mod auth;
+mod count;
+mod compression;
+mod archives;
+mod uptime;
+mod batch;
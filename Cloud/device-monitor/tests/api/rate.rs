@@ -0,0 +1,64 @@
+// Rate of Change API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/rate/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test the per-minute rate of a rising temperature
+/// 
+/// This test stores readings with different spacing and verifies each rate
+/// accounts for the time between readings.
+#[tokio::test]
+async fn test_rate_per_minute() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_rate_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (timestamp, temperature) in [(1640995200, "20"), (1640995260, "21"), (1640995380, "25")] {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/rate/{}?metric=temperature", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid rate series");
+
+    assert_eq!(body["per_secs"], 60);
+    assert_eq!(body["smoothing"]["type"], "none");
+    assert_eq!(body["points"], serde_json::json!([
+        { "timestamp": 1640995260, "rate": 1.0 },
+        { "timestamp": 1640995380, "rate": 2.0 }
+    ]));
+}
+
+/// Test a rate request with unknown smoothing
+/// 
+/// This test verifies that the API returns 400 Bad Request.
+#[tokio::test]
+async fn test_rate_invalid_smoothing() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    let response = client
+        .get(format!("/iot/data/rate/{}?metric=temperature&smoothing=median", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -5,9 +5,10 @@
 // error cases, edge cases, and invalid inputs.
 
 use crate::helper::TestApp;
-use rocket::http::Status;
+use rocket::http::{Header, Status};
 use rocket::local::asynchronous::Client;
 use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
 
 /// Test reading telemetry for a device that doesn't exist in the database
 /// 
@@ -315,3 +316,483 @@ async fn test_read_malformed_urls() {
         .await;
     assert_eq!(response.status(), Status::NotFound);
 }
+
+/// Test paging through a device's telemetry with a continuation token
+/// 
+/// This test stores three records and verifies that pages of two are
+/// returned oldest first with `order=asc`, that the first page carries a continuation token
+/// in the `X-Continuation-Token` header, and that the last page does not.
+#[tokio::test]
+async fn test_read_paged_with_continuation() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_paging_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    // Store three records, newest first, to check they are returned by timestamp
+    for timestamp in [1640995320, 1640995260, 1640995200] {
+        let data = [("temperature".to_string(), timestamp.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?limit=2&order=asc", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let token = response.headers().get_one("X-Continuation-Token").map(str::to_string);
+    let first: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    let timestamps: Vec<Option<i64>> = first.iter().map(|t| t.timestamp).collect();
+    assert_eq!(timestamps, vec![Some(1640995200), Some(1640995260)]);
+    let token = token.expect("First page should carry a continuation token");
+
+    let response = client
+        .get(format!("/iot/data/read/{}?limit=2&order=asc&continuation={}", device_id, token))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.headers().get_one("X-Continuation-Token").is_none());
+    let last: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(last.len(), 1);
+    assert_eq!(last[0].timestamp, Some(1640995320));
+
+    // A malformed token is rejected
+    let response = client
+        .get(format!("/iot/data/read/{}?continuation=bogus", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test restricting a telemetry read to a time window
+/// 
+/// This test verifies that only records within the inclusive `from`/`to`
+/// window are returned, that a window without records returns an empty
+/// array, and that a window ending before it starts is rejected.
+#[tokio::test]
+async fn test_read_time_range() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_range_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995200, 1640995260, 1640995320] {
+        let data = [("temperature".to_string(), timestamp.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?from=1640995260&to=1640995320&order=asc", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    let timestamps: Vec<Option<i64>> = telemetry.iter().map(|t| t.timestamp).collect();
+    assert_eq!(timestamps, vec![Some(1640995260), Some(1640995320)]);
+
+    let response = client
+        .get(format!("/iot/data/read/{}?from=1700000000", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert!(telemetry.is_empty());
+
+    let response = client
+        .get(format!("/iot/data/read/{}?from=1640995320&to=1640995200", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test downsampling a metric's series to a number of points
+/// 
+/// This test verifies that `metric` and `points` reduce the series with
+/// LTTB while keeping its first and last records, still returned newest
+/// first by default, and that `points`
+/// without a metric or combined with paging is rejected.
+#[tokio::test]
+async fn test_read_downsampled() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_lttb_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for offset in 0..10 {
+        let timestamp = 1640995200 + offset * 60;
+        let data = [("temperature".to_string(), (offset * offset).to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?metric=temperature&points=3", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    let timestamps: Vec<Option<i64>> = telemetry.iter().map(|t| t.timestamp).collect();
+    assert_eq!(timestamps.len(), 3);
+    assert_eq!(timestamps.first(), Some(&Some(1640995740)));
+    assert_eq!(timestamps.last(), Some(&Some(1640995200)));
+
+    let response = client
+        .get(format!("/iot/data/read/{}?points=500", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+        .get(format!("/iot/data/read/{}?metric=temperature&points=500&limit=10", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test reading only some metrics of each record
+/// 
+/// This test verifies that only the requested keys of `telemetry_data` are
+/// returned and that records reporting none of them are left out.
+#[tokio::test]
+async fn test_read_selected_metrics() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_metrics_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let records = [
+        (1640995200, vec![("temperature", "23.5"), ("humidity", "45.2"), ("voltage", "3.3")]),
+        (1640995260, vec![("status", "online")]),
+    ];
+    for (timestamp, data) in records {
+        let data = data.into_iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?metrics=temperature,voltage", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(telemetry.len(), 1);
+    let mut metrics: Vec<&String> = telemetry[0].telemetry_data.keys().collect();
+    metrics.sort();
+    assert_eq!(metrics, vec!["temperature", "voltage"]);
+}
+
+/// Test the order in which telemetry records are returned
+/// 
+/// This test verifies that records are returned newest first by default,
+/// that pages follow the requested order, and that an unknown order is
+/// rejected.
+#[tokio::test]
+async fn test_read_sort_order() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_order_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995260, 1640995200, 1640995320] {
+        let data = [("temperature".to_string(), timestamp.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    for (query, expected) in [
+        ("", vec![Some(1640995320), Some(1640995260), Some(1640995200)]),
+        ("?order=desc", vec![Some(1640995320), Some(1640995260), Some(1640995200)]),
+        ("?order=asc", vec![Some(1640995200), Some(1640995260), Some(1640995320)]),
+        ("?order=desc&limit=2", vec![Some(1640995320), Some(1640995260)]),
+    ] {
+        let response = client
+            .get(format!("/iot/data/read/{}{}", device_id, query))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+        let timestamps: Vec<Option<i64>> = telemetry.iter().map(|t| t.timestamp).collect();
+        assert_eq!(timestamps, expected, "{}", query);
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?order=newest", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test that repeated reads are served from the query cache until it is invalidated
+/// 
+/// This test verifies that a record stored after a read does not show up in
+/// an identical read, and does once the device's cached queries are dropped,
+/// as happens when the device index shows the device stored new telemetry.
+#[tokio::test]
+async fn test_read_cached_until_invalidated() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_cache_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for timestamp in [1640995200, 1640995260] {
+        let data = [("temperature".to_string(), timestamp.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+
+        let response = client
+            .get(format!("/iot/data/read/{}", device_id))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+        assert_eq!(telemetry.len(), 1);
+    }
+
+    app.app_state.query_cache.invalidate(&device_id);
+
+    let response = client
+        .get(format!("/iot/data/read/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(telemetry.len(), 2);
+}
+
+/// Test that a full history is streamed as a complete JSON array
+/// 
+/// This test verifies that an unpaged read of more records than a single
+/// Cosmos DB page typically holds returns every record, in order, as valid
+/// JSON, with only the requested metrics.
+#[tokio::test]
+async fn test_read_streams_full_history() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_stream_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for i in 0..150 {
+        let data = [
+            ("temperature".to_string(), i.to_string()),
+            ("humidity".to_string(), "45".to_string()),
+        ]
+        .into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200 + i * 60)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?metrics=temperature", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(rocket::http::ContentType::JSON));
+
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(telemetry.len(), 150);
+    assert_eq!(telemetry[0].timestamp, Some(1640995200 + 149 * 60));
+    assert_eq!(telemetry[149].timestamp, Some(1640995200));
+    assert!(telemetry.iter().all(|record| record.telemetry_data.len() == 1));
+}
+
+/// Test converting metric values to other units
+/// 
+/// This test verifies that temperatures and voltages are converted in both
+/// streamed and paged reads, that metrics without a unit are unchanged, and
+/// that an unknown unit is rejected.
+#[tokio::test]
+async fn test_read_unit_conversion() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_unit_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let data = [
+        ("temperature".to_string(), "20".to_string()),
+        ("voltage".to_string(), "3.3".to_string()),
+        ("humidity".to_string(), "45.2".to_string()),
+    ]
+    .into();
+    let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200)).unwrap();
+    app.app_state.cosmos_client
+        .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+        .await
+        .expect("Failed to store telemetry");
+
+    for query in ["unit=fahrenheit,mv", "unit=fahrenheit,mv&limit=10"] {
+        let response = client
+            .get(format!("/iot/data/read/{}?{}", device_id, query))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+        assert_eq!(telemetry[0].telemetry_data["temperature"], "68");
+        assert_eq!(telemetry[0].telemetry_data["voltage"], "3300");
+        assert_eq!(telemetry[0].telemetry_data["humidity"], "45.2");
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?unit=furlong", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+/// Test conditional reads with If-None-Match
+/// 
+/// This test verifies that a read carries a weak ETag, that sending it
+/// back returns 304 Not Modified, and that new telemetry changes the tag.
+#[tokio::test]
+async fn test_read_conditional_etag() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_etag_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let store = |timestamp: i64| {
+        let data = [("temperature".to_string(), "21".to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        serde_json::to_value(&telemetry).unwrap()
+    };
+    app.app_state.cosmos_client.insert_telemetry(&store(1640995200)).await.expect("Failed to store telemetry");
+
+    let response = client
+        .get(format!("/iot/data/read/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").expect("Missing ETag").to_string();
+    assert!(etag.starts_with("W/"));
+
+    let response = client
+        .get(format!("/iot/data/read/{}", device_id))
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some(etag.as_str()));
+
+    // A newer record changes the tag, so the full response is sent again
+    app.app_state.cosmos_client.insert_telemetry(&store(1640995260)).await.expect("Failed to store telemetry");
+    let response = client
+        .get(format!("/iot/data/read/{}", device_id))
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_ne!(response.headers().get_one("ETag"), Some(etag.as_str()));
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(telemetry.len(), 2);
+}
+
+/// Test reading only the readings that breached a limit
+/// 
+/// This test verifies that `where` keeps the records meeting every
+/// condition, streamed or paged, and that unsafe conditions are rejected.
+#[tokio::test]
+async fn test_read_where_filter() {
+    // Load environment variables for test configuration
+    dotenv().ok();
+    
+    // Create test application instance
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_where_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (i, (temperature, humidity)) in [("25", "40"), ("31.5", "35"), ("35", "60"), ("n/a", "30")].iter().enumerate() {
+        let data = [
+            ("temperature".to_string(), temperature.to_string()),
+            ("humidity".to_string(), humidity.to_string()),
+        ]
+        .into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200 + i as i64 * 60)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/read/{}?where=temperature%3E30&order=asc", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    let timestamps: Vec<_> = telemetry.iter().filter_map(|record| record.timestamp).collect();
+    assert_eq!(timestamps, vec![1640995260, 1640995320]);
+
+    let response = client
+        .get(format!("/iot/data/read/{}?where=temperature%3E30,humidity%3C50&limit=10", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert_eq!(telemetry.len(), 1);
+    assert_eq!(telemetry[0].telemetry_data["temperature"], "31.5");
+
+    // No breach is an empty result, not a missing device
+    let response = client
+        .get(format!("/iot/data/read/{}?where=temperature%3E100", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let telemetry: Vec<Telemetry> = response.into_json().await.expect("Invalid telemetry");
+    assert!(telemetry.is_empty());
+
+    let response = client
+        .get(format!("/iot/data/read/{}?where=temperature%3E30%20OR%201%3D1", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -0,0 +1,95 @@
+// Retention API Integration Tests
+// 
+// This module contains integration tests for the telemetry retention
+// cleanup and the GET /iot/retention endpoint of the device monitoring
+// service.
+
+use std::time::Duration;
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::retention::RetentionPolicy;
+use device_monitor::domain::sort_order::SortOrder;
+use device_monitor::domain::telemetry::Telemetry;
+use device_monitor::domain::time_range::TimeRange;
+use device_monitor::domain::value_filter::ValueFilter;
+use device_monitor::services::RetentionJob;
+
+/// Test that a cleanup run deletes only telemetry older than the device's retention period
+#[tokio::test]
+async fn test_retention_cleanup() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let device_id = format!("test_retention_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let now = chrono::Utc::now().timestamp();
+
+    let document = serde_json::json!({
+        "id": device_id,
+        "device_id": device_id,
+        "timestamp": now,
+        "heartbeat": false,
+    });
+    app.app_state.cosmos_client.devices_client
+        .upsert_item(&device_id, &document, None)
+        .await
+        .expect("Failed to store device index document");
+
+    for timestamp in [now - 10 * 24 * 60 * 60, now] {
+        let data = [("temperature".to_string(), "21.5".to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to insert telemetry");
+    }
+
+    // Only the test device has a retention period, so other devices keep their telemetry
+    let policy = RetentionPolicy {
+        default_days: None,
+        device_days: [(device_id.clone(), 7)].into(),
+    };
+    let job = RetentionJob::new(
+        app.app_state.cosmos_client.clone(),
+        app.app_state.query_cache.clone(),
+        policy,
+        Duration::from_secs(3600),
+    );
+
+    let run = job.run_once().await.expect("Failed to run retention cleanup");
+    assert_eq!(run.devices_checked, 1);
+    assert_eq!(run.records_deleted, 1);
+    assert_eq!(run.devices[0].device_id, device_id);
+
+    let remaining = app.app_state.cosmos_client
+        .query_telemetry(&device_id, &TimeRange::default(), &ValueFilter::NONE, SortOrder::Asc, None)
+        .await
+        .expect("Failed to read telemetry");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].timestamp, Some(now));
+
+    // A second run has nothing left to delete
+    let run = job.run_once().await.expect("Failed to run retention cleanup");
+    assert_eq!(run.records_deleted, 0);
+    let status = job.status();
+    assert_eq!(status.runs, 2);
+    assert_eq!(status.total_records_deleted, 1);
+}
+
+/// Test that the retention status is served
+#[tokio::test]
+async fn test_retention_status() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client.get("/iot/retention").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let status: serde_json::Value = response.into_json().await.expect("Invalid retention status");
+    assert!(status["interval_secs"].as_u64().unwrap() > 0);
+    assert!(status["policy"]["device_days"].is_object());
+}
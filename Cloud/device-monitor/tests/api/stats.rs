@@ -0,0 +1,90 @@
+// Metric Statistics API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/stats/<device_id>
+// endpoint of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::Status;
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Test summarizing a device's metric within a time range
+/// 
+/// This test stores samples inside and outside the range, and a
+/// non-numeric one, and verifies only the numeric samples in range count.
+#[tokio::test]
+async fn test_stats_summarize_metric() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_stats_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    for (timestamp, temperature) in [
+        (1640995200, "10"),
+        (1640995260, "20"),
+        (1640995320, "n/a"),
+        (1640995380, "30"),
+        (1640995440, "40"),
+        (1640995500, "50"),
+        (1640999000, "1000"),
+    ] {
+        let data = [("temperature".to_string(), temperature.to_string())].into();
+        let telemetry = Telemetry::parse(device_id.clone(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+
+    let response = client
+        .get(format!("/iot/data/stats/{}?metric=temperature&from=1640995200&to=1640995500", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid statistics");
+
+    assert_eq!(body["count"], 5);
+    assert_eq!(body["mean"], 30.0);
+    assert_eq!(body["min"], 10.0);
+    assert_eq!(body["max"], 50.0);
+    assert_eq!(body["p50"], 30.0);
+    assert_eq!(body["p95"], 48.0);
+    assert_eq!(body["latest"], 50.0);
+}
+
+/// Test statistics of a metric the device never reported, and of an unknown device
+/// 
+/// This test verifies that a missing metric has a count of 0 and null
+/// statistics, and that an unknown device returns 404 Not Found.
+#[tokio::test]
+async fn test_stats_without_samples() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_stats_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    let data = [("temperature".to_string(), "21".to_string())].into();
+    let telemetry = Telemetry::parse(device_id.clone(), data, Some(1640995200)).unwrap();
+    app.app_state.cosmos_client
+        .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+        .await
+        .expect("Failed to store telemetry");
+
+    let response = client
+        .get(format!("/iot/data/stats/{}?metric=humidity", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid statistics");
+    assert_eq!(body["count"], 0);
+    assert!(body["mean"].is_null());
+
+    let response = client
+        .get(format!("/iot/data/stats/{}?metric=temperature", app.generate_test_device_id()))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
@@ -0,0 +1,62 @@
+// Live Telemetry Stream API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/data/stream/<device_id>
+// server-sent events endpoint of the device monitoring service.
+
+use std::time::Duration;
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use rocket::tokio::io::AsyncReadExt;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Stores a telemetry record with a single `status` value
+async fn store(app: &TestApp, device_id: &str, timestamp: i64, status: &str) {
+    let data = [("status".to_string(), status.to_string())].into();
+    let telemetry = Telemetry::parse(device_id.to_string(), data, Some(timestamp)).unwrap();
+    app.app_state.cosmos_client
+        .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+        .await
+        .expect("Failed to store telemetry");
+}
+
+/// Test that a record stored after the stream was opened is pushed as an event
+/// 
+/// A record stored before the stream was opened must not be sent.
+#[tokio::test]
+async fn test_stream_pushes_new_telemetry() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_stream_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+
+    store(&app, &device_id, 1640995200, "stored-before").await;
+
+    let mut response = client
+        .get(format!("/iot/data/stream/{}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::EventStream));
+
+    store(&app, &device_id, 1640995260, "stored-after").await;
+
+    // Read the stream until the new record arrives; it is polled every few seconds
+    let mut received = String::new();
+    let read = rocket::tokio::time::timeout(Duration::from_secs(20), async {
+        let mut buffer = [0; 1024];
+        while !received.contains("stored-after") {
+            let read = response.read(&mut buffer).await.expect("Failed to read stream");
+            assert!(read > 0, "Stream ended early");
+            received.push_str(&String::from_utf8_lossy(&buffer[..read]));
+        }
+    })
+    .await;
+
+    assert!(read.is_ok(), "No event received, got: {}", received);
+    assert!(received.contains("event: telemetry"));
+    assert!(!received.contains("stored-before"));
+}
@@ -0,0 +1,55 @@
+// WebSocket Subscription API Integration Tests
+// 
+// This module contains integration tests for the opening handshake of the
+// GET /iot/data/ws endpoint of the device monitoring service. The local
+// test client does not upgrade connections, so the subscription protocol
+// itself is covered by the unit tests of the subscription domain module.
+
+use crate::helper::TestApp;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+
+/// Test that a WebSocket handshake is accepted with the key derived per RFC 6455
+#[tokio::test]
+async fn test_subscribe_handshake() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    // The sample handshake from RFC 6455, section 1.3
+    let response = client
+        .get("/iot/data/ws")
+        .header(Header::new("Connection", "Upgrade"))
+        .header(Header::new("Upgrade", "websocket"))
+        .header(Header::new("Sec-WebSocket-Version", "13"))
+        .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+        .dispatch()
+        .await;
+    assert_eq!(
+        response.headers().get_one("Sec-WebSocket-Accept"),
+        Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")
+    );
+}
+
+/// Test that requests that are not WebSocket upgrades are rejected
+#[tokio::test]
+async fn test_subscribe_requires_upgrade() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+
+    let response = client.get("/iot/data/ws").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+        .get("/iot/data/ws")
+        .header(Header::new("Upgrade", "websocket"))
+        .header(Header::new("Sec-WebSocket-Version", "8"))
+        .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
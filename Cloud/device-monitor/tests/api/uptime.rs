@@ -0,0 +1,102 @@
+// Uptime Report API Integration Tests
+// 
+// This module contains integration tests for the GET /iot/uptime endpoint
+// of the device monitoring service.
+
+use crate::helper::TestApp;
+use rocket::http::{ContentType, Status};
+use rocket::local::asynchronous::Client;
+use dotenvy::dotenv;
+use device_monitor::domain::telemetry::Telemetry;
+
+/// Stores a device index document and readings a minute apart with a half-hour dropout
+async fn store_device_with_dropout(app: &TestApp, device_id: &str) {
+    let document = serde_json::json!({
+        "id": device_id,
+        "device_id": device_id,
+        "timestamp": 1640997180,
+        "heartbeat": false,
+    });
+    app.app_state.cosmos_client.devices_client
+        .upsert_item(device_id, &document, None)
+        .await
+        .expect("Failed to store device index document");
+
+    for timestamp in [1640995200, 1640995260, 1640995320, 1640997120, 1640997180] {
+        let data = [("temperature".to_string(), "21".to_string())].into();
+        let telemetry = Telemetry::parse(device_id.to_string(), data, Some(timestamp)).unwrap();
+        app.app_state.cosmos_client
+            .insert_telemetry(&serde_json::to_value(&telemetry).unwrap())
+            .await
+            .expect("Failed to store telemetry");
+    }
+}
+
+/// Test that a device's availability is derived from its dropouts
+/// 
+/// The dropout and the silence up to the end of the hour are down beyond
+/// the first two minutes of each, so the device was up 420 of 3600 seconds.
+#[tokio::test]
+async fn test_uptime_report() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = format!("test_uptime_{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    store_device_with_dropout(&app, &device_id).await;
+
+    let response = client
+        .get(format!("/iot/uptime?interval=2m&from=1640995200&to=1640998800&devices={}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: serde_json::Value = response.into_json().await.expect("Invalid uptime report");
+
+    assert_eq!(body["interval_secs"], 120);
+    assert_eq!(body["availability_percent"], 11.667);
+    assert_eq!(body["devices"], serde_json::json!([
+        { "device_id": device_id, "records": 5, "downtime_secs": 3180, "availability_percent": 11.667 }
+    ]));
+
+    let response = client
+        .get(format!("/iot/uptime?interval=2m&from=1640995200&to=1640998800&devices={}&format=csv", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::CSV));
+    assert_eq!(
+        response.into_string().await.unwrap(),
+        format!(
+            "device_id,from,to,interval_secs,records,downtime_secs,availability_percent\r\n\
+             {},1640995200,1640998800,120,5,3180,11.667\r\n",
+            device_id
+        )
+    );
+}
+
+/// Test uptime reports with an invalid period or format, or an unknown device
+/// 
+/// This test verifies that the API returns 400 Bad Request and 404 Not Found.
+#[tokio::test]
+async fn test_uptime_invalid_requests() {
+    dotenv().ok();
+
+    let app = TestApp::new().await.expect("Failed to create test app");
+    let client: &Client = &app.client;
+    let device_id = app.generate_test_device_id();
+
+    for query in [
+        "interval=5&from=1640995200",
+        "interval=2m&from=1640998800&to=1640995200",
+        "interval=2m&from=1640995200&format=xml",
+    ] {
+        let response = client.get(format!("/iot/uptime?{}", query)).dispatch().await;
+        assert_eq!(response.status(), Status::BadRequest, "{}", query);
+    }
+
+    let response = client
+        .get(format!("/iot/uptime?interval=2m&from=1640995200&devices={}", device_id))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::NotFound);
+}
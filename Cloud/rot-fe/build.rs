@@ -34,7 +34,18 @@ fn main() {
         }
     };
 
+    // Azure AD sign-in settings; without a client ID, sign-in is disabled
+    let auth_client_id = env::var("ROT_AUTH_CLIENT_ID").unwrap_or_default();
+    let auth_tenant_id = env::var("ROT_AUTH_TENANT_ID").unwrap_or_else(|_| "organizations".to_string());
+    let auth_scope = env::var("ROT_AUTH_SCOPE").unwrap_or_else(|_| "openid profile offline_access".to_string());
+    if auth_client_id.is_empty() {
+        println!("cargo:warning=ROT_AUTH_CLIENT_ID not set. Sign-in is disabled.");
+    }
+
     // Pass to the compiler
     println!("cargo:rustc-env=ROT_API_URL={}", api_url);
     println!("cargo:rustc-env=ROT_DC_URL={}", dc_url);
+    println!("cargo:rustc-env=ROT_AUTH_CLIENT_ID={}", auth_client_id);
+    println!("cargo:rustc-env=ROT_AUTH_TENANT_ID={}", auth_tenant_id);
+    println!("cargo:rustc-env=ROT_AUTH_SCOPE={}", auth_scope);
 } 
\ No newline at end of file
@@ -0,0 +1,29 @@
+#!/bin/sh
+# Write config.json
+#
+# Runs from the nginx image's /docker-entrypoint.d at container start and
+# writes the API base URLs from ROT_API_URL and ROT_DC_URL and the sign-in
+# settings from ROT_AUTH_CLIENT_ID, ROT_AUTH_TENANT_ID and ROT_AUTH_SCOPE to
+# config.json, which the frontend reads on startup. Unset variables are
+# written as null, so the frontend keeps the settings it was built with.
+set -eu
+
+json_value() {
+    if [ -n "$1" ]; then
+        printf '"%s"' "$(printf '%s' "$1" | sed 's/\\/\\\\/g; s/"/\\"/g')"
+    else
+        printf 'null'
+    fi
+}
+
+cat > /usr/share/nginx/html/config.json <<EOF
+{
+  "apiUrl": $(json_value "${ROT_API_URL:-}"),
+  "dcUrl": $(json_value "${ROT_DC_URL:-}"),
+  "authClientId": $(json_value "${ROT_AUTH_CLIENT_ID:-}"),
+  "authTenantId": $(json_value "${ROT_AUTH_TENANT_ID:-}"),
+  "authScope": $(json_value "${ROT_AUTH_SCOPE:-}")
+}
+EOF
+
+echo "Wrote config.json with ROT_API_URL=${ROT_API_URL:-<unset>} ROT_DC_URL=${ROT_DC_URL:-<unset>} ROT_AUTH_CLIENT_ID=${ROT_AUTH_CLIENT_ID:-<unset>}"
@@ -0,0 +1,127 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import range formatting for the selection summary
+use crate::domain::annotation::format_range;
+
+/// Longest note the annotations API accepts, in characters
+const MAX_TEXT_LEN: usize = 1000;
+
+/// Properties for the AnnotationForm component
+/// - range: Start and end of the selected time range (Unix timestamps)
+/// - saving: Whether the annotation is being saved
+/// - error: Error returned by the last save attempt, if any
+/// - on_save: Callback emitting the note text and author
+/// - on_cancel: Callback for discarding the selection
+#[derive(Properties, PartialEq)]
+pub struct AnnotationFormProps {
+    pub range: (i64, i64),
+    pub saving: bool,
+    #[prop_or_default]
+    pub error: Option<String>,
+    pub on_save: Callback<(String, String)>,
+    pub on_cancel: Callback<()>,
+}
+
+/// AnnotationForm component shown over a chart after a time range is selected
+/// Collects the note and author for a new annotation
+#[function_component(AnnotationForm)]
+pub fn annotation_form(props: &AnnotationFormProps) -> Html {
+    // Note typed into the text area
+    let text = use_state(String::new);
+    // Author typed into the name field
+    let author = use_state(String::new);
+
+    // Callback for typing the note
+    let on_text_input = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlTextAreaElement = e.target_unchecked_into();
+            text.set(input.value());
+        })
+    };
+
+    // Callback for typing the author
+    let on_author_input = {
+        let author = author.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            author.set(input.value());
+        })
+    };
+
+    // Callback for the "Save" button
+    // Emits the note and author once both are filled in
+    let on_save_click = {
+        let text = text.clone();
+        let author = author.clone();
+        let on_save = props.on_save.clone();
+        Callback::from(move |_| {
+            if !text.trim().is_empty() && !author.trim().is_empty() {
+                on_save.emit(((*text).clone(), (*author).clone()));
+            }
+        })
+    };
+
+    let on_cancel_click = {
+        let on_cancel = props.on_cancel.clone();
+        Callback::from(move |_| on_cancel.emit(()))
+    };
+
+    let (start, end) = props.range;
+    let too_long = text.chars().count() > MAX_TEXT_LEN;
+    let can_save = !props.saving && !too_long && !text.trim().is_empty() && !author.trim().is_empty();
+
+    html! {
+        <div class="absolute top-12 right-4 z-10 w-80 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-xl p-4">
+            <h4 class="text-sm font-semibold text-gray-800 dark:text-gray-100 mb-1">{"Add annotation"}</h4>
+            <p class="text-xs text-gray-500 dark:text-gray-400 mb-3">{format_range(start, end)}</p>
+
+            if let Some(err) = props.error.as_ref() {
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-3 py-2 rounded text-xs mb-3">
+                    {err}
+                </div>
+            }
+
+            <label for="annotation-text" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Note"}</label>
+            <textarea
+                id="annotation-text"
+                value={(*text).clone()}
+                oninput={on_text_input}
+                rows="3"
+                class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2 mb-1"
+                placeholder="e.g. Sensor relocated"
+            />
+            if too_long {
+                <p class="text-xs text-red-600 dark:text-red-400 mb-2">{format!("Notes are limited to {} characters", MAX_TEXT_LEN)}</p>
+            }
+
+            <label for="annotation-author" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1 mt-2">{"Author"}</label>
+            <input
+                type="text"
+                id="annotation-author"
+                value={(*author).clone()}
+                oninput={on_author_input}
+                class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2 mb-3"
+                placeholder="Your name"
+            />
+
+            <div class="flex justify-end gap-2">
+                <button
+                    type="button"
+                    onclick={on_cancel_click}
+                    class="px-3 py-1 rounded bg-gray-500 text-white text-sm font-semibold shadow hover:bg-gray-700 transition"
+                >
+                    {"Cancel"}
+                </button>
+                <button
+                    type="button"
+                    onclick={on_save_click}
+                    disabled={!can_save}
+                    class="px-3 py-1 rounded bg-blue-600 text-white text-sm font-semibold shadow hover:bg-blue-700 transition disabled:opacity-50"
+                >
+                    { if props.saving { "Saving..." } else { "Save" } }
+                </button>
+            </div>
+        </div>
+    }
+}
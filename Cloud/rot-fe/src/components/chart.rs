@@ -4,6 +4,8 @@ use yew::prelude::*;
 use web_sys::{window, Element};
 // Import wasm_bindgen for JS interop
 use wasm_bindgen::prelude::*;
+// Import js_sys for attaching callbacks to chart options
+use js_sys::{Array, Object, Reflect};
 // Import serde_wasm_bindgen for serializing Rust data to JS values
 use serde_wasm_bindgen::to_value;
 // Import serde for struct serialization
@@ -12,14 +14,40 @@ use serde::Serialize;
 use crate::services::device_service::DeviceService;
 // Import telemetry data model
 use crate::domain::telemetry::Telemetry;
+// Import the time range limiting the charted readings
+use crate::domain::preset::TimeRange;
+// Import annotation models and selection conversion
+use crate::domain::annotation::{format_range, selection_range, Annotation, AnnotationRequest};
+// Import the thresholds drawn over the chart
+use crate::domain::threshold::{Threshold, ThresholdSource};
+// Import the anomalous readings marked on the chart
+use crate::domain::anomaly::{format_reading, Anomaly, AnomalyReport};
+// Import timestamp formatting for the anomaly tooltip
+use crate::domain::telemetry::format_timestamp;
+// Import the popover used to annotate a selection
+use crate::components::AnnotationForm;
+// Import the banner warning that the charted data failed to refresh or comes from the cache
+use crate::components::StaleBanner;
 // Import chrono for date/time handling
-use chrono::{DateTime, Utc};
+use chrono::Utc;
+// Import stream helpers for live telemetry
+use futures::{future, StreamExt};
+// Import the hook reading the colour theme
+use crate::components::use_theme;
+// Import the hook reading the units readings are charted in
+use crate::components::use_units;
+use crate::domain::units::UnitPreferences;
+// Import shared cells for the zoom handlers and the pan debounce
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
 // JavaScript bindings for ApexCharts library
 #[wasm_bindgen]
 extern "C" {
     // ApexCharts type from JS
+    // Cloning copies the reference to the same chart
     #[wasm_bindgen(js_namespace = window, js_name = ApexCharts)]
+    #[derive(Clone)]
     type ApexCharts;
     
     // Constructor for ApexCharts
@@ -37,6 +65,14 @@ extern "C" {
     // Update the chart series data
     #[wasm_bindgen(method, js_name = updateSeries)]
     fn update_series(this: &ApexCharts, series: &JsValue);
+
+    // Merge new options (e.g. annotations) into the chart
+    #[wasm_bindgen(method, js_name = updateOptions)]
+    fn update_options(this: &ApexCharts, options: &JsValue);
+
+    // Append points to the existing series without redrawing the chart
+    #[wasm_bindgen(method, js_name = appendData)]
+    fn append_data(this: &ApexCharts, data: &JsValue);
 }
 
 // Chart configuration options for ApexCharts
@@ -49,6 +85,7 @@ struct ChartOptions {
     title: Title,          // Chart title
     stroke: Stroke,        // Line style
     markers: Markers,      // Marker style
+    theme: ThemeOptions,   // Light or dark colours
 }
 
 #[derive(Serialize)]
@@ -58,6 +95,10 @@ struct ChartType {
     width: String,
     height: String,
     animations: Animations,
+    selection: Selection,
+    zoom: Zoom,
+    toolbar: Toolbar,
+    background: String, // Transparent, so the card's colour shows through
 }
 
 #[derive(Serialize)]
@@ -65,16 +106,47 @@ struct Animations {
     enabled: bool,
 }
 
+// Lets the user drag across the x-axis to select a time range
+#[derive(Serialize)]
+struct Selection {
+    enabled: bool,
+    #[serde(rename = "type")]
+    selection_type: String,
+}
+
+// Lets the user zoom into a dragged x-axis range with the toolbar's zoom
+// tool, or with its zoom in/out buttons, and pan with its pan tool
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Zoom {
+    enabled: bool,
+    #[serde(rename = "type")]
+    zoom_type: String,
+    auto_scale_yaxis: bool, // Fit the y-axis to the readings in view
+}
+
+#[derive(Serialize)]
+struct Toolbar {
+    #[serde(rename = "autoSelected")]
+    auto_selected: String, // Tool active when the chart loads
+}
+
 #[derive(Serialize)]
 struct Series {
     name: String,
     data: Vec<DataPoint>,
 }
 
+// Points appended to the chart's series by appendData
+#[derive(Serialize)]
+struct AppendedSeries {
+    data: Vec<DataPoint>,
+}
+
 #[derive(Serialize)]
 struct DataPoint {
-    x: String, // timestamp as string
-    y: f64,    // value as number
+    x: i64, // timestamp in milliseconds since the epoch
+    y: f64, // value as number
 }
 
 #[derive(Serialize)]
@@ -111,12 +183,210 @@ struct Markers {
     size: u32,
 }
 
+// ApexCharts theme, matching the application's colour theme
+#[derive(Serialize)]
+struct ThemeOptions {
+    mode: String, // "light" or "dark"
+}
+
+// Theme options passed to updateOptions when the colour theme changes
+#[derive(Serialize)]
+struct ThemeUpdate {
+    theme: ThemeOptions,
+}
+
+// Y-axis options passed to updateOptions when the units change
+#[derive(Serialize)]
+struct YAxisUpdate {
+    yaxis: YAxis,
+}
+
+// Annotation options passed to updateOptions
+#[derive(Serialize)]
+struct AnnotationOptions {
+    annotations: ChartAnnotations,
+}
+
+#[derive(Serialize)]
+struct ChartAnnotations {
+    xaxis: Vec<XAxisAnnotation>,
+    yaxis: Vec<YAxisAnnotation>,
+    points: Vec<PointAnnotation>,
+}
+
+// A shaded x-axis region (or a line, for an instant)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct XAxisAnnotation {
+    id: String, // annotation id, read back by the label hover callbacks
+    x: i64,     // start in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    x2: Option<i64>, // end in milliseconds (None draws a line)
+    fill_color: String,
+    border_color: String,
+    opacity: f64,
+    label: AnnotationLabel,
+}
+
+// A threshold line, or the shaded region of readings breaching it
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct YAxisAnnotation {
+    y: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y2: Option<f64>, // other edge of a region (None draws a line)
+    border_color: String,
+    fill_color: String,
+    opacity: f64,
+    stroke_dash_array: u32, // dash length, 0 for a solid line
+    label: AnnotationLabel,
+}
+
+// A marker on an anomalous reading
+#[derive(Serialize)]
+struct PointAnnotation {
+    id: String, // annotation id, read back by the hover callbacks
+    x: i64,     // timestamp in milliseconds
+    y: f64,     // the reading
+    marker: PointMarker,
+    label: AnnotationLabel,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PointMarker {
+    size: u32,
+    fill_color: String,
+    stroke_color: String,
+    stroke_width: u32,
+    shape: String,
+}
+
+#[derive(Serialize)]
+struct AnnotationLabel {
+    text: String,
+    orientation: String,
+    style: LabelStyle,
+}
+
+#[derive(Serialize)]
+struct LabelStyle {
+    color: String,
+    background: String,
+}
+
+/// Longest annotation label drawn on the chart; the full text is shown on hover
+const LABEL_LEN: usize = 24;
+
+/// Prefix of the ids of anomaly markers, telling them from annotations on hover
+const ANOMALY_ID_PREFIX: &str = "anomaly-";
+
+/// Milliseconds without panning after which the panned range is fetched
+const PAN_SETTLE_MS: i32 = 500;
+
+/// The parent's zoom callbacks, replaced on every render so the chart's
+/// JS callbacks, created once, always call the current ones
+#[derive(Default)]
+struct ZoomHandlers {
+    on_zoom: Callback<(i64, i64)>,
+    on_zoom_reset: Callback<()>,
+}
+
+/// JS callbacks registered with ApexCharts
+/// Kept in the component so they live as long as the chart that calls them
+struct ChartCallbacks {
+    selection: Closure<dyn FnMut(JsValue, JsValue)>,
+    zoomed: Closure<dyn FnMut(JsValue, JsValue)>,
+    scrolled: Closure<dyn FnMut(JsValue, JsValue)>,
+    _pan_settled: Closure<dyn FnMut()>, // Called through its JS function by the pan timer
+    before_reset_zoom: Closure<dyn FnMut(JsValue, JsValue)>,
+    label_enter: Closure<dyn FnMut(JsValue, JsValue)>,
+    label_leave: Closure<dyn FnMut(JsValue, JsValue)>,
+}
+
+impl ChartCallbacks {
+    fn new(
+        selection: UseStateSetter<Option<(i64, i64)>>,
+        hovered: UseStateSetter<Option<String>>,
+        handlers: Rc<RefCell<ZoomHandlers>>,
+    ) -> Self {
+        let unhovered = hovered.clone();
+        // Range panned to, and the timer fetching it once panning stops
+        let panned = Rc::new(Cell::new(None::<(i64, i64)>));
+        let pan_timer = Rc::new(Cell::new(None::<i32>));
+        let pan_settled = {
+            let panned = panned.clone();
+            let handlers = handlers.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                if let Some(range) = panned.take() {
+                    handlers.borrow().on_zoom.emit(range);
+                }
+            })
+        };
+        let settle = pan_settled.as_ref().unchecked_ref::<js_sys::Function>().clone();
+        let zoom_handlers = handlers.clone();
+        ChartCallbacks {
+            // Called as selection(chartContext, { xaxis: { min, max } })
+            selection: Closure::new(move |_context: JsValue, range: JsValue| {
+                if let Some((min, max)) = xaxis_bounds(&range) {
+                    selection.set(Some(selection_range(min, max)));
+                }
+            }),
+            // Called as zoomed(chartContext, { xaxis: { min, max } }) after
+            // zooming; the bounds are missing when the zoom is reset
+            zoomed: Closure::new(move |_context: JsValue, range: JsValue| {
+                if let Some((min, max)) = xaxis_bounds(&range) {
+                    zoom_handlers.borrow().on_zoom.emit(selection_range(min, max));
+                }
+            }),
+            // Called as scrolled(chartContext, { xaxis: { min, max } }) for
+            // every step of a pan, so the range is fetched once panning stops
+            scrolled: Closure::new(move |_context: JsValue, range: JsValue| {
+                if let Some((min, max)) = xaxis_bounds(&range) {
+                    panned.set(Some(selection_range(min, max)));
+                    if let Some(window) = web_sys::window() {
+                        if let Some(handle) = pan_timer.take() {
+                            window.clear_timeout_with_handle(handle);
+                        }
+                        pan_timer.set(
+                            window
+                                .set_timeout_with_callback_and_timeout_and_arguments_0(&settle, PAN_SETTLE_MS)
+                                .ok(),
+                        );
+                    }
+                }
+            }),
+            _pan_settled: pan_settled,
+            // Called as beforeResetZoom(chartContext, options) by the toolbar's reset button
+            before_reset_zoom: Closure::new(move |_context: JsValue, _options: JsValue| {
+                handlers.borrow().on_zoom_reset.emit(());
+            }),
+            // Called as mouseEnter(annotation, event) with the options built below
+            label_enter: Closure::new(move |annotation: JsValue, _event: JsValue| {
+                let id = Reflect::get(&annotation, &"id".into()).ok().and_then(|id| id.as_string());
+                hovered.set(id);
+            }),
+            label_leave: Closure::new(move |_annotation: JsValue, _event: JsValue| {
+                unhovered.set(None);
+            }),
+        }
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct ApexChartProps {
     pub metric_key: String, // Which telemetry key to chart (e.g., "temperature")
     pub title: String,      // Chart title
     pub device_id: String,  // Device ID to fetch data for
     pub refresh_count: usize,
+    #[prop_or_default]
+    pub range: TimeRange, // Only chart readings within this time range
+    #[prop_or_default]
+    pub thresholds: Vec<Threshold>, // Thresholds to draw; only those on metric_key are shown
+    #[prop_or_default]
+    pub on_zoom: Callback<(i64, i64)>, // Emits the range zoomed or panned to, in Unix timestamps
+    #[prop_or_default]
+    pub on_zoom_reset: Callback<()>, // Emits when the toolbar's reset button is clicked
 }
 
 #[function_component(ApexChart)]
@@ -125,23 +395,93 @@ pub fn apex_chart(props: &ApexChartProps) -> Html {
     let chart_instance = use_state(|| None::<ApexCharts>);
     let telemetry_data = use_state(|| Vec::<Telemetry>::new());
     let loading = use_state(|| true);
+    // When the charted readings were last fetched, when they were cached if
+    // they come from the offline cache, and whether the last fetch failed,
+    // leaving the previous readings on the chart
+    let fetched_at = use_state(|| None::<i64>);
+    let cached_at = use_state(|| None::<i64>);
+    let fetch_failed = use_state(|| false);
+    // Annotations on the device's timeline
+    let annotations = use_state(|| Vec::<Annotation>::new());
+    // Time range selected on the chart, awaiting an annotation
+    let selection = use_state(|| None::<(i64, i64)>);
+    // Anomalous readings of the charted metric, marked on the chart
+    let anomalies = use_state(|| None::<AnomalyReport>);
+    // Annotation or anomaly marker the mouse is over
+    let hovered = use_state(|| None::<String>);
+    let saving = use_state(|| false);
+    let save_error = use_state(|| None::<String>);
+    // Colour theme, applied to the chart's text, grid and tooltips
+    let theme = use_theme().theme;
+    // Units the readings, thresholds and anomalies are charted in
+    let units = use_units().units;
+    // The parent's zoom callbacks, kept current for the chart's JS callbacks
+    let zoom_handlers = use_mut_ref(ZoomHandlers::default);
+    *zoom_handlers.borrow_mut() = ZoomHandlers {
+        on_zoom: props.on_zoom.clone(),
+        on_zoom_reset: props.on_zoom_reset.clone(),
+    };
+    let callbacks = {
+        let selection = selection.setter();
+        let hovered = hovered.setter();
+        use_memo((), move |_| ChartCallbacks::new(selection, hovered, zoom_handlers))
+    };
 
-    // Fetch telemetry data
+    // Fetch telemetry data, annotations and anomalies
     {
         let telemetry_data = telemetry_data.clone();
         let loading = loading.clone();
+        let fetched_at = fetched_at.clone();
+        let cached_at = cached_at.clone();
+        let fetch_failed = fetch_failed.clone();
+        let annotations = annotations.clone();
+        let anomalies = anomalies.clone();
+        let metric_key = props.metric_key.clone();
         let device_id = props.device_id.clone();
         let refresh_count = props.refresh_count;
-        use_effect_with((device_id.clone(), refresh_count), move |(device_id, _)| {
+        use_effect_with((device_id.clone(), refresh_count, props.range), move |(device_id, _, range)| {
             let device_id = device_id.clone();
+            // Quick ranges end now, so their bounds move with every refresh
+            let (from, to) = range.bounds(Utc::now().timestamp());
+            {
+                let device_id = device_id.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match DeviceService::get_annotations(&device_id).await {
+                        Ok(data) => annotations.set(data),
+                        Err(e) => {
+                            web_sys::console::log_1(&format!("Failed to fetch annotations: {}", e).into());
+                        }
+                    }
+                });
+            }
+            {
+                // Markers are only a hint, so the chart is drawn without them on failure
+                let device_id = device_id.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match DeviceService::get_anomalies(&device_id, &metric_key, from, to).await {
+                        Ok(report) => anomalies.set(Some(report)),
+                        Err(e) => {
+                            web_sys::console::log_1(&format!("Failed to fetch anomalies: {}", e).into());
+                            anomalies.set(None);
+                        }
+                    }
+                });
+            }
             wasm_bindgen_futures::spawn_local(async move {
-                match DeviceService::get_telemetry(&device_id).await {
-                    Ok(data) => {
-                        telemetry_data.set(data);
+                // The cached readings stand in while the API is unreachable
+                match DeviceService::get_telemetry_or_cached(&device_id, from, to).await {
+                    Ok(fetched) => {
+                        telemetry_data.set(fetched.data);
+                        if fetched.cached_at.is_none() {
+                            fetched_at.set(Some(Utc::now().timestamp()));
+                        }
+                        cached_at.set(fetched.cached_at);
+                        fetch_failed.set(false);
                         loading.set(false);
                     }
                     Err(e) => {
                         web_sys::console::log_1(&format!("Failed to fetch telemetry: {}", e).into());
+                        fetch_failed.set(true);
                         loading.set(false);
                     }
                 }
@@ -158,15 +498,26 @@ pub fn apex_chart(props: &ApexChartProps) -> Html {
         let metric_key = props.metric_key.clone();
         let title = props.title.clone();
         let loading = *loading;
+        let callbacks = callbacks.clone();
         
-        use_effect_with((telemetry_data.clone(), loading), move |_| {
+        use_effect_with((telemetry_data.clone(), loading, units.clone()), move |(_, _, units)| {
             if !loading {
                 if let Some(element) = chart_ref.cast::<Element>() {
                     // Prepare chart data
-                    let chart_data = prepare_chart_data(&telemetry_data, &metric_key);
+                    let chart_data = prepare_chart_data(&telemetry_data, &metric_key, units);
                     
                     if let Some(existing_chart) = chart_instance.as_ref() {
-                        // Update existing chart
+                        // Update existing chart, and its axis title in case the units changed
+                        let update = YAxisUpdate {
+                            yaxis: YAxis {
+                                title: AxisTitle {
+                                    text: get_unit_for_metric(&metric_key, units),
+                                },
+                            },
+                        };
+                        if let Ok(options) = to_value(&update) {
+                            existing_chart.update_options(&options);
+                        }
                         if let Ok(series_js) = to_value(&chart_data) {
                             existing_chart.update_series(&series_js);
                         }
@@ -178,6 +529,19 @@ pub fn apex_chart(props: &ApexChartProps) -> Html {
                                 width: "100%".to_string(),
                                 height: "350".to_string(),
                                 animations: Animations { enabled: true },
+                                selection: Selection {
+                                    enabled: true,
+                                    selection_type: "x".to_string(),
+                                },
+                                zoom: Zoom {
+                                    enabled: true,
+                                    zoom_type: "x".to_string(),
+                                    auto_scale_yaxis: true,
+                                },
+                                toolbar: Toolbar {
+                                    auto_selected: "selection".to_string(),
+                                },
+                                background: "transparent".to_string(),
                             },
                             series: vec![Series {
                                 name: metric_key.clone(),
@@ -191,7 +555,7 @@ pub fn apex_chart(props: &ApexChartProps) -> Html {
                             },
                             yaxis: YAxis {
                                 title: AxisTitle {
-                                    text: get_unit_for_metric(&metric_key),
+                                    text: get_unit_for_metric(&metric_key, units),
                                 },
                             },
                             title: Title {
@@ -203,9 +567,13 @@ pub fn apex_chart(props: &ApexChartProps) -> Html {
                                 width: 2,
                             },
                             markers: Markers { size: 4 },
+                            theme: ThemeOptions {
+                                mode: theme.key().to_string(),
+                            },
                         };
                         
                         if let Ok(options_js) = to_value(&options) {
+                            attach_chart_callbacks(&options_js, &callbacks);
                             let chart = ApexCharts::new(&element, &options_js);
                             chart.render();
                             chart_instance.set(Some(chart));
@@ -217,25 +585,240 @@ pub fn apex_chart(props: &ApexChartProps) -> Html {
         });
     }
 
+    // Switch the chart's theme when the colour theme is toggled
+    // New charts are created with the current theme
+    {
+        let chart_instance = chart_instance.clone();
+        let has_chart = chart_instance.is_some();
+        use_effect_with((theme, has_chart), move |(theme, _)| {
+            if let Some(chart) = chart_instance.as_ref() {
+                let update = ThemeUpdate {
+                    theme: ThemeOptions {
+                        mode: theme.key().to_string(),
+                    },
+                };
+                if let Ok(options) = to_value(&update) {
+                    chart.update_options(&options);
+                }
+            }
+            || ()
+        });
+    }
+
+    // Append live telemetry to the chart as the device reports it
+    // Only ranges ending now can grow, and points are appended once the chart exists
+    {
+        let chart_instance = chart_instance.clone();
+        let metric_key = props.metric_key.clone();
+        let has_chart = chart_instance.is_some();
+        let live = props.range.bounds(Utc::now().timestamp()).1.is_none();
+        use_effect_with((props.device_id.clone(), has_chart && live, units.clone()), move |(device_id, streaming, units)| {
+            let units = units.clone();
+            // Dropping the sender stops the stream task and closes the connection
+            let mut stop = None;
+            if let (true, Some(chart)) = (*streaming, chart_instance.as_ref().cloned()) {
+                match DeviceService::stream_telemetry(device_id) {
+                    Ok((connection, records)) => {
+                        let (sender, stopped) = futures::channel::oneshot::channel::<()>();
+                        stop = Some(sender);
+                        wasm_bindgen_futures::spawn_local(async move {
+                            // Keep the connection open until the task ends
+                            let _connection = connection;
+                            let append = records.for_each(move |record| {
+                                let point = prepare_chart_data(std::slice::from_ref(&record), &metric_key, &units);
+                                if !point.is_empty() {
+                                    if let Ok(data) = to_value(&[AppendedSeries { data: point }]) {
+                                        chart.append_data(&data);
+                                    }
+                                }
+                                future::ready(())
+                            });
+                            future::select(Box::pin(append), stopped).await;
+                        });
+                    }
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("Failed to stream telemetry: {}", e).into());
+                    }
+                }
+            }
+            move || drop(stop)
+        });
+    }
+
+    // Anomalous readings, converted to the charted units
+    let charted_anomalies: Vec<Anomaly> = anomalies
+        .as_ref()
+        .map(|report| {
+            report
+                .anomalies
+                .iter()
+                .map(|anomaly| Anomaly {
+                    value: units.convert(&props.metric_key, anomaly.value),
+                    expected: units.convert(&props.metric_key, anomaly.expected),
+                    ..anomaly.clone()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Draw annotations as shaded regions, thresholds as lines and anomalies as
+    // markers once the chart exists
+    // Breaches are shaded up to the charted extremes, so they follow the data
+    {
+        let chart_instance = chart_instance.clone();
+        let annotations = annotations.clone();
+        let callbacks = callbacks.clone();
+        let range = props.range;
+        let has_chart = chart_instance.is_some();
+        // Thresholds are saved in the reported units, so they are converted like the readings
+        let thresholds: Vec<Threshold> = props
+            .thresholds
+            .iter()
+            .filter(|threshold| threshold.metric == props.metric_key)
+            .map(|threshold| Threshold {
+                value: units.convert(&threshold.metric, threshold.value),
+                ..threshold.clone()
+            })
+            .collect();
+        let extent = value_extent(&prepare_chart_data(&telemetry_data, &props.metric_key, &units));
+        use_effect_with((annotations.clone(), charted_anomalies.clone(), has_chart, range, thresholds, extent), move |(_, anomalies, _, _, thresholds, extent)| {
+            if let Some(chart) = chart_instance.as_ref() {
+                if let Some(options) = annotation_options(&annotations, anomalies, range, thresholds, *extent, &callbacks) {
+                    chart.update_options(&options);
+                }
+            }
+            || ()
+        });
+    }
+
+    // Callback for the popover's "Save" button
+    // Creates the annotation and adds it to the chart
+    let on_save = {
+        let device_id = props.device_id.clone();
+        let annotations = annotations.clone();
+        let selection = selection.clone();
+        let saving = saving.clone();
+        let save_error = save_error.clone();
+        Callback::from(move |(text, author): (String, String)| {
+            let Some(range) = *selection else {
+                return;
+            };
+            let request = AnnotationRequest::for_range(range, &text, &author);
+            let device_id = device_id.clone();
+            let annotations = annotations.clone();
+            let selection = selection.clone();
+            let saving = saving.clone();
+            let save_error = save_error.clone();
+
+            saving.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::create_annotation(&device_id, &request).await {
+                    Ok(annotation) => {
+                        let mut updated = (*annotations).clone();
+                        updated.push(annotation);
+                        annotations.set(updated);
+                        selection.set(None);
+                        save_error.set(None);
+                    }
+                    Err(e) => save_error.set(Some(e)),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    // Callback for the popover's "Cancel" button
+    let on_cancel = {
+        let selection = selection.clone();
+        let save_error = save_error.clone();
+        Callback::from(move |_| {
+            selection.set(None);
+            save_error.set(None);
+        })
+    };
+
+    let hovered_annotation = hovered
+        .as_ref()
+        .and_then(|id| annotations.iter().find(|annotation| &annotation.id == id));
+    let hovered_anomaly = hovered
+        .as_ref()
+        .and_then(|id| id.strip_prefix(ANOMALY_ID_PREFIX))
+        .and_then(|timestamp| timestamp.parse::<i64>().ok())
+        .zip(anomalies.as_ref())
+        .and_then(|(timestamp, report)| {
+            charted_anomalies
+                .iter()
+                .find(|anomaly| anomaly.timestamp == timestamp)
+                .map(|anomaly| (anomaly, report.threshold))
+        });
+    let anomaly_count = charted_anomalies.len();
+    let charted_unit = units.unit(&props.metric_key).map(|unit| format!(" {}", unit)).unwrap_or_default();
+
     html! {
-        <div class="bg-white p-5 rounded-lg shadow-lg">
+        <div class="relative bg-white dark:bg-gray-800 p-5 rounded-lg shadow-lg">
             <h3 class="text-lg font-semibold mb-4">{&props.title}</h3>
+            // A chart that failed to refresh, or shows cached readings, says so
+            if *fetch_failed || cached_at.is_some() {
+                <StaleBanner fetched_at={*fetched_at} cached_at={*cached_at} failed={*fetch_failed} />
+            }
             {
                 if *loading {
                     html! {
                         <div class="flex justify-center items-center h-80">
-                            <div class="text-gray-500">{"Loading chart data..."}</div>
+                            <div class="text-gray-500 dark:text-gray-400">{"Loading chart data..."}</div>
                         </div>
                     }
                 } else {
-                    html! { <div ref={chart_ref}></div> }
+                    html! {
+                        <>
+                            <div ref={chart_ref}></div>
+                            <p class="text-xs text-gray-400 mt-2">
+                                {"Drag across the chart to annotate a time range, or pick the zoom or pan tool to explore it"}
+                                if anomaly_count > 0 {
+                                    {format!(". {} anomalous reading{} marked in red; hover a marker for its score", anomaly_count, if anomaly_count == 1 { "" } else { "s" })}
+                                }
+                            </p>
+                        </>
+                    }
                 }
             }
+            if let Some(annotation) = hovered_annotation {
+                <div class="absolute top-12 left-4 z-10 max-w-xs bg-gray-800 text-white text-sm rounded shadow-lg px-3 py-2 pointer-events-none">
+                    <p class="whitespace-pre-wrap">{&annotation.text}</p>
+                    <p class="text-xs text-gray-300 mt-1">
+                        {format!("{} · {}", annotation.author, format_range(annotation.start, annotation.end))}
+                    </p>
+                </div>
+            }
+            if let Some((anomaly, threshold)) = hovered_anomaly {
+                <div class="absolute top-12 left-4 z-10 max-w-xs bg-gray-800 text-white text-sm rounded shadow-lg px-3 py-2 pointer-events-none">
+                    <p class="font-semibold">{format!("Anomaly: {}{} {}", format_reading(anomaly.value), charted_unit, props.metric_key)}</p>
+                    <p>{format!("{}{}", anomaly.describe(), charted_unit)}</p>
+                    <p class="text-xs text-gray-300 mt-1">
+                        {format!(
+                            "{} · flagged beyond {} standard deviations from the readings before it",
+                            format_timestamp(anomaly.timestamp),
+                            format_reading(threshold)
+                        )}
+                    </p>
+                </div>
+            }
+            if let Some(range) = *selection {
+                <AnnotationForm
+                    key={format!("{}-{}", range.0, range.1)}
+                    range={range}
+                    saving={*saving}
+                    error={(*save_error).clone()}
+                    on_save={on_save}
+                    on_cancel={on_cancel}
+                />
+            }
         </div>
     }
 }
 
-fn prepare_chart_data(telemetry_data: &[Telemetry], metric_key: &str) -> Vec<DataPoint> {
+fn prepare_chart_data(telemetry_data: &[Telemetry], metric_key: &str, units: &UnitPreferences) -> Vec<DataPoint> {
+    // Readings are already limited to the charted range by the API
     telemetry_data
         .iter()
         .filter_map(|telemetry| {
@@ -247,22 +830,206 @@ fn prepare_chart_data(telemetry_data: &[Telemetry], metric_key: &str) -> Vec<Dat
             
             // Format timestamp
             let timestamp = telemetry.timestamp?;
-            let datetime = DateTime::from_timestamp(timestamp, 0)?;
-            let formatted_time = datetime.format("%Y-%m-%d %H:%M:%S").to_string();
-            
             Some(DataPoint {
-                x: formatted_time,
-                y: numeric_value,
+                x: timestamp * 1000,
+                y: units.convert(metric_key, numeric_value),
             })
         })
         .collect()
 }
 
-fn get_unit_for_metric(metric_key: &str) -> String {
-    match metric_key.to_lowercase().as_str() {
-        "temperature" => "Temperature (°C)".to_string(),
-        "pressure" => "Pressure (hPa)".to_string(),
-        "voltage" => "Voltage (V)".to_string(),
-        _ => metric_key.to_string(),
+/// Reads the `xaxis` bounds passed to selection, zoom and pan events, in milliseconds
+fn xaxis_bounds(range: &JsValue) -> Option<(f64, f64)> {
+    let bound = |name: &str| {
+        Reflect::get(range, &"xaxis".into())
+            .and_then(|xaxis| Reflect::get(&xaxis, &name.into()))
+            .ok()
+            .and_then(|value| value.as_f64())
+    };
+    bound("min").zip(bound("max"))
+}
+
+/// Sets the selection, zoom and pan callbacks in `chart.events` on serialized chart options
+fn attach_chart_callbacks(options: &JsValue, callbacks: &ChartCallbacks) {
+    let events = Object::new();
+    let _ = Reflect::set(&events, &"selection".into(), callbacks.selection.as_ref());
+    let _ = Reflect::set(&events, &"zoomed".into(), callbacks.zoomed.as_ref());
+    let _ = Reflect::set(&events, &"scrolled".into(), callbacks.scrolled.as_ref());
+    let _ = Reflect::set(&events, &"beforeResetZoom".into(), callbacks.before_reset_zoom.as_ref());
+    if let Ok(chart) = Reflect::get(options, &"chart".into()) {
+        let _ = Reflect::set(&chart, &"events".into(), &events);
+    }
+}
+
+/// Lowest and highest charted value, if any
+fn value_extent(points: &[DataPoint]) -> Option<(f64, f64)> {
+    points.iter().map(|point| point.y).fold(None, |extent, y| match extent {
+        None => Some((y, y)),
+        Some((min, max)) => Some((min.min(y), max.max(y))),
+    })
+}
+
+/// Builds the y-axis annotations drawing thresholds
+/// Each threshold is a dashed line, red for alert rules and indigo for the
+/// user's own, over a faint region shading the readings that breach it
+fn threshold_annotations(thresholds: &[Threshold], extent: Option<(f64, f64)>) -> Vec<YAxisAnnotation> {
+    thresholds
+        .iter()
+        .flat_map(|threshold| {
+            let (color, text) = match threshold.source {
+                ThresholdSource::AlertRule => ("#ef4444", format!("Alert {} {}", threshold.operator.symbol(), format_reading(threshold.value))),
+                ThresholdSource::User => ("#6366f1", format!("{} {}", threshold.operator.symbol(), format_reading(threshold.value))),
+            };
+            let region = extent
+                .and_then(|(min, max)| threshold.breach_region(min, max))
+                .map(|(low, high)| YAxisAnnotation {
+                    y: low,
+                    y2: Some(high),
+                    border_color: "transparent".to_string(),
+                    fill_color: color.to_string(),
+                    opacity: 0.08,
+                    stroke_dash_array: 0,
+                    label: AnnotationLabel {
+                        text: String::new(),
+                        orientation: "horizontal".to_string(),
+                        style: LabelStyle {
+                            color: color.to_string(),
+                            background: "transparent".to_string(),
+                        },
+                    },
+                });
+            let line = YAxisAnnotation {
+                y: threshold.value,
+                y2: None,
+                border_color: color.to_string(),
+                fill_color: color.to_string(),
+                opacity: 1.0,
+                stroke_dash_array: 6,
+                label: AnnotationLabel {
+                    text,
+                    orientation: "horizontal".to_string(),
+                    style: LabelStyle {
+                        color: "#ffffff".to_string(),
+                        background: color.to_string(),
+                    },
+                },
+            };
+            region.into_iter().chain(std::iter::once(line))
+        })
+        .collect()
+}
+
+/// Builds the point annotations marking anomalous readings
+/// Each marker is a red circle labelled with its score
+fn anomaly_annotations(anomalies: &[Anomaly]) -> Vec<PointAnnotation> {
+    anomalies
+        .iter()
+        .map(|anomaly| PointAnnotation {
+            id: format!("{}{}", ANOMALY_ID_PREFIX, anomaly.timestamp),
+            x: anomaly.timestamp * 1000,
+            y: anomaly.value,
+            marker: PointMarker {
+                size: 6,
+                fill_color: "#ef4444".to_string(),
+                stroke_color: "#ffffff".to_string(),
+                stroke_width: 2,
+                shape: "circle".to_string(),
+            },
+            label: AnnotationLabel {
+                text: anomaly.label(),
+                orientation: "horizontal".to_string(),
+                style: LabelStyle {
+                    color: "#ffffff".to_string(),
+                    background: "#ef4444".to_string(),
+                },
+            },
+        })
+        .collect()
+}
+
+/// Builds the `annotations` options for the chart, with hover callbacks on
+/// every label and anomaly marker
+/// Annotations not overlapping the charted range are left out; thresholds
+/// are drawn across the whole chart
+fn annotation_options(
+    annotations: &[Annotation],
+    anomalies: &[Anomaly],
+    range: TimeRange,
+    thresholds: &[Threshold],
+    extent: Option<(f64, f64)>,
+    callbacks: &ChartCallbacks,
+) -> Option<JsValue> {
+    let (from, to) = range.bounds(Utc::now().timestamp());
+
+    let options = AnnotationOptions {
+        annotations: ChartAnnotations {
+            xaxis: annotations
+                .iter()
+                .filter(|annotation| {
+                    from.is_none_or(|from| annotation.end >= from) && to.is_none_or(|to| annotation.start <= to)
+                })
+                .map(|annotation| XAxisAnnotation {
+                    id: annotation.id.clone(),
+                    x: annotation.start * 1000,
+                    x2: (!annotation.is_instant()).then_some(annotation.end * 1000),
+                    fill_color: "#f59e0b".to_string(),
+                    border_color: "#d97706".to_string(),
+                    opacity: 0.2,
+                    label: AnnotationLabel {
+                        text: short_label(&annotation.text),
+                        orientation: "horizontal".to_string(),
+                        style: LabelStyle {
+                            color: "#ffffff".to_string(),
+                            background: "#d97706".to_string(),
+                        },
+                    },
+                })
+                .collect(),
+            yaxis: threshold_annotations(thresholds, extent),
+            points: anomaly_annotations(anomalies),
+        },
+    };
+
+    let options_js = to_value(&options).ok()?;
+    let annotations_js = Reflect::get(&options_js, &"annotations".into()).ok()?;
+    let xaxis = Reflect::get(&annotations_js, &"xaxis".into()).ok()?;
+    for annotation in Array::from(&xaxis).iter() {
+        if let Ok(label) = Reflect::get(&annotation, &"label".into()) {
+            let _ = Reflect::set(&label, &"mouseEnter".into(), callbacks.label_enter.as_ref());
+            let _ = Reflect::set(&label, &"mouseLeave".into(), callbacks.label_leave.as_ref());
+        }
+    }
+    // Markers are hovered on the point itself, which passes the annotation too
+    let points = Reflect::get(&annotations_js, &"points".into()).ok()?;
+    for annotation in Array::from(&points).iter() {
+        let _ = Reflect::set(&annotation, &"mouseEnter".into(), callbacks.label_enter.as_ref());
+        let _ = Reflect::set(&annotation, &"mouseLeave".into(), callbacks.label_leave.as_ref());
+    }
+
+    Some(options_js)
+}
+
+/// Shortens annotation text to fit in a chart label
+fn short_label(text: &str) -> String {
+    let first_line = text.lines().next().unwrap_or_default();
+    if first_line.chars().count() > LABEL_LEN || first_line.len() < text.trim_end().len() {
+        format!("{}…", first_line.chars().take(LABEL_LEN).collect::<String>())
+    } else {
+        first_line.to_string()
     }
-}
\ No newline at end of file
+}
+
+/// Y-axis title of a metric with the unit it is charted in, e.g. "Temperature (°F)"
+fn get_unit_for_metric(metric_key: &str, units: &UnitPreferences) -> String {
+    match units.unit(metric_key) {
+        Some(_) => {
+            let label = units.label(&metric_key.to_lowercase());
+            let mut chars = label.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        None => metric_key.to_string(),
+    }
+}
@@ -0,0 +1,69 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import JSON values for the inspected documents
+use serde_json::Value;
+// Import the future wrapper awaiting the clipboard's promise
+use wasm_bindgen_futures::JsFuture;
+
+/// Properties for the JsonInspector component
+/// - document: The document to show, as returned by the API
+#[derive(Properties, PartialEq)]
+pub struct JsonInspectorProps {
+    pub document: Value,
+}
+
+/// JsonInspector component showing a document pretty-printed, metadata such
+/// as Cosmos DB's `_rid`, `_etag` and `_ts` included, with a button copying
+/// it to the clipboard
+/// Useful to see why a record does not read as expected
+#[function_component(JsonInspector)]
+pub fn json_inspector(props: &JsonInspectorProps) -> Html {
+    // Outcome of the last copy of this document, shown on the button
+    let copied = use_state(|| None::<bool>);
+    {
+        let copied = copied.clone();
+        use_effect_with(props.document.clone(), move |_| {
+            copied.set(None);
+            || ()
+        });
+    }
+
+    let pretty = serde_json::to_string_pretty(&props.document).unwrap_or_else(|_| props.document.to_string());
+
+    // Callback for the "Copy" button
+    let on_copy = {
+        let copied = copied.clone();
+        let pretty = pretty.clone();
+        Callback::from(move |_: MouseEvent| {
+            let copied = copied.clone();
+            let Some(window) = web_sys::window() else {
+                return;
+            };
+            let promise = window.navigator().clipboard().write_text(&pretty);
+            wasm_bindgen_futures::spawn_local(async move {
+                copied.set(Some(JsFuture::from(promise).await.is_ok()));
+            });
+        })
+    };
+
+    let copy_label = match *copied {
+        Some(true) => "Copied",
+        Some(false) => "Copy failed",
+        None => "Copy",
+    };
+
+    html! {
+        <div class="relative">
+            <button
+                type="button"
+                onclick={on_copy}
+                class="absolute top-2 right-2 px-2 py-1 rounded text-xs bg-gray-200 dark:bg-gray-700 text-gray-700 dark:text-gray-200 hover:bg-gray-300 dark:hover:bg-gray-600 transition"
+            >
+                {copy_label}
+            </button>
+            <pre class="max-h-96 overflow-auto rounded bg-gray-100 dark:bg-gray-950 text-gray-800 dark:text-gray-200 text-xs font-mono p-3 pr-16 whitespace-pre">
+                {pretty}
+            </pre>
+        </div>
+    }
+}
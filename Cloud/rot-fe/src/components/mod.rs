@@ -2,8 +2,34 @@
 mod header;  // Header component for application branding
 mod navbar;  // Navigation bar component for view switching
 mod chart;   // Chart component for data visualization
+mod preset_picker;  // Dropdown for saving and recalling chart presets
+mod annotation_form;  // Popover for annotating a selected chart range
+mod sparkline;  // Small axis-less line chart for device tiles
+mod theme_provider;  // Light/dark theme context shared by all components
+mod recent_devices;  // Quick selection of recently viewed devices
+mod stat_cards;  // Summary statistics of the charted metrics
+mod telemetry_table;  // Paged table of raw telemetry records
+mod threshold_editor;  // Chips and form for the thresholds drawn on charts
+mod status_tile;  // Whether a device is still reporting, for the dashboard
+mod json_inspector;  // Pretty-printed raw API documents with a copy button
+mod units_provider;  // Units telemetry is shown in, shared by all components
+mod stale_banner;  // When data was fetched, and a warning when it is stale
+mod svg_chart;  // Native SVG chart, drawn without the ApexCharts library
 
 // Public exports - these components can be used by other modules
 pub use header::Header;      // Export Header component
 pub use navbar::Navbar;      // Export Navbar component  
 pub use chart::ApexChart;    // Export ApexChart component for data visualization
+pub use annotation_form::AnnotationForm;  // Export AnnotationForm component used by charts
+pub use preset_picker::PresetPicker;  // Export PresetPicker component for saved chart views
+pub use sparkline::Sparkline;  // Export Sparkline component for the fleet dashboard tiles
+pub use theme_provider::{use_theme, ThemeProvider};  // Export the theme provider and the hook reading its theme
+pub use recent_devices::{use_recent_devices, RecentDevices};  // Export the recent device chips and the hook remembering viewed devices
+pub use stat_cards::StatCards;  // Export StatCards component summarizing the charted metrics
+pub use telemetry_table::TelemetryTable;  // Export TelemetryTable component listing exact values
+pub use threshold_editor::ThresholdEditor;  // Export ThresholdEditor component for chart thresholds
+pub use status_tile::StatusTile;  // Export StatusTile component for dashboard widgets
+pub use json_inspector::JsonInspector;  // Export JsonInspector component for raw telemetry and config documents
+pub use units_provider::{use_units, UnitsProvider};  // Export the units provider and the hook reading its units
+pub use stale_banner::StaleBanner;  // Export StaleBanner component for views showing fetched data
+pub use svg_chart::SvgChart;  // Export SvgChart component, the native alternative to ApexChart
\ No newline at end of file
@@ -1,38 +1,60 @@
 // Import Yew framework prelude for component development
 use yew::prelude::*;
+// Import router links and the application routes
+use yew_router::prelude::*;
 
-/// Properties for the Navbar component
-/// - on_nav_click: Callback to handle navigation button clicks
-/// - current_view: String indicating which view is currently active
-#[derive(Properties, PartialEq)]
-pub struct NavbarProps {
-    pub on_nav_click: Callback<String>,
-    pub current_view: String,
-}
+use crate::components::{use_theme, use_units};
+use crate::domain::units::UnitPreferences;
+use crate::route::Route;
+use crate::services::auth_service::AuthService;
 
 /// Navbar component for application navigation
-/// Renders navigation buttons and highlights the active view
+/// Renders navigation links, highlights the view of the current route,
+/// toggles between the light and dark themes and between °C and °F, and
+/// shows the signed-in user
 #[function_component(Navbar)]
-pub fn navbar(props: &NavbarProps) -> Html {
-    // Callback for the "Device Monitoring" button
-    // Emits "telemetry" when clicked
-    let on_telemetry_click = {
-        let on_nav_click = props.on_nav_click.clone();
-        Callback::from(move |_| {
-            on_nav_click.emit("telemetry".to_string());
+pub fn navbar() -> Html {
+    // Whether the current route shows the dashboard, fleet, devices, configuration, alerts or settings view
+    // Unknown routes redirect to telemetry, so anything else counts as telemetry
+    let route = use_route::<Route>();
+    let dashboard_active = route.as_ref().is_some_and(Route::is_dashboard);
+    let fleet_active = route.as_ref().is_some_and(Route::is_fleet);
+    let devices_active = route.as_ref().is_some_and(Route::is_devices);
+    let config_active = route.as_ref().is_some_and(Route::is_config);
+    let alerts_active = route.as_ref().is_some_and(Route::is_alerts);
+    let settings_active = route.as_ref().is_some_and(Route::is_settings);
+
+    // Active colour theme and the callback switching it
+    let theme = use_theme();
+    let on_theme_toggle = theme.toggle.reform(|_: MouseEvent| ());
+
+    // Unit temperatures are shown in and the callback switching it
+    let units = use_units();
+    let temperature = units.units.temperature;
+    let on_temperature_toggle = {
+        let current = units.units.clone();
+        units.set.reform(move |_: MouseEvent| UnitPreferences {
+            temperature: current.temperature.toggled(),
+            ..current.clone()
         })
     };
 
-    // Callback for the "Device Configuration" button
-    // Emits "config" when clicked
-    let on_config_click = {
-        let on_nav_click = props.on_nav_click.clone();
-        Callback::from(move |_| {
-            on_nav_click.emit("config".to_string());
-        })
+    // The signed-in user, if sign-in is enabled; read on every render, so
+    // it appears once the route changes after signing in
+    let signed_in = AuthService::is_enabled() && AuthService::is_signed_in();
+    let user_name = AuthService::user_name();
+    let on_sign_out = Callback::from(|_: MouseEvent| AuthService::logout());
+
+    // Link classes, highlighted if the link's view is active
+    let link_classes = |active: bool| {
+        classes!(
+            "font-medium",
+            "transition",
+            if active { "text-green-400" } else { "text-white hover:text-green-400" }
+        )
     };
 
-    // Render the navigation bar with branding and navigation buttons
+    // Render the navigation bar with branding and navigation links
     html! {
         <nav class="bg-black border-b-2 border-green-500 px-6 py-4">
             <div class="max-w-6xl mx-auto flex items-center justify-between">
@@ -43,34 +65,67 @@ pub fn navbar(props: &NavbarProps) -> Html {
                 </div>
                 // Navigation Links
                 <div class="hidden md:flex gap-8">
-                    // Device Monitoring button, highlighted if active
-                    <button
-                        onclick={on_telemetry_click}
-                        class={format!(
-                            "font-medium transition {}",
-                            if props.current_view == "telemetry" {
-                                "text-green-400"
-                            } else {
-                                "text-white hover:text-green-400"
-                            }
-                        )}
-                    >
+                    // Dashboard link, highlighted if active
+                    <Link<Route> to={Route::Dashboard} classes={link_classes(dashboard_active)}>
+                        {"Dashboard"}
+                    </Link<Route>>
+                    // Fleet link, highlighted if active
+                    <Link<Route> to={Route::Fleet} classes={link_classes(fleet_active)}>
+                        {"Fleet"}
+                    </Link<Route>>
+                    // Devices link, highlighted if active
+                    <Link<Route> to={Route::Devices} classes={link_classes(devices_active)}>
+                        {"Devices"}
+                    </Link<Route>>
+                    // Device Monitoring link, highlighted if active
+                    <Link<Route> to={Route::Telemetry} classes={link_classes(!dashboard_active && !fleet_active && !devices_active && !config_active && !alerts_active && !settings_active)}>
                         {"Device Monitoring"}
-                    </button>
-                    // Device Configuration button, highlighted if active
+                    </Link<Route>>
+                    // Device Configuration link, highlighted if active
+                    <Link<Route> to={Route::Config} classes={link_classes(config_active)}>
+                        {"Device Configuration"}
+                    </Link<Route>>
+                    // Alerts link, highlighted if active
+                    <Link<Route> to={Route::Alerts} classes={link_classes(alerts_active)}>
+                        {"Alerts"}
+                    </Link<Route>>
+                    // Settings link, highlighted if active
+                    <Link<Route> to={Route::Settings} classes={link_classes(settings_active)}>
+                        {"Settings"}
+                    </Link<Route>>
+                </div>
+                <div class="flex items-center gap-4">
+                // Signed-in user and sign-out button
+                if signed_in {
+                    if let Some(user_name) = user_name {
+                        <span class="hidden sm:inline text-sm text-gray-300">{user_name}</span>
+                    }
                     <button
-                        onclick={on_config_click}
-                        class={format!(
-                            "font-medium transition {}",
-                            if props.current_view == "config" {
-                                "text-green-400"
-                            } else {
-                                "text-white hover:text-green-400"
-                            }
-                        )}
+                        type="button"
+                        onclick={on_sign_out}
+                        class="text-sm text-white hover:text-green-400 transition"
                     >
-                        {"Device Configuration"}
+                        {"Sign out"}
                     </button>
+                }
+                // Temperature unit toggle, showing the unit in use
+                <button
+                    type="button"
+                    onclick={on_temperature_toggle}
+                    title={format!("Show temperatures in {}", temperature.toggled().symbol())}
+                    class="text-sm font-semibold text-white hover:text-green-400 transition"
+                >
+                    {temperature.symbol()}
+                </button>
+                // Theme toggle, showing the theme it switches to
+                <button
+                    type="button"
+                    onclick={on_theme_toggle}
+                    title={if theme.theme.is_dark() { "Switch to light mode" } else { "Switch to dark mode" }}
+                    class="text-white hover:text-green-400 transition text-xl"
+                >
+                    { if theme.theme.is_dark() { "☀️" } else { "🌙" } }
+                </button>
                 </div>
             </div>
         </nav>
@@ -0,0 +1,129 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import chart preset model
+use crate::domain::preset::ChartPreset;
+
+/// Properties for the PresetPicker component
+/// - presets: Saved presets shown in the dropdown
+/// - on_select: Callback emitting the preset chosen from the dropdown
+/// - on_save: Callback emitting the name to save the current view under
+/// - on_delete: Callback emitting the name of the preset to delete
+#[derive(Properties, PartialEq)]
+pub struct PresetPickerProps {
+    pub presets: Vec<ChartPreset>,
+    pub on_select: Callback<ChartPreset>,
+    pub on_save: Callback<String>,
+    pub on_delete: Callback<String>,
+}
+
+/// PresetPicker component for saving and recalling chart configurations
+/// Renders a dropdown of saved presets plus a name field to save the current view
+#[function_component(PresetPicker)]
+pub fn preset_picker(props: &PresetPickerProps) -> Html {
+    // Name of the preset currently selected in the dropdown
+    let selected = use_state(String::new);
+    // Name typed into the "save as" field
+    let name_input = use_state(String::new);
+
+    // Callback for choosing a preset from the dropdown
+    // Emits the matching preset so the view can apply it
+    let on_change = {
+        let selected = selected.clone();
+        let presets = props.presets.clone();
+        let on_select = props.on_select.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let name = select.value();
+            if let Some(preset) = presets.iter().find(|preset| preset.name == name) {
+                on_select.emit(preset.clone());
+            }
+            selected.set(name);
+        })
+    };
+
+    // Callback for typing a preset name
+    let on_name_input = {
+        let name_input = name_input.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            name_input.set(input.value());
+        })
+    };
+
+    // Callback for the "Save preset" button
+    // Emits the trimmed name and selects the saved preset
+    let on_save_click = {
+        let name_input = name_input.clone();
+        let selected = selected.clone();
+        let on_save = props.on_save.clone();
+        Callback::from(move |_| {
+            let name = name_input.trim().to_string();
+            if !name.is_empty() {
+                on_save.emit(name.clone());
+                selected.set(name);
+                name_input.set(String::new());
+            }
+        })
+    };
+
+    // Callback for the "Delete" button
+    // Emits the selected preset name and clears the selection
+    let on_delete_click = {
+        let selected = selected.clone();
+        let on_delete = props.on_delete.clone();
+        Callback::from(move |_| {
+            if !selected.is_empty() {
+                on_delete.emit((*selected).clone());
+                selected.set(String::new());
+            }
+        })
+    };
+
+    html! {
+        <div class="flex flex-col sm:flex-row gap-2 items-end mb-6">
+            <div class="flex-1">
+                <label for="preset" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Saved views"}</label>
+                <select
+                    id="preset"
+                    onchange={on_change}
+                    class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                >
+                    <option value="" selected={selected.is_empty()}>{"Select a saved view..."}</option>
+                    {
+                        props.presets.iter().map(|preset| html! {
+                            <option value={preset.name.clone()} selected={*selected == preset.name}>
+                                {&preset.name}
+                            </option>
+                        }).collect::<Html>()
+                    }
+                </select>
+            </div>
+            <button
+                type="button"
+                onclick={on_delete_click}
+                disabled={selected.is_empty()}
+                class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+            >
+                {"Delete"}
+            </button>
+            <div class="flex-1">
+                <label for="preset-name" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Save current view as"}</label>
+                <input
+                    type="text"
+                    id="preset-name"
+                    value={(*name_input).clone()}
+                    oninput={on_name_input}
+                    class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                    placeholder="Preset name"
+                />
+            </div>
+            <button
+                type="button"
+                onclick={on_save_click}
+                class="px-4 py-2 rounded bg-blue-600 text-white font-semibold shadow hover:bg-blue-700 transition"
+            >
+                {"Save preset"}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,93 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import the rule ordering the recently viewed devices
+use crate::domain::device::remember_device;
+// Import the preferences service persisting the recently viewed devices
+use crate::services::preferences_service::PreferencesService;
+
+/// Properties for the RecentDevices component
+/// - device_ids: Recently viewed device IDs, most recent first
+/// - current: The device currently shown, highlighted among the chips
+/// - list_id: ID of the datalist offering the devices as autocomplete;
+///   the device ID input refers to it with its `list` attribute
+/// - on_select: Callback emitting the device ID of the chip clicked
+#[derive(Properties, PartialEq)]
+pub struct RecentDevicesProps {
+    pub device_ids: Vec<String>,
+    #[prop_or_default]
+    pub current: String,
+    pub list_id: AttrValue,
+    pub on_select: Callback<String>,
+}
+
+/// RecentDevices component offering recently viewed devices for quick selection
+/// Renders a chip per device and a datalist autocompleting the device ID input
+#[function_component(RecentDevices)]
+pub fn recent_devices(props: &RecentDevicesProps) -> Html {
+    html! {
+        <>
+            <datalist id={props.list_id.clone()}>
+                { props.device_ids.iter().map(|device_id| html! { <option value={device_id.clone()} /> }).collect::<Html>() }
+            </datalist>
+            if !props.device_ids.is_empty() {
+                <div class="flex flex-wrap items-center gap-2 mt-2">
+                    <span class="text-xs text-gray-500 dark:text-gray-400">{"Recent:"}</span>
+                    {
+                        props.device_ids.iter().map(|device_id| {
+                            let current = *device_id == props.current;
+                            let on_click = {
+                                let device_id = device_id.clone();
+                                props.on_select.reform(move |_: MouseEvent| device_id.clone())
+                            };
+                            html! {
+                                <button
+                                    type="button"
+                                    onclick={on_click}
+                                    class={classes!(
+                                        "px-2", "py-1", "rounded-full", "text-xs", "transition",
+                                        if current {
+                                            "bg-blue-600 text-white"
+                                        } else {
+                                            "bg-gray-100 dark:bg-gray-700 text-gray-700 dark:text-gray-200 hover:bg-gray-200 dark:hover:bg-gray-600"
+                                        }
+                                    )}
+                                >
+                                    {device_id}
+                                </button>
+                            }
+                        }).collect::<Html>()
+                    }
+                </div>
+            }
+        </>
+    }
+}
+
+/// Hook remembering the device a view shows among the recently viewed devices
+/// Reads the list from local storage whenever the device changes, so devices
+/// viewed in other views are included, and saves it with the device in front
+///
+/// # Parameters
+/// * `device_id` - The device currently shown; blank IDs are not remembered
+///
+/// # Returns
+/// * The recently viewed device IDs, most recent first
+#[hook]
+pub fn use_recent_devices(device_id: &str) -> Vec<String> {
+    let recent = use_state(|| PreferencesService::load_recent_devices().unwrap_or_default());
+
+    {
+        let recent = recent.clone();
+        use_effect_with(device_id.to_string(), move |device_id| {
+            let mut updated = PreferencesService::load_recent_devices().unwrap_or_default();
+            remember_device(&mut updated, device_id);
+            if let Err(e) = PreferencesService::save_recent_devices(&updated) {
+                web_sys::console::log_1(&format!("Failed to save recent devices: {}", e).into());
+            }
+            recent.set(updated);
+            || ()
+        });
+    }
+
+    (*recent).clone()
+}
@@ -0,0 +1,65 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+
+// Size of the drawing area in SVG user units; the SVG scales to its container
+const WIDTH: f64 = 100.0;
+const HEIGHT: f64 = 30.0;
+
+/// Properties for the Sparkline component
+/// - points: `(timestamp, value)` pairs to plot, oldest first
+#[derive(Properties, PartialEq)]
+pub struct SparklineProps {
+    pub points: Vec<(i64, f64)>,
+}
+
+/// Sparkline component drawing a small line chart without axes or labels
+/// Drawn as an inline SVG rather than an ApexCharts chart, so a grid of
+/// device tiles stays cheap to render and refresh
+#[function_component(Sparkline)]
+pub fn sparkline(props: &SparklineProps) -> Html {
+    // A line needs at least two readings
+    if props.points.len() < 2 {
+        return html! {
+            <div class="h-8 flex items-center text-xs text-gray-400">{"Not enough data"}</div>
+        };
+    }
+
+    html! {
+        <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} preserveAspectRatio="none" class="w-full h-8">
+            <polyline
+                points={polyline_points(&props.points)}
+                fill="none"
+                stroke="currentColor"
+                stroke-width="1.5"
+                vector-effect="non-scaling-stroke"
+                class="text-blue-500"
+            />
+        </svg>
+    }
+}
+
+/// Scales readings into the SVG drawing area.
+///
+/// # Parameters
+/// * `points` - `(timestamp, value)` pairs, oldest first
+///
+/// # Returns
+/// * The `points` attribute of an SVG polyline; time runs left to right and
+///   the lowest value sits at the bottom. Equal timestamps or values are
+///   drawn at the left edge or the middle.
+fn polyline_points(points: &[(i64, f64)]) -> String {
+    let (first, last) = (points[0].0, points[points.len() - 1].0);
+    let (min, max) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, value)| (min.min(*value), max.max(*value)));
+
+    points
+        .iter()
+        .map(|(timestamp, value)| {
+            let x = if last > first { (timestamp - first) as f64 / (last - first) as f64 * WIDTH } else { 0.0 };
+            let y = if max > min { HEIGHT - (value - min) / (max - min) * HEIGHT } else { HEIGHT / 2.0 };
+            format!("{:.2},{:.2}", x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
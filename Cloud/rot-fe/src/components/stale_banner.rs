@@ -0,0 +1,95 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import the judgement of whether data is stale
+use crate::domain::freshness::Staleness;
+// Import age and timestamp formatting
+use crate::domain::device::format_age;
+use crate::domain::telemetry::format_timestamp;
+// Import chrono for the current time
+use chrono::Utc;
+// Import the JS closure and casts for the clock timer
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+
+/// How often the ages shown are recomputed, in milliseconds
+const CLOCK_TICK_MS: i32 = 30_000;
+
+/// Properties for the StaleBanner component
+/// - fetched_at: When the data shown was last fetched successfully, if ever
+/// - cached_at: When the data shown was cached, if it was taken from the
+///   offline cache because the backend is unreachable
+/// - failed: Whether the last fetch failed; the view reports why
+/// - latest_reading: Timestamp of the newest reading shown, if known
+/// - interval: How often the device reports, in seconds; `None` to only
+///   warn about failed fetches
+#[derive(Properties, PartialEq)]
+pub struct StaleBannerProps {
+    pub fetched_at: Option<i64>,
+    #[prop_or_default]
+    pub cached_at: Option<i64>,
+    #[prop_or_default]
+    pub failed: bool,
+    #[prop_or_default]
+    pub latest_reading: Option<i64>,
+    #[prop_or_default]
+    pub interval: Option<i64>,
+}
+
+/// StaleBanner component showing when the data on screen was fetched, and
+/// a prominent warning when it comes from the offline cache, the last fetch
+/// failed or the newest reading is older than the device's reporting
+/// interval allows
+/// The ages are recomputed every 30 seconds, so data left on screen visibly
+/// grows old without a refetch
+#[function_component(StaleBanner)]
+pub fn stale_banner(props: &StaleBannerProps) -> Html {
+    // Current time, advanced by the clock timer
+    let now = use_state(|| Utc::now().timestamp());
+    {
+        let now = now.setter();
+        use_effect_with((), move |_| {
+            let tick = Closure::<dyn FnMut()>::new(move || now.set(Utc::now().timestamp()));
+            let handle = web_sys::window().and_then(|window| {
+                window
+                    .set_interval_with_callback_and_timeout_and_arguments_0(tick.as_ref().unchecked_ref(), CLOCK_TICK_MS)
+                    .ok()
+            });
+
+            // Cleanup function stopping the timer when the banner is removed
+            move || {
+                if let (Some(window), Some(handle)) = (web_sys::window(), handle) {
+                    window.clear_interval_with_handle(handle);
+                }
+                drop(tick);
+            }
+        });
+    }
+    // A fetch that just completed counts from now, not from the last tick
+    let now = props.fetched_at.map_or(*now, |fetched_at| (*now).max(fetched_at));
+
+    let staleness = Staleness::assess(now, props.fetched_at, props.cached_at, props.failed, props.latest_reading, props.interval);
+
+    match staleness.warning() {
+        Some(warning) => html! {
+            <div role="alert" class="bg-yellow-50 dark:bg-yellow-950 border border-yellow-300 dark:border-yellow-700 text-yellow-800 dark:text-yellow-200 px-4 py-3 rounded flex items-start gap-2 mb-4">
+                <span>{"⚠️"}</span>
+                <div>
+                    <p class="font-semibold">{warning}</p>
+                    if let Some(cached_at) = props.cached_at {
+                        <p class="text-sm">{format!("Cached {}", format_timestamp(cached_at))}</p>
+                    } else if let Some(fetched_at) = props.fetched_at {
+                        <p class="text-sm">{format!("Last fetched {}", format_timestamp(fetched_at))}</p>
+                    }
+                </div>
+            </div>
+        },
+        None => match props.fetched_at {
+            Some(fetched_at) => html! {
+                <p class="text-xs text-gray-500 dark:text-gray-400 mb-2" title={format_timestamp(fetched_at)}>
+                    {format!("Fetched {}", format_age(now - fetched_at))}
+                </p>
+            },
+            None => html! {},
+        },
+    }
+}
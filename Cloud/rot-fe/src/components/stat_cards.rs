@@ -0,0 +1,116 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import device service for fetching metric statistics
+use crate::services::device_service::DeviceService;
+// Import the statistics model and its formatting
+use crate::domain::stats::{format_stat, MetricStats};
+// Import the time range the statistics cover
+use crate::domain::preset::TimeRange;
+// Import chrono for resolving quick ranges, which end now
+use chrono::Utc;
+// Import future helpers for fetching every metric at once
+use futures::future;
+// Import the units the statistics are shown in
+use crate::components::use_units;
+use crate::domain::units::UnitPreferences;
+
+/// Properties for the StatCards component
+/// - device_id: Device whose metrics are summarized
+/// - metrics: Metrics to summarize, one card per metric
+/// - range: Time range the statistics cover
+/// - refresh_count: Changes whenever the statistics should be re-fetched
+#[derive(Properties, PartialEq)]
+pub struct StatCardsProps {
+    pub device_id: String,
+    pub metrics: Vec<String>,
+    #[prop_or_default]
+    pub range: TimeRange,
+    pub refresh_count: usize,
+}
+
+/// StatCards component showing the min, max, average and latest value of
+/// each metric over the selected time range
+/// The statistics come from the device monitor statistics endpoint, so the
+/// extremes cover every reading, not only the ones visible on the charts
+/// Statistics are shown in the units the user chose
+#[function_component(StatCards)]
+pub fn stat_cards(props: &StatCardsProps) -> Html {
+    // Statistics of each metric, in the order of the metrics
+    let stats = use_state(Vec::<MetricStats>::new);
+    let error = use_state(|| None::<String>);
+    let units = use_units().units;
+
+    {
+        let stats = stats.clone();
+        let error = error.clone();
+        use_effect_with(
+            (props.device_id.clone(), props.metrics.clone(), props.range, props.refresh_count),
+            move |(device_id, metrics, range, _)| {
+                let device_id = device_id.clone();
+                let metrics = metrics.clone();
+                // Quick ranges end now, so their bounds move with every refresh
+                let (from, to) = range.bounds(Utc::now().timestamp());
+                wasm_bindgen_futures::spawn_local(async move {
+                    let requests = metrics.iter().map(|metric| DeviceService::get_stats(&device_id, metric, from, to));
+                    match future::join_all(requests).await.into_iter().collect::<Result<Vec<_>, _>>() {
+                        Ok(fetched) => {
+                            error.set(None);
+                            stats.set(fetched);
+                        }
+                        Err(e) => {
+                            // The view reports an unknown device already
+                            if e != "404" {
+                                error.set(Some(e));
+                            }
+                            stats.set(Vec::new());
+                        }
+                    }
+                });
+                || ()
+            },
+        );
+    }
+
+    html! {
+        <>
+            if let Some(err) = error.as_ref() {
+                <p class="text-sm text-red-600 dark:text-red-400 mt-6">{format!("Failed to load statistics: {}", err)}</p>
+            }
+            if !stats.is_empty() {
+                // A single card takes the full width
+                <div class={classes!("mt-6", "grid", "grid-cols-1", "gap-4", (stats.len() > 1).then_some("md:grid-cols-2"))}>
+                    { stats.iter().map(|stats| stat_card(stats, &units)).collect::<Html>() }
+                </div>
+            }
+        </>
+    }
+}
+
+/// Renders the card of one metric, converted to the chosen units
+fn stat_card(stats: &MetricStats, units: &UnitPreferences) -> Html {
+    let convert = |value: Option<f64>| value.map(|value| units.convert(&stats.metric, value));
+    let values = [
+        ("Min", convert(stats.min)),
+        ("Max", convert(stats.max)),
+        ("Avg", convert(stats.mean)),
+        ("Latest", convert(stats.latest)),
+    ];
+    html! {
+        <div class="bg-gray-50 dark:bg-gray-900 rounded-lg p-4">
+            <div class="flex items-baseline justify-between mb-2">
+                <h3 class="text-sm font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wide">{units.label(&stats.metric)}</h3>
+                <span class="text-xs text-gray-500 dark:text-gray-400">{format!("{} readings", stats.count)}</span>
+            </div>
+            <div class="grid grid-cols-4 gap-2 text-center">
+                {
+                    values.into_iter().map(|(label, value)| html! {
+                        <div>
+                            <div class="text-xl font-semibold text-gray-900 dark:text-gray-100">{format_stat(value)}</div>
+                            <div class="text-xs text-gray-600 dark:text-gray-300">{label}</div>
+                        </div>
+                    }).collect::<Html>()
+                }
+            </div>
+        </div>
+    }
+}
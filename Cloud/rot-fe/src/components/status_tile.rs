@@ -0,0 +1,62 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import device service for fetching the device status
+use crate::services::device_service::DeviceService;
+// Import the status report and the formatting of its age
+use crate::domain::device::{format_age, DeviceStatusReport};
+
+/// Properties for the StatusTile component
+/// - device_id: Device whose status is shown
+/// - refresh_count: Changes whenever the status should be re-fetched
+#[derive(Properties, PartialEq)]
+pub struct StatusTileProps {
+    pub device_id: String,
+    pub refresh_count: usize,
+}
+
+/// StatusTile component showing whether a device is still reporting and
+/// when it last reported, as judged by the device monitor status API
+#[function_component(StatusTile)]
+pub fn status_tile(props: &StatusTileProps) -> Html {
+    let report = use_state(|| None::<DeviceStatusReport>);
+    let error = use_state(|| None::<String>);
+
+    {
+        let report = report.clone();
+        let error = error.clone();
+        use_effect_with((props.device_id.clone(), props.refresh_count), move |(device_id, _)| {
+            let device_id = device_id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::get_device_status(&device_id).await {
+                    Ok(fetched) => {
+                        error.set(None);
+                        report.set(Some(fetched));
+                    }
+                    Err(e) => {
+                        let message = if e == "404" { "Device not found".to_string() } else { e };
+                        error.set(Some(message));
+                        report.set(None);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <div class="flex flex-col items-center justify-center py-6 gap-2">
+            if let Some(report) = report.as_ref() {
+                <span class={classes!("px-4", "py-1", "rounded-full", "text-lg", "font-semibold", report.status.badge_classes())}>
+                    {report.status.label()}
+                </span>
+                <span class="text-sm text-gray-600 dark:text-gray-300">
+                    {format!("Last reported {}", format_age(report.seconds_since_last_seen))}
+                </span>
+            } else if let Some(err) = error.as_ref() {
+                <p class="text-sm text-red-600 dark:text-red-400">{format!("Failed to load status: {}", err)}</p>
+            } else {
+                <span class="text-sm text-gray-500 dark:text-gray-400">{"Loading..."}</span>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,485 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import web_sys for measuring the drawn chart
+use web_sys::Element;
+// Import device service for fetching and streaming telemetry data
+use crate::services::device_service::DeviceService;
+// Import telemetry data model and timestamp formatting for the tooltip
+use crate::domain::telemetry::{format_timestamp, Telemetry};
+// Import the time range limiting the charted readings
+use crate::domain::preset::TimeRange;
+// Import the thresholds drawn over the chart
+use crate::domain::threshold::{Threshold, ThresholdSource};
+// Import reading formatting for labels
+use crate::domain::anomaly::format_reading;
+// Import the chart geometry
+use crate::domain::plot::{decimate, format_time_tick, line_path, nearest_point, time_ticks, value_ticks, PlotPoint, Scale};
+// Import annotation range conversion for drag-to-zoom
+use crate::domain::annotation::selection_range;
+// Import the banner warning that the charted data failed to refresh or comes from the cache
+use crate::components::StaleBanner;
+// Import the hook reading the units readings are charted in
+use crate::components::use_units;
+use crate::domain::units::UnitPreferences;
+// Import chrono for date/time handling
+use chrono::Utc;
+// Import stream helpers for live telemetry
+use futures::{future, StreamExt};
+use std::rc::Rc;
+
+// Size of the drawing in SVG user units; the SVG scales to its container
+// keeping this aspect ratio, so text is never stretched
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 320.0;
+// Margins around the plot area, leaving room for the axis labels
+const LEFT: f64 = 56.0;
+const RIGHT: f64 = 16.0;
+const TOP: f64 = 12.0;
+const BOTTOM: f64 = 28.0;
+// Rough number of ticks on each axis
+const X_TICKS: usize = 6;
+const Y_TICKS: usize = 5;
+// Drags narrower than this, in SVG user units, are clicks rather than zooms
+const MIN_DRAG: f64 = 4.0;
+
+/// Readings charted, replaced by every fetch and grown by the live stream
+#[derive(Default, PartialEq)]
+struct Readings(Vec<Telemetry>);
+
+/// Changes to the charted readings
+enum ReadingsAction {
+    /// A fetch completed
+    Replace(Vec<Telemetry>),
+    /// The device reported a reading
+    Append(Box<Telemetry>),
+}
+
+impl Reducible for Readings {
+    type Action = ReadingsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            ReadingsAction::Replace(records) => Rc::new(Readings(records)),
+            ReadingsAction::Append(record) => {
+                let mut records = self.0.clone();
+                records.push(*record);
+                Rc::new(Readings(records))
+            }
+        }
+    }
+}
+
+/// Properties for the SvgChart component
+/// - metric_key: Which telemetry key to chart (e.g., "temperature")
+/// - title: Chart title
+/// - device_id: Device ID to fetch data for
+/// - refresh_count: Bumped to refetch the readings
+/// - range: Only chart readings within this time range
+/// - thresholds: Thresholds to draw; only those on metric_key are shown
+/// - on_zoom: Emits the range dragged across, in Unix timestamps
+#[derive(Properties, PartialEq)]
+pub struct SvgChartProps {
+    pub metric_key: String,
+    pub title: String,
+    pub device_id: String,
+    pub refresh_count: usize,
+    #[prop_or_default]
+    pub range: TimeRange,
+    #[prop_or_default]
+    pub thresholds: Vec<Threshold>,
+    #[prop_or_default]
+    pub on_zoom: Callback<(i64, i64)>,
+}
+
+/// SvgChart component drawing a metric's readings as an inline SVG line
+/// chart, without the ApexCharts JavaScript library
+/// Readings stay in Rust from fetch to drawing, and series longer than the
+/// plot is wide are thinned to each pixel's lowest and highest reading, so
+/// large ranges and live updates don't stutter
+/// Hovering shows the nearest reading; dragging across zooms into a range
+#[function_component(SvgChart)]
+pub fn svg_chart(props: &SvgChartProps) -> Html {
+    let svg_ref = use_node_ref();
+    let readings = use_reducer(Readings::default);
+    let loading = use_state(|| true);
+    // When the charted readings were last fetched, when they were cached if
+    // they come from the offline cache, and whether the last fetch failed,
+    // leaving the previous readings on the chart
+    let fetched_at = use_state(|| None::<i64>);
+    let cached_at = use_state(|| None::<i64>);
+    let fetch_failed = use_state(|| false);
+    // Timestamp under the mouse, for the tooltip
+    let hovered = use_state(|| None::<i64>);
+    // Where a drag started and where it is now, in SVG user units
+    let drag = use_state(|| None::<(f64, f64)>);
+    // Units the readings and thresholds are charted in
+    let units = use_units().units;
+
+    // Fetch telemetry data
+    {
+        let readings = readings.dispatcher();
+        let loading = loading.clone();
+        let fetched_at = fetched_at.clone();
+        let cached_at = cached_at.clone();
+        let fetch_failed = fetch_failed.clone();
+        use_effect_with((props.device_id.clone(), props.refresh_count, props.range), move |(device_id, _, range)| {
+            let device_id = device_id.clone();
+            // Quick ranges end now, so their bounds move with every refresh
+            let (from, to) = range.bounds(Utc::now().timestamp());
+            wasm_bindgen_futures::spawn_local(async move {
+                // The cached readings stand in while the API is unreachable
+                match DeviceService::get_telemetry_or_cached(&device_id, from, to).await {
+                    Ok(fetched) => {
+                        readings.dispatch(ReadingsAction::Replace(fetched.data));
+                        if fetched.cached_at.is_none() {
+                            fetched_at.set(Some(Utc::now().timestamp()));
+                        }
+                        cached_at.set(fetched.cached_at);
+                        fetch_failed.set(false);
+                        loading.set(false);
+                    }
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("Failed to fetch telemetry: {}", e).into());
+                        fetch_failed.set(true);
+                        loading.set(false);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    // Append live telemetry as the device reports it
+    // Only ranges ending now can grow
+    {
+        let readings = readings.dispatcher();
+        let live = props.range.bounds(Utc::now().timestamp()).1.is_none();
+        use_effect_with((props.device_id.clone(), live && !*loading), move |(device_id, streaming)| {
+            // Dropping the sender stops the stream task and closes the connection
+            let mut stop = None;
+            if *streaming {
+                match DeviceService::stream_telemetry(device_id) {
+                    Ok((connection, records)) => {
+                        let (sender, stopped) = futures::channel::oneshot::channel::<()>();
+                        stop = Some(sender);
+                        wasm_bindgen_futures::spawn_local(async move {
+                            // Keep the connection open until the task ends
+                            let _connection = connection;
+                            let append = records.for_each(move |record| {
+                                readings.dispatch(ReadingsAction::Append(Box::new(record)));
+                                future::ready(())
+                            });
+                            future::select(Box::pin(append), stopped).await;
+                        });
+                    }
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("Failed to stream telemetry: {}", e).into());
+                    }
+                }
+            }
+            move || drop(stop)
+        });
+    }
+
+    // Readings of the charted metric, converted to the charted units
+    let points = {
+        let metric_key = props.metric_key.clone();
+        use_memo((readings.clone(), units.clone()), move |(readings, units)| {
+            plot_points(&readings.0, &metric_key, units)
+        })
+    };
+    let plot = Plot::new(&points);
+
+    // Thresholds are saved in the reported units, so they are converted like the readings
+    let thresholds: Vec<Threshold> = props
+        .thresholds
+        .iter()
+        .filter(|threshold| threshold.metric == props.metric_key)
+        .map(|threshold| Threshold {
+            value: units.convert(&threshold.metric, threshold.value),
+            ..threshold.clone()
+        })
+        .collect();
+
+    // Position of the mouse along the drawing, in SVG user units
+    let pointer_x = {
+        let svg_ref = svg_ref.clone();
+        move |e: &MouseEvent| {
+            let rect = svg_ref.cast::<Element>()?.get_bounding_client_rect();
+            (rect.width() > 0.0).then(|| (e.client_x() as f64 - rect.left()) / rect.width() * WIDTH)
+        }
+    };
+
+    // Callback for moving the mouse over the chart
+    // Shows the nearest reading, and stretches the drag in progress
+    let on_mouse_move = {
+        let hovered = hovered.clone();
+        let drag = drag.clone();
+        let pointer_x = pointer_x.clone();
+        let plot = plot.clone();
+        Callback::from(move |e: MouseEvent| {
+            let Some((x, plot)) = pointer_x(&e).zip(plot.as_ref()) else {
+                return;
+            };
+            hovered.set(Some(plot.x.invert(x - LEFT).round() as i64));
+            if let Some((start, _)) = *drag {
+                drag.set(Some((start, x)));
+            }
+        })
+    };
+
+    // Callback for pressing the mouse over the chart, starting a drag
+    let on_mouse_down = {
+        let drag = drag.clone();
+        let pointer_x = pointer_x.clone();
+        Callback::from(move |e: MouseEvent| {
+            if let Some(x) = pointer_x(&e) {
+                e.prevent_default();
+                drag.set(Some((x, x)));
+            }
+        })
+    };
+
+    // Callback for releasing the mouse, zooming into the range dragged across
+    let on_mouse_up = {
+        let drag = drag.clone();
+        let on_zoom = props.on_zoom.clone();
+        let plot = plot.clone();
+        Callback::from(move |_: MouseEvent| {
+            match (*drag, plot.as_ref()) {
+                (Some((start, end)), Some(plot)) if (end - start).abs() >= MIN_DRAG => {
+                    on_zoom.emit(selection_range(
+                        plot.x.invert(start.min(end) - LEFT) * 1000.0,
+                        plot.x.invert(start.max(end) - LEFT) * 1000.0,
+                    ));
+                }
+                _ => {}
+            }
+            drag.set(None);
+        })
+    };
+
+    // Callback for the mouse leaving the chart, hiding the tooltip and dropping the drag
+    let on_mouse_leave = {
+        let hovered = hovered.clone();
+        let drag = drag.clone();
+        Callback::from(move |_: MouseEvent| {
+            hovered.set(None);
+            drag.set(None);
+        })
+    };
+
+    let hovered_point = (*hovered).and_then(|timestamp| nearest_point(&points, timestamp));
+    let charted_unit = units.unit(&props.metric_key).map(|unit| format!(" {}", unit)).unwrap_or_default();
+
+    html! {
+        <div class="relative bg-white dark:bg-gray-800 p-5 rounded-lg shadow-lg">
+            <h3 class="text-lg font-semibold mb-4">{&props.title}</h3>
+            // A chart that failed to refresh, or shows cached readings, says so
+            if *fetch_failed || cached_at.is_some() {
+                <StaleBanner fetched_at={*fetched_at} cached_at={*cached_at} failed={*fetch_failed} />
+            }
+            {
+                match (*loading, plot.as_ref()) {
+                    (true, _) => html! {
+                        <div class="flex justify-center items-center h-80">
+                            <div class="text-gray-500 dark:text-gray-400">{"Loading chart data..."}</div>
+                        </div>
+                    },
+                    (false, None) => html! {
+                        <div class="flex justify-center items-center h-80">
+                            <div class="text-gray-500 dark:text-gray-400">{"No readings in this range"}</div>
+                        </div>
+                    },
+                    (false, Some(plot)) => html! {
+                        <>
+                            <div class="relative">
+                                <svg
+                                    ref={svg_ref}
+                                    viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)}
+                                    class="w-full h-auto select-none cursor-crosshair"
+                                    role="img"
+                                    aria-label={format!("{} chart", props.title)}
+                                    onmousemove={on_mouse_move}
+                                    onmousedown={on_mouse_down}
+                                    onmouseup={on_mouse_up}
+                                    onmouseleave={on_mouse_leave}
+                                >
+                                    {view_axes(plot, &props.metric_key, &units)}
+                                    <g transform={format!("translate({},{})", LEFT, TOP)}>
+                                        {thresholds.iter().filter_map(|threshold| view_threshold(plot, threshold)).collect::<Html>()}
+                                        <path
+                                            d={line_path(&plot.points, &plot.x, &plot.y)}
+                                            fill="none"
+                                            stroke="currentColor"
+                                            stroke-width="2"
+                                            stroke-linejoin="round"
+                                            class="text-blue-500 dark:text-blue-400"
+                                        />
+                                        if let Some((start, end)) = *drag {
+                                            <rect
+                                                x={format!("{:.1}", (start.min(end) - LEFT).max(0.0))}
+                                                y="0"
+                                                width={format!("{:.1}", (end - start).abs())}
+                                                height={format!("{:.1}", plot.y.length)}
+                                                fill="currentColor"
+                                                opacity="0.15"
+                                                class="text-blue-500"
+                                            />
+                                        }
+                                        if let Some(point) = hovered_point {
+                                            <line
+                                                x1={format!("{:.1}", plot.x.project(point.x as f64))}
+                                                x2={format!("{:.1}", plot.x.project(point.x as f64))}
+                                                y1="0"
+                                                y2={format!("{:.1}", plot.y.length)}
+                                                stroke="currentColor"
+                                                stroke-dasharray="3 3"
+                                                class="text-gray-400 dark:text-gray-500"
+                                            />
+                                            <circle
+                                                cx={format!("{:.1}", plot.x.project(point.x as f64))}
+                                                cy={format!("{:.1}", plot.y.length - plot.y.project(point.y))}
+                                                r="4"
+                                                fill="currentColor"
+                                                class="text-blue-600 dark:text-blue-300"
+                                            />
+                                        }
+                                    </g>
+                                </svg>
+                                if let Some(point) = hovered_point {
+                                    <div
+                                        class="absolute top-0 z-10 -translate-x-1/2 bg-gray-800 dark:bg-gray-700 text-white text-sm rounded shadow-lg px-3 py-2 pointer-events-none whitespace-nowrap"
+                                        style={format!("left: {:.1}%", (LEFT + plot.x.project(point.x as f64)) / WIDTH * 100.0)}
+                                    >
+                                        <p class="font-semibold">{format!("{}{}", format_reading(point.y), charted_unit)}</p>
+                                        <p class="text-xs text-gray-300">{format_timestamp(point.x)}</p>
+                                    </div>
+                                }
+                            </div>
+                            <p class="text-xs text-gray-400 mt-2">
+                                {"Hover for exact readings, or drag across the chart to zoom into a time range"}
+                            </p>
+                        </>
+                    },
+                }
+            }
+        </div>
+    }
+}
+
+/// Scales of a drawn chart, and the readings thinned to its width
+#[derive(Clone, PartialEq)]
+struct Plot {
+    x: Scale,
+    y: Scale,
+    points: Vec<PlotPoint>,
+}
+
+impl Plot {
+    /// Fits the plot area around the readings, `None` without readings.
+    fn new(points: &[PlotPoint]) -> Option<Self> {
+        let (first, last) = (points.first()?, points.last()?);
+        let (min, max) = points
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), point| (min.min(point.y), max.max(point.y)));
+        // Leave a little room above and below the extremes
+        let pad = (max - min) * 0.05;
+        let width = WIDTH - LEFT - RIGHT;
+        Some(Plot {
+            x: Scale::new(first.x as f64, last.x as f64, width),
+            y: Scale::new(min - pad, max + pad, HEIGHT - TOP - BOTTOM),
+            points: decimate(points, width as usize),
+        })
+    }
+}
+
+/// Draws the grid lines, tick labels and y-axis title
+fn view_axes(plot: &Plot, metric_key: &str, units: &UnitPreferences) -> Html {
+    let (from, to) = (plot.x.min.round() as i64, plot.x.max.round() as i64);
+    html! {
+        <g class="text-gray-500 dark:text-gray-400" font-size="11" fill="currentColor">
+            {
+                value_ticks(plot.y.min, plot.y.max, Y_TICKS).into_iter().map(|value| {
+                    let y = TOP + plot.y.length - plot.y.project(value);
+                    html! {
+                        <>
+                            <line
+                                x1={LEFT.to_string()}
+                                x2={(WIDTH - RIGHT).to_string()}
+                                y1={format!("{:.1}", y)}
+                                y2={format!("{:.1}", y)}
+                                stroke="currentColor"
+                                stroke-opacity="0.25"
+                            />
+                            <text x={(LEFT - 6.0).to_string()} y={format!("{:.1}", y + 4.0)} text-anchor="end">
+                                {format_reading(value)}
+                            </text>
+                        </>
+                    }
+                }).collect::<Html>()
+            }
+            {
+                time_ticks(from, to, X_TICKS).into_iter().map(|timestamp| {
+                    let x = LEFT + plot.x.project(timestamp as f64);
+                    html! {
+                        <text x={format!("{:.1}", x)} y={(HEIGHT - 8.0).to_string()} text-anchor="middle">
+                            {format_time_tick(timestamp, to - from)}
+                        </text>
+                    }
+                }).collect::<Html>()
+            }
+            <text
+                transform={format!("translate(12,{:.1}) rotate(-90)", TOP + plot.y.length / 2.0)}
+                text-anchor="middle"
+            >
+                {units.label(metric_key)}
+            </text>
+        </g>
+    }
+}
+
+/// Draws a threshold as a dashed line, red for alert rules and indigo for
+/// the user's own, `None` if it lies outside the charted values
+fn view_threshold(plot: &Plot, threshold: &Threshold) -> Option<Html> {
+    if threshold.value < plot.y.min || threshold.value > plot.y.max {
+        return None;
+    }
+    let (class, text) = match threshold.source {
+        ThresholdSource::AlertRule => ("text-red-500 dark:text-red-400", format!("Alert {} {}", threshold.operator.symbol(), format_reading(threshold.value))),
+        ThresholdSource::User => ("text-indigo-500 dark:text-indigo-400", format!("{} {}", threshold.operator.symbol(), format_reading(threshold.value))),
+    };
+    let y = plot.y.length - plot.y.project(threshold.value);
+    Some(html! {
+        <g class={class}>
+            <line
+                x1="0"
+                x2={format!("{:.1}", plot.x.length)}
+                y1={format!("{:.1}", y)}
+                y2={format!("{:.1}", y)}
+                stroke="currentColor"
+                stroke-dasharray="6 4"
+            />
+            <text x={format!("{:.1}", plot.x.length - 4.0)} y={format!("{:.1}", y - 4.0)} text-anchor="end" font-size="11" fill="currentColor">
+                {text}
+            </text>
+        </g>
+    })
+}
+
+/// Readings of a metric as chart points, oldest first, in the charted units
+fn plot_points(telemetry_data: &[Telemetry], metric_key: &str, units: &UnitPreferences) -> Vec<PlotPoint> {
+    let mut points: Vec<PlotPoint> = telemetry_data
+        .iter()
+        .filter_map(|telemetry| {
+            // Non-finite readings such as "NaN" can't be placed on the chart
+            let value = telemetry.telemetry_data.get(metric_key)?.parse::<f64>().ok().filter(|value| value.is_finite())?;
+            Some(PlotPoint {
+                x: telemetry.timestamp?,
+                y: units.convert(metric_key, value),
+            })
+        })
+        .collect();
+    points.sort_by_key(|point| point.x);
+    points
+}
@@ -0,0 +1,255 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import device service for fetching pages of telemetry
+use crate::services::device_service::DeviceService;
+// Import the table's pages, columns and sorting
+use crate::domain::table::{sort_records, table_columns, TableSort, TelemetryPage, TABLE_PAGE_SIZE};
+// Import the telemetry record and timestamp formatting shared with the telemetry view
+use crate::domain::telemetry::{format_timestamp, Telemetry};
+// Import the time range limiting the listed records
+use crate::domain::preset::TimeRange;
+// Import the inspector showing a record's raw document
+use crate::components::JsonInspector;
+// Import chrono for resolving quick ranges, which end now
+use chrono::Utc;
+// Import a set for the records whose document is shown
+use std::collections::HashSet;
+// Import the units values are shown in
+use crate::components::use_units;
+
+/// Properties for the TelemetryTable component
+/// - device_id: Device whose records are listed
+/// - range: Time range the records are listed from
+///
+/// The table starts over from the first page when it is re-created, so
+/// give it a `key` that changes with the device, range and refreshes
+#[derive(Properties, PartialEq)]
+pub struct TelemetryTableProps {
+    pub device_id: String,
+    #[prop_or_default]
+    pub range: TimeRange,
+}
+
+/// TelemetryTable component listing raw telemetry records, a timestamp and
+/// a column per metric, with exact values, converted only for metrics the
+/// user chose other units for
+/// Pages are fetched newest first from the read API; clicking a column
+/// header sorts the page on screen by that column, and a record's `{ }`
+/// button shows the raw document it was read from
+#[function_component(TelemetryTable)]
+pub fn telemetry_table(props: &TelemetryTableProps) -> Html {
+    // Bounds of the range, fixed when the table is created so every page
+    // comes from the same window even as a quick range moves on
+    let bounds = use_state(|| props.range.bounds(Utc::now().timestamp()));
+    // Tokens of the pages visited, the current page last; the first page has none
+    let tokens = use_state(|| vec![None::<String>]);
+    let page = use_state(TelemetryPage::default);
+    let sort = use_state(TableSort::default);
+    // Records whose raw document is shown, by `record_key`
+    let inspected = use_state(HashSet::<String>::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+    let units = use_units().units;
+
+    // Fetch the current page whenever another page is visited
+    {
+        let page = page.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        let (from, to) = *bounds;
+        use_effect_with((props.device_id.clone(), (*tokens).clone()), move |(device_id, tokens)| {
+            let device_id = device_id.clone();
+            let continuation = tokens.last().cloned().flatten();
+            loading.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::get_telemetry_page(&device_id, from, to, TABLE_PAGE_SIZE, continuation.as_deref()).await {
+                    Ok(fetched) => {
+                        error.set(None);
+                        page.set(fetched);
+                    }
+                    Err(e) if e == "404" => {
+                        error.set(None);
+                        page.set(TelemetryPage::default());
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    // Callback for moving to the next page
+    let on_next = {
+        let tokens = tokens.clone();
+        let continuation = page.continuation.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(continuation) = &continuation {
+                let mut visited = (*tokens).clone();
+                visited.push(Some(continuation.clone()));
+                tokens.set(visited);
+            }
+        })
+    };
+
+    // Callback for moving back to the previous page
+    let on_previous = {
+        let tokens = tokens.clone();
+        Callback::from(move |_: MouseEvent| {
+            if tokens.len() > 1 {
+                let mut visited = (*tokens).clone();
+                visited.pop();
+                tokens.set(visited);
+            }
+        })
+    };
+
+    // Callback for showing or hiding a record's raw document
+    let on_inspect = {
+        let inspected = inspected.clone();
+        Callback::from(move |key: String| {
+            let mut updated = (*inspected).clone();
+            if !updated.remove(&key) {
+                updated.insert(key);
+            }
+            inspected.set(updated);
+        })
+    };
+
+    // Callback for sorting by a column, `None` for the timestamp
+    let on_sort = {
+        let sort = sort.clone();
+        Callback::from(move |column: Option<String>| sort.set(sort.toggle(column)))
+    };
+
+    let columns = table_columns(&page.records);
+    let mut records = page.records.clone();
+    sort_records(&mut records, &sort);
+    let page_number = tokens.len();
+
+    let header = |label: String, column: Option<String>| {
+        let indicator = sort.indicator(column.as_deref());
+        let on_click = on_sort.reform(move |_: MouseEvent| column.clone());
+        html! {
+            <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300 whitespace-nowrap">
+                <button type="button" onclick={on_click} class="hover:text-gray-900 dark:hover:text-gray-100">
+                    {format!("{}{}", label, indicator)}
+                </button>
+            </th>
+        }
+    };
+
+    html! {
+        <div class="mt-8">
+            if let Some(err) = error.as_ref() {
+                <p class="text-sm text-red-600 dark:text-red-400 mb-2">{format!("Failed to load telemetry: {}", err)}</p>
+            }
+            if !page.unreadable.is_empty() {
+                <details class="mb-2 text-sm text-yellow-800 dark:text-yellow-200 bg-yellow-50 dark:bg-yellow-950 border border-yellow-200 dark:border-yellow-800 rounded px-3 py-2">
+                    <summary class="cursor-pointer">
+                        {format!("{} records on this page could not be read", page.unreadable.len())}
+                    </summary>
+                    {
+                        page.unreadable.iter().map(|unreadable| html! {
+                            <div class="mt-2">
+                                <p class="font-mono text-xs mb-1">{&unreadable.error}</p>
+                                <JsonInspector document={unreadable.document.clone()} />
+                            </div>
+                        }).collect::<Html>()
+                    }
+                </details>
+            }
+            <div class={classes!("overflow-x-auto", "rounded-lg", "border", "dark:border-gray-700", loading.then_some("opacity-50"))}>
+                <table class="min-w-full text-sm">
+                    <thead class="bg-gray-50 dark:bg-gray-900">
+                        <tr>
+                            { header("Timestamp".to_string(), None) }
+                            { columns.iter().map(|column| header(units.label(column), Some(column.clone()))).collect::<Html>() }
+                            <th class="px-3 py-2"><span class="sr-only">{"Raw document"}</span></th>
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-gray-200 dark:divide-gray-700">
+                        {
+                            records.iter().enumerate().map(|(index, record)| {
+                                let key = record_key(record, index);
+                                let open = inspected.contains(&key);
+                                let on_click = on_inspect.reform(move |_: MouseEvent| key.clone());
+                                html! {
+                                    <>
+                                        <tr class="hover:bg-gray-50 dark:hover:bg-gray-700">
+                                            <td class="px-3 py-2 text-gray-700 dark:text-gray-200 whitespace-nowrap">
+                                                {record.timestamp.map(format_timestamp).unwrap_or_default()}
+                                            </td>
+                                            {
+                                                columns.iter().map(|column| html! {
+                                                    <td class="px-3 py-2 text-gray-900 dark:text-gray-100 font-mono">
+                                                        {record.telemetry_data.get(column).map(|value| units.format_raw(column, value, false)).unwrap_or_default()}
+                                                    </td>
+                                                }).collect::<Html>()
+                                            }
+                                            <td class="px-3 py-2 text-right">
+                                                if record.document.is_some() {
+                                                    <button
+                                                        type="button"
+                                                        onclick={on_click}
+                                                        title={if open { "Hide raw JSON" } else { "Show raw JSON" }}
+                                                        class={classes!(
+                                                            "px-2", "rounded", "font-mono", "text-xs", "transition",
+                                                            if open {
+                                                                "bg-indigo-100 dark:bg-indigo-900 text-indigo-800 dark:text-indigo-200"
+                                                            } else {
+                                                                "text-gray-500 dark:text-gray-400 hover:bg-gray-200 dark:hover:bg-gray-600"
+                                                            }
+                                                        )}
+                                                    >
+                                                        {"{ }"}
+                                                    </button>
+                                                }
+                                            </td>
+                                        </tr>
+                                        if let Some(document) = record.document.as_ref().filter(|_| open) {
+                                            <tr>
+                                                <td colspan={(columns.len() + 2).to_string()} class="px-3 py-2 bg-gray-50 dark:bg-gray-900">
+                                                    <JsonInspector document={document.clone()} />
+                                                </td>
+                                            </tr>
+                                        }
+                                    </>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </tbody>
+                </table>
+                if records.is_empty() && !*loading {
+                    <div class="text-center text-gray-500 dark:text-gray-400 py-8">{"No telemetry in this time range"}</div>
+                }
+            </div>
+            <div class="flex items-center justify-between mt-3 text-sm text-gray-600 dark:text-gray-300">
+                <button
+                    type="button"
+                    onclick={on_previous}
+                    disabled={page_number == 1 || *loading}
+                    class="px-3 py-1 rounded bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 disabled:opacity-50 transition"
+                >
+                    {"← Newer"}
+                </button>
+                <span>{format!("Page {}", page_number)}</span>
+                <button
+                    type="button"
+                    onclick={on_next}
+                    disabled={page.continuation.is_none() || *loading}
+                    class="px-3 py-1 rounded bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 disabled:opacity-50 transition"
+                >
+                    {"Older →"}
+                </button>
+            </div>
+        </div>
+    }
+}
+
+/// Identifies a record among the records shown, to remember whether its
+/// document is shown when the page is sorted: its ID, or its position in
+/// the sorted page if it has none
+fn record_key(record: &Telemetry, index: usize) -> String {
+    record.id.clone().unwrap_or_else(|| format!("#{}", index))
+}
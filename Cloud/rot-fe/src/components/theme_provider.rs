@@ -0,0 +1,88 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import the colour theme model
+use crate::domain::theme::Theme;
+// Import the preferences service persisting the chosen theme
+use crate::services::preferences_service::PreferencesService;
+
+/// Theme shared with every component below the ThemeProvider
+/// - theme: The active colour theme
+/// - toggle: Callback switching to the other theme
+#[derive(Clone, PartialEq)]
+pub struct ThemeContext {
+    pub theme: Theme,
+    pub toggle: Callback<()>,
+}
+
+/// Properties for the ThemeProvider component
+/// - children: The components that can read the theme
+#[derive(Properties, PartialEq)]
+pub struct ThemeProviderProps {
+    pub children: Html,
+}
+
+/// ThemeProvider component holding the colour theme of the application
+/// Starts from the theme saved in local storage, or the system preference
+/// if none was chosen, and applies it by toggling the `dark` class on the
+/// document root so the `dark:` Tailwind variants take effect
+#[function_component(ThemeProvider)]
+pub fn theme_provider(props: &ThemeProviderProps) -> Html {
+    // The active theme, restored from the last session if one was chosen
+    let theme = use_state(|| {
+        PreferencesService::load_theme()
+            .ok()
+            .flatten()
+            .unwrap_or_else(system_theme)
+    });
+
+    // Apply the theme to the document whenever it changes
+    use_effect_with(*theme, |theme| {
+        let root = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.document_element());
+        if let Some(root) = root {
+            let _ = root.class_list().toggle_with_force("dark", theme.is_dark());
+        }
+        || ()
+    });
+
+    // Callback for the navbar toggle
+    // Switches and persists the theme, so it survives page reloads
+    let toggle = {
+        let theme = theme.clone();
+        Callback::from(move |_| {
+            let next = theme.toggled();
+            if let Err(e) = PreferencesService::save_theme(next) {
+                web_sys::console::log_1(&format!("Failed to save theme: {}", e).into());
+            }
+            theme.set(next);
+        })
+    };
+
+    let context = ThemeContext { theme: *theme, toggle };
+
+    html! {
+        <ContextProvider<ThemeContext> context={context}>
+            { props.children.clone() }
+        </ContextProvider<ThemeContext>>
+    }
+}
+
+/// Hook returning the theme shared by the ThemeProvider
+/// Components outside a provider get the light theme and a no-op toggle
+#[hook]
+pub fn use_theme() -> ThemeContext {
+    use_context::<ThemeContext>().unwrap_or_else(|| ThemeContext {
+        theme: Theme::default(),
+        toggle: Callback::noop(),
+    })
+}
+
+/// Theme requested by the operating system or browser
+/// Dark if `prefers-color-scheme: dark` matches, light otherwise
+fn system_theme() -> Theme {
+    let prefers_dark = web_sys::window()
+        .and_then(|window| window.match_media("(prefers-color-scheme: dark)").ok().flatten())
+        .is_some_and(|query| query.matches());
+    if prefers_dark { Theme::Dark } else { Theme::Light }
+}
@@ -0,0 +1,166 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import the operators a threshold compares readings with
+use crate::domain::alert::Operator;
+// Import the threshold model and the rule adding thresholds
+use crate::domain::threshold::{add_threshold, Threshold};
+// Import the units thresholds are typed and shown in
+use crate::components::use_units;
+
+/// Properties for the ThresholdEditor component
+/// - thresholds: The user's thresholds, shown as removable chips
+/// - metrics: Metrics offered for new thresholds
+/// - on_change: Callback emitting the thresholds after one is added or removed
+#[derive(Properties, PartialEq)]
+pub struct ThresholdEditorProps {
+    pub thresholds: Vec<Threshold>,
+    pub metrics: Vec<String>,
+    pub on_change: Callback<Vec<Threshold>>,
+}
+
+/// ThresholdEditor component for setting the thresholds drawn on the charts
+/// Renders a chip per threshold and a metric, operator and value form to add one
+/// Values are typed and shown in the units the user chose, and saved in the
+/// units the devices report, like the readings they are compared with
+#[function_component(ThresholdEditor)]
+pub fn threshold_editor(props: &ThresholdEditorProps) -> Html {
+    // Fields of the form; the metric defaults to the first one offered
+    let metric = use_state(String::new);
+    let operator = use_state(Operator::default);
+    let value = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let units = use_units().units;
+
+    let chosen_metric = if props.metrics.contains(&metric) {
+        (*metric).clone()
+    } else {
+        props.metrics.first().cloned().unwrap_or_default()
+    };
+
+    // Callback for choosing the metric
+    let on_metric_change = {
+        let metric = metric.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            metric.set(select.value());
+        })
+    };
+
+    // Callback for choosing the operator
+    let on_operator_change = {
+        let operator = operator.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Some(selected) = Operator::from_key(&select.value()) {
+                operator.set(selected);
+            }
+        })
+    };
+
+    // Callback for typing the value
+    let on_value_input = {
+        let value = value.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            value.set(input.value());
+        })
+    };
+
+    // Callback for the "Add threshold" button
+    // Emits the thresholds with the new one and clears the value
+    let on_add_click = {
+        let thresholds = props.thresholds.clone();
+        let on_change = props.on_change.clone();
+        let chosen_metric = chosen_metric.clone();
+        let operator = operator.clone();
+        let value = value.clone();
+        let error = error.clone();
+        let units = units.clone();
+        Callback::from(move |_| match Threshold::parse(&chosen_metric, *operator, &value) {
+            Ok(mut threshold) => {
+                threshold.value = units.revert(&threshold.metric, threshold.value);
+                let mut updated = thresholds.clone();
+                add_threshold(&mut updated, threshold);
+                on_change.emit(updated);
+                value.set(String::new());
+                error.set(None);
+            }
+            Err(e) => error.set(Some(e)),
+        })
+    };
+
+    html! {
+        <div class="mb-6">
+            <span class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Thresholds"}</span>
+            <div class="flex flex-wrap items-center gap-2">
+                {
+                    props.thresholds.iter().enumerate().map(|(index, threshold)| {
+                        let on_remove = {
+                            let thresholds = props.thresholds.clone();
+                            props.on_change.reform(move |_: MouseEvent| {
+                                let mut updated = thresholds.clone();
+                                updated.remove(index);
+                                updated
+                            })
+                        };
+                        html! {
+                            <span class="flex items-center gap-1 px-2 py-1 rounded-full text-xs bg-indigo-100 dark:bg-indigo-900 text-indigo-800 dark:text-indigo-200">
+                                {threshold.describe(&units)}
+                                <button
+                                    type="button"
+                                    onclick={on_remove}
+                                    title="Remove threshold"
+                                    class="hover:text-indigo-600 dark:hover:text-indigo-400"
+                                >
+                                    {"✕"}
+                                </button>
+                            </span>
+                        }
+                    }).collect::<Html>()
+                }
+                <select
+                    aria-label="Threshold metric"
+                    onchange={on_metric_change}
+                    class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-2 py-1"
+                >
+                    {
+                        props.metrics.iter().map(|option| html! {
+                            <option value={option.clone()} selected={*option == chosen_metric}>{option}</option>
+                        }).collect::<Html>()
+                    }
+                </select>
+                <select
+                    aria-label="Threshold condition"
+                    onchange={on_operator_change}
+                    class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-2 py-1"
+                >
+                    {
+                        Operator::ALL.iter().map(|option| html! {
+                            <option value={option.key()} selected={*option == *operator}>{option.symbol()}</option>
+                        }).collect::<Html>()
+                    }
+                </select>
+                <input
+                    type="number"
+                    step="any"
+                    aria-label="Threshold value"
+                    value={(*value).clone()}
+                    oninput={on_value_input}
+                    class="w-24 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-2 py-1"
+                    placeholder={units.unit(&chosen_metric).map_or_else(|| "Value".to_string(), |unit| format!("Value ({})", unit))}
+                />
+                <button
+                    type="button"
+                    onclick={on_add_click}
+                    disabled={props.metrics.is_empty()}
+                    class="px-3 py-1 rounded bg-indigo-600 text-white text-sm font-semibold shadow hover:bg-indigo-700 transition"
+                >
+                    {"Add threshold"}
+                </button>
+            </div>
+            if let Some(err) = error.as_ref() {
+                <p class="text-sm text-red-600 dark:text-red-400 mt-1">{err}</p>
+            }
+        </div>
+    }
+}
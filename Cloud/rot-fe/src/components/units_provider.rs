@@ -0,0 +1,67 @@
+// Import Yew framework prelude for component development
+use yew::prelude::*;
+// Import the units telemetry is shown in
+use crate::domain::units::UnitPreferences;
+// Import the preferences service persisting the chosen units
+use crate::services::preferences_service::PreferencesService;
+
+/// Units shared with every component below the UnitsProvider
+/// - units: The units telemetry is shown in
+/// - set: Callback applying and saving other units
+#[derive(Clone, PartialEq)]
+pub struct UnitsContext {
+    pub units: UnitPreferences,
+    pub set: Callback<UnitPreferences>,
+}
+
+/// Properties for the UnitsProvider component
+/// - children: The components that can read the units
+#[derive(Properties, PartialEq)]
+pub struct UnitsProviderProps {
+    pub children: Html,
+}
+
+/// UnitsProvider component holding the units telemetry is shown in
+/// Starts from the units saved in local storage, or the units the devices
+/// report if none were chosen, so stat cards, tables, tooltips and chart
+/// axes all convert readings the same way
+#[function_component(UnitsProvider)]
+pub fn units_provider(props: &UnitsProviderProps) -> Html {
+    // The chosen units, restored from the last session
+    let units = use_state(|| {
+        PreferencesService::load_units().unwrap_or_else(|e| {
+            web_sys::console::log_1(&format!("Failed to load units: {}", e).into());
+            UnitPreferences::default()
+        })
+    });
+
+    // Callback for the navbar toggle and the settings page
+    // Persists the units, so they survive page reloads
+    let set = {
+        let units = units.clone();
+        Callback::from(move |next: UnitPreferences| {
+            if let Err(e) = PreferencesService::save_units(&next) {
+                web_sys::console::log_1(&format!("Failed to save units: {}", e).into());
+            }
+            units.set(next);
+        })
+    };
+
+    let context = UnitsContext { units: (*units).clone(), set };
+
+    html! {
+        <ContextProvider<UnitsContext> context={context}>
+            { props.children.clone() }
+        </ContextProvider<UnitsContext>>
+    }
+}
+
+/// Hook returning the units shared by the UnitsProvider
+/// Components outside a provider get the reported units and a no-op setter
+#[hook]
+pub fn use_units() -> UnitsContext {
+    use_context::<UnitsContext>().unwrap_or_else(|| UnitsContext {
+        units: UnitPreferences::default(),
+        set: Callback::noop(),
+    })
+}
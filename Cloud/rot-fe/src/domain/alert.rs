@@ -0,0 +1,263 @@
+/// # Alert Domain Models
+///
+/// This module defines threshold alert rules, such as "temperature above 30
+/// for 5 minutes", and the alerts they raise, as served by the device
+/// monitor alerts API (`/iot/data/alerts`). Operators acknowledge an alert
+/// when they take it on and resolve it once it is dealt with.
+
+use serde::{Deserialize, Serialize};
+
+/// Longest duration a condition can be required to hold for, in seconds (one day)
+pub const MAX_ALERT_DURATION_SECS: i64 = 24 * 60 * 60;
+
+/// Comparison of a reading with a rule's threshold.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    /// Reading above the threshold
+    #[default]
+    Gt,
+    /// Reading at or above the threshold
+    Gte,
+    /// Reading below the threshold
+    Lt,
+    /// Reading at or below the threshold
+    Lte,
+    /// Reading equal to the threshold
+    Eq,
+    /// Reading different from the threshold
+    Ne,
+}
+
+impl Operator {
+    /// Every operator, in the order offered by the rule form.
+    pub const ALL: [Operator; 6] = [Operator::Gt, Operator::Gte, Operator::Lt, Operator::Lte, Operator::Eq, Operator::Ne];
+
+    /// Stable identifier used as the value of the operator selector, as sent to the API.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Operator::Gt => "gt",
+            Operator::Gte => "gte",
+            Operator::Lt => "lt",
+            Operator::Lte => "lte",
+            Operator::Eq => "eq",
+            Operator::Ne => "ne",
+        }
+    }
+
+    /// Comparison sign shown in rule and alert descriptions.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Gt => ">",
+            Operator::Gte => "≥",
+            Operator::Lt => "<",
+            Operator::Lte => "≤",
+            Operator::Eq => "=",
+            Operator::Ne => "≠",
+        }
+    }
+
+    /// Parses an operator from its `key()`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|operator| operator.key() == key)
+    }
+}
+
+/// A threshold rule on one metric of a device.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AlertRule {
+    /// Unique identifier assigned by the API
+    pub id: String,
+    /// The device the rule watches
+    pub device_id: String,
+    /// The metric compared with the threshold
+    pub metric: String,
+    /// How the metric is compared with the threshold
+    pub operator: Operator,
+    /// The threshold
+    pub value: f64,
+    /// How long, in seconds, the condition must hold before an alert is raised
+    pub duration_secs: i64,
+}
+
+impl AlertRule {
+    /// Describes the rule's condition, e.g. "temperature > 30 for 5 min".
+    pub fn describe(&self) -> String {
+        let condition = format!("{} {} {}", self.metric, self.operator.symbol(), self.value);
+        if self.duration_secs > 0 {
+            format!("{} for {}", condition, format_duration(self.duration_secs))
+        } else {
+            condition
+        }
+    }
+}
+
+/// Body of a request creating or replacing an alert rule.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AlertRuleRequest {
+    /// The metric compared with the threshold
+    pub metric: String,
+    /// How the metric is compared with the threshold
+    pub operator: Operator,
+    /// The threshold
+    pub value: f64,
+    /// How long, in seconds, the condition must hold
+    pub duration_secs: i64,
+}
+
+impl AlertRuleRequest {
+    /// Builds a request from the fields of the rule form, with the checks the API applies.
+    ///
+    /// # Parameters
+    /// * `metric` - The metric field
+    /// * `operator` - The chosen operator
+    /// * `value` - The threshold field
+    /// * `duration_secs` - The duration field, in seconds; blank for none
+    ///
+    /// # Returns
+    /// * `Ok(AlertRuleRequest)` - The request, with the metric trimmed
+    /// * `Err(String)` - Why the fields are not a valid rule
+    pub fn parse(metric: &str, operator: Operator, value: &str, duration_secs: &str) -> Result<Self, String> {
+        let metric = metric.trim();
+        if metric.is_empty() {
+            return Err("Enter the metric the rule watches.".to_string());
+        }
+        let value = value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| "The threshold must be a number.".to_string())?;
+        let duration_secs = match duration_secs.trim() {
+            "" => 0,
+            duration => duration
+                .parse::<i64>()
+                .ok()
+                .filter(|secs| (0..=MAX_ALERT_DURATION_SECS).contains(secs))
+                .ok_or_else(|| format!("The duration must be whole seconds between 0 and {}.", MAX_ALERT_DURATION_SECS))?,
+        };
+        Ok(AlertRuleRequest { metric: metric.to_string(), operator, value, duration_secs })
+    }
+}
+
+/// Who acknowledged or resolved an alert, and when.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AlertAction {
+    /// The caller that took the action
+    pub by: String,
+    /// When the action was taken (RFC 3339)
+    pub at: String,
+}
+
+/// Where an alert is in its handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertStatus {
+    /// Raised and not yet looked at
+    Open,
+    /// Taken on by an operator
+    Acknowledged,
+    /// Dealt with
+    Resolved,
+}
+
+impl AlertStatus {
+    /// Every status, in the order offered by the status filter.
+    pub const ALL: [AlertStatus; 3] = [AlertStatus::Open, AlertStatus::Acknowledged, AlertStatus::Resolved];
+
+    /// Stable identifier used as the value of the status filter.
+    pub fn key(&self) -> &'static str {
+        match self {
+            AlertStatus::Open => "open",
+            AlertStatus::Acknowledged => "acknowledged",
+            AlertStatus::Resolved => "resolved",
+        }
+    }
+
+    /// Human-readable name shown in the status badge.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertStatus::Open => "Open",
+            AlertStatus::Acknowledged => "Acknowledged",
+            AlertStatus::Resolved => "Resolved",
+        }
+    }
+
+    /// Tailwind classes colouring the status badge.
+    pub fn badge_classes(&self) -> &'static str {
+        match self {
+            AlertStatus::Open => "bg-red-100 dark:bg-red-900 text-red-800 dark:text-red-200",
+            AlertStatus::Acknowledged => "bg-yellow-100 dark:bg-yellow-900 text-yellow-800 dark:text-yellow-200",
+            AlertStatus::Resolved => "bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200",
+        }
+    }
+
+    /// Parses a status from its `key()`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.key() == key)
+    }
+}
+
+/// An alert raised by a rule.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Alert {
+    /// Unique identifier assigned by the API
+    pub id: String,
+    /// The rule that raised the alert
+    pub rule_id: String,
+    /// The device the alert is about
+    pub device_id: String,
+    /// The metric that met the condition
+    pub metric: String,
+    /// The rule's comparison
+    pub operator: Operator,
+    /// The rule's threshold
+    pub threshold: f64,
+    /// The reading that raised the alert
+    pub value: f64,
+    /// Unix timestamp of the first reading of the breach
+    pub breach_started: i64,
+    /// Unix timestamp of the reading that raised the alert
+    pub triggered_at: i64,
+    /// Who acknowledged the alert, if anyone did
+    #[serde(default)]
+    pub acknowledged: Option<AlertAction>,
+    /// Who resolved the alert, if anyone did
+    #[serde(default)]
+    pub resolved: Option<AlertAction>,
+}
+
+impl Alert {
+    /// Where the alert is in its handling.
+    pub fn status(&self) -> AlertStatus {
+        if self.resolved.is_some() {
+            AlertStatus::Resolved
+        } else if self.acknowledged.is_some() {
+            AlertStatus::Acknowledged
+        } else {
+            AlertStatus::Open
+        }
+    }
+
+    /// Describes what raised the alert, e.g. "temperature 31.2 > 30".
+    pub fn describe(&self) -> String {
+        format!("{} {} {} {}", self.metric, self.value, self.operator.symbol(), self.threshold)
+    }
+}
+
+/// Formats a rule's duration for rule descriptions.
+///
+/// # Parameters
+/// * `secs` - Duration in seconds
+///
+/// # Returns
+/// * Label such as "45s", "5 min" or "2 h", in whole minutes or hours
+///   when the duration divides evenly
+fn format_duration(secs: i64) -> String {
+    if secs % 3600 == 0 {
+        format!("{} h", secs / 3600)
+    } else if secs % 60 == 0 {
+        format!("{} min", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
@@ -0,0 +1,105 @@
+/// # Telemetry Annotation Domain Models
+///
+/// This module defines the notes operators attach to a device's telemetry
+/// timeline, as stored by the device monitor annotations API. An annotation
+/// covers a time range, or a single instant when it starts and ends at the
+/// same time.
+
+use serde::{Deserialize, Serialize};
+use chrono::DateTime;
+
+/// A note attached to a device's telemetry timeline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Annotation {
+    /// Unique identifier assigned by the API
+    pub id: String,
+    /// Device the annotation belongs to
+    pub device_id: String,
+    /// Unix timestamp at which the annotated period starts
+    pub start: i64,
+    /// Unix timestamp at which the annotated period ends
+    pub end: i64,
+    /// The note itself
+    pub text: String,
+    /// Who wrote the note
+    pub author: String,
+}
+
+impl Annotation {
+    /// Whether the annotation marks a single instant rather than a range.
+    pub fn is_instant(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Body of a request creating an annotation.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AnnotationRequest {
+    /// Unix timestamp at which the annotated period starts
+    pub start: i64,
+    /// Unix timestamp at which the annotated period ends (omitted for an instant)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<i64>,
+    /// The note itself
+    pub text: String,
+    /// Who wrote the note
+    pub author: String,
+}
+
+impl AnnotationRequest {
+    /// Builds a request for a selected time range.
+    ///
+    /// A range that starts and ends at the same second is sent as an instant.
+    ///
+    /// # Parameters
+    /// * `range` - Start and end of the selection as Unix timestamps
+    /// * `text` - The note
+    /// * `author` - Who wrote the note
+    pub fn for_range(range: (i64, i64), text: &str, author: &str) -> Self {
+        let (start, end) = range;
+        AnnotationRequest {
+            start,
+            end: (end != start).then_some(end),
+            text: text.trim().to_string(),
+            author: author.trim().to_string(),
+        }
+    }
+}
+
+/// Converts a chart selection, in milliseconds, into a Unix timestamp range.
+///
+/// The range is widened to whole seconds so the selected readings are
+/// always covered.
+///
+/// # Parameters
+/// * `min_ms` - Start of the selection in milliseconds since the epoch
+/// * `max_ms` - End of the selection in milliseconds since the epoch
+pub fn selection_range(min_ms: f64, max_ms: f64) -> (i64, i64) {
+    let start = (min_ms.min(max_ms) / 1000.0).floor() as i64;
+    let end = (min_ms.max(max_ms) / 1000.0).ceil() as i64;
+    (start.max(0), end.max(0))
+}
+
+/// Formats an annotation time range for display.
+///
+/// # Parameters
+/// * `start` - Start of the range as a Unix timestamp
+/// * `end` - End of the range as a Unix timestamp
+///
+/// # Returns
+/// * e.g. "2025-01-01 10:00:00 - 10:30:00 UTC", or a single time for an instant
+pub fn format_range(start: i64, end: i64) -> String {
+    let format = |timestamp: i64, pattern: &str| {
+        DateTime::from_timestamp(timestamp, 0)
+            .map(|dt| dt.format(pattern).to_string())
+            .unwrap_or_else(|| timestamp.to_string())
+    };
+
+    if start == end {
+        format!("{} UTC", format(start, "%Y-%m-%d %H:%M:%S"))
+    } else if start / 86_400 == end / 86_400 {
+        format!("{} - {} UTC", format(start, "%Y-%m-%d %H:%M:%S"), format(end, "%H:%M:%S"))
+    } else {
+        format!("{} - {} UTC", format(start, "%Y-%m-%d %H:%M:%S"), format(end, "%Y-%m-%d %H:%M:%S"))
+    }
+}
@@ -0,0 +1,62 @@
+/// # Anomaly Domain Models
+///
+/// This module defines the anomalous readings flagged by the device monitor
+/// anomaly endpoint (`/iot/data/anomalies/{device_id}`). Each reading of a
+/// metric is scored by how many standard deviations it lies from the value
+/// expected from the readings before it; readings scoring beyond the
+/// threshold are flagged, and marked on the telemetry charts.
+
+use serde::Deserialize;
+
+/// A reading flagged as anomalous.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Anomaly {
+    /// Unix timestamp of the reading
+    pub timestamp: i64,
+    /// The reading
+    pub value: f64,
+    /// The value expected from the earlier readings
+    pub expected: f64,
+    /// Deviation from the expected value, in standard deviations (negative below it)
+    pub score: f64,
+}
+
+impl Anomaly {
+    /// Short label drawn next to the marker, e.g. "+9.8σ".
+    pub fn label(&self) -> String {
+        format!("{:+.1}σ", self.score)
+    }
+
+    /// Explains the score, e.g. "9.8 standard deviations above the expected 22.3".
+    pub fn describe(&self) -> String {
+        let side = if self.score < 0.0 { "below" } else { "above" };
+        format!(
+            "{:.1} standard deviations {} the expected {}",
+            self.score.abs(),
+            side,
+            format_reading(self.expected)
+        )
+    }
+}
+
+/// The anomalies of a device's metric, as reported by the anomaly endpoint.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AnomalyReport {
+    /// The device whose telemetry was analyzed
+    pub device_id: String,
+    /// The metric that was analyzed
+    pub metric: String,
+    /// The score above which readings were flagged, in standard deviations
+    pub threshold: f64,
+    /// Number of numeric readings analyzed
+    pub points: usize,
+    /// The anomalous readings, oldest first
+    #[serde(default)]
+    pub anomalies: Vec<Anomaly>,
+}
+
+/// Formats a reading with at most two decimals, e.g. "22.35".
+pub fn format_reading(value: f64) -> String {
+    let rounded = format!("{:.2}", value);
+    rounded.trim_end_matches('0').trim_end_matches('.').to_string()
+}
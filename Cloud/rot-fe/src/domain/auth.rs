@@ -0,0 +1,134 @@
+/// # Authentication Domain Models
+///
+/// This module defines the pieces of the OAuth2 authorization code flow
+/// with PKCE (Proof Key for Code Exchange) used to sign in with Azure AD:
+/// the login in progress, the tokens returned by the token endpoint and the
+/// signed-in session kept between page loads.
+///
+/// A single-page application cannot keep a client secret, so PKCE proves
+/// that the code is redeemed by the page that started the login: the page
+/// sends the SHA-256 hash of a random verifier with the login and the
+/// verifier itself when redeeming the code.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Seconds before expiry at which an access token is treated as expired,
+/// so it does not expire while a request is in flight
+const EXPIRY_LEEWAY_SECS: i64 = 60;
+
+/// A login started in this tab, kept while the browser visits Azure AD.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingLogin {
+    /// Random secret whose hash was sent with the login
+    pub verifier: String,
+    /// Random value echoed by Azure AD, matched to reject forged callbacks
+    pub state: String,
+    /// Path to return to once signed in
+    pub return_to: String,
+}
+
+/// Response of the token endpoint.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TokenResponse {
+    /// Bearer token sent to the backend APIs
+    pub access_token: String,
+    /// Lifetime of the access token, in seconds
+    pub expires_in: i64,
+    /// Token redeemable for a new access token, if `offline_access` was granted
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// OpenID Connect token identifying the user, if `openid` was granted
+    #[serde(default)]
+    pub id_token: Option<String>,
+}
+
+/// A signed-in session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Session {
+    /// Bearer token sent to the backend APIs
+    pub access_token: String,
+    /// Unix timestamp at which the access token expires
+    pub expires_at: i64,
+    /// Token redeemable for a new access token, if any
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// OpenID Connect token identifying the user, if any
+    #[serde(default)]
+    pub id_token: Option<String>,
+}
+
+/// Claims of an ID token naming the user.
+#[derive(Debug, Deserialize)]
+struct IdClaims {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    preferred_username: Option<String>,
+}
+
+impl Session {
+    /// Starts a session from a token response.
+    ///
+    /// # Parameters
+    /// * `response` - The token endpoint's response
+    /// * `now` - The current Unix timestamp
+    /// * `previous` - The session being refreshed, whose refresh and ID
+    ///   tokens are kept if the response has none
+    pub fn from_response(response: TokenResponse, now: i64, previous: Option<&Session>) -> Self {
+        Session {
+            access_token: response.access_token,
+            expires_at: now + response.expires_in,
+            refresh_token: response.refresh_token.or_else(|| previous.and_then(|session| session.refresh_token.clone())),
+            id_token: response.id_token.or_else(|| previous.and_then(|session| session.id_token.clone())),
+        }
+    }
+
+    /// Whether the access token can still be sent.
+    ///
+    /// # Parameters
+    /// * `now` - The current Unix timestamp
+    ///
+    /// # Returns
+    /// * `true` if the token expires more than a minute from now
+    pub fn is_fresh(&self, now: i64) -> bool {
+        now + EXPIRY_LEEWAY_SECS < self.expires_at
+    }
+
+    /// Name of the signed-in user, from the ID token.
+    ///
+    /// # Returns
+    /// * The user's display name, or their user name if the token has no
+    ///   name, or `None` without a readable ID token
+    pub fn user_name(&self) -> Option<String> {
+        // The token came straight from the token endpoint over TLS, so its
+        // claims are read without checking the signature
+        let payload = self.id_token.as_deref()?.split('.').nth(1)?;
+        let claims: IdClaims = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+        claims.name.or(claims.preferred_username)
+    }
+}
+
+/// Encodes random bytes as a verifier or state value.
+///
+/// # Parameters
+/// * `bytes` - Random bytes
+///
+/// # Returns
+/// * The bytes in unpadded base64url, which only uses characters PKCE allows
+pub fn encode_random(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Derives the code challenge sent with the login from the verifier.
+///
+/// # Parameters
+/// * `verifier` - The login's verifier
+///
+/// # Returns
+/// * The unpadded base64url SHA-256 hash of the verifier (the `S256` method)
+pub fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
@@ -0,0 +1,74 @@
+/// # Offline Cache Domain Models
+///
+/// This module defines the responses kept in local storage so the last
+/// known telemetry and configuration of a device can still be shown when
+/// the backends are unreachable, e.g. on a flaky field connection. Only
+/// failures to reach a backend fall back on the cache; a backend answering
+/// that a device is unknown is reported as usual.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::telemetry::Telemetry;
+
+/// Most telemetry records cached per device, newest kept, so the cache
+/// stays well within the browser's local storage quota
+pub const MAX_CACHED_RECORDS: usize = 1000;
+
+/// A response kept in local storage.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedResponse<T> {
+    /// Unix timestamp of when the response was received
+    pub cached_at: i64,
+    /// The response
+    pub data: T,
+}
+
+/// Data fetched from a backend, or taken from the cache when unreachable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fetched<T> {
+    /// The data
+    pub data: T,
+    /// When the data was cached, if it comes from the cache rather than the backend
+    pub cached_at: Option<i64>,
+}
+
+impl<T> Fetched<T> {
+    /// Data just received from a backend.
+    pub fn live(data: T) -> Self {
+        Fetched { data, cached_at: None }
+    }
+
+    /// Data taken from the cache.
+    pub fn cached(response: CachedResponse<T>) -> Self {
+        Fetched { data: response.data, cached_at: Some(response.cached_at) }
+    }
+}
+
+/// Whether a service error means the backend could not be reached.
+///
+/// # Parameters
+/// * `error` - Error message returned by a service
+///
+/// # Returns
+/// * `true` for requests that could not be sent, e.g. when offline, and for
+///   the gateway errors (502, 503, 504) of a backend that is down
+pub fn is_unreachable(error: &str) -> bool {
+    error.starts_with("Request failed: ")
+        || ["502", "503", "504"]
+            .iter()
+            .any(|status| error == format!("Request failed with status: {}", status))
+}
+
+/// Keeps the newest records of a device's telemetry for caching.
+///
+/// # Parameters
+/// * `records` - The records received, in any order
+///
+/// # Returns
+/// * At most `MAX_CACHED_RECORDS` of the newest records, oldest first
+pub fn records_to_cache(records: &[Telemetry]) -> Vec<Telemetry> {
+    let mut kept = records.to_vec();
+    kept.sort_by_key(|record| record.timestamp);
+    let skip = kept.len().saturating_sub(MAX_CACHED_RECORDS);
+    kept.split_off(skip)
+}
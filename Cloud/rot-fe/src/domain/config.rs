@@ -1,8 +1,397 @@
+/// # Device Configuration Domain Models
+///
+/// This module defines device configurations as sent to and served by the
+/// device configuration API, the stored versions listed by its history
+/// endpoint, the key/value entries the configuration editor works on and
+/// the keys an edit changes, previewed before it is pushed.
+/// It also compares the configuration served to a device (the desired
+/// configuration) with the one the device reports having applied, to flag
+/// devices that drifted from it.
+/// The firmware accepts arbitrary keys, so entries are checked against the
+/// API's default limits before they are pushed.
+
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+/// Longest key the API accepts by default (`CONFIG_MAX_KEY_LENGTH`)
+const MAX_KEY_LENGTH: usize = 64;
+
+/// Longest value the API accepts by default (`CONFIG_MAX_VALUE_LENGTH`)
+const MAX_VALUE_LENGTH: usize = 256;
+
+/// Most keys the API accepts by default (`CONFIG_MAX_KEYS`)
+const MAX_KEYS: usize = 64;
+
+/// Keys the API reserves for its own fields
+const RESERVED_KEYS: [&str; 5] = ["id", "device_id", "apply_at", "signature", "is_default"];
 
+/// Placeholder the API serves instead of secret values
+const REDACTED_VALUE: &str = "********";
+
+/// Body of a configuration update.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DeviceConfig {
     pub device_id: String,
     pub config: Value,
-} 
\ No newline at end of file
+}
+
+/// A device's current configuration, as served by the API.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct StoredConfig {
+    /// Device the configuration is served to
+    pub device_id: String,
+    /// The configuration values
+    pub config: Value,
+    /// Number of the served version, if the device has a configuration of its own
+    #[serde(default)]
+    pub version_number: Option<u64>,
+    /// Whether the fleet-wide defaults are served because the device has no configuration
+    #[serde(default)]
+    pub is_default: bool,
+    /// The document as returned by the API, for the record inspector
+    #[serde(skip)]
+    pub document: Option<Value>,
+}
+
+/// A key changed by a configuration version, or by an edit about to be pushed.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ConfigChange {
+    /// The configuration key
+    pub key: String,
+    /// Value before the version, `None` if the key was added
+    #[serde(default)]
+    pub old_value: Option<String>,
+    /// Value in the version, `None` if the key was removed
+    #[serde(default)]
+    pub new_value: Option<String>,
+}
+
+/// A stored version of a device's configuration, as listed by the history API.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ConfigVersion {
+    /// Number of the version, counting from 1 for each device
+    #[serde(default)]
+    pub version_number: Option<u64>,
+    /// The configuration values, secret values redacted
+    pub config: Value,
+    /// When the version took effect, or is scheduled to, as an RFC 3339 time
+    #[serde(default)]
+    pub effective_at: Option<String>,
+    /// Who stored the version
+    #[serde(default)]
+    pub modified_by: Option<String>,
+    /// Whether this is the version currently served to the device
+    #[serde(default)]
+    pub active: bool,
+    /// Keys changed compared to the version stored before it
+    #[serde(default)]
+    pub changes: Vec<ConfigChange>,
+    /// The document as returned by the API, for the record inspector
+    #[serde(skip)]
+    pub document: Option<Value>,
+}
+
+/// The configuration a device reports having applied, as served by the
+/// configuration service's reported-configuration endpoint.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ReportedConfig {
+    /// Device that reported the configuration
+    pub device_id: String,
+    /// The configuration values the device applied, secret values redacted
+    pub config: Value,
+    /// Number of the version the device applied, if it reports one
+    #[serde(default)]
+    pub version_number: Option<u64>,
+    /// When the device applied the configuration, as an RFC 3339 time
+    #[serde(default)]
+    pub applied_at: Option<String>,
+    /// The document as returned by the API, for the record inspector
+    #[serde(skip)]
+    pub document: Option<Value>,
+}
+
+/// How the desired and reported values of a key compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Drift {
+    /// The device applied the desired value
+    InSync,
+    /// The device reports another value
+    Changed,
+    /// The key is desired but the device doesn't report it
+    Missing,
+    /// The device reports a key that isn't desired
+    Unexpected,
+    /// A secret set on both sides; its value is redacted, so only its
+    /// presence is compared
+    Secret,
+}
+
+impl Drift {
+    /// Whether the key drifted from the desired configuration.
+    pub fn is_drift(&self) -> bool {
+        matches!(self, Drift::Changed | Drift::Missing | Drift::Unexpected)
+    }
+
+    /// Short description shown next to the key.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Drift::InSync => "In sync",
+            Drift::Changed => "Differs",
+            Drift::Missing => "Not applied",
+            Drift::Unexpected => "Not desired",
+            Drift::Secret => "Secret, set",
+        }
+    }
+}
+
+/// A key of the desired and reported configurations, side by side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyComparison {
+    /// The configuration key
+    pub key: String,
+    /// Value served to the device, `None` if not desired
+    pub desired: Option<String>,
+    /// Value the device reports, `None` if not reported
+    pub reported: Option<String>,
+    /// How the two values compare
+    pub drift: Drift,
+}
+
+/// Whether a device applied the configuration it is served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    /// The device reports the served version and values
+    InSync,
+    /// The device reports another version than the one served, e.g. because
+    /// it hasn't fetched the latest push yet
+    VersionBehind { desired: u64, reported: Option<u64> },
+    /// The device reports the served version, or neither has a version, but
+    /// `keys` keys differ
+    Drifted { keys: usize },
+    /// The device hasn't reported a configuration
+    NotReported,
+}
+
+impl SyncStatus {
+    /// Judges a device's configuration.
+    ///
+    /// # Parameters
+    /// * `desired` - The configuration served to the device
+    /// * `reported` - The configuration the device reports, if any
+    /// * `comparisons` - The keys of both, from `compare_configs`
+    pub fn assess(desired: &StoredConfig, reported: Option<&ReportedConfig>, comparisons: &[KeyComparison]) -> Self {
+        let Some(reported) = reported else {
+            return SyncStatus::NotReported;
+        };
+        match desired.version_number {
+            Some(version) if reported.version_number != Some(version) => SyncStatus::VersionBehind {
+                desired: version,
+                reported: reported.version_number,
+            },
+            _ => match comparisons.iter().filter(|comparison| comparison.drift.is_drift()).count() {
+                0 => SyncStatus::InSync,
+                keys => SyncStatus::Drifted { keys },
+            },
+        }
+    }
+
+    /// Summary shown above the comparison.
+    pub fn describe(&self) -> String {
+        match self {
+            SyncStatus::InSync => "In sync: the device applied the configuration it is served".to_string(),
+            SyncStatus::VersionBehind { desired, reported: Some(reported) } => {
+                format!("Not applied yet: version {} is served, the device reports version {}", desired, reported)
+            }
+            SyncStatus::VersionBehind { desired, reported: None } => {
+                format!("Not applied yet: version {} is served, the device reports no version", desired)
+            }
+            SyncStatus::Drifted { keys: 1 } => "Drift: 1 key differs from the configuration served".to_string(),
+            SyncStatus::Drifted { keys } => format!("Drift: {} keys differ from the configuration served", keys),
+            SyncStatus::NotReported => "The device hasn't reported the configuration it applied".to_string(),
+        }
+    }
+}
+
+/// Lines up the desired and reported configurations key by key.
+///
+/// # Parameters
+/// * `desired` - The configuration served to the device, a JSON object
+/// * `reported` - The configuration the device reports, a JSON object
+///
+/// # Returns
+/// * One comparison per key of either configuration, sorted by key
+pub fn compare_configs(desired: &Value, reported: &Value) -> Vec<KeyComparison> {
+    let empty = Map::new();
+    let desired = desired.as_object().unwrap_or(&empty);
+    let reported = reported.as_object().unwrap_or(&empty);
+
+    let mut keys: Vec<&String> = desired.keys().chain(reported.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|key| {
+            let desired = desired.get(key).map(display_value);
+            let reported = reported.get(key).map(display_value);
+            let drift = match (&desired, &reported) {
+                (Some(desired), Some(reported)) if desired == REDACTED_VALUE || reported == REDACTED_VALUE => Drift::Secret,
+                (Some(desired), Some(reported)) if desired == reported => Drift::InSync,
+                (Some(_), Some(_)) => Drift::Changed,
+                (Some(_), None) => Drift::Missing,
+                _ => Drift::Unexpected,
+            };
+            KeyComparison { key: key.clone(), desired, reported, drift }
+        })
+        .collect()
+}
+
+/// One key/value row of the configuration editor.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+}
+
+/// Turns a configuration into editor rows, sorted by key.
+///
+/// # Parameters
+/// * `config` - The configuration values, a JSON object
+///
+/// # Returns
+/// * One entry per key; non-string values are shown as JSON
+pub fn entries_from_config(config: &Value) -> Vec<ConfigEntry> {
+    let mut entries: Vec<ConfigEntry> = config
+        .as_object()
+        .map(|values| {
+            values
+                .iter()
+                .map(|(key, value)| ConfigEntry { key: key.clone(), value: display_value(value) })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+}
+
+/// Shows a configuration value as text; non-string values are shown as JSON.
+fn display_value(value: &Value) -> String {
+    value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())
+}
+
+/// Lists the keys a push would change, to preview it before it is sent.
+///
+/// # Parameters
+/// * `current` - The configuration currently stored, a JSON object
+/// * `proposed` - The configuration about to be pushed, a JSON object
+///
+/// # Returns
+/// * One change per key added, removed or given another value, sorted by
+///   key; empty if the push changes nothing
+pub fn diff_configs(current: &Value, proposed: &Value) -> Vec<ConfigChange> {
+    let empty = Map::new();
+    let current = current.as_object().unwrap_or(&empty);
+    let proposed = proposed.as_object().unwrap_or(&empty);
+
+    let mut keys: Vec<&String> = current.keys().chain(proposed.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let old_value = current.get(key).map(display_value);
+            let new_value = proposed.get(key).map(display_value);
+            (old_value != new_value).then(|| ConfigChange { key: key.clone(), old_value, new_value })
+        })
+        .collect()
+}
+
+/// Builds the configuration pushed to the device from editor rows.
+///
+/// # Parameters
+/// * `entries` - The editor rows, which should have been validated
+///
+/// # Returns
+/// * JSON object mapping each trimmed key to its value; rows with a blank
+///   key are left out
+pub fn config_from_entries(entries: &[ConfigEntry]) -> Value {
+    let values: Map<String, Value> = entries
+        .iter()
+        .filter(|entry| !entry.key.trim().is_empty())
+        .map(|entry| (entry.key.trim().to_string(), Value::String(entry.value.clone())))
+        .collect();
+    Value::Object(values)
+}
+
+/// Describes the values a key accepts, for keys the firmware interprets.
+///
+/// # Parameters
+/// * `key` - The configuration key
+///
+/// # Returns
+/// * A hint shown next to the value, or `None` for free-form keys
+pub fn key_hint(key: &str) -> Option<&'static str> {
+    match key.trim() {
+        "LED" => Some("on or off"),
+        "interval" => Some("Whole number of seconds"),
+        _ => None,
+    }
+}
+
+/// Checks editor rows against the API's default limits and value rules.
+///
+/// # Parameters
+/// * `entries` - The editor rows
+///
+/// # Returns
+/// * One message per problem, empty if the configuration can be pushed
+pub fn validate_entries(entries: &[ConfigEntry]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+
+    for entry in entries {
+        let key = entry.key.trim();
+        if key.is_empty() {
+            if !entry.value.is_empty() {
+                errors.push(format!("The value \"{}\" needs a key.", entry.value));
+            }
+            continue;
+        }
+
+        if key.len() > MAX_KEY_LENGTH {
+            errors.push(format!("{}: keys can be at most {} bytes long.", key, MAX_KEY_LENGTH));
+        }
+        if !key.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c)) {
+            errors.push(format!("{}: keys can only contain letters, digits and _-.", key));
+        }
+        if RESERVED_KEYS.contains(&key) {
+            errors.push(format!("{}: this key is reserved.", key));
+        }
+        if seen.contains(&key) {
+            errors.push(format!("{}: the key is set more than once.", key));
+        }
+        seen.push(key);
+
+        if entry.value.len() > MAX_VALUE_LENGTH {
+            errors.push(format!("{}: values can be at most {} bytes long.", key, MAX_VALUE_LENGTH));
+        }
+        let value = entry.value.trim();
+        match key {
+            "LED" if value != "on" && value != "off" => {
+                errors.push("LED: must be on or off.".to_string());
+            }
+            "interval" if !value.parse::<u32>().is_ok_and(|secs| secs >= 1) => {
+                errors.push("interval: must be a whole number of seconds, at least 1.".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    if seen.is_empty() {
+        errors.push("Add at least one setting.".to_string());
+    }
+    if seen.len() > MAX_KEYS {
+        errors.push(format!("At most {} settings can be pushed.", MAX_KEYS));
+    }
+    errors
+}
@@ -0,0 +1,173 @@
+/// # Dashboard Domain Models
+///
+/// This module defines the user's own dashboard: a grid of widgets, each
+/// showing one device, and for charts and stat cards one of its metrics,
+/// over a quick time range. Widgets keep the order the user arranged them
+/// in and take one or two grid columns.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::preset::TimeRange;
+
+/// What a dashboard widget shows.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetKind {
+    /// A line chart of a metric
+    #[default]
+    Chart,
+    /// The min, max, average and latest value of a metric
+    StatCard,
+    /// Whether the device is still reporting
+    StatusTile,
+}
+
+impl WidgetKind {
+    /// Every widget kind offered when adding a widget, in display order.
+    pub const ALL: [WidgetKind; 3] = [WidgetKind::Chart, WidgetKind::StatCard, WidgetKind::StatusTile];
+
+    /// Stable identifier used as the `<select>` option value.
+    pub fn key(&self) -> &'static str {
+        match self {
+            WidgetKind::Chart => "chart",
+            WidgetKind::StatCard => "stat_card",
+            WidgetKind::StatusTile => "status_tile",
+        }
+    }
+
+    /// Human-readable name shown in the widget selector.
+    pub fn label(&self) -> &'static str {
+        match self {
+            WidgetKind::Chart => "Chart",
+            WidgetKind::StatCard => "Stat card",
+            WidgetKind::StatusTile => "Status tile",
+        }
+    }
+
+    /// Parses a widget kind from its `key()`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+
+    /// Whether the widget shows a metric, rather than the whole device.
+    pub fn needs_metric(&self) -> bool {
+        !matches!(self, WidgetKind::StatusTile)
+    }
+}
+
+/// How many grid columns a widget takes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetSize {
+    /// One column
+    #[default]
+    Normal,
+    /// Both columns, on screens wide enough for two
+    Wide,
+}
+
+impl WidgetSize {
+    /// The other size, switched to by the widget's resize button.
+    pub fn toggle(&self) -> Self {
+        match self {
+            WidgetSize::Normal => WidgetSize::Wide,
+            WidgetSize::Wide => WidgetSize::Normal,
+        }
+    }
+
+    /// Tailwind classes spanning the widget's grid columns.
+    pub fn grid_classes(&self) -> &'static str {
+        match self {
+            WidgetSize::Normal => "col-span-1",
+            WidgetSize::Wide => "col-span-1 lg:col-span-2",
+        }
+    }
+}
+
+/// A widget on the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Widget {
+    /// Identifier of the widget, unique on the dashboard
+    pub id: u32,
+    /// What the widget shows
+    pub kind: WidgetKind,
+    /// The device shown
+    pub device_id: String,
+    /// The metric shown, `None` for status tiles
+    #[serde(default)]
+    pub metric: Option<String>,
+    /// Time range charted or summarized
+    #[serde(default)]
+    pub range: TimeRange,
+    /// Grid columns the widget takes
+    #[serde(default)]
+    pub size: WidgetSize,
+}
+
+impl Widget {
+    /// Builds a widget from the fields of the add widget form.
+    ///
+    /// # Parameters
+    /// * `widgets` - The widgets already on the dashboard, to pick a new ID
+    /// * `kind` - The chosen widget kind
+    /// * `device_id` - The device ID field
+    /// * `metric` - The metric field, ignored for status tiles
+    /// * `range` - The chosen time range
+    ///
+    /// # Returns
+    /// * `Ok(Widget)` - The widget, with the device ID and metric trimmed
+    /// * `Err(String)` - Why the fields are not a valid widget
+    pub fn parse(
+        widgets: &[Widget],
+        kind: WidgetKind,
+        device_id: &str,
+        metric: &str,
+        range: TimeRange,
+    ) -> Result<Self, String> {
+        let device_id = device_id.trim();
+        if device_id.is_empty() {
+            return Err("Enter the device the widget shows.".to_string());
+        }
+        let metric = if kind.needs_metric() {
+            let metric = metric.trim();
+            if metric.is_empty() {
+                return Err(format!("Enter the metric the {} shows.", kind.label().to_lowercase()));
+            }
+            Some(metric.to_string())
+        } else {
+            None
+        };
+        let id = widgets.iter().map(|widget| widget.id + 1).max().unwrap_or(0);
+        Ok(Widget {
+            id,
+            kind,
+            device_id: device_id.to_string(),
+            metric,
+            range,
+            size: WidgetSize::default(),
+        })
+    }
+
+    /// Title shown above the widget, e.g. "temperature · 4321".
+    pub fn title(&self) -> String {
+        match &self.metric {
+            Some(metric) => format!("{} · {}", metric, self.device_id),
+            None => format!("Device {}", self.device_id),
+        }
+    }
+}
+
+/// Moves a widget one place earlier or later on the dashboard.
+///
+/// # Parameters
+/// * `widgets` - The dashboard's widgets, in display order
+/// * `index` - Position of the widget to move
+/// * `later` - `true` to move it after the next widget, `false` before the previous one
+///
+/// Widgets already first or last, or out of range, stay where they are.
+pub fn move_widget(widgets: &mut [Widget], index: usize, later: bool) {
+    let target = if later { index.checked_add(1) } else { index.checked_sub(1) };
+    if let Some(target) = target.filter(|target| *target < widgets.len() && index < widgets.len()) {
+        widgets.swap(index, target);
+    }
+}
@@ -0,0 +1,127 @@
+/// # Device Domain Models
+///
+/// This module defines the devices listed by the device monitor discovery
+/// API (`/iot/devices`): every device that has reported telemetry, when it
+/// was last seen and whether it is still reporting, as also reported for a
+/// single device by the status API (`/iot/devices/{device_id}/status`). It also keeps the list
+/// of recently viewed devices offered as quick selections.
+
+use serde::{Deserialize, Serialize};
+
+/// Most device IDs remembered as recently viewed
+pub const MAX_RECENT_DEVICES: usize = 8;
+
+/// Whether a device is reporting, as judged by the device monitor.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceStatus {
+    /// Reported recently
+    Online,
+    /// Has not reported for a while
+    Stale,
+    /// Has not reported for a long time
+    Offline,
+}
+
+impl DeviceStatus {
+    /// Human-readable name shown in the status badge.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeviceStatus::Online => "Online",
+            DeviceStatus::Stale => "Stale",
+            DeviceStatus::Offline => "Offline",
+        }
+    }
+
+    /// Tailwind classes colouring the status badge.
+    pub fn badge_classes(&self) -> &'static str {
+        match self {
+            DeviceStatus::Online => "bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200",
+            DeviceStatus::Stale => "bg-yellow-100 dark:bg-yellow-900 text-yellow-800 dark:text-yellow-200",
+            DeviceStatus::Offline => "bg-red-100 dark:bg-red-900 text-red-800 dark:text-red-200",
+        }
+    }
+}
+
+/// A device that has reported telemetry, as listed by the discovery API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeviceSummary {
+    /// Unique identifier of the device
+    pub device_id: String,
+    /// Unix timestamp of the device's most recent reading or heartbeat
+    pub last_seen: i64,
+    /// Gateway that forwarded the most recent record, if any
+    #[serde(default)]
+    pub gateway_id: Option<String>,
+    /// Whether the device is still reporting
+    pub status: DeviceStatus,
+}
+
+impl DeviceSummary {
+    /// Whether the device matches a search term.
+    ///
+    /// # Parameters
+    /// * `search` - Text typed in the search box
+    ///
+    /// # Returns
+    /// * `true` if the device ID or gateway ID contains the term, ignoring
+    ///   case, or the term is blank
+    pub fn matches(&self, search: &str) -> bool {
+        let search = search.trim().to_lowercase();
+        search.is_empty()
+            || self.device_id.to_lowercase().contains(&search)
+            || self
+                .gateway_id
+                .as_ref()
+                .is_some_and(|gateway_id| gateway_id.to_lowercase().contains(&search))
+    }
+}
+
+/// A device's status, as reported by the status API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeviceStatusReport {
+    /// Unique identifier of the device
+    pub device_id: String,
+    /// Whether the device is still reporting
+    pub status: DeviceStatus,
+    /// Unix timestamp of the device's latest telemetry
+    pub last_seen: i64,
+    /// Seconds elapsed since the latest telemetry, by the API's clock
+    pub seconds_since_last_seen: i64,
+}
+
+/// Describes how long ago something happened.
+///
+/// # Parameters
+/// * `age` - Seconds elapsed; negative ages, from clock skew, count as none
+///
+/// # Returns
+/// * "just now" under a minute, otherwise the age in whole minutes, hours
+///   or days, e.g. "5 min ago"
+pub fn format_age(age: i64) -> String {
+    let age = age.max(0);
+    match age {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{} min ago", age / 60),
+        3600..86400 => format!("{} h ago", age / 3600),
+        _ => format!("{} d ago", age / 86400),
+    }
+}
+
+/// Moves a device to the front of the recently viewed devices.
+///
+/// The list holds each device once, most recent first, and forgets the
+/// oldest devices beyond `MAX_RECENT_DEVICES`.
+///
+/// # Parameters
+/// * `recent` - The recently viewed device IDs
+/// * `device_id` - The device just viewed; blank IDs are ignored
+pub fn remember_device(recent: &mut Vec<String>, device_id: &str) {
+    let device_id = device_id.trim();
+    if device_id.is_empty() {
+        return;
+    }
+    recent.retain(|recent_id| recent_id != device_id);
+    recent.insert(0, device_id.to_string());
+    recent.truncate(MAX_RECENT_DEVICES);
+}
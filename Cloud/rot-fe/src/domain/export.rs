@@ -0,0 +1,36 @@
+/// # Telemetry Export
+///
+/// This module names the CSV files the telemetry view downloads from the
+/// device monitor export API (`/iot/data/export/{device_id}.csv`), so files
+/// exported for different devices or time ranges do not overwrite each other.
+
+use chrono::DateTime;
+
+/// Format of the range bounds in export file names; UTC, without colons
+/// so the name is valid on every file system
+const FILENAME_TIME_FORMAT: &str = "%Y-%m-%dT%H%MZ";
+
+/// Names the CSV file of a device's telemetry over a time range.
+///
+/// # Parameters
+/// * `device_id` - ID of the exported device
+/// * `from` - Unix timestamp the range starts at, `None` if it is open
+/// * `to` - Unix timestamp the range ends at
+///
+/// # Returns
+/// * A name such as "sensor-001_2024-01-31T1245Z_2024-01-31T1345Z.csv";
+///   an open start is written as "start", and characters of the device ID
+///   other than letters, digits, `-` and `_` are replaced by `_`
+pub fn export_filename(device_id: &str, from: Option<i64>, to: i64) -> String {
+    let device: String = device_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let bound = |timestamp: i64| {
+        DateTime::from_timestamp(timestamp, 0)
+            .map(|dt| dt.format(FILENAME_TIME_FORMAT).to_string())
+            .unwrap_or_else(|| timestamp.to_string())
+    };
+    let from = from.map_or_else(|| "start".to_string(), bound);
+    format!("{}_{}_{}.csv", device, from, bound(to))
+}
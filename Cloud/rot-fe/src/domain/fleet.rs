@@ -0,0 +1,144 @@
+/// # Fleet Domain Models
+///
+/// This module defines the fleet-wide views of the device monitor API: the
+/// fleet summary (`/iot/fleet/summary`), with statistics of each metric in
+/// the devices' latest records, and the fleet health scores
+/// (`/iot/fleet/health`), with each device's status and recent alerts.
+
+use serde::Deserialize;
+
+use crate::domain::device::DeviceStatus;
+use crate::domain::telemetry::Telemetry;
+
+/// Fleet-wide statistics of one metric.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct MetricSummary {
+    /// Name of the metric
+    pub metric: String,
+    /// Lowest latest value across the fleet
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Highest latest value across the fleet
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Mean of the latest values across the fleet
+    #[serde(default)]
+    pub avg: Option<f64>,
+    /// Number of devices reporting the metric
+    pub devices: usize,
+}
+
+/// Summary of the whole fleet.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct FleetSummary {
+    /// Number of known devices
+    pub devices: usize,
+    /// Number of devices that reported within the window
+    pub reporting_devices: usize,
+    /// The reporting window, in minutes
+    pub window_minutes: i64,
+    /// Statistics of each numeric metric
+    #[serde(default)]
+    pub metrics: Vec<MetricSummary>,
+}
+
+/// The health score of one device.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DeviceScore {
+    /// Unique identifier of the device
+    pub device_id: String,
+    /// Health score from 0 (needs attention first) to 100 (no problem found)
+    pub score: u32,
+    /// Whether the device is still reporting
+    pub status: DeviceStatus,
+    /// Unix timestamp of the device's most recent reading or heartbeat
+    pub last_seen: i64,
+    /// Number of alerts the device raised within the breach window
+    #[serde(default)]
+    pub breaches: usize,
+}
+
+/// Health of the whole fleet, devices needing attention first.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct FleetHealth {
+    /// Mean score of all devices, if there are any
+    #[serde(default)]
+    pub score: Option<u32>,
+    /// Number of devices scored
+    pub devices: usize,
+    /// Number of devices scoring below 100
+    pub devices_needing_attention: usize,
+    /// The window alerts are counted in, in hours
+    pub breach_window_hours: i64,
+    /// The score of each device, lowest first
+    #[serde(default)]
+    pub scores: Vec<DeviceScore>,
+}
+
+impl FleetHealth {
+    /// Counts the devices with a given status.
+    ///
+    /// # Parameters
+    /// * `status` - The status to count
+    ///
+    /// # Returns
+    /// * Number of scored devices with that status
+    pub fn count(&self, status: DeviceStatus) -> usize {
+        self.scores.iter().filter(|device| device.status == status).count()
+    }
+
+    /// Total number of alerts raised within the breach window.
+    pub fn alerts(&self) -> usize {
+        self.scores.iter().map(|device| device.breaches).sum()
+    }
+
+    /// The devices that raised alerts within the breach window.
+    ///
+    /// # Returns
+    /// * The devices with at least one alert, most alerts first
+    pub fn alerting_devices(&self) -> Vec<&DeviceScore> {
+        let mut devices: Vec<&DeviceScore> = self.scores.iter().filter(|device| device.breaches > 0).collect();
+        devices.sort_by(|a, b| b.breaches.cmp(&a.breaches).then_with(|| a.device_id.cmp(&b.device_id)));
+        devices
+    }
+}
+
+/// Picks the metric shown in the device tiles' sparklines.
+///
+/// # Parameters
+/// * `summary` - The fleet summary listing the reported metrics
+///
+/// # Returns
+/// * `temperature` if any device reports it, otherwise the metric reported
+///   by the most devices, or `None` if no numeric metric is reported
+pub fn default_sparkline_metric(summary: &FleetSummary) -> Option<String> {
+    if summary.metrics.iter().any(|metric| metric.metric == "temperature") {
+        return Some("temperature".to_string());
+    }
+    summary
+        .metrics
+        .iter()
+        .max_by(|a, b| a.devices.cmp(&b.devices).then_with(|| b.metric.cmp(&a.metric)))
+        .map(|metric| metric.metric.clone())
+}
+
+/// Extracts the points of a sparkline from a device's records.
+///
+/// # Parameters
+/// * `records` - The device's records, in any order
+/// * `metric` - The metric to plot
+///
+/// # Returns
+/// * `(timestamp, value)` pairs, oldest first; records without a timestamp
+///   or a numeric value of the metric are skipped
+pub fn sparkline_points(records: &[Telemetry], metric: &str) -> Vec<(i64, f64)> {
+    let mut points: Vec<(i64, f64)> = records
+        .iter()
+        .filter_map(|record| {
+            let value = record.telemetry_data.get(metric)?.trim().parse::<f64>().ok()?;
+            Some((record.timestamp?, value)).filter(|_| value.is_finite())
+        })
+        .collect();
+    points.sort_by_key(|(timestamp, _)| *timestamp);
+    points
+}
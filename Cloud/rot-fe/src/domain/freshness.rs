@@ -0,0 +1,116 @@
+/// # Data Freshness Domain Models
+///
+/// This module judges whether the data a view shows can still be trusted:
+/// whether the last fetch succeeded, and whether the newest reading is
+/// recent given how often the device reports. A refresh that fails keeps
+/// the previous data on screen, which otherwise looks like a healthy but
+/// flat chart.
+
+use serde_json::Value;
+
+/// Reporting interval assumed for devices whose configuration sets none,
+/// in seconds, matching the fleet-wide default configuration
+pub const DEFAULT_REPORTING_INTERVAL_SECS: i64 = 60;
+
+/// Reports a device may miss before its newest reading counts as old
+pub const MISSED_REPORTS: i64 = 2;
+
+/// How fresh the data on screen is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Staleness {
+    /// Fetched successfully, and the newest reading is recent
+    Fresh,
+    /// The backend was unreachable, so the data shown was taken from the
+    /// offline cache, where it was stored `age` seconds ago
+    Offline { age: i64 },
+    /// The last fetch failed; `age` is how long ago the data on screen was
+    /// fetched, `None` if nothing was ever fetched
+    FetchFailed { age: Option<i64> },
+    /// Fetched successfully, but the newest reading is `age` seconds old,
+    /// longer than the device takes to report `MISSED_REPORTS` times every
+    /// `interval` seconds
+    Behind { age: i64, interval: i64 },
+}
+
+impl Staleness {
+    /// Judges the data on screen.
+    ///
+    /// # Parameters
+    /// * `now` - Current Unix timestamp
+    /// * `fetched_at` - When the data on screen was fetched, if ever
+    /// * `cached_at` - When the data on screen was cached, if it was taken
+    ///   from the offline cache
+    /// * `failed` - Whether the last fetch failed
+    /// * `latest_reading` - Timestamp of the newest reading shown, if known
+    /// * `interval` - How often the device reports, in seconds; `None` to
+    ///   only judge the fetch, e.g. for historical ranges
+    pub fn assess(
+        now: i64,
+        fetched_at: Option<i64>,
+        cached_at: Option<i64>,
+        failed: bool,
+        latest_reading: Option<i64>,
+        interval: Option<i64>,
+    ) -> Self {
+        if let Some(cached_at) = cached_at {
+            return Staleness::Offline { age: (now - cached_at).max(0) };
+        }
+        if failed {
+            return Staleness::FetchFailed { age: fetched_at.map(|fetched_at| (now - fetched_at).max(0)) };
+        }
+        match latest_reading.zip(interval) {
+            Some((latest, interval)) if now - latest > interval.max(1) * MISSED_REPORTS => {
+                Staleness::Behind { age: now - latest, interval }
+            }
+            _ => Staleness::Fresh,
+        }
+    }
+
+    /// Warning shown for stale data, `None` when the data is fresh.
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            Staleness::Fresh => None,
+            Staleness::Offline { age } => Some(format!(
+                "Offline: the backend is unreachable, showing data cached {} ago",
+                format_span(*age)
+            )),
+            Staleness::FetchFailed { age: Some(age) } => Some(format!(
+                "Couldn't refresh: the data shown is {} old",
+                format_span(*age)
+            )),
+            Staleness::FetchFailed { age: None } => Some("Couldn't load the data".to_string()),
+            Staleness::Behind { age, interval } => Some(format!(
+                "Data is {} old, though the device reports every {}",
+                format_span(*age),
+                format_span(*interval)
+            )),
+        }
+    }
+}
+
+/// Reporting interval set in a device configuration.
+///
+/// # Parameters
+/// * `config` - The configuration values, as served to the device
+///
+/// # Returns
+/// * The `interval` key in seconds, if set to a whole number of at least one
+pub fn reporting_interval(config: &Value) -> Option<i64> {
+    match config.get("interval")? {
+        Value::String(secs) => secs.trim().parse::<i64>().ok(),
+        Value::Number(secs) => secs.as_i64(),
+        _ => None,
+    }
+    .filter(|secs| *secs >= 1)
+}
+
+/// Describes a time span, e.g. "45 s", "12 min", "3 h" or "2 d".
+pub fn format_span(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..60 => format!("{} s", secs),
+        60..3600 => format!("{} min", secs / 60),
+        3600..86400 => format!("{} h", secs / 3600),
+        _ => format!("{} d", secs / 86400),
+    }
+}
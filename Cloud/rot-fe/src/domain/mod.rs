@@ -9,3 +9,60 @@ pub mod telemetry;
 /// Configuration models for device settings and preferences
 pub mod config;
 
+/// Saved chart configurations
+pub mod preset;
+
+/// Notes attached to a device's telemetry timeline
+pub mod annotation;
+
+
+/// Devices listed by the discovery API, with their status
+pub mod device;
+
+/// Fleet summary and health scores for the fleet dashboard
+pub mod fleet;
+
+/// Light and dark colour themes
+pub mod theme;
+
+/// Threshold alert rules and the alerts they raise
+pub mod alert;
+
+/// Sign-in with Azure AD: the PKCE login and the signed-in session
+pub mod auth;
+
+/// File names of telemetry exports
+pub mod export;
+
+/// Summary statistics of a metric over a time range
+pub mod stats;
+
+/// Pages and sorting of the raw telemetry table
+pub mod table;
+
+/// Thresholds drawn over the telemetry charts
+pub mod threshold;
+
+/// Base URLs of the backend APIs, resolved at startup
+pub mod settings;
+
+/// Widgets of the user's own dashboard
+pub mod dashboard;
+
+/// Anomalous readings marked on the telemetry charts
+pub mod anomaly;
+
+/// Units telemetry is shown in: °C or °F, and scalings of other metrics
+pub mod units;
+
+/// Whether the data shown is recent, for the stale-data banner
+pub mod freshness;
+
+/// Responses cached for when the backends are unreachable
+pub mod cache;
+
+/// Geometry of the native SVG charts, and the chart renderer chosen
+pub mod plot;
+
+/// Server-sent events read from the live telemetry stream
+pub mod sse;
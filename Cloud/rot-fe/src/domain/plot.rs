@@ -0,0 +1,213 @@
+/// # Plot Domain Models
+///
+/// This module holds the geometry of the native SVG charts, drawn by Yew
+/// without the ApexCharts JavaScript library: mapping readings to the
+/// plot area, choosing axis ticks, and thinning large series down to what
+/// the plot's width can show, so a week of readings stays smooth.
+///
+/// It also defines which chart renderer the user chose.
+
+use chrono::DateTime;
+
+/// Chart library the telemetry charts are drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChartRenderer {
+    /// ApexCharts, through the JavaScript bridge: zooming, annotations and
+    /// anomaly markers
+    #[default]
+    ApexCharts,
+    /// SVG drawn by Yew, without JavaScript: lighter on large series
+    Native,
+}
+
+impl ChartRenderer {
+    /// Every renderer, in display order.
+    pub const ALL: [ChartRenderer; 2] = [ChartRenderer::ApexCharts, ChartRenderer::Native];
+
+    /// Stable identifier, as persisted and used as the `<input>` value.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ChartRenderer::ApexCharts => "apexcharts",
+            ChartRenderer::Native => "native",
+        }
+    }
+
+    /// Parses a renderer from its `key()`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|renderer| renderer.key() == key)
+    }
+
+    /// Human-readable name shown on the settings page.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChartRenderer::ApexCharts => "ApexCharts (zoom, annotations and anomaly markers)",
+            ChartRenderer::Native => "Native SVG (no JavaScript, faster on large series)",
+        }
+    }
+}
+
+/// A reading placed on a chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlotPoint {
+    /// Unix timestamp of the reading
+    pub x: i64,
+    /// The reading, in the units it is shown in
+    pub y: f64,
+}
+
+/// Maps a range of values onto a length in SVG units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    /// Value at the start of the axis
+    pub min: f64,
+    /// Value at the end of the axis
+    pub max: f64,
+    /// Length of the axis
+    pub length: f64,
+}
+
+impl Scale {
+    /// Builds a scale over a range, widened if empty so a flat series is
+    /// drawn across the middle of the axis.
+    pub fn new(min: f64, max: f64, length: f64) -> Self {
+        if max > min {
+            Scale { min, max, length }
+        } else {
+            let pad = if min == 0.0 { 1.0 } else { min.abs() * 0.1 };
+            Scale { min: min - pad, max: max + pad, length }
+        }
+    }
+
+    /// Position of a value along the axis, from 0 to `length`.
+    pub fn project(&self, value: f64) -> f64 {
+        (value - self.min) / (self.max - self.min) * self.length
+    }
+
+    /// Value at a position along the axis; the inverse of `project`.
+    pub fn invert(&self, position: f64) -> f64 {
+        self.min + position / self.length * (self.max - self.min)
+    }
+}
+
+/// Evenly spaced, round values spanning a range, for the value axis.
+///
+/// # Parameters
+/// * `min` - Lowest value shown
+/// * `max` - Highest value shown
+/// * `count` - Rough number of ticks wanted
+///
+/// # Returns
+/// * Multiples of 1, 2 or 5 times a power of ten within the range, e.g.
+///   20, 22.5, 25 are avoided in favour of 20, 22, 24
+pub fn value_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    if !min.is_finite() || !max.is_finite() || max <= min || count == 0 {
+        return Vec::new();
+    }
+    let rough = (max - min) / count as f64;
+    let magnitude = 10f64.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(10.0 * magnitude);
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    (first..=last).map(|index| index as f64 * step).collect()
+}
+
+/// Round timestamps spanning a range, for the time axis.
+///
+/// # Parameters
+/// * `from` - Earliest timestamp shown
+/// * `to` - Latest timestamp shown
+/// * `count` - Rough number of ticks wanted
+///
+/// # Returns
+/// * Timestamps on whole minutes, hours or days, in UTC
+pub fn time_ticks(from: i64, to: i64, count: usize) -> Vec<i64> {
+    const STEPS: [i64; 12] = [60, 300, 600, 900, 1800, 3600, 7200, 10800, 21600, 43200, 86400, 604800];
+    if to <= from || count == 0 {
+        return Vec::new();
+    }
+    let rough = (to - from) / count as i64;
+    let step = STEPS.into_iter().find(|step| *step >= rough).unwrap_or(604800);
+    let first = from.div_euclid(step) + 1;
+    let last = to.div_euclid(step);
+    (first..=last).map(|index| index * step).collect()
+}
+
+/// Label of a time axis tick: the time of day for spans under two days,
+/// the date otherwise.
+pub fn format_time_tick(timestamp: i64, span: i64) -> String {
+    let format = if span < 2 * 86400 { "%H:%M" } else { "%m-%d" };
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format(format).to_string())
+        .unwrap_or_default()
+}
+
+/// Thins a series to at most two points per bucket, keeping the lowest and
+/// highest reading of each so peaks and dips survive.
+///
+/// # Parameters
+/// * `points` - The series, oldest first
+/// * `buckets` - Number of buckets, e.g. the plot's width in pixels
+///
+/// # Returns
+/// * The series unchanged if it is small enough, otherwise the extremes of
+///   each bucket of consecutive points, oldest first
+pub fn decimate(points: &[PlotPoint], buckets: usize) -> Vec<PlotPoint> {
+    if buckets == 0 || points.len() <= buckets * 2 {
+        return points.to_vec();
+    }
+    let size = points.len().div_ceil(buckets);
+    points
+        .chunks(size)
+        .flat_map(|chunk| {
+            let low = chunk.iter().min_by(|a, b| a.y.total_cmp(&b.y));
+            let high = chunk.iter().max_by(|a, b| a.y.total_cmp(&b.y));
+            match (low, high) {
+                (Some(low), Some(high)) if low.x < high.x => vec![*low, *high],
+                (Some(low), Some(high)) if low.x > high.x => vec![*high, *low],
+                (Some(low), _) => vec![*low],
+                _ => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// SVG path drawing a series as a line.
+///
+/// # Parameters
+/// * `points` - The series, oldest first
+/// * `x` - Scale of the time axis
+/// * `y` - Scale of the value axis; SVG's y axis points down, so values are flipped
+pub fn line_path(points: &[PlotPoint], x: &Scale, y: &Scale) -> String {
+    points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            format!(
+                "{}{:.1},{:.1}",
+                if index == 0 { "M" } else { "L" },
+                x.project(point.x as f64),
+                y.length - y.project(point.y)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The reading closest in time to a timestamp.
+///
+/// # Parameters
+/// * `points` - The series, oldest first
+/// * `x` - The timestamp, e.g. under the mouse
+pub fn nearest_point(points: &[PlotPoint], x: i64) -> Option<PlotPoint> {
+    let index = points.partition_point(|point| point.x < x);
+    let before = index.checked_sub(1).and_then(|index| points.get(index));
+    let after = points.get(index);
+    match (before, after) {
+        (Some(before), Some(after)) => Some(if x - before.x <= after.x - x { *before } else { *after }),
+        (before, after) => before.or(after).copied(),
+    }
+}
@@ -0,0 +1,147 @@
+/// # Chart Preset Domain Models
+///
+/// This module defines saved chart configurations ("presets"). A preset
+/// captures everything needed to rebuild a telemetry view: the device,
+/// the charted metrics and the time range.
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// Format of the value of a `datetime-local` input
+const DATETIME_LOCAL_FORMAT: &str = "%Y-%m-%dT%H:%M";
+
+/// Time window shown by the telemetry charts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeRange {
+    /// The last hour of readings
+    LastHour,
+    /// The last 24 hours of readings
+    LastDay,
+    /// The last 7 days of readings
+    LastWeek,
+    /// Every stored reading
+    #[default]
+    All,
+    /// Readings between two chosen instants, either of which may be open
+    Custom {
+        /// Unix timestamp of the earliest reading, if bounded
+        from: Option<i64>,
+        /// Unix timestamp of the latest reading, if bounded
+        to: Option<i64>,
+    },
+}
+
+impl TimeRange {
+    /// Every quick range offered in the range selector, in display order.
+    pub const ALL: [TimeRange; 4] = [
+        TimeRange::LastHour,
+        TimeRange::LastDay,
+        TimeRange::LastWeek,
+        TimeRange::All,
+    ];
+
+    /// Stable identifier used as the `<select>` option value.
+    pub fn key(&self) -> &'static str {
+        match self {
+            TimeRange::LastHour => "last_hour",
+            TimeRange::LastDay => "last_day",
+            TimeRange::LastWeek => "last_week",
+            TimeRange::All => "all",
+            TimeRange::Custom { .. } => "custom",
+        }
+    }
+
+    /// Human-readable label shown in the range selector.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeRange::LastHour => "Last hour",
+            TimeRange::LastDay => "Last 24 hours",
+            TimeRange::LastWeek => "Last 7 days",
+            TimeRange::All => "All data",
+            TimeRange::Custom { .. } => "Custom range",
+        }
+    }
+
+    /// Parses a quick range from its `key()`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|range| range.key() == key)
+    }
+
+    /// Length of the window in seconds, or `None` for all data and custom ranges.
+    pub fn as_secs(&self) -> Option<i64> {
+        match self {
+            TimeRange::LastHour => Some(60 * 60),
+            TimeRange::LastDay => Some(24 * 60 * 60),
+            TimeRange::LastWeek => Some(7 * 24 * 60 * 60),
+            TimeRange::All | TimeRange::Custom { .. } => None,
+        }
+    }
+
+    /// Earliest and latest timestamps in the range, as sent to the read API.
+    ///
+    /// # Parameters
+    /// * `now` - The current Unix timestamp, which quick ranges end at
+    ///
+    /// # Returns
+    /// * `(from, to)` - Unix timestamps, `None` where the range is open
+    pub fn bounds(&self, now: i64) -> (Option<i64>, Option<i64>) {
+        match self {
+            TimeRange::Custom { from, to } => (*from, *to),
+            quick => (quick.as_secs().map(|secs| now - secs), None),
+        }
+    }
+}
+
+/// Parses the value of a `datetime-local` input as local time.
+///
+/// # Parameters
+/// * `value` - Input value such as "2024-01-31T13:45", empty if cleared
+///
+/// # Returns
+/// * Unix timestamp, or `None` if the input is empty or invalid
+pub fn parse_datetime_local(value: &str) -> Option<i64> {
+    let naive = NaiveDateTime::parse_from_str(value, DATETIME_LOCAL_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).earliest().map(|dt| dt.timestamp())
+}
+
+/// Formats a Unix timestamp as the value of a `datetime-local` input.
+///
+/// # Parameters
+/// * `timestamp` - Unix timestamp, or `None` for an empty input
+///
+/// # Returns
+/// * Local time such as "2024-01-31T13:45", or an empty string
+pub fn format_datetime_local(timestamp: Option<i64>) -> String {
+    timestamp
+        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+        .map(|dt| dt.with_timezone(&Local).format(DATETIME_LOCAL_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+/// A named, saved chart configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChartPreset {
+    /// Name chosen by the user, unique among saved presets
+    pub name: String,
+    /// Device whose telemetry is charted
+    pub device_id: String,
+    /// Telemetry keys to chart, one chart per metric
+    pub metrics: Vec<String>,
+    /// Time window shown by the charts
+    #[serde(default)]
+    pub range: TimeRange,
+}
+
+/// Adds a preset, replacing any existing preset with the same name.
+///
+/// Presets are kept sorted by name so the dropdown order is stable.
+///
+/// # Parameters
+/// * `presets` - The saved presets
+/// * `preset` - The preset to add or replace
+pub fn upsert_preset(presets: &mut Vec<ChartPreset>, preset: ChartPreset) {
+    presets.retain(|existing| existing.name != preset.name);
+    presets.push(preset);
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+}
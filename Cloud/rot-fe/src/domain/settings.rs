@@ -0,0 +1,155 @@
+/// # API Settings Domain Models
+///
+/// This module defines the base URLs of the backend APIs and the Azure AD
+/// sign-in settings. They are resolved when the application starts rather
+/// than when it is built, so one build can be deployed against any
+/// environment: each source below overrides the settings it sets in the
+/// ones before it.
+/// - The settings the application was built with (`ROT_API_URL`, `ROT_DC_URL`,
+///   `ROT_AUTH_*`)
+/// - `config.json`, served next to `index.html` by the deployment
+/// - `window.ROT_CONFIG`, set by a script in the page
+/// - The user's own overrides, set on the settings page; URLs only
+
+use serde::{Deserialize, Serialize};
+
+/// Base URLs of the backend APIs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiSettings {
+    /// Base URL of the device monitor API
+    pub api_url: String,
+    /// Base URL of the device config API
+    pub dc_url: String,
+}
+
+/// Base URLs set by one source, in `config.json` and `window.ROT_CONFIG`
+/// as `{ "apiUrl": ..., "dcUrl": ... }`; unset or blank URLs are left as
+/// they are.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSettingsOverride {
+    /// Base URL of the device monitor API
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Base URL of the device config API
+    #[serde(default)]
+    pub dc_url: Option<String>,
+}
+
+impl ApiSettings {
+    /// Applies the URLs a source sets.
+    ///
+    /// # Parameters
+    /// * `overrides` - The source's URLs
+    ///
+    /// # Returns
+    /// * The settings with the source's non-blank URLs, without trailing slashes
+    pub fn apply(&self, overrides: &ApiSettingsOverride) -> Self {
+        let pick = |current: &str, replacement: &Option<String>| {
+            replacement
+                .as_deref()
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .unwrap_or(current)
+                .trim_end_matches('/')
+                .to_string()
+        };
+        ApiSettings {
+            api_url: pick(&self.api_url, &overrides.api_url),
+            dc_url: pick(&self.dc_url, &overrides.dc_url),
+        }
+    }
+}
+
+/// Azure AD sign-in settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthSettings {
+    /// Application (client) ID of the app registration; empty if sign-in is disabled
+    pub client_id: String,
+    /// Directory (tenant) the users sign in to, e.g. a tenant ID or `organizations`
+    pub tenant_id: String,
+    /// Space-separated scopes requested at sign-in
+    pub scope: String,
+}
+
+/// Sign-in settings set by one source, in `config.json` and
+/// `window.ROT_CONFIG` as `{ "authClientId": ..., "authTenantId": ...,
+/// "authScope": ... }`; unset or blank settings are left as they are.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthSettingsOverride {
+    /// Application (client) ID of the app registration
+    #[serde(default)]
+    pub auth_client_id: Option<String>,
+    /// Directory (tenant) the users sign in to
+    #[serde(default)]
+    pub auth_tenant_id: Option<String>,
+    /// Space-separated scopes requested at sign-in
+    #[serde(default)]
+    pub auth_scope: Option<String>,
+}
+
+impl AuthSettings {
+    /// Whether users must sign in, i.e. a client ID is configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.client_id.is_empty()
+    }
+
+    /// Applies the settings a source sets.
+    ///
+    /// # Parameters
+    /// * `overrides` - The source's settings
+    ///
+    /// # Returns
+    /// * The settings with the source's non-blank values, trimmed
+    pub fn apply(&self, overrides: &AuthSettingsOverride) -> Self {
+        let pick = |current: &str, replacement: &Option<String>| {
+            replacement
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .unwrap_or(current)
+                .to_string()
+        };
+        AuthSettings {
+            client_id: pick(&self.client_id, &overrides.auth_client_id),
+            tenant_id: pick(&self.tenant_id, &overrides.auth_tenant_id),
+            scope: pick(&self.scope, &overrides.auth_scope),
+        }
+    }
+}
+
+/// Everything a deployment source sets: `config.json` or `window.ROT_CONFIG`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DeploymentSettings {
+    /// Base URLs of the backend APIs
+    #[serde(flatten)]
+    pub api: ApiSettingsOverride,
+    /// Azure AD sign-in settings
+    #[serde(flatten)]
+    pub auth: AuthSettingsOverride,
+}
+
+/// Checks a base URL typed on the settings page.
+///
+/// # Parameters
+/// * `url` - The URL field; blank to keep the deployment's URL
+///
+/// # Returns
+/// * `Ok(None)` - The field is blank
+/// * `Ok(Some(String))` - The URL, trimmed and without trailing slashes
+/// * `Err(String)` - Why the URL is not an HTTP(S) URL
+pub fn parse_base_url(url: &str) -> Result<Option<String>, String> {
+    let url = url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return Ok(None);
+    }
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| format!("{} must start with http:// or https://", url))?;
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return Err(format!("{} is not a valid URL", url));
+    }
+    Ok(Some(url.to_string()))
+}
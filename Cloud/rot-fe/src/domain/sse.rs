@@ -0,0 +1,72 @@
+/// # Server-Sent Events
+///
+/// This module splits a streamed response body into server-sent events.
+/// The live telemetry stream is read with `fetch` rather than the browser's
+/// `EventSource`, which cannot send the access token, so the events are
+/// parsed here instead of by the browser.
+
+/// An event received on a stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SseEvent {
+    /// Name of the event, `message` if the server named none
+    pub event: String,
+    /// Data of the event, its `data` lines joined by newlines
+    pub data: String,
+}
+
+/// Collects the lines of a streamed body into events.
+///
+/// Chunks may end anywhere, even inside a character, so incomplete lines
+/// are kept until the rest arrives.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    /// Bytes received after the last complete line
+    buffer: Vec<u8>,
+    /// Name of the event being read
+    event: Option<String>,
+    /// Data lines of the event being read
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Reads a chunk of the body.
+    ///
+    /// # Parameters
+    /// * `chunk` - The bytes received
+    ///
+    /// # Returns
+    /// * The events completed by the chunk; comments, such as the server's
+    ///   heartbeats, and events without data are skipped
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            // A blank line ends the event
+            if line.is_empty() {
+                let event = self.event.take().unwrap_or_else(|| "message".to_string());
+                if !self.data.is_empty() {
+                    events.push(SseEvent { event, data: self.data.join("\n") });
+                    self.data.clear();
+                }
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                // Comments have no field name; other fields are not used
+                _ => {}
+            }
+        }
+        events
+    }
+}
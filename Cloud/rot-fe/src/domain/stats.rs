@@ -0,0 +1,50 @@
+/// # Metric Statistics Domain Models
+///
+/// This module defines the summary of a device's metric over a time range,
+/// as computed server-side by the device monitor statistics endpoint
+/// (`/iot/data/stats`), and its formatting for the stat cards.
+
+use serde::Deserialize;
+
+/// Summary statistics of a device's metric over a time range.
+///
+/// Every statistic is `None` when the range holds no numeric sample.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct MetricStats {
+    /// The metric that was summarized
+    pub metric: String,
+    /// Number of numeric samples
+    pub count: usize,
+    /// Mean of the samples
+    #[serde(default)]
+    pub mean: Option<f64>,
+    /// Smallest sample
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Largest sample
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Most recent sample
+    #[serde(default)]
+    pub latest: Option<f64>,
+}
+
+/// Formats a statistic for a stat card.
+///
+/// # Parameters
+/// * `value` - The statistic, if the range holds samples
+///
+/// # Returns
+/// * The value rounded to two decimals without trailing zeros, e.g. "23.4",
+///   or "—" without a value
+pub fn format_stat(value: Option<f64>) -> String {
+    match value {
+        Some(value) => {
+            let rounded = format!("{:.2}", value);
+            let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+            // Avoid showing "-0" for small negative values
+            if trimmed == "-0" { "0".to_string() } else { trimmed.to_string() }
+        }
+        None => "—".to_string(),
+    }
+}
@@ -0,0 +1,162 @@
+/// # Telemetry Table Models
+///
+/// This module defines the raw telemetry table: pages of records read from
+/// the device monitor read API (`/iot/data/read`), the columns shown for
+/// them and the sorting of a page by any column. Pages are fetched newest
+/// first and followed with the API's continuation token; sorting only
+/// reorders the page on screen. Each record keeps the document it was read
+/// from for the record inspector, and documents that cannot be read as
+/// records are listed rather than failing the page.
+
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use crate::domain::telemetry::Telemetry;
+
+/// Records shown per page of the table
+pub const TABLE_PAGE_SIZE: usize = 50;
+
+/// A page of telemetry records.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TelemetryPage {
+    /// The records, newest first
+    pub records: Vec<Telemetry>,
+    /// Documents of the page that are not valid records
+    pub unreadable: Vec<UnreadableRecord>,
+    /// Token fetching the next page, `None` on the last page
+    pub continuation: Option<String>,
+}
+
+/// A document returned by the read API that is not a valid record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnreadableRecord {
+    /// The document as returned by the API
+    pub document: Value,
+    /// Why it could not be read
+    pub error: String,
+}
+
+/// Reads the documents of a page as records.
+///
+/// # Parameters
+/// * `documents` - The documents returned by the read API, newest first
+///
+/// # Returns
+/// * The records, each keeping its document, and the documents that are
+///   not valid records
+pub fn read_documents(documents: Vec<Value>) -> (Vec<Telemetry>, Vec<UnreadableRecord>) {
+    let mut records = Vec::new();
+    let mut unreadable = Vec::new();
+    for document in documents {
+        match serde_json::from_value::<Telemetry>(document.clone()) {
+            Ok(mut record) => {
+                record.document = Some(document);
+                records.push(record);
+            }
+            Err(e) => unreadable.push(UnreadableRecord { document, error: e.to_string() }),
+        }
+    }
+    (records, unreadable)
+}
+
+/// The column a table is sorted by, and in which direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSort {
+    /// The metric sorted by, or `None` for the timestamp
+    pub column: Option<String>,
+    /// Whether the smallest value comes first
+    pub ascending: bool,
+}
+
+impl Default for TableSort {
+    /// Newest records first, the order pages are fetched in.
+    fn default() -> Self {
+        TableSort { column: None, ascending: false }
+    }
+}
+
+impl TableSort {
+    /// The sort after clicking a column header.
+    ///
+    /// # Parameters
+    /// * `column` - The clicked column, `None` for the timestamp
+    ///
+    /// # Returns
+    /// * The opposite direction if already sorted by the column, otherwise
+    ///   the column ascending, or newest first for the timestamp
+    pub fn toggle(&self, column: Option<String>) -> Self {
+        if self.column == column {
+            TableSort { column, ascending: !self.ascending }
+        } else {
+            let ascending = column.is_some();
+            TableSort { column, ascending }
+        }
+    }
+
+    /// Arrow shown next to a column header, empty unless sorted by it.
+    pub fn indicator(&self, column: Option<&str>) -> &'static str {
+        match (self.column.as_deref() == column, self.ascending) {
+            (false, _) => "",
+            (true, true) => " ▲",
+            (true, false) => " ▼",
+        }
+    }
+}
+
+/// Lists the metric columns of a page.
+///
+/// # Parameters
+/// * `records` - The page's records
+///
+/// # Returns
+/// * Sorted, de-duplicated names of every metric in any record, numeric or not
+pub fn table_columns(records: &[Telemetry]) -> Vec<String> {
+    let mut columns: Vec<String> = records
+        .iter()
+        .flat_map(|record| record.telemetry_data.keys().cloned())
+        .collect();
+
+    columns.sort();
+    columns.dedup();
+    columns
+}
+
+/// Sorts a page's records.
+///
+/// Values compare as numbers when both are numeric and as text otherwise;
+/// records without a value for the column come last in either direction.
+///
+/// # Parameters
+/// * `records` - The page's records
+/// * `sort` - The column and direction to sort by
+pub fn sort_records(records: &mut [Telemetry], sort: &TableSort) {
+    records.sort_by(|a, b| match &sort.column {
+        None => order_missing_last(a.timestamp, b.timestamp, sort.ascending, i64::cmp),
+        Some(column) => order_missing_last(
+            a.telemetry_data.get(column),
+            b.telemetry_data.get(column),
+            sort.ascending,
+            |a, b| compare_values(a, b),
+        ),
+    });
+}
+
+/// Orders two optional values, keeping missing ones last.
+fn order_missing_last<T>(a: Option<T>, b: Option<T>, ascending: bool, compare: impl Fn(&T, &T) -> Ordering) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if ascending => compare(&a, &b),
+        (Some(a), Some(b)) => compare(&b, &a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Compares two reported values, numerically when both are numbers.
+fn compare_values(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.cmp(b),
+    }
+}
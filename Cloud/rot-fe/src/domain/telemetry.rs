@@ -88,6 +88,11 @@ pub struct Telemetry {
     /// Cosmos DB attachments
     #[serde(rename = "_attachments", skip_serializing_if = "Option::is_none")]
     attachments: Option<String>,
+
+    /// The document as returned by the API, metadata included, for the
+    /// record inspector; only kept for the records of the telemetry table
+    #[serde(skip)]
+    pub document: Option<serde_json::Value>,
 }
 
 /// Error types for telemetry validation and processing.
@@ -156,6 +161,7 @@ impl Telemetry {
             etag: None,
             attachments: None,
             timestamp: Some(timestamp),
+            document: None,
         }
     }
 
@@ -210,6 +216,45 @@ impl Telemetry {
             etag: None,
             attachments: None,
             timestamp: Some(timestamp),
+            document: None,
         })
     }
 }
+
+/// Lists the metrics a device has reported numeric values for.
+///
+/// Devices report arbitrary metrics, so the chartable ones are discovered
+/// from the records rather than assumed.
+///
+/// # Parameters
+/// * `records` - Telemetry records of the device
+///
+/// # Returns
+/// * Sorted, de-duplicated names of every metric with a numeric value in
+///   at least one record
+pub fn numeric_metrics(records: &[Telemetry]) -> Vec<String> {
+    let mut metrics: Vec<String> = records
+        .iter()
+        .flat_map(|record| record.telemetry_data.iter())
+        .filter(|(_, value)| value.parse::<f64>().is_ok())
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    metrics.sort();
+    metrics.dedup();
+    metrics
+}
+
+/// Formats a Unix timestamp into a human-readable date string.
+///
+/// # Parameters
+/// * `timestamp` - Unix timestamp (seconds since epoch)
+///
+/// # Returns
+/// * Formatted date string in "YYYY-MM-DD HH:MM:SS UTC" format
+/// * If conversion fails, returns the raw timestamp as string
+pub fn format_timestamp(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| format!("{}", timestamp))
+}
@@ -0,0 +1,54 @@
+/// # Theme Domain Model
+///
+/// This module defines the colour themes of the application. The theme is
+/// applied by toggling the `dark` class on the document root, which turns
+/// on the `dark:` Tailwind variants, and is passed to ApexCharts as its
+/// theme mode.
+
+/// Colour theme of the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    /// Dark text on light backgrounds
+    #[default]
+    Light,
+    /// Light text on dark backgrounds, for low-light environments
+    Dark,
+}
+
+impl Theme {
+    /// Name of the theme, as persisted and as ApexCharts' `theme.mode`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    /// Parses a persisted theme name.
+    ///
+    /// # Parameters
+    /// * `key` - The name returned by `key`
+    ///
+    /// # Returns
+    /// * The theme, or `None` for an unknown name
+    pub fn from_key(key: &str) -> Option<Theme> {
+        match key {
+            "light" => Some(Theme::Light),
+            "dark" => Some(Theme::Dark),
+            _ => None,
+        }
+    }
+
+    /// The other theme, as switched to by the navbar toggle.
+    pub fn toggled(self) -> Theme {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+
+    /// Whether this is the dark theme.
+    pub fn is_dark(&self) -> bool {
+        *self == Theme::Dark
+    }
+}
@@ -0,0 +1,105 @@
+/// # Threshold Domain Models
+///
+/// This module defines the thresholds drawn over the telemetry charts, such
+/// as "temperature > 30": either set by the user for a metric on any device,
+/// or taken from the alert rules of the device shown. A threshold is drawn
+/// as a line at its value, with the readings that breach it shaded.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::alert::{AlertRule, Operator};
+use crate::domain::units::UnitPreferences;
+
+/// Where a threshold comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThresholdSource {
+    /// Set by the user, for the metric on every device
+    #[default]
+    User,
+    /// An alert rule of the device shown
+    AlertRule,
+}
+
+/// A threshold on a metric.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Threshold {
+    /// The metric the threshold applies to
+    pub metric: String,
+    /// Which side of the value breaches the threshold
+    pub operator: Operator,
+    /// The threshold value
+    pub value: f64,
+    /// Where the threshold comes from; only user thresholds are saved
+    #[serde(skip)]
+    pub source: ThresholdSource,
+}
+
+impl Threshold {
+    /// Builds a user threshold from the fields of the threshold form.
+    ///
+    /// # Parameters
+    /// * `metric` - The metric field
+    /// * `operator` - The chosen operator
+    /// * `value` - The value field
+    ///
+    /// # Returns
+    /// * `Ok(Threshold)` - The threshold, with the metric trimmed
+    /// * `Err(String)` - Why the fields are not a valid threshold
+    pub fn parse(metric: &str, operator: Operator, value: &str) -> Result<Self, String> {
+        let metric = metric.trim();
+        if metric.is_empty() {
+            return Err("Choose the metric the threshold applies to.".to_string());
+        }
+        let value = value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| "The threshold must be a number.".to_string())?;
+        Ok(Threshold { metric: metric.to_string(), operator, value, source: ThresholdSource::User })
+    }
+
+    /// The threshold drawn for an alert rule.
+    pub fn from_rule(rule: &AlertRule) -> Self {
+        Threshold {
+            metric: rule.metric.clone(),
+            operator: rule.operator,
+            value: rule.value,
+            source: ThresholdSource::AlertRule,
+        }
+    }
+
+    /// Describes the threshold in the units the user chose, e.g. "temperature > 86°F".
+    pub fn describe(&self, units: &UnitPreferences) -> String {
+        format!("{} {} {}", self.metric, self.operator.symbol(), units.format(&self.metric, self.value))
+    }
+
+    /// The y-axis range of breaching readings, shaded on the chart.
+    ///
+    /// # Parameters
+    /// * `min` - Lowest charted reading
+    /// * `max` - Highest charted reading
+    ///
+    /// # Returns
+    /// * The range from the value to the highest reading for `>`/`≥`, or from
+    ///   the lowest reading to the value for `<`/`≤`, if any reading is on
+    ///   that side; `None` for `=`/`≠`, which are drawn as a line only
+    pub fn breach_region(&self, min: f64, max: f64) -> Option<(f64, f64)> {
+        match self.operator {
+            Operator::Gt | Operator::Gte if max > self.value => Some((self.value, max)),
+            Operator::Lt | Operator::Lte if min < self.value => Some((min, self.value)),
+            _ => None,
+        }
+    }
+}
+
+/// Adds a user threshold, unless the same one is already set.
+///
+/// # Parameters
+/// * `thresholds` - The user thresholds
+/// * `threshold` - The threshold to add
+pub fn add_threshold(thresholds: &mut Vec<Threshold>, threshold: Threshold) {
+    if !thresholds.contains(&threshold) {
+        thresholds.push(threshold);
+    }
+}
@@ -0,0 +1,277 @@
+/// # Units Domain Models
+///
+/// This module defines the units telemetry is shown in. Devices report
+/// temperatures in degrees Celsius; the user can have them shown in degrees
+/// Fahrenheit instead, and can scale any other metric linearly, e.g.
+/// pressure from hPa to inHg. Conversions only change what is shown: the
+/// APIs, saved thresholds and alert rules keep the reported units.
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::anomaly::format_reading;
+
+/// Metric whose unit is chosen with the temperature toggle.
+pub const TEMPERATURE_METRIC: &str = "temperature";
+
+/// Unit temperatures are shown in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    /// Degrees Celsius, as reported by the devices
+    #[default]
+    Celsius,
+    /// Degrees Fahrenheit
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Every temperature unit, in display order.
+    pub const ALL: [TemperatureUnit; 2] = [TemperatureUnit::Celsius, TemperatureUnit::Fahrenheit];
+
+    /// Stable identifier used as the `<input>` value.
+    pub fn key(&self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "celsius",
+            TemperatureUnit::Fahrenheit => "fahrenheit",
+        }
+    }
+
+    /// Parses a temperature unit from its `key()`.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|unit| unit.key() == key)
+    }
+
+    /// Unit symbol, e.g. "°F".
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+        }
+    }
+
+    /// The other unit, as switched to by the navbar toggle.
+    pub fn toggled(self) -> Self {
+        match self {
+            TemperatureUnit::Celsius => TemperatureUnit::Fahrenheit,
+            TemperatureUnit::Fahrenheit => TemperatureUnit::Celsius,
+        }
+    }
+
+    /// Converts a reading in degrees Celsius to this unit.
+    pub fn convert(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Fahrenheit => value * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    /// Converts a value in this unit back to degrees Celsius.
+    pub fn revert(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+        }
+    }
+}
+
+/// A linear scaling of a metric, shown as `value * factor + offset`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricScale {
+    /// The metric scaled
+    pub metric: String,
+    /// Multiplier applied to the reported value
+    pub factor: f64,
+    /// Added after multiplying
+    #[serde(default)]
+    pub offset: f64,
+    /// Unit of the scaled value, e.g. "inHg"
+    #[serde(default)]
+    pub unit: String,
+}
+
+impl MetricScale {
+    /// Builds a scaling from the fields of the settings form.
+    ///
+    /// # Parameters
+    /// * `scales` - The scalings already set, to reject a second one for a metric
+    /// * `metric` - The metric field
+    /// * `factor` - The factor field
+    /// * `offset` - The offset field, zero if blank
+    /// * `unit` - The unit field
+    ///
+    /// # Returns
+    /// * `Ok(MetricScale)` - The scaling, with the metric and unit trimmed
+    /// * `Err(String)` - Why the fields are not a valid scaling
+    pub fn parse(
+        scales: &[MetricScale],
+        metric: &str,
+        factor: &str,
+        offset: &str,
+        unit: &str,
+    ) -> Result<Self, String> {
+        let metric = metric.trim();
+        if metric.is_empty() {
+            return Err("Enter the metric to scale.".to_string());
+        }
+        if metric.eq_ignore_ascii_case(TEMPERATURE_METRIC) {
+            return Err("Choose °C or °F for temperature instead.".to_string());
+        }
+        if scales.iter().any(|scale| scale.metric.eq_ignore_ascii_case(metric)) {
+            return Err(format!("{} is already scaled; remove its scaling first.", metric));
+        }
+        // A positive factor keeps thresholds on the same side of the readings
+        let factor = factor
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|factor| factor.is_finite() && *factor > 0.0)
+            .ok_or_else(|| "The factor must be a positive number.".to_string())?;
+        let offset = match offset.trim() {
+            "" => 0.0,
+            offset => offset
+                .parse::<f64>()
+                .ok()
+                .filter(|offset| offset.is_finite())
+                .ok_or_else(|| "The offset must be a number.".to_string())?,
+        };
+        Ok(MetricScale {
+            metric: metric.to_string(),
+            factor,
+            offset,
+            unit: unit.trim().to_string(),
+        })
+    }
+
+    /// Describes the scaling, e.g. "pressure × 0.02953 inHg".
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} × {}", self.metric, self.factor);
+        if self.offset != 0.0 {
+            description.push_str(&format!(" {} {}", if self.offset < 0.0 { "−" } else { "+" }, self.offset.abs()));
+        }
+        if !self.unit.is_empty() {
+            description.push_str(&format!(" {}", self.unit));
+        }
+        description
+    }
+}
+
+/// The units the user chose, saved in local storage.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct UnitPreferences {
+    /// Unit temperatures are shown in
+    #[serde(default)]
+    pub temperature: TemperatureUnit,
+    /// Scalings of other metrics
+    #[serde(default)]
+    pub scales: Vec<MetricScale>,
+}
+
+impl UnitPreferences {
+    /// Converts a reported reading of a metric to the unit it is shown in.
+    ///
+    /// # Parameters
+    /// * `metric` - The metric the reading belongs to
+    /// * `value` - The reading, in the unit the device reported
+    ///
+    /// # Returns
+    /// * The reading in the chosen unit, unchanged for metrics the user did not scale
+    pub fn convert(&self, metric: &str, value: f64) -> f64 {
+        if metric.eq_ignore_ascii_case(TEMPERATURE_METRIC) {
+            return self.temperature.convert(value);
+        }
+        match self.scale(metric) {
+            Some(scale) => value * scale.factor + scale.offset,
+            None => value,
+        }
+    }
+
+    /// Converts a value shown in the chosen unit back to the reported unit,
+    /// e.g. a threshold the user typed.
+    pub fn revert(&self, metric: &str, value: f64) -> f64 {
+        if metric.eq_ignore_ascii_case(TEMPERATURE_METRIC) {
+            return self.temperature.revert(value);
+        }
+        match self.scale(metric) {
+            Some(scale) => (value - scale.offset) / scale.factor,
+            None => value,
+        }
+    }
+
+    /// Whether readings of the metric are shown converted.
+    pub fn converts(&self, metric: &str) -> bool {
+        if metric.eq_ignore_ascii_case(TEMPERATURE_METRIC) {
+            self.temperature != TemperatureUnit::Celsius
+        } else {
+            self.scale(metric).is_some()
+        }
+    }
+
+    /// Unit a metric is shown in, e.g. "°F", if it is known.
+    pub fn unit(&self, metric: &str) -> Option<String> {
+        let lower = metric.to_lowercase();
+        if lower == TEMPERATURE_METRIC {
+            return Some(self.temperature.symbol().to_string());
+        }
+        if let Some(scale) = self.scale(metric) {
+            return Some(scale.unit.clone()).filter(|unit| !unit.is_empty());
+        }
+        match lower.as_str() {
+            "pressure" => Some("hPa".to_string()),
+            "voltage" => Some("V".to_string()),
+            "humidity" => Some("%".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Metric name with its unit, e.g. "temperature (°F)", for column headings
+    /// and chart axes.
+    pub fn label(&self, metric: &str) -> String {
+        match self.unit(metric) {
+            Some(unit) => format!("{} ({})", metric, unit),
+            None => metric.to_string(),
+        }
+    }
+
+    /// Formats a reading of a metric with its unit, e.g. "73.4°F".
+    ///
+    /// # Parameters
+    /// * `metric` - The metric the reading belongs to
+    /// * `value` - The reading, in the unit the device reported
+    pub fn format(&self, metric: &str, value: f64) -> String {
+        self.with_unit(metric, format_reading(self.convert(metric, value)))
+    }
+
+    /// Formats a reading as stored in a telemetry record.
+    ///
+    /// # Parameters
+    /// * `metric` - The metric the reading belongs to
+    /// * `raw` - The reading as reported
+    /// * `with_unit` - Whether to append the unit
+    ///
+    /// # Returns
+    /// * The converted reading, or the reading exactly as reported if the
+    ///   metric is not converted or the reading is not a number
+    pub fn format_raw(&self, metric: &str, raw: &str, with_unit: bool) -> String {
+        let value = match raw.trim().parse::<f64>() {
+            Ok(value) if self.converts(metric) => format_reading(self.convert(metric, value)),
+            Ok(_) => raw.to_string(),
+            Err(_) => return raw.to_string(),
+        };
+        if with_unit { self.with_unit(metric, value) } else { value }
+    }
+
+    /// The scaling the user set for a metric.
+    fn scale(&self, metric: &str) -> Option<&MetricScale> {
+        self.scales.iter().find(|scale| scale.metric.eq_ignore_ascii_case(metric))
+    }
+
+    /// Appends the metric's unit to a formatted value; degrees and percentages
+    /// follow the number directly, other units after a space.
+    fn with_unit(&self, metric: &str, value: String) -> String {
+        match self.unit(metric) {
+            Some(unit) if unit.starts_with('°') || unit == "%" => format!("{}{}", value, unit),
+            Some(unit) => format!("{} {}", value, unit),
+            None => value,
+        }
+    }
+}
@@ -1,9 +1,10 @@
 // Import Yew framework prelude for web application development
 use yew::prelude::*;
 // Import custom components for navigation and header
-use components::{Header, Navbar};
-// Import view components for different application sections
-use views::{TelemetryView, ConfigView};
+use components::{Header, Navbar, ThemeProvider, UnitsProvider};
+// Import the router and the application routes
+use yew_router::prelude::*;
+use route::{switch, Route};
 // Import WASM-specific tracing configuration
 use tracing_wasm::WASMLayerConfigBuilder;
 // Import tracing subscriber prelude for logging setup
@@ -14,40 +15,29 @@ mod components;  // UI components like header, navbar, charts
 mod services;    // Business logic and API services
 mod domain;      // Data models and domain logic
 mod views;       // Main view components for different pages
+mod route;       // URL routes mapping paths to views
 
 /// Main application component that handles routing and layout
-/// This component manages the current view state and renders the appropriate content
+/// The current view and device are taken from the URL, so views can be
+/// bookmarked and the browser's back button works; the colour theme and
+/// the units telemetry is shown in are shared with every component through
+/// the theme and units providers
 #[function_component(App)]
 fn app() -> Html {
-    // State to track which view is currently active (telemetry or config)
-    // Default to "telemetry" view when the app starts
-    let current_view = use_state(|| "telemetry".to_string());
-
-    // Callback function to handle navigation clicks
-    // This function updates the current view state when user clicks navigation items
-    let on_nav_click = {
-        let current_view = current_view.clone();
-        Callback::from(move |view: String| {
-            current_view.set(view);
-        })
-    };
-
-    // Render the main application layout
+    // Render the main application layout inside the theme and units providers and the browser router
     html! {
-        <div>
-            // Navigation bar component with click handler and current view indicator
-            <Navbar on_nav_click={on_nav_click} current_view={(*current_view).clone()} />
-            // Header component for branding/title
-            <Header />
-            // Conditional rendering based on current view selection
-            {
-                match (*current_view).as_str() {
-                    "telemetry" => html! { <TelemetryView /> },
-                    "config" => html! { <ConfigView /> },
-                    _ => html! { <TelemetryView /> },  // Default fallback to telemetry view
-                }
-            }
-        </div>
+        <ThemeProvider>
+            <UnitsProvider>
+                <BrowserRouter>
+                    // Navigation bar component, highlighting the view of the current route
+                    <Navbar />
+                    // Header component for branding/title
+                    <Header />
+                    // Render the view matching the current URL
+                    <Switch<Route> render={switch} />
+                </BrowserRouter>
+            </UnitsProvider>
+        </ThemeProvider>
     }
 }
 
@@ -65,6 +55,10 @@ fn main() {
     // Log application startup for debugging purposes
     tracing::info!("Application starting...");
     
-    // Start the Yew application by rendering the App component
-    yew::Renderer::<App>::new().render();
+    // Resolve the API URLs of this deployment, then start the Yew
+    // application by rendering the App component
+    wasm_bindgen_futures::spawn_local(async {
+        services::settings_service::SettingsService::init().await;
+        yew::Renderer::<App>::new().render();
+    });
 }
\ No newline at end of file
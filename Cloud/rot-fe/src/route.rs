@@ -0,0 +1,147 @@
+/// # Routes
+///
+/// This module defines the URL routes of the application. Each view has
+/// its own path, optionally followed by the device shown, so views can be
+/// bookmarked and the browser's back button moves between them:
+/// - `/telemetry` and `/telemetry/:device_id` - Device monitoring
+/// - `/config` and `/config/:device_id` - Device configuration
+/// - `/devices` - Searchable list of the known devices
+/// - `/fleet` - Overview of the whole fleet
+/// - `/alerts` and `/alerts/:device_id` - Alert rules and raised alerts
+/// - `/dashboard` - The user's own dashboard of widgets
+/// - `/settings` - Base URLs of the backend APIs
+/// - `/auth/callback` - Where Azure AD redirects back after sign-in
+///
+/// Any other path redirects to the telemetry view. When sign-in is enabled,
+/// every view but the callback shows the sign-in page until the user is
+/// signed in.
+
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::services::auth_service::AuthService;
+use crate::views::{AlertsView, AuthCallbackView, ConfigView, DashboardView, DevicesView, FleetView, LoginView, SettingsView, TelemetryView};
+
+/// URL routes of the application views.
+#[derive(Clone, Debug, Routable, PartialEq)]
+pub enum Route {
+    /// Application root, redirects to the telemetry view
+    #[at("/")]
+    Home,
+    /// Telemetry view for the default device
+    #[at("/telemetry")]
+    Telemetry,
+    /// Telemetry view for a given device
+    #[at("/telemetry/:device_id")]
+    TelemetryDevice { device_id: String },
+    /// Configuration view without a device selected
+    #[at("/config")]
+    Config,
+    /// Configuration view for a given device
+    #[at("/config/:device_id")]
+    ConfigDevice { device_id: String },
+    /// List of the known devices
+    #[at("/devices")]
+    Devices,
+    /// Overview of the whole fleet
+    #[at("/fleet")]
+    Fleet,
+    /// Alerts view without a device selected
+    #[at("/alerts")]
+    Alerts,
+    /// Alerts view for a given device
+    #[at("/alerts/:device_id")]
+    AlertsDevice { device_id: String },
+    /// The user's own dashboard of widgets
+    #[at("/dashboard")]
+    Dashboard,
+    /// Base URLs of the backend APIs
+    #[at("/settings")]
+    Settings,
+    /// Where Azure AD redirects back after sign-in; matches `AuthService::CALLBACK_PATH`
+    #[at("/auth/callback")]
+    AuthCallback,
+    /// Any unknown path, redirects to the telemetry view
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
+
+impl Route {
+    /// Whether the route shows the configuration view.
+    ///
+    /// # Returns
+    /// * `true` for the configuration routes
+    pub fn is_config(&self) -> bool {
+        matches!(self, Route::Config | Route::ConfigDevice { .. })
+    }
+
+    /// Whether the route shows the devices view.
+    ///
+    /// # Returns
+    /// * `true` for the devices route
+    pub fn is_devices(&self) -> bool {
+        matches!(self, Route::Devices)
+    }
+
+    /// Whether the route shows the fleet view.
+    ///
+    /// # Returns
+    /// * `true` for the fleet route
+    pub fn is_fleet(&self) -> bool {
+        matches!(self, Route::Fleet)
+    }
+
+    /// Whether the route shows the alerts view.
+    ///
+    /// # Returns
+    /// * `true` for the alerts routes
+    pub fn is_alerts(&self) -> bool {
+        matches!(self, Route::Alerts | Route::AlertsDevice { .. })
+    }
+
+    /// Whether the route shows the dashboard view.
+    ///
+    /// # Returns
+    /// * `true` for the dashboard route
+    pub fn is_dashboard(&self) -> bool {
+        matches!(self, Route::Dashboard)
+    }
+
+    /// Whether the route shows the settings view.
+    ///
+    /// # Returns
+    /// * `true` for the settings route
+    pub fn is_settings(&self) -> bool {
+        matches!(self, Route::Settings)
+    }
+}
+
+/// Renders the view for a route.
+///
+/// # Parameters
+/// * `route` - The route matched from the current URL
+///
+/// # Returns
+/// * The view to show, or a redirect for the root and unknown paths; the
+///   sign-in page while sign-in is required
+pub fn switch(route: Route) -> Html {
+    if route != Route::AuthCallback && !AuthService::is_signed_in() {
+        return html! { <LoginView /> };
+    }
+
+    match route {
+        Route::Telemetry => html! { <TelemetryView /> },
+        Route::TelemetryDevice { device_id } => html! { <TelemetryView device_id={device_id} /> },
+        Route::Config => html! { <ConfigView /> },
+        Route::ConfigDevice { device_id } => html! { <ConfigView device_id={device_id} /> },
+        Route::Devices => html! { <DevicesView /> },
+        Route::Fleet => html! { <FleetView /> },
+        Route::Alerts => html! { <AlertsView /> },
+        Route::AlertsDevice { device_id } => html! { <AlertsView device_id={device_id} /> },
+        Route::Dashboard => html! { <DashboardView /> },
+        Route::Settings => html! { <SettingsView /> },
+        Route::AuthCallback => html! { <AuthCallbackView /> },
+        Route::Home | Route::NotFound => html! { <Redirect<Route> to={Route::Telemetry} /> },
+    }
+}
@@ -0,0 +1,358 @@
+/// # Authentication Service
+///
+/// This module signs users in with Azure AD using the OAuth2 authorization
+/// code flow with PKCE, keeps the tokens for the browser tab and attaches
+/// the access token to the requests sent to the backend APIs.
+///
+/// The flow leaves the application twice: `login` redirects to the Azure AD
+/// sign-in page, which redirects back to `/auth/callback`, where
+/// `complete_login` redeems the code for tokens. Tokens are kept in session
+/// storage, so they are forgotten when the tab is closed, and renewed with
+/// the refresh token when the access token expires.
+///
+/// Authentication is configured like the API URLs: built in from the
+/// `ROT_AUTH_CLIENT_ID`, `ROT_AUTH_TENANT_ID` and `ROT_AUTH_SCOPE`
+/// environment variables and overridden by the deployment's `config.json`,
+/// as resolved by `SettingsService`; without a client ID it is disabled and
+/// requests are sent without credentials.
+
+use crate::domain::auth::{code_challenge, encode_random, PendingLogin, Session, TokenResponse};
+use crate::services::settings_service::SettingsService;
+use chrono::Utc;
+use gloo_net::http::{Request, RequestBuilder};
+use tracing::{info, instrument, Level};
+use web_sys::Storage;
+
+/// Service for signing in and authorizing API requests.
+pub struct AuthService;
+
+impl AuthService {
+    /// Session storage key holding the signed-in session as JSON.
+    const SESSION_KEY: &'static str = "rot.auth.session";
+
+    /// Session storage key holding the login in progress as JSON.
+    const PENDING_KEY: &'static str = "rot.auth.pending";
+
+    /// Path Azure AD redirects to after sign-in; must be registered as a
+    /// single-page application redirect URI of the app registration.
+    pub const CALLBACK_PATH: &'static str = "/auth/callback";
+
+    /// Whether users must sign in, i.e. a client ID was configured.
+    pub fn is_enabled() -> bool {
+        SettingsService::auth().is_enabled()
+    }
+
+    /// Whether the views can be shown.
+    ///
+    /// # Returns
+    /// * `true` if authentication is disabled, or a session exists whose
+    ///   access token is fresh or can be renewed
+    pub fn is_signed_in() -> bool {
+        if !Self::is_enabled() {
+            return true;
+        }
+        Self::load_session().is_some_and(|session| session.is_fresh(Utc::now().timestamp()) || session.refresh_token.is_some())
+    }
+
+    /// Name of the signed-in user, if known.
+    pub fn user_name() -> Option<String> {
+        Self::load_session().and_then(|session| session.user_name())
+    }
+
+    /// Starts signing in by redirecting to the Azure AD sign-in page.
+    ///
+    /// # Parameters
+    /// * `return_to` - Path to show once signed in
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the browser is being redirected
+    /// * `Err(String)` - Error message if the login could not be started
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track sign-ins
+    #[instrument(level = Level::INFO)]
+    pub fn login(return_to: &str) -> Result<(), String> {
+        info!("Starting sign-in");
+
+        let pending = PendingLogin {
+            verifier: encode_random(&Self::random_bytes::<32>()?),
+            state: encode_random(&Self::random_bytes::<16>()?),
+            return_to: return_to.to_string(),
+        };
+        let json = serde_json::to_string(&pending).map_err(|e| format!("JSON serialize failed: {}", e))?;
+        Self::storage()?
+            .set_item(Self::PENDING_KEY, &json)
+            .map_err(|_| "Failed to save the login".to_string())?;
+
+        let settings = SettingsService::auth();
+        let url = format!(
+            "{}/authorize?{}",
+            Self::endpoint_base(),
+            Self::encode_params(&[
+                ("client_id", &settings.client_id),
+                ("response_type", "code"),
+                ("redirect_uri", &Self::redirect_uri()?),
+                ("response_mode", "query"),
+                ("scope", &settings.scope),
+                ("state", &pending.state),
+                ("code_challenge", &code_challenge(&pending.verifier)),
+                ("code_challenge_method", "S256"),
+            ])
+        );
+        Self::window()?
+            .location()
+            .set_href(&url)
+            .map_err(|_| "Failed to open the sign-in page".to_string())
+    }
+
+    /// Finishes signing in by redeeming the code Azure AD redirected back with.
+    ///
+    /// # Parameters
+    /// * `code` - The authorization code from the callback URL
+    /// * `state` - The state from the callback URL
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The path to return to
+    /// * `Err(String)` - Error message if the callback does not match the
+    ///   login started in this tab or the code cannot be redeemed
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track sign-ins
+    #[instrument(skip_all, level = Level::INFO)]
+    pub async fn complete_login(code: &str, state: &str) -> Result<String, String> {
+        info!("Completing sign-in");
+
+        let storage = Self::storage()?;
+        let pending: PendingLogin = storage
+            .get_item(Self::PENDING_KEY)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .ok_or_else(|| "No sign-in was started in this tab".to_string())?;
+        let _ = storage.remove_item(Self::PENDING_KEY);
+
+        if pending.state != state {
+            info!("Sign-in state mismatch");
+            return Err("The sign-in response does not match the sign-in started in this tab".to_string());
+        }
+
+        let session = Self::request_token(
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &Self::redirect_uri()?),
+                ("code_verifier", &pending.verifier),
+            ],
+            None,
+        )
+        .await?;
+        Self::save_session(&session)?;
+
+        info!("Signed in successfully");
+        Ok(pending.return_to)
+    }
+
+    /// Signs out, forgetting the tokens and ending the Azure AD session.
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track sign-outs
+    #[instrument(level = Level::INFO)]
+    pub fn logout() {
+        info!("Signing out");
+
+        if let Ok(storage) = Self::storage() {
+            let _ = storage.remove_item(Self::SESSION_KEY);
+        }
+        let Ok(window) = Self::window() else { return };
+        let origin = window.location().origin().unwrap_or_default();
+        let url = format!(
+            "{}/logout?{}",
+            Self::endpoint_base(),
+            Self::encode_params(&[("post_logout_redirect_uri", &origin)])
+        );
+        let _ = window.location().set_href(&url);
+    }
+
+    /// Attaches the access token to a request to a backend API.
+    ///
+    /// An expired access token is renewed with the refresh token first. If
+    /// it cannot be renewed, the session is forgotten and the user is sent
+    /// to sign in again, returning to the current page.
+    ///
+    /// # Parameters
+    /// * `request` - The request to authorize
+    ///
+    /// # Returns
+    /// * The request with an `Authorization: Bearer` header, or unchanged if
+    ///   authentication is disabled or no token is available
+    pub async fn authorize(request: RequestBuilder) -> RequestBuilder {
+        if !Self::is_enabled() {
+            return request;
+        }
+        let Some(session) = Self::load_session() else {
+            return request;
+        };
+
+        let session = if session.is_fresh(Utc::now().timestamp()) {
+            Some(session)
+        } else {
+            Self::refresh(&session).await
+        };
+
+        match session {
+            Some(session) => request.header("Authorization", &format!("Bearer {}", session.access_token)),
+            None => {
+                Self::sign_in_again();
+                request
+            }
+        }
+    }
+
+    /// Renews an expired access token.
+    ///
+    /// # Parameters
+    /// * `session` - The session whose access token expired
+    ///
+    /// # Returns
+    /// * The renewed session, saved for later requests, or `None` if it has
+    ///   no refresh token or renewing failed
+    #[instrument(skip_all, level = Level::INFO)]
+    async fn refresh(session: &Session) -> Option<Session> {
+        let refresh_token = session.refresh_token.as_deref()?;
+        info!("Renewing access token");
+
+        match Self::request_token(&[("grant_type", "refresh_token"), ("refresh_token", refresh_token)], Some(session)).await {
+            Ok(renewed) => {
+                let _ = Self::save_session(&renewed);
+                Some(renewed)
+            }
+            Err(e) => {
+                info!(error = %e, "Failed to renew access token");
+                None
+            }
+        }
+    }
+
+    /// Forgets the session and starts signing in, returning to the current page.
+    fn sign_in_again() {
+        if let Ok(storage) = Self::storage() {
+            let _ = storage.remove_item(Self::SESSION_KEY);
+        }
+        let return_to = Self::window()
+            .ok()
+            .map(|window| {
+                let location = window.location();
+                format!("{}{}", location.pathname().unwrap_or_default(), location.search().unwrap_or_default())
+            })
+            .unwrap_or_else(|| "/".to_string());
+        if let Err(e) = Self::login(&return_to) {
+            info!(error = %e, "Failed to start sign-in");
+        }
+    }
+
+    /// Posts a grant to the token endpoint.
+    ///
+    /// # Parameters
+    /// * `grant` - The grant's form parameters; the client ID and scope are added
+    /// * `previous` - The session being refreshed, if any
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - The session started from the response
+    /// * `Err(String)` - Error message, with Azure AD's explanation if it gave one
+    async fn request_token(grant: &[(&str, &str)], previous: Option<&Session>) -> Result<Session, String> {
+        let settings = SettingsService::auth();
+        let mut params = vec![("client_id", settings.client_id.as_str()), ("scope", settings.scope.as_str())];
+        params.extend_from_slice(grant);
+        let url = format!("{}/token", Self::endpoint_base());
+
+        let response = Request::post(&url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Self::encode_params(&params))
+            .map_err(|e| format!("Request failed: {}", e))?
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to reach the token endpoint");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            let reason = response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|body| body["error_description"].as_str().map(str::to_string))
+                .unwrap_or_else(|| format!("status {}", status_code));
+            info!(status = %status_code, "Token request failed");
+            return Err(format!("Sign-in failed: {}", reason));
+        }
+
+        let tokens = response
+            .json::<TokenResponse>()
+            .await
+            .map_err(|e| format!("JSON parse failed: {}", e))?;
+        Ok(Session::from_response(tokens, Utc::now().timestamp(), previous))
+    }
+
+    /// Base URL of the tenant's OAuth2 endpoints.
+    fn endpoint_base() -> String {
+        format!("https://login.microsoftonline.com/{}/oauth2/v2.0", SettingsService::auth().tenant_id)
+    }
+
+    /// Absolute URL of the callback route on this origin.
+    fn redirect_uri() -> Result<String, String> {
+        let origin = Self::window()?
+            .location()
+            .origin()
+            .map_err(|_| "Failed to read the page origin".to_string())?;
+        Ok(format!("{}{}", origin, Self::CALLBACK_PATH))
+    }
+
+    /// Encodes parameters as a query string or form body.
+    fn encode_params(params: &[(&str, &str)]) -> String {
+        params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, String::from(js_sys::encode_uri_component(value))))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// Draws random bytes from the browser's cryptographic generator.
+    fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+        let mut bytes = [0u8; N];
+        Self::window()?
+            .crypto()
+            .map_err(|_| "Secure random numbers are not available".to_string())?
+            .get_random_values_with_u8_array(&mut bytes)
+            .map_err(|_| "Secure random numbers are not available".to_string())?;
+        Ok(bytes)
+    }
+
+    /// Loads the signed-in session, if any.
+    fn load_session() -> Option<Session> {
+        let json = Self::storage().ok()?.get_item(Self::SESSION_KEY).ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Saves the signed-in session.
+    fn save_session(session: &Session) -> Result<(), String> {
+        let json = serde_json::to_string(session).map_err(|e| format!("JSON serialize failed: {}", e))?;
+        Self::storage()?
+            .set_item(Self::SESSION_KEY, &json)
+            .map_err(|_| "Failed to save the session".to_string())
+    }
+
+    /// Returns the browser window, if available.
+    fn window() -> Result<web_sys::Window, String> {
+        web_sys::window().ok_or_else(|| "No window available".to_string())
+    }
+
+    /// Returns the tab's session storage, if available.
+    fn storage() -> Result<Storage, String> {
+        Self::window()?
+            .session_storage()
+            .map_err(|_| "Session storage is not accessible".to_string())?
+            .ok_or_else(|| "Session storage is not available".to_string())
+    }
+}
@@ -0,0 +1,105 @@
+/// # Cache Service
+///
+/// This module keeps the last successful telemetry and configuration
+/// responses of each device in the browser's local storage, so they can be
+/// shown when the backends are unreachable. Entries are replaced by every
+/// newer response; when storage is full, the cache is cleared to make room
+/// rather than failing the request.
+
+use crate::domain::cache::CachedResponse;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{info, instrument, Level};
+use web_sys::Storage;
+
+/// Service for caching API responses.
+pub struct CacheService;
+
+impl CacheService {
+    /// Prefix of the local storage keys holding cached responses.
+    const KEY_PREFIX: &'static str = "rot.cache.";
+
+    /// Returns the browser's local storage, if available.
+    fn storage() -> Result<Storage, String> {
+        web_sys::window()
+            .ok_or_else(|| "No window available".to_string())?
+            .local_storage()
+            .map_err(|_| "Local storage is not accessible".to_string())?
+            .ok_or_else(|| "Local storage is not available".to_string())
+    }
+
+    /// Cache key of a device's telemetry.
+    pub fn telemetry_key(device_id: &str) -> String {
+        format!("telemetry.{}", device_id)
+    }
+
+    /// Cache key of a device's configuration.
+    pub fn config_key(device_id: &str) -> String {
+        format!("config.{}", device_id)
+    }
+
+    /// Caches a response, replacing the one cached under the same key.
+    ///
+    /// # Parameters
+    /// * `key` - The cache key, e.g. from `telemetry_key`
+    /// * `data` - The response to cache
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the response was cached
+    /// * `Err(String)` - Error message if storage is unavailable, or still
+    ///   full after clearing the cache
+    #[instrument(skip(data), level = Level::INFO)]
+    pub fn store<T: Serialize>(key: &str, data: &T) -> Result<(), String> {
+        let json = serde_json::to_string(&CachedResponse { cached_at: Utc::now().timestamp(), data })
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+
+        let storage = Self::storage()?;
+        let item = format!("{}{}", Self::KEY_PREFIX, key);
+        if storage.set_item(&item, &json).is_err() {
+            // Most likely over quota: drop every cached response and retry once
+            info!("Local storage full, clearing the cache");
+            Self::clear(&storage);
+            storage
+                .set_item(&item, &json)
+                .map_err(|_| "Failed to cache the response".to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Loads a cached response.
+    ///
+    /// # Parameters
+    /// * `key` - The cache key the response was stored under
+    ///
+    /// # Returns
+    /// * `Ok(Some(CachedResponse))` - The response and when it was cached
+    /// * `Ok(None)` - If nothing is cached under the key
+    /// * `Err(String)` - Error message if storage is unavailable or the entry is corrupt
+    #[instrument(level = Level::INFO)]
+    pub fn load<T: DeserializeOwned>(key: &str) -> Result<Option<CachedResponse<T>>, String> {
+        let stored = Self::storage()?
+            .get_item(&format!("{}{}", Self::KEY_PREFIX, key))
+            .map_err(|_| "Failed to read the cache".to_string())?;
+
+        match stored {
+            Some(json) => serde_json::from_str(&json).map(Some).map_err(|e| {
+                info!(error = %e, "Failed to parse the cached response");
+                format!("JSON parse failed: {}", e)
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Removes every cached response, leaving the user's preferences.
+    fn clear(storage: &Storage) {
+        let count = storage.length().unwrap_or(0);
+        let keys: Vec<String> = (0..count)
+            .filter_map(|index| storage.key(index).ok().flatten())
+            .filter(|key| key.starts_with(Self::KEY_PREFIX))
+            .collect();
+        for key in keys {
+            let _ = storage.remove_item(&key);
+        }
+    }
+}
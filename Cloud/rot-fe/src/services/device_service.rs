@@ -1,15 +1,42 @@
 /// # Device Service
 ///
 /// This module provides a service for interacting with device-related APIs.
-/// It handles fetching telemetry data and updating device configurations.
+/// It handles listing devices and reading their status, summarizing the
+/// fleet, fetching, streaming, summarizing and exporting telemetry data,
+/// fetching annotations, managing alert rules and alerts and reading,
+/// updating and rolling back device configurations.
+///
+/// Requests carry the signed-in user's access token, see `AuthService`, and
+/// go to the API URLs resolved when the application starts, see
+/// `SettingsService`. The `_or_cached` methods fall back on the last
+/// response cached by `CacheService` when a backend is unreachable.
 ///
 /// The service communicates with two backend services:
-/// - Device Monitor API - for fetching telemetry data
-/// - Device Config API - for updating device configurations
+/// - Device Monitor API - for listing devices and reading their status, summarizing the fleet, fetching, streaming, summarizing and exporting telemetry data and managing annotations, alert rules and alerts
+/// - Device Config API - for reading, updating and rolling back device configurations and listing their history
 
+use futures::channel::mpsc::UnboundedSender;
+use futures::{future, Stream};
 use gloo_net::http::Request;
+use js_sys::{Promise, Reflect, Uint8Array};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, AbortSignal, ReadableStreamDefaultReader};
 use crate::domain::telemetry::Telemetry;
-use crate::domain::config::DeviceConfig;
+use crate::services::auth_service::AuthService;
+use crate::services::settings_service::SettingsService;
+use crate::services::cache_service::CacheService;
+use crate::domain::cache::{is_unreachable, records_to_cache, Fetched};
+use crate::domain::config::{ConfigVersion, DeviceConfig, ReportedConfig, StoredConfig};
+use crate::domain::annotation::{Annotation, AnnotationRequest};
+use crate::domain::alert::{Alert, AlertRule, AlertRuleRequest};
+use crate::domain::device::{DeviceStatusReport, DeviceSummary};
+use crate::domain::fleet::{FleetHealth, FleetSummary};
+use crate::domain::stats::MetricStats;
+use crate::domain::anomaly::AnomalyReport;
+use crate::domain::table::{read_documents, TelemetryPage};
+use crate::domain::sse::SseParser;
+use std::collections::HashMap;
 use tracing::{info, instrument, Level};
 
 /// Service for interacting with device APIs.
@@ -18,20 +45,280 @@ use tracing::{info, instrument, Level};
 /// backend services to fetch and update device data.
 pub struct DeviceService;
 
+/// Most devices the batch read endpoint accepts in one request
+const MAX_BATCH_DEVICES: usize = 50;
+
+/// Name of the server-sent event carrying a telemetry record
+const TELEMETRY_EVENT: &str = "telemetry";
+
+/// Time to wait before reopening a dropped telemetry stream, in milliseconds
+const STREAM_RETRY_MS: i32 = 3000;
+
+/// An open telemetry stream, see `DeviceService::stream_telemetry`.
+///
+/// Dropping it cancels the request and stops reconnecting.
+pub struct StreamConnection(AbortController);
+
+impl Drop for StreamConnection {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 impl DeviceService {
-    /// Base URL for the device monitor API.
+    /// Lists every device that has reported telemetry.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<DeviceSummary>)` - The devices with their last-seen time and
+    ///   status, sorted by device ID
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(level = Level::INFO)]
+    pub async fn get_devices() -> Result<Vec<DeviceSummary>, String> {
+        info!("Fetching devices");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/devices", base_url);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch devices");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Device request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<Vec<DeviceSummary>>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse devices");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Fetches whether a device is still reporting.
+    ///
+    /// The device monitor judges the device online, stale or offline by the
+    /// age of its latest telemetry.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch the status of
+    ///
+    /// # Returns
+    /// * `Ok(DeviceStatusReport)` - The device's status and when it last reported
+    /// * `Err(String)` - Error message if the request fails, "404" if the
+    ///   device has no telemetry
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_device_status(device_id: &str) -> Result<DeviceStatusReport, String> {
+        info!("Fetching device status");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/devices/{}/status", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch device status");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code == 404 {
+            info!("No telemetry data found for device");
+            return Err("404".to_string());
+        }
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Device status request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<DeviceStatusReport>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse device status");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Fetches the fleet summary.
+    ///
+    /// # Returns
+    /// * `Ok(FleetSummary)` - How many devices there are, how many reported
+    ///   recently and fleet-wide statistics of each metric
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(level = Level::INFO)]
+    pub async fn get_fleet_summary() -> Result<FleetSummary, String> {
+        info!("Fetching fleet summary");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/fleet/summary", base_url);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch fleet summary");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Fleet summary request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<FleetSummary>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse fleet summary");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Fetches the health score of every device.
+    ///
+    /// # Returns
+    /// * `Ok(FleetHealth)` - The fleet score and each device's score, status
+    ///   and alerts of the last 24 hours, devices needing attention first
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(level = Level::INFO)]
+    pub async fn get_fleet_health() -> Result<FleetHealth, String> {
+        info!("Fetching fleet health");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/fleet/health", base_url);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch fleet health");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Fleet health request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<FleetHealth>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse fleet health");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Fetches the recent readings of one metric for several devices.
+    ///
+    /// The devices are read through the batch read endpoint, in as many
+    /// requests as its device limit requires, sent concurrently.
+    ///
+    /// # Parameters
+    /// * `device_ids` - IDs of the devices to read
+    /// * `metric` - The metric to read
+    /// * `from` - Earliest Unix timestamp to include
+    /// * `limit` - Maximum number of records per device, newest first
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<String, Vec<Telemetry>>)` - Each device ID mapped to its
+    ///   records; devices without matching readings map to an empty list
+    /// * `Err(String)` - Error message if any request fails
     ///
-    /// This is set from the ROT_API_URL environment variable
-    /// at build time to avoid hardcoding URLs.
-    const BASE_URL: &'static str = env!("ROT_API_URL");
-    
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(devices = device_ids.len(), metric = %metric), level = Level::INFO)]
+    pub async fn read_batch(
+        device_ids: &[String],
+        metric: &str,
+        from: i64,
+        limit: usize,
+    ) -> Result<HashMap<String, Vec<Telemetry>>, String> {
+        info!("Fetching telemetry for several devices");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/read/batch", base_url);
+        info!(url = %url, "Making request to URL");
+
+        let requests = device_ids.chunks(MAX_BATCH_DEVICES).map(|chunk| {
+            let body = serde_json::json!({
+                "device_ids": chunk,
+                "from": from,
+                "limit": limit,
+                "metrics": metric,
+            });
+            let url = url.clone();
+            async move {
+                let response = AuthService::authorize(Request::post(&url)).await
+                    .json(&body)
+                    .map_err(|e| format!("JSON serialize failed: {}", e))?
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        info!(error = %e, "Failed to fetch batch telemetry");
+                        format!("Request failed: {}", e)
+                    })?;
+
+                let status_code = response.status();
+                if status_code < 200 || status_code >= 300 {
+                    info!(status = %status_code, "Batch telemetry request failed");
+                    return Err(format!("Request failed with status: {}", status_code));
+                }
+
+                response
+                    .json::<HashMap<String, Vec<Telemetry>>>()
+                    .await
+                    .map_err(|e| {
+                        info!(error = %e, "Failed to parse batch telemetry");
+                        format!("JSON parse failed: {}", e)
+                    })
+            }
+        });
+
+        let mut records = HashMap::new();
+        for batch in future::join_all(requests).await {
+            records.extend(batch?);
+        }
+        Ok(records)
+    }
+
     /// Fetches all telemetry data for a specific device.
     ///
-    /// This method queries the device monitor API to retrieve all
-    /// historical telemetry records for the specified device.
+    /// This method queries the device monitor API to retrieve the
+    /// historical telemetry records for the specified device, oldest first.
+    /// The API filters the records to the requested time range.
     ///
     /// # Parameters
     /// * `device_id` - ID of the device to fetch telemetry for
+    /// * `from` - Unix timestamp of the earliest record to fetch, if bounded
+    /// * `to` - Unix timestamp of the latest record to fetch, if bounded
     ///
     /// # Returns
     /// * `Ok(Vec<Telemetry>)` - List of telemetry records if successful
@@ -40,19 +327,25 @@ impl DeviceService {
     /// # Instrumentation
     /// This method is instrumented with tracing to track API calls
     #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
-    pub async fn get_telemetry(device_id: &str) -> Result<Vec<Telemetry>, String> {
+    pub async fn get_telemetry(device_id: &str, from: Option<i64>, to: Option<i64>) -> Result<Vec<Telemetry>, String> {
         info!("Fetching telemetry data for device");
         
-        // Ensure BASE_URL is properly formatted (remove trailing slash if present)
-        let base_url = Self::BASE_URL.trim_end_matches('/');
+        // Base URL resolved at startup, already without a trailing slash
+        let base_url = SettingsService::api_url();
         info!(base_url = %base_url, "Using base URL");
         
         // Construct the full API URL
-        let url = format!("{}/iot/data/read/{}", base_url, device_id);
+        let mut url = format!("{}/iot/data/read/{}?order=asc", base_url, device_id);
+        if let Some(from) = from {
+            url.push_str(&format!("&from={}", from));
+        }
+        if let Some(to) = to {
+            url.push_str(&format!("&to={}", to));
+        }
         info!(url = %url, "Making request to URL");
         
         // Make the HTTP request to the API
-        let response = Request::get(&url)
+        let response = AuthService::authorize(Request::get(&url)).await
             .send()
             .await
             .map_err(|e| {
@@ -76,92 +369,1136 @@ impl DeviceService {
             })
     }
 
-    /// Fetches the latest telemetry data for a specific device.
+    /// Fetches a device's telemetry like `get_telemetry`, caching the newest
+    /// records, or returns the records cached last if the API is unreachable.
     ///
-    /// This method retrieves all telemetry records for the device
-    /// and returns only the most recent one based on timestamp.
+    /// Cached records are the device's last known readings, whatever range
+    /// they were fetched for.
     ///
     /// # Parameters
     /// * `device_id` - ID of the device to fetch telemetry for
+    /// * `from` - Unix timestamp of the earliest record to fetch, if bounded
+    /// * `to` - Unix timestamp of the latest record to fetch, if bounded
     ///
     /// # Returns
-    /// * `Ok(Telemetry)` - Most recent telemetry record if available
-    /// * `Err(String)` - Error message if the request fails or no data found
+    /// * `Ok(Fetched<Vec<Telemetry>>)` - The records, and when they were
+    ///   cached if they come from the cache
+    /// * `Err(String)` - Error message if the request fails and nothing is cached
     ///
     /// # Instrumentation
     /// This method is instrumented with tracing to track API calls
     #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
-    pub async fn get_latest_telemetry(device_id: &str) -> Result<Telemetry, String> {
-        info!("Fetching latest telemetry data for device");
-        
-        // Get all telemetry data for the device
-        let telemetry_list = Self::get_telemetry(device_id).await?;
-        
-        // Find the entry with the latest timestamp
-        telemetry_list
-            .into_iter()
-            .max_by_key(|t| t.timestamp)  // Sort by timestamp (descending)
-            .ok_or_else(|| {
-                info!("No telemetry data found for device");
-                "No telemetry data found".to_string()
-            })
+    pub async fn get_telemetry_or_cached(
+        device_id: &str,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Fetched<Vec<Telemetry>>, String> {
+        let key = CacheService::telemetry_key(device_id);
+        match Self::get_telemetry(device_id, from, to).await {
+            Ok(records) => {
+                if let Err(e) = CacheService::store(&key, &records_to_cache(&records)) {
+                    info!(error = %e, "Failed to cache telemetry");
+                }
+                Ok(Fetched::live(records))
+            }
+            Err(e) if is_unreachable(&e) => match CacheService::load::<Vec<Telemetry>>(&key) {
+                Ok(Some(cached)) => {
+                    info!(cached_at = cached.cached_at, "API unreachable, using cached telemetry");
+                    Ok(Fetched::cached(cached))
+                }
+                _ => Err(e),
+            },
+            Err(e) => Err(e),
+        }
     }
 
-    /// Updates the configuration for a specific device.
+    /// Fetches a page of a device's telemetry records, newest first.
     ///
-    /// This method sends a configuration update request to the device
-    /// configuration API to change device settings.
+    /// The device monitor read API pages records with a continuation token,
+    /// sent back in the `X-Continuation-Token` header while more records follow.
     ///
     /// # Parameters
-    /// * `device_id` - ID of the device to update
-    /// * `config` - New configuration settings for the device
+    /// * `device_id` - ID of the device to fetch telemetry for
+    /// * `from` - Unix timestamp of the earliest record to fetch, if bounded
+    /// * `to` - Unix timestamp of the latest record to fetch, if bounded
+    /// * `limit` - Most records in the page
+    /// * `continuation` - Token of the page to fetch, `None` for the first page
     ///
     /// # Returns
-    /// * `Ok(())` - If update was successful
-    /// * `Err(String)` - Error message if the update fails
+    /// * `Ok(TelemetryPage)` - The records with their documents, the documents
+    ///   that are not valid records and the token of the next page
+    /// * `Err(String)` - Error message if the request fails, "404" if the
+    ///   device has no telemetry
     ///
     /// # Instrumentation
     /// This method is instrumented with tracing to track API calls
-    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
-    pub async fn update_device_config(device_id: &str, config: &DeviceConfig) -> Result<(), String> {
-        info!("Updating device configuration");
-        
-        // Get the base URL for the device configuration API
-        let base_url = env!("ROT_DC_URL").trim_end_matches('/');
-        let url = format!("{}/device-config/update", base_url);
+    #[instrument(skip_all, fields(device_id = %device_id, limit = limit), level = Level::INFO)]
+    pub async fn get_telemetry_page(
+        device_id: &str,
+        from: Option<i64>,
+        to: Option<i64>,
+        limit: usize,
+        continuation: Option<&str>,
+    ) -> Result<TelemetryPage, String> {
+        info!("Fetching a page of telemetry data for device");
+
+        let base_url = SettingsService::api_url();
+        let mut params = vec![("order", "desc".to_string()), ("limit", limit.to_string())];
+        if let Some(from) = from {
+            params.push(("from", from.to_string()));
+        }
+        if let Some(to) = to {
+            params.push(("to", to.to_string()));
+        }
+        if let Some(continuation) = continuation {
+            params.push(("continuation", continuation.to_string()));
+        }
+        let url = format!("{}/iot/data/read/{}", base_url, device_id);
         info!(url = %url, "Making request to URL");
-        
-        // Create a POST request with the config as JSON body
-        let response = Request::post(&url)
-            // Serialize the config to JSON
-            .json(config)
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .query(params)
+            .send()
+            .await
             .map_err(|e| {
-                info!(error = %e, "Failed to serialize config");
-                format!("JSON serialize failed: {}", e)
-            })?
-            // Send the request
+                info!(error = %e, "Failed to fetch telemetry page");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code == 404 {
+            info!("No telemetry data found for device");
+            return Err("404".to_string());
+        }
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Telemetry page request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        let continuation = response.headers().get("X-Continuation-Token");
+        let documents = response
+            .json::<Vec<serde_json::Value>>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse telemetry page");
+                format!("JSON parse failed: {}", e)
+            })?;
+        let (records, unreadable) = read_documents(documents);
+        if !unreadable.is_empty() {
+            info!(count = unreadable.len(), "Telemetry page holds unreadable records");
+        }
+        Ok(TelemetryPage { records, unreadable, continuation })
+    }
+
+    /// Fetches the summary statistics of a device's metric over a time range.
+    ///
+    /// The device monitor statistics endpoint computes them from every
+    /// numeric sample in the range, so the records are not downloaded.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to summarize
+    /// * `metric` - The metric to summarize (e.g. "temperature")
+    /// * `from` - Unix timestamp of the earliest record to include, if bounded
+    /// * `to` - Unix timestamp of the latest record to include, if bounded
+    ///
+    /// # Returns
+    /// * `Ok(MetricStats)` - The statistics, empty if the range holds no sample
+    /// * `Err(String)` - Error message if the request fails, "404" if the
+    ///   device has no telemetry
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id, metric = %metric), level = Level::INFO)]
+    pub async fn get_stats(device_id: &str, metric: &str, from: Option<i64>, to: Option<i64>) -> Result<MetricStats, String> {
+        info!("Fetching metric statistics for device");
+
+        let base_url = SettingsService::api_url();
+        let mut params = vec![("metric", metric.to_string())];
+        if let Some(from) = from {
+            params.push(("from", from.to_string()));
+        }
+        if let Some(to) = to {
+            params.push(("to", to.to_string()));
+        }
+        let url = format!("{}/iot/data/stats/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .query(params)
             .send()
             .await
             .map_err(|e| {
-                info!(error = %e, "Failed to update device config");
+                info!(error = %e, "Failed to fetch metric statistics");
                 format!("Request failed: {}", e)
             })?;
-        
-        // Handle 404 (device not found) specially
-        if response.status() == 404 {
-            info!("Device not found for config update");
+
+        let status_code = response.status();
+        if status_code == 404 {
+            info!("No telemetry data found for device");
             return Err("404".to_string());
         }
-        
-        // Check for other error status codes
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Statistics request failed");
+            return Err(format!("Statistics failed with status: {}", status_code));
+        }
+
+        response
+            .json::<MetricStats>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse metric statistics");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Fetches the anomalous readings of a device's metric over a time range.
+    ///
+    /// The device monitor anomaly endpoint scores each reading against the
+    /// readings before it with its default method (a rolling z-score) and
+    /// threshold.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to analyze
+    /// * `metric` - The metric to analyze (e.g. "temperature")
+    /// * `from` - Unix timestamp of the earliest record to analyze, if bounded
+    /// * `to` - Unix timestamp of the latest record to analyze, if bounded
+    ///
+    /// # Returns
+    /// * `Ok(AnomalyReport)` - The anomalous readings, oldest first
+    /// * `Err(String)` - Error message if the request fails, "404" if the
+    ///   device has no telemetry
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id, metric = %metric), level = Level::INFO)]
+    pub async fn get_anomalies(device_id: &str, metric: &str, from: Option<i64>, to: Option<i64>) -> Result<AnomalyReport, String> {
+        info!("Fetching anomalies for device");
+
+        let base_url = SettingsService::api_url();
+        let mut params = vec![("metric", metric.to_string())];
+        if let Some(from) = from {
+            params.push(("from", from.to_string()));
+        }
+        if let Some(to) = to {
+            params.push(("to", to.to_string()));
+        }
+        let url = format!("{}/iot/data/anomalies/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch anomalies");
+                format!("Request failed: {}", e)
+            })?;
+
         let status_code = response.status();
+        if status_code == 404 {
+            info!("No telemetry data found for device");
+            return Err("404".to_string());
+        }
         if status_code < 200 || status_code >= 300 {
-            info!(status = %status_code, "Config update failed");
-            return Err(format!("Update failed with status: {}", status_code));
+            info!(status = %status_code, "Anomaly request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<AnomalyReport>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse anomalies");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Exports a device's telemetry over a time range as CSV.
+    ///
+    /// The device monitor export endpoint writes one line per record, with
+    /// the columns `timestamp`, `time` and `device_id` followed by one
+    /// column per metric.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to export telemetry for
+    /// * `from` - Unix timestamp of the earliest record to export, if bounded
+    /// * `to` - Unix timestamp of the latest record to export, if bounded
+    /// * `metrics` - Metrics to export as columns; every metric reported in
+    ///   the range if empty
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The CSV file contents
+    /// * `Err(String)` - Error message if the request fails, "404" if the
+    ///   device has no telemetry
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn export_csv(device_id: &str, from: Option<i64>, to: Option<i64>, metrics: &[String]) -> Result<String, String> {
+        info!("Exporting telemetry data as CSV");
+
+        let base_url = SettingsService::api_url();
+        let mut params = Vec::new();
+        if let Some(from) = from {
+            params.push(("from", from.to_string()));
+        }
+        if let Some(to) = to {
+            params.push(("to", to.to_string()));
+        }
+        if !metrics.is_empty() {
+            params.push(("metrics", metrics.join(",")));
+        }
+        let url = format!("{}/iot/data/export/{}.csv", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to export telemetry data");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code == 404 {
+            info!("No telemetry data found for device");
+            return Err("404".to_string());
+        }
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Export request failed");
+            return Err(format!("Export failed with status: {}", status_code));
+        }
+
+        response
+            .text()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to read exported telemetry");
+                format!("Reading the export failed: {}", e)
+            })
+    }
+
+    /// Streams a device's new telemetry as it is stored.
+    ///
+    /// This method reads the device monitor stream endpoint, which sends
+    /// every record stored after the connection was opened as server-sent
+    /// events. The stream is read with `fetch` rather than `EventSource`,
+    /// which cannot send headers, so it carries the access token like any
+    /// other request. If the connection drops, it is opened again after
+    /// `STREAM_RETRY_MS`, with a renewed token if it expired.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to stream telemetry for
+    ///
+    /// # Returns
+    /// * `Ok((StreamConnection, stream))` - The connection, closed when
+    ///   dropped, and the records received on it
+    /// * `Err(String)` - Error message if the connection cannot be opened
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub fn stream_telemetry(device_id: &str) -> Result<(StreamConnection, impl Stream<Item = Telemetry> + use<>), String> {
+        info!("Opening telemetry stream for device");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/stream/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let controller = AbortController::new().map_err(|_| {
+            info!("Failed to create an abort controller");
+            "Stream failed: the request cannot be cancelled".to_string()
+        })?;
+        let signal = controller.signal();
+        let (sender, records) = futures::channel::mpsc::unbounded();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            // Reconnect until the connection is dropped or nobody reads the records
+            while !signal.aborted() && !sender.is_closed() {
+                if let Err(e) = Self::read_stream(&url, &signal, &sender).await {
+                    info!(error = %e, "Telemetry stream error");
+                }
+                if !signal.aborted() {
+                    Self::delay(STREAM_RETRY_MS).await;
+                }
+            }
+            info!("Closed telemetry stream");
+        });
+
+        Ok((StreamConnection(controller), records))
+    }
+
+    /// Reads one connection of a telemetry stream until it ends.
+    ///
+    /// # Parameters
+    /// * `url` - The stream endpoint URL
+    /// * `signal` - Cancels the request when the connection is dropped
+    /// * `sender` - Receives each record; events that cannot be parsed are
+    ///   skipped
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the server ended the stream
+    /// * `Err(String)` - Error message if the request or a read failed
+    async fn read_stream(url: &str, signal: &AbortSignal, sender: &UnboundedSender<Telemetry>) -> Result<(), String> {
+        let request = Request::get(url)
+            .header("Accept", "text/event-stream")
+            .abort_signal(Some(signal));
+        let response = AuthService::authorize(request)
+            .await
+            .send()
+            .await
+            .map_err(|e| format!("Stream failed: {}", e))?;
+        if !response.ok() {
+            return Err(format!("Stream failed with status: {}", response.status()));
+        }
+
+        let reader: ReadableStreamDefaultReader = response
+            .body()
+            .ok_or_else(|| "Stream failed: the response has no body".to_string())?
+            .get_reader()
+            .unchecked_into();
+        let mut parser = SseParser::default();
+        loop {
+            let chunk = JsFuture::from(reader.read())
+                .await
+                .map_err(|_| "Stream read failed".to_string())?;
+            let done = Reflect::get(&chunk, &"done".into()).ok().and_then(|done| done.as_bool()).unwrap_or(true);
+            if done {
+                return Ok(());
+            }
+            let bytes = Reflect::get(&chunk, &"value".into())
+                .map(|value| Uint8Array::new(&value).to_vec())
+                .map_err(|_| "Stream read failed".to_string())?;
+
+            // Each telemetry event's data is one record as JSON
+            for event in parser.push(&bytes) {
+                if event.event != TELEMETRY_EVENT {
+                    continue;
+                }
+                if let Ok(record) = serde_json::from_str::<Telemetry>(&event.data) {
+                    if sender.unbounded_send(record).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Waits before reconnecting a dropped stream.
+    ///
+    /// # Parameters
+    /// * `ms` - Time to wait in milliseconds
+    async fn delay(ms: i32) {
+        let timer = Promise::new(&mut |resolve, _| {
+            if let Some(window) = web_sys::window() {
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms);
+            }
+        });
+        let _ = JsFuture::from(timer).await;
+    }
+
+    /// Fetches the latest telemetry data for a specific device.
+    ///
+    /// This method retrieves all telemetry records for the device
+    /// and returns only the most recent one based on timestamp, from the
+    /// cached records if the API is unreachable.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch telemetry for
+    ///
+    /// # Returns
+    /// * `Ok(Fetched<Telemetry>)` - Most recent telemetry record if available,
+    ///   and when it was cached if it comes from the cache
+    /// * `Err(String)` - Error message if the request fails or no data found
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_latest_telemetry(device_id: &str) -> Result<Fetched<Telemetry>, String> {
+        info!("Fetching latest telemetry data for device");
+        
+        // Get all telemetry data for the device
+        let fetched = Self::get_telemetry_or_cached(device_id, None, None).await?;
+        let cached_at = fetched.cached_at;
+        
+        // Find the entry with the latest timestamp
+        fetched
+            .data
+            .into_iter()
+            .max_by_key(|t| t.timestamp)  // Sort by timestamp (descending)
+            .map(|latest| Fetched { data: latest, cached_at })
+            .ok_or_else(|| {
+                info!("No telemetry data found for device");
+                "No telemetry data found".to_string()
+            })
+    }
+
+    /// Fetches the configuration currently served to a specific device.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch the configuration of
+    ///
+    /// # Returns
+    /// * `Ok(Some(StoredConfig))` - The device's configuration, or the fleet
+    ///   defaults if it has none of its own
+    /// * `Ok(None)` - If neither a configuration nor defaults are stored
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_config(device_id: &str) -> Result<Option<StoredConfig>, String> {
+        info!("Fetching device configuration");
+
+        let base_url = SettingsService::dc_url();
+        let url = format!("{}/device-config/get/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch device config");
+                format!("Request failed: {}", e)
+            })?;
+
+        // A device without a configuration, and no defaults to fall back on
+        if response.status() == 404 {
+            info!("No configuration found for device");
+            return Ok(None);
+        }
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Config request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        // The API answers with a list holding the served configuration; its
+        // document is kept for the record inspector
+        let documents = response
+            .json::<Vec<serde_json::Value>>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse device config");
+                format!("JSON parse failed: {}", e)
+            })?;
+        documents.into_iter().next().map(Self::stored_config).transpose()
+    }
+
+    /// Fetches a device's configuration like `get_config`, caching it, or
+    /// returns the configuration cached last if the API is unreachable.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch the configuration of
+    ///
+    /// # Returns
+    /// * `Ok(Fetched<Option<StoredConfig>>)` - The configuration, if any, and
+    ///   when it was cached if it comes from the cache
+    /// * `Err(String)` - Error message if the request fails and nothing is cached
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_config_or_cached(device_id: &str) -> Result<Fetched<Option<StoredConfig>>, String> {
+        // The document is cached, so the record inspector works offline too
+        let key = CacheService::config_key(device_id);
+        match Self::get_config(device_id).await {
+            Ok(stored) => {
+                let document = stored.as_ref().and_then(|stored| stored.document.clone());
+                if let Err(e) = CacheService::store(&key, &document) {
+                    info!(error = %e, "Failed to cache device config");
+                }
+                Ok(Fetched::live(stored))
+            }
+            Err(e) if is_unreachable(&e) => match CacheService::load::<Option<serde_json::Value>>(&key) {
+                Ok(Some(cached)) => {
+                    info!(cached_at = cached.cached_at, "API unreachable, using cached device config");
+                    let cached_at = cached.cached_at;
+                    let stored = cached.data.map(Self::stored_config).transpose()?;
+                    Ok(Fetched { data: stored, cached_at: Some(cached_at) })
+                }
+                _ => Err(e),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reads a configuration document, keeping the document for the record inspector.
+    fn stored_config(document: serde_json::Value) -> Result<StoredConfig, String> {
+        let mut stored = serde_json::from_value::<StoredConfig>(document.clone()).map_err(|e| {
+            info!(error = %e, "Failed to parse device config");
+            format!("JSON parse failed: {}", e)
+        })?;
+        stored.document = Some(document);
+        Ok(stored)
+    }
+
+    /// Fetches the configuration a device reports having applied.
+    ///
+    /// Devices report it to the configuration service after applying a
+    /// configuration, so it can be compared with the configuration they are
+    /// served.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch the reported configuration of
+    ///
+    /// # Returns
+    /// * `Ok(Some(ReportedConfig))` - The configuration the device last reported
+    /// * `Ok(None)` - If the device hasn't reported a configuration
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_reported_config(device_id: &str) -> Result<Option<ReportedConfig>, String> {
+        info!("Fetching reported device configuration");
+
+        let base_url = SettingsService::dc_url();
+        let url = format!("{}/device-config/reported/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch reported config");
+                format!("Request failed: {}", e)
+            })?;
+
+        // A device that hasn't reported since it was provisioned
+        if response.status() == 404 {
+            info!("No reported configuration found for device");
+            return Ok(None);
+        }
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Reported config request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        // The document is kept for the record inspector
+        let document = response
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse reported config");
+                format!("JSON parse failed: {}", e)
+            })?;
+        let mut reported = serde_json::from_value::<ReportedConfig>(document.clone()).map_err(|e| {
+            info!(error = %e, "Failed to parse reported config");
+            format!("JSON parse failed: {}", e)
+        })?;
+        reported.document = Some(document);
+        Ok(Some(reported))
+    }
+
+    /// Updates the configuration for a specific device.
+    ///
+    /// This method sends a configuration update request to the device
+    /// configuration API to change device settings.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to update
+    /// * `config` - New configuration settings for the device
+    ///
+    /// # Returns
+    /// * `Ok(())` - If update was successful
+    /// * `Err(String)` - Error message if the update fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn update_device_config(device_id: &str, config: &DeviceConfig) -> Result<(), String> {
+        info!("Updating device configuration");
+        
+        // Get the base URL for the device configuration API
+        let base_url = SettingsService::dc_url();
+        let url = format!("{}/device-config/update", base_url);
+        info!(url = %url, "Making request to URL");
+        
+        // Create a POST request with the config as JSON body
+        let response = AuthService::authorize(Request::post(&url)).await
+            // Serialize the config to JSON
+            .json(config)
+            .map_err(|e| {
+                info!(error = %e, "Failed to serialize config");
+                format!("JSON serialize failed: {}", e)
+            })?
+            // Send the request
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to update device config");
+                format!("Request failed: {}", e)
+            })?;
+        
+        // Handle 404 (device not found) specially
+        if response.status() == 404 {
+            info!("Device not found for config update");
+            return Err("404".to_string());
+        }
+        
+        // The API explains which key or value it rejected
+        if response.status() == 400 {
+            let reason = response.text().await.unwrap_or_default();
+            info!(reason = %reason, "Config update rejected");
+            return Err(format!("Configuration rejected: {}", reason));
+        }
+
+        // Check for other error status codes
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Config update failed");
+            return Err(format!("Update failed with status: {}", status_code));
         }
         
         // Update was successful
         info!("Device configuration updated successfully");
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Fetches the stored versions of a device's configuration.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch the history of
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ConfigVersion>)` - The versions, newest first, each with the
+    ///   keys it changed; empty if the device has no configuration
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_config_history(device_id: &str) -> Result<Vec<ConfigVersion>, String> {
+        info!("Fetching device configuration history");
+
+        let base_url = SettingsService::dc_url();
+        let url = format!("{}/device-config/history/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch config history");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Config history request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        // Each version keeps its document for the record inspector
+        let documents = response
+            .json::<Vec<serde_json::Value>>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse config history");
+                format!("JSON parse failed: {}", e)
+            })?;
+        documents
+            .into_iter()
+            .map(|document| {
+                let mut version = serde_json::from_value::<ConfigVersion>(document.clone()).map_err(|e| {
+                    info!(error = %e, "Failed to parse config history");
+                    format!("JSON parse failed: {}", e)
+                })?;
+                version.document = Some(document);
+                Ok(version)
+            })
+            .collect()
+    }
+
+    /// Rolls a device back to an earlier version of its configuration.
+    ///
+    /// The API stores the version's values again as a new version, secret
+    /// values included, so they do not have to be re-entered.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to roll back
+    /// * `version_number` - Number of the version to roll back to
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the rollback was applied
+    /// * `Ok(false)` - If the rollback is held for approval
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id, version_number = version_number), level = Level::INFO)]
+    pub async fn rollback_config(device_id: &str, version_number: u64) -> Result<bool, String> {
+        info!("Rolling back device configuration");
+
+        let base_url = SettingsService::dc_url();
+        let url = format!("{}/device-config/history/{}/{}/rollback", base_url, device_id, version_number);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::post(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to roll back device config");
+                format!("Request failed: {}", e)
+            })?;
+
+        if response.status() == 404 {
+            info!("Config version not found for rollback");
+            return Err(format!("Version {} no longer exists", version_number));
+        }
+
+        // The stored values may no longer pass the current limits
+        if response.status() == 400 {
+            let reason = response.text().await.unwrap_or_default();
+            info!(reason = %reason, "Config rollback rejected");
+            return Err(format!("Rollback rejected: {}", reason));
+        }
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Config rollback failed");
+            return Err(format!("Rollback failed with status: {}", status_code));
+        }
+
+        info!("Device configuration rolled back");
+        Ok(status_code != 202)
+    }
+
+    /// Fetches the annotations on a device's telemetry timeline.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch annotations for
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Annotation>)` - The device's annotations, oldest first
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_annotations(device_id: &str) -> Result<Vec<Annotation>, String> {
+        info!("Fetching annotations for device");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/annotations/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch annotations");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Annotation request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<Vec<Annotation>>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse annotations");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Creates an annotation on a device's telemetry timeline.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to annotate
+    /// * `request` - Time range, text and author of the annotation
+    ///
+    /// # Returns
+    /// * `Ok(Annotation)` - The stored annotation
+    /// * `Err(String)` - Error message if the request fails or is rejected
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn create_annotation(device_id: &str, request: &AnnotationRequest) -> Result<Annotation, String> {
+        info!("Creating annotation");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/annotations/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::post(&url)).await
+            .json(request)
+            .map_err(|e| {
+                info!(error = %e, "Failed to serialize annotation");
+                format!("JSON serialize failed: {}", e)
+            })?
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to create annotation");
+                format!("Request failed: {}", e)
+            })?;
+
+        // The API rejects empty text, missing authors and inverted ranges
+        let status_code = response.status();
+        if status_code == 400 {
+            return Err("The annotation needs a note and an author".to_string());
+        }
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Annotation creation failed");
+            return Err(format!("Create failed with status: {}", status_code));
+        }
+
+        info!("Annotation created successfully");
+        response
+            .json::<Annotation>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse created annotation");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Fetches a device's alert rules.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch the rules of
+    ///
+    /// # Returns
+    /// * `Ok(Vec<AlertRule>)` - The rules, oldest first
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_alert_rules(device_id: &str) -> Result<Vec<AlertRule>, String> {
+        info!("Fetching alert rules for device");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/alerts/rules/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch alert rules");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Alert rule request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<Vec<AlertRule>>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse alert rules");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Creates an alert rule, or replaces the condition of an existing one.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device the rule watches
+    /// * `rule_id` - ID of the rule to replace, or `None` to create one
+    /// * `request` - The rule's condition
+    ///
+    /// # Returns
+    /// * `Ok(AlertRule)` - The stored rule
+    /// * `Err(String)` - Error message if the request fails or is rejected
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id, rule_id = ?rule_id), level = Level::INFO)]
+    pub async fn save_alert_rule(device_id: &str, rule_id: Option<&str>, request: &AlertRuleRequest) -> Result<AlertRule, String> {
+        info!("Saving alert rule");
+
+        let base_url = SettingsService::api_url();
+        let builder = match rule_id {
+            Some(rule_id) => Request::put(&format!("{}/iot/data/alerts/rules/{}/{}", base_url, device_id, rule_id)),
+            None => Request::post(&format!("{}/iot/data/alerts/rules/{}", base_url, device_id)),
+        };
+
+        let response = AuthService::authorize(builder)
+            .await
+            .json(request)
+            .map_err(|e| {
+                info!(error = %e, "Failed to serialize alert rule");
+                format!("JSON serialize failed: {}", e)
+            })?
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to save alert rule");
+                format!("Request failed: {}", e)
+            })?;
+
+        // The API rejects empty metrics and durations out of range
+        let status_code = response.status();
+        if status_code == 400 {
+            return Err("The rule needs a metric, a numeric threshold and a duration of at most a day".to_string());
+        }
+        if status_code == 404 {
+            return Err("The rule no longer exists".to_string());
+        }
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Alert rule save failed");
+            return Err(format!("Save failed with status: {}", status_code));
+        }
+
+        info!("Alert rule saved successfully");
+        response
+            .json::<AlertRule>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse saved alert rule");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Deletes an alert rule. Alerts it already raised are kept.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device the rule watches
+    /// * `rule_id` - ID of the rule to delete
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the rule was deleted or no longer existed
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id, rule_id = %rule_id), level = Level::INFO)]
+    pub async fn delete_alert_rule(device_id: &str, rule_id: &str) -> Result<(), String> {
+        info!("Deleting alert rule");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/alerts/rules/{}/{}", base_url, device_id, rule_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::delete(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to delete alert rule");
+                format!("Request failed: {}", e)
+            })?;
+
+        // A rule deleted elsewhere is as good as deleted
+        let status_code = response.status();
+        if status_code != 404 && (status_code < 200 || status_code >= 300) {
+            info!(status = %status_code, "Alert rule deletion failed");
+            return Err(format!("Delete failed with status: {}", status_code));
+        }
+
+        info!("Alert rule deleted successfully");
+        Ok(())
+    }
+
+    /// Fetches the alerts raised for a device.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device to fetch the alerts of
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Alert>)` - The alerts, newest first
+    /// * `Err(String)` - Error message if the request fails
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id), level = Level::INFO)]
+    pub async fn get_alerts(device_id: &str) -> Result<Vec<Alert>, String> {
+        info!("Fetching alerts for device");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/alerts/{}", base_url, device_id);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::get(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to fetch alerts");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Alert request failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        response
+            .json::<Vec<Alert>>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse alerts");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+
+    /// Acknowledges or resolves an alert.
+    ///
+    /// The API records the caller and the time; repeating an action keeps
+    /// the first record.
+    ///
+    /// # Parameters
+    /// * `device_id` - ID of the device the alert is about
+    /// * `alert_id` - ID of the alert to handle
+    /// * `action` - `"acknowledge"` or `"resolve"`
+    ///
+    /// # Returns
+    /// * `Ok(Alert)` - The updated alert
+    /// * `Err(String)` - Error message if the request fails or the alert's
+    ///   status does not allow the action
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track API calls
+    #[instrument(skip_all, fields(device_id = %device_id, alert_id = %alert_id, action = %action), level = Level::INFO)]
+    pub async fn handle_alert(device_id: &str, alert_id: &str, action: &str) -> Result<Alert, String> {
+        info!("Handling alert");
+
+        let base_url = SettingsService::api_url();
+        let url = format!("{}/iot/data/alerts/{}/{}/{}", base_url, device_id, alert_id, action);
+        info!(url = %url, "Making request to URL");
+
+        let response = AuthService::authorize(Request::post(&url)).await
+            .send()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to handle alert");
+                format!("Request failed: {}", e)
+            })?;
+
+        let status_code = response.status();
+        if status_code == 404 {
+            return Err("The alert no longer exists".to_string());
+        }
+        if status_code == 409 {
+            return Err("The alert is already resolved".to_string());
+        }
+        if status_code < 200 || status_code >= 300 {
+            info!(status = %status_code, "Alert handling failed");
+            return Err(format!("Request failed with status: {}", status_code));
+        }
+
+        info!("Alert handled successfully");
+        response
+            .json::<Alert>()
+            .await
+            .map_err(|e| {
+                info!(error = %e, "Failed to parse handled alert");
+                format!("JSON parse failed: {}", e)
+            })
+    }
+}
@@ -0,0 +1,56 @@
+/// # Download Service
+///
+/// This module saves files generated in the browser, such as telemetry
+/// exports, to the user's downloads. The contents are wrapped in a blob
+/// and saved through a temporary link, which lets the application choose
+/// the file name even when the data comes from another origin.
+
+use tracing::{info, instrument, Level};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+/// Service for saving generated files.
+pub struct DownloadService;
+
+impl DownloadService {
+    /// Saves text as a file in the user's downloads.
+    ///
+    /// # Parameters
+    /// * `filename` - Name suggested for the saved file
+    /// * `mime_type` - Media type of the contents, e.g. "text/csv"
+    /// * `contents` - The file contents
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the download was started
+    /// * `Err(String)` - Error message if the browser could not create the file
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track downloads
+    #[instrument(skip(contents), fields(bytes = contents.len()), level = Level::INFO)]
+    pub fn save_text(filename: &str, mime_type: &str, contents: &str) -> Result<(), String> {
+        let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+        let options = BlobPropertyBag::new();
+        options.set_type(mime_type);
+        let blob = Blob::new_with_str_sequence_and_options(&parts, &options)
+            .map_err(|_| "Failed to create file".to_string())?;
+        let url = Url::create_object_url_with_blob(&blob)
+            .map_err(|_| "Failed to create file URL".to_string())?;
+
+        // Clicking a link with a download attribute saves its target
+        let link = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| "No document available".to_string())?
+            .create_element("a")
+            .map_err(|_| "Failed to create download link".to_string())?
+            .dyn_into::<HtmlAnchorElement>()
+            .map_err(|_| "Failed to create download link".to_string())?;
+        link.set_href(&url);
+        link.set_download(filename);
+        link.click();
+
+        // The browser has started the download, so the blob can be released
+        let _ = Url::revoke_object_url(&url);
+        info!("Download started");
+        Ok(())
+    }
+}
@@ -1 +1,5 @@
-pub mod device_service;
\ No newline at end of file
+pub mod device_service;
+pub mod preferences_service;
+pub mod download_service;
+pub mod auth_service;
+pub mod settings_service;pub mod cache_service;
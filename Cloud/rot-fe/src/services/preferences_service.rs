@@ -0,0 +1,367 @@
+/// # Preferences Service
+///
+/// This module persists user preferences: the saved chart presets, the
+/// colour theme, the recently viewed devices, the chart thresholds, the
+/// API URLs set on the settings page, the dashboard widgets, the units
+/// telemetry is shown in and the chart renderer.
+/// There is no server-side preferences API yet, so preferences are kept in
+/// the browser's local storage and survive page reloads on the same browser.
+
+use crate::domain::dashboard::Widget;
+use crate::domain::plot::ChartRenderer;
+use crate::domain::preset::ChartPreset;
+use crate::domain::settings::ApiSettingsOverride;
+use crate::domain::theme::Theme;
+use crate::domain::threshold::Threshold;
+use crate::domain::units::UnitPreferences;
+use tracing::{info, instrument, Level};
+use web_sys::Storage;
+
+/// Service for loading and saving user preferences.
+pub struct PreferencesService;
+
+impl PreferencesService {
+    /// Local storage key holding the saved chart presets as JSON.
+    const PRESETS_KEY: &'static str = "rot.chart_presets";
+
+    /// Local storage key holding the chosen colour theme.
+    const THEME_KEY: &'static str = "rot.theme";
+
+    /// Local storage key holding the recently viewed device IDs as JSON.
+    const RECENT_DEVICES_KEY: &'static str = "rot.recent_devices";
+
+    /// Local storage key holding the user's chart thresholds as JSON.
+    const THRESHOLDS_KEY: &'static str = "rot.thresholds";
+
+    /// Local storage key holding the API URLs set on the settings page as JSON.
+    const API_SETTINGS_KEY: &'static str = "rot.api_settings";
+
+    /// Local storage key holding the dashboard widgets as JSON.
+    const DASHBOARD_KEY: &'static str = "rot.dashboard";
+
+    /// Local storage key holding the units telemetry is shown in as JSON.
+    const UNITS_KEY: &'static str = "rot.units";
+
+    /// Local storage key holding the chosen chart renderer.
+    const CHART_RENDERER_KEY: &'static str = "rot.chart_renderer";
+
+    /// Returns the browser's local storage, if available.
+    fn storage() -> Result<Storage, String> {
+        web_sys::window()
+            .ok_or_else(|| "No window available".to_string())?
+            .local_storage()
+            .map_err(|_| "Local storage is not accessible".to_string())?
+            .ok_or_else(|| "Local storage is not available".to_string())
+    }
+
+    /// Loads the saved chart presets.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ChartPreset>)` - Saved presets, empty if none were saved
+    /// * `Err(String)` - Error message if storage is unavailable or corrupt
+    #[instrument(level = Level::INFO)]
+    pub fn load_presets() -> Result<Vec<ChartPreset>, String> {
+        let stored = Self::storage()?
+            .get_item(Self::PRESETS_KEY)
+            .map_err(|_| "Failed to read saved presets".to_string())?;
+
+        match stored {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                info!(error = %e, "Failed to parse saved presets");
+                format!("JSON parse failed: {}", e)
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Replaces the saved chart presets.
+    ///
+    /// # Parameters
+    /// * `presets` - The full list of presets to persist
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the presets were saved
+    /// * `Err(String)` - Error message if storage is unavailable or full
+    #[instrument(skip_all, fields(count = presets.len()), level = Level::INFO)]
+    pub fn save_presets(presets: &[ChartPreset]) -> Result<(), String> {
+        let json = serde_json::to_string(presets)
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+
+        Self::storage()?
+            .set_item(Self::PRESETS_KEY, &json)
+            .map_err(|_| "Failed to save presets".to_string())?;
+
+        info!("Chart presets saved");
+        Ok(())
+    }
+
+    /// Loads the chosen colour theme.
+    ///
+    /// # Returns
+    /// * `Ok(Some(Theme))` - The theme chosen with the navbar toggle
+    /// * `Ok(None)` - If no theme was chosen yet, or the stored one is unknown
+    /// * `Err(String)` - Error message if storage is unavailable
+    #[instrument(level = Level::INFO)]
+    pub fn load_theme() -> Result<Option<Theme>, String> {
+        let stored = Self::storage()?
+            .get_item(Self::THEME_KEY)
+            .map_err(|_| "Failed to read the theme".to_string())?;
+
+        Ok(stored.as_deref().and_then(Theme::from_key))
+    }
+
+    /// Saves the chosen colour theme.
+    ///
+    /// # Parameters
+    /// * `theme` - The theme to persist
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the theme was saved
+    /// * `Err(String)` - Error message if storage is unavailable
+    #[instrument(level = Level::INFO)]
+    pub fn save_theme(theme: Theme) -> Result<(), String> {
+        Self::storage()?
+            .set_item(Self::THEME_KEY, theme.key())
+            .map_err(|_| "Failed to save the theme".to_string())?;
+
+        info!(theme = theme.key(), "Theme saved");
+        Ok(())
+    }
+
+    /// Loads the recently viewed device IDs.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The device IDs, most recent first, empty if none were viewed
+    /// * `Err(String)` - Error message if storage is unavailable or corrupt
+    #[instrument(level = Level::INFO)]
+    pub fn load_recent_devices() -> Result<Vec<String>, String> {
+        let stored = Self::storage()?
+            .get_item(Self::RECENT_DEVICES_KEY)
+            .map_err(|_| "Failed to read recent devices".to_string())?;
+
+        match stored {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                info!(error = %e, "Failed to parse recent devices");
+                format!("JSON parse failed: {}", e)
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Replaces the recently viewed device IDs.
+    ///
+    /// # Parameters
+    /// * `device_ids` - The device IDs, most recent first
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the device IDs were saved
+    /// * `Err(String)` - Error message if storage is unavailable or full
+    #[instrument(skip_all, fields(count = device_ids.len()), level = Level::INFO)]
+    pub fn save_recent_devices(device_ids: &[String]) -> Result<(), String> {
+        let json = serde_json::to_string(device_ids)
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+
+        Self::storage()?
+            .set_item(Self::RECENT_DEVICES_KEY, &json)
+            .map_err(|_| "Failed to save recent devices".to_string())?;
+
+        Ok(())
+    }
+
+    /// Loads the thresholds the user set on the charts.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Threshold>)` - The thresholds, empty if none were set
+    /// * `Err(String)` - Error message if storage is unavailable or corrupt
+    #[instrument(level = Level::INFO)]
+    pub fn load_thresholds() -> Result<Vec<Threshold>, String> {
+        let stored = Self::storage()?
+            .get_item(Self::THRESHOLDS_KEY)
+            .map_err(|_| "Failed to read thresholds".to_string())?;
+
+        match stored {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                info!(error = %e, "Failed to parse thresholds");
+                format!("JSON parse failed: {}", e)
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Replaces the thresholds the user set on the charts.
+    ///
+    /// # Parameters
+    /// * `thresholds` - The full list of thresholds to persist
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the thresholds were saved
+    /// * `Err(String)` - Error message if storage is unavailable or full
+    #[instrument(skip_all, fields(count = thresholds.len()), level = Level::INFO)]
+    pub fn save_thresholds(thresholds: &[Threshold]) -> Result<(), String> {
+        let json = serde_json::to_string(thresholds)
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+
+        Self::storage()?
+            .set_item(Self::THRESHOLDS_KEY, &json)
+            .map_err(|_| "Failed to save thresholds".to_string())?;
+
+        info!("Thresholds saved");
+        Ok(())
+    }
+
+    /// Loads the API URLs the user set on the settings page.
+    ///
+    /// # Returns
+    /// * `Ok(ApiSettingsOverride)` - Saved URLs, none set if none were saved
+    /// * `Err(String)` - Error message if storage is unavailable or corrupt
+    #[instrument(level = Level::INFO)]
+    pub fn load_api_settings() -> Result<ApiSettingsOverride, String> {
+        let stored = Self::storage()?
+            .get_item(Self::API_SETTINGS_KEY)
+            .map_err(|_| "Failed to read API settings".to_string())?;
+
+        match stored {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                info!(error = %e, "Failed to parse API settings");
+                format!("JSON parse failed: {}", e)
+            }),
+            None => Ok(ApiSettingsOverride::default()),
+        }
+    }
+
+    /// Replaces the API URLs the user set on the settings page.
+    ///
+    /// # Parameters
+    /// * `settings` - The URLs to persist, `None` for the deployment's
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the URLs were saved
+    /// * `Err(String)` - Error message if storage is unavailable or full
+    #[instrument(skip_all, level = Level::INFO)]
+    pub fn save_api_settings(settings: &ApiSettingsOverride) -> Result<(), String> {
+        let json = serde_json::to_string(settings)
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+
+        Self::storage()?
+            .set_item(Self::API_SETTINGS_KEY, &json)
+            .map_err(|_| "Failed to save API settings".to_string())?;
+
+        info!("API settings saved");
+        Ok(())
+    }
+
+    /// Loads the widgets of the user's dashboard.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Widget>)` - Saved widgets in display order, empty if none were saved
+    /// * `Err(String)` - Error message if storage is unavailable or corrupt
+    #[instrument(level = Level::INFO)]
+    pub fn load_dashboard() -> Result<Vec<Widget>, String> {
+        let stored = Self::storage()?
+            .get_item(Self::DASHBOARD_KEY)
+            .map_err(|_| "Failed to read the dashboard".to_string())?;
+
+        match stored {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                info!(error = %e, "Failed to parse the dashboard");
+                format!("JSON parse failed: {}", e)
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Replaces the widgets of the user's dashboard.
+    ///
+    /// # Parameters
+    /// * `widgets` - The full list of widgets to persist, in display order
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the widgets were saved
+    /// * `Err(String)` - Error message if storage is unavailable or full
+    #[instrument(skip_all, fields(count = widgets.len()), level = Level::INFO)]
+    pub fn save_dashboard(widgets: &[Widget]) -> Result<(), String> {
+        let json = serde_json::to_string(widgets)
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+
+        Self::storage()?
+            .set_item(Self::DASHBOARD_KEY, &json)
+            .map_err(|_| "Failed to save the dashboard".to_string())?;
+
+        info!("Dashboard saved");
+        Ok(())
+    }
+
+    /// Loads the units telemetry is shown in.
+    ///
+    /// # Returns
+    /// * `Ok(UnitPreferences)` - The saved units, the reported units if none were saved
+    /// * `Err(String)` - Error message if storage is unavailable or corrupt
+    #[instrument(level = Level::INFO)]
+    pub fn load_units() -> Result<UnitPreferences, String> {
+        let stored = Self::storage()?
+            .get_item(Self::UNITS_KEY)
+            .map_err(|_| "Failed to read the units".to_string())?;
+
+        match stored {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                info!(error = %e, "Failed to parse the units");
+                format!("JSON parse failed: {}", e)
+            }),
+            None => Ok(UnitPreferences::default()),
+        }
+    }
+
+    /// Saves the units telemetry is shown in.
+    ///
+    /// # Parameters
+    /// * `units` - The units to persist
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the units were saved
+    /// * `Err(String)` - Error message if storage is unavailable or full
+    #[instrument(skip_all, fields(temperature = units.temperature.key(), scales = units.scales.len()), level = Level::INFO)]
+    pub fn save_units(units: &UnitPreferences) -> Result<(), String> {
+        let json = serde_json::to_string(units)
+            .map_err(|e| format!("JSON serialize failed: {}", e))?;
+
+        Self::storage()?
+            .set_item(Self::UNITS_KEY, &json)
+            .map_err(|_| "Failed to save the units".to_string())?;
+
+        info!("Units saved");
+        Ok(())
+    }
+
+    /// Loads the chosen chart renderer.
+    ///
+    /// # Returns
+    /// * `Ok(ChartRenderer)` - The renderer chosen on the settings page, the
+    ///   default if none was chosen yet or the stored one is unknown
+    /// * `Err(String)` - Error message if storage is unavailable
+    #[instrument(level = Level::INFO)]
+    pub fn load_chart_renderer() -> Result<ChartRenderer, String> {
+        let stored = Self::storage()?
+            .get_item(Self::CHART_RENDERER_KEY)
+            .map_err(|_| "Failed to read the chart renderer".to_string())?;
+
+        Ok(stored.as_deref().and_then(ChartRenderer::from_key).unwrap_or_default())
+    }
+
+    /// Saves the chosen chart renderer.
+    ///
+    /// # Parameters
+    /// * `renderer` - The renderer to persist
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the renderer was saved
+    /// * `Err(String)` - Error message if storage is unavailable
+    #[instrument(level = Level::INFO)]
+    pub fn save_chart_renderer(renderer: ChartRenderer) -> Result<(), String> {
+        Self::storage()?
+            .set_item(Self::CHART_RENDERER_KEY, renderer.key())
+            .map_err(|_| "Failed to save the chart renderer".to_string())?;
+
+        info!(renderer = renderer.key(), "Chart renderer saved");
+        Ok(())
+    }
+}
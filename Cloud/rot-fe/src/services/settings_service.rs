@@ -0,0 +1,195 @@
+/// # Settings Service
+///
+/// This module resolves the base URLs of the backend APIs and the Azure AD
+/// sign-in settings when the application starts, so one build works against
+/// dev, staging and prod. The settings the application was built with are
+/// overridden by `config.json`, then by `window.ROT_CONFIG`; the URLs are
+/// further overridden by the user's overrides from the settings page, which
+/// are kept in local storage.
+///
+/// `init` must complete before the first request; services read the
+/// resolved URLs with `api_url` and `dc_url`, and the sign-in settings with
+/// `auth`.
+
+use crate::domain::settings::{ApiSettings, ApiSettingsOverride, AuthSettings, DeploymentSettings};
+use crate::services::preferences_service::PreferencesService;
+use gloo_net::http::Request;
+use std::cell::RefCell;
+use tracing::{info, instrument, Level};
+use wasm_bindgen::JsValue;
+
+thread_local! {
+    /// URLs set by the deployment, and the user's overrides
+    static SETTINGS: RefCell<(ApiSettings, ApiSettingsOverride)> = RefCell::new((
+        SettingsService::built_in(),
+        ApiSettingsOverride::default(),
+    ));
+
+    /// Sign-in settings set by the deployment
+    static AUTH: RefCell<AuthSettings> = RefCell::new(SettingsService::built_in_auth());
+}
+
+/// Service for resolving and overriding the backend API URLs.
+pub struct SettingsService;
+
+impl SettingsService {
+    /// Path of the deployment's configuration file, next to `index.html`.
+    const CONFIG_PATH: &'static str = "/config.json";
+
+    /// Name of the window global a page script can set the URLs in.
+    const WINDOW_GLOBAL: &'static str = "ROT_CONFIG";
+
+    /// URLs the application was built with, used unless the deployment sets others.
+    fn built_in() -> ApiSettings {
+        ApiSettings {
+            api_url: env!("ROT_API_URL").to_string(),
+            dc_url: env!("ROT_DC_URL").to_string(),
+        }
+        .apply(&ApiSettingsOverride::default())
+    }
+
+    /// Sign-in settings the application was built with, used unless the deployment sets others.
+    fn built_in_auth() -> AuthSettings {
+        AuthSettings {
+            client_id: env!("ROT_AUTH_CLIENT_ID").to_string(),
+            tenant_id: env!("ROT_AUTH_TENANT_ID").to_string(),
+            scope: env!("ROT_AUTH_SCOPE").to_string(),
+        }
+    }
+
+    /// Resolves the URLs and sign-in settings from every source.
+    ///
+    /// A missing or invalid `config.json` or window global is skipped, so
+    /// the application still starts with the settings it was built with.
+    ///
+    /// # Instrumentation
+    /// This method is instrumented with tracing to track the resolution
+    #[instrument(level = Level::INFO)]
+    pub async fn init() {
+        let mut deployed = Self::built_in();
+        let mut auth = Self::built_in_auth();
+
+        match Self::fetch_config_file().await {
+            Ok(Some(file)) => {
+                deployed = deployed.apply(&file.api);
+                auth = auth.apply(&file.auth);
+            }
+            Ok(None) => info!("No config.json deployed"),
+            Err(e) => info!(error = %e, "Ignoring config.json"),
+        }
+        match Self::read_window_global() {
+            Ok(Some(global)) => {
+                deployed = deployed.apply(&global.api);
+                auth = auth.apply(&global.auth);
+            }
+            Ok(None) => {}
+            Err(e) => info!(error = %e, "Ignoring window.ROT_CONFIG"),
+        }
+        let overrides = PreferencesService::load_api_settings().unwrap_or_else(|e| {
+            info!(error = %e, "Ignoring saved API settings");
+            ApiSettingsOverride::default()
+        });
+
+        info!(api_url = %deployed.api_url, dc_url = %deployed.dc_url, "Resolved deployed API URLs");
+        info!(sign_in = auth.is_enabled(), tenant_id = %auth.tenant_id, "Resolved sign-in settings");
+        SETTINGS.with(|settings| *settings.borrow_mut() = (deployed, overrides));
+        AUTH.with(|settings| *settings.borrow_mut() = auth);
+    }
+
+    /// Azure AD sign-in settings set by the deployment.
+    pub fn auth() -> AuthSettings {
+        AUTH.with(|settings| settings.borrow().clone())
+    }
+
+    /// Base URL of the device monitor API, without a trailing slash.
+    pub fn api_url() -> String {
+        Self::effective().api_url
+    }
+
+    /// Base URL of the device config API, without a trailing slash.
+    pub fn dc_url() -> String {
+        Self::effective().dc_url
+    }
+
+    /// URLs in use, with the user's overrides applied.
+    pub fn effective() -> ApiSettings {
+        SETTINGS.with(|settings| {
+            let (deployed, overrides) = &*settings.borrow();
+            deployed.apply(overrides)
+        })
+    }
+
+    /// URLs set by the deployment, used where the user set none.
+    pub fn deployed() -> ApiSettings {
+        SETTINGS.with(|settings| settings.borrow().0.clone())
+    }
+
+    /// URLs the user set on the settings page.
+    pub fn overrides() -> ApiSettingsOverride {
+        SETTINGS.with(|settings| settings.borrow().1.clone())
+    }
+
+    /// Saves and applies the user's URLs; requests sent afterwards use them.
+    ///
+    /// # Parameters
+    /// * `overrides` - The URLs to use, `None` for the deployment's
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the URLs were saved
+    /// * `Err(String)` - Error message if storage is unavailable
+    #[instrument(skip_all, level = Level::INFO)]
+    pub fn set_overrides(overrides: ApiSettingsOverride) -> Result<(), String> {
+        PreferencesService::save_api_settings(&overrides)?;
+        SETTINGS.with(|settings| settings.borrow_mut().1 = overrides);
+        info!("API settings overridden");
+        Ok(())
+    }
+
+    /// Fetches the deployment's `config.json`.
+    ///
+    /// # Returns
+    /// * `Ok(Some(DeploymentSettings))` - The settings the file sets
+    /// * `Ok(None)` - If no file is deployed
+    /// * `Err(String)` - Error message if the request fails or the file is invalid
+    async fn fetch_config_file() -> Result<Option<DeploymentSettings>, String> {
+        let response = Request::get(Self::CONFIG_PATH)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        // Development servers answer unknown paths with index.html
+        let is_json = response
+            .headers()
+            .get("content-type")
+            .is_some_and(|content_type| content_type.contains("json"));
+        if response.status() == 404 || !is_json {
+            return Ok(None);
+        }
+
+        response
+            .json::<DeploymentSettings>()
+            .await
+            .map(Some)
+            .map_err(|e| format!("JSON parse failed: {}", e))
+    }
+
+    /// Reads `window.ROT_CONFIG`.
+    ///
+    /// # Returns
+    /// * `Ok(Some(DeploymentSettings))` - The settings the global sets
+    /// * `Ok(None)` - If the global is not set
+    /// * `Err(String)` - Error message if the global is not a valid object
+    fn read_window_global() -> Result<Option<DeploymentSettings>, String> {
+        let Some(window) = web_sys::window() else {
+            return Ok(None);
+        };
+        let global = js_sys::Reflect::get(&window, &JsValue::from_str(Self::WINDOW_GLOBAL))
+            .map_err(|_| "Failed to read the window global".to_string())?;
+        if global.is_undefined() || global.is_null() {
+            return Ok(None);
+        }
+        serde_wasm_bindgen::from_value(global)
+            .map(Some)
+            .map_err(|e| format!("Invalid window global: {}", e))
+    }
+}
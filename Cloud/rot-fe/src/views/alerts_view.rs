@@ -0,0 +1,632 @@
+/// # Alerts View
+///
+/// This component manages a device's threshold alerts. It allows users to:
+/// - Select a device by ID
+/// - Create alert rules, such as "temperature above 30 for 5 minutes", and
+///   edit or delete them
+/// - Browse the alerts the rules raised, newest first, filtered by status
+/// - Acknowledge an alert when taking it on and resolve it once dealt with
+///
+/// Rules and alerts come from the device monitor alerts API; the device
+/// shown comes from the route (`/alerts/:device_id`), so it can be bookmarked.
+
+use crate::components::{use_recent_devices, RecentDevices};
+use crate::domain::alert::{Alert, AlertRule, AlertRuleRequest, AlertStatus, Operator, MAX_ALERT_DURATION_SECS};
+use crate::route::Route;
+use crate::services::device_service::DeviceService;
+use chrono::DateTime;
+use futures::future;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct AlertsViewProps {
+    /// ID of the device whose alerts are shown, from the route (`/alerts/:device_id`)
+    #[prop_or_default]
+    pub device_id: Option<String>,
+}
+
+/// Fields of the rule form, kept as typed so they can be checked on save.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RuleForm {
+    /// ID of the rule being edited, or `None` when creating one
+    rule_id: Option<String>,
+    metric: String,
+    operator: Operator,
+    value: String,
+    duration_secs: String,
+}
+
+impl RuleForm {
+    /// Fills the form with an existing rule, to edit it.
+    fn edit(rule: &AlertRule) -> Self {
+        RuleForm {
+            rule_id: Some(rule.id.clone()),
+            metric: rule.metric.clone(),
+            operator: rule.operator,
+            value: rule.value.to_string(),
+            duration_secs: rule.duration_secs.to_string(),
+        }
+    }
+}
+
+/// Component listing a device's alert rules and the alerts they raised.
+#[function_component(AlertsView)]
+pub fn alerts_view(props: &AlertsViewProps) -> Html {
+    let device_id = props.device_id.clone().unwrap_or_default();
+    let navigator = use_navigator();
+
+    // State for the device ID input field
+    let input_value = use_state(|| device_id.clone());
+
+    // Recently viewed devices, offered below the device ID input
+    let recent_devices = use_recent_devices(&device_id);
+
+    // State for the device's rules and alerts
+    let rules = use_state(Vec::<AlertRule>::new);
+    let alerts = use_state(Vec::<Alert>::new);
+
+    // State for the rule being created or edited
+    let form = use_state(RuleForm::default);
+
+    // State for the status the alert list is filtered by, `None` for all
+    let status_filter = use_state(|| None::<AlertStatus>);
+
+    // State for tracking loading status
+    let loading = use_state(|| false);
+
+    // State for error and success messages
+    let error = use_state(|| None::<String>);
+    let success_message = use_state(|| None::<String>);
+
+    // Bumped after a rule is saved or deleted to reload the rules
+    let revision = use_state(|| 0);
+
+    // Keep the input field in sync when the route changes, e.g. on back navigation
+    {
+        let input_value = input_value.clone();
+        use_effect_with(device_id.clone(), move |device_id| {
+            input_value.set(device_id.clone());
+            || ()
+        });
+    }
+
+    // Clear messages and the rule form when the routed device changes;
+    // reloads after saving a rule keep the messages
+    {
+        let error = error.clone();
+        let success_message = success_message.clone();
+        let form = form.clone();
+        use_effect_with(device_id.clone(), move |_| {
+            error.set(None);
+            success_message.set(None);
+            form.set(RuleForm::default());
+            || ()
+        });
+    }
+
+    // Effect hook for fetching the rules and alerts whenever the routed
+    // device changes or a rule is saved or deleted
+    {
+        let rules = rules.clone();
+        let alerts = alerts.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        use_effect_with((device_id.clone(), *revision), move |(device_id, _)| {
+            let device_id = device_id.clone();
+            rules.set(Vec::new());
+            alerts.set(Vec::new());
+
+            if !device_id.trim().is_empty() {
+                loading.set(true);
+                wasm_bindgen_futures::spawn_local(async move {
+                    let (fetched_rules, fetched_alerts) =
+                        future::join(DeviceService::get_alert_rules(&device_id), DeviceService::get_alerts(&device_id)).await;
+
+                    match fetched_rules {
+                        Ok(data) => rules.set(data),
+                        Err(e) => error.set(Some(format!("Failed to load alert rules: {}", e))),
+                    }
+                    match fetched_alerts {
+                        Ok(data) => alerts.set(data),
+                        Err(e) => error.set(Some(format!("Failed to load alerts: {}", e))),
+                    }
+                    loading.set(false);
+                });
+            }
+
+            // Cleanup function (no-op in this case)
+            || ()
+        });
+    }
+
+    // Callback for handling input changes in the device ID field
+    let on_input_change = {
+        let input_value = input_value.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            input_value.set(input.value());
+        })
+    };
+
+    // Loading a device goes through its route, so the view can be bookmarked
+    let on_load = {
+        let input_value = input_value.clone();
+        let navigator = navigator.clone();
+        let error = error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let device_id = input_value.trim().to_string();
+            if device_id.is_empty() {
+                error.set(Some("Please enter a device ID.".to_string()));
+            } else if let Some(navigator) = &navigator {
+                navigator.push(&Route::AlertsDevice { device_id });
+            }
+        })
+    };
+
+    // Callback for picking a recently viewed device
+    let on_recent_select = {
+        let navigator = navigator.clone();
+        Callback::from(move |device_id: String| {
+            if let Some(navigator) = &navigator {
+                navigator.push(&Route::AlertsDevice { device_id });
+            }
+        })
+    };
+
+    // Callbacks for editing the text fields of the rule form
+    let on_metric_input = on_form_input(&form, |form, text| form.metric = text);
+    let on_value_input = on_form_input(&form, |form, text| form.value = text);
+    let on_duration_input = on_form_input(&form, |form, text| form.duration_secs = text);
+
+    // Callback for choosing the rule's operator
+    let on_operator_change = {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Some(operator) = Operator::from_key(&select.value()) {
+                let mut updated = (*form).clone();
+                updated.operator = operator;
+                form.set(updated);
+            }
+        })
+    };
+
+    // Callback for saving the rule form, creating a rule or replacing the edited one
+    let on_rule_submit = {
+        let device_id = device_id.clone();
+        let form = form.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        let success_message = success_message.clone();
+        let revision = revision.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let request = match AlertRuleRequest::parse(&form.metric, form.operator, &form.value, &form.duration_secs) {
+                Ok(request) => request,
+                Err(e) => {
+                    error.set(Some(e));
+                    return;
+                }
+            };
+            let device_id = device_id.clone();
+            let rule_id = form.rule_id.clone();
+            let form = form.clone();
+            let loading = loading.clone();
+            let error = error.clone();
+            let success_message = success_message.clone();
+            let revision = revision.clone();
+
+            loading.set(true);
+            error.set(None);
+            success_message.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::save_alert_rule(&device_id, rule_id.as_deref(), &request).await {
+                    Ok(rule) => {
+                        let verb = if rule_id.is_some() { "updated" } else { "created" };
+                        success_message.set(Some(format!("Rule {}: {}", verb, rule.describe())));
+                        form.set(RuleForm::default());
+                        revision.set(*revision + 1);
+                    }
+                    Err(e) => error.set(Some(format!("Failed to save rule: {}", e))),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    // Callback for editing an existing rule in the form
+    let on_rule_edit = {
+        let form = form.clone();
+        Callback::from(move |rule: AlertRule| form.set(RuleForm::edit(&rule)))
+    };
+
+    // Callback for abandoning an edit
+    let on_edit_cancel = {
+        let form = form.clone();
+        Callback::from(move |_| form.set(RuleForm::default()))
+    };
+
+    // Callback for deleting a rule; the alerts it raised are kept
+    let on_rule_delete = {
+        let device_id = device_id.clone();
+        let form = form.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        let success_message = success_message.clone();
+        let revision = revision.clone();
+        Callback::from(move |rule: AlertRule| {
+            let device_id = device_id.clone();
+            let form = form.clone();
+            let loading = loading.clone();
+            let error = error.clone();
+            let success_message = success_message.clone();
+            let revision = revision.clone();
+
+            loading.set(true);
+            error.set(None);
+            success_message.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::delete_alert_rule(&device_id, &rule.id).await {
+                    Ok(()) => {
+                        success_message.set(Some(format!("Rule deleted: {}", rule.describe())));
+                        // The edited rule no longer exists
+                        if form.rule_id.as_deref() == Some(rule.id.as_str()) {
+                            form.set(RuleForm::default());
+                        }
+                        revision.set(*revision + 1);
+                    }
+                    Err(e) => error.set(Some(format!("Failed to delete rule: {}", e))),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    // Callback for changing the status the alerts are filtered by
+    let on_filter_change = {
+        let status_filter = status_filter.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            status_filter.set(AlertStatus::from_key(&select.value()));
+        })
+    };
+
+    // Callback for acknowledging or resolving an alert
+    // The updated alert replaces the listed one, so the list is not reloaded
+    let on_alert_action = {
+        let alerts = alerts.clone();
+        let error = error.clone();
+        Callback::from(move |(alert, action): (Alert, &'static str)| {
+            let alerts = alerts.clone();
+            let error = error.clone();
+            error.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::handle_alert(&alert.device_id, &alert.id, action).await {
+                    Ok(handled) => {
+                        let updated = alerts
+                            .iter()
+                            .map(|listed| if listed.id == handled.id { handled.clone() } else { listed.clone() })
+                            .collect();
+                        alerts.set(updated);
+                    }
+                    Err(e) => error.set(Some(format!("Failed to {} alert: {}", action, e))),
+                }
+            });
+        })
+    };
+
+    let shown_alerts: Vec<&Alert> = alerts
+        .iter()
+        .filter(|alert| status_filter.is_none_or(|status| alert.status() == status))
+        .collect();
+    let open_alerts = alerts.iter().filter(|alert| alert.status() == AlertStatus::Open).count();
+
+    html! {
+        <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8">
+            <div class="mb-6">
+                <h2 class="text-3xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Alerts"}</h2>
+                <p class="text-gray-600 dark:text-gray-300 mb-4">{"Threshold rules watching a device's telemetry, and the alerts they raised"}</p>
+                <form onsubmit={on_load}>
+                    <label for="device-id" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Device ID"}</label>
+                    <div class="flex gap-2">
+                        <input
+                            type="text"
+                            id="device-id"
+                            list="recent-device-ids"
+                            value={(*input_value).clone()}
+                            oninput={on_input_change}
+                            class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
+                            placeholder="Enter device ID (e.g., 4321)"
+                            autofocus=true
+                        />
+                        <button
+                            type="submit"
+                            disabled={*loading}
+                            class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                        >
+                            {"Load"}
+                        </button>
+                    </div>
+                    <RecentDevices
+                        device_ids={recent_devices}
+                        current={device_id.clone()}
+                        list_id="recent-device-ids"
+                        on_select={on_recent_select}
+                    />
+                </form>
+            </div>
+
+            if let Some(err) = error.as_ref() {
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                    <span>{"❌"}</span>
+                    <span>{format!("Error: {}", err)}</span>
+                </div>
+            }
+
+            if let Some(success) = success_message.as_ref() {
+                <div class="bg-green-50 dark:bg-green-950 border border-green-200 dark:border-green-800 text-green-700 dark:text-green-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                    <span>{"✅"}</span>
+                    <span>{success}</span>
+                </div>
+            }
+
+            if device_id.trim().is_empty() {
+                <div class="text-center text-gray-500 dark:text-gray-400 py-8">{"Enter a device ID to manage its alerts"}</div>
+            } else {
+                <div class="bg-gray-50 dark:bg-gray-900 p-6 rounded-lg mb-6">
+                    <h3 class="text-lg font-semibold text-gray-800 dark:text-gray-100 mb-4">{"Rules"}</h3>
+                    if rules.is_empty() {
+                        <p class="text-sm text-gray-500 dark:text-gray-400 mb-4">{"No rules yet; add one below"}</p>
+                    } else {
+                        <ul class="space-y-2 mb-4">
+                            {
+                                rules.iter().map(|rule| {
+                                    let editing = form.rule_id.as_deref() == Some(rule.id.as_str());
+                                    let on_edit = {
+                                        let rule = rule.clone();
+                                        on_rule_edit.reform(move |_: MouseEvent| rule.clone())
+                                    };
+                                    let on_delete = {
+                                        let rule = rule.clone();
+                                        on_rule_delete.reform(move |_: MouseEvent| rule.clone())
+                                    };
+                                    html! {
+                                        <li class={classes!(
+                                            "flex", "items-center", "gap-2", "bg-white", "dark:bg-gray-800", "border", "rounded", "p-3",
+                                            if editing { "border-blue-400 dark:border-blue-500" } else { "border-gray-200 dark:border-gray-700" }
+                                        )}>
+                                            <span class="font-mono text-gray-800 dark:text-gray-100">{rule.describe()}</span>
+                                            <button
+                                                type="button"
+                                                onclick={on_edit}
+                                                class="ml-auto px-3 py-1 rounded text-sm text-blue-600 dark:text-blue-400 hover:bg-blue-50 dark:hover:bg-blue-950 transition"
+                                            >
+                                                {"Edit"}
+                                            </button>
+                                            <button
+                                                type="button"
+                                                onclick={on_delete}
+                                                disabled={*loading}
+                                                class="px-3 py-1 rounded text-sm text-red-600 dark:text-red-400 hover:bg-red-50 dark:hover:bg-red-950 transition"
+                                            >
+                                                {"Delete"}
+                                            </button>
+                                        </li>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </ul>
+                    }
+
+                    <form onsubmit={on_rule_submit} class="flex flex-col sm:flex-row gap-2 sm:items-end">
+                        <div class="flex-1">
+                            <label for="rule-metric" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Metric"}</label>
+                            <input
+                                type="text"
+                                id="rule-metric"
+                                value={form.metric.clone()}
+                                oninput={on_metric_input}
+                                class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                                placeholder="e.g. temperature"
+                            />
+                        </div>
+                        <div>
+                            <label for="rule-operator" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Condition"}</label>
+                            <select
+                                id="rule-operator"
+                                onchange={on_operator_change}
+                                class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                            >
+                                {
+                                    Operator::ALL.iter().map(|operator| html! {
+                                        <option value={operator.key()} selected={*operator == form.operator}>
+                                            {operator.symbol()}
+                                        </option>
+                                    }).collect::<Html>()
+                                }
+                            </select>
+                        </div>
+                        <div>
+                            <label for="rule-value" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Threshold"}</label>
+                            <input
+                                type="number"
+                                step="any"
+                                id="rule-value"
+                                value={form.value.clone()}
+                                oninput={on_value_input}
+                                class="w-full sm:w-28 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                            />
+                        </div>
+                        <div>
+                            <label for="rule-duration" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"For (seconds)"}</label>
+                            <input
+                                type="number"
+                                min="0"
+                                max={MAX_ALERT_DURATION_SECS.to_string()}
+                                id="rule-duration"
+                                value={form.duration_secs.clone()}
+                                oninput={on_duration_input}
+                                class="w-full sm:w-28 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                                placeholder="0"
+                            />
+                        </div>
+                        <button
+                            type="submit"
+                            disabled={*loading}
+                            class="px-4 py-2 rounded bg-green-600 text-white font-semibold shadow hover:bg-green-700 transition"
+                        >
+                            { if form.rule_id.is_some() { "Save rule" } else { "Add rule" } }
+                        </button>
+                        if form.rule_id.is_some() {
+                            <button
+                                type="button"
+                                onclick={on_edit_cancel}
+                                class="px-4 py-2 rounded text-gray-700 dark:text-gray-200 hover:bg-gray-200 dark:hover:bg-gray-700 transition"
+                            >
+                                {"Cancel"}
+                            </button>
+                        }
+                    </form>
+                    <p class="text-sm text-gray-500 dark:text-gray-400 mt-2">
+                        {"An alert is raised once the metric has met the condition for the given time, and again only after it stopped meeting it"}
+                    </p>
+                </div>
+
+                <div class="bg-gray-50 dark:bg-gray-900 p-6 rounded-lg">
+                    <div class="flex flex-wrap items-center gap-2 mb-4">
+                        <h3 class="text-lg font-semibold text-gray-800 dark:text-gray-100">{"Raised alerts"}</h3>
+                        if open_alerts > 0 {
+                            <span class="px-2 py-1 rounded text-xs font-semibold bg-red-100 dark:bg-red-900 text-red-800 dark:text-red-200">
+                                {format!("{} open", open_alerts)}
+                            </span>
+                        }
+                        <select
+                            aria-label="Alert status"
+                            onchange={on_filter_change}
+                            class="ml-auto rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                        >
+                            <option value="all" selected={status_filter.is_none()}>{"All statuses"}</option>
+                            {
+                                AlertStatus::ALL.iter().map(|status| html! {
+                                    <option value={status.key()} selected={*status_filter == Some(*status)}>
+                                        {status.label()}
+                                    </option>
+                                }).collect::<Html>()
+                            }
+                        </select>
+                    </div>
+                    if *loading && alerts.is_empty() {
+                        <div class="text-gray-500 dark:text-gray-400 animate-pulse">{"Loading alerts..."}</div>
+                    } else if shown_alerts.is_empty() {
+                        <p class="text-sm text-gray-500 dark:text-gray-400">{"No alerts to show"}</p>
+                    } else {
+                        <ul class="space-y-2">
+                            {
+                                shown_alerts.into_iter().map(|alert| {
+                                    let status = alert.status();
+                                    let on_acknowledge = {
+                                        let alert = alert.clone();
+                                        on_alert_action.reform(move |_: MouseEvent| (alert.clone(), "acknowledge"))
+                                    };
+                                    let on_resolve = {
+                                        let alert = alert.clone();
+                                        on_alert_action.reform(move |_: MouseEvent| (alert.clone(), "resolve"))
+                                    };
+                                    html! {
+                                        <li class="bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded p-3">
+                                            <div class="flex flex-wrap items-center gap-2">
+                                                <span class={classes!("px-2", "py-1", "rounded", "text-xs", "font-semibold", status.badge_classes())}>
+                                                    {status.label()}
+                                                </span>
+                                                <span class="font-mono text-gray-800 dark:text-gray-100">{alert.describe()}</span>
+                                                <span class="text-sm text-gray-500 dark:text-gray-400">{format_time(alert.triggered_at)}</span>
+                                                <span class="ml-auto flex gap-2">
+                                                    if status == AlertStatus::Open {
+                                                        <button
+                                                            type="button"
+                                                            onclick={on_acknowledge}
+                                                            class="px-3 py-1 rounded text-sm text-yellow-700 dark:text-yellow-300 border border-yellow-300 dark:border-yellow-700 hover:bg-yellow-50 dark:hover:bg-yellow-950 transition"
+                                                        >
+                                                            {"Acknowledge"}
+                                                        </button>
+                                                    }
+                                                    if status != AlertStatus::Resolved {
+                                                        <button
+                                                            type="button"
+                                                            onclick={on_resolve}
+                                                            class="px-3 py-1 rounded text-sm text-green-700 dark:text-green-300 border border-green-300 dark:border-green-700 hover:bg-green-50 dark:hover:bg-green-950 transition"
+                                                        >
+                                                            {"Resolve"}
+                                                        </button>
+                                                    }
+                                                </span>
+                                            </div>
+                                            <div class="text-xs text-gray-500 dark:text-gray-400 mt-1 space-x-3">
+                                                <span>{format!("Breach since {}", format_time(alert.breach_started))}</span>
+                                                if let Some(acknowledged) = &alert.acknowledged {
+                                                    <span>{format!("Acknowledged by {} {}", acknowledged.by, format_action_time(&acknowledged.at))}</span>
+                                                }
+                                                if let Some(resolved) = &alert.resolved {
+                                                    <span>{format!("Resolved by {} {}", resolved.by, format_action_time(&resolved.at))}</span>
+                                                }
+                                            </div>
+                                        </li>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </ul>
+                    }
+                </div>
+            }
+        </div>
+    }
+}
+
+/// Builds a callback storing a text field of the rule form.
+///
+/// # Parameters
+/// * `form` - The rule form state
+/// * `apply` - Stores the field's new text in the form
+///
+/// # Returns
+/// * The field's `oninput` callback
+fn on_form_input(form: &UseStateHandle<RuleForm>, apply: fn(&mut RuleForm, String)) -> Callback<InputEvent> {
+    let form = form.clone();
+    Callback::from(move |e: InputEvent| {
+        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+        let mut updated = (*form).clone();
+        apply(&mut updated, input.value());
+        form.set(updated);
+    })
+}
+
+/// Formats a Unix timestamp from an alert for display.
+///
+/// # Parameters
+/// * `timestamp` - Unix timestamp
+///
+/// # Returns
+/// * Text such as "2025-01-02 13:45:00 UTC", or the raw number if out of range
+fn format_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+/// Formats when an alert was acknowledged or resolved.
+///
+/// # Parameters
+/// * `at` - RFC 3339 time from the API
+///
+/// # Returns
+/// * Text such as "at 2025-01-02 13:45 UTC", or the raw value if it does not parse
+fn format_action_time(at: &str) -> String {
+    DateTime::parse_from_rfc3339(at)
+        .map(|dt| dt.format("at %Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|_| format!("at {}", at))
+}
@@ -1,21 +1,224 @@
-use crate::domain::config::DeviceConfig;
+use crate::domain::config::{
+    compare_configs, config_from_entries, diff_configs, entries_from_config, key_hint, validate_entries, ConfigChange,
+    ConfigEntry, ConfigVersion, DeviceConfig, Drift, KeyComparison, ReportedConfig, StoredConfig, SyncStatus,
+};
+use crate::components::{use_recent_devices, JsonInspector, RecentDevices, StaleBanner};
+use crate::domain::device::format_age;
+use crate::route::Route;
+use chrono::{DateTime, Utc};
 use crate::services::device_service::DeviceService;
 use yew::prelude::*;
-use wasm_bindgen::JsCast;
+use yew_router::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct ConfigViewProps {
-    pub device_id: String,
+    /// ID of the device to configure, from the route (`/config/:device_id`)
+    #[prop_or_default]
+    pub device_id: Option<String>,
+}
+
+/// A push waiting for the user to confirm the keys it changes.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingPush {
+    /// The configuration to push
+    config: DeviceConfig,
+    /// Keys the push changes compared to the configuration stored now
+    changes: Vec<ConfigChange>,
+    /// Whether the device is served the fleet defaults, which the push replaces
+    replaces_defaults: bool,
 }
 
 #[function_component(ConfigView)]
-pub fn config_view() -> Html {
-    let device_id = use_state(|| "".to_string());
-    let input_value = use_state(|| "".to_string());
+pub fn config_view(props: &ConfigViewProps) -> Html {
+    let device_id = props.device_id.clone().unwrap_or_default();
+    let navigator = use_navigator();
+    let input_value = use_state(|| device_id.clone());
     let loading = use_state(|| false);
     let error = use_state(|| None::<String>);
     let success_message = use_state(|| None::<String>);
-    let led_status = use_state(|| "off".to_string());
+    // Key/value rows being edited
+    let entries = use_state(Vec::<ConfigEntry>::new);
+    // Describes where the loaded rows came from, e.g. the served version
+    let loaded_from = use_state(|| None::<String>);
+    // The loaded configuration as returned by the API, for the record inspector
+    let loaded_document = use_state(|| None::<serde_json::Value>);
+    // When the loaded configuration was cached, if the device config API was
+    // unreachable and it comes from the offline cache
+    let cached_at = use_state(|| None::<i64>);
+    // The loaded configuration, as served to the device
+    let desired = use_state(|| None::<StoredConfig>);
+    // The configuration the device reports having applied, compared with the desired one
+    let reported = use_state(|| None::<ReportedConfig>);
+    let reported_error = use_state(|| None::<String>);
+    // Bumped by "Check again" to refetch the reported configuration only
+    let reported_check = use_state(|| 0);
+    // Whether the comparison lists only the keys that drifted
+    let drift_only = use_state(|| false);
+    // Stored versions of the configuration, newest first
+    let history = use_state(Vec::<ConfigVersion>::new);
+    let history_error = use_state(|| None::<String>);
+    // Bumped after a push or rollback to reload the configuration and history
+    let revision = use_state(|| 0);
+    // Push shown for review, with the keys it changes, until confirmed or cancelled
+    let pending = use_state(|| None::<PendingPush>);
+    // Recently viewed devices, offered below the device ID input
+    let recent_devices = use_recent_devices(&device_id);
+
+    // Keep the input field in sync when the route changes, e.g. on back navigation
+    {
+        let input_value = input_value.clone();
+        use_effect_with(device_id.clone(), move |device_id| {
+            input_value.set(device_id.clone());
+            || ()
+        });
+    }
+
+    // Clear messages about the previous device when the routed device changes;
+    // reloads after a push or rollback keep them
+    {
+        let error = error.clone();
+        let success_message = success_message.clone();
+        use_effect_with(device_id.clone(), move |_| {
+            error.set(None);
+            success_message.set(None);
+            || ()
+        });
+    }
+
+    // Load the device's current configuration whenever the routed device
+    // changes or a new version is stored
+    {
+        let entries = entries.clone();
+        let loaded_from = loaded_from.clone();
+        let loaded_document = loaded_document.clone();
+        let cached_at = cached_at.clone();
+        let desired = desired.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        use_effect_with((device_id.clone(), *revision), move |(device_id, _)| {
+            let device_id = device_id.clone();
+            entries.set(Vec::new());
+            loaded_from.set(None);
+            loaded_document.set(None);
+            cached_at.set(None);
+            desired.set(None);
+
+            if !device_id.trim().is_empty() {
+                loading.set(true);
+                wasm_bindgen_futures::spawn_local(async move {
+                    let fetched = DeviceService::get_config_or_cached(&device_id).await.map(|fetched| {
+                        cached_at.set(fetched.cached_at);
+                        fetched.data
+                    });
+                    match fetched {
+                        Ok(Some(stored)) => {
+                            entries.set(entries_from_config(&stored.config));
+                            let source = if stored.is_default {
+                                "No configuration of its own yet; showing the fleet defaults it is served".to_string()
+                            } else if let Some(version) = stored.version_number {
+                                format!("Loaded version {}", version)
+                            } else {
+                                "Loaded the current configuration".to_string()
+                            };
+                            loaded_from.set(Some(source));
+                            desired.set(Some(stored.clone()));
+                            loaded_document.set(stored.document);
+                        }
+                        Ok(None) => {
+                            entries.set(vec![ConfigEntry::default()]);
+                            loaded_from.set(Some("No configuration stored yet".to_string()));
+                        }
+                        Err(e) => error.set(Some(format!("Failed to load configuration: {}", e))),
+                    }
+                    loading.set(false);
+                });
+            }
+            || ()
+        });
+    }
+
+    // Close the review once the settings or the device it was for change,
+    // so only the changes shown can be confirmed
+    {
+        let pending = pending.clone();
+        use_effect_with(((*entries).clone(), (*input_value).clone()), move |_| {
+            pending.set(None);
+            || ()
+        });
+    }
+
+    // Load the configuration history alongside the configuration
+    {
+        let history = history.clone();
+        let history_error = history_error.clone();
+        use_effect_with((device_id.clone(), *revision), move |(device_id, _)| {
+            let device_id = device_id.clone();
+            history.set(Vec::new());
+            history_error.set(None);
+
+            if !device_id.trim().is_empty() {
+                wasm_bindgen_futures::spawn_local(async move {
+                    match DeviceService::get_config_history(&device_id).await {
+                        Ok(versions) => history.set(versions),
+                        Err(e) => history_error.set(Some(format!("Failed to load history: {}", e))),
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    // Load the configuration the device reports alongside the configuration,
+    // and again when "Check again" is clicked
+    {
+        let reported = reported.clone();
+        let reported_error = reported_error.clone();
+        use_effect_with((device_id.clone(), *revision, *reported_check), move |(device_id, _, _)| {
+            let device_id = device_id.clone();
+            reported_error.set(None);
+
+            if device_id.trim().is_empty() {
+                reported.set(None);
+            } else {
+                wasm_bindgen_futures::spawn_local(async move {
+                    match DeviceService::get_reported_config(&device_id).await {
+                        Ok(config) => reported.set(config),
+                        Err(e) => {
+                            reported.set(None);
+                            reported_error.set(Some(format!("Failed to load the reported configuration: {}", e)));
+                        }
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    // Callback for the "Check again" button, refetching the reported configuration
+    let on_check_reported = {
+        let reported_check = reported_check.clone();
+        Callback::from(move |_: MouseEvent| reported_check.set(*reported_check + 1))
+    };
+
+    // Callback for the "Only keys that drifted" checkbox
+    let on_drift_only_change = {
+        let drift_only = drift_only.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            drift_only.set(input.checked());
+        })
+    };
+
+    // Keys of the desired and reported configurations side by side, and
+    // whether the device applied the configuration it is served
+    let comparisons = desired.as_ref().map(|desired| {
+        let reported_values = reported.as_ref().map_or(serde_json::Value::Null, |reported| reported.config.clone());
+        compare_configs(&desired.config, &reported_values)
+    });
+    let sync_status = desired
+        .as_ref()
+        .zip(comparisons.as_ref())
+        .map(|(desired, comparisons)| SyncStatus::assess(desired, reported.as_ref(), comparisons));
 
     let on_input_change = {
         let input_value = input_value.clone();
@@ -25,60 +228,172 @@ pub fn config_view() -> Html {
         })
     };
 
-    let on_led_change = {
-        let led_status = led_status.clone();
-        Callback::from(move |e: Event| {
-            if let Some(target) = e.target() {
-                if let Some(input) = target.dyn_into::<web_sys::HtmlInputElement>().ok() {
-                    let value = input.value();
-                    web_sys::console::log_1(&format!("Radio button changed to: {}", value).into());
-                    led_status.set(value);
+    // Loading a device goes through its route, so the view can be bookmarked
+    let on_load = {
+        let input_value = input_value.clone();
+        let navigator = navigator.clone();
+        let error = error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let device_id = input_value.trim().to_string();
+            if device_id.is_empty() {
+                error.set(Some("Please enter a device ID.".to_string()));
+            } else if let Some(navigator) = &navigator {
+                navigator.push(&Route::ConfigDevice { device_id });
+            }
+        })
+    };
+
+    // Picking a recently viewed device loads it through its route
+    let on_recent_select = {
+        let navigator = navigator.clone();
+        Callback::from(move |device_id: String| {
+            if let Some(navigator) = &navigator {
+                navigator.push(&Route::ConfigDevice { device_id });
+            }
+        })
+    };
+
+    // Edits a row's key (`true`) or value (`false`)
+    let on_entry_change = {
+        let entries = entries.clone();
+        Callback::from(move |(index, is_key, text): (usize, bool, String)| {
+            let mut updated = (*entries).clone();
+            if let Some(entry) = updated.get_mut(index) {
+                if is_key {
+                    entry.key = text;
+                } else {
+                    entry.value = text;
                 }
             }
+            entries.set(updated);
         })
     };
 
+    let on_entry_remove = {
+        let entries = entries.clone();
+        Callback::from(move |index: usize| {
+            let mut updated = (*entries).clone();
+            if index < updated.len() {
+                updated.remove(index);
+            }
+            entries.set(updated);
+        })
+    };
+
+    let on_entry_add = {
+        let entries = entries.clone();
+        Callback::from(move |_| {
+            let mut updated = (*entries).clone();
+            updated.push(ConfigEntry::default());
+            entries.set(updated);
+        })
+    };
+
+    let validation_errors = validate_entries(&entries);
+
+    // Fetches the configuration stored now and shows the keys the edited
+    // rows would change, so nothing is overwritten by accident
     let on_push_config = {
-        let device_id = device_id.clone();
         let input_value = input_value.clone();
-        let led_status = led_status.clone();
+        let entries = entries.clone();
         let error = error.clone();
         let success_message = success_message.clone();
         let loading = loading.clone();
+        let pending = pending.clone();
+        let valid = validation_errors.is_empty();
         Callback::from(move |_| {
-            let device_id = (*input_value).clone();
-            let led_status = (*led_status).clone();
+            let device_id = input_value.trim().to_string();
             let error = error.clone();
-            let success_message = success_message.clone();
             let loading = loading.clone();
+            let pending = pending.clone();
 
-            if device_id.trim().is_empty() {
+            if device_id.is_empty() {
                 error.set(Some("Please enter a device ID.".to_string()));
                 return;
             }
+            if !valid {
+                error.set(Some("Fix the highlighted settings before pushing.".to_string()));
+                return;
+            }
 
-            // Debug: Log the LED status being sent
-            web_sys::console::log_1(&format!("Sending LED status: {}", led_status).into());
+            let config = DeviceConfig {
+                device_id: device_id.clone(),
+                config: config_from_entries(&entries),
+            };
 
             loading.set(true);
             error.set(None);
             success_message.set(None);
 
             wasm_bindgen_futures::spawn_local(async move {
-                let config = DeviceConfig {
-                    device_id: device_id.clone(),
-                    config: serde_json::json!({
-                        "LED": led_status
-                    }),
-                };
+                match DeviceService::get_config(&device_id).await {
+                    Ok(stored) => {
+                        let (current, replaces_defaults) = match stored {
+                            Some(stored) => (stored.config, stored.is_default),
+                            None => (serde_json::Value::Object(Default::default()), false),
+                        };
+                        let changes = diff_configs(&current, &config.config);
+                        pending.set(Some(PendingPush { config, changes, replaces_defaults }));
+                    }
+                    Err(e) => error.set(Some(format!("Failed to fetch the current configuration: {}", e))),
+                }
+                loading.set(false);
+            });
+        })
+    };
 
-                // Debug: Log the full config being sent
-                web_sys::console::log_1(&format!("Sending config: {:?}", config).into());
+    // Closes the review without pushing
+    let on_cancel_push = {
+        let pending = pending.clone();
+        Callback::from(move |_: MouseEvent| pending.set(None))
+    };
 
+    // Pushes the reviewed configuration as the device's whole configuration,
+    // so keys removed from the editor are removed from the device
+    let on_confirm_push = {
+        let current_device_id = device_id.clone();
+        let navigator = navigator.clone();
+        let error = error.clone();
+        let success_message = success_message.clone();
+        let loading = loading.clone();
+        let revision = revision.clone();
+        let pending = pending.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(PendingPush { config, .. }) = (*pending).clone() else {
+                return;
+            };
+            let device_id = config.device_id.clone();
+            let error = error.clone();
+            let success_message = success_message.clone();
+            let loading = loading.clone();
+            let revision = revision.clone();
+            pending.set(None);
+
+            // Put the configured device in the URL so the view can be bookmarked
+            if device_id != current_device_id {
+                if let Some(navigator) = &navigator {
+                    navigator.push(&Route::ConfigDevice { device_id: device_id.clone() });
+                }
+            }
+
+            // Debug: Log the full config being sent
+            web_sys::console::log_1(&format!("Sending config: {:?}", config).into());
+
+            loading.set(true);
+            error.set(None);
+            success_message.set(None);
+            let reload = device_id == current_device_id;
+
+            wasm_bindgen_futures::spawn_local(async move {
                 match DeviceService::update_device_config(&device_id, &config).await {
                     Ok(_) => {
                         success_message.set(Some(format!("Configuration pushed successfully to device {}!", device_id)));
                         loading.set(false);
+                        // Pushing to the loaded device stores a new version
+                        if reload {
+                            revision.set(*revision + 1);
+                        }
                     }
                     Err(e) => {
                         error.set(Some(format!("Failed to push configuration: {}", e)));
@@ -89,110 +404,535 @@ pub fn config_view() -> Html {
         })
     };
 
+    // Stores the values of an earlier version again as a new version
+    let on_rollback = {
+        let device_id = device_id.clone();
+        let error = error.clone();
+        let success_message = success_message.clone();
+        let loading = loading.clone();
+        let revision = revision.clone();
+        Callback::from(move |version_number: u64| {
+            let device_id = device_id.clone();
+            let error = error.clone();
+            let success_message = success_message.clone();
+            let loading = loading.clone();
+            let revision = revision.clone();
+
+            loading.set(true);
+            error.set(None);
+            success_message.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::rollback_config(&device_id, version_number).await {
+                    Ok(true) => {
+                        success_message.set(Some(format!("Device {} rolled back to version {}.", device_id, version_number)));
+                        revision.set(*revision + 1);
+                    }
+                    Ok(false) => {
+                        success_message.set(Some(format!("Rollback to version {} is waiting for approval.", version_number)));
+                    }
+                    Err(e) => error.set(Some(format!("Failed to roll back: {}", e))),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
     html! {
-        <div class="w-full bg-white rounded-xl shadow-md p-8 mt-8">
+        <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8">
             <div class="mb-6">
-                <h2 class="text-3xl font-bold text-gray-800 mb-2">{"Device Configuration"}</h2>
-                <p class="text-gray-600 mb-4">{"Build and push configuration to your IoT devices"}</p>
+                <h2 class="text-3xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Device Configuration"}</h2>
+                <p class="text-gray-600 dark:text-gray-300 mb-4">{"Build and push configuration to your IoT devices"}</p>
             </div>
 
             if let Some(err) = error.as_ref() {
-                <div class="bg-red-50 border border-red-200 text-red-700 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
                     <span>{"❌"}</span>
                     <span>{format!("Error: {}", err)}</span>
                 </div>
             }
 
             if let Some(success) = success_message.as_ref() {
-                <div class="bg-green-50 border border-green-200 text-green-700 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                <div class="bg-green-50 dark:bg-green-950 border border-green-200 dark:border-green-800 text-green-700 dark:text-green-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
                     <span>{"✅"}</span>
                     <span>{success}</span>
                 </div>
             }
 
-            <div class="bg-gray-50 p-6 rounded-lg">
-                <h3 class="text-lg font-semibold text-gray-800 mb-4">{"Configuration Settings"}</h3>
-                
+            <div class="bg-gray-50 dark:bg-gray-900 p-6 rounded-lg">
+                <h3 class="text-lg font-semibold text-gray-800 dark:text-gray-100 mb-4">{"Configuration Settings"}</h3>
+
                 <div class="space-y-4">
-                    <div>
-                        <label for="device-id" class="block text-sm font-medium text-gray-700 mb-2">
+                    <form onsubmit={on_load}>
+                        <label for="device-id" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-2">
                             {"Device ID"}
                         </label>
-                        <input
-                            type="text"
-                            id="device-id"
-                            value={(*input_value).clone()}
-                            oninput={on_input_change}
-                            class="w-full rounded-md border-gray-300 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
-                            placeholder="Enter device ID (e.g., 4321)"
-                            autofocus=true
+                        <div class="flex gap-2">
+                            <input
+                                type="text"
+                                id="device-id"
+                                list="recent-device-ids"
+                                value={(*input_value).clone()}
+                                oninput={on_input_change}
+                                class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
+                                placeholder="Enter device ID (e.g., 4321)"
+                                autofocus=true
+                            />
+                            <button
+                                type="submit"
+                                disabled={*loading}
+                                class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                            >
+                                {"Load"}
+                            </button>
+                        </div>
+                        <RecentDevices
+                            device_ids={recent_devices}
+                            current={device_id.clone()}
+                            list_id="recent-device-ids"
+                            on_select={on_recent_select}
                         />
-                        <p class="text-sm text-gray-500 mt-1">
-                            {"The ID of the device you want to configure"}
+                        <p class="text-sm text-gray-500 dark:text-gray-400 mt-1">
+                            {"The ID of the device you want to configure; Load fetches its current configuration"}
                         </p>
-                    </div>
+                    </form>
 
                     <div>
-                        <label class="block text-sm font-medium text-gray-700 mb-2">
-                            {"LED Status"}
-                        </label>
+                        <span class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-2">{"Settings"}</span>
+                        // Pushing needs the API, so a cached configuration is flagged
+                        <StaleBanner fetched_at={None::<i64>} cached_at={*cached_at} />
+                        if let Some(source) = loaded_from.as_ref() {
+                            <p class="text-sm text-blue-600 dark:text-blue-400 mb-2">{source}</p>
+                        }
+                        if let Some(document) = loaded_document.as_ref() {
+                            { raw_json_details(document) }
+                        }
                         <div class="space-y-2">
-                            <label class="flex items-center">
-                                <input
-                                    type="radio"
-                                    name="led-status"
-                                    value="off"
-                                    checked={*led_status == "off"}
-                                    onchange={on_led_change.clone()}
-                                    class="mr-2"
-                                />
-                                <span>{"Off"}</span>
-                            </label>
-                            <label class="flex items-center">
-                                <input
-                                    type="radio"
-                                    name="led-status"
-                                    value="on"
-                                    checked={*led_status == "on"}
-                                    onchange={on_led_change}
-                                    class="mr-2"
-                                />
-                                <span>{"On"}</span>
-                            </label>
+                            {
+                                entries.iter().enumerate().map(|(index, entry)| {
+                                    let on_key_input = on_entry_change.reform(move |e: InputEvent| {
+                                        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                        (index, true, input.value())
+                                    });
+                                    let on_value_input = on_entry_change.reform(move |e: InputEvent| {
+                                        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                        (index, false, input.value())
+                                    });
+                                    let on_remove = on_entry_remove.reform(move |_: MouseEvent| index);
+                                    html! {
+                                        <div class="flex flex-col sm:flex-row gap-2 sm:items-center">
+                                            <input
+                                                type="text"
+                                                aria-label="Key"
+                                                value={entry.key.clone()}
+                                                oninput={on_key_input}
+                                                class="sm:w-1/3 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                                                placeholder="Key"
+                                            />
+                                            <input
+                                                type="text"
+                                                aria-label="Value"
+                                                value={entry.value.clone()}
+                                                oninput={on_value_input}
+                                                class="flex-1 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                                                placeholder={key_hint(&entry.key).unwrap_or("Value")}
+                                            />
+                                            <span class="sm:w-48 text-xs text-gray-500 dark:text-gray-400">
+                                                {key_hint(&entry.key).unwrap_or_default()}
+                                            </span>
+                                            <button
+                                                type="button"
+                                                onclick={on_remove}
+                                                class="px-3 py-2 rounded text-red-600 dark:text-red-400 hover:bg-red-50 dark:hover:bg-red-950 transition"
+                                            >
+                                                {"Remove"}
+                                            </button>
+                                        </div>
+                                    }
+                                }).collect::<Html>()
+                            }
                         </div>
-                        <p class="text-sm text-gray-500 mt-1">
-                            {"Control the LED status on the device"}
-                        </p>
-                        <p class="text-sm text-blue-600 mt-2">
-                            {format!("Current selection: {}", *led_status)}
-                        </p>
+                        <button
+                            type="button"
+                            onclick={on_entry_add}
+                            class="mt-2 px-3 py-2 rounded text-blue-600 dark:text-blue-400 hover:bg-blue-50 dark:hover:bg-blue-950 transition"
+                        >
+                            {"+ Add setting"}
+                        </button>
+                        if !validation_errors.is_empty() {
+                            <ul class="mt-2 text-sm text-red-600 dark:text-red-400 list-disc list-inside">
+                                { validation_errors.iter().map(|message| html! { <li>{message}</li> }).collect::<Html>() }
+                            </ul>
+                        }
                     </div>
                 </div>
 
                 <div class="mt-6">
                     <button
                         onclick={on_push_config}
-                        disabled={*loading}
+                        disabled={*loading || !validation_errors.is_empty()}
                         class={format!(
                             "px-6 py-2 rounded bg-green-600 text-white font-semibold shadow hover:bg-green-700 transition {}",
-                            if *loading { "opacity-50 cursor-not-allowed" } else { "" }
+                            if *loading || !validation_errors.is_empty() { "opacity-50 cursor-not-allowed" } else { "" }
                         )}
                     >
                         { if *loading { html! { <span class="animate-spin mr-2">{"⏳"}</span> } } else { html!{} } }
                         {"Push Configuration"}
                     </button>
                 </div>
+
+                if let Some(review) = pending.as_ref() {
+                    <div class="mt-6 bg-yellow-50 dark:bg-yellow-950 border border-yellow-300 dark:border-yellow-700 rounded-lg p-4 animate-fade-in">
+                        <h4 class="font-semibold text-gray-800 dark:text-gray-100 mb-2">
+                            {format!("Review the changes to device {}", review.config.device_id)}
+                        </h4>
+                        if review.replaces_defaults {
+                            <p class="text-sm text-gray-600 dark:text-gray-300 mb-2">
+                                {"The device is served the fleet defaults; pushing gives it a configuration of its own."}
+                            </p>
+                        }
+                        if review.changes.is_empty() {
+                            <p class="text-sm text-gray-600 dark:text-gray-300 mb-2">{"Nothing changes compared to the current configuration."}</p>
+                        } else {
+                            <div class="overflow-x-auto rounded border border-gray-200 dark:border-gray-700 mb-2">
+                                <table class="min-w-full text-sm font-mono">
+                                    <thead class="bg-gray-50 dark:bg-gray-900">
+                                        <tr>
+                                            <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300">{"Key"}</th>
+                                            <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300">{"Current"}</th>
+                                            <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300">{"After push"}</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody class="divide-y divide-gray-200 dark:divide-gray-700 bg-white dark:bg-gray-800">
+                                        { review.changes.iter().map(render_diff_row).collect::<Html>() }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }
+                        <div class="flex gap-2 mt-3">
+                            <button
+                                type="button"
+                                onclick={on_confirm_push}
+                                disabled={*loading}
+                                class="px-4 py-2 rounded bg-green-600 text-white font-semibold shadow hover:bg-green-700 transition"
+                            >
+                                {"Confirm push"}
+                            </button>
+                            <button
+                                type="button"
+                                onclick={on_cancel_push}
+                                class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                            >
+                                {"Cancel"}
+                            </button>
+                        </div>
+                    </div>
+                }
             </div>
 
-            <div class="mt-6 bg-blue-50 border border-blue-200 text-blue-700 px-4 py-3 rounded">
+            if let (Some(desired), Some(comparisons), Some(status)) = (desired.as_ref(), comparisons.as_ref(), sync_status) {
+                <div class="mt-6 bg-gray-50 dark:bg-gray-900 p-6 rounded-lg">
+                    <div class="flex flex-wrap items-center gap-2 mb-4">
+                        <h3 class="text-lg font-semibold text-gray-800 dark:text-gray-100">{"Desired vs reported"}</h3>
+                        <button
+                            type="button"
+                            onclick={on_check_reported}
+                            class="ml-auto px-3 py-1 rounded text-sm text-blue-600 dark:text-blue-400 hover:bg-blue-50 dark:hover:bg-blue-950 transition"
+                        >
+                            {"Check again"}
+                        </button>
+                    </div>
+                    if let Some(err) = reported_error.as_ref() {
+                        <p class="text-sm text-red-600 dark:text-red-400 mb-2">{err}</p>
+                    } else {
+                        { render_sync_status(status) }
+                    }
+                    <div class="grid grid-cols-1 sm:grid-cols-2 gap-2 text-sm mb-4">
+                        <div class="bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded p-3">
+                            <p class="font-medium text-gray-700 dark:text-gray-200">{"Desired"}</p>
+                            <p class="text-gray-500 dark:text-gray-400">
+                                {
+                                    if desired.is_default {
+                                        "Fleet defaults".to_string()
+                                    } else {
+                                        desired.version_number.map_or("Unnumbered version".to_string(), |number| format!("Version {}", number))
+                                    }
+                                }
+                            </p>
+                        </div>
+                        <div class="bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded p-3">
+                            <p class="font-medium text-gray-700 dark:text-gray-200">{"Reported"}</p>
+                            <p class="text-gray-500 dark:text-gray-400">
+                                { reported.as_ref().map_or("Nothing reported yet".to_string(), describe_reported) }
+                            </p>
+                            if let Some(document) = reported.as_ref().and_then(|reported| reported.document.as_ref()) {
+                                { raw_json_details(document) }
+                            }
+                        </div>
+                    </div>
+                    if reported.is_some() {
+                        <label class="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-200 mb-2">
+                            <input type="checkbox" checked={*drift_only} onchange={on_drift_only_change} />
+                            {"Only keys that drifted"}
+                        </label>
+                        <div class="overflow-x-auto rounded border border-gray-200 dark:border-gray-700">
+                            <table class="min-w-full text-sm font-mono">
+                                <thead class="bg-gray-50 dark:bg-gray-900">
+                                    <tr>
+                                        <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300">{"Key"}</th>
+                                        <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300">{"Desired"}</th>
+                                        <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300">{"Reported"}</th>
+                                        <th class="px-3 py-2 text-left font-medium text-gray-600 dark:text-gray-300">{"Status"}</th>
+                                    </tr>
+                                </thead>
+                                <tbody class="divide-y divide-gray-200 dark:divide-gray-700 bg-white dark:bg-gray-800">
+                                    {
+                                        comparisons
+                                            .iter()
+                                            .filter(|comparison| !*drift_only || comparison.drift.is_drift())
+                                            .map(render_comparison_row)
+                                            .collect::<Html>()
+                                    }
+                                </tbody>
+                            </table>
+                        </div>
+                        if *drift_only && !comparisons.iter().any(|comparison| comparison.drift.is_drift()) {
+                            <p class="text-sm text-gray-500 dark:text-gray-400 mt-2">{"No key drifted"}</p>
+                        }
+                    }
+                </div>
+            }
+
+            if !device_id.trim().is_empty() {
+                <div class="mt-6 bg-gray-50 dark:bg-gray-900 p-6 rounded-lg">
+                    <h3 class="text-lg font-semibold text-gray-800 dark:text-gray-100 mb-4">{"History"}</h3>
+                    if let Some(err) = history_error.as_ref() {
+                        <p class="text-sm text-red-600 dark:text-red-400">{err}</p>
+                    } else if history.is_empty() {
+                        <p class="text-sm text-gray-500 dark:text-gray-400">{"No versions stored yet"}</p>
+                    }
+                    <ul class="space-y-3">
+                        {
+                            history.iter().map(|version| {
+                                let rollback = version.version_number.filter(|_| !version.active).map(|number| {
+                                    let on_click = on_rollback.reform(move |_: MouseEvent| number);
+                                    html! {
+                                        <button
+                                            type="button"
+                                            onclick={on_click}
+                                            disabled={*loading}
+                                            class="px-3 py-1 rounded text-sm text-orange-700 dark:text-orange-300 border border-orange-300 dark:border-orange-700 hover:bg-orange-50 dark:hover:bg-orange-950 transition"
+                                        >
+                                            {"Roll back"}
+                                        </button>
+                                    }
+                                });
+                                html! {
+                                    <li class="bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded p-3">
+                                        <div class="flex flex-wrap items-center gap-2 mb-2">
+                                            <span class="font-semibold text-gray-800 dark:text-gray-100">
+                                                { version.version_number.map_or("Unnumbered version".to_string(), |number| format!("Version {}", number)) }
+                                            </span>
+                                            if version.active {
+                                                <span class="px-2 py-1 rounded text-xs font-semibold bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200">{"Active"}</span>
+                                            }
+                                            <span class="text-sm text-gray-500 dark:text-gray-400">{describe_version(version)}</span>
+                                            <span class="ml-auto">{ rollback.unwrap_or_default() }</span>
+                                        </div>
+                                        <ul class="text-sm font-mono space-y-1">
+                                            { version.changes.iter().map(render_change).collect::<Html>() }
+                                        </ul>
+                                        if let Some(document) = version.document.as_ref() {
+                                            { raw_json_details(document) }
+                                        }
+                                    </li>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </ul>
+                </div>
+            }
+
+            <div class="mt-6 bg-blue-50 dark:bg-blue-950 border border-blue-200 dark:border-blue-800 text-blue-700 dark:text-blue-300 px-4 py-3 rounded">
                 <h4 class="font-semibold mb-2">{"How it works:"}</h4>
                 <ul class="text-sm space-y-1">
-                    <li>{"1. Enter the device ID you want to configure"}</li>
-                    <li>{"2. Select the desired LED status (on/off)"}</li>
-                    <li>{"3. Click 'Push Configuration' to send the settings to the device"}</li>
+                    <li>{"1. Enter the device ID you want to configure and click 'Load' to fetch its current settings"}</li>
+                    <li>{"2. Edit, add or remove settings; keys the firmware interprets, such as LED and interval, show the values they accept"}</li>
+                    <li>{"3. Click 'Push Configuration' to review the keys that change compared to the stored configuration, then 'Confirm push' to send the settings to the device; they replace its whole configuration"}</li>
                     <li>{"4. The device will download and apply the new configuration"}</li>
+                    <li>{"5. 'Desired vs reported' compares the configuration served with the one the device reports having applied, and flags the keys that drifted; 'Check again' refetches the device's report"}</li>
+                    <li>{"6. The history lists every stored version with the settings it changed; 'Roll back' restores an earlier one"}</li>
                 </ul>
             </div>
         </div>
     }
-} 
\ No newline at end of file
+}
+
+/// Describes when a version took effect and who stored it.
+///
+/// # Parameters
+/// * `version` - The stored version
+///
+/// # Returns
+/// * Text such as "2025-01-02 00:00 UTC by operator@example.com"
+fn describe_version(version: &ConfigVersion) -> String {
+    let when = version.effective_at.as_deref().map(format_rfc3339);
+    match (when, version.modified_by.as_deref()) {
+        (Some(when), Some(by)) => format!("{} by {}", when, by),
+        (Some(when), None) => when,
+        (None, Some(by)) => format!("by {}", by),
+        (None, None) => String::new(),
+    }
+}
+
+/// Describes the configuration a device reports: its version and when it
+/// was applied.
+///
+/// # Parameters
+/// * `reported` - The reported configuration
+///
+/// # Returns
+/// * Text such as "Version 3, applied 2025-01-02 00:00 UTC (5 min ago)"
+fn describe_reported(reported: &ReportedConfig) -> String {
+    let version = reported.version_number.map_or("No version".to_string(), |number| format!("Version {}", number));
+    match reported.applied_at.as_deref() {
+        Some(applied_at) => {
+            let age = DateTime::parse_from_rfc3339(applied_at)
+                .map(|dt| format!(" ({})", format_age(Utc::now().timestamp() - dt.timestamp())))
+                .unwrap_or_default();
+            format!("{}, applied {}{}", version, format_rfc3339(applied_at), age)
+        }
+        None => version,
+    }
+}
+
+/// Formats an RFC 3339 time from the API in UTC, e.g. "2025-01-02 00:00 UTC".
+///
+/// # Parameters
+/// * `time` - The time as returned by the API
+///
+/// # Returns
+/// * The formatted time, or the text unchanged if it can't be parsed
+fn format_rfc3339(time: &str) -> String {
+    DateTime::parse_from_rfc3339(time)
+        .map(|dt| dt.with_timezone(&Utc).format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|_| time.to_string())
+}
+
+/// Renders the sync status of a device above the comparison.
+///
+/// # Parameters
+/// * `status` - Whether the device applied the configuration it is served
+///
+/// # Returns
+/// * A green line when in sync, yellow while a version is being applied or
+///   nothing was reported, and red when keys drifted
+fn render_sync_status(status: SyncStatus) -> Html {
+    let classes = match status {
+        SyncStatus::InSync => "text-green-700 dark:text-green-300",
+        SyncStatus::VersionBehind { .. } | SyncStatus::NotReported => "text-yellow-700 dark:text-yellow-300",
+        SyncStatus::Drifted { .. } => "text-red-700 dark:text-red-300",
+    };
+    html! {
+        <p class={classes!("text-sm", "font-semibold", "mb-2", classes)}>{status.describe()}</p>
+    }
+}
+
+/// Renders one key of the desired and reported configurations as a row of
+/// the comparison table.
+///
+/// # Parameters
+/// * `comparison` - The key with its desired and reported values
+///
+/// # Returns
+/// * The key with both values and its status; drifted keys are highlighted
+///   in red, and a dash stands for a missing value
+fn render_comparison_row(comparison: &KeyComparison) -> Html {
+    let cell = |value: &Option<String>| match value {
+        Some(value) => html! { <td class="px-3 py-2 break-all text-gray-800 dark:text-gray-100">{value}</td> },
+        None => html! { <td class="px-3 py-2 text-gray-400 dark:text-gray-500">{"—"}</td> },
+    };
+    let (row, badge) = match comparison.drift {
+        Drift::InSync | Drift::Secret => ("", "bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200"),
+        Drift::Changed | Drift::Missing | Drift::Unexpected => (
+            "bg-red-50 dark:bg-red-950",
+            "bg-red-100 dark:bg-red-900 text-red-800 dark:text-red-200",
+        ),
+    };
+    html! {
+        <tr class={row}>
+            <td class="px-3 py-2 text-gray-800 dark:text-gray-100">{&comparison.key}</td>
+            { cell(&comparison.desired) }
+            { cell(&comparison.reported) }
+            <td class="px-3 py-2">
+                <span class={classes!("px-2", "py-1", "rounded", "text-xs", "font-semibold", "font-sans", "whitespace-nowrap", badge)}>
+                    {comparison.drift.label()}
+                </span>
+            </td>
+        </tr>
+    }
+}
+
+/// Renders one changed key of a version as a diff line.
+///
+/// # Parameters
+/// * `change` - The changed key
+///
+/// # Returns
+/// * A green line for added keys, a red one for removed keys and the old
+///   and new value for modified keys
+fn render_change(change: &ConfigChange) -> Html {
+    match (&change.old_value, &change.new_value) {
+        (None, Some(new)) => html! {
+            <li class="text-green-700 dark:text-green-300">{format!("+ {} = {}", change.key, new)}</li>
+        },
+        (Some(old), None) => html! {
+            <li class="text-red-700 dark:text-red-300">{format!("- {} = {}", change.key, old)}</li>
+        },
+        (old, new) => html! {
+            <li class="text-gray-700 dark:text-gray-200">
+                {format!("~ {}: {} → {}", change.key, old.as_deref().unwrap_or_default(), new.as_deref().unwrap_or_default())}
+            </li>
+        },
+    }
+}
+
+/// Renders one key a push changes as a row of the review table.
+///
+/// # Parameters
+/// * `change` - The changed key
+///
+/// # Returns
+/// * The key with its current value struck through in red and the pushed
+///   one in green; a dash where the key is added or removed
+fn render_diff_row(change: &ConfigChange) -> Html {
+    let cell = |value: &Option<String>, classes: &'static str| match value {
+        Some(value) => html! { <td class={classes!("px-3", "py-2", "break-all", classes)}>{value}</td> },
+        None => html! { <td class="px-3 py-2 text-gray-400 dark:text-gray-500">{"—"}</td> },
+    };
+    html! {
+        <tr>
+            <td class="px-3 py-2 text-gray-800 dark:text-gray-100">{&change.key}</td>
+            { cell(&change.old_value, "line-through text-red-700 dark:text-red-300 bg-red-50 dark:bg-red-950") }
+            { cell(&change.new_value, "text-green-700 dark:text-green-300 bg-green-50 dark:bg-green-950") }
+        </tr>
+    }
+}
+
+/// Renders a configuration document, collapsed until expanded.
+///
+/// # Parameters
+/// * `document` - The document as returned by the API
+///
+/// # Returns
+/// * An expandable "Raw JSON" section holding the record inspector
+fn raw_json_details(document: &serde_json::Value) -> Html {
+    html! {
+        <details class="mt-2 mb-2">
+            <summary class="text-sm text-gray-500 dark:text-gray-400 cursor-pointer hover:text-gray-700 dark:hover:text-gray-200">{"Raw JSON"}</summary>
+            <div class="mt-2">
+                <JsonInspector document={document.clone()} />
+            </div>
+        </details>
+    }
+}
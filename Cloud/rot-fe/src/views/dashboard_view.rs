@@ -0,0 +1,341 @@
+/// # Dashboard View
+///
+/// This component shows a dashboard the user composes from widgets, so each
+/// operator can keep the devices and metrics they care about on one page.
+/// It allows users to:
+/// - Add a chart or stat card of a device's metric, or a device status tile,
+///   over a quick time range
+/// - Arrange the widgets in a grid, moving them earlier or later and
+///   widening them to both columns
+/// - Remove widgets, and refresh every widget at once
+///
+/// The widgets are kept in the browser's local storage; the device and
+/// metric inputs suggest the devices and metrics the fleet reports.
+
+use crate::components::{ApexChart, StatCards, StatusTile, SvgChart};
+use crate::domain::dashboard::{move_widget, Widget, WidgetKind};
+use crate::domain::plot::ChartRenderer;
+use crate::domain::preset::TimeRange;
+use crate::route::Route;
+use crate::services::device_service::DeviceService;
+use crate::services::preferences_service::PreferencesService;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Time range of new widgets
+const DEFAULT_WIDGET_RANGE: TimeRange = TimeRange::LastDay;
+
+/// Component for composing and showing the user's dashboard.
+#[function_component(DashboardView)]
+pub fn dashboard_view() -> Html {
+    // State for the widgets, loaded from local storage
+    let widgets = use_state(|| PreferencesService::load_dashboard().unwrap_or_default());
+
+    // State for the fields of the add widget form
+    let kind = use_state(WidgetKind::default);
+    let device_id = use_state(String::new);
+    let metric = use_state(String::new);
+    let range = use_state(|| DEFAULT_WIDGET_RANGE);
+
+    // State for the devices and metrics suggested in the form
+    let known_devices = use_state(Vec::<String>::new);
+    let known_metrics = use_state(Vec::<String>::new);
+
+    // State for the library the chart widgets are drawn with, chosen on the settings page
+    let renderer = use_state(|| PreferencesService::load_chart_renderer().unwrap_or_default());
+
+    // State for error messages
+    let error = use_state(|| None::<String>);
+
+    // Counter for triggering a refresh of every widget
+    let refresh_count = use_state(|| 0);
+
+    // Effect hook for fetching the suggestions once; without them the form still works
+    {
+        let known_devices = known_devices.clone();
+        let known_metrics = known_metrics.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::get_devices().await {
+                    Ok(devices) => known_devices.set(devices.into_iter().map(|device| device.device_id).collect()),
+                    Err(e) => web_sys::console::log_1(&format!("Failed to fetch devices: {}", e).into()),
+                }
+                match DeviceService::get_fleet_summary().await {
+                    Ok(summary) => known_metrics.set(summary.metrics.into_iter().map(|metric| metric.metric).collect()),
+                    Err(e) => web_sys::console::log_1(&format!("Failed to fetch metrics: {}", e).into()),
+                }
+            });
+            || ()
+        });
+    }
+
+    // Saves the widgets and shows them; a failed save keeps them for this visit
+    let set_widgets = {
+        let widgets = widgets.clone();
+        let error = error.clone();
+        Callback::from(move |updated: Vec<Widget>| {
+            match PreferencesService::save_dashboard(&updated) {
+                Ok(()) => error.set(None),
+                Err(e) => error.set(Some(format!("Failed to save the dashboard: {}", e))),
+            }
+            widgets.set(updated);
+        })
+    };
+
+    // Callback for choosing the widget kind
+    let on_kind_change = {
+        let kind = kind.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Some(selected) = WidgetKind::from_key(&select.value()) {
+                kind.set(selected);
+            }
+        })
+    };
+
+    // Callback for typing the device ID
+    let on_device_input = {
+        let device_id = device_id.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            device_id.set(input.value());
+        })
+    };
+
+    // Callback for typing the metric
+    let on_metric_input = {
+        let metric = metric.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            metric.set(input.value());
+        })
+    };
+
+    // Callback for choosing the time range
+    let on_range_change = {
+        let range = range.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Some(selected) = TimeRange::from_key(&select.value()) {
+                range.set(selected);
+            }
+        })
+    };
+
+    // Callback for adding the widget described by the form
+    // Keeps the device so several of its metrics can be added in a row
+    let on_add = {
+        let widgets = widgets.clone();
+        let kind = kind.clone();
+        let device_id = device_id.clone();
+        let metric = metric.clone();
+        let range = range.clone();
+        let error = error.clone();
+        let set_widgets = set_widgets.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            match Widget::parse(&widgets, *kind, &device_id, &metric, *range) {
+                Ok(widget) => {
+                    let mut updated = (*widgets).clone();
+                    updated.push(widget);
+                    set_widgets.emit(updated);
+                    metric.set(String::new());
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        })
+    };
+
+    // Callback for handling refresh button clicks
+    let on_refresh = {
+        let refresh_count = refresh_count.clone();
+        Callback::from(move |_: MouseEvent| refresh_count.set(*refresh_count + 1))
+    };
+
+    // Renders a widget with its controls
+    let widget_card = |index: usize, widget: &Widget| {
+        // Callbacks editing the widgets, each applied to a copy of the current ones
+        let edit = |apply: fn(&mut Vec<Widget>, usize)| {
+            let widgets = (*widgets).clone();
+            set_widgets.reform(move |_: MouseEvent| {
+                let mut updated = widgets.clone();
+                apply(&mut updated, index);
+                updated
+            })
+        };
+        let on_earlier = edit(|widgets, index| move_widget(widgets, index, false));
+        let on_later = edit(|widgets, index| move_widget(widgets, index, true));
+        let on_resize = edit(|widgets, index| widgets[index].size = widgets[index].size.toggle());
+        let on_remove = edit(|widgets, index| {
+            widgets.remove(index);
+        });
+        let last = index + 1 == widgets.len();
+
+        let body = match (widget.kind, &widget.metric) {
+            (WidgetKind::Chart, Some(metric)) if *renderer == ChartRenderer::Native => html! {
+                <SvgChart
+                    metric_key={metric.clone()}
+                    title={metric.clone()}
+                    device_id={widget.device_id.clone()}
+                    refresh_count={*refresh_count}
+                    range={widget.range}
+                />
+            },
+            (WidgetKind::Chart, Some(metric)) => html! {
+                <ApexChart
+                    metric_key={metric.clone()}
+                    title={metric.clone()}
+                    device_id={widget.device_id.clone()}
+                    refresh_count={*refresh_count}
+                    range={widget.range}
+                />
+            },
+            (WidgetKind::StatCard, Some(metric)) => html! {
+                <StatCards
+                    device_id={widget.device_id.clone()}
+                    metrics={vec![metric.clone()]}
+                    range={widget.range}
+                    refresh_count={*refresh_count}
+                />
+            },
+            (WidgetKind::StatusTile, _) => html! {
+                <StatusTile device_id={widget.device_id.clone()} refresh_count={*refresh_count} />
+            },
+            (_, None) => html! {
+                <p class="text-sm text-gray-500 dark:text-gray-400 py-6 text-center">{"No metric chosen; remove the widget and add it again"}</p>
+            },
+        };
+
+        let control_classes = "px-2 py-1 rounded text-gray-600 dark:text-gray-300 hover:bg-gray-200 dark:hover:bg-gray-700 disabled:opacity-50 transition";
+        html! {
+            <div key={widget.id} class={classes!("bg-gray-50", "dark:bg-gray-900", "rounded-lg", "p-4", widget.size.grid_classes())}>
+                <div class="flex items-start justify-between gap-2">
+                    <div>
+                        <Link<Route>
+                            to={Route::TelemetryDevice { device_id: widget.device_id.clone() }}
+                            classes="font-semibold text-gray-800 dark:text-gray-100 hover:text-indigo-600 dark:hover:text-indigo-400"
+                        >
+                            {widget.title()}
+                        </Link<Route>>
+                        <p class="text-xs text-gray-500 dark:text-gray-400">
+                            if widget.kind.needs_metric() {
+                                {format!("{} · {}", widget.kind.label(), widget.range.label())}
+                            } else {
+                                {widget.kind.label()}
+                            }
+                        </p>
+                    </div>
+                    <div class="flex items-center gap-1 text-sm">
+                        <button type="button" onclick={on_earlier} disabled={index == 0} title="Move earlier" class={control_classes}>{"←"}</button>
+                        <button type="button" onclick={on_later} disabled={last} title="Move later" class={control_classes}>{"→"}</button>
+                        <button type="button" onclick={on_resize} title="Toggle width" class={control_classes}>{"⇔"}</button>
+                        <button type="button" onclick={on_remove} title="Remove widget" class={control_classes}>{"✕"}</button>
+                    </div>
+                </div>
+                { body }
+            </div>
+        }
+    };
+
+    html! {
+        <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8">
+            <div class="flex items-center justify-between mb-6">
+                <div>
+                    <h2 class="text-3xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Dashboard"}</h2>
+                    <p class="text-gray-600 dark:text-gray-300">{"Your own selection of charts, statistics and device statuses"}</p>
+                </div>
+                <button
+                    type="button"
+                    onclick={on_refresh}
+                    class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                >
+                    {"Refresh"}
+                </button>
+            </div>
+
+            <form onsubmit={on_add} class="bg-gray-50 dark:bg-gray-900 p-4 rounded-lg mb-6 flex flex-wrap items-end gap-3">
+                <div>
+                    <label for="widget-kind" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Widget"}</label>
+                    <select
+                        id="widget-kind"
+                        onchange={on_kind_change}
+                        class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                    >
+                        {
+                            WidgetKind::ALL.iter().map(|option| html! {
+                                <option value={option.key()} selected={*option == *kind}>{option.label()}</option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                </div>
+                <div>
+                    <label for="widget-device" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Device ID"}</label>
+                    <input
+                        type="text"
+                        id="widget-device"
+                        list="dashboard-device-ids"
+                        value={(*device_id).clone()}
+                        oninput={on_device_input}
+                        class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                        placeholder="e.g., 4321"
+                    />
+                    <datalist id="dashboard-device-ids">
+                        { known_devices.iter().map(|id| html! { <option value={id.clone()} /> }).collect::<Html>() }
+                    </datalist>
+                </div>
+                if kind.needs_metric() {
+                    <div>
+                        <label for="widget-metric" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Metric"}</label>
+                        <input
+                            type="text"
+                            id="widget-metric"
+                            list="dashboard-metrics"
+                            value={(*metric).clone()}
+                            oninput={on_metric_input}
+                            class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                            placeholder="e.g., temperature"
+                        />
+                        <datalist id="dashboard-metrics">
+                            { known_metrics.iter().map(|name| html! { <option value={name.clone()} /> }).collect::<Html>() }
+                        </datalist>
+                    </div>
+                    <div>
+                        <label for="widget-range" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Time range"}</label>
+                        <select
+                            id="widget-range"
+                            onchange={on_range_change}
+                            class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                        >
+                            {
+                                TimeRange::ALL.iter().map(|option| html! {
+                                    <option value={option.key()} selected={*option == *range}>{option.label()}</option>
+                                }).collect::<Html>()
+                            }
+                        </select>
+                    </div>
+                }
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-indigo-600 text-white font-semibold shadow hover:bg-indigo-700 transition"
+                >
+                    {"Add widget"}
+                </button>
+            </form>
+
+            if let Some(err) = error.as_ref() {
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                    <span>{"❌"}</span>
+                    <span>{format!("Error: {}", err)}</span>
+                </div>
+            }
+
+            if widgets.is_empty() {
+                <div class="text-center text-gray-500 dark:text-gray-400 py-8">{"Add a widget to start your dashboard"}</div>
+            } else {
+                <div class="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                    { widgets.iter().enumerate().map(|(index, widget)| widget_card(index, widget)).collect::<Html>() }
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,206 @@
+/// # Devices View
+///
+/// This component lists every device that has reported telemetry, so users
+/// do not have to remember device IDs. It allows users to:
+/// - Search the devices by device or gateway ID
+/// - See when each device was last seen and whether it is still reporting
+/// - Open a device's telemetry or configuration view
+/// - Refresh the list
+
+use crate::domain::device::{format_age, DeviceSummary};
+use crate::route::Route;
+use crate::services::device_service::DeviceService;
+use chrono::{DateTime, Utc};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Component listing the known devices in a searchable table.
+#[function_component(DevicesView)]
+pub fn devices_view() -> Html {
+    // State for the fetched devices
+    let devices = use_state(Vec::<DeviceSummary>::new);
+
+    // State for the search box
+    let search = use_state(String::new);
+
+    // State for tracking loading status
+    let loading = use_state(|| true);
+
+    // State for error messages
+    let error = use_state(|| None::<String>);
+
+    // Counter for triggering a refresh of the list
+    let refresh_count = use_state(|| 0);
+
+    // Navigator for opening a device's telemetry when its row is clicked
+    let navigator = use_navigator();
+
+    // Callback for handling changes in the search box
+    let on_search_change = {
+        let search = search.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            search.set(input.value());
+        })
+    };
+
+    // Callback for handling refresh button clicks
+    let on_refresh = {
+        let refresh_count = refresh_count.clone();
+        Callback::from(move |_| {
+            refresh_count.set(*refresh_count + 1);
+        })
+    };
+
+    // Effect hook for fetching the devices on load and on every refresh
+    {
+        let devices = devices.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with(*refresh_count, move |_| {
+            loading.set(true);
+            error.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::get_devices().await {
+                    Ok(data) => devices.set(data),
+                    Err(e) => error.set(Some(format!("Failed to load devices: {}", e))),
+                }
+                loading.set(false);
+            });
+
+            // Cleanup function (no-op in this case)
+            || ()
+        });
+    }
+
+    // Devices matching the search, in the API's device ID order
+    let matching: Vec<&DeviceSummary> = devices.iter().filter(|device| device.matches(&search)).collect();
+    let now = Utc::now().timestamp();
+
+    html! {
+        <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8">
+            <div class="mb-6">
+                <h2 class="text-3xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Devices"}</h2>
+                <p class="text-gray-600 dark:text-gray-300 mb-4">{"Every device that has reported telemetry"}</p>
+                <div class="flex flex-col sm:flex-row gap-2 items-end">
+                    <div class="flex-1">
+                        <label for="device-search" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Search"}</label>
+                        <input
+                            type="search"
+                            id="device-search"
+                            value={(*search).clone()}
+                            oninput={on_search_change}
+                            class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
+                            placeholder="Filter by device or gateway ID"
+                            autofocus=true
+                        />
+                    </div>
+                    <button
+                        type="button"
+                        onclick={on_refresh}
+                        class="mt-2 sm:mt-0 px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                        disabled={*loading}
+                    >
+                        { if *loading { html! { <span class="animate-spin mr-2">{"⏳"}</span> } } else { html!{} } }
+                        {"Refresh"}
+                    </button>
+                </div>
+            </div>
+
+            if let Some(err) = error.as_ref() {
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                    <span>{"❌"}</span>
+                    <span>{format!("Error: {}", err)}</span>
+                </div>
+            }
+
+            if *loading && devices.is_empty() {
+                <div class="flex justify-center items-center h-32">
+                    <div class="text-gray-500 dark:text-gray-400 animate-pulse">{"Loading devices..."}</div>
+                </div>
+            } else if matching.is_empty() {
+                <div class="text-center text-gray-500 dark:text-gray-400 py-8">
+                    { if devices.is_empty() { "No devices have reported telemetry yet" } else { "No devices match the search" } }
+                </div>
+            } else {
+                <div class="overflow-x-auto">
+                    <table class="min-w-full divide-y divide-gray-200 dark:divide-gray-700">
+                        <thead class="bg-gray-50 dark:bg-gray-900">
+                            <tr>
+                                <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wide">{"Device ID"}</th>
+                                <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wide">{"Last seen"}</th>
+                                <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wide">{"Status"}</th>
+                                <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wide">{"Gateway"}</th>
+                                <th class="px-4 py-2"></th>
+                            </tr>
+                        </thead>
+                        <tbody class="divide-y divide-gray-200 dark:divide-gray-700">
+                            {
+                                matching.into_iter().map(|device| {
+                                    // Clicking a row opens the device's telemetry
+                                    let on_row_click = {
+                                        let navigator = navigator.clone();
+                                        let device_id = device.device_id.clone();
+                                        Callback::from(move |_| {
+                                            if let Some(navigator) = &navigator {
+                                                navigator.push(&Route::TelemetryDevice { device_id: device_id.clone() });
+                                            }
+                                        })
+                                    };
+                                    // Links stop the click so the row does not navigate as well
+                                    let stop_propagation = Callback::from(|e: MouseEvent| e.stop_propagation());
+
+                                    html! {
+                                        <tr key={device.device_id.clone()} onclick={on_row_click} class="hover:bg-gray-50 dark:hover:bg-gray-700 cursor-pointer">
+                                            <td class="px-4 py-2 font-medium text-gray-900 dark:text-gray-100">{&device.device_id}</td>
+                                            <td class="px-4 py-2 text-sm text-gray-600 dark:text-gray-300">{format_last_seen(device.last_seen, now)}</td>
+                                            <td class="px-4 py-2">
+                                                <span class={classes!("px-2", "py-1", "rounded", "text-xs", "font-semibold", device.status.badge_classes())}>
+                                                    {device.status.label()}
+                                                </span>
+                                            </td>
+                                            <td class="px-4 py-2 text-sm text-gray-600 dark:text-gray-300">{device.gateway_id.clone().unwrap_or_default()}</td>
+                                            <td class="px-4 py-2 text-sm text-right whitespace-nowrap" onclick={stop_propagation}>
+                                                <Link<Route>
+                                                    to={Route::TelemetryDevice { device_id: device.device_id.clone() }}
+                                                    classes="text-blue-600 dark:text-blue-400 hover:text-blue-800 mr-4"
+                                                >
+                                                    {"Telemetry"}
+                                                </Link<Route>>
+                                                <Link<Route>
+                                                    to={Route::ConfigDevice { device_id: device.device_id.clone() }}
+                                                    classes="text-green-600 hover:text-green-800"
+                                                >
+                                                    {"Configure"}
+                                                </Link<Route>>
+                                            </td>
+                                        </tr>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </tbody>
+                    </table>
+                </div>
+            }
+        </div>
+    }
+}
+
+/// Formats when a device was last seen, with how long ago that was.
+///
+/// # Parameters
+/// * `last_seen` - Unix timestamp of the device's latest record
+/// * `now` - The current Unix timestamp
+///
+/// # Returns
+/// * Date in "YYYY-MM-DD HH:MM:SS UTC" format followed by the age, e.g.
+///   "2022-01-01 00:01:00 UTC (5 min ago)"
+fn format_last_seen(last_seen: i64, now: i64) -> String {
+    let date = DateTime::from_timestamp(last_seen, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| last_seen.to_string());
+
+    format!("{} ({})", date, format_age(now - last_seen))
+}
@@ -0,0 +1,315 @@
+/// # Fleet View
+///
+/// This component gives an overview of the whole fleet, where the other
+/// views show one device at a time. It allows users to:
+/// - See how many devices are online, stale and offline, the fleet health
+///   score and how many alerts were raised in the last 24 hours
+/// - See which devices raised alerts recently
+/// - Scan a grid of device tiles, devices needing attention first, each
+///   with its status, health score and a sparkline of a chosen metric over
+///   the last hour
+/// - Open a device's telemetry view from its tile
+/// - Refresh the overview, and see when it was fetched, with a warning when
+///   a refresh fails and the previous overview is still shown
+///
+/// The counts and tiles come from the fleet health endpoint and the metric
+/// choices from the fleet summary; the sparklines are read in batches.
+
+use crate::components::{Sparkline, StaleBanner};
+use crate::domain::device::{format_age, DeviceStatus};
+use crate::domain::fleet::{default_sparkline_metric, sparkline_points, FleetHealth, FleetSummary};
+use crate::domain::telemetry::Telemetry;
+use crate::route::Route;
+use crate::services::device_service::DeviceService;
+use chrono::Utc;
+use futures::future;
+use std::collections::HashMap;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Time span covered by the sparklines, in seconds
+const SPARKLINE_WINDOW_SECS: i64 = 3600;
+
+/// Most readings plotted per sparkline
+const SPARKLINE_POINTS: usize = 60;
+
+/// Number of devices listed in the recent alerts card
+const ALERT_LIST_LENGTH: usize = 5;
+
+/// Component showing fleet summary cards and a grid of device tiles.
+#[function_component(FleetView)]
+pub fn fleet_view() -> Html {
+    // State for the fetched fleet summary and health scores
+    let summary = use_state(|| None::<FleetSummary>);
+    let health = use_state(|| None::<FleetHealth>);
+
+    // State for the metric plotted in the sparklines
+    let metric = use_state(|| None::<String>);
+
+    // State for the sparkline readings, by device ID
+    let sparklines = use_state(HashMap::<String, Vec<Telemetry>>::new);
+
+    // State for tracking loading status
+    let loading = use_state(|| true);
+
+    // State for error messages
+    let error = use_state(|| None::<String>);
+
+    // State for when the health scores were last fetched, and whether the
+    // last fetch failed, for the stale-data banner
+    let fetched_at = use_state(|| None::<i64>);
+    let fetch_failed = use_state(|| false);
+
+    // Counter for triggering a refresh of the overview
+    let refresh_count = use_state(|| 0);
+
+    // Callback for handling refresh button clicks
+    let on_refresh = {
+        let refresh_count = refresh_count.clone();
+        Callback::from(move |_| {
+            refresh_count.set(*refresh_count + 1);
+        })
+    };
+
+    // Callback for handling changes of the sparkline metric
+    let on_metric_change = {
+        let metric = metric.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            metric.set(Some(select.value()));
+        })
+    };
+
+    // Effect hook for fetching the summary and health scores on load and on every refresh
+    {
+        let summary = summary.clone();
+        let health = health.clone();
+        let metric = metric.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        let fetched_at = fetched_at.clone();
+        let fetch_failed = fetch_failed.clone();
+
+        use_effect_with(*refresh_count, move |_| {
+            loading.set(true);
+            error.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                let (fetched_summary, fetched_health) =
+                    future::join(DeviceService::get_fleet_summary(), DeviceService::get_fleet_health()).await;
+
+                match fetched_summary {
+                    Ok(data) => {
+                        // Keep the chosen metric while the fleet still reports it
+                        let reported = metric
+                            .as_ref()
+                            .is_some_and(|chosen| data.metrics.iter().any(|summary| &summary.metric == chosen));
+                        if !reported {
+                            metric.set(default_sparkline_metric(&data));
+                        }
+                        summary.set(Some(data));
+                    }
+                    Err(e) => error.set(Some(format!("Failed to load fleet summary: {}", e))),
+                }
+                match fetched_health {
+                    Ok(data) => {
+                        health.set(Some(data));
+                        fetched_at.set(Some(Utc::now().timestamp()));
+                        fetch_failed.set(false);
+                    }
+                    Err(e) => {
+                        error.set(Some(format!("Failed to load fleet health: {}", e)));
+                        fetch_failed.set(true);
+                    }
+                }
+                loading.set(false);
+            });
+
+            // Cleanup function (no-op in this case)
+            || ()
+        });
+    }
+
+    // Devices shown as tiles, needing attention first
+    let device_ids: Vec<String> = health
+        .as_ref()
+        .map(|health| health.scores.iter().map(|device| device.device_id.clone()).collect())
+        .unwrap_or_default();
+
+    // Effect hook for fetching the sparkline readings whenever the devices or
+    // the metric change, and on every refresh
+    {
+        let sparklines = sparklines.clone();
+        let error = error.clone();
+
+        use_effect_with((device_ids.clone(), (*metric).clone(), *refresh_count), move |(device_ids, metric, _)| {
+            match metric.clone().filter(|_| !device_ids.is_empty()) {
+                Some(metric) => {
+                    let device_ids = device_ids.clone();
+                    let from = Utc::now().timestamp() - SPARKLINE_WINDOW_SECS;
+                    wasm_bindgen_futures::spawn_local(async move {
+                        match DeviceService::read_batch(&device_ids, &metric, from, SPARKLINE_POINTS).await {
+                            Ok(records) => sparklines.set(records),
+                            Err(e) => error.set(Some(format!("Failed to load sparklines: {}", e))),
+                        }
+                    });
+                }
+                None => sparklines.set(HashMap::new()),
+            }
+
+            // Cleanup function (no-op in this case)
+            || ()
+        });
+    }
+
+    let now = Utc::now().timestamp();
+
+    html! {
+        <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8">
+            <div class="mb-6 flex flex-col sm:flex-row gap-2 sm:items-end justify-between">
+                <div>
+                    <h2 class="text-3xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Fleet"}</h2>
+                    <p class="text-gray-600 dark:text-gray-300">{"Every device at a glance, devices needing attention first"}</p>
+                </div>
+                <button
+                    type="button"
+                    onclick={on_refresh}
+                    class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                    disabled={*loading}
+                >
+                    { if *loading { html! { <span class="animate-spin mr-2">{"⏳"}</span> } } else { html!{} } }
+                    {"Refresh"}
+                </button>
+            </div>
+
+            if let Some(err) = error.as_ref() {
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                    <span>{"❌"}</span>
+                    <span>{format!("Error: {}", err)}</span>
+                </div>
+            }
+
+            if let Some(health) = health.as_ref() {
+                <StaleBanner fetched_at={*fetched_at} failed={*fetch_failed} />
+                <div class="grid grid-cols-2 md:grid-cols-3 lg:grid-cols-6 gap-4 mb-6">
+                    { summary_card("Devices", health.devices.to_string(), "text-gray-800 dark:text-gray-100") }
+                    { summary_card("Online", health.count(DeviceStatus::Online).to_string(), "text-green-700 dark:text-green-300") }
+                    { summary_card("Stale", health.count(DeviceStatus::Stale).to_string(), "text-yellow-700 dark:text-yellow-300") }
+                    { summary_card("Offline", health.count(DeviceStatus::Offline).to_string(), "text-red-700 dark:text-red-300") }
+                    { summary_card("Health score", health.score.map_or("–".to_string(), |score| score.to_string()), "text-blue-700 dark:text-blue-300") }
+                    { summary_card(&format!("Alerts ({} h)", health.breach_window_hours), health.alerts().to_string(), "text-orange-700 dark:text-orange-300") }
+                </div>
+
+                <div class="bg-gray-50 dark:bg-gray-900 p-4 rounded-lg mb-6">
+                    <h3 class="text-lg font-semibold text-gray-800 dark:text-gray-100 mb-2">{"Recent alerts"}</h3>
+                    if health.alerting_devices().is_empty() {
+                        <p class="text-sm text-gray-500 dark:text-gray-400">
+                            {format!("No alerts raised in the last {} hours", health.breach_window_hours)}
+                        </p>
+                    } else {
+                        <ul class="text-sm space-y-1">
+                            {
+                                health.alerting_devices().into_iter().take(ALERT_LIST_LENGTH).map(|device| html! {
+                                    <li class="flex justify-between">
+                                        <Link<Route>
+                                            to={Route::TelemetryDevice { device_id: device.device_id.clone() }}
+                                            classes="text-blue-600 dark:text-blue-400 hover:text-blue-800"
+                                        >
+                                            {&device.device_id}
+                                        </Link<Route>>
+                                        <span class="text-orange-700 dark:text-orange-300">
+                                            {format!("{} alert{}", device.breaches, if device.breaches == 1 { "" } else { "s" })}
+                                        </span>
+                                    </li>
+                                }).collect::<Html>()
+                            }
+                        </ul>
+                    }
+                </div>
+            }
+
+            if let Some(summary) = summary.as_ref().filter(|summary| !summary.metrics.is_empty()) {
+                <div class="mb-4 flex items-center gap-2">
+                    <label for="sparkline-metric" class="text-sm font-medium text-gray-700 dark:text-gray-200">{"Sparkline metric"}</label>
+                    <select
+                        id="sparkline-metric"
+                        onchange={on_metric_change}
+                        class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                    >
+                        {
+                            summary.metrics.iter().map(|summary| html! {
+                                <option
+                                    value={summary.metric.clone()}
+                                    selected={metric.as_deref() == Some(summary.metric.as_str())}
+                                >
+                                    {&summary.metric}
+                                </option>
+                            }).collect::<Html>()
+                        }
+                    </select>
+                    <span class="text-sm text-gray-500 dark:text-gray-400">{"over the last hour"}</span>
+                </div>
+            }
+
+            if *loading && health.is_none() {
+                <div class="flex justify-center items-center h-32">
+                    <div class="text-gray-500 dark:text-gray-400 animate-pulse">{"Loading fleet..."}</div>
+                </div>
+            } else if device_ids.is_empty() {
+                <div class="text-center text-gray-500 dark:text-gray-400 py-8">{"No devices have reported telemetry yet"}</div>
+            } else {
+                <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 gap-4">
+                    {
+                        health.as_ref().map(|health| health.scores.iter().map(|device| {
+                            let points = match (metric.as_deref(), sparklines.get(&device.device_id)) {
+                                (Some(metric), Some(records)) => sparkline_points(records, metric),
+                                _ => Vec::new(),
+                            };
+                            let latest = points.last().map(|(_, value)| format!("{:.1}", value));
+
+                            html! {
+                                <Link<Route>
+                                    to={Route::TelemetryDevice { device_id: device.device_id.clone() }}
+                                    classes="block border border-gray-200 dark:border-gray-700 rounded-lg p-4 hover:shadow-md transition"
+                                >
+                                    <div class="flex items-center justify-between mb-1">
+                                        <span class="font-semibold text-gray-900 dark:text-gray-100 truncate">{&device.device_id}</span>
+                                        <span class={classes!("px-2", "py-1", "rounded", "text-xs", "font-semibold", device.status.badge_classes())}>
+                                            {device.status.label()}
+                                        </span>
+                                    </div>
+                                    <div class="flex justify-between text-xs text-gray-500 dark:text-gray-400 mb-2">
+                                        <span>{format!("Health {}", device.score)}</span>
+                                        <span>{format!("Seen {}", format_age(now - device.last_seen))}</span>
+                                    </div>
+                                    <Sparkline points={points} />
+                                    if let Some(latest) = latest {
+                                        <div class="text-right text-sm text-gray-700 dark:text-gray-200 mt-1">{latest}</div>
+                                    }
+                                </Link<Route>>
+                            }
+                        }).collect::<Html>()).unwrap_or_default()
+                    }
+                </div>
+            }
+        </div>
+    }
+}
+
+/// Renders one of the fleet summary cards.
+///
+/// # Parameters
+/// * `label` - What the card counts
+/// * `value` - The count or score shown
+/// * `value_classes` - Tailwind classes colouring the value
+///
+/// # Returns
+/// * The card's HTML
+fn summary_card(label: &str, value: String, value_classes: &'static str) -> Html {
+    html! {
+        <div class="bg-gray-50 dark:bg-gray-900 rounded-lg p-4 text-center">
+            <div class={classes!("text-3xl", "font-bold", value_classes)}>{value}</div>
+            <div class="text-sm text-gray-600 dark:text-gray-300">{label}</div>
+        </div>
+    }
+}
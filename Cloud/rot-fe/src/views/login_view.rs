@@ -0,0 +1,152 @@
+/// # Login Views
+///
+/// These components sign users in with Azure AD when authentication is
+/// enabled:
+/// - `LoginView` is shown instead of any view while nobody is signed in,
+///   and starts the sign-in, returning to the requested page afterwards
+/// - `AuthCallbackView` handles the redirect back from Azure AD
+///   (`/auth/callback`), redeeming the code for tokens
+///
+/// The sign-in flow itself lives in `AuthService`.
+
+use crate::route::Route;
+use crate::services::auth_service::AuthService;
+use serde::Deserialize;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Query parameters Azure AD redirects back with.
+#[derive(Debug, Default, Deserialize)]
+struct CallbackQuery {
+    /// Authorization code to redeem, on success
+    #[serde(default)]
+    code: Option<String>,
+    /// State sent with the login
+    #[serde(default)]
+    state: Option<String>,
+    /// Error code, if the sign-in failed or was cancelled
+    #[serde(default)]
+    error: Option<String>,
+    /// Explanation of the error
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Component asking the user to sign in before showing the application.
+#[function_component(LoginView)]
+pub fn login_view() -> Html {
+    // The page to return to once signed in
+    let location = use_location();
+    let return_to = location.map_or_else(|| "/".to_string(), |location| location.path().to_string());
+
+    // State for error messages
+    let error = use_state(|| None::<String>);
+
+    // Callback for the sign-in button, redirecting to Azure AD
+    let on_sign_in = {
+        let error = error.clone();
+        Callback::from(move |_| {
+            if let Err(e) = AuthService::login(&return_to) {
+                error.set(Some(e));
+            }
+        })
+    };
+
+    html! {
+        <SignInCard error={(*error).clone()} on_sign_in={on_sign_in} />
+    }
+}
+
+/// Component finishing the sign-in when Azure AD redirects back.
+#[function_component(AuthCallbackView)]
+pub fn auth_callback_view() -> Html {
+    let navigator = use_navigator();
+    let query = use_location()
+        .and_then(|location| location.query::<CallbackQuery>().ok())
+        .unwrap_or_default();
+
+    // State for error messages; the sign-in is in progress while there are none
+    let error = use_state(|| None::<String>);
+
+    // Effect hook redeeming the code once, when the page loads
+    {
+        let error = error.clone();
+        use_effect_with((), move |_| {
+            match (query.code, query.state, query.error) {
+                (_, _, Some(code)) => {
+                    error.set(Some(query.error_description.unwrap_or(code)));
+                }
+                (Some(code), Some(state), None) => {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        match AuthService::complete_login(&code, &state).await {
+                            Ok(return_to) => {
+                                // Replace the callback URL, so back does not redeem the code again
+                                if let Some(navigator) = &navigator {
+                                    navigator.replace(&Route::recognize(&return_to).unwrap_or(Route::Home));
+                                }
+                            }
+                            Err(e) => error.set(Some(e)),
+                        }
+                    });
+                }
+                _ => error.set(Some("The sign-in response is incomplete".to_string())),
+            }
+
+            // Cleanup function (no-op in this case)
+            || ()
+        });
+    }
+
+    // Callback for retrying after a failed sign-in
+    let on_sign_in = {
+        let error = error.clone();
+        Callback::from(move |_| {
+            if let Err(e) = AuthService::login("/") {
+                error.set(Some(e));
+            }
+        })
+    };
+
+    match error.as_ref() {
+        Some(err) => html! { <SignInCard error={Some(err.clone())} on_sign_in={on_sign_in} /> },
+        None => html! {
+            <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8 text-center">
+                <div class="text-gray-500 dark:text-gray-400 animate-pulse">{"Signing in..."}</div>
+            </div>
+        },
+    }
+}
+
+/// Properties for the SignInCard component
+/// - error: Why the last sign-in failed, if it did
+/// - on_sign_in: Callback starting the sign-in
+#[derive(Properties, PartialEq)]
+struct SignInCardProps {
+    #[prop_or_default]
+    error: Option<String>,
+    on_sign_in: Callback<MouseEvent>,
+}
+
+/// SignInCard component explaining that signing in is required
+#[function_component(SignInCard)]
+fn sign_in_card(props: &SignInCardProps) -> Html {
+    html! {
+        <div class="w-full max-w-md mx-auto bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8 text-center">
+            <h2 class="text-2xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Sign in"}</h2>
+            <p class="text-gray-600 dark:text-gray-300 mb-6">{"Sign in with your organization account to monitor and configure devices"}</p>
+            if let Some(err) = props.error.as_ref() {
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 text-left animate-fade-in">
+                    <span>{"❌"}</span>
+                    <span>{format!("Error: {}", err)}</span>
+                </div>
+            }
+            <button
+                type="button"
+                onclick={props.on_sign_in.clone()}
+                class="px-6 py-2 rounded bg-blue-600 text-white font-semibold shadow hover:bg-blue-700 transition"
+            >
+                {"Sign in with Microsoft"}
+            </button>
+        </div>
+    }
+}
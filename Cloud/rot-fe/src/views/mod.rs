@@ -1,5 +1,17 @@
 pub mod telemetry_view;
 pub mod config_view;
+pub mod devices_view;
+pub mod fleet_view;
+pub mod alerts_view;
+pub mod login_view;
+pub mod settings_view;
+pub mod dashboard_view;
 
 pub use telemetry_view::TelemetryView;
-pub use config_view::ConfigView;
\ No newline at end of file
+pub use config_view::ConfigView;
+pub use devices_view::DevicesView;
+pub use fleet_view::FleetView;
+pub use alerts_view::AlertsView;
+pub use login_view::{AuthCallbackView, LoginView};
+pub use settings_view::SettingsView;
+pub use dashboard_view::DashboardView;
\ No newline at end of file
@@ -0,0 +1,387 @@
+/// # Settings View
+///
+/// This component lets users point the application at other backend APIs
+/// without rebuilding it, and choose the units telemetry is shown in and
+/// how charts are drawn.
+/// It allows users to:
+/// - See the base URLs of the device monitor and device config APIs in use
+/// - Override either URL, e.g. to try a staging API from a production build
+/// - Reset to the URLs set by the deployment
+/// - Show temperatures in °C or °F, and scale other metrics linearly
+/// - Draw telemetry charts with ApexCharts or natively as SVG
+///
+/// Overrides are kept in the browser's local storage and apply to every
+/// request sent after saving; views already open refetch on their next load.
+/// Units apply immediately; the chart renderer applies to charts opened after
+/// choosing it.
+
+use crate::components::use_units;
+use crate::domain::plot::ChartRenderer;
+use crate::domain::settings::{parse_base_url, ApiSettingsOverride};
+use crate::domain::units::{MetricScale, TemperatureUnit, UnitPreferences};
+use crate::services::preferences_service::PreferencesService;
+use crate::services::settings_service::SettingsService;
+use yew::prelude::*;
+
+/// Component for overriding the backend API URLs, choosing units and the chart renderer.
+#[function_component(SettingsView)]
+pub fn settings_view() -> Html {
+    // State for the URL input fields, prefilled with the user's overrides
+    let api_url = use_state(|| SettingsService::overrides().api_url.unwrap_or_default());
+    let dc_url = use_state(|| SettingsService::overrides().dc_url.unwrap_or_default());
+
+    // State for the URLs in use, refreshed after saving or resetting
+    let effective = use_state(SettingsService::effective);
+
+    // State for error and success messages
+    let error = use_state(|| None::<String>);
+    let success_message = use_state(|| None::<String>);
+
+    let deployed = SettingsService::deployed();
+
+    // Units telemetry is shown in and the callback changing them
+    let units = use_units();
+
+    // State for the scaling form fields
+    let scale_metric = use_state(String::new);
+    let scale_factor = use_state(String::new);
+    let scale_offset = use_state(String::new);
+    let scale_unit = use_state(String::new);
+    let scale_error = use_state(|| None::<String>);
+
+    // State for the chart renderer, saved as soon as it is chosen
+    let renderer = use_state(|| PreferencesService::load_chart_renderer().unwrap_or_default());
+
+    // Callback for typing the device monitor API URL
+    let on_api_url_input = {
+        let api_url = api_url.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            api_url.set(input.value());
+        })
+    };
+
+    // Callback for typing the device config API URL
+    let on_dc_url_input = {
+        let dc_url = dc_url.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            dc_url.set(input.value());
+        })
+    };
+
+    // Callback for saving the URLs
+    // Both URLs are checked before either is saved
+    let on_save = {
+        let api_url = api_url.clone();
+        let dc_url = dc_url.clone();
+        let effective = effective.clone();
+        let error = error.clone();
+        let success_message = success_message.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            success_message.set(None);
+
+            let overrides = parse_base_url(&api_url).and_then(|api_url| {
+                parse_base_url(&dc_url).map(|dc_url| ApiSettingsOverride { api_url, dc_url })
+            });
+            match overrides.and_then(|overrides| {
+                SettingsService::set_overrides(overrides.clone()).map(|_| overrides)
+            }) {
+                Ok(overrides) => {
+                    api_url.set(overrides.api_url.unwrap_or_default());
+                    dc_url.set(overrides.dc_url.unwrap_or_default());
+                    effective.set(SettingsService::effective());
+                    error.set(None);
+                    success_message.set(Some("Settings saved".to_string()));
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        })
+    };
+
+    // Callback for the "Reset to deployment defaults" button
+    let on_reset = {
+        let api_url = api_url.clone();
+        let dc_url = dc_url.clone();
+        let effective = effective.clone();
+        let error = error.clone();
+        let success_message = success_message.clone();
+        Callback::from(move |_: MouseEvent| {
+            match SettingsService::set_overrides(ApiSettingsOverride::default()) {
+                Ok(()) => {
+                    api_url.set(String::new());
+                    dc_url.set(String::new());
+                    effective.set(SettingsService::effective());
+                    error.set(None);
+                    success_message.set(Some("Reset to the deployment's URLs".to_string()));
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        })
+    };
+
+    // Callback for choosing the temperature unit
+    let on_temperature_change = {
+        let current = units.units.clone();
+        let set = units.set.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Some(temperature) = TemperatureUnit::from_key(&input.value()) {
+                set.emit(UnitPreferences { temperature, ..current.clone() });
+            }
+        })
+    };
+
+    // Callback for choosing the chart renderer
+    let on_renderer_change = {
+        let renderer = renderer.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if let Some(chosen) = ChartRenderer::from_key(&input.value()) {
+                if let Err(e) = PreferencesService::save_chart_renderer(chosen) {
+                    web_sys::console::log_1(&format!("Failed to save the chart renderer: {}", e).into());
+                }
+                renderer.set(chosen);
+            }
+        })
+    };
+
+    // Callback for typing a field of the scaling form
+    let on_scale_input = |field: &UseStateHandle<String>| {
+        let field = field.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            field.set(input.value());
+        })
+    };
+
+    // Callback for the "Add scaling" button
+    // Applies the units with the new scaling and clears the form
+    let on_add_scale = {
+        let current = units.units.clone();
+        let set = units.set.clone();
+        let scale_metric = scale_metric.clone();
+        let scale_factor = scale_factor.clone();
+        let scale_offset = scale_offset.clone();
+        let scale_unit = scale_unit.clone();
+        let scale_error = scale_error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            match MetricScale::parse(&current.scales, &scale_metric, &scale_factor, &scale_offset, &scale_unit) {
+                Ok(scale) => {
+                    let mut updated = current.clone();
+                    updated.scales.push(scale);
+                    set.emit(updated);
+                    scale_metric.set(String::new());
+                    scale_factor.set(String::new());
+                    scale_offset.set(String::new());
+                    scale_unit.set(String::new());
+                    scale_error.set(None);
+                }
+                Err(e) => scale_error.set(Some(e)),
+            }
+        })
+    };
+
+    html! {
+        <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8">
+            <div class="mb-6">
+                <h2 class="text-3xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Settings"}</h2>
+                <p class="text-gray-600 dark:text-gray-300">{"Backend APIs this browser talks to; leave a field blank to use the deployment's URL"}</p>
+            </div>
+
+            if let Some(err) = error.as_ref() {
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                    <span>{"❌"}</span>
+                    <span>{format!("Error: {}", err)}</span>
+                </div>
+            }
+
+            if let Some(success) = success_message.as_ref() {
+                <div class="bg-green-50 dark:bg-green-950 border border-green-200 dark:border-green-800 text-green-700 dark:text-green-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                    <span>{"✅"}</span>
+                    <span>{success}</span>
+                </div>
+            }
+
+            <form onsubmit={on_save} class="space-y-4 mb-6">
+                <div>
+                    <label for="api-url" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Device monitor API URL"}</label>
+                    <input
+                        type="url"
+                        id="api-url"
+                        value={(*api_url).clone()}
+                        oninput={on_api_url_input}
+                        class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
+                        placeholder={deployed.api_url.clone()}
+                    />
+                </div>
+                <div>
+                    <label for="dc-url" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Device config API URL"}</label>
+                    <input
+                        type="url"
+                        id="dc-url"
+                        value={(*dc_url).clone()}
+                        oninput={on_dc_url_input}
+                        class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
+                        placeholder={deployed.dc_url.clone()}
+                    />
+                </div>
+                <div class="flex gap-2">
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-indigo-600 text-white font-semibold shadow hover:bg-indigo-700 transition"
+                    >
+                        {"Save"}
+                    </button>
+                    <button
+                        type="button"
+                        onclick={on_reset}
+                        class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                    >
+                        {"Reset to deployment defaults"}
+                    </button>
+                </div>
+            </form>
+
+            <div class="bg-gray-50 dark:bg-gray-900 p-6 rounded-lg">
+                <h3 class="text-lg font-semibold text-gray-800 dark:text-gray-100 mb-4">{"In use"}</h3>
+                <dl class="grid grid-cols-1 sm:grid-cols-[auto_1fr] gap-x-6 gap-y-2 text-sm">
+                    <dt class="text-gray-600 dark:text-gray-300">{"Device monitor API"}</dt>
+                    <dd class="font-mono text-gray-900 dark:text-gray-100 break-all">{effective.api_url.clone()}</dd>
+                    <dt class="text-gray-600 dark:text-gray-300">{"Device config API"}</dt>
+                    <dd class="font-mono text-gray-900 dark:text-gray-100 break-all">{effective.dc_url.clone()}</dd>
+                </dl>
+            </div>
+
+            <div class="mt-8">
+                <h3 class="text-xl font-semibold text-gray-800 dark:text-gray-100 mb-2">{"Units"}</h3>
+                <p class="text-sm text-gray-600 dark:text-gray-300 mb-4">
+                    {"Applied to stat cards, tables, tooltips and chart axes in this browser; devices, thresholds and alert rules keep the reported units"}
+                </p>
+                <fieldset class="mb-6">
+                    <legend class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Temperature"}</legend>
+                    <div class="flex gap-6">
+                        {
+                            TemperatureUnit::ALL.iter().map(|unit| html! {
+                                <label class="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-200">
+                                    <input
+                                        type="radio"
+                                        name="temperature-unit"
+                                        value={unit.key()}
+                                        checked={*unit == units.units.temperature}
+                                        onchange={on_temperature_change.clone()}
+                                    />
+                                    {unit.symbol()}
+                                </label>
+                            }).collect::<Html>()
+                        }
+                    </div>
+                </fieldset>
+                <span class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Scaled metrics"}</span>
+                <div class="flex flex-wrap items-center gap-2 mb-2">
+                    {
+                        units.units.scales.iter().enumerate().map(|(index, scale)| {
+                            let on_remove = {
+                                let current = units.units.clone();
+                                units.set.reform(move |_: MouseEvent| {
+                                    let mut updated = current.clone();
+                                    updated.scales.remove(index);
+                                    updated
+                                })
+                            };
+                            html! {
+                                <span class="flex items-center gap-1 px-2 py-1 rounded-full text-xs bg-indigo-100 dark:bg-indigo-900 text-indigo-800 dark:text-indigo-200">
+                                    {scale.describe()}
+                                    <button
+                                        type="button"
+                                        onclick={on_remove}
+                                        title="Remove scaling"
+                                        class="hover:text-indigo-600 dark:hover:text-indigo-400"
+                                    >
+                                        {"✕"}
+                                    </button>
+                                </span>
+                            }
+                        }).collect::<Html>()
+                    }
+                    if units.units.scales.is_empty() {
+                        <span class="text-sm text-gray-500 dark:text-gray-400">{"None; other metrics are shown as reported"}</span>
+                    }
+                </div>
+                <form onsubmit={on_add_scale} class="flex flex-wrap items-center gap-2">
+                    <input
+                        type="text"
+                        aria-label="Metric to scale"
+                        value={(*scale_metric).clone()}
+                        oninput={on_scale_input(&scale_metric)}
+                        class="w-36 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-2 py-1"
+                        placeholder="Metric, e.g. pressure"
+                    />
+                    <span class="text-sm text-gray-600 dark:text-gray-300">{"×"}</span>
+                    <input
+                        type="number"
+                        step="any"
+                        aria-label="Scaling factor"
+                        value={(*scale_factor).clone()}
+                        oninput={on_scale_input(&scale_factor)}
+                        class="w-28 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-2 py-1"
+                        placeholder="Factor"
+                    />
+                    <span class="text-sm text-gray-600 dark:text-gray-300">{"+"}</span>
+                    <input
+                        type="number"
+                        step="any"
+                        aria-label="Scaling offset"
+                        value={(*scale_offset).clone()}
+                        oninput={on_scale_input(&scale_offset)}
+                        class="w-24 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-2 py-1"
+                        placeholder="Offset"
+                    />
+                    <input
+                        type="text"
+                        aria-label="Scaled unit"
+                        value={(*scale_unit).clone()}
+                        oninput={on_scale_input(&scale_unit)}
+                        class="w-24 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-2 py-1"
+                        placeholder="Unit"
+                    />
+                    <button
+                        type="submit"
+                        class="px-3 py-1 rounded bg-indigo-600 text-white text-sm font-semibold shadow hover:bg-indigo-700 transition"
+                    >
+                        {"Add scaling"}
+                    </button>
+                </form>
+                if let Some(err) = scale_error.as_ref() {
+                    <p class="text-sm text-red-600 dark:text-red-400 mt-1">{err}</p>
+                }
+            </div>
+
+            <div class="mt-8">
+                <h3 class="text-xl font-semibold text-gray-800 dark:text-gray-100 mb-2">{"Charts"}</h3>
+                <p class="text-sm text-gray-600 dark:text-gray-300 mb-4">
+                    {"How the telemetry and dashboard charts are drawn in this browser; the native renderer needs no JavaScript library and stays smooth on large ranges, but can't annotate or mark anomalies"}
+                </p>
+                <fieldset class="flex flex-col gap-2">
+                    <legend class="sr-only">{"Chart renderer"}</legend>
+                    {
+                        ChartRenderer::ALL.iter().map(|option| html! {
+                            <label class="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-200">
+                                <input
+                                    type="radio"
+                                    name="chart-renderer"
+                                    value={option.key()}
+                                    checked={*option == *renderer}
+                                    onchange={on_renderer_change.clone()}
+                                />
+                                {option.label()}
+                            </label>
+                        }).collect::<Html>()
+                    }
+                </fieldset>
+            </div>
+        </div>
+    }
+}
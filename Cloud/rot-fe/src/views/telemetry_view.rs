@@ -3,21 +3,85 @@
 /// This component provides a view for displaying telemetry data from devices.
 /// It allows users to:
 /// - Select a device by ID
-/// - View the latest telemetry data for the device
-/// - See charts of temperature and voltage history
-/// - Refresh the data
+/// - View the latest telemetry data for the device, and whether it is
+///   online, stale or offline
+/// - See charts of the history of any metric the device reports, with the
+///   min, max, average and latest value of each over the time range
+/// - Switch from the charts to a table of the raw records, sortable by any
+///   column and paged newest first
+/// - Choose which metrics to chart and over what time range, either a quick
+///   range (1h/24h/7d) or a custom range between two dates
+/// - Draw thresholds over the charts, set per metric or taken from the
+///   device's alert rules, with breaching readings shaded
+/// - Zoom into or pan a chart to fetch the readings of the range in view,
+///   and reset the zoom to return to the range chosen before
+/// - Save the current view as a named preset and recall it later
+/// - Download the charted metrics over the chosen time range as CSV
+/// - Refresh the data, manually or automatically at a chosen interval
+/// - See when the data was fetched, with a warning when a refresh fails or
+///   the latest reading is older than the device's reporting interval allows
+///
+/// The device shown comes from the route (`/telemetry/:device_id`), so a
+/// device's view can be bookmarked and selecting a device adds a history entry.
 
-use crate::components::ApexChart;
-use crate::domain::telemetry::Telemetry;
+use crate::components::{use_recent_devices, use_units, ApexChart, PresetPicker, RecentDevices, StaleBanner, StatCards, SvgChart, TelemetryTable, ThresholdEditor};
+use crate::domain::device::{format_age, DeviceStatusReport};
+use crate::domain::export::export_filename;
+use crate::domain::freshness::{reporting_interval, DEFAULT_REPORTING_INTERVAL_SECS};
+use crate::domain::plot::ChartRenderer;
+use crate::domain::preset::{format_datetime_local, parse_datetime_local, upsert_preset, ChartPreset, TimeRange};
+use crate::domain::telemetry::{format_timestamp, numeric_metrics, Telemetry};
+use crate::domain::threshold::Threshold;
+use crate::route::Route;
 use crate::services::device_service::DeviceService;
-use chrono::{DateTime, Utc};
+use crate::services::download_service::DownloadService;
+use crate::services::preferences_service::PreferencesService;
+use chrono::Utc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
 use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Device shown when the route does not name one
+const DEFAULT_DEVICE_ID: &str = "4321";
+
+/// Auto-refresh intervals offered, in seconds
+const AUTO_REFRESH_INTERVALS: [u32; 4] = [5, 15, 30, 60];
+
+/// Auto-refresh interval selected until the user picks another, in seconds
+const DEFAULT_AUTO_REFRESH_SECS: u32 = 15;
+
+/// Metrics charted when no preset has been applied, if the device reports them
+const DEFAULT_METRICS: [&str; 2] = ["temperature", "voltage"];
+
+/// Ways the telemetry history is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HistoryTab {
+    /// One chart per selected metric
+    Charts,
+    /// The raw records, with exact values
+    Table,
+}
+
+impl HistoryTab {
+    /// Every tab, in display order.
+    const ALL: [HistoryTab; 2] = [HistoryTab::Charts, HistoryTab::Table];
+
+    /// Label of the tab's button.
+    fn label(&self) -> &'static str {
+        match self {
+            HistoryTab::Charts => "Charts",
+            HistoryTab::Table => "Table",
+        }
+    }
+}
 
 /// Properties for the TelemetryView component.
 #[derive(Properties, PartialEq)]
 pub struct TelemetryViewProps {
-    /// ID of the device to display telemetry for
-    pub device_id: String,
+    /// ID of the device to display telemetry for, from the route
+    #[prop_or_default]
+    pub device_id: Option<String>,
 }
 
 /// Component for displaying device telemetry data.
@@ -25,16 +89,39 @@ pub struct TelemetryViewProps {
 /// This component fetches and displays the latest telemetry data
 /// for a specified device, including numeric values and charts.
 #[function_component(TelemetryView)]
-pub fn telemetry_view() -> Html {
-    // State for the currently selected device ID
-    let device_id = use_state(|| "4321".to_string());
+pub fn telemetry_view(props: &TelemetryViewProps) -> Html {
+    // The currently selected device ID, taken from the route
+    let device_id = props.device_id.clone().unwrap_or_else(|| DEFAULT_DEVICE_ID.to_string());
+
+    // Navigator for moving to another device's route
+    let navigator = use_navigator();
     
     // State for the device ID input field
-    let input_value = use_state(|| "4321".to_string());
+    let input_value = use_state(|| device_id.clone());
+
+    // Recently viewed devices; only devices opened through their route are
+    // remembered, not the default device
+    let recent_devices = use_recent_devices(props.device_id.as_deref().unwrap_or_default());
     
     // State for the fetched telemetry data
     let telemetry_data = use_state(|| None::<Telemetry>);
     
+    // State for whether the device is still reporting
+    let device_status = use_state(|| None::<DeviceStatusReport>);
+
+    // Units the latest readings are shown in
+    let units = use_units().units;
+
+    // State for when the latest reading was last fetched, when it was cached
+    // if it comes from the offline cache, and whether the last fetch failed,
+    // for the stale-data banner
+    let fetched_at = use_state(|| None::<i64>);
+    let cached_at = use_state(|| None::<i64>);
+    let fetch_failed = use_state(|| false);
+
+    // State for how often the device reports, from its configuration
+    let interval = use_state(|| DEFAULT_REPORTING_INTERVAL_SECS);
+
     // State for tracking loading status
     let loading = use_state(|| true);
     
@@ -44,6 +131,58 @@ pub fn telemetry_view() -> Html {
     // Counter for triggering data refresh
     let refresh_count = use_state(|| 0);
 
+    // State for whether the data is re-fetched periodically
+    let auto_refresh = use_state(|| false);
+
+    // State for the auto-refresh interval, in seconds
+    let auto_refresh_secs = use_state(|| DEFAULT_AUTO_REFRESH_SECS);
+
+    // Counter incremented by every auto-refresh tick
+    // Kept apart from refresh_count so polling updates the page in place
+    // instead of showing the loading state and rebuilding the charts
+    let poll_count = use_state(|| 0);
+
+    // State for the metrics the device has reported numeric values for
+    let device_metrics = use_state(Vec::<String>::new);
+
+    // State for the metrics to chart, one chart per metric
+    let metrics = use_state(|| DEFAULT_METRICS.iter().map(|m| m.to_string()).collect::<Vec<_>>());
+
+    // State for the time range shown by the charts
+    let range = use_state(TimeRange::default);
+
+    // State for the range chosen before zooming into the charts, restored
+    // when the zoom is reset; None while not zoomed
+    let zoomed_from = use_state(|| None::<TimeRange>);
+
+    // State for the library the charts are drawn with, chosen on the settings page
+    let renderer = use_state(|| PreferencesService::load_chart_renderer().unwrap_or_default());
+
+    // State for whether the history is charted or listed in a table
+    let history_tab = use_state(|| HistoryTab::Charts);
+
+    // State for tracking a CSV export in progress
+    let exporting = use_state(|| false);
+
+    // State for the saved chart presets, loaded once from preferences
+    let presets = use_state(|| {
+        PreferencesService::load_presets().unwrap_or_else(|e| {
+            web_sys::console::log_1(&format!("Failed to load presets: {}", e).into());
+            Vec::new()
+        })
+    });
+
+    // State for the thresholds the user set, loaded once from preferences
+    let user_thresholds = use_state(|| {
+        PreferencesService::load_thresholds().unwrap_or_else(|e| {
+            web_sys::console::log_1(&format!("Failed to load thresholds: {}", e).into());
+            Vec::new()
+        })
+    });
+
+    // State for the thresholds of the device's alert rules
+    let rule_thresholds = use_state(Vec::<Threshold>::new);
+
     // Callback for handling changes in the device ID input field
     let on_input_change = {
         let input_value = input_value.clone();
@@ -55,9 +194,18 @@ pub fn telemetry_view() -> Html {
         })
     };
 
+    // Keep the input field in sync when the route changes, e.g. on back navigation
+    {
+        let input_value = input_value.clone();
+        use_effect_with(device_id.clone(), move |device_id| {
+            input_value.set(device_id.clone());
+            || ()
+        });
+    }
+
     // Callback for handling form submission
     let on_submit = {
-        let device_id = device_id.clone();
+        let navigator = navigator.clone();
         let input_value = input_value.clone();
         let error = error.clone();
         Callback::from(move |e: yew::events::SubmitEvent| {
@@ -69,9 +217,11 @@ pub fn telemetry_view() -> Html {
                 // Show error if input is empty
                 error.set(Some("Please enter a device ID.".to_string()));
             } else {
-                // Update the device_id state with the input value
-                // This will trigger a data fetch via the use_effect hook
-                device_id.set((*input_value).clone());
+                // Navigate to the device's route
+                // The new device_id will trigger a data fetch via the use_effect hook
+                if let Some(navigator) = &navigator {
+                    navigator.push(&Route::TelemetryDevice { device_id: input_value.trim().to_string() });
+                }
             }
         })
     };
@@ -83,17 +233,289 @@ pub fn telemetry_view() -> Html {
         refresh_count_setter.set(*refresh_count_setter + 1);
     });
 
+    // Callback for turning auto-refresh on or off
+    let on_auto_refresh_toggle = {
+        let auto_refresh = auto_refresh.clone();
+        Callback::from(move |_| auto_refresh.set(!*auto_refresh))
+    };
+
+    // Callback for changing the auto-refresh interval
+    let on_auto_refresh_interval_change = {
+        let auto_refresh_secs = auto_refresh_secs.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(secs) = select.value().parse() {
+                auto_refresh_secs.set(secs);
+            }
+        })
+    };
+
+    // Callback for changing the chart time range
+    let on_range_change = {
+        let range = range.clone();
+        let zoomed_from = zoomed_from.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Some(selected) = TimeRange::from_key(&select.value()) {
+                range.set(selected);
+                zoomed_from.set(None);
+            }
+        })
+    };
+
+    // Callback for zooming or panning a chart
+    // The charts fetch the readings of the range in view, so zooming refines
+    // the data rather than only magnifying it
+    let on_zoom = {
+        let range = range.clone();
+        let zoomed_from = zoomed_from.clone();
+        Callback::from(move |(from, to): (i64, i64)| {
+            if zoomed_from.is_none() {
+                zoomed_from.set(Some(*range));
+            }
+            range.set(TimeRange::Custom { from: Some(from), to: Some(to) });
+        })
+    };
+
+    // Callback for resetting the zoom, back to the range chosen before zooming
+    let on_zoom_reset = {
+        let range = range.clone();
+        let zoomed_from = zoomed_from.clone();
+        Callback::from(move |_| {
+            if let Some(previous) = *zoomed_from {
+                range.set(previous);
+                zoomed_from.set(None);
+            }
+        })
+    };
+
+    // Callback for changing either end of a custom time range
+    // Editing a bound switches to a custom range keeping the other bound,
+    // and clearing a bound leaves that end of the range open
+    let on_range_bound_change = {
+        let range = range.clone();
+        let zoomed_from = zoomed_from.clone();
+        let error = error.clone();
+        Callback::from(move |(is_from, e): (bool, Event)| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let value = parse_datetime_local(&input.value());
+            let (from, to) = match *range {
+                TimeRange::Custom { from, to } => (from, to),
+                _ => (None, None),
+            };
+            let (from, to) = if is_from { (value, to) } else { (from, value) };
+
+            if from.zip(to).is_some_and(|(from, to)| from > to) {
+                error.set(Some("The start of the time range must be before its end.".to_string()));
+            } else {
+                error.set(None);
+                range.set(TimeRange::Custom { from, to });
+                zoomed_from.set(None);
+            }
+        })
+    };
+    let on_from_change = on_range_bound_change.reform(|e: Event| (true, e));
+    let on_to_change = on_range_bound_change.reform(|e: Event| (false, e));
+
+    // Bounds shown in the custom range inputs, empty for quick ranges
+    let (custom_from, custom_to) = match *range {
+        TimeRange::Custom { from, to } => (format_datetime_local(from), format_datetime_local(to)),
+        _ => (String::new(), String::new()),
+    };
+
+    // Callback for adding or removing a metric from the charts
+    let on_metric_toggle = {
+        let metrics = metrics.clone();
+        Callback::from(move |metric: String| {
+            let mut updated = (*metrics).clone();
+            if updated.contains(&metric) {
+                updated.retain(|m| *m != metric);
+            } else {
+                updated.push(metric);
+            }
+            metrics.set(updated);
+        })
+    };
+
+    // Callback for picking a recently viewed device
+    let on_recent_select = {
+        let navigator = navigator.clone();
+        Callback::from(move |device_id: String| {
+            if let Some(navigator) = &navigator {
+                navigator.push(&Route::TelemetryDevice { device_id });
+            }
+        })
+    };
+
+    // Callback for applying a saved preset
+    // Restores the device, metrics and range stored in the preset
+    let on_preset_select = {
+        let navigator = navigator.clone();
+        let metrics = metrics.clone();
+        let range = range.clone();
+        let zoomed_from = zoomed_from.clone();
+        Callback::from(move |preset: ChartPreset| {
+            if let Some(navigator) = &navigator {
+                navigator.push(&Route::TelemetryDevice { device_id: preset.device_id });
+            }
+            metrics.set(preset.metrics);
+            range.set(preset.range);
+            zoomed_from.set(None);
+        })
+    };
+
+    // Callback for saving the current view under a name
+    // A preset with the same name is overwritten
+    let on_preset_save = {
+        let presets = presets.clone();
+        let device_id = device_id.clone();
+        let metrics = metrics.clone();
+        let range = range.clone();
+        let error = error.clone();
+        Callback::from(move |name: String| {
+            let mut updated = (*presets).clone();
+            upsert_preset(&mut updated, ChartPreset {
+                name,
+                device_id: device_id.clone(),
+                metrics: (*metrics).clone(),
+                range: *range,
+            });
+
+            match PreferencesService::save_presets(&updated) {
+                Ok(()) => presets.set(updated),
+                Err(e) => error.set(Some(format!("Failed to save preset: {}", e))),
+            }
+        })
+    };
+
+    // Callback for exporting the charted metrics over the chosen range as CSV
+    // Quick ranges end now, so the file name records when the export was made
+    let on_export = {
+        let device_id = device_id.clone();
+        let metrics = metrics.clone();
+        let range = range.clone();
+        let exporting = exporting.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            let device_id = device_id.clone();
+            let metrics = (*metrics).clone();
+            let now = Utc::now().timestamp();
+            let (from, to) = range.bounds(now);
+            let filename = export_filename(&device_id, from, to.unwrap_or(now));
+            let exporting = exporting.clone();
+            let error = error.clone();
+
+            exporting.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let saved = DeviceService::export_csv(&device_id, from, to, &metrics)
+                    .await
+                    .and_then(|csv| DownloadService::save_text(&filename, "text/csv", &csv));
+                match saved {
+                    Ok(()) => {}
+                    Err(e) if e == "404" => error.set(Some("No telemetry to export for this device.".to_string())),
+                    Err(e) => error.set(Some(format!("Failed to export telemetry: {}", e))),
+                }
+                exporting.set(false);
+            });
+        })
+    };
+
+    // Callback for deleting a saved preset
+    let on_preset_delete = {
+        let presets = presets.clone();
+        let error = error.clone();
+        Callback::from(move |name: String| {
+            let mut updated = (*presets).clone();
+            updated.retain(|preset| preset.name != name);
+
+            match PreferencesService::save_presets(&updated) {
+                Ok(()) => presets.set(updated),
+                Err(e) => error.set(Some(format!("Failed to delete preset: {}", e))),
+            }
+        })
+    };
+
+    // Callback for switching between the charts and the table
+    let on_tab_select = {
+        let history_tab = history_tab.clone();
+        Callback::from(move |tab: HistoryTab| history_tab.set(tab))
+    };
+
+    // Callback for adding or removing a threshold
+    let on_thresholds_change = {
+        let user_thresholds = user_thresholds.clone();
+        let error = error.clone();
+        Callback::from(move |updated: Vec<Threshold>| {
+            match PreferencesService::save_thresholds(&updated) {
+                Ok(()) => user_thresholds.set(updated),
+                Err(e) => error.set(Some(format!("Failed to save thresholds: {}", e))),
+            }
+        })
+    };
+
+    // Effect hook for fetching the device's alert rules, drawn as thresholds
+    // A device without rules, or rules that fail to load, draws none
+    {
+        let rule_thresholds = rule_thresholds.clone();
+        use_effect_with(device_id.clone(), move |device_id| {
+            let device_id = device_id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match DeviceService::get_alert_rules(&device_id).await {
+                    Ok(rules) => rule_thresholds.set(rules.iter().map(Threshold::from_rule).collect()),
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("Failed to fetch alert rules: {}", e).into());
+                        rule_thresholds.set(Vec::new());
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    // Every threshold drawn on the charts
+    let thresholds: Vec<Threshold> = user_thresholds.iter().chain(rule_thresholds.iter()).cloned().collect();
+
+    // Metrics offered as chart options: every numeric metric the device
+    // has reported, and the selected ones
+    let available_metrics = get_available_metrics(&device_metrics, &metrics);
+
+    // Effect hook for fetching the device's reporting interval, which tells
+    // how old its latest reading may be before the data counts as stale
+    // Devices whose configuration sets none, or fails to load, get the default
+    {
+        let interval = interval.clone();
+        use_effect_with(device_id.clone(), move |device_id| {
+            let device_id = device_id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let configured = match DeviceService::get_config(&device_id).await {
+                    Ok(stored) => stored.and_then(|stored| reporting_interval(&stored.config)),
+                    Err(e) => {
+                        web_sys::console::log_1(&format!("Failed to fetch reporting interval: {}", e).into());
+                        None
+                    }
+                };
+                interval.set(configured.unwrap_or(DEFAULT_REPORTING_INTERVAL_SECS));
+            });
+            || ()
+        });
+    }
+
     // Effect hook for fetching telemetry data when device_id or refresh_count changes
     {
         // Clone state variables to use in the effect closure
         let telemetry_data = telemetry_data.clone();
+        let fetched_at = fetched_at.clone();
+        let cached_at = cached_at.clone();
+        let fetch_failed = fetch_failed.clone();
         let loading = loading.clone();
         let error = error.clone();
-        let device_id = device_id.clone();
         let refresh_count = refresh_count.clone();
+        let device_metrics = device_metrics.clone();
+        let metrics = metrics.clone();
+        let device_status = device_status.clone();
         
         // Set up effect that runs when device_id or refresh_count changes
-        use_effect_with(((*device_id).clone(), *refresh_count), move |(device_id, _)| {
+        use_effect_with((device_id.clone(), *refresh_count), move |(device_id, _)| {
             let device_id = device_id.clone();
             
             // Set loading state and clear any previous errors
@@ -105,31 +527,51 @@ pub fn telemetry_view() -> Html {
                 error.set(Some("Please enter a device ID.".to_string()));
                 loading.set(false);
                 telemetry_data.set(None);
+                device_metrics.set(Vec::new());
+                device_status.set(None);
             } else {
+                fetch_device_status(device_id.clone(), device_status);
+
                 // Spawn an async task to fetch the data
                 wasm_bindgen_futures::spawn_local(async move {
-                    // Call the API service to get latest telemetry
-                    match DeviceService::get_latest_telemetry(&device_id).await {
+                    // Call the API service to get the device's telemetry, from
+                    // which both the latest reading and its metrics are taken;
+                    // the cached records stand in while the API is unreachable
+                    let records = DeviceService::get_telemetry_or_cached(&device_id, None, None).await;
+                    let reported = records.as_ref().map(|fetched| numeric_metrics(&fetched.data)).unwrap_or_default();
+                    let latest = records.and_then(|fetched| {
+                        let cached = fetched.cached_at;
+                        fetched
+                            .data
+                            .into_iter()
+                            .max_by_key(|t| t.timestamp)
+                            .map(|latest| (latest, cached))
+                            .ok_or_else(|| "No telemetry data found".to_string())
+                    });
+
+                    match latest {
                         // Success case
-                        Ok(data) => {
+                        Ok((data, cached)) => {
+                            // Chart something the device reports if none of the
+                            // selected metrics are, e.g. after switching devices
+                            if !metrics.iter().any(|metric| reported.contains(metric)) {
+                                metrics.set(initial_metrics(&reported));
+                            }
                             // Update state with the fetched data
                             telemetry_data.set(Some(data));
+                            device_metrics.set(reported);
+                            if cached.is_none() {
+                                fetched_at.set(Some(Utc::now().timestamp()));
+                            }
+                            cached_at.set(cached);
+                            fetch_failed.set(false);
                             loading.set(false);
                         }
                         // Error case
                         Err(e) => {
                             // Handle different error scenarios with user-friendly messages
-                            if e.contains("No telemetry data found") {
-                                error.set(Some(
-                                    "No telemetry data found for this device ID.".to_string(),
-                                ));
-                            } else if e.contains("404") {
-                                error.set(Some(
-                                    "Device not found. Please check the device ID.".to_string(),
-                                ));
-                            } else {
-                                error.set(Some(e));
-                            }
+                            error.set(Some(describe_fetch_error(e)));
+                            fetch_failed.set(true);
                             loading.set(false);
                         }
                     }
@@ -141,22 +583,109 @@ pub fn telemetry_view() -> Html {
         });
     }
 
+    // Effect hook for ticking poll_count while auto-refresh is on
+    // Ticks are skipped while the browser tab is hidden, so a background
+    // tab does not keep polling the API
+    {
+        let mut ticks = *poll_count;
+        let poll_count = poll_count.setter();
+
+        use_effect_with((*auto_refresh, *auto_refresh_secs), move |(enabled, secs)| {
+            // Register the timer only while auto-refresh is on
+            let timer = enabled.then(|| {
+                let tick = Closure::<dyn FnMut()>::new(move || {
+                    let hidden = web_sys::window()
+                        .and_then(|window| window.document())
+                        .is_some_and(|document| document.hidden());
+                    if !hidden {
+                        ticks += 1;
+                        poll_count.set(ticks);
+                    }
+                });
+                let handle = web_sys::window().and_then(|window| {
+                    window
+                        .set_interval_with_callback_and_timeout_and_arguments_0(
+                            tick.as_ref().unchecked_ref(),
+                            (*secs * 1000) as i32,
+                        )
+                        .ok()
+                });
+                (tick, handle)
+            });
+
+            // Cleanup function stopping the timer when auto-refresh is turned
+            // off, the interval changes or the view is left
+            move || {
+                if let Some((tick, Some(handle))) = timer {
+                    if let Some(window) = web_sys::window() {
+                        window.clear_interval_with_handle(handle);
+                    }
+                    drop(tick);
+                }
+            }
+        });
+    }
+
+    // Effect hook for re-fetching the latest telemetry and the device status
+    // on every auto-refresh tick
+    // Unlike a manual refresh, the current data stays on screen while fetching
+    {
+        let telemetry_data = telemetry_data.clone();
+        let fetched_at = fetched_at.clone();
+        let cached_at = cached_at.clone();
+        let fetch_failed = fetch_failed.clone();
+        let error = error.clone();
+        let device_id = device_id.clone();
+        let device_status = device_status.clone();
+
+        use_effect_with(*poll_count, move |poll_count| {
+            if *poll_count > 0 && !device_id.trim().is_empty() {
+                fetch_device_status(device_id.clone(), device_status);
+                wasm_bindgen_futures::spawn_local(async move {
+                    match DeviceService::get_latest_telemetry(&device_id).await {
+                        Ok(fetched) => {
+                            error.set(None);
+                            telemetry_data.set(Some(fetched.data));
+                            if fetched.cached_at.is_none() {
+                                fetched_at.set(Some(Utc::now().timestamp()));
+                            }
+                            cached_at.set(fetched.cached_at);
+                            fetch_failed.set(false);
+                        }
+                        Err(e) => {
+                            error.set(Some(describe_fetch_error(e)));
+                            fetch_failed.set(true);
+                        }
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
     html! {
-        <div class="w-full bg-white rounded-xl shadow-md p-8 mt-8">
+        <div class="w-full bg-white dark:bg-gray-800 rounded-xl shadow-md p-8 mt-8">
             <div class="mb-6">
-                <h2 class="text-3xl font-bold text-gray-800 mb-2">{"Device Telemetry"}</h2>
+                <h2 class="text-3xl font-bold text-gray-800 dark:text-gray-100 mb-2">{"Device Telemetry"}</h2>
                 <form onsubmit={on_submit} class="flex flex-col sm:flex-row gap-2 items-end">
                     <div class="flex-1">
-                        <label for="device-id" class="block text-sm font-medium text-gray-700 mb-1">{"Device ID"}</label>
+                        <label for="device-id" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Device ID"}</label>
                         <input
                             type="text"
                             id="device-id"
+                            list="recent-device-ids"
                             value={(*input_value).clone()}
                             oninput={on_input_change}
-                            class="w-full rounded-md border-gray-300 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
+                            class="w-full rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm px-3 py-2"
                             placeholder="Enter device ID"
                             autofocus=true
                         />
+                        <RecentDevices
+                            device_ids={recent_devices}
+                            current={device_id.clone()}
+                            list_id="recent-device-ids"
+                            on_select={on_recent_select}
+                        />
                     </div>
                     <button
                         type="submit"
@@ -178,11 +707,136 @@ pub fn telemetry_view() -> Html {
                         { if *loading { html! { <span class="animate-spin mr-2">{"⏳"}</span> } } else { html!{} } }
                         {"Refresh"}
                     </button>
+                    <div class="mt-2 sm:mt-0 ml-2 flex items-center gap-2">
+                        <label class="flex items-center text-sm text-gray-700 dark:text-gray-200">
+                            <input
+                                type="checkbox"
+                                checked={*auto_refresh}
+                                onchange={on_auto_refresh_toggle}
+                                class="mr-2"
+                            />
+                            {"Auto-refresh"}
+                        </label>
+                        <select
+                            aria-label="Auto-refresh interval"
+                            onchange={on_auto_refresh_interval_change}
+                            disabled={!*auto_refresh}
+                            class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                        >
+                            {
+                                AUTO_REFRESH_INTERVALS.iter().map(|secs| html! {
+                                    <option value={secs.to_string()} selected={*secs == *auto_refresh_secs}>
+                                        {format_interval(*secs)}
+                                    </option>
+                                }).collect::<Html>()
+                            }
+                        </select>
+                    </div>
                 </form>
             </div>
 
+            <PresetPicker
+                presets={(*presets).clone()}
+                on_select={on_preset_select}
+                on_save={on_preset_save}
+                on_delete={on_preset_delete}
+            />
+
+            <div class="flex flex-col sm:flex-row gap-4 mb-6">
+                <div>
+                    <label for="time-range" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Time range"}</label>
+                    <select
+                        id="time-range"
+                        onchange={on_range_change}
+                        class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                    >
+                        {
+                            TimeRange::ALL.iter().map(|option| html! {
+                                <option value={option.key()} selected={*option == *range}>
+                                    {option.label()}
+                                </option>
+                            }).collect::<Html>()
+                        }
+                        // Shown only while a custom range is applied, as it is chosen with the date inputs
+                        if let TimeRange::Custom { .. } = *range {
+                            <option value="custom" selected=true disabled=true>{range.label()}</option>
+                        }
+                    </select>
+                </div>
+                <div>
+                    <label for="range-from" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"From"}</label>
+                    <input
+                        type="datetime-local"
+                        id="range-from"
+                        value={custom_from}
+                        onchange={on_from_change}
+                        class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                    />
+                </div>
+                <div>
+                    <label for="range-to" class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"To"}</label>
+                    <input
+                        type="datetime-local"
+                        id="range-to"
+                        value={custom_to}
+                        onchange={on_to_change}
+                        class="rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-gray-100 shadow-sm sm:text-sm px-3 py-2"
+                    />
+                </div>
+                <div>
+                    <span class="block text-sm font-medium text-gray-700 dark:text-gray-200 mb-1">{"Metrics"}</span>
+                    <div class="flex flex-wrap gap-4 py-2">
+                        {
+                            available_metrics.iter().cloned().map(|metric| {
+                                let on_metric_toggle = on_metric_toggle.clone();
+                                let toggled = metric.clone();
+                                html! {
+                                    <label class="flex items-center">
+                                        <input
+                                            type="checkbox"
+                                            checked={metrics.contains(&metric)}
+                                            onchange={Callback::from(move |_| on_metric_toggle.emit(toggled.clone()))}
+                                            class="mr-2"
+                                        />
+                                        <span>{metric}</span>
+                                    </label>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                </div>
+                <div class="sm:ml-auto sm:self-end flex gap-2">
+                    if zoomed_from.is_some() {
+                        <button
+                            type="button"
+                            onclick={on_zoom_reset.reform(|_: MouseEvent| ())}
+                            class="px-4 py-2 rounded bg-gray-500 text-white font-semibold shadow hover:bg-gray-700 transition"
+                            title="Return to the time range chosen before zooming"
+                        >
+                            {"Reset zoom"}
+                        </button>
+                    }
+                    <button
+                        type="button"
+                        onclick={on_export}
+                        class="px-4 py-2 rounded bg-green-600 text-white font-semibold shadow hover:bg-green-700 transition"
+                        disabled={*exporting || device_id.trim().is_empty()}
+                        title="Download the charted metrics over the selected time range"
+                    >
+                        { if *exporting { html! { <span class="animate-spin mr-2">{"⏳"}</span> } } else { html!{} } }
+                        {"Export CSV"}
+                    </button>
+                </div>
+            </div>
+
+            <ThresholdEditor
+                thresholds={(*user_thresholds).clone()}
+                metrics={available_metrics}
+                on_change={on_thresholds_change}
+            />
+
             if let Some(err) = error.as_ref() {
-                <div class="bg-red-50 border border-red-200 text-red-700 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
+                <div class="bg-red-50 dark:bg-red-950 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-300 px-4 py-3 rounded flex items-center gap-2 mb-4 animate-fade-in">
                     <span>{"❌"}</span>
                     <span>{format!("Error: {}", err)}</span>
                 </div>
@@ -190,16 +844,34 @@ pub fn telemetry_view() -> Html {
 
             if *loading {
                 <div class="flex justify-center items-center h-32">
-                    <div class="text-gray-500 animate-pulse">{"Loading telemetry data..."}</div>
+                    <div class="text-gray-500 dark:text-gray-400 animate-pulse">{"Loading telemetry data..."}</div>
                 </div>
             } else if let Some(data) = telemetry_data.as_ref() {
                 <div>
+                    <StaleBanner
+                        fetched_at={*fetched_at}
+                        cached_at={*cached_at}
+                        failed={*fetch_failed}
+                        latest_reading={data.timestamp}
+                        interval={Some(*interval)}
+                    />
                     <div class="mb-6">
-                        <p class="text-gray-600">{format!("Device ID: {}", data.device_id)}</p>
+                        <div class="flex items-center gap-2">
+                            <p class="text-gray-600 dark:text-gray-300">{format!("Device ID: {}", data.device_id)}</p>
+                            // Status of the device shown, hidden while another device's status is still loaded
+                            if let Some(report) = device_status.as_ref().filter(|report| report.device_id == data.device_id) {
+                                <span
+                                    class={classes!("px-2", "py-0.5", "rounded-full", "text-xs", "font-semibold", report.status.badge_classes())}
+                                    title={format!("Last reported {}", format_age(report.seconds_since_last_seen))}
+                                >
+                                    {report.status.label()}
+                                </span>
+                            }
+                        </div>
                         {
                             if let Some(timestamp) = data.timestamp {
                                 html! {
-                                    <p class="text-sm text-gray-500">
+                                    <p class="text-sm text-gray-500 dark:text-gray-400">
                                         {format!("Last updated: {}", format_timestamp(timestamp))}
                                     </p>
                                 }
@@ -212,12 +884,12 @@ pub fn telemetry_view() -> Html {
                         {
                             get_sorted_telemetry_items(data).into_iter().map(|(key, value)| {
                                 html! {
-                                    <div class="bg-white p-4 rounded-lg shadow border">
-                                        <h3 class="text-sm font-medium text-gray-500 uppercase tracking-wide">
+                                    <div class="bg-white dark:bg-gray-800 p-4 rounded-lg shadow border">
+                                        <h3 class="text-sm font-medium text-gray-500 dark:text-gray-400 uppercase tracking-wide">
                                             {key}
                                         </h3>
-                                        <p class="text-2xl font-semibold text-gray-900 mt-2">
-                                            {format_value(key, value)}
+                                        <p class="text-2xl font-semibold text-gray-900 dark:text-gray-100 mt-2">
+                                            {units.format_raw(key, value, true)}
                                         </p>
                                     </div>
                                 }
@@ -226,58 +898,133 @@ pub fn telemetry_view() -> Html {
                     </div>
                 </div>
             } else {
-                <div class="text-center text-gray-500 py-8">
+                <div class="text-center text-gray-500 dark:text-gray-400 py-8">
                     {"No telemetry data available"}
                 </div>
             }
-                    <div class="mt-8 grid grid-cols-1 lg:grid-cols-2 gap-6">
-            <ApexChart
-                key={format!("temperature-{}-{}", *device_id, *refresh_count)}
-                metric_key="temperature"
-                title="Temperature Over Time"
-                device_id={(*device_id).clone()}
-                refresh_count={*refresh_count}
+            <StatCards
+                device_id={device_id.clone()}
+                metrics={(*metrics).clone()}
+                range={*range}
+                refresh_count={*refresh_count + *poll_count}
             />
-            <ApexChart
-                key={format!("voltage-{}-{}", *device_id, *refresh_count)}
-                metric_key="voltage"
-                title="Voltage Over Time"
-                device_id={(*device_id).clone()}
-                refresh_count={*refresh_count}
-            />
-        </div>
+            <div class="mt-8 flex gap-2 border-b border-gray-200 dark:border-gray-700">
+                {
+                    HistoryTab::ALL.iter().map(|tab| {
+                        let tab = *tab;
+                        let selected = tab == *history_tab;
+                        html! {
+                            <button
+                                type="button"
+                                onclick={on_tab_select.reform(move |_: MouseEvent| tab)}
+                                class={classes!(
+                                    "px-4", "py-2", "-mb-px", "border-b-2", "font-medium", "transition",
+                                    if selected {
+                                        "border-blue-600 text-blue-600 dark:text-blue-400"
+                                    } else {
+                                        "border-transparent text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-200"
+                                    }
+                                )}
+                            >
+                                {tab.label()}
+                            </button>
+                        }
+                    }).collect::<Html>()
+                }
+            </div>
+            if *history_tab == HistoryTab::Table {
+                // Re-created, back on the first page, when the device, range or data is refreshed
+                <TelemetryTable
+                    key={format!("{}-{:?}-{}", device_id, *range, *refresh_count)}
+                    device_id={device_id.clone()}
+                    range={*range}
+                />
+            } else {
+                <div class="mt-8 grid grid-cols-1 lg:grid-cols-2 gap-6">
+                    {
+                        metrics.iter().map(|metric| match *renderer {
+                            ChartRenderer::ApexCharts => html! {
+                                <ApexChart
+                                    key={format!("{}-{}-{}-{}", metric, device_id, range.key(), *refresh_count)}
+                                    metric_key={metric.clone()}
+                                    title={chart_title(metric)}
+                                    device_id={device_id.clone()}
+                                    refresh_count={*refresh_count + *poll_count}
+                                    range={*range}
+                                    thresholds={thresholds.clone()}
+                                    on_zoom={on_zoom.clone()}
+                                    on_zoom_reset={on_zoom_reset.clone()}
+                                />
+                            },
+                            ChartRenderer::Native => html! {
+                                <SvgChart
+                                    key={format!("{}-{}-{}-{}", metric, device_id, range.key(), *refresh_count)}
+                                    metric_key={metric.clone()}
+                                    title={chart_title(metric)}
+                                    device_id={device_id.clone()}
+                                    refresh_count={*refresh_count + *poll_count}
+                                    range={*range}
+                                    thresholds={thresholds.clone()}
+                                    on_zoom={on_zoom.clone()}
+                                />
+                            },
+                        }).collect::<Html>()
+                    }
+                </div>
+            }
         </div>
     }
 }
 
-/// Formats a Unix timestamp into a human-readable date string.
+/// Fetches whether a device is still reporting into the status state.
+///
+/// The badge is only a hint, so a failure hides it rather than showing an
+/// error; failures fetching the telemetry itself are reported instead.
 ///
 /// # Parameters
-/// * `timestamp` - Unix timestamp (seconds since epoch)
+/// * `device_id` - ID of the device to fetch the status of
+/// * `device_status` - State receiving the status, `None` on failure
+fn fetch_device_status(device_id: String, device_status: UseStateHandle<Option<DeviceStatusReport>>) {
+    wasm_bindgen_futures::spawn_local(async move {
+        match DeviceService::get_device_status(&device_id).await {
+            Ok(report) => device_status.set(Some(report)),
+            Err(e) => {
+                web_sys::console::log_1(&format!("Failed to fetch device status: {}", e).into());
+                device_status.set(None);
+            }
+        }
+    });
+}
+
+/// Turns an error from fetching the latest telemetry into a user-friendly message.
+///
+/// # Parameters
+/// * `e` - Error message returned by the device service
 ///
 /// # Returns
-/// * Formatted date string in "YYYY-MM-DD HH:MM:SS UTC" format
-/// * If conversion fails, returns the raw timestamp as string
-fn format_timestamp(timestamp: i64) -> String {
-    DateTime::from_timestamp(timestamp, 0)
-        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-        .unwrap_or_else(|| format!("{}", timestamp))
+/// * Message to show to the user
+fn describe_fetch_error(e: String) -> String {
+    if e.contains("No telemetry data found") {
+        "No telemetry data found for this device ID.".to_string()
+    } else if e.contains("404") {
+        "Device not found. Please check the device ID.".to_string()
+    } else {
+        e
+    }
 }
 
-/// Formats a telemetry value with appropriate units based on the metric type.
+/// Formats an auto-refresh interval for the interval selector.
 ///
 /// # Parameters
-/// * `key` - Name of the telemetry metric (e.g., "temperature")
-/// * `value` - Raw value as string
+/// * `secs` - Interval in seconds
 ///
 /// # Returns
-/// * Formatted value with appropriate units
-fn format_value(key: &str, value: &str) -> String {
-    match key.to_lowercase().as_str() {
-        "temperature" => format!("{}°C", value),  // Add Celsius units
-        "pressure" => format!("{} hPa", value),   // Add hectopascal units
-        "voltage" => format!("{}V", value),       // Add volt units
-        _ => value.to_string(),                   // Use raw value for unknown metrics
+/// * Label such as "Every 15s" or "Every 1 min"
+fn format_interval(secs: u32) -> String {
+    if secs.is_multiple_of(60) {
+        format!("Every {} min", secs / 60)
+    } else {
+        format!("Every {}s", secs)
     }
 }
 
@@ -301,3 +1048,57 @@ fn get_sorted_telemetry_items(data: &Telemetry) -> Vec<(&str, &str)> {
     
     items
 }
+
+/// Builds the chart title for a metric, e.g. "Temperature Over Time".
+///
+/// # Parameters
+/// * `metric` - Name of the telemetry metric
+///
+/// # Returns
+/// * Title with the metric name capitalized
+fn chart_title(metric: &str) -> String {
+    let mut chars = metric.chars();
+    match chars.next() {
+        Some(first) => format!("{}{} Over Time", first.to_uppercase(), chars.as_str()),
+        None => "Over Time".to_string(),
+    }
+}
+
+/// Chooses the metrics to chart for a device when none of the selected ones apply.
+///
+/// # Parameters
+/// * `reported` - Numeric metrics the device has reported
+///
+/// # Returns
+/// * The default metrics the device reports, or else its first reported
+///   metric; empty if it has reported no numeric metric
+fn initial_metrics(reported: &[String]) -> Vec<String> {
+    let defaults: Vec<String> = DEFAULT_METRICS
+        .iter()
+        .map(|m| m.to_string())
+        .filter(|m| reported.contains(m))
+        .collect();
+
+    if defaults.is_empty() {
+        reported.iter().take(1).cloned().collect()
+    } else {
+        defaults
+    }
+}
+
+/// Lists the metrics that can be charted.
+///
+/// # Parameters
+/// * `reported` - Numeric metrics the device has reported
+/// * `selected` - Metrics currently charted
+///
+/// # Returns
+/// * Sorted, de-duplicated list of the reported and selected metrics
+fn get_available_metrics(reported: &[String], selected: &[String]) -> Vec<String> {
+    let mut metrics: Vec<String> = reported.to_vec();
+    metrics.extend(selected.iter().cloned());
+
+    metrics.sort();
+    metrics.dedup();
+    metrics
+}
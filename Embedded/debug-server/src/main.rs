@@ -1,9 +1,23 @@
 #[macro_use] extern crate rocket;
 
-// Post request that revieves and prints logs
+use rocket::request::{FromRequest, Outcome, Request};
+
+// Boot session id sent by the firmware in the X-Session-Id header
+struct SessionId<'r>(Option<&'r str>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SessionId<'r> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(SessionId(request.headers().get_one("X-Session-Id")))
+    }
+}
+
+// Post request that revieves and prints logs, tagged with the boot session
 #[post("/log", data = "<log_data>")]
-fn log(log_data: String) -> String {
-    println!("Received log data: {}", log_data);
+fn log(session: SessionId<'_>, log_data: String) -> String {
+    println!("Received log data [session {}]: {}", session.0.unwrap_or("unknown"), log_data);
     "Log received".to_string()
 }
 // lisen on 0.0.0.0:8000
@@ -45,6 +45,13 @@ fn main() {
         "YOUR_DEVICE_ID".to_string()
     });
 
+    // Device Key - optional, sent with config fetches once the service
+    // requires credentials
+    let device_key = env::var("DEVICE_KEY").unwrap_or_else(|_| {
+        println!("cargo:warning=DEVICE_KEY not set, config fetches are unauthenticated");
+        String::new()
+    });
+
     // Debug Server - optional
     let debug_server = env::var("DEBUG_SERVER").unwrap_or_else(|_| {
         println!("cargo:warning=DEBUG_SERVER not set, using default");
@@ -58,6 +65,7 @@ fn main() {
     println!("cargo:rustc-env=CONFIG_HOST={}", config_host);
     println!("cargo:rustc-env=DEBUG_SERVER={}", debug_server);
     println!("cargo:rustc-env=DEVICE_ID={}", device_id);
+    println!("cargo:rustc-env=DEVICE_KEY={}", device_key);
 
     // Rebuild if .env file changes
     println!("cargo:rerun-if-changed=.env");
@@ -66,6 +74,7 @@ fn main() {
     println!("cargo:rerun-if-env-changed=DEBUG_SERVER");
     println!("cargo:rerun-if-env-changed=CONFIG_HOST");
     println!("cargo:rerun-if-env-changed=DEVICE_ID");
+    println!("cargo:rerun-if-env-changed=DEVICE_KEY");
     // Put `memory.x` in our output directory and ensure it's
     // on the linker search path.
     let out = &PathBuf::from(env::var_os("OUT_DIR").unwrap());
@@ -0,0 +1,37 @@
+//! # Configuration Response Fuzz Target
+//!
+//! Feeds arbitrary bytes through the configuration fetch task's response
+//! handling: the end-of-response check, UTF-8 decoding, the 304 check, ETag
+//! header extraction and the serde-json-core configuration parser.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Firmware modules, compiled for the host
+#[allow(dead_code, non_snake_case)]
+#[path = "../../src/config/device.rs"]
+mod device;
+#[path = "../../src/utils/http_response.rs"]
+mod http_response;
+
+// Mirror the firmware's module paths used by `http_response`
+mod config {
+    pub(crate) use crate::device;
+}
+
+use http_response::{is_complete, parse_config_response, ConfigResponse, MAX_ETAG_LEN, MAX_RESPONSE_LEN};
+
+fuzz_target!(|data: &[u8]| {
+    // The fetch task checks every partial read, bounded by its buffer
+    let _ = is_complete(&data[..data.len().min(MAX_RESPONSE_LEN)]);
+
+    let Ok(response) = core::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(ConfigResponse::Updated { config, etag }) = parse_config_response(response, "sensor-001") {
+        assert_eq!(config.device_id.as_str(), "sensor-001");
+        assert!(etag.len() <= MAX_ETAG_LEN);
+    }
+});
@@ -0,0 +1,120 @@
+//! # Fuzz Corpus Regression Tests
+//!
+//! Replays the committed corpus (`corpus/`) through the same firmware
+//! parsing code as the fuzz targets, so inputs that once crashed them are
+//! checked on every test run.
+
+use std::fs;
+use std::path::PathBuf;
+
+// Firmware modules, compiled for the host
+#[allow(dead_code, non_snake_case)]
+#[path = "../../src/config/device.rs"]
+mod device;
+#[path = "../../src/utils/http_response.rs"]
+mod http_response;
+
+// Mirror the firmware's module paths used by `http_response`
+mod config {
+    pub(crate) use crate::device;
+}
+
+use http_response::{is_complete, parse_config_response, ConfigResponse, MAX_ETAG_LEN, MAX_RESPONSE_LEN};
+
+/// Reads every input in a fuzz target's corpus directory
+///
+/// # Parameters
+/// * `target` - Name of the fuzz target
+fn corpus(target: &str) -> Vec<(PathBuf, Vec<u8>)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("corpus").join(target);
+    let mut inputs: Vec<(PathBuf, Vec<u8>)> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Failed to read corpus {}: {}", dir.display(), e))
+        .map(|entry| {
+            let path = entry.expect("Failed to read corpus entry").path();
+            let data = fs::read(&path).expect("Failed to read corpus input");
+            (path, data)
+        })
+        .collect();
+    inputs.sort();
+
+    assert!(!inputs.is_empty(), "Corpus {} is empty", dir.display());
+    inputs
+}
+
+#[test]
+fn test_config_response_corpus() {
+    for (path, data) in corpus("config_response") {
+        let Ok(response) = core::str::from_utf8(&data) else {
+            continue;
+        };
+
+        if let Ok(ConfigResponse::Updated { config, etag }) = parse_config_response(response, "sensor-001") {
+            assert_eq!(config.device_id.as_str(), "sensor-001", "{}", path.display());
+            assert!(etag.len() <= MAX_ETAG_LEN, "{}", path.display());
+        }
+    }
+}
+
+#[test]
+fn test_reads_full_response_with_firmware_manifest() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("corpus/config_response/firmware_manifest.http");
+    let data = fs::read(&path).expect("Failed to read corpus input");
+
+    // Larger than a single 1 KB read, but fits the response buffer
+    assert!(data.len() > 1024 && data.len() <= MAX_RESPONSE_LEN);
+
+    // Only complete once every announced body byte has arrived
+    let header_end = data.windows(4).position(|window| window == b"\r\n\r\n").unwrap() + 4;
+    for len in [0, 512, header_end - 1, header_end, 1024, data.len() - 1] {
+        assert!(!is_complete(&data[..len]), "complete after {} bytes", len);
+    }
+    assert!(is_complete(&data));
+
+    let response = core::str::from_utf8(&data).unwrap();
+    match parse_config_response(response, "sensor-001") {
+        Ok(ConfigResponse::Updated { config, etag }) => {
+            assert_eq!(config.config.LED.as_deref(), Some("on"));
+            assert_eq!(etag.as_str(), "9c4e1f7a2b3d5e60");
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    // What the old single 1 KB read would have handed the parser
+    let truncated = core::str::from_utf8(&data[..1024]).unwrap();
+    assert!(parse_config_response(truncated, "sensor-001").is_err());
+}
+
+#[test]
+fn test_response_without_content_length_waits_for_close() {
+    assert!(is_complete(b"HTTP/1.1 304 Not Modified\r\nETag: \"0123456789abcdef\"\r\n\r\n"));
+    assert!(!is_complete(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n[{\"device_id\""));
+    assert!(!is_complete(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\n[{}"));
+    assert!(is_complete(b"HTTP/1.1 200 OK\r\ncontent-length: 3\r\n\r\n[{}"));
+}
+
+#[test]
+fn test_parses_config_with_etag() {
+    let response = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nETag: \"0123456789abcdef\"\r\n\r\n\
+                    [{\"device_id\":\"sensor-001\",\"config\":{\"LED\":\"on\"}}]";
+
+    match parse_config_response(response, "sensor-001") {
+        Ok(ConfigResponse::Updated { config, etag }) => {
+            assert_eq!(config.config.LED.as_deref(), Some("on"));
+            assert_eq!(etag.as_str(), "0123456789abcdef");
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_not_modified_and_foreign_device() {
+    assert_eq!(
+        parse_config_response("HTTP/1.1 304 Not Modified\r\n\r\n", "sensor-001"),
+        Ok(ConfigResponse::NotModified)
+    );
+    assert!(parse_config_response(
+        "HTTP/1.1 200 OK\r\n\r\n[{\"device_id\":\"sensor-002\",\"config\":{}}]",
+        "sensor-001"
+    )
+    .is_err());
+}
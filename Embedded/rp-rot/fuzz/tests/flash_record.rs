@@ -0,0 +1,171 @@
+//! # Flash Record Tests
+//!
+//! Checks the versioned flash record format on the host: records written
+//! by one firmware must be read back, corrupt or foreign data rejected,
+//! and older layouts upgraded through the migration chain.
+
+// Firmware modules, compiled for the host
+#[allow(dead_code)]
+#[path = "../../src/error/mod.rs"]
+mod error;
+#[path = "../../src/utils/flash_record.rs"]
+mod flash_record;
+
+use error::RecordError;
+use flash_record::{
+    crc32, decode_record, encode_record, load_record, LoadedRecord, Migration, RecordHeader, RecordKind, HEADER_LEN,
+    RECORD_MAGIC,
+};
+
+/// Encodes a configuration store record into a buffer the size of a flash page
+///
+/// # Parameters
+/// * `version` - Layout version of the payload
+/// * `payload` - The record payload
+fn record(version: u8, payload: &[u8]) -> [u8; 256] {
+    let mut out = [0xFF; 256];
+    encode_record(RecordKind::ConfigStore, version, payload, &mut out).unwrap();
+    out
+}
+
+/// Upgrades version 1 payloads by appending `,2`
+fn to_v2(buf: &mut [u8], len: usize) -> Result<usize, RecordError> {
+    let new_len = len + 2;
+    buf.get_mut(len..new_len).ok_or(RecordError::BufferTooSmall)?.copy_from_slice(b",2");
+    Ok(new_len)
+}
+
+/// Upgrades version 2 payloads by appending `,3`
+fn to_v3(buf: &mut [u8], len: usize) -> Result<usize, RecordError> {
+    let new_len = len + 2;
+    buf.get_mut(len..new_len).ok_or(RecordError::BufferTooSmall)?.copy_from_slice(b",3");
+    Ok(new_len)
+}
+
+/// A step that cannot convert its payload
+fn fail(_: &mut [u8], _: usize) -> Result<usize, RecordError> {
+    Err(RecordError::Corrupt)
+}
+
+const CHAIN: &[Migration] = &[
+    Migration { kind: RecordKind::ConfigStore, from: 2, migrate: to_v3 },
+    Migration { kind: RecordKind::ConfigStore, from: 1, migrate: to_v2 },
+];
+
+#[test]
+fn test_crc32_matches_ieee() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32(b""), 0);
+}
+
+#[test]
+fn test_encode_decode_round_trip() {
+    let bytes = record(1, b"v1");
+
+    assert_eq!(bytes[0..4], RECORD_MAGIC);
+    let (header, payload) = decode_record(&bytes).unwrap();
+    assert_eq!(
+        header,
+        RecordHeader { kind: RecordKind::ConfigStore as u8, version: 1, len: 2, crc: crc32(b"v1") }
+    );
+    assert_eq!(payload, b"v1");
+
+    let mut small = [0u8; HEADER_LEN + 1];
+    assert_eq!(
+        encode_record(RecordKind::ConfigStore, 1, b"v1", &mut small),
+        Err(RecordError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn test_rejects_blank_and_malformed_headers() {
+    assert_eq!(decode_record(&[0xFF; 256]), Err(RecordError::Blank));
+    assert_eq!(decode_record(&[0xFF; HEADER_LEN - 1]), Err(RecordError::Truncated));
+
+    let mut bad_magic = record(1, b"v1");
+    bad_magic[0] = b'X';
+    assert_eq!(decode_record(&bad_magic), Err(RecordError::BadMagic));
+
+    // A length running past the end of the region
+    let mut too_long = record(1, b"v1");
+    too_long[6..8].copy_from_slice(&300u16.to_le_bytes());
+    assert_eq!(decode_record(&too_long), Err(RecordError::Truncated));
+}
+
+#[test]
+fn test_rejects_corrupt_records() {
+    let mut flipped_payload = record(1, b"v1");
+    flipped_payload[HEADER_LEN] ^= 0x01;
+    assert_eq!(decode_record(&flipped_payload), Err(RecordError::Corrupt));
+
+    let mut flipped_crc = record(1, b"v1");
+    flipped_crc[8] ^= 0x01;
+    assert_eq!(decode_record(&flipped_crc), Err(RecordError::Corrupt));
+
+    // A shorter length covers fewer bytes than the checksum was computed over
+    let mut short_len = record(1, b"v1");
+    short_len[6..8].copy_from_slice(&1u16.to_le_bytes());
+    assert_eq!(decode_record(&short_len), Err(RecordError::Corrupt));
+}
+
+#[test]
+fn test_load_runs_migration_chain_in_order() {
+    let mut scratch = [0u8; 64];
+
+    let loaded = load_record(&record(1, b"v1"), RecordKind::ConfigStore, 3, CHAIN, &mut scratch).unwrap();
+    assert_eq!(loaded, LoadedRecord { len: 6, migrated: true });
+    assert_eq!(&scratch[..loaded.len], b"v1,2,3");
+
+    let loaded = load_record(&record(2, b"v2"), RecordKind::ConfigStore, 3, CHAIN, &mut scratch).unwrap();
+    assert_eq!(&scratch[..loaded.len], b"v2,3");
+
+    let loaded = load_record(&record(3, b"v3"), RecordKind::ConfigStore, 3, CHAIN, &mut scratch).unwrap();
+    assert_eq!(loaded, LoadedRecord { len: 2, migrated: false });
+    assert_eq!(&scratch[..loaded.len], b"v3");
+}
+
+#[test]
+fn test_load_rejects_unreadable_versions() {
+    let mut scratch = [0u8; 64];
+
+    // Written by a newer firmware
+    assert_eq!(
+        load_record(&record(4, b"v4"), RecordKind::ConfigStore, 3, CHAIN, &mut scratch),
+        Err(RecordError::UnsupportedVersion(4))
+    );
+
+    // A gap in the chain
+    assert_eq!(
+        load_record(&record(1, b"v1"), RecordKind::ConfigStore, 3, &CHAIN[..1], &mut scratch),
+        Err(RecordError::UnsupportedVersion(1))
+    );
+
+    let failing = [Migration { kind: RecordKind::ConfigStore, from: 1, migrate: fail }];
+    assert_eq!(
+        load_record(&record(1, b"v1"), RecordKind::ConfigStore, 2, &failing, &mut scratch),
+        Err(RecordError::MigrationFailed(1))
+    );
+
+    // No room for the upgraded payload
+    let mut tiny = [0u8; 3];
+    assert_eq!(
+        load_record(&record(1, b"v1"), RecordKind::ConfigStore, 2, CHAIN, &mut tiny),
+        Err(RecordError::MigrationFailed(1))
+    );
+    assert_eq!(
+        load_record(&record(1, b"v1"), RecordKind::ConfigStore, 1, CHAIN, &mut [0u8; 1]),
+        Err(RecordError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn test_load_rejects_other_record_kinds() {
+    let mut other = record(1, b"v1");
+    other[4] = 2;
+
+    let mut scratch = [0u8; 64];
+    assert_eq!(
+        load_record(&other, RecordKind::ConfigStore, 1, CHAIN, &mut scratch),
+        Err(RecordError::WrongKind(2))
+    );
+}
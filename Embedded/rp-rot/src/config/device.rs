@@ -27,6 +27,11 @@ pub struct DeviceConfigItem {
     
     /// Configuration settings for the device
     pub config: Config,
+
+    /// Number of the configuration version, as assigned by the server
+    /// This is optional - configurations saved by older firmware have none
+    #[serde(default)]
+    pub version_number: Option<u64>,
 }
 
 /// Contains specific configuration settings for a device.
@@ -37,6 +42,7 @@ pub struct DeviceConfigItem {
 pub struct Config {
     /// LED state: "on" to enable, "off" to disable
     /// This is optional - if not provided, the LED state remains unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub LED: Option<String<MAX_VALUE_LEN>>,
     
     // Add more configuration fields as needed for future enhancements:
@@ -45,6 +51,20 @@ pub struct Config {
     // etc.
 }
 
+/// Represents the report of an applied configuration.
+///
+/// The device sends this to the configuration API after applying a
+/// configuration, so operators can compare what the device runs with what
+/// it is served.
+#[derive(Debug, Serialize)]
+pub struct ConfigReport<'a> {
+    /// Configuration settings the device applied
+    pub config: &'a Config,
+
+    /// Number of the configuration version applied, if it has one
+    pub version_number: Option<u64>,
+}
+
 /// Represents the response from the configuration API.
 ///
 /// The configuration API returns an array of device configurations.
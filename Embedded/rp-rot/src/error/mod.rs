@@ -2,12 +2,15 @@
 ///
 /// This module defines error types for various parts of the application.
 /// These error types are used for structured error handling and logging.
+/// They are only formatted for defmt on the device, so the module also
+/// builds on the host for the tests in `fuzz/`.
 
 /// Errors that can occur during telemetry operations.
 ///
 /// This enum represents the various failure modes when collecting
 /// and transmitting telemetry data.
-#[derive(Debug, defmt::Format)]
+#[derive(Debug)]
+#[cfg_attr(target_os = "none", derive(defmt::Format))]
 pub enum TelemetryError {
     /// DNS resolution failed (couldn't resolve hostname to IP)
     DnsResolve,
@@ -29,7 +32,8 @@ pub enum TelemetryError {
 ///
 /// This enum represents the various failure modes when connecting
 /// to and using a WiFi network.
-#[derive(Debug, defmt::Format)]
+#[derive(Debug)]
+#[cfg_attr(target_os = "none", derive(defmt::Format))]
 pub enum WiFiError {
     /// Failed to join the WiFi network with a specific status code
     JoinFailed(u32),
@@ -45,4 +49,39 @@ pub enum WiFiError {
     
     /// Operation timed out
     Timeout
-}
\ No newline at end of file
+}
+
+/// Errors that can occur while reading or writing flash-stored records.
+///
+/// This enum represents the various failure modes when decoding a
+/// versioned record or upgrading it to the current layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(target_os = "none", derive(defmt::Format))]
+pub enum RecordError {
+    /// The flash region is erased (all 0xFF); no record has been written
+    Blank,
+
+    /// The region does not start with the record magic bytes
+    BadMagic,
+
+    /// The record holds a different kind of data than expected
+    WrongKind(u8),
+
+    /// The record is shorter than its header claims
+    Truncated,
+
+    /// The payload checksum does not match
+    Corrupt,
+
+    /// No migration path exists from this stored version (e.g. a downgrade)
+    UnsupportedVersion(u8),
+
+    /// A migration step failed to convert the payload
+    MigrationFailed(u8),
+
+    /// The output buffer cannot hold the record
+    BufferTooSmall,
+
+    /// Reading, erasing or writing the flash failed
+    Flash,
+}
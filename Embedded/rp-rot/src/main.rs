@@ -46,8 +46,9 @@ use drivers::{Led, TemperatureSensor};
 use tasks::config_fetch_task;
 use tasks::{cyw43_task, network_task, telemetry_task, TelemetryTaskConfig};
 use utils::config_store::get_device_config;
-use utils::config_store::init_config_store;
+use utils::config_store::{init_config_store, load_saved_config};
 use utils::debug_server::post_to_debug_server;
+use utils::session::{init_session_id, session_id};
 
 // Import additional required types
 use embassy_rp::gpio::AnyPin;
@@ -76,6 +77,11 @@ async fn main(spawner: Spawner) {
     // This is used for network stack initialization
     let mut rng = RoscRng;
 
+    // Generate a random id for this boot session
+    // It is attached to all network traffic so the cloud can group requests per boot
+    init_session_id(rng.next_u32());
+    info!("Boot session id: {}", session_id().as_str());
+
     // ======== Initialize LED ========
     info!("Initializing LED...");
     // Create LED driver connected to GPIO pin 16
@@ -256,7 +262,9 @@ async fn main(spawner: Spawner) {
 
     // ======== Initialize Configuration Store ========
     // This initializes the persistent storage for device configuration
+    // and restores the configuration saved before the last restart
     init_config_store();
+    load_saved_config(embassy_rp::flash::Flash::new_blocking(p.FLASH)).await;
 
     // ======== Spawn Configuration Fetch Task ========
     // This task periodically fetches configuration updates from the cloud
@@ -2,16 +2,19 @@
 ///
 /// This module implements a task that periodically fetches device configuration
 /// from the cloud backend. It allows remote configuration of device parameters.
+/// Every configuration applied is reported back, so the backend can show
+/// what the device actually runs next to what it is served.
 
 use defmt::*;
 use embassy_net::Stack;
 use embassy_time::{Duration, Timer};
 use embedded_io_async::Write;
 use heapless::String;
-use serde_json_core::de::from_str;
 
-use crate::config::device::{DeviceConfigItem, DeviceConfigResponse};
-use crate::utils::config_store::set_device_config;
+use crate::config::device::{ConfigReport, DeviceConfigItem};
+use crate::utils::config_store::{get_device_config, save_device_config, set_device_config};
+use crate::utils::http_response::{is_complete, parse_config_response, ConfigResponse, MAX_ETAG_LEN, MAX_RESPONSE_LEN};
+use crate::utils::session::{session_id, SESSION_HEADER};
 
 // Configuration parameters from environment variables
 // These are set at build time to avoid hardcoding sensitive information
@@ -21,6 +24,8 @@ const CONFIG_URL_HOST: &str = env!("CONFIG_HOST");
 const CONFIG_URL_PORT: u16 = 80;
 /// The unique identifier for this device
 const DEVICE_ID: &str = env!("DEVICE_ID");
+/// The key this device authenticates with, empty if none was issued
+const DEVICE_KEY: &str = env!("DEVICE_KEY");
 
 /// Embassy task for periodically fetching device configuration from the cloud.
 ///
@@ -36,13 +41,36 @@ const DEVICE_ID: &str = env!("DEVICE_ID");
 /// device lifecycle.
 #[embassy_executor::task]
 pub async fn config_fetch_task(stack: Stack<'static>) {
+    // Entity tag of the last applied configuration, sent back so the
+    // server can answer 304 Not Modified instead of the full config
+    let mut etag = String::<MAX_ETAG_LEN>::new();
+    // Whether the applied configuration still has to be reported
+    let mut report_pending = false;
+
     // Main task loop - runs forever
     loop {
         // Attempt to fetch and update the device configuration
-        match fetch_and_update_config(&stack).await {
-            Ok(_) => info!("Config fetch and update succeeded"),
+        match fetch_and_update_config(&stack, &mut etag).await {
+            Ok(updated) => {
+                info!("Config fetch and update succeeded");
+                report_pending |= updated;
+            }
             Err(e) => warn!("Config fetch failed: {}", e),
         }
+
+        // Report the applied configuration, retrying on the next cycle if
+        // the report does not get through
+        if report_pending {
+            if let Some(config) = get_device_config().await {
+                match report_applied_config(&stack, &config).await {
+                    Ok(_) => {
+                        info!("Config report accepted");
+                        report_pending = false;
+                    }
+                    Err(e) => warn!("Config report failed: {}", e),
+                }
+            }
+        }
         
         // Wait 60 seconds before the next configuration check
         // This reduces network traffic while still allowing timely updates
@@ -55,24 +83,142 @@ pub async fn config_fetch_task(stack: Stack<'static>) {
 /// This function performs the following steps:
 /// 1. Resolves the configuration server hostname using DNS
 /// 2. Connects to the server
-/// 3. Sends an HTTP GET request
-/// 4. Receives and parses the response
+/// 3. Sends an HTTP GET request, with the device key and the last entity
+///    tag if there are any
+/// 4. Receives and parses the response, stopping early on 304 Not Modified
 /// 5. Updates the local configuration storage
 ///
 /// # Parameters
 /// * `stack` - Network stack for communication
+/// * `etag` - Entity tag of the current configuration, updated on success
 ///
 /// # Returns
-/// * `Ok(())` - If configuration was fetched and updated successfully
+/// * `Ok(true)` - If a configuration was fetched and applied
+/// * `Ok(false)` - If the configuration is unchanged
 /// * `Err(&'static str)` - If any step fails, with an error message
-async fn fetch_and_update_config(stack: &Stack<'_>) -> Result<(), &'static str> {
+async fn fetch_and_update_config(
+    stack: &Stack<'_>,
+    etag: &mut String<MAX_ETAG_LEN>,
+) -> Result<bool, &'static str> {
     // Create buffers for TCP socket (1KB each)
     let mut rx_buffer = [0; 1024];
     let mut tx_buffer = [0; 1024];
     
     // Create a new TCP socket using the network stack
     let mut socket = embassy_net::tcp::TcpSocket::new(*stack, &mut rx_buffer, &mut tx_buffer);
+    connect(stack, &mut socket).await?;
+
+    // === Prepare HTTP Request ===
+    // Build the API path: /device-config/get/<DEVICE_ID>
+    let mut path = String::<64>::new();
+    let _ = core::fmt::write(&mut path, format_args!("/device-config/get/{}", DEVICE_ID));
+
+    // Log the full URL being requested for debugging
+    info!(
+        "Fetching config from http://{}:{}{}",
+        CONFIG_URL_HOST, CONFIG_URL_PORT, path
+    );
+
+    // Prepare HTTP GET request with proper headers
+    // Using heapless String with fixed capacity for no-alloc environment
+    let mut request = String::<448>::new();
+    let _ = core::fmt::write(
+        &mut request,
+        format_args!(
+            "GET {} HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Connection: close\r\n\
+             User-Agent: RustEmbedded/1.0\r\n\
+             {}: {}\r\n",
+            path,            // API endpoint path
+            CONFIG_URL_HOST, // Host header value
+            SESSION_HEADER,  // Boot session header name
+            session_id()     // Boot session header value
+        ),
+    );
+    // Authenticate with the device key once the service requires credentials
+    if !DEVICE_KEY.is_empty() {
+        let _ = core::fmt::write(&mut request, format_args!("X-API-Key: {}\r\n", DEVICE_KEY));
+    }
+    // Only ask for changes if we already hold a configuration
+    if !etag.is_empty() {
+        let _ = core::fmt::write(&mut request, format_args!("If-None-Match: \"{}\"\r\n", etag));
+    }
+    let _ = request.push_str("\r\n");
+
+    // === Send HTTP Request ===
+    // Write the request to the socket
+    socket
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|_| "Write failed")?;
+
+    // === Read HTTP Response ===
+    // Create a buffer for the whole response (2KB)
+    let mut buf = [0; MAX_RESPONSE_LEN];
+    let mut n = 0;
 
+    // A response arrives over several segments, so keep reading until the
+    // server closes the connection or the announced body has arrived
+    loop {
+        if n == buf.len() {
+            return Err("Response too large");
+        }
+        let read = socket.read(&mut buf[n..]).await.map_err(|_| "Read failed")?;
+        if read == 0 {
+            break;
+        }
+        n += read;
+        if is_complete(&buf[..n]) {
+            break;
+        }
+    }
+    
+    // Convert the bytes to a UTF-8 string
+    let response = core::str::from_utf8(&buf[..n]).map_err(|_| "Invalid UTF-8")?;
+
+    // === Parse Response ===
+    let (device_config, new_etag) = match parse_config_response(response, DEVICE_ID)? {
+        ConfigResponse::NotModified => {
+            info!("Config unchanged");
+            return Ok(false);
+        }
+        ConfigResponse::Updated { config, etag } => (config, etag),
+    };
+
+    // === Store Configuration ===
+    // Save a changed configuration to flash so it survives a restart; the
+    // first fetch after boot usually returns the restored one, which is not
+    // rewritten to spare the flash
+    if get_device_config().await.as_ref() != Some(&device_config) {
+        if let Err(e) = save_device_config(&device_config).await {
+            warn!("Saving config to flash failed: {}", e);
+        }
+    }
+
+    // Update the local configuration store with the new config
+    set_device_config(device_config).await;
+
+    // Remember the tag of the applied configuration for the next request
+    *etag = new_etag;
+    
+    // Return success
+    Ok(true)
+}
+
+/// Resolves the configuration server and connects a socket to it.
+///
+/// # Parameters
+/// * `stack` - Network stack for communication
+/// * `socket` - The socket to connect
+///
+/// # Returns
+/// * `Ok(())` - If the socket is connected
+/// * `Err(&'static str)` - If resolution or connection fails, with an error message
+async fn connect(
+    stack: &Stack<'_>,
+    socket: &mut embassy_net::tcp::TcpSocket<'_>,
+) -> Result<(), &'static str> {
     // === DNS Resolution ===
     // Create a DNS socket to resolve the hostname to an IP address
     let dns_socket = embassy_net::dns::DnsSocket::new(*stack);
@@ -98,72 +244,87 @@ async fn fetch_and_update_config(stack: &Stack<'_>) -> Result<(), &'static str>
         .await
         .map_err(|_| "Connection failed")?;
 
+    Ok(())
+}
+
+/// Reports an applied configuration to the cloud server.
+///
+/// Sends the configuration values and version number to
+/// `/device-config/reported/<DEVICE_ID>`, authenticated with the device
+/// key if there is one.
+///
+/// # Parameters
+/// * `stack` - Network stack for communication
+/// * `config` - The configuration the device applied
+///
+/// # Returns
+/// * `Ok(())` - If the server accepted the report
+/// * `Err(&'static str)` - If any step fails or the server rejected the report
+async fn report_applied_config(stack: &Stack<'_>, config: &DeviceConfigItem) -> Result<(), &'static str> {
+    // Create buffers for TCP socket (1KB each)
+    let mut rx_buffer = [0; 1024];
+    let mut tx_buffer = [0; 1024];
+
+    // Create a new TCP socket using the network stack
+    let mut socket = embassy_net::tcp::TcpSocket::new(*stack, &mut rx_buffer, &mut tx_buffer);
+    connect(stack, &mut socket).await?;
+
+    // === Prepare Report Body ===
+    let report = ConfigReport {
+        config: &config.config,
+        version_number: config.version_number,
+    };
+    let mut body_buf = [0u8; 128];
+    let len = serde_json_core::to_slice(&report, &mut body_buf).map_err(|_| "Report too large")?;
+    let body = core::str::from_utf8(&body_buf[..len]).map_err(|_| "Invalid UTF-8")?;
+
     // === Prepare HTTP Request ===
-    // Build the API path: /device-config/get/<DEVICE_ID>
+    // Build the API path: /device-config/reported/<DEVICE_ID>
     let mut path = String::<64>::new();
-    let _ = core::fmt::write(&mut path, format_args!("/device-config/get/{}", DEVICE_ID));
+    let _ = core::fmt::write(&mut path, format_args!("/device-config/reported/{}", DEVICE_ID));
 
-    // Log the full URL being requested for debugging
-    info!(
-        "Fetching config from http://{}:{}{}",
-        CONFIG_URL_HOST, CONFIG_URL_PORT, path
-    );
-
-    // Prepare HTTP GET request with proper headers
-    // Using heapless String with fixed capacity for no-alloc environment
-    let mut request = String::<256>::new();
+    let mut request = String::<512>::new();
     let _ = core::fmt::write(
         &mut request,
         format_args!(
-            "GET {} HTTP/1.1\r\n\
+            "POST {} HTTP/1.1\r\n\
              Host: {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
              Connection: close\r\n\
              User-Agent: RustEmbedded/1.0\r\n\
-             \r\n",
-            path,           // API endpoint path
-            CONFIG_URL_HOST // Host header value
+             {}: {}\r\n",
+            path,            // API endpoint path
+            CONFIG_URL_HOST, // Host header value
+            body.len(),      // Content length
+            SESSION_HEADER,  // Boot session header name
+            session_id()     // Boot session header value
         ),
     );
+    // Authenticate with the device key once the service requires credentials
+    if !DEVICE_KEY.is_empty() {
+        let _ = core::fmt::write(&mut request, format_args!("X-API-Key: {}\r\n", DEVICE_KEY));
+    }
+    let _ = request.push_str("\r\n");
+    request.push_str(body).map_err(|_| "Report request too large")?;
 
     // === Send HTTP Request ===
-    // Write the request to the socket
     socket
         .write_all(request.as_bytes())
         .await
         .map_err(|_| "Write failed")?;
 
     // === Read HTTP Response ===
-    // Create a buffer for the response (1KB)
-    let mut buf = [0; 1024];
-    
-    // Read the response from the socket
+    // Only the status line is needed
+    let mut buf = [0; 64];
     let n = socket.read(&mut buf).await.map_err(|_| "Read failed")?;
-    
-    // Convert the bytes to a UTF-8 string
-    let response = core::str::from_utf8(&buf[..n]).map_err(|_| "Invalid UTF-8")?;
+    let response = core::str::from_utf8(&buf[..n]).unwrap_or("");
 
-    // === Parse Response ===
-    // Find start of JSON data (skip HTTP headers)
-    // The API returns a JSON array that starts with '[' character
-    let json_start = response.find('[').ok_or("No JSON array in response")?;
-    let json_str = &response[json_start..];
-
-    // Parse the JSON data into our DeviceConfigResponse type
-    // Using no_std-compatible serde_json_core parser
-    let (parsed, _): (DeviceConfigResponse, _) =
-        from_str(json_str).map_err(|_| "JSON parse error")?;
-
-    // Find the configuration specific to this device
-    // The API returns configs for multiple devices, so we filter by device_id
-    let device_config = parsed
-        .into_iter()
-        .find(|item| item.device_id.as_str() == DEVICE_ID)
-        .ok_or("Device config not found")?;
+    socket.close();
 
-    // === Store Configuration ===
-    // Update the local configuration store with the new config
-    set_device_config(device_config).await;
-    
-    // Return success
-    Ok(())
+    if response.starts_with("HTTP/1.1 2") || response.starts_with("HTTP/1.0 2") {
+        Ok(())
+    } else {
+        Err("Server rejected the report")
+    }
 }
@@ -12,6 +12,7 @@ use embedded_io_async::Write;
 use crate::config::TelemetryConfig;
 use crate::drivers::TemperatureSensor;
 use crate::error::TelemetryError;
+use crate::utils::session::{session_id, SESSION_HEADER};
 use heapless::String;
 
 /// Configuration for the telemetry task.
@@ -109,9 +110,9 @@ async fn send_telemetry(
     let _ = core::fmt::write(
         &mut telemetry_data,
         format_args!(
-            // JSON structure with device ID, temperature, voltage, and status
-            "{{\"device_id\":\"1\",\"telemetry_data\":{{\"temperature\":\"{:.1}\",\"voltage\":\"{:.2}\",\"status\":\"active\"}}}}",
-            temperature, voltage
+            // JSON structure with device ID, boot session ID, temperature, voltage, and status
+            "{{\"device_id\":\"1\",\"session_id\":\"{}\",\"telemetry_data\":{{\"temperature\":\"{:.1}\",\"voltage\":\"{:.2}\",\"status\":\"active\"}}}}",
+            session_id(), temperature, voltage
         ),
     );
 
@@ -129,11 +130,14 @@ async fn send_telemetry(
              Content-Length: {}\r\n\
              Connection: close\r\n\
              User-Agent: RustEmbedded/1.0\r\n\
+             {}: {}\r\n\
              \r\n\
              {}",
             TelemetryConfig::PATH,     // API endpoint path
             TelemetryConfig::HOST,     // Host header value
             telemetry_data.len(),      // Content length
+            SESSION_HEADER,            // Boot session header name
+            session_id(),              // Boot session header value
             telemetry_data             // Request body (JSON)
         ),
     );
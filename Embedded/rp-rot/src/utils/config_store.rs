@@ -2,8 +2,16 @@
 ///
 /// This module provides a thread-safe storage mechanism for device configuration.
 /// It uses a mutex-protected global store that can be accessed by different tasks.
+/// The last fetched configuration is also saved to the last sector of flash as a
+/// versioned record, and restored at boot, so it survives restarts and firmware
+/// updates.
 
 use crate::config::device::{DeviceConfigItem, MAX_DEVICE_ID_LEN};
+use crate::error::RecordError;
+use crate::utils::flash_record::{self, Migration, RecordKind, HEADER_LEN};
+use defmt::*;
+use embassy_rp::flash::{Blocking, Flash, ERASE_SIZE};
+use embassy_rp::peripherals::FLASH;
 use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
 use embassy_sync::mutex::Mutex;
 use heapless::String;
@@ -24,6 +32,21 @@ pub static mut DEVICE_CONFIG_REF: Option<
     &'static Mutex<ThreadModeRawMutex, Option<DeviceConfigItem>>,
 > = None;
 
+/// Size of the Pico's flash chip.
+pub const FLASH_SIZE: usize = 2 * 1024 * 1024;
+
+/// Offset of the saved configuration record from the start of flash.
+///
+/// The record lives in the last 4 KB sector, which `memory.x` keeps out of
+/// the firmware image.
+pub const CONFIG_FLASH_OFFSET: u32 = (FLASH_SIZE - ERASE_SIZE) as u32;
+
+/// Flash driver the configuration is saved with.
+pub type ConfigFlash = Flash<'static, FLASH, Blocking, FLASH_SIZE>;
+
+/// The flash driver, handed over by `load_saved_config`.
+static CONFIG_FLASH: Mutex<ThreadModeRawMutex, Option<ConfigFlash>> = Mutex::new(None);
+
 /// Thread mode raw mutex context for the configuration store.
 ///
 /// This mutex ensures that only one task at a time can access the configuration.
@@ -95,3 +118,147 @@ pub async fn get_device_config() -> Option<DeviceConfigItem> {
     
     // Lock is automatically released when guard goes out of scope
 }
+
+/// Layout version of the configuration record payload.
+///
+/// Bump this whenever `DeviceConfigItem` changes in a way older firmware
+/// cannot read, and add a step to `CONFIG_MIGRATIONS` upgrading from the
+/// previous version.
+pub const CONFIG_RECORD_VERSION: u8 = 1;
+
+/// Maximum size of an encoded configuration record, header included.
+pub const CONFIG_RECORD_LEN: usize = 256;
+
+/// Migration steps for stored configuration records, oldest first.
+pub const CONFIG_MIGRATIONS: &[Migration] = &[];
+
+/// Serializes a configuration into a versioned flash record.
+///
+/// # Parameters
+/// * `config` - The configuration to store
+/// * `out` - Buffer receiving the record
+///
+/// # Returns
+/// * `Ok(usize)` - Number of bytes to write to flash
+/// * `Err(RecordError::BufferTooSmall)` - If the configuration does not fit
+pub fn encode_config_record(
+    config: &DeviceConfigItem,
+    out: &mut [u8; CONFIG_RECORD_LEN],
+) -> Result<usize, RecordError> {
+    let mut payload = [0u8; CONFIG_RECORD_LEN - HEADER_LEN];
+    let len = serde_json_core::to_slice(config, &mut payload)
+        .map_err(|_| RecordError::BufferTooSmall)?;
+
+    flash_record::encode_record(
+        RecordKind::ConfigStore,
+        CONFIG_RECORD_VERSION,
+        &payload[..len],
+        out,
+    )
+}
+
+/// Reads a configuration from a flash record, upgrading older layouts.
+///
+/// When the record was written by an older firmware, `migrated` is true
+/// and the caller should re-encode the returned configuration and write
+/// it back so the upgrade only happens once.
+///
+/// # Parameters
+/// * `bytes` - Raw bytes read from the configuration flash region
+///
+/// # Returns
+/// * `Ok((DeviceConfigItem, bool))` - The configuration and whether it was migrated
+/// * `Err(RecordError)` - If no valid record exists; callers fall back to fetching from the cloud
+pub fn decode_config_record(bytes: &[u8]) -> Result<(DeviceConfigItem, bool), RecordError> {
+    let mut scratch = [0u8; CONFIG_RECORD_LEN - HEADER_LEN];
+    let loaded = flash_record::load_record(
+        bytes,
+        RecordKind::ConfigStore,
+        CONFIG_RECORD_VERSION,
+        CONFIG_MIGRATIONS,
+        &mut scratch,
+    )?;
+
+    let (config, _) = serde_json_core::from_slice::<DeviceConfigItem>(&scratch[..loaded.len])
+        .map_err(|_| RecordError::Corrupt)?;
+
+    Ok((config, loaded.migrated))
+}
+
+/// Restores the configuration saved before the last restart.
+///
+/// Reads the configuration record from flash into the store, so the device
+/// applies its last configuration before the first fetch completes. A
+/// record written by an older firmware is upgraded and written back; a
+/// blank or invalid record leaves the store empty. The flash driver is kept
+/// for `save_device_config`.
+///
+/// # Parameters
+/// * `flash` - The flash driver
+///
+/// # Panics
+/// Panics if the configuration store hasn't been initialized
+pub async fn load_saved_config(mut flash: ConfigFlash) {
+    let mut bytes = [0u8; CONFIG_RECORD_LEN];
+    match flash.blocking_read(CONFIG_FLASH_OFFSET, &mut bytes) {
+        Ok(()) => match decode_config_record(&bytes) {
+            Ok((config, migrated)) => {
+                info!("Restored saved configuration");
+                // Write the upgraded layout back so the migration only runs once
+                if migrated {
+                    if let Err(e) = write_config_record(&mut flash, &config) {
+                        warn!("Saving migrated configuration failed: {}", e);
+                    }
+                }
+                set_device_config(config).await;
+            }
+            Err(RecordError::Blank) => info!("No saved configuration"),
+            Err(e) => warn!("Ignoring saved configuration: {}", e),
+        },
+        Err(e) => warn!("Reading saved configuration failed: {}", e),
+    }
+
+    *CONFIG_FLASH.lock().await = Some(flash);
+}
+
+/// Saves a configuration to flash so it is restored after a restart.
+///
+/// Every save erases a flash sector, which wears out after about 100,000
+/// erases, so only configurations that differ from the current one should
+/// be saved.
+///
+/// # Parameters
+/// * `config` - The configuration to save
+///
+/// # Returns
+/// * `Ok(())` - If the configuration was saved, or there is no flash driver yet
+/// * `Err(RecordError)` - If it does not fit a record or the flash could not be written
+pub async fn save_device_config(config: &DeviceConfigItem) -> Result<(), RecordError> {
+    let mut guard = CONFIG_FLASH.lock().await;
+    match guard.as_mut() {
+        Some(flash) => write_config_record(flash, config),
+        None => Ok(()),
+    }
+}
+
+/// Erases the configuration sector and writes a configuration record to it.
+///
+/// # Parameters
+/// * `flash` - The flash driver
+/// * `config` - The configuration to write
+///
+/// # Returns
+/// * `Ok(())` - If the record was written
+/// * `Err(RecordError)` - If it does not fit a record or the flash could not be written
+fn write_config_record(flash: &mut ConfigFlash, config: &DeviceConfigItem) -> Result<(), RecordError> {
+    // Bytes after the record are left erased
+    let mut record = [0xFF; CONFIG_RECORD_LEN];
+    encode_config_record(config, &mut record)?;
+
+    flash
+        .blocking_erase(CONFIG_FLASH_OFFSET, CONFIG_FLASH_OFFSET + ERASE_SIZE as u32)
+        .map_err(|_| RecordError::Flash)?;
+    flash
+        .blocking_write(CONFIG_FLASH_OFFSET, &record)
+        .map_err(|_| RecordError::Flash)
+}
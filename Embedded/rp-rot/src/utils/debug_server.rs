@@ -5,6 +5,7 @@ use embedded_io_async::Write;
 use heapless::String;
 
 use crate::config::TelemetryConfig;
+use crate::utils::session::{session_id, SESSION_HEADER};
 
 const LOCAL_DEBUG_PORT: u16 = 8000;
 
@@ -81,10 +82,13 @@ async fn send_to_local_debug_server(
              Content-Length: {}\r\n\
              Connection: close\r\n\
              User-Agent: RustEmbedded/1.0\r\n\
+             {}: {}\r\n\
              \r\n\
              {}",
             debug_server,
             log_data.len(),
+            SESSION_HEADER,
+            session_id(),
             log_data
         ),
     );
@@ -183,11 +187,14 @@ async fn send_to_azure(stack: &Stack<'_>, log_data: &str) -> Result<(), &'static
              Content-Length: {}\r\n\
              Connection: close\r\n\
              User-Agent: RustEmbedded/1.0\r\n\
+             {}: {}\r\n\
              \r\n\
              {}",
             TelemetryConfig::PATH,
             TelemetryConfig::HOST,
             log_data.len(),
+            SESSION_HEADER,
+            session_id(),
             log_data
         ),
     );
@@ -0,0 +1,224 @@
+/// # Versioned Flash Records
+///
+/// This module defines the on-flash layout shared by every record the
+/// firmware persists (currently the config store) and the migration
+/// framework used to upgrade stored layouts in place. It has no Embassy
+/// dependencies and only derives defmt formatting on the device, so it is
+/// also tested on the host (see `fuzz/tests/flash_record.rs`).
+///
+/// Every record starts with a 12-byte header:
+///
+/// | Offset | Size | Field                                  |
+/// |--------|------|----------------------------------------|
+/// | 0      | 4    | Magic `ROTR`                           |
+/// | 4      | 1    | Record kind (`RecordKind`)             |
+/// | 5      | 1    | Layout version of the payload          |
+/// | 6      | 2    | Payload length (little endian)         |
+/// | 8      | 4    | CRC-32 of the payload (little endian)  |
+///
+/// When a newer firmware reads a record written by an older one, the
+/// payload is upgraded one version at a time by the registered
+/// `Migration` steps and should then be written back with the current
+/// version, so device state survives firmware updates.
+
+use crate::error::RecordError;
+
+/// Magic bytes identifying a record header
+pub const RECORD_MAGIC: [u8; 4] = *b"ROTR";
+
+/// Size of the record header in bytes
+pub const HEADER_LEN: usize = 12;
+
+/// Value of erased flash
+const ERASED: u8 = 0xFF;
+
+/// Kind of data held by a record.
+///
+/// Values are stored on flash and must never be reused or renumbered;
+/// 2 to 4 are reserved for the secure store, event log and OTA state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(target_os = "none", derive(defmt::Format))]
+#[repr(u8)]
+pub enum RecordKind {
+    /// Last applied device configuration
+    ConfigStore = 1,
+}
+
+/// Decoded record header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(target_os = "none", derive(defmt::Format))]
+pub struct RecordHeader {
+    /// Raw record kind
+    pub kind: u8,
+    /// Layout version of the payload
+    pub version: u8,
+    /// Payload length in bytes
+    pub len: u16,
+    /// CRC-32 of the payload
+    pub crc: u32,
+}
+
+/// A step upgrading one kind of record from `from` to `from + 1`.
+///
+/// The payload is converted in place: `migrate` receives the buffer and
+/// the current payload length and returns the new payload length. The
+/// buffer is the full scratch buffer passed to `load_record`, so steps
+/// may grow the payload up to its capacity.
+pub struct Migration {
+    /// Kind of record this step applies to
+    pub kind: RecordKind,
+    /// Version this step upgrades from
+    pub from: u8,
+    /// Conversion function
+    pub migrate: fn(&mut [u8], usize) -> Result<usize, RecordError>,
+}
+
+/// Result of loading a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(target_os = "none", derive(defmt::Format))]
+pub struct LoadedRecord {
+    /// Length of the (possibly migrated) payload in the scratch buffer
+    pub len: usize,
+    /// True if the stored record was upgraded and should be written back
+    pub migrated: bool,
+}
+
+/// Computes the CRC-32 (IEEE) of a byte slice.
+///
+/// A bitwise implementation is used to avoid a 1 KB lookup table;
+/// records are small and rarely read.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// Writes a record (header and payload) into a buffer.
+///
+/// # Parameters
+/// * `kind` - Kind of data in the payload
+/// * `version` - Layout version of the payload
+/// * `payload` - The record payload
+/// * `out` - Buffer to write the record to
+///
+/// # Returns
+/// * `Ok(usize)` - Number of bytes written
+/// * `Err(RecordError::BufferTooSmall)` - If the record does not fit
+pub fn encode_record(
+    kind: RecordKind,
+    version: u8,
+    payload: &[u8],
+    out: &mut [u8],
+) -> Result<usize, RecordError> {
+    let total = HEADER_LEN + payload.len();
+    if payload.len() > u16::MAX as usize || out.len() < total {
+        return Err(RecordError::BufferTooSmall);
+    }
+
+    out[0..4].copy_from_slice(&RECORD_MAGIC);
+    out[4] = kind as u8;
+    out[5] = version;
+    out[6..8].copy_from_slice(&(payload.len() as u16).to_le_bytes());
+    out[8..12].copy_from_slice(&crc32(payload).to_le_bytes());
+    out[HEADER_LEN..total].copy_from_slice(payload);
+
+    Ok(total)
+}
+
+/// Reads and validates a record header and payload.
+///
+/// # Parameters
+/// * `bytes` - Raw bytes read from flash, starting at the record
+///
+/// # Returns
+/// * `Ok((RecordHeader, &[u8]))` - The header and its verified payload
+/// * `Err(RecordError)` - If the region is blank, malformed or corrupt
+pub fn decode_record(bytes: &[u8]) -> Result<(RecordHeader, &[u8]), RecordError> {
+    if bytes.len() < HEADER_LEN {
+        return Err(RecordError::Truncated);
+    }
+    if bytes[..HEADER_LEN].iter().all(|b| *b == ERASED) {
+        return Err(RecordError::Blank);
+    }
+    if bytes[0..4] != RECORD_MAGIC {
+        return Err(RecordError::BadMagic);
+    }
+
+    let header = RecordHeader {
+        kind: bytes[4],
+        version: bytes[5],
+        len: u16::from_le_bytes([bytes[6], bytes[7]]),
+        crc: u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+    };
+
+    let payload = bytes
+        .get(HEADER_LEN..HEADER_LEN + header.len as usize)
+        .ok_or(RecordError::Truncated)?;
+    if crc32(payload) != header.crc {
+        return Err(RecordError::Corrupt);
+    }
+
+    Ok((header, payload))
+}
+
+/// Loads a record and upgrades it to the current layout version.
+///
+/// The payload is copied into `scratch` and every migration step from
+/// the stored version up to `current_version` is applied in order. A
+/// stored version newer than `current_version` (a firmware downgrade) or
+/// a gap in the migration chain yields `UnsupportedVersion`, so callers
+/// can fall back to defaults instead of misreading the data.
+///
+/// # Parameters
+/// * `bytes` - Raw bytes read from flash, starting at the record
+/// * `kind` - Kind of record expected
+/// * `current_version` - Layout version this firmware understands
+/// * `migrations` - Migration steps for this kind of record
+/// * `scratch` - Buffer receiving the (migrated) payload
+///
+/// # Returns
+/// * `Ok(LoadedRecord)` - Payload length and whether it was migrated
+/// * `Err(RecordError)` - If the record cannot be read or upgraded
+pub fn load_record(
+    bytes: &[u8],
+    kind: RecordKind,
+    current_version: u8,
+    migrations: &[Migration],
+    scratch: &mut [u8],
+) -> Result<LoadedRecord, RecordError> {
+    let (header, payload) = decode_record(bytes)?;
+    if header.kind != kind as u8 {
+        return Err(RecordError::WrongKind(header.kind));
+    }
+    if header.version > current_version {
+        return Err(RecordError::UnsupportedVersion(header.version));
+    }
+
+    let mut len = payload.len();
+    scratch
+        .get_mut(..len)
+        .ok_or(RecordError::BufferTooSmall)?
+        .copy_from_slice(payload);
+
+    let mut version = header.version;
+    while version < current_version {
+        let step = migrations
+            .iter()
+            .find(|step| step.kind == kind && step.from == version)
+            .ok_or(RecordError::UnsupportedVersion(version))?;
+
+        len = (step.migrate)(scratch, len).map_err(|_| RecordError::MigrationFailed(version))?;
+        version += 1;
+    }
+
+    Ok(LoadedRecord {
+        len,
+        migrated: header.version != current_version,
+    })
+}
@@ -0,0 +1,136 @@
+/// # Configuration Response Parsing
+///
+/// This module parses the raw HTTP response returned by the configuration
+/// server. It is kept free of networking and logging so the same code can
+/// be built on the host and fuzzed (see `fuzz/`), since the response comes
+/// straight off the network.
+
+use heapless::String;
+use serde_json_core::de::from_str;
+
+use crate::config::device::{DeviceConfigItem, DeviceConfigResponse};
+
+/// Maximum length of the configuration entity tag (16 hex characters)
+pub const MAX_ETAG_LEN: usize = 16;
+
+/// Size of the buffer a response is read into, headers included
+///
+/// A configuration with its signature and firmware manifest takes about
+/// 1 KB, so this leaves room for longer URLs and extra headers.
+pub const MAX_RESPONSE_LEN: usize = 2048;
+
+/// Outcome of a configuration fetch
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConfigResponse {
+    /// The server answered 304; the stored configuration is still current
+    NotModified,
+    /// A configuration for this device, with its entity tag if one was sent
+    Updated {
+        config: DeviceConfigItem,
+        etag: String<MAX_ETAG_LEN>,
+    },
+}
+
+/// Parses a configuration server response for this device.
+///
+/// # Parameters
+/// * `response` - The raw HTTP response, headers first
+/// * `device_id` - Identifier of this device
+///
+/// # Returns
+/// * `Ok(ConfigResponse)` - The parsed outcome
+/// * `Err(&'static str)` - If the response has no usable configuration for the device
+pub fn parse_config_response(response: &str, device_id: &str) -> Result<ConfigResponse, &'static str> {
+    // === Check for Unchanged Configuration ===
+    // A 304 response has no body; the stored configuration is still current
+    if response.starts_with("HTTP/1.1 304") {
+        return Ok(ConfigResponse::NotModified);
+    }
+
+    let mut etag = String::new();
+    if let Some(value) = parse_etag(response) {
+        let _ = etag.push_str(value);
+    }
+
+    // === Parse Response ===
+    // Find start of JSON data (skip HTTP headers)
+    // The API returns a JSON array that starts with '[' character
+    let json_start = response.find('[').ok_or("No JSON array in response")?;
+    let json_str = &response[json_start..];
+
+    // Parse the JSON data into our DeviceConfigResponse type
+    // Using no_std-compatible serde_json_core parser
+    let (parsed, _): (DeviceConfigResponse, _) =
+        from_str(json_str).map_err(|_| "JSON parse error")?;
+
+    // Find the configuration specific to this device
+    // The API returns configs for multiple devices, so we filter by device_id
+    let config = parsed
+        .into_iter()
+        .find(|item| item.device_id.as_str() == device_id)
+        .ok_or("Device config not found")?;
+
+    Ok(ConfigResponse::Updated { config, etag })
+}
+
+/// Checks whether a response read so far is complete.
+///
+/// A response is complete once its headers have ended and as many body
+/// bytes as its `Content-Length` header announces have followed; a `304`
+/// has no body. Without a `Content-Length`, the response only ends when
+/// the server closes the connection.
+///
+/// # Parameters
+/// * `response` - The bytes received so far
+///
+/// # Returns
+/// * `true` - If the whole response has been received
+/// * `false` - If more bytes should be read
+pub fn is_complete(response: &[u8]) -> bool {
+    let Some(header_end) = response.windows(4).position(|window| window == b"\r\n\r\n") else {
+        return false;
+    };
+    let Ok(headers) = core::str::from_utf8(&response[..header_end]) else {
+        return false;
+    };
+    if headers.starts_with("HTTP/1.1 304") {
+        return true;
+    }
+
+    let body_len = response.len() - (header_end + 4);
+    header(headers, "content-length")
+        .and_then(|length| length.parse::<usize>().ok())
+        .is_some_and(|length| body_len >= length)
+}
+
+/// Extracts the unquoted `ETag` header value from an HTTP response.
+///
+/// # Parameters
+/// * `response` - The raw HTTP response, headers first
+///
+/// # Returns
+/// * `Some(&str)` - The entity tag, if present and short enough to store
+/// * `None` - If the response has no usable ETag header
+fn parse_etag(response: &str) -> Option<&str> {
+    let headers = response.split("\r\n\r\n").next()?;
+    header(headers, "etag")
+        .map(|value| value.trim_matches('"'))
+        .filter(|value| !value.is_empty() && value.len() <= MAX_ETAG_LEN)
+}
+
+/// Finds a header's value in the header section of an HTTP response.
+///
+/// # Parameters
+/// * `headers` - The status line and headers, without the blank line ending them
+/// * `name` - The header name, in any case
+///
+/// # Returns
+/// * `Some(&str)` - The trimmed value of the first header with that name
+/// * `None` - If there is no such header
+fn header<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+    headers
+        .split("\r\n")
+        .filter_map(|line| line.split_once(':'))
+        .find(|(header, _)| header.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
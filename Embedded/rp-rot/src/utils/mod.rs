@@ -1,2 +1,5 @@
 pub mod config_store;
 pub mod debug_server;
+pub mod session;
+pub mod flash_record;
+pub mod http_response;
\ No newline at end of file
@@ -0,0 +1,42 @@
+/// # Boot Session Identifier
+///
+/// This module holds a random identifier that is generated once per boot.
+/// It is sent with every network request (telemetry, config fetches and
+/// debug logs) so the cloud can group all traffic from a single boot
+/// session, which makes reboot loops easy to spot.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+use heapless::String;
+
+/// Length of the hex-encoded session id
+pub const SESSION_ID_LEN: usize = 8;
+
+/// HTTP header used to carry the session id on every request
+pub const SESSION_HEADER: &str = "X-Session-Id";
+
+/// Random session id for the current boot.
+///
+/// A plain 32-bit atomic is used because the RP2040 (Cortex-M0+) has no
+/// 64-bit atomics; 32 random bits are plenty to tell one device's boots apart.
+static SESSION_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Initializes the session id for this boot.
+///
+/// This function should be called once at startup, before any task that
+/// talks to the network is spawned.
+///
+/// # Parameters
+/// * `random` - A random value, e.g. from the ring oscillator RNG
+pub fn init_session_id(random: u32) {
+    SESSION_ID.store(random, Ordering::Relaxed);
+}
+
+/// Returns the session id for this boot as a lowercase hex string.
+///
+/// # Returns
+/// * `String<SESSION_ID_LEN>` - The zero-padded 8 character session id
+pub fn session_id() -> String<SESSION_ID_LEN> {
+    let mut id = String::<SESSION_ID_LEN>::new();
+    let _ = core::fmt::write(&mut id, format_args!("{:08x}", SESSION_ID.load(Ordering::Relaxed)));
+    id
+}